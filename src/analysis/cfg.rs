@@ -2,8 +2,24 @@
 
 use crate::memory::{Address, MemoryReader, MemoryError};
 use crate::analysis::disassembler::{Disassembler, DisassembledInstruction};
+use crate::analysis::disasm::{DecodedInstruction, DisassemblyContext, InstructionCategory};
+use crate::utils::arm64::Arm64Utils;
+use std::ops::Range;
 use std::sync::Arc;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Most entries read from one jump table; also the count when no bounds
+/// check on the index is found
+const MAX_JUMP_TABLE_ENTRIES: usize = 256;
+
+/// Instructions searched back from a `BR` for the table-load idiom
+const JUMP_TABLE_WINDOW: u64 = 16;
+
+const LDR_X_REG: u32 = 0xF8600800;
+const LDRSW_REG: u32 = 0xB8A00800;
+const LDRH_REG: u32 = 0x78600800;
+const LDRB_REG: u32 = 0x38600800;
+const LOAD_REG_MASK: u32 = 0xFFE00C00;
 
 #[derive(Debug, Clone)]
 pub struct ControlFlowGraph {
@@ -11,6 +27,7 @@ pub struct ControlFlowGraph {
     blocks: HashMap<u64, CfgBlock>,
     edges: Vec<CfgEdge>,
     exit_blocks: Vec<u64>,
+    jump_tables: Vec<JumpTable>,
 }
 
 impl ControlFlowGraph {
@@ -20,11 +37,13 @@ impl ControlFlowGraph {
             blocks: HashMap::new(),
             edges: Vec::new(),
             exit_blocks: Vec::new(),
+            jump_tables: Vec::new(),
         }
     }
 
     pub fn build(reader: Arc<dyn MemoryReader>, entry: Address, max_size: usize) -> Result<Self, MemoryError> {
-        let disasm = Disassembler::new(reader);
+        let function = entry..entry + max_size as u64 + 4;
+        let disasm = Disassembler::new(reader.clone());
         let mut cfg = Self::new(entry);
         let mut visited = HashSet::new();
         let mut queue = VecDeque::new();
@@ -46,6 +65,7 @@ impl ControlFlowGraph {
             let mut instructions = Vec::new();
             let mut current = block_start;
             let mut successors = Vec::new();
+            let mut table_targets = Vec::new();
             let mut is_exit = false;
 
             loop {
@@ -67,6 +87,16 @@ impl ControlFlowGraph {
                     if let Some(target) = disasm.get_branch_target(&instr) {
                         successors.push(target);
                         queue.push_back(target);
+                    } else if instr.mnemonic == "BR" {
+                        if let Some(table) = JumpTable::recover(reader.as_ref(), current, function.clone()) {
+                            for &target in &table.targets {
+                                if !table_targets.contains(&target) {
+                                    table_targets.push(target);
+                                    queue.push_back(target);
+                                }
+                            }
+                            cfg.jump_tables.push(table);
+                        }
                     }
 
                     if instr.mnemonic != "B" && instr.mnemonic != "BR" {
@@ -110,7 +140,7 @@ impl ControlFlowGraph {
                 end: block_end,
                 instructions,
                 predecessors: Vec::new(),
-                successors: successors.clone(),
+                successors: successors.iter().chain(&table_targets).copied().collect(),
             };
 
             cfg.blocks.insert(block_start.as_u64(), block);
@@ -126,12 +156,154 @@ impl ControlFlowGraph {
                     edge_type: EdgeType::Flow,
                 });
             }
+            for succ in table_targets {
+                cfg.edges.push(CfgEdge {
+                    from: block_start,
+                    to: succ,
+                    edge_type: EdgeType::Jump,
+                });
+            }
         }
 
         cfg.compute_predecessors();
         Ok(cfg)
     }
 
+    /// Basic-block graph of the function at `entry`, explored from the
+    /// entry through every reachable path. Blocks start at branch targets,
+    /// after conditional branches and after calls, so no two blocks share
+    /// an instruction. Conditional edges carry which way they go, calls get
+    /// a `Return` edge to the instruction after them, and `B` to an address
+    /// before the entry or past the instruction cap is a tail call that
+    /// ends the function.
+    pub fn from_context(ctx: &mut DisassemblyContext, entry: Address) -> Result<Self, MemoryError> {
+        let max_instructions = ctx.max_function_instructions();
+        let function = entry..entry + (max_instructions as u64) * 4;
+        let reader = ctx.reader();
+
+        let mut cfg = Self::new(entry);
+        let mut decoded: BTreeMap<u64, DecodedInstruction> = BTreeMap::new();
+        let mut leaders: BTreeSet<u64> = BTreeSet::from([entry.as_u64()]);
+        let mut terminators: HashMap<u64, Vec<(Address, EdgeType)>> = HashMap::new();
+        let mut queue = VecDeque::from([entry]);
+
+        while let Some(start) = queue.pop_front() {
+            let mut current = start;
+
+            while function.contains(&current) && !decoded.contains_key(&current.as_u64()) {
+                if decoded.len() >= max_instructions {
+                    break;
+                }
+
+                let instr = match ctx.disassemble_at(current) {
+                    Ok(instr) => instr,
+                    Err(e) if current == entry => return Err(e),
+                    Err(_) => break,
+                };
+                let next = current + instr.size as u64;
+                let target = instr.get_branch_target();
+                let mnemonic = instr.mnemonic.clone();
+                let category = instr.category;
+                decoded.insert(current.as_u64(), instr);
+
+                let mut successors = Vec::new();
+                match category {
+                    InstructionCategory::Return => {}
+                    InstructionCategory::ConditionalBranch => {
+                        if let Some(target) = target.filter(|t| function.contains(t)) {
+                            successors.push((target, EdgeType::ConditionalTrue));
+                        }
+                        successors.push((next, EdgeType::ConditionalFalse));
+                    }
+                    InstructionCategory::Branch if mnemonic == "BR" => {
+                        if let Some(table) = JumpTable::recover(reader.as_ref(), current, function.clone()) {
+                            for &target in &table.targets {
+                                if !successors.iter().any(|&(t, _)| t == target) {
+                                    successors.push((target, EdgeType::Jump));
+                                }
+                            }
+                            cfg.jump_tables.push(table);
+                        }
+                    }
+                    InstructionCategory::Branch => {
+                        if let Some(target) = target.filter(|t| function.contains(t)) {
+                            successors.push((target, EdgeType::Jump));
+                        }
+                    }
+                    InstructionCategory::Call => successors.push((next, EdgeType::Return)),
+                    _ => {
+                        current = next;
+                        continue;
+                    }
+                }
+
+                for &(succ, _) in &successors {
+                    leaders.insert(succ.as_u64());
+                    queue.push_back(succ);
+                }
+                terminators.insert(current.as_u64(), successors);
+                break;
+            }
+        }
+
+        let addresses: Vec<u64> = decoded.keys().copied().collect();
+        let mut i = 0;
+        while i < addresses.len() {
+            let start = addresses[i];
+            let mut instructions = Vec::new();
+            let mut successors = Vec::new();
+
+            loop {
+                let addr = addresses[i];
+                let instr = &decoded[&addr];
+                instructions.push(DisassembledInstruction::from(instr));
+                i += 1;
+
+                if let Some(targets) = terminators.get(&addr) {
+                    successors = targets.clone();
+                    break;
+                }
+
+                let next = addr + instr.size as u64;
+                if i == addresses.len() || addresses[i] != next {
+                    break;
+                }
+                if leaders.contains(&next) {
+                    successors.push((Address::new(next), EdgeType::Flow));
+                    break;
+                }
+            }
+
+            // Calls or branches off the end of what was decoded lead nowhere
+            successors.retain(|(to, _)| decoded.contains_key(&to.as_u64()));
+            let end = instructions.last().map(|instr| instr.address).unwrap_or(Address::new(start));
+            let from = Address::new(start);
+
+            if successors.is_empty() {
+                cfg.exit_blocks.push(start);
+            }
+            for &(to, edge_type) in &successors {
+                cfg.edges.push(CfgEdge { from, to, edge_type });
+            }
+
+            cfg.blocks.insert(start, CfgBlock {
+                start: from,
+                end,
+                instructions,
+                predecessors: Vec::new(),
+                successors: successors.into_iter().map(|(to, _)| to).collect(),
+            });
+        }
+
+        cfg.compute_predecessors();
+        Ok(cfg)
+    }
+
+    /// Edges leaving the block at `addr`, in the order they were found
+    pub fn edges_from(&self, addr: Address) -> impl Iterator<Item = &CfgEdge> {
+        self.edges.iter().filter(move |edge| edge.from == addr)
+    }
+
     fn compute_predecessors(&mut self) {
         for edge in &self.edges {
             if let Some(block) = self.blocks.get_mut(&edge.to.as_u64()) {
@@ -172,6 +344,11 @@ impl ControlFlowGraph {
         self.exit_blocks.contains(&addr.as_u64())
     }
 
+    /// Tables recovered behind the function's `BR` instructions
+    pub fn jump_tables(&self) -> &[JumpTable] {
+        &self.jump_tables
+    }
+
     pub fn add_block(&mut self, block: CfgBlock) {
         self.blocks.insert(block.start.as_u64(), block);
     }
@@ -184,6 +361,14 @@ impl ControlFlowGraph {
         });
     }
 
+    pub fn add_typed_edge(&mut self, from_id: u64, to_id: u64, edge_type: EdgeType) {
+        self.edges.push(CfgEdge {
+            from: Address::new(from_id),
+            to: Address::new(to_id),
+            edge_type,
+        });
+    }
+
     pub fn predecessors(&self, addr: Address) -> Vec<Address> {
         if let Some(block) = self.blocks.get(&addr.as_u64()) {
             block.predecessors.clone()
@@ -325,3 +510,192 @@ pub enum EdgeType {
     Return,
     Jump,
 }
+
+impl EdgeType {
+    /// How the edge reads on a rendered graph
+    pub fn label(&self) -> &'static str {
+        match self {
+            EdgeType::ConditionalTrue => "taken",
+            EdgeType::ConditionalFalse | EdgeType::Flow => "fallthrough",
+            EdgeType::Call => "call",
+            EdgeType::Return => "return",
+            EdgeType::Jump => "jump",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JumpTableKind {
+    /// `ldr xT, [xBase, xIdx, lsl #3]`: 8-byte absolute targets
+    Absolute,
+    /// `ldrsw` offsets added to an origin (usually the table itself)
+    Relative,
+    /// `ldrb`/`ldrh` instruction counts added to an ADR label
+    Compact,
+}
+
+/// A switch's table of branch targets, read from the binary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpTable {
+    pub branch: Address,
+    pub table: Address,
+    pub kind: JumpTableKind,
+    pub entry_size: usize,
+    /// In table order, duplicates kept; stops at the first entry landing
+    /// outside the function
+    pub targets: Vec<Address>,
+}
+
+impl JumpTable {
+    /// The table behind the `BR` at `branch`, if the instructions before it
+    /// load the target from a table whose base comes from ADRP/ADD or ADR.
+    /// The entry count comes from a `cmp` on the index when there is one.
+    pub fn recover(reader: &dyn MemoryReader, branch: Address, function: Range<Address>) -> Option<Self> {
+        let br = reader.read_u32(branch).ok()?;
+        if br & 0xFFFFFC1F != 0xD61F0000 {
+            return None;
+        }
+        let window = Window::read(reader, branch, function.start);
+
+        let (at, def) = window.definition(reg_n(br), 0)?;
+        let (load_at, load, origin, shift) = if def & LOAD_REG_MASK == LDR_X_REG {
+            (at, def, None, 0)
+        } else if def & 0xFF200000 == 0x8B000000 {
+            // add xT, xOrigin, xEntry{, lsl #shift}
+            let shift = (def >> 10) & 0x3F;
+            let (load_at, load) = window.definition(reg_m(def), at + 1)?;
+            let origin = window.value(reg_n(def), at + 1)?;
+            (load_at, load, Some(origin), shift)
+        } else {
+            return None;
+        };
+
+        let (kind, entry_size) = match load & LOAD_REG_MASK {
+            LDR_X_REG if origin.is_none() => (JumpTableKind::Absolute, 8),
+            LDRSW_REG => (JumpTableKind::Relative, 4),
+            LDRH_REG => (JumpTableKind::Compact, 2),
+            LDRB_REG => (JumpTableKind::Compact, 1),
+            _ => return None,
+        };
+        if kind != JumpTableKind::Absolute && origin.is_none() {
+            return None;
+        }
+
+        let table = window.value(reg_n(load), load_at + 1)?;
+        let count = window.bound(reg_m(load)).unwrap_or(MAX_JUMP_TABLE_ENTRIES);
+
+        let mut targets = Vec::new();
+        for i in 0..count {
+            let slot = Address::new(table + (i * entry_size) as u64);
+            let target = match (kind, origin) {
+                (JumpTableKind::Absolute, _) => reader.read_u64(slot).ok(),
+                (JumpTableKind::Relative, Some(origin)) => reader.read_u32(slot).ok()
+                    .map(|e| origin.wrapping_add(((e as i32 as i64) << shift) as u64)),
+                (_, Some(origin)) => reader.read_bytes(slot, entry_size).ok()
+                    .map(|b| b.iter().rev().fold(0u64, |acc, &byte| (acc << 8) | byte as u64))
+                    .map(|e| origin.wrapping_add(e << shift)),
+                _ => None,
+            };
+
+            match target.map(Address::new) {
+                Some(target) if function.contains(&target) && target.as_u64() % 4 == 0 => targets.push(target),
+                _ => break,
+            }
+        }
+
+        if targets.is_empty() {
+            return None;
+        }
+        Some(Self { branch, table: Address::new(table), kind, entry_size, targets })
+    }
+}
+
+/// Instructions before a `BR`, nearest first
+struct Window {
+    insns: Vec<(Address, u32)>,
+}
+
+impl Window {
+    fn read(reader: &dyn MemoryReader, branch: Address, floor: Address) -> Self {
+        let insns = (1..=JUMP_TABLE_WINDOW)
+            .map(|i| branch.as_u64().wrapping_sub(i * 4))
+            .take_while(|&addr| addr >= floor.as_u64())
+            .map_while(|addr| reader.read_u32(Address::new(addr)).ok().map(|insn| (Address::new(addr), insn)))
+            .collect();
+        Self { insns }
+    }
+
+    /// Index and encoding of the nearest instruction from `from` on that
+    /// writes `reg`. Conditional branches are stepped over (the bounds
+    /// check sits between the load and the `BR`); any other branch or call
+    /// ends the search.
+    fn definition(&self, reg: u32, from: usize) -> Option<(usize, u32)> {
+        for (i, &(_, insn)) in self.insns.iter().enumerate().skip(from) {
+            if is_conditional(insn) || is_store(insn) || is_compare(insn) {
+                continue;
+            }
+            if insn & 0x7C000000 == 0x14000000 || insn & 0xFE000000 == 0xD6000000 {
+                return None;
+            }
+            if insn & 0x1F == reg {
+                return Some((i, insn));
+            }
+        }
+        None
+    }
+
+    /// Address `reg` holds, from ADR or ADRP + ADD
+    fn value(&self, reg: u32, from: usize) -> Option<u64> {
+        let (i, insn) = self.definition(reg, from)?;
+        let at = self.insns[i].0.as_u64();
+
+        if let Some(target) = Arm64Utils::get_adr_target(insn, at) {
+            return Some(target);
+        }
+        if insn & 0xFF800000 == 0x91000000 && reg_n(insn) == reg {
+            let (j, adrp) = self.definition(reg, i + 1)?;
+            return Arm64Utils::fold_adrp_add(adrp, insn, self.insns[j].0.as_u64());
+        }
+        None
+    }
+
+    /// Entry count from `cmp index, #n` guarding the table with `b.hi`
+    fn bound(&self, index: u32) -> Option<usize> {
+        self.insns.iter()
+            .map(|&(_, insn)| insn)
+            .find(|&insn| is_compare(insn) && reg_n(insn) == index)
+            .map(|insn| {
+                let imm = ((insn >> 10) & 0xFFF) << (12 * ((insn >> 22) & 1));
+                (imm as usize + 1).min(MAX_JUMP_TABLE_ENTRIES)
+            })
+    }
+}
+
+fn reg_n(insn: u32) -> u32 {
+    (insn >> 5) & 0x1F
+}
+
+fn reg_m(insn: u32) -> u32 {
+    (insn >> 16) & 0x1F
+}
+
+/// `cmp` with an immediate: SUBS into the zero register
+fn is_compare(insn: u32) -> bool {
+    insn & 0x7F800000 == 0x71000000 && insn & 0x1F == 31
+}
+
+/// B.cond, CBZ/CBNZ or TBZ/TBNZ
+fn is_conditional(insn: u32) -> bool {
+    insn & 0xFF000010 == 0x54000000 || insn & 0x7C000000 == 0x34000000
+}
+
+/// Load/store group: STR* by opc == 00, pairs by the L bit
+fn is_store(insn: u32) -> bool {
+    if (insn >> 25) & 0x5 != 0x4 {
+        return false;
+    }
+    match (insn >> 27) & 0x7 {
+        0b111 => (insn >> 22) & 0x3 == 0,
+        _ => insn & (1 << 22) == 0,
+    }
+}
@@ -2,8 +2,14 @@
 
 use crate::memory::{Address, MemoryReader, MemoryError, MemoryRegion};
 use crate::analysis::disassembler::{Disassembler, DisassembledInstruction};
+use crate::analysis::string::find_c_strings;
+use crate::xref::chains::enclosing_function;
+use crate::xref::StringXrefScanner;
 use std::sync::Arc;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Range;
+
+const CHUNK: usize = 0x10000;
 
 pub struct CrossReferenceAnalyzer {
     reader: Arc<dyn MemoryReader>,
@@ -11,6 +17,7 @@ pub struct CrossReferenceAnalyzer {
     code_refs: HashMap<u64, Vec<CodeReference>>,
     data_refs: HashMap<u64, Vec<DataReference>>,
     string_refs: HashMap<u64, Vec<StringReference>>,
+    string_section: Option<Range<Address>>,
 }
 
 impl CrossReferenceAnalyzer {
@@ -21,7 +28,97 @@ impl CrossReferenceAnalyzer {
             code_refs: HashMap::new(),
             data_refs: HashMap::new(),
             string_refs: HashMap::new(),
+            string_section: None,
+        }
+    }
+
+    /// Where `xrefs_to_string` looks for strings (`__cstring`); without it
+    /// every readable region is searched
+    pub fn with_string_section(mut self, section: Range<Address>) -> Self {
+        self.string_section = Some(section);
+        self
+    }
+
+    /// Code that loads the C string `s`: ADRP+ADD and ADR pairs, and
+    /// literal-pool LDRs of a pointer to it, in executable regions. Each
+    /// reference carries the entry of the function around it. Sorted by
+    /// instruction address; also recorded for `get_string_refs_to`.
+    pub fn xrefs_to_string(&mut self, s: &str) -> Result<Vec<StringReference>, MemoryError> {
+        let search: Vec<Range<Address>> = match &self.string_section {
+            Some(section) => vec![section.clone()],
+            None => self.reader.get_regions()?.iter()
+                .filter(|r| r.protection().can_read())
+                .map(|r| r.start()..r.end())
+                .collect(),
+        };
+        let strings: HashSet<u64> = search.into_iter()
+            .flat_map(|range| find_c_strings(self.reader.as_ref(), s, range))
+            .map(|addr| addr.as_u64())
+            .collect();
+        if strings.is_empty() {
+            return Ok(Vec::new());
         }
+
+        let code: Vec<Range<Address>> = self.reader.get_regions()?.iter()
+            .filter(|r| r.protection().is_executable())
+            .map(|r| r.start()..r.end())
+            .collect();
+
+        let scanner = StringXrefScanner::new(self.reader.clone());
+        let mut sites: Vec<(Address, Address)> = Vec::new();
+        for range in &code {
+            sites.extend(scanner.scan_targets(range.clone(), &strings));
+            sites.extend(self.literal_loads(range.clone(), &strings));
+        }
+        sites.sort();
+        sites.dedup();
+
+        let mut refs = Vec::with_capacity(sites.len());
+        for (from, string_addr) in sites {
+            let reference = StringReference {
+                from,
+                string_addr,
+                string_content: s.to_string(),
+                function: enclosing_function(self.reader.as_ref(), from).0,
+            };
+            self.string_refs.entry(string_addr.as_u64()).or_default().push(reference.clone());
+            refs.push(reference);
+        }
+
+        Ok(refs)
+    }
+
+    /// `LDR Xt, <literal>` in `code` whose literal holds one of `targets`,
+    /// as (instruction, target)
+    fn literal_loads(&self, code: Range<Address>, targets: &HashSet<u64>) -> Vec<(Address, Address)> {
+        let mut loads = Vec::new();
+        let mut current = code.start;
+
+        while current < code.end {
+            let len = ((code.end - current) as usize).min(CHUNK) & !3;
+
+            if let Ok(bytes) = self.reader.read_bytes(current, len) {
+                for (i, word) in bytes.chunks_exact(4).enumerate() {
+                    let insn = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+                    if (insn & 0xFF000000) != 0x58000000 {
+                        continue;
+                    }
+
+                    let at = current + i as u64 * 4;
+                    let imm19 = ((((insn >> 5) & 0x7FFFF) as i64) << 45) >> 45;
+                    let literal = Address::new((at.as_u64() as i64 + imm19 * 4) as u64);
+
+                    match self.reader.read_u64(literal) {
+                        Ok(value) if targets.contains(&value) => loads.push((at, Address::new(value))),
+                        _ => {}
+                    }
+                }
+            }
+
+            current = current + CHUNK as u64;
+        }
+
+        loads
     }
 
     pub fn analyze_region(&mut self, region: &MemoryRegion) -> Result<usize, MemoryError> {
@@ -393,6 +490,8 @@ pub struct StringReference {
     pub from: Address,
     pub string_addr: Address,
     pub string_content: String,
+    /// Entry of the function containing `from`
+    pub function: Address,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -1,6 +1,6 @@
 // Wed Jan 15 2026 - Alex
 
-use super::{Arm64Instruction, Arm64Operand, Arm64Register, Arm64Condition, Arm64Shift, Arm64Extend};
+use super::{Arm64Instruction, Arm64Operand, Arm64Register, Arm64Condition, Arm64Shift};
 use crate::memory::Address;
 
 pub struct Arm64Decoder;
@@ -10,7 +10,7 @@ impl Arm64Decoder {
         let op0 = (raw >> 25) & 0xF;
 
         match op0 {
-            0b0000 | 0b0001 | 0b0010 | 0b0011 => Self::decode_unallocated(raw, addr),
+            0b0000..=0b0011 => Self::decode_unallocated(raw, addr),
             0b1000 | 0b1001 => Self::decode_data_processing_imm(raw, addr),
             0b1010 | 0b1011 => Self::decode_branch(raw, addr),
             0b0100 | 0b0110 | 0b1100 | 0b1110 => Self::decode_load_store(raw, addr),
@@ -60,7 +60,7 @@ impl Arm64Decoder {
             (0, 0) => ("ADD", false),
             (0, 1) => ("ADDS", true),
             (1, 0) => ("SUB", false),
-            (1, 1) => ("SUBS", true),
+            _ => ("SUBS", true),
         };
 
         Arm64Instruction {
@@ -334,8 +334,8 @@ impl Arm64Decoder {
         let mnemonic = match (v, is_load, size) {
             (0, false, _) => "STR",
             (0, true, _) => "LDR",
-            (1, false, _) => "STR",
-            (1, true, _) => "LDR",
+            (_, false, _) => "STR",
+            (_, true, _) => "LDR",
         };
 
         Arm64Instruction {
@@ -1,6 +1,6 @@
 // Wed Jan 15 2026 - Alex
 
-use super::{Arm64Register, Arm64Condition, Arm64Shift};
+use super::{Arm64Register, Arm64Condition};
 use crate::memory::Address;
 
 pub struct Arm64Encoder;
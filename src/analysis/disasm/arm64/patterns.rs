@@ -23,7 +23,7 @@ impl Arm64Patterns {
 
         if first.mnemonic == "SUB" {
             if let (Some(Arm64Operand::Register(Arm64Register::Sp)), Some(Arm64Operand::Register(Arm64Register::Sp))) = 
-                (first.operands.get(0), first.operands.get(1)) {
+                (first.operands.first(), first.operands.get(1)) {
                 return true;
             }
         }
@@ -32,7 +32,7 @@ impl Arm64Patterns {
             let second = &instructions[1];
             if first.mnemonic == "STP" && second.mnemonic == "ADD" {
                 if let (Some(Arm64Operand::Register(Arm64Register::X(29))), Some(Arm64Operand::Register(Arm64Register::Sp))) =
-                    (second.operands.get(0), second.operands.get(1)) {
+                    (second.operands.first(), second.operands.get(1)) {
                     return true;
                 }
             }
@@ -52,12 +52,10 @@ impl Arm64Patterns {
             return true;
         }
 
-        if last.mnemonic == "B" {
-            if instructions.len() >= 2 {
-                let prev = &instructions[instructions.len() - 2];
-                if prev.mnemonic == "LDP" {
-                    return true;
-                }
+        if last.mnemonic == "B" && instructions.len() >= 2 {
+            let prev = &instructions[instructions.len() - 2];
+            if prev.mnemonic == "LDP" {
+                return true;
             }
         }
 
@@ -102,8 +100,8 @@ impl Arm64Patterns {
             return None;
         }
 
-        let adrp_reg = match first.operands.get(0) {
-            Some(Arm64Operand::Register(r)) => r.clone(),
+        let adrp_reg = match first.operands.first() {
+            Some(Arm64Operand::Register(r)) => *r,
             _ => return None,
         };
 
@@ -135,7 +133,7 @@ impl Arm64Patterns {
             return None;
         }
 
-        let is_sp_dst = matches!(instr.operands.get(0), Some(Arm64Operand::Register(Arm64Register::Sp)));
+        let is_sp_dst = matches!(instr.operands.first(), Some(Arm64Operand::Register(Arm64Register::Sp)));
         let is_sp_src = matches!(instr.operands.get(1), Some(Arm64Operand::Register(Arm64Register::Sp)));
 
         if !is_sp_dst || !is_sp_src {
@@ -159,7 +157,7 @@ impl Arm64Patterns {
             return false;
         }
 
-        let is_fp_dst = matches!(instr.operands.get(0), Some(Arm64Operand::Register(Arm64Register::X(29))));
+        let is_fp_dst = matches!(instr.operands.first(), Some(Arm64Operand::Register(Arm64Register::X(29))));
         let is_sp_src = matches!(instr.operands.get(1), Some(Arm64Operand::Register(Arm64Register::Sp)));
 
         is_fp_dst && is_sp_src
@@ -174,11 +172,10 @@ impl Arm64Patterns {
             return false;
         }
 
-        if let Some(Arm64Operand::Memory { index: Some(_), .. }) = instr.operands.get(1) {
-            return true;
-        }
-
-        false
+        matches!(
+            instr.operands.get(2),
+            Some(Arm64Operand::ShiftedReg { .. }) | Some(Arm64Operand::ExtendedReg { .. })
+        )
     }
 
     pub fn is_computed_jump(instr: &Arm64Instruction) -> bool {
@@ -1,15 +1,39 @@
 // Wed Jan 15 2026 - Alex
 
 use crate::memory::Address;
-use crate::analysis::disasm::DecodedInstruction;
+use crate::analysis::disasm::{DecodedInstruction, Operand};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::{Arc, RwLock};
 
+/// Bumped whenever the engine decodes some word differently, so caches
+/// written by an older decoder are thrown away rather than replayed
+const DECODER_VERSION: u32 = 1;
+
+/// On-disk form of a `DisassemblyCache`, tied to the binary it decoded
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    binary_hash: String,
+    #[serde(default)]
+    decoder_version: u32,
+    instructions: Vec<DecodedInstruction>,
+}
+
+/// One shared decode per distinct instruction word
+type TemplateMap = HashMap<u32, Arc<DecodedInstruction>>;
+
+/// Decoded instructions by address. Entries are shared templates, so with
+/// `with_content_dedup` every address holding the same 4-byte word points
+/// at one decode, moved to the requested address on the way out.
 pub struct DisassemblyCache {
-    cache: Arc<RwLock<HashMap<u64, DecodedInstruction>>>,
+    cache: Arc<RwLock<HashMap<u64, Arc<DecodedInstruction>>>>,
     max_size: usize,
     hits: Arc<RwLock<u64>>,
     misses: Arc<RwLock<u64>>,
+    /// One decode per distinct word, when content dedup is on
+    templates: Option<Arc<RwLock<TemplateMap>>>,
+    content_hits: Arc<RwLock<u64>>,
 }
 
 impl DisassemblyCache {
@@ -19,15 +43,30 @@ impl DisassemblyCache {
             max_size,
             hits: Arc::new(RwLock::new(0)),
             misses: Arc::new(RwLock::new(0)),
+            templates: None,
+            content_hits: Arc::new(RwLock::new(0)),
         }
     }
 
+    /// Also keys decodes by their word, so `get_word` can serve an address
+    /// never seen before, e.g. in a relocated copy of the same code.
+    /// `capacity` bounds both the addresses and the distinct words kept.
+    pub fn with_content_dedup(capacity: usize) -> Self {
+        let mut cache = Self::new(capacity);
+        cache.templates = Some(Arc::new(RwLock::new(HashMap::new())));
+        cache
+    }
+
+    pub fn dedups_content(&self) -> bool {
+        self.templates.is_some()
+    }
+
     pub fn get(&self, addr: Address) -> Option<DecodedInstruction> {
         let cache = self.cache.read().unwrap();
         if let Some(instr) = cache.get(&addr.as_u64()) {
             let mut hits = self.hits.write().unwrap();
             *hits += 1;
-            Some(instr.clone())
+            Some(relocate(instr, addr))
         } else {
             let mut misses = self.misses.write().unwrap();
             *misses += 1;
@@ -35,14 +74,55 @@ impl DisassemblyCache {
         }
     }
 
+    /// The decode of `raw` from any address, moved to `addr` and cached
+    /// there. Always `None` without content dedup.
+    pub fn get_word(&self, addr: Address, raw: u32) -> Option<DecodedInstruction> {
+        let template = self.templates.as_ref()?.read().unwrap().get(&raw).cloned()?;
+        *self.content_hits.write().unwrap() += 1;
+
+        let instr = relocate(&template, addr);
+        self.insert_shared(addr.as_u64(), template);
+        Some(instr)
+    }
+
     pub fn insert(&self, addr: Address, instr: DecodedInstruction) {
+        let template = self.template_for(instr);
+        self.insert_shared(addr.as_u64(), template);
+    }
+
+    fn insert_shared(&self, addr: u64, template: Arc<DecodedInstruction>) {
         let mut cache = self.cache.write().unwrap();
 
         if cache.len() >= self.max_size {
             self.evict(&mut cache);
         }
 
-        cache.insert(addr.as_u64(), instr);
+        cache.insert(addr, template);
+    }
+
+    /// The shared decode of `instr`'s word, registering `instr` if it is
+    /// the first; a fresh allocation without content dedup
+    fn template_for(&self, instr: DecodedInstruction) -> Arc<DecodedInstruction> {
+        let Some(templates) = &self.templates else {
+            return Arc::new(instr);
+        };
+
+        let mut templates = templates.write().unwrap();
+        if let Some(template) = templates.get(&instr.raw) {
+            return template.clone();
+        }
+        if templates.len() >= self.max_size {
+            let to_remove = templates.len() / 4;
+            let keys: Vec<u32> = templates.keys().take(to_remove).copied().collect();
+            for key in keys {
+                templates.remove(&key);
+            }
+        }
+
+        let raw = instr.raw;
+        let template = Arc::new(instr);
+        templates.insert(raw, template.clone());
+        template
     }
 
     pub fn contains(&self, addr: Address) -> bool {
@@ -52,17 +132,22 @@ impl DisassemblyCache {
 
     pub fn remove(&self, addr: Address) -> Option<DecodedInstruction> {
         let mut cache = self.cache.write().unwrap();
-        cache.remove(&addr.as_u64())
+        cache.remove(&addr.as_u64()).map(|instr| relocate(&instr, addr))
     }
 
     pub fn clear(&self) {
         let mut cache = self.cache.write().unwrap();
         cache.clear();
+        if let Some(templates) = &self.templates {
+            templates.write().unwrap().clear();
+        }
 
         let mut hits = self.hits.write().unwrap();
         let mut misses = self.misses.write().unwrap();
+        let mut content_hits = self.content_hits.write().unwrap();
         *hits = 0;
         *misses = 0;
+        *content_hits = 0;
     }
 
     pub fn len(&self) -> usize {
@@ -97,10 +182,12 @@ impl DisassemblyCache {
             hits,
             misses,
             hit_rate: self.hit_rate(),
+            templates: self.templates.as_ref().map(|templates| templates.read().unwrap().len()),
+            content_hits: *self.content_hits.read().unwrap(),
         }
     }
 
-    fn evict(&self, cache: &mut HashMap<u64, DecodedInstruction>) {
+    fn evict(&self, cache: &mut HashMap<u64, Arc<DecodedInstruction>>) {
         let to_remove = cache.len() / 4;
         let keys: Vec<u64> = cache.keys().take(to_remove).copied().collect();
         for key in keys {
@@ -115,7 +202,7 @@ impl DisassemblyCache {
         let mut addr = start.as_u64();
         while addr < end.as_u64() {
             if let Some(instr) = cache.get(&addr) {
-                result.push(instr.clone());
+                result.push(relocate(instr, Address::new(addr)));
                 addr += instr.size as u64;
             } else {
                 addr += 4;
@@ -126,15 +213,70 @@ impl DisassemblyCache {
     }
 
     pub fn insert_batch(&self, instructions: &[DecodedInstruction]) {
-        let mut cache = self.cache.write().unwrap();
-
         for instr in instructions {
-            if cache.len() >= self.max_size {
-                self.evict(&mut cache);
+            self.insert(instr.address, instr.clone());
+        }
+    }
+}
+
+/// `template` as decoded at `addr`: the address and any PC-relative
+/// target move with it. Targets are always the last operand printed.
+fn relocate(template: &DecodedInstruction, addr: Address) -> DecodedInstruction {
+    let mut instr = template.clone();
+    if template.address == addr {
+        return instr;
+    }
+
+    let delta = addr.as_u64().wrapping_sub(template.address.as_u64());
+    instr.address = addr;
+    for operand in &mut instr.operands {
+        if let Operand::Address(target) = operand {
+            let moved = Address::new(target.as_u64().wrapping_add(delta));
+            if let Some(rest) = instr.operand_str.strip_suffix(&format!("0x{:X}", target.as_u64())) {
+                instr.operand_str = format!("{}0x{:X}", rest, moved.as_u64());
             }
-            cache.insert(instr.address.as_u64(), instr.clone());
+            *target = moved;
         }
     }
+    instr
+}
+
+impl DisassemblyCache {
+    /// Writes every cached instruction, tagged with the hash of the binary
+    /// they were decoded from.
+    pub fn save(&self, path: &Path, binary_hash: &str) -> std::io::Result<()> {
+        let mut instructions: Vec<DecodedInstruction> = self.cache.read().unwrap()
+            .iter()
+            .map(|(&addr, instr)| relocate(instr, Address::new(addr)))
+            .collect();
+        instructions.sort_by_key(|instr| instr.address);
+
+        let file = CacheFile { binary_hash: binary_hash.to_string(), decoder_version: DECODER_VERSION, instructions };
+        let json = serde_json::to_vec(&file)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, json)
+    }
+
+    /// Reads a cache written by `save`. Returns `None` when it was written
+    /// for a different binary or by another decoder version, so stale
+    /// decodes are never used.
+    pub fn load(path: &Path, binary_hash: &str, max_size: usize) -> std::io::Result<Option<Self>> {
+        let content = std::fs::read(path)?;
+        let file: CacheFile = serde_json::from_slice(&content)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        if file.binary_hash != binary_hash || file.decoder_version != DECODER_VERSION {
+            return Ok(None);
+        }
+
+        let cache = Self::new(max_size.max(file.instructions.len()));
+        cache.insert_batch(&file.instructions);
+        Ok(Some(cache))
+    }
 }
 
 impl Clone for DisassemblyCache {
@@ -144,6 +286,8 @@ impl Clone for DisassemblyCache {
             max_size: self.max_size,
             hits: self.hits.clone(),
             misses: self.misses.clone(),
+            templates: self.templates.clone(),
+            content_hits: self.content_hits.clone(),
         }
     }
 }
@@ -155,11 +299,15 @@ pub struct CacheStats {
     pub hits: u64,
     pub misses: u64,
     pub hit_rate: f64,
+    /// Distinct words decoded, with content dedup on
+    pub templates: Option<usize>,
+    /// Address misses served from another address's decode of the word
+    pub content_hits: u64,
 }
 
 impl CacheStats {
     pub fn format(&self) -> String {
-        format!(
+        let summary = format!(
             "Cache: {}/{} entries ({:.1}% full), {:.1}% hit rate ({} hits, {} misses)",
             self.size,
             self.max_size,
@@ -167,7 +315,15 @@ impl CacheStats {
             self.hit_rate * 100.0,
             self.hits,
             self.misses
-        )
+        );
+
+        match self.templates {
+            Some(templates) => format!(
+                "{}, {} distinct words ({} misses served by content)",
+                summary, templates, self.content_hits
+            ),
+            None => summary,
+        }
     }
 }
 
@@ -231,3 +387,65 @@ impl Clone for FunctionCache {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::disasm::{InstructionCategory, Operand};
+
+    fn ret_at(addr: u64) -> DecodedInstruction {
+        DecodedInstruction {
+            address: Address::new(addr),
+            bytes: vec![0xC0, 0x03, 0x5F, 0xD6],
+            size: 4,
+            mnemonic: "RET".to_string(),
+            operands: vec![Operand::Register(30)],
+            operand_str: String::new(),
+            raw: 0xD65F03C0,
+            category: InstructionCategory::Return,
+        }
+    }
+
+    #[test]
+    fn test_saved_cache_only_loads_for_same_binary() {
+        let path = std::env::temp_dir().join(format!("disasm-cache-test-{}.json", std::process::id()));
+
+        let cache = DisassemblyCache::new(16);
+        cache.insert_batch(&[ret_at(0x1000), ret_at(0x1004)]);
+        cache.save(&path, "abc123").unwrap();
+
+        let loaded = DisassemblyCache::load(&path, "abc123", 16).unwrap().expect("hash matches");
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.get(Address::new(0x1004)).unwrap().raw, 0xD65F03C0);
+
+        assert!(DisassemblyCache::load(&path, "other", 16).unwrap().is_none());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_content_dedup_moves_branch_targets() {
+        let cache = DisassemblyCache::with_content_dedup(16);
+        cache.insert(Address::new(0x1000), DecodedInstruction {
+            address: Address::new(0x1000),
+            bytes: vec![0x40, 0x00, 0x00, 0x94],
+            size: 4,
+            mnemonic: "BL".to_string(),
+            operands: vec![Operand::Address(Address::new(0x1100))],
+            operand_str: "0x1100".to_string(),
+            raw: 0x94000040,
+            category: InstructionCategory::Call,
+        });
+
+        assert!(cache.get_word(Address::new(0x2000), 0xD65F03C0).is_none());
+        let moved = cache.get_word(Address::new(0x2000), 0x94000040).expect("same word");
+        assert_eq!(moved.address, Address::new(0x2000));
+        assert!(matches!(moved.operands[..], [Operand::Address(target)] if target == Address::new(0x2100)));
+        assert_eq!(moved.operand_str, "0x2100");
+
+        assert_eq!(cache.get(Address::new(0x2000)).unwrap().operand_str, "0x2100");
+        assert_eq!(cache.get(Address::new(0x1000)).unwrap().operand_str, "0x1100");
+        let stats = cache.stats();
+        assert_eq!((stats.templates, stats.content_hits), (Some(1), 1));
+    }
+}
@@ -1,9 +1,13 @@
 // Wed Jan 15 2026 - Alex
 
 use crate::memory::{Address, MemoryReader, MemoryError};
-use crate::analysis::disasm::{DecodedInstruction, Operand, InstructionCategory, ShiftType};
+use crate::analysis::disasm::{DecodedInstruction, DecoderCoverage, Operand, InstructionCategory, InstructionFormatter};
+use crate::analysis::disasm::{VectorArrangement, VectorElement};
+use std::ops::Range;
 use std::sync::Arc;
 
+const CHUNK: usize = 0x10000;
+
 pub struct DisassemblyEngine {
     reader: Arc<dyn MemoryReader>,
 }
@@ -31,14 +35,41 @@ impl DisassemblyEngine {
         })
     }
 
+    /// Decodes every word in `range` and tallies how many the decoder
+    /// understood. Unreadable chunks are skipped.
+    pub fn coverage(&self, range: Range<Address>) -> DecoderCoverage {
+        let mut coverage = DecoderCoverage::default();
+        let mut current = range.start;
+
+        while current < range.end {
+            let len = ((range.end - current) as usize).min(CHUNK) & !3;
+            if len == 0 {
+                break;
+            }
+
+            if let Ok(bytes) = self.reader.read_bytes(current, len) {
+                for (i, word) in bytes.chunks_exact(4).enumerate() {
+                    let raw = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+                    let (_, _, _, category) = self.decode_arm64(raw, current + i as u64 * 4);
+                    coverage.record(raw, category);
+                }
+            }
+
+            current = current + len as u64;
+        }
+
+        coverage
+    }
+
     fn decode_arm64(&self, raw: u32, addr: Address) -> (String, Vec<Operand>, String, InstructionCategory) {
         let op0 = (raw >> 25) & 0xF;
 
         match op0 {
-            0b0101 | 0b1101 => self.decode_data_processing_imm(raw),
+            0b1000 | 0b1001 => self.decode_data_processing_imm(raw),
             0b1010 | 0b1011 => self.decode_branch(raw, addr),
             0b0100 | 0b0110 | 0b1100 | 0b1110 => self.decode_load_store(raw),
             0b0101 => self.decode_data_processing_reg(raw),
+            0b0111 | 0b1111 => self.decode_simd(raw),
             _ => self.decode_fallback(raw),
         }
     }
@@ -47,7 +78,7 @@ impl DisassemblyEngine {
         let op0 = (raw >> 23) & 0x7;
 
         match op0 {
-            0b000 | 0b001 => {
+            0b010 => {
                 let sf = (raw >> 31) & 1;
                 let op = (raw >> 30) & 1;
                 let s = (raw >> 29) & 1;
@@ -76,7 +107,7 @@ impl DisassemblyEngine {
 
                 (mnemonic.to_string(), operands, operand_str, category)
             }
-            0b100 => {
+            0b101 => {
                 let rd = (raw & 0x1F) as u8;
                 let imm16 = ((raw >> 5) & 0xFFFF) as u16;
                 let hw = ((raw >> 21) & 0x3) as u8;
@@ -106,7 +137,7 @@ impl DisassemblyEngine {
 
                 (mnemonic.to_string(), operands, operand_str, InstructionCategory::Move)
             }
-            0b101 => {
+            0b000 | 0b001 => {
                 let rd = (raw & 0x1F) as u8;
                 let immhi = ((raw >> 5) & 0x7FFFF) as i64;
                 let immlo = ((raw >> 29) & 0x3) as i64;
@@ -183,6 +214,7 @@ impl DisassemblyEngine {
 
                 (mnemonic, operands, operand_str, InstructionCategory::ConditionalBranch)
             }
+            0b110 if (raw & 0xFFC00000) == 0xD5000000 => self.decode_system(raw),
             0b110 => {
                 let opc = (raw >> 21) & 0x7;
                 let rn = ((raw >> 5) & 0x1F) as u8;
@@ -206,6 +238,7 @@ impl DisassemblyEngine {
 
                 (mnemonic.to_string(), operands, operand_str, category)
             }
+            0b001 | 0b101 if (raw >> 25) & 1 == 1 => self.decode_test_branch(raw, addr),
             0b001 | 0b101 => {
                 let sf = (raw >> 31) & 1;
                 let op = (raw >> 24) & 1;
@@ -234,6 +267,54 @@ impl DisassemblyEngine {
         }
     }
 
+    fn decode_test_branch(&self, raw: u32, addr: Address) -> (String, Vec<Operand>, String, InstructionCategory) {
+        let op = (raw >> 24) & 1;
+        let bit = ((raw >> 19) & 0x1F) | ((raw >> 26) & 0x20);
+        let imm14 = (raw >> 5) & 0x3FFF;
+        let rt = (raw & 0x1F) as u8;
+
+        let offset = if imm14 & 0x2000 != 0 {
+            ((imm14 | 0xFFFFC000) as i32) * 4
+        } else {
+            (imm14 as i32) * 4
+        };
+
+        let target = (addr.as_u64() as i64 + offset as i64) as u64;
+        let reg_prefix = if bit >= 32 { "X" } else { "W" };
+        let mnemonic = if op == 0 { "TBZ" } else { "TBNZ" };
+
+        let operand_str = format!("{}{}, #{}, 0x{:X}", reg_prefix, rt, bit, target);
+        let operands = vec![
+            Operand::Register(rt),
+            Operand::Immediate(bit as i64),
+            Operand::Address(Address::new(target)),
+        ];
+
+        (mnemonic.to_string(), operands, operand_str, InstructionCategory::ConditionalBranch)
+    }
+
+    fn decode_system(&self, raw: u32) -> (String, Vec<Operand>, String, InstructionCategory) {
+        // MSR/MRS (register): 1101 0101 00 L 1 o0 op1 CRn CRm op2 Rt
+        if (raw & 0xFFD00000) != 0xD5100000 {
+            return self.decode_fallback(raw);
+        }
+
+        let is_read = (raw >> 21) & 1 == 1;
+        let sysreg = ((raw >> 5) & 0x7FFF) as u16;
+        let rt = (raw & 0x1F) as u8;
+
+        let reg_name = InstructionFormatter::system_register_name(sysreg);
+        let rt_name = if rt == 31 { "XZR".to_string() } else { format!("X{}", rt) };
+
+        let (mnemonic, operand_str, operands) = if is_read {
+            ("MRS", format!("{}, {}", rt_name, reg_name), vec![Operand::Register(rt), Operand::SystemRegister(sysreg)])
+        } else {
+            ("MSR", format!("{}, {}", reg_name, rt_name), vec![Operand::SystemRegister(sysreg), Operand::Register(rt)])
+        };
+
+        (mnemonic.to_string(), operands, operand_str, InstructionCategory::System)
+    }
+
     fn decode_load_store(&self, raw: u32) -> (String, Vec<Operand>, String, InstructionCategory) {
         let op0 = (raw >> 28) & 0xF;
         let op1 = (raw >> 26) & 0x1;
@@ -245,9 +326,16 @@ impl DisassemblyEngine {
         let rt = (raw & 0x1F) as u8;
         let rn = ((raw >> 5) & 0x1F) as u8;
 
-        if op1 == 0 && op2 == 0b01 {
+        if (raw & 0xBFBF0000) == 0x0C000000 || (raw & 0xBFA00000) == 0x0C800000 {
+            return self.decode_simd_load_store_multiple(raw);
+        }
+
+        // 128-bit Q registers use size 0 with the high opc bit set
+        let is_q = v == 1 && size == 0 && opc >= 2;
+
+        if (raw & 0x3B000000) == 0x39000000 {
             let imm12 = ((raw >> 10) & 0xFFF) as i64;
-            let scale = match size { 0 => 1, 1 => 2, 2 => 4, 3 => 8, _ => 1 };
+            let scale = if is_q { 16 } else { match size { 0 => 1, 1 => 2, 2 => 4, 3 => 8, _ => 1 } };
             let offset = imm12 * scale;
 
             let (mnemonic, category) = match (v, opc) {
@@ -256,10 +344,14 @@ impl DisassemblyEngine {
                 (0, 2) => ("LDRS", InstructionCategory::Load),
                 (1, 0) => ("STR", InstructionCategory::Store),
                 (1, 1) => ("LDR", InstructionCategory::Load),
+                (1, 2) if is_q => ("STR", InstructionCategory::Store),
+                (1, 3) if is_q => ("LDR", InstructionCategory::Load),
                 _ => ("UNKNOWN", InstructionCategory::Unknown),
             };
 
-            let reg_prefix = if v == 1 {
+            let reg_prefix = if is_q {
+                "Q"
+            } else if v == 1 {
                 match size { 0 => "B", 1 => "H", 2 => "S", 3 => "D", _ => "?" }
             } else {
                 match size { 2 => "W", 3 => "X", _ => "?" }
@@ -277,7 +369,7 @@ impl DisassemblyEngine {
             ];
 
             (mnemonic.to_string(), operands, operand_str, category)
-        } else if (raw >> 27) & 0x1F == 0b10100 {
+        } else if (raw & 0x3E000000) == 0x28000000 {
             let opc = (raw >> 22) & 0x3;
             let l = (raw >> 22) & 1;
             let imm7 = ((raw >> 15) & 0x7F) as i8;
@@ -300,6 +392,291 @@ impl DisassemblyEngine {
         }
     }
 
+    /// LD1-LD4/ST1-ST4 (multiple structures), no offset or post-indexed
+    fn decode_simd_load_store_multiple(&self, raw: u32) -> (String, Vec<Operand>, String, InstructionCategory) {
+        let q = (raw >> 30) & 1;
+        let load = (raw >> 22) & 1 == 1;
+        let post_index = (raw >> 23) & 1 == 1;
+        let rm = ((raw >> 16) & 0x1F) as u8;
+        let opcode = (raw >> 12) & 0xF;
+        let size = (raw >> 10) & 0x3;
+        let rn = ((raw >> 5) & 0x1F) as u8;
+        let rt = (raw & 0x1F) as u8;
+
+        // (structure elements, registers in the list)
+        let (structures, count) = match opcode {
+            0b0000 => (4, 4),
+            0b0010 => (1, 4),
+            0b0100 => (3, 3),
+            0b0110 => (1, 3),
+            0b0111 => (1, 1),
+            0b1000 => (2, 2),
+            0b1010 => (1, 2),
+            _ => return self.decode_fallback(raw),
+        };
+
+        // Only LD1/ST1 can address a single 64-bit lane
+        if structures > 1 && size == 3 && q == 0 {
+            return self.decode_fallback(raw);
+        }
+
+        let arrangement = VectorArrangement::from_size_q(size, q);
+        let mnemonic = format!("{}{}", if load { "LD" } else { "ST" }, structures);
+        let category = if load { InstructionCategory::Load } else { InstructionCategory::Store };
+
+        let mut operands = vec![
+            Operand::VectorList { first: rt, count, arrangement },
+            Operand::Memory { base: rn, offset: 0, index: None, scale: 1 },
+        ];
+
+        let mut operand_str = format!("{}, [{}]", vector_list(rt, count, arrangement), base_register(rn));
+
+        if post_index {
+            if rm == 31 {
+                let amount = count as i64 * if q == 1 { 16 } else { 8 };
+                operand_str.push_str(&format!(", #{}", amount));
+                operands.push(Operand::Immediate(amount));
+            } else {
+                operand_str.push_str(&format!(", X{}", rm));
+                operands.push(Operand::Register(rm));
+            }
+        }
+
+        (mnemonic, operands, operand_str, category)
+    }
+
+    /// Advanced SIMD and scalar floating-point data processing
+    fn decode_simd(&self, raw: u32) -> (String, Vec<Operand>, String, InstructionCategory) {
+        if (raw & 0x9F200400) == 0x0E200400 {
+            self.decode_simd_three_same(raw)
+        } else if (raw & 0x9FE08400) == 0x0E000400 {
+            self.decode_simd_copy(raw)
+        } else if (raw & 0x9FF80400) == 0x0F000400 {
+            self.decode_simd_modified_immediate(raw)
+        } else if (raw & 0xFF200C00) == 0x1E200800 {
+            self.decode_fp_two_source(raw)
+        } else {
+            self.decode_fallback(raw)
+        }
+    }
+
+    fn decode_simd_three_same(&self, raw: u32) -> (String, Vec<Operand>, String, InstructionCategory) {
+        let q = (raw >> 30) & 1;
+        let u = (raw >> 29) & 1;
+        let size = (raw >> 22) & 0x3;
+        let rm = ((raw >> 16) & 0x1F) as u8;
+        let opcode = (raw >> 11) & 0x1F;
+        let rn = ((raw >> 5) & 0x1F) as u8;
+        let rd = (raw & 0x1F) as u8;
+
+        let (mnemonic, arrangement) = if opcode == 0b00011 {
+            // Bitwise ops reuse the size field as part of the opcode
+            let mnemonic = match (u, size) {
+                (0, 0) => "AND",
+                (0, 1) => "BIC",
+                (0, 2) => "ORR",
+                (0, _) => "ORN",
+                (_, 0) => "EOR",
+                (_, 1) => "BSL",
+                (_, 2) => "BIT",
+                _ => "BIF",
+            };
+            (mnemonic, VectorArrangement::from_size_q(0, q))
+        } else if opcode >= 0b11000 {
+            // Floating point: size<1> extends the opcode, size<0> picks S or D lanes
+            let sz = size & 1;
+            if sz == 1 && q == 0 {
+                return self.decode_fallback(raw);
+            }
+            let mnemonic = match (u, size >> 1, opcode) {
+                (0, 0, 0b11001) => "FMLA",
+                (0, 1, 0b11001) => "FMLS",
+                (0, 0, 0b11010) => "FADD",
+                (0, 1, 0b11010) => "FSUB",
+                (0, 0, 0b11110) => "FMAX",
+                (0, 1, 0b11110) => "FMIN",
+                (1, 0, 0b11010) => "FADDP",
+                (1, 0, 0b11011) => "FMUL",
+                (1, 0, 0b11111) => "FDIV",
+                _ => return self.decode_fallback(raw),
+            };
+            (mnemonic, VectorArrangement::from_size_q(2 + sz, q))
+        } else {
+            if size == 3 && q == 0 {
+                return self.decode_fallback(raw);
+            }
+            let mnemonic = match (u, opcode) {
+                (0, 0b10000) => "ADD",
+                (1, 0b10000) => "SUB",
+                (1, 0b10001) => "CMEQ",
+                (0, 0b10011) if size != 3 => "MUL",
+                _ => return self.decode_fallback(raw),
+            };
+            (mnemonic, VectorArrangement::from_size_q(size, q))
+        };
+
+        // ORR with both sources the same register is the vector MOV alias
+        if mnemonic == "ORR" && rn == rm {
+            let operands = vec![
+                Operand::Vector { reg: rd, arrangement },
+                Operand::Vector { reg: rn, arrangement },
+            ];
+            let operand_str = format!("{}, {}", vector(rd, arrangement), vector(rn, arrangement));
+            return ("MOV".to_string(), operands, operand_str, InstructionCategory::Simd);
+        }
+
+        let operands = vec![
+            Operand::Vector { reg: rd, arrangement },
+            Operand::Vector { reg: rn, arrangement },
+            Operand::Vector { reg: rm, arrangement },
+        ];
+        let operand_str = format!("{}, {}, {}",
+            vector(rd, arrangement), vector(rn, arrangement), vector(rm, arrangement));
+
+        (mnemonic.to_string(), operands, operand_str, InstructionCategory::Simd)
+    }
+
+    /// DUP, INS and UMOV, printed with their MOV aliases where they apply
+    fn decode_simd_copy(&self, raw: u32) -> (String, Vec<Operand>, String, InstructionCategory) {
+        let q = (raw >> 30) & 1;
+        let op = (raw >> 29) & 1;
+        let imm5 = (raw >> 16) & 0x1F;
+        let imm4 = (raw >> 11) & 0xF;
+        let rn = ((raw >> 5) & 0x1F) as u8;
+        let rd = (raw & 0x1F) as u8;
+
+        // The lowest set bit of imm5 gives the element size
+        let size = imm5.trailing_zeros();
+        if size > 3 {
+            return self.decode_fallback(raw);
+        }
+        let element = VectorElement::from_size(size);
+        let index = (imm5 >> (size + 1)) as u8;
+        let general = if size == 3 { "X" } else { "W" };
+
+        let lane = |reg: u8, index: u8| -> (Operand, String) {
+            (Operand::VectorLane { reg, element, index }, format!("V{}.{}[{}]", reg, element.name(), index))
+        };
+
+        match (op, imm4) {
+            (0, 0b0000) | (0, 0b0001) => {
+                if size == 3 && q == 0 {
+                    return self.decode_fallback(raw);
+                }
+                let arrangement = VectorArrangement::from_size_q(size, q);
+                let (source, source_str) = if imm4 == 0 {
+                    lane(rn, index)
+                } else {
+                    (Operand::Register(rn), format!("{}{}", general, rn))
+                };
+                let operands = vec![Operand::Vector { reg: rd, arrangement }, source];
+                let operand_str = format!("{}, {}", vector(rd, arrangement), source_str);
+                ("DUP".to_string(), operands, operand_str, InstructionCategory::Simd)
+            }
+            (0, 0b0011) if q == 1 => {
+                let (dest, dest_str) = lane(rd, index);
+                let operand_str = format!("{}, {}{}", dest_str, general, rn);
+                ("MOV".to_string(), vec![dest, Operand::Register(rn)], operand_str, InstructionCategory::Simd)
+            }
+            (0, 0b0111) => {
+                // UMOV needs Q set for 64-bit lanes and clear otherwise
+                if (size == 3) != (q == 1) {
+                    return self.decode_fallback(raw);
+                }
+                let mnemonic = if size >= 2 { "MOV" } else { "UMOV" };
+                let (source, source_str) = lane(rn, index);
+                let operand_str = format!("{}{}, {}", general, rd, source_str);
+                (mnemonic.to_string(), vec![Operand::Register(rd), source], operand_str, InstructionCategory::Simd)
+            }
+            (1, _) if q == 1 => {
+                let (dest, dest_str) = lane(rd, index);
+                let (source, source_str) = lane(rn, (imm4 >> size) as u8);
+                let operand_str = format!("{}, {}", dest_str, source_str);
+                ("MOV".to_string(), vec![dest, source], operand_str, InstructionCategory::Simd)
+            }
+            _ => self.decode_fallback(raw),
+        }
+    }
+
+    /// MOVI, MVNI and FMOV (vector, immediate)
+    fn decode_simd_modified_immediate(&self, raw: u32) -> (String, Vec<Operand>, String, InstructionCategory) {
+        let q = (raw >> 30) & 1;
+        let op = (raw >> 29) & 1;
+        let cmode = (raw >> 12) & 0xF;
+        let rd = (raw & 0x1F) as u8;
+        let imm8 = (((raw >> 16) & 0x7) << 5) | ((raw >> 5) & 0x1F);
+
+        let (mnemonic, arrangement, immediate) = match (op, cmode) {
+            (0, 0b1110) => ("MOVI", VectorArrangement::from_size_q(0, q), format!("#0x{:x}", imm8)),
+            (1, 0b1110) => {
+                // Each imm8 bit expands to a whole byte of the 64-bit value
+                let imm64 = (0..8).fold(0u64, |acc, bit| {
+                    if (imm8 >> bit) & 1 == 1 { acc | (0xFF << (bit * 8)) } else { acc }
+                });
+                if q == 0 {
+                    let operand_str = format!("D{}, #0x{:x}", rd, imm64);
+                    return ("MOVI".to_string(), vec![Operand::Register(rd), Operand::Immediate(imm64 as i64)], operand_str, InstructionCategory::Simd);
+                }
+                ("MOVI", VectorArrangement::from_size_q(3, 1), format!("#0x{:x}", imm64))
+            }
+            (_, 0b1111) => {
+                if op == 1 && q == 0 {
+                    return self.decode_fallback(raw);
+                }
+                let size = if op == 1 { 3 } else { 2 };
+                ("FMOV", VectorArrangement::from_size_q(size, q), format!("#{:?}", expand_fp_imm8(imm8)))
+            }
+            (_, c) if c & 0b1001 == 0 || c & 0b1101 == 0b1000 => {
+                // 0xx0 shifts 32-bit lanes by 0-24, 10x0 shifts 16-bit lanes by 0 or 8
+                let (size, shift) = if c & 0b1000 == 0 { (2, 8 * ((c >> 1) & 3)) } else { (1, 8 * ((c >> 1) & 1)) };
+                let mnemonic = if op == 1 { "MVNI" } else { "MOVI" };
+                let immediate = if shift == 0 {
+                    format!("#0x{:x}", imm8)
+                } else {
+                    format!("#0x{:x}, LSL #{}", imm8, shift)
+                };
+                (mnemonic, VectorArrangement::from_size_q(size, q), immediate)
+            }
+            _ => return self.decode_fallback(raw),
+        };
+
+        let operands = vec![Operand::Vector { reg: rd, arrangement }, Operand::Immediate(imm8 as i64)];
+        let operand_str = format!("{}, {}", vector(rd, arrangement), immediate);
+
+        (mnemonic.to_string(), operands, operand_str, InstructionCategory::Simd)
+    }
+
+    /// Scalar FMUL/FDIV/FADD/FSUB/FMAX/FMIN
+    fn decode_fp_two_source(&self, raw: u32) -> (String, Vec<Operand>, String, InstructionCategory) {
+        let ftype = (raw >> 22) & 0x3;
+        let rm = ((raw >> 16) & 0x1F) as u8;
+        let opcode = (raw >> 12) & 0xF;
+        let rn = ((raw >> 5) & 0x1F) as u8;
+        let rd = (raw & 0x1F) as u8;
+
+        let prefix = match ftype {
+            0 => "S",
+            1 => "D",
+            3 => "H",
+            _ => return self.decode_fallback(raw),
+        };
+
+        let mnemonic = match opcode {
+            0b0000 => "FMUL",
+            0b0001 => "FDIV",
+            0b0010 => "FADD",
+            0b0011 => "FSUB",
+            0b0100 => "FMAX",
+            0b0101 => "FMIN",
+            _ => return self.decode_fallback(raw),
+        };
+
+        let operands = vec![Operand::Register(rd), Operand::Register(rn), Operand::Register(rm)];
+        let operand_str = format!("{}{}, {}{}, {}{}", prefix, rd, prefix, rn, prefix, rm);
+
+        (mnemonic.to_string(), operands, operand_str, InstructionCategory::Simd)
+    }
+
     fn decode_data_processing_reg(&self, raw: u32) -> (String, Vec<Operand>, String, InstructionCategory) {
         let sf = (raw >> 31) & 1;
         let opc = (raw >> 29) & 0x3;
@@ -309,6 +686,25 @@ impl DisassemblyEngine {
 
         let reg_prefix = if sf == 1 { "X" } else { "W" };
 
+        // Add/subtract (shifted register) sits beside the logical ops
+        if (raw >> 24) & 1 == 1 {
+            let mnemonic = match opc {
+                0b00 => "ADD",
+                0b01 => "ADDS",
+                0b10 => "SUB",
+                _ => "SUBS",
+            };
+
+            let operand_str = format!("{}{}, {}{}, {}{}", reg_prefix, rd, reg_prefix, rn, reg_prefix, rm);
+            let operands = vec![
+                Operand::Register(rd),
+                Operand::Register(rn),
+                Operand::Register(rm),
+            ];
+
+            return (mnemonic.to_string(), operands, operand_str, InstructionCategory::Arithmetic);
+        }
+
         if (raw >> 21) & 0x7FF == 0b01011000000 {
             let operand_str = format!("{}{}, {}{}", reg_prefix, rd, reg_prefix, rm);
             let operands = vec![Operand::Register(rd), Operand::Register(rm)];
@@ -338,3 +734,30 @@ impl DisassemblyEngine {
         ("UNKNOWN".to_string(), vec![], operand_str, InstructionCategory::Unknown)
     }
 }
+
+fn vector(reg: u8, arrangement: VectorArrangement) -> String {
+    format!("V{}.{}", reg, arrangement.name())
+}
+
+fn vector_list(first: u8, count: u8, arrangement: VectorArrangement) -> String {
+    let regs: Vec<String> = (0..count)
+        .map(|i| vector((first + i) % 32, arrangement))
+        .collect();
+    format!("{{{}}}", regs.join(", "))
+}
+
+fn base_register(reg: u8) -> String {
+    if reg == 31 { "SP".to_string() } else { format!("X{}", reg) }
+}
+
+/// VFPExpandImm for single precision: sign a, exponent from NOT(b):c:d,
+/// fraction efgh
+fn expand_fp_imm8(imm8: u32) -> f32 {
+    let sign = if imm8 & 0x80 != 0 { -1.0 } else { 1.0 };
+    let b = (imm8 >> 6) & 1;
+    let cd = ((imm8 >> 4) & 0x3) as i32;
+    let exponent = if b == 1 { cd - 3 } else { cd + 1 };
+    let fraction = 1.0 + (imm8 & 0xF) as f32 / 16.0;
+
+    sign * fraction * 2f32.powi(exponent)
+}
@@ -1,7 +1,7 @@
 // Wed Jan 15 2026 - Alex
 
 use crate::memory::Address;
-use crate::analysis::disasm::{DecodedInstruction, InstructionCategory};
+use crate::analysis::disasm::DecodedInstruction;
 use std::collections::HashMap;
 
 pub struct InstructionFormatter {
@@ -86,6 +86,50 @@ impl InstructionFormatter {
         result
     }
 
+    /// Canonical name of an MRS/MSR system register, or LLVM's generic
+    /// `S<op0>_<op1>_c<CRn>_c<CRm>_<op2>` spelling when it isn't known.
+    pub fn system_register_name(sysreg: u16) -> String {
+        let op0 = 2 + ((sysreg >> 14) & 0x1);
+        let op1 = (sysreg >> 11) & 0x7;
+        let crn = (sysreg >> 7) & 0xF;
+        let crm = (sysreg >> 3) & 0xF;
+        let op2 = sysreg & 0x7;
+
+        let name = match (op0, op1, crn, crm, op2) {
+            (2, 0, 0, 2, 2) => "MDSCR_EL1",
+            (3, 0, 0, 0, 0) => "MIDR_EL1",
+            (3, 0, 0, 0, 5) => "MPIDR_EL1",
+            (3, 0, 0, 4, 0) => "ID_AA64PFR0_EL1",
+            (3, 0, 0, 6, 0) => "ID_AA64ISAR0_EL1",
+            (3, 0, 0, 7, 0) => "ID_AA64MMFR0_EL1",
+            (3, 0, 4, 1, 0) => "SP_EL0",
+            (3, 0, 4, 2, 2) => "CurrentEL",
+            (3, 0, 13, 0, 4) => "TPIDR_EL1",
+            (3, 3, 0, 0, 1) => "CTR_EL0",
+            (3, 3, 0, 0, 7) => "DCZID_EL0",
+            (3, 3, 2, 4, 0) => "RNDR",
+            (3, 3, 2, 4, 1) => "RNDRRS",
+            (3, 3, 4, 2, 0) => "NZCV",
+            (3, 3, 4, 2, 1) => "DAIF",
+            (3, 3, 4, 2, 5) => "DIT",
+            (3, 3, 4, 2, 6) => "SSBS",
+            (3, 3, 4, 2, 7) => "TCO",
+            (3, 3, 4, 4, 0) => "FPCR",
+            (3, 3, 4, 4, 1) => "FPSR",
+            (3, 3, 9, 13, 0) => "PMCCNTR_EL0",
+            (3, 3, 13, 0, 2) => "TPIDR_EL0",
+            (3, 3, 13, 0, 3) => "TPIDRRO_EL0",
+            (3, 3, 14, 0, 0) => "CNTFRQ_EL0",
+            (3, 3, 14, 0, 1) => "CNTPCT_EL0",
+            (3, 3, 14, 0, 2) => "CNTVCT_EL0",
+            (3, 3, 14, 3, 1) => "CNTV_CTL_EL0",
+            (3, 3, 14, 3, 2) => "CNTV_CVAL_EL0",
+            _ => return format!("S{}_{}_c{}_c{}_{}", op0, op1, crn, crm, op2),
+        };
+
+        name.to_string()
+    }
+
     pub fn format_block(&self, instructions: &[DecodedInstruction]) -> String {
         let mut lines = Vec::new();
 
@@ -104,10 +148,10 @@ impl InstructionFormatter {
             output.push_str(&format!("; Address: 0x{:X}\n", first.address.as_u64()));
         }
         output.push_str(&format!("; Size: {} instructions\n", instructions.len()));
-        output.push_str("\n");
+        output.push('\n');
 
         output.push_str(&self.format_block(instructions));
-        output.push_str("\n");
+        output.push('\n');
 
         output
     }
@@ -214,3 +258,22 @@ impl Default for HexDumper {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_register_names() {
+        // mrs x0, tpidrro_el0
+        assert_eq!(InstructionFormatter::system_register_name(((0xD53BD060u32 >> 5) & 0x7FFF) as u16), "TPIDRRO_EL0");
+        // mrs x8, cntvct_el0
+        assert_eq!(InstructionFormatter::system_register_name(((0xD53BE048u32 >> 5) & 0x7FFF) as u16), "CNTVCT_EL0");
+    }
+
+    #[test]
+    fn test_unknown_system_register_uses_llvm_form() {
+        // mrs x0, s3_7_c15_c2_0
+        assert_eq!(InstructionFormatter::system_register_name(((0xD53FF200u32 >> 5) & 0x7FFF) as u16), "S3_7_c15_c2_0");
+    }
+}
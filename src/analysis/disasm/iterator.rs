@@ -1,7 +1,7 @@
 // Wed Jan 15 2026 - Alex
 
 use crate::memory::{Address, MemoryReader, MemoryError};
-use crate::analysis::disasm::{DecodedInstruction, InstructionCategory, Operand, ShiftType};
+use crate::analysis::disasm::{DecodedInstruction, InstructionCategory, Operand};
 use std::sync::Arc;
 
 pub struct InstructionIterator {
@@ -12,6 +12,7 @@ pub use iterator::InstructionIterator;
 pub use cache::DisassemblyCache;
 
 use crate::memory::{Address, MemoryReader, MemoryError};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 pub struct DisassemblyContext {
@@ -19,6 +20,7 @@ pub struct DisassemblyContext {
     engine: DisassemblyEngine,
     cache: DisassemblyCache,
     config: DisassemblyConfig,
+    coverage: DecoderCoverage,
 }
 
 impl DisassemblyContext {
@@ -28,6 +30,7 @@ impl DisassemblyContext {
             engine: DisassemblyEngine::new(reader),
             cache: DisassemblyCache::new(10000),
             config: DisassemblyConfig::default(),
+            coverage: DecoderCoverage::default(),
         }
     }
 
@@ -36,7 +39,16 @@ impl DisassemblyContext {
             return Ok(cached);
         }
 
+        if self.cache.dedups_content() {
+            let raw = self.reader.read_u32(addr)?;
+            if let Some(instruction) = self.cache.get_word(addr, raw) {
+                self.coverage.record(instruction.raw, instruction.category);
+                return Ok(instruction);
+            }
+        }
+
         let instruction = self.engine.decode(addr)?;
+        self.coverage.record(instruction.raw, instruction.category);
         self.cache.insert(addr, instruction.clone());
         Ok(instruction)
     }
@@ -56,8 +68,15 @@ impl DisassemblyContext {
     }
 
     pub fn disassemble_function(&mut self, entry: Address) -> Result<Vec<DecodedInstruction>, MemoryError> {
+        Ok(self.disassemble_function_listing(entry)?.instructions)
+    }
+
+    /// Linear decode from `entry` until the first RET or the configured
+    /// instruction cap, recording which of the two ended it.
+    pub fn disassemble_function_listing(&mut self, entry: Address) -> Result<FunctionListing, MemoryError> {
         let mut instructions = Vec::new();
         let mut current = entry;
+        let mut ended_on_return = false;
         let max_instructions = self.config.max_function_instructions;
 
         for _ in 0..max_instructions {
@@ -65,15 +84,39 @@ impl DisassemblyContext {
             let is_ret = instr.is_return();
             let size = instr.size;
             instructions.push(instr);
+            current = current + size as u64;
 
             if is_ret {
+                ended_on_return = true;
                 break;
             }
-
-            current = current + size as u64;
         }
 
-        Ok(instructions)
+        Ok(FunctionListing {
+            entry,
+            end: current,
+            ended_on_return,
+            instructions,
+        })
+    }
+
+    pub fn with_config(mut self, config: DisassemblyConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Caps how far `disassemble_function_listing` decodes looking for a RET,
+    /// and how many instructions `ControlFlowGraph::from_context` explores
+    pub fn set_max_function_instructions(&mut self, max: usize) {
+        self.config.max_function_instructions = max;
+    }
+
+    pub fn max_function_instructions(&self) -> usize {
+        self.config.max_function_instructions
+    }
+
+    pub fn reader(&self) -> Arc<dyn MemoryReader> {
+        self.reader.clone()
     }
 
     pub fn iter_from(&mut self, start: Address) -> InstructionIterator {
@@ -83,9 +126,113 @@ impl DisassemblyContext {
     pub fn clear_cache(&mut self) {
         self.cache.clear();
     }
+
+    /// Starts from previously decoded instructions, e.g. a cache loaded
+    /// from disk with `DisassemblyCache::load`.
+    pub fn with_cache(mut self, cache: DisassemblyCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    pub fn cache(&self) -> &DisassemblyCache {
+        &self.cache
+    }
+
+    /// How many of the words decoded so far the engine understood;
+    /// cache hits are not counted again
+    pub fn coverage(&self) -> DecoderCoverage {
+        self.coverage
+    }
+}
+
+/// Unknown-encoding rate above which a region's decoding is unreliable,
+/// most likely because the binary uses an extension the decoder lacks
+pub const LOW_COVERAGE_RATE: f64 = 0.2;
+
+/// Tally of words the decoder did and didn't understand. Zero words are
+/// padding (or UDF #0 traps) rather than code, so they count separately
+/// and never toward the unknown rate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecoderCoverage {
+    pub decoded: usize,
+    pub unknown: usize,
+    pub padding: usize,
+}
+
+impl DecoderCoverage {
+    pub fn record(&mut self, raw: u32, category: InstructionCategory) {
+        if raw == 0 {
+            self.padding += 1;
+        } else if category == InstructionCategory::Unknown {
+            self.unknown += 1;
+        } else {
+            self.decoded += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other: DecoderCoverage) {
+        self.decoded += other.decoded;
+        self.unknown += other.unknown;
+        self.padding += other.padding;
+    }
+
+    /// Words other than padding
+    pub fn instructions(&self) -> usize {
+        self.decoded + self.unknown
+    }
+
+    /// Fraction of non-padding words the decoder could not place
+    pub fn unknown_rate(&self) -> f64 {
+        match self.instructions() {
+            0 => 0.0,
+            n => self.unknown as f64 / n as f64,
+        }
+    }
+
+    pub fn is_low(&self) -> bool {
+        self.unknown_rate() > LOW_COVERAGE_RATE
+    }
 }
 
 #[derive(Debug, Clone)]
+pub struct FunctionListing {
+    pub entry: Address,
+    /// One past the last decoded instruction.
+    pub end: Address,
+    pub ended_on_return: bool,
+    pub instructions: Vec<DecodedInstruction>,
+}
+
+impl FunctionListing {
+    /// Decoding stopped at the instruction cap, so the listing is only a
+    /// prefix of the function
+    pub fn is_truncated(&self) -> bool {
+        !self.ended_on_return
+    }
+
+    /// Instructions the decoder could not place; they are listed, not
+    /// taken as the end of the function
+    pub fn unknown_count(&self) -> usize {
+        self.instructions.iter().filter(|i| i.is_unknown()).count()
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let instructions: Vec<serde_json::Value> = self.instructions.iter()
+            .map(|instr| instr.to_json())
+            .collect();
+
+        serde_json::json!({
+            "entry": format!("0x{:X}", self.entry.as_u64()),
+            "end": format!("0x{:X}", self.end.as_u64()),
+            "ended_on": if self.ended_on_return { "ret" } else { "instruction_cap" },
+            "truncated": self.is_truncated(),
+            "instruction_count": self.instructions.len(),
+            "instructions": instructions,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecodedInstruction {
     pub address: Address,
     pub bytes: Vec<u8>,
@@ -110,6 +257,10 @@ impl DecodedInstruction {
         matches!(self.category, InstructionCategory::Call)
     }
 
+    pub fn is_unknown(&self) -> bool {
+        matches!(self.category, InstructionCategory::Unknown)
+    }
+
     pub fn is_return(&self) -> bool {
         matches!(self.category, InstructionCategory::Return)
     }
@@ -131,16 +282,30 @@ impl DecodedInstruction {
             return None;
         }
 
-        for op in &self.operands {
-            if let Operand::Immediate(imm) = op {
-                return Some(Address::new(*imm as u64));
-            }
-            if let Operand::Address(addr) = op {
-                return Some(*addr);
-            }
-        }
+        // TBZ/TBNZ carry the tested bit as an immediate, so a resolved
+        // address always wins over an immediate operand
+        let address = self.operands.iter().find_map(|op| match op {
+            Operand::Address(addr) => Some(*addr),
+            _ => None,
+        });
+
+        address.or_else(|| self.operands.iter().find_map(|op| match op {
+            Operand::Immediate(imm) => Some(Address::new(*imm as u64)),
+            _ => None,
+        }))
+    }
 
-        None
+    pub fn to_json(&self) -> serde_json::Value {
+        let bytes: Vec<String> = self.bytes.iter().map(|b| format!("{:02X}", b)).collect();
+
+        serde_json::json!({
+            "address": format!("0x{:X}", self.address.as_u64()),
+            "bytes": bytes.join(" "),
+            "mnemonic": self.mnemonic,
+            "operands": self.operand_str,
+            "category": self.category.name(),
+            "branch_target": self.get_branch_target().map(|t| format!("0x{:X}", t.as_u64())),
+        })
     }
 
     pub fn format(&self) -> String {
@@ -152,7 +317,7 @@ impl DecodedInstruction {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Operand {
     Register(u8),
     Immediate(i64),
@@ -160,9 +325,71 @@ pub enum Operand {
     Memory { base: u8, offset: i64, index: Option<u8>, scale: u8 },
     Condition(u8),
     ShiftedReg { reg: u8, shift_type: ShiftType, amount: u8 },
+    /// o0:op1:CRn:CRm:op2 of an MRS/MSR operand; op0 is 2 + o0.
+    SystemRegister(u16),
+    /// Whole SIMD register with its lane arrangement, e.g. V0.4S
+    Vector { reg: u8, arrangement: VectorArrangement },
+    /// One lane of a SIMD register, e.g. V1.S[2]
+    VectorLane { reg: u8, element: VectorElement, index: u8 },
+    /// Consecutive SIMD registers of an LD1/ST1-style list, wrapping at V31
+    VectorList { first: u8, count: u8, arrangement: VectorArrangement },
+}
+
+/// Lane size of a SIMD register or element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VectorElement {
+    B,
+    H,
+    S,
+    D,
+}
+
+impl VectorElement {
+    /// From a 2-bit size field (0 = byte .. 3 = doubleword)
+    pub fn from_size(size: u32) -> Self {
+        match size & 3 {
+            0 => VectorElement::B,
+            1 => VectorElement::H,
+            2 => VectorElement::S,
+            _ => VectorElement::D,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            VectorElement::B => "B",
+            VectorElement::H => "H",
+            VectorElement::S => "S",
+            VectorElement::D => "D",
+        }
+    }
+}
+
+/// Lane count and size of a whole SIMD register operand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorArrangement {
+    pub element: VectorElement,
+    pub lanes: u8,
+}
+
+impl VectorArrangement {
+    /// From the size and Q fields shared by most vector encodings; Q selects
+    /// the full 128-bit register.
+    pub fn from_size_q(size: u32, q: u32) -> Self {
+        let element = VectorElement::from_size(size);
+        let bits = if q == 1 { 128 } else { 64 };
+        let lane_bits = 8 << (size & 3);
+
+        Self { element, lanes: (bits / lane_bits) as u8 }
+    }
+
+    /// Suffix after the register, e.g. `4S` in V0.4S
+    pub fn name(&self) -> String {
+        format!("{}{}", self.lanes, self.element.name())
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ShiftType {
     Lsl,
     Lsr,
@@ -170,7 +397,7 @@ pub enum ShiftType {
     Ror,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InstructionCategory {
     Arithmetic,
     Logic,
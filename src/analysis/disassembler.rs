@@ -1,6 +1,8 @@
 // Tue Jan 13 2026 - Alex
 
 use crate::memory::{Address, MemoryReader, MemoryError};
+use crate::analysis::disasm::DecodedInstruction;
+use crate::utils::arm64::Arm64Utils;
 use std::sync::Arc;
 use std::collections::HashMap;
 
@@ -139,15 +141,20 @@ impl Disassembler {
                 let rm = (raw >> 16) & 0x1F;
                 ("MOV".to_string(), format!("X{}, X{}", rd, rm))
             }
-            0xB4 => {
+            0x34 | 0x35 | 0xB4 | 0xB5 => {
                 let rt = raw & 0x1F;
                 let imm19 = (raw >> 5) & 0x7FFFF;
-                ("CBZ".to_string(), format!("X{}, #{}", rt, imm19))
+                let reg = if raw >> 31 == 1 { "X" } else { "W" };
+                let mnemonic = if op & 1 == 0 { "CBZ" } else { "CBNZ" };
+                (mnemonic.to_string(), format!("{}{}, #{}", reg, rt, imm19))
             }
-            0xB5 => {
+            0x36 | 0x37 | 0xB6 | 0xB7 => {
                 let rt = raw & 0x1F;
-                let imm19 = (raw >> 5) & 0x7FFFF;
-                ("CBNZ".to_string(), format!("X{}, #{}", rt, imm19))
+                let imm14 = (raw >> 5) & 0x3FFF;
+                let bit = Arm64Utils::get_test_bit(raw).unwrap_or(0);
+                let reg = if bit >= 32 { "X" } else { "W" };
+                let mnemonic = if op & 1 == 0 { "TBZ" } else { "TBNZ" };
+                (mnemonic.to_string(), format!("{}{}, #{}, #{}", reg, rt, bit, imm14))
             }
             0xEB => {
                 let rd = raw & 0x1F;
@@ -189,7 +196,7 @@ impl Disassembler {
     }
 
     pub fn is_branch_instruction(&self, instr: &DisassembledInstruction) -> bool {
-        instr.mnemonic.starts_with("B")
+        instr.mnemonic.starts_with("B") || instr.is_conditional_branch()
     }
 
     pub fn is_return_instruction(&self, instr: &DisassembledInstruction) -> bool {
@@ -212,36 +219,17 @@ impl Disassembler {
     }
 
     pub fn get_branch_target(&self, instr: &DisassembledInstruction) -> Option<Address> {
-        if instr.mnemonic == "B" || instr.mnemonic.starts_with("B.") {
-            let imm = if instr.mnemonic == "B" {
-                instr.raw & 0x03FFFFFF
-            } else {
-                (instr.raw >> 5) & 0x7FFFF
-            };
-            let offset = if instr.mnemonic == "B" {
-                if imm & 0x02000000 != 0 {
-                    ((imm | 0xFC000000) as i32) * 4
-                } else {
-                    (imm as i32) * 4
-                }
-            } else {
-                if imm & 0x40000 != 0 {
-                    ((imm | 0xFFF80000) as i32) * 4
-                } else {
-                    (imm as i32) * 4
-                }
-            };
-            let target = (instr.address.as_u64() as i64 + offset as i64) as u64;
-            Some(Address::new(target))
-        } else if instr.mnemonic == "CBZ" || instr.mnemonic == "CBNZ" {
-            let imm19 = (instr.raw >> 5) & 0x7FFFF;
-            let offset = if imm19 & 0x40000 != 0 {
-                ((imm19 | 0xFFF80000) as i32) * 4
+        if instr.mnemonic == "B" {
+            let imm26 = instr.raw & 0x03FFFFFF;
+            let offset = if imm26 & 0x02000000 != 0 {
+                ((imm26 | 0xFC000000) as i32) * 4
             } else {
-                (imm19 as i32) * 4
+                (imm26 as i32) * 4
             };
             let target = (instr.address.as_u64() as i64 + offset as i64) as u64;
             Some(Address::new(target))
+        } else if instr.is_conditional_branch() {
+            Arm64Utils::get_conditional_branch_target(instr.raw, instr.address.as_u64()).map(Address::new)
         } else {
             None
         }
@@ -273,7 +261,7 @@ impl DisassembledInstruction {
     }
 
     pub fn is_branch(&self) -> bool {
-        self.mnemonic.starts_with("B") || self.mnemonic == "RET" || self.mnemonic == "CBZ" || self.mnemonic == "CBNZ"
+        self.mnemonic.starts_with("B") || self.mnemonic == "RET" || self.is_conditional_branch()
     }
 
     pub fn is_call(&self) -> bool {
@@ -285,7 +273,7 @@ impl DisassembledInstruction {
     }
 
     pub fn is_conditional_branch(&self) -> bool {
-        self.mnemonic.starts_with("B.") || self.mnemonic == "CBZ" || self.mnemonic == "CBNZ"
+        self.mnemonic.starts_with("B.") || matches!(self.mnemonic.as_str(), "CBZ" | "CBNZ" | "TBZ" | "TBNZ")
     }
 
     pub fn is_unconditional_branch(&self) -> bool {
@@ -302,15 +290,8 @@ impl DisassembledInstruction {
             };
             let target = (self.address.as_u64() as i64 + offset as i64) as u64;
             Some(Address::new(target))
-        } else if self.mnemonic.starts_with("B.") || self.mnemonic == "CBZ" || self.mnemonic == "CBNZ" {
-            let imm19 = (self.raw >> 5) & 0x7FFFF;
-            let offset = if imm19 & 0x40000 != 0 {
-                ((imm19 | 0xFFF80000) as i32) * 4
-            } else {
-                (imm19 as i32) * 4
-            };
-            let target = (self.address.as_u64() as i64 + offset as i64) as u64;
-            Some(Address::new(target))
+        } else if self.is_conditional_branch() {
+            Arm64Utils::get_conditional_branch_target(self.raw, self.address.as_u64()).map(Address::new)
         } else {
             None
         }
@@ -357,3 +338,17 @@ impl std::fmt::Display for DisassembledInstruction {
         write!(f, "{:016X}: {} {}", self.address.as_u64(), self.mnemonic, self.operands)
     }
 }
+
+impl From<&DecodedInstruction> for DisassembledInstruction {
+    fn from(instr: &DecodedInstruction) -> Self {
+        Self {
+            address: instr.address,
+            bytes: instr.bytes.clone(),
+            mnemonic: instr.mnemonic.clone(),
+            operands: instr.operand_str.clone(),
+            op_str: instr.operand_str.clone(),
+            raw: instr.raw,
+            size: instr.size,
+        }
+    }
+}
@@ -0,0 +1,180 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::memory::{Address, MemoryReader};
+use std::ops::Range;
+use std::sync::Arc;
+
+const CHUNK: usize = 0x10000;
+
+pub const DEFAULT_WINDOW: usize = 0x1000;
+
+/// Bits per byte above which a window is most likely compressed or
+/// encrypted; ARM64 code sits around 5.5-6.5, strings and tables lower
+pub const DEFAULT_THRESHOLD: f64 = 7.2;
+
+/// Shannon entropy of `bytes` in bits per byte, from 0 (one value
+/// repeated) to 8 (every value equally often)
+pub fn shannon_entropy(bytes: &[u8]) -> f64 {
+    if bytes.is_empty() {
+        return 0.0;
+    }
+
+    let mut counts = [0usize; 256];
+    for &b in bytes {
+        counts[b as usize] += 1;
+    }
+
+    let len = bytes.len() as f64;
+    counts.iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| c as f64 / len * (len / c as f64).log2())
+        .sum()
+}
+
+/// One fixed-size window and its entropy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntropyWindow {
+    pub start: Address,
+    pub end: Address,
+    pub entropy: f64,
+}
+
+impl EntropyWindow {
+    pub fn size(&self) -> u64 {
+        self.end.as_u64() - self.start.as_u64()
+    }
+}
+
+/// Adjacent windows that all passed the threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntropySpan {
+    pub start: Address,
+    pub end: Address,
+    pub windows: usize,
+    pub mean: f64,
+    pub peak: f64,
+}
+
+impl EntropySpan {
+    pub fn size(&self) -> u64 {
+        self.end.as_u64() - self.start.as_u64()
+    }
+}
+
+/// Measures entropy over fixed-size windows, reading in large chunks.
+/// High-entropy stretches are where packed or encrypted data hides, and
+/// where XOR-obfuscation detection is worth running.
+pub struct EntropyScanner {
+    reader: Arc<dyn MemoryReader>,
+    window: usize,
+}
+
+impl EntropyScanner {
+    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self { reader, window: DEFAULT_WINDOW }
+    }
+
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Every window in `range`, in address order. The last window may be
+    /// short; unreadable chunks are skipped.
+    pub fn scan(&self, range: Range<Address>) -> Vec<EntropyWindow> {
+        let chunk = (CHUNK / self.window).max(1) * self.window;
+        let mut windows = Vec::new();
+        let mut current = range.start;
+
+        while current < range.end {
+            let len = ((range.end - current) as usize).min(chunk);
+            if len == 0 {
+                break;
+            }
+
+            if let Ok(bytes) = self.reader.read_bytes(current, len) {
+                for (i, window) in bytes.chunks(self.window).enumerate() {
+                    let start = current + (i * self.window) as u64;
+                    windows.push(EntropyWindow {
+                        start,
+                        end: start + window.len() as u64,
+                        entropy: shannon_entropy(window),
+                    });
+                }
+            }
+
+            current = current + len as u64;
+        }
+
+        windows
+    }
+}
+
+/// Runs of contiguous windows at or above `threshold`, in address order
+pub fn high_entropy_spans(windows: &[EntropyWindow], threshold: f64) -> Vec<EntropySpan> {
+    let mut spans: Vec<EntropySpan> = Vec::new();
+    let mut total = 0.0;
+
+    for window in windows.iter().filter(|w| w.entropy >= threshold) {
+        match spans.last_mut() {
+            Some(span) if span.end == window.start => {
+                span.end = window.end;
+                span.windows += 1;
+                span.peak = span.peak.max(window.entropy);
+                total += window.entropy;
+                span.mean = total / span.windows as f64;
+            }
+            _ => {
+                total = window.entropy;
+                spans.push(EntropySpan {
+                    start: window.start,
+                    end: window.end,
+                    windows: 1,
+                    mean: window.entropy,
+                    peak: window.entropy,
+                });
+            }
+        }
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::MachOFixture;
+
+    #[test]
+    fn test_high_entropy_windows_merge_into_spans() {
+        assert_eq!(shannon_entropy(&[0x41; 64]), 0.0);
+        assert_eq!(shannon_entropy(&(0..=255).collect::<Vec<u8>>()), 8.0);
+
+        // xorshift output stands in for encrypted bytes
+        let mut state = 0x2545F4914F6CDD1Du64;
+        let noise: Vec<u8> = (0..0x800).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        }).collect();
+
+        let mut fixture = MachOFixture::new();
+        let plain = fixture.add_data(&[0; 0x400]);
+        let packed = fixture.add_data(&noise);
+        let end = fixture.add_data(&[0x90; 0x400]) + 0x400;
+
+        let reader: Arc<dyn MemoryReader> = Arc::new(fixture.build());
+        let windows = EntropyScanner::new(reader).with_window(0x400).scan(plain..end);
+        assert_eq!(windows.len(), 4);
+        assert!(windows[1].entropy > 7.5 && windows[3].entropy == 0.0);
+
+        let spans = high_entropy_spans(&windows, DEFAULT_THRESHOLD);
+        assert_eq!(spans.len(), 1);
+        assert_eq!((spans[0].start, spans[0].size(), spans[0].windows), (packed, 0x800, 2));
+    }
+}
@@ -4,9 +4,17 @@ use crate::memory::{Address, MemoryReader, MemoryError};
 use crate::analysis::disassembler::{Disassembler, DisassembledInstruction};
 use crate::analysis::block::BasicBlock;
 use crate::analysis::cfg::{ControlFlowGraph, CfgBlock};
+use crate::xref::builder::stub_target;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::collections::{HashMap, HashSet};
 
+/// Most instructions a tail-call thunk runs before its `B`
+const MAX_THUNK_INSNS: usize = 8;
+
+/// Thunks and stubs followed before giving up on reaching a real body
+const MAX_THUNK_HOPS: usize = 4;
+
 pub struct FunctionAnalyzer {
     reader: Arc<dyn MemoryReader>,
     disassembler: Arc<Disassembler>,
@@ -19,6 +27,7 @@ impl FunctionAnalyzer {
 
     pub fn analyze(&self, entry_point: Address) -> Result<AnalyzedFunction, MemoryError> {
         let mut function = AnalyzedFunction::new(entry_point);
+        (function.kind, function.tail_target) = classify_function(self.reader.as_ref(), entry_point);
 
         let instructions = self.disassembler.disassemble_function(entry_point, 0x10000)?;
 
@@ -374,6 +383,81 @@ impl FunctionAnalyzer {
     }
 }
 
+/// What the code at a function entry turns out to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionKind {
+    #[default]
+    Function,
+    /// A few straight-line instructions that tail-call another function
+    /// with `B` instead of returning
+    Thunk,
+    /// An ADRP/LDR/BR or ADRP/ADD/BR jump to another image or island
+    Stub,
+}
+
+impl FunctionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Function => "function",
+            Self::Thunk => "thunk",
+            Self::Stub => "stub",
+        }
+    }
+}
+
+/// Reads the first instructions at `entry`. Straight-line code reaching a
+/// `B` out of itself before any RET, call, conditional branch or frame
+/// setup is a thunk; the returned address is where it (or a stub) jumps.
+pub fn classify_function(reader: &dyn MemoryReader, entry: Address) -> (FunctionKind, Option<Address>) {
+    if let Some(target) = stub_target(reader, entry) {
+        return (FunctionKind::Stub, Some(target));
+    }
+
+    for i in 0..MAX_THUNK_INSNS as u64 {
+        let at = entry + i * 4;
+        let insn = match reader.read_u32(at) {
+            Ok(insn) => insn,
+            Err(_) => break,
+        };
+
+        // B imm26: a tail call unless it loops back into what we just read
+        if (insn & 0xFC000000) == 0x14000000 {
+            let offset = (((insn & 0x03FFFFFF) << 6) as i32 >> 4) as i64;
+            let target = Address::new(at.as_u64().wrapping_add(offset as u64));
+            if target >= entry && target <= at {
+                break;
+            }
+            return (FunctionKind::Thunk, Some(target));
+        }
+
+        let ret = (insn & 0xFFFFFC1F) == 0xD65F0000;
+        let call = (insn & 0xFC000000) == 0x94000000 || (insn & 0xFFFFFC1F) == 0xD63F0000;
+        let conditional = (insn & 0xFF000010) == 0x54000000 || (insn & 0x7C000000) == 0x34000000;
+        let frame = (insn & 0x7E407FFF) == 0x28007BFD;
+        if ret || call || conditional || frame {
+            break;
+        }
+    }
+
+    (FunctionKind::Function, None)
+}
+
+/// Follows thunks and stubs from `entry` to the function doing the work;
+/// `entry` itself when it is one already or the chain loops
+pub fn resolve_thunks(reader: &dyn MemoryReader, entry: Address) -> Address {
+    let mut current = entry;
+
+    for _ in 0..MAX_THUNK_HOPS {
+        match classify_function(reader, current) {
+            (FunctionKind::Thunk | FunctionKind::Stub, Some(target)) if target != entry => current = target,
+            _ => break,
+        }
+    }
+
+    current
+}
+
 #[derive(Debug, Clone)]
 pub struct AnalyzedFunction {
     pub entry_point: Address,
@@ -390,6 +474,9 @@ pub struct AnalyzedFunction {
     pub stack_accesses: Vec<StackAccess>,
     pub register_definitions: HashMap<String, Vec<Address>>,
     pub register_uses: HashMap<String, Vec<Address>>,
+    pub kind: FunctionKind,
+    /// Where a thunk or stub jumps
+    pub tail_target: Option<Address>,
 }
 
 impl AnalyzedFunction {
@@ -409,6 +496,8 @@ impl AnalyzedFunction {
             stack_accesses: Vec::new(),
             register_definitions: HashMap::new(),
             register_uses: HashMap::new(),
+            kind: FunctionKind::Function,
+            tail_target: None,
         }
     }
 
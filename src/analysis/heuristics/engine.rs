@@ -90,7 +90,8 @@ impl HeuristicsEngine {
 
         let inst0 = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
 
-        if (inst0 & 0xFFC003E0) == 0xA9800000 {
+        // stp xN, xM, [sp, #-imm]!
+        if (inst0 & 0xFFC003E0) == 0xA98003E0 {
             return Ok(true);
         }
 
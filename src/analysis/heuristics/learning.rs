@@ -3,12 +3,23 @@
 use crate::memory::{Address, MemoryReader, MemoryError};
 use crate::analysis::heuristics::patterns::{HeuristicPattern, InstructionPattern, PatternType};
 use crate::analysis::heuristics::scoring::ScoringWeights;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// Pseudo-samples pulling a finder's weight toward 1.0, so one or two
+/// results can't swing it far
+const WEIGHT_PRIOR: f64 = 2.0;
+const MIN_WEIGHT: f64 = 0.5;
+const MAX_WEIGHT: f64 = 1.5;
+
+/// How far above a sample a threshold sits to drop it
+const DROP_MARGIN: f64 = 0.001;
 
 pub struct PatternLearner {
     reader: Arc<dyn MemoryReader>,
     samples: Vec<LearningSample>,
+    scored: Vec<ScoredSample>,
     learned_patterns: Vec<LearnedPattern>,
     config: LearningConfig,
 }
@@ -18,11 +29,42 @@ impl PatternLearner {
         Self {
             reader,
             samples: Vec::new(),
+            scored: Vec::new(),
             learned_patterns: Vec::new(),
             config: LearningConfig::default(),
         }
     }
 
+    /// Record a finder result that was checked against known-good offsets
+    pub fn add_scored_sample(&mut self, finder: &str, confidence: f64, correct: bool) {
+        self.scored.push(ScoredSample {
+            finder: finder.to_string(),
+            confidence,
+            correct,
+        });
+    }
+
+    /// Per-finder weight and threshold that best separate the correct
+    /// scored samples from the wrong ones. The weight scales a finder's
+    /// confidence toward how often it was actually right; the threshold is
+    /// then the cut on weighted confidence that agrees with the truth most
+    /// often, preferring `default_threshold` on ties.
+    pub fn tune_thresholds(&self, default_threshold: f64) -> TunedProfile {
+        let mut by_finder: BTreeMap<&str, Vec<&ScoredSample>> = BTreeMap::new();
+        for sample in &self.scored {
+            by_finder.entry(sample.finder.as_str()).or_default().push(sample);
+        }
+
+        let finders = by_finder.into_iter()
+            .map(|(finder, samples)| (finder.to_string(), tune_finder(&samples, default_threshold)))
+            .collect();
+
+        TunedProfile {
+            finders,
+            ..TunedProfile::default()
+        }
+    }
+
     pub fn add_positive_sample(&mut self, addr: Address, label: &str) -> Result<(), MemoryError> {
         let data = self.reader.read_bytes(addr, self.config.sample_size)?;
         self.samples.push(LearningSample {
@@ -239,6 +281,7 @@ impl PatternLearner {
 
     pub fn clear_samples(&mut self) {
         self.samples.clear();
+        self.scored.clear();
     }
 
     pub fn clear_patterns(&mut self) {
@@ -246,7 +289,7 @@ impl PatternLearner {
     }
 
     pub fn sample_count(&self) -> usize {
-        self.samples.len()
+        self.samples.len() + self.scored.len()
     }
 
     pub fn pattern_count(&self) -> usize {
@@ -266,6 +309,91 @@ pub struct LearningSample {
     pub is_positive: bool,
 }
 
+fn tune_finder(samples: &[&ScoredSample], default_threshold: f64) -> FinderTuning {
+    let n = samples.len() as f64;
+    let correct = samples.iter().filter(|s| s.correct).count() as f64;
+    let mean = samples.iter().map(|s| s.confidence).sum::<f64>() / n;
+
+    let weight = if mean > 0.0 {
+        let calibrated = (correct + WEIGHT_PRIOR * mean) / (n + WEIGHT_PRIOR);
+        (calibrated / mean).clamp(MIN_WEIGHT, MAX_WEIGHT)
+    } else {
+        1.0
+    };
+
+    let weighted: Vec<(f64, bool)> = samples.iter()
+        .map(|s| ((s.confidence * weight).min(1.0), s.correct))
+        .collect();
+    let agreement = |threshold: f64| weighted.iter()
+        .filter(|&&(confidence, correct)| (confidence >= threshold) == correct)
+        .count();
+
+    // Cuts at each sample, or just above it to drop it
+    let mut candidates = vec![default_threshold];
+    for &(confidence, _) in &weighted {
+        candidates.push(confidence);
+        candidates.push(confidence + DROP_MARGIN);
+    }
+
+    let threshold = candidates.into_iter()
+        .max_by(|&a, &b| {
+            agreement(a).cmp(&agreement(b))
+                .then_with(|| (b - default_threshold).abs().total_cmp(&(a - default_threshold).abs()))
+        })
+        .unwrap_or(default_threshold);
+
+    FinderTuning {
+        weight,
+        threshold,
+        samples: samples.len(),
+        agreement: agreement(threshold) as f64 / n,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScoredSample {
+    pub finder: String,
+    pub confidence: f64,
+    pub correct: bool,
+}
+
+/// How `scan --profile` treats one finder's results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FinderTuning {
+    /// Multiplies the finder's confidence (capped at 1.0)
+    pub weight: f64,
+    /// Weighted confidence a result needs to be kept
+    pub threshold: f64,
+    /// Results checked against the truth while tuning
+    pub samples: usize,
+    /// Share of those samples the threshold kept if right and dropped if wrong
+    pub agreement: f64,
+}
+
+/// Tuned weights and thresholds, keyed by finder. Finders missing from the
+/// profile keep their own confidence and the scan's `--min-confidence`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TunedProfile {
+    #[serde(default)]
+    pub trained_on: Option<String>,
+    #[serde(default)]
+    pub generated_at: String,
+    pub finders: BTreeMap<String, FinderTuning>,
+}
+
+impl TunedProfile {
+    pub fn weighted_confidence(&self, finder: &str, confidence: f64) -> f64 {
+        match self.finders.get(finder) {
+            Some(tuning) => (confidence * tuning.weight).min(1.0),
+            None => confidence,
+        }
+    }
+
+    pub fn threshold(&self, finder: &str, default: f64) -> f64 {
+        self.finders.get(finder).map_or(default, |tuning| tuning.threshold)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LearnedPattern {
     pub name: String,
@@ -431,3 +559,37 @@ pub struct LearningSnapshot {
     pub sample_count: usize,
     pub pattern_count: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::MachOFixture;
+
+    #[test]
+    fn test_tuning_follows_the_truth() {
+        let mut learner = PatternLearner::new(Arc::new(MachOFixture::new().build()));
+
+        // Right below the default cut, wrong above it, and already in line
+        learner.add_scored_sample("shy", 0.6, true);
+        learner.add_scored_sample("shy", 0.65, true);
+        learner.add_scored_sample("eager", 0.95, false);
+        learner.add_scored_sample("eager", 0.9, false);
+        learner.add_scored_sample("agrees", 0.9, true);
+        learner.add_scored_sample("agrees", 0.3, false);
+
+        let profile = learner.tune_thresholds(0.7);
+
+        let shy = &profile.finders["shy"];
+        assert!(shy.weight > 1.0);
+        assert!(profile.weighted_confidence("shy", 0.6) >= shy.threshold);
+        assert_eq!(shy.agreement, 1.0);
+
+        let eager = &profile.finders["eager"];
+        assert!(eager.weight < 1.0);
+        assert!(profile.weighted_confidence("eager", 0.95) < eager.threshold);
+
+        assert_eq!(profile.finders["agrees"].threshold, 0.7);
+        assert_eq!(profile.threshold("unknown", 0.7), 0.7);
+        assert_eq!(profile.weighted_confidence("unknown", 0.4), 0.4);
+    }
+}
@@ -11,7 +11,7 @@ pub use engine::HeuristicsEngine;
 pub use patterns::HeuristicPattern;
 pub use rules::HeuristicRule;
 pub use scoring::HeuristicScorer;
-pub use learning::PatternLearner;
+pub use learning::{FinderTuning, PatternLearner, TunedProfile};
 pub use detector::OffsetDetector;
 
 use crate::memory::{Address, MemoryError};
@@ -1,6 +1,7 @@
 // Tue Jan 13 2026 - Alex
 
 pub mod disassembler;
+pub mod disasm;
 pub mod block;
 pub mod cfg;
 pub mod function;
@@ -10,17 +11,21 @@ pub mod heuristics;
 pub mod string;
 pub mod signature;
 pub mod cross_reference;
+pub mod padding;
+pub mod entropy;
 
 pub use disassembler::{Disassembler, DisassembledInstruction};
 pub use block::BasicBlock;
 pub use cfg::ControlFlowGraph;
-pub use function::{FunctionAnalyzer, AnalyzedFunction};
+pub use function::{FunctionAnalyzer, AnalyzedFunction, FunctionKind, classify_function, resolve_thunks};
 pub use dataflow::DataFlowAnalyzer;
 pub use pattern::PatternRecognizer;
 pub use heuristics::HeuristicAnalyzer;
 pub use string::StringAnalyzer;
 pub use signature::SignatureAnalyzer;
 pub use cross_reference::CrossReferenceAnalyzer;
+pub use padding::{CodeMap, Gap, PaddingScanner};
+pub use entropy::{EntropyScanner, EntropySpan, EntropyWindow};
 
 use crate::memory::{MemoryReader, MemoryError, Address};
 use std::sync::Arc;
@@ -0,0 +1,303 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::memory::{Address, MemoryReader, MemoryRegion};
+use crate::utils::arm64::Arm64Utils;
+use std::ops::Range;
+use std::sync::Arc;
+
+const CHUNK: usize = 0x10000;
+
+/// NOP runs at least this long are padding wherever they sit. Shorter runs
+/// only count after a RET or BRK; inside a function they align loop heads.
+const MIN_NOP_RUN: usize = 4;
+
+/// Zero words (UDF #0) form padding on their own once there are this many;
+/// a lone one inside a function is an unknown instruction or a trap, not
+/// the end of the function
+const MIN_ZERO_RUN: usize = 2;
+
+/// Most bytes `skip_padding` steps over
+const MAX_SKIP: usize = 0x1000;
+
+const BRK_MASK: u32 = 0xFFE0001F;
+const BRK: u32 = 0xD4200000;
+const B_MASK: u32 = 0xFC000000;
+const B: u32 = 0x14000000;
+const BR_MASK: u32 = 0xFFFFFC1F;
+const BR: u32 = 0xD61F0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddingKind {
+    Nop,
+    Zero,
+    Mixed,
+}
+
+impl PaddingKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PaddingKind::Nop => "nop",
+            PaddingKind::Zero => "zero",
+            PaddingKind::Mixed => "mixed",
+        }
+    }
+}
+
+/// A run of padding between two stretches of code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    pub start: Address,
+    pub end: Address,
+    pub kind: PaddingKind,
+}
+
+impl Gap {
+    pub fn size(&self) -> u64 {
+        self.end.as_u64() - self.start.as_u64()
+    }
+
+    pub fn contains(&self, addr: Address) -> bool {
+        addr >= self.start && addr < self.end
+    }
+}
+
+/// Padding found by `PaddingScanner`, sorted by address. Whatever lies
+/// between two gaps is code: one function, or several placed back to back.
+#[derive(Debug, Clone, Default)]
+pub struct CodeMap {
+    gaps: Vec<Gap>,
+}
+
+impl CodeMap {
+    pub fn from_gaps(mut gaps: Vec<Gap>) -> Self {
+        gaps.sort_by_key(|g| g.start);
+        Self { gaps }
+    }
+
+    pub fn gaps(&self) -> &[Gap] {
+        &self.gaps
+    }
+
+    pub fn padding_bytes(&self) -> u64 {
+        self.gaps.iter().map(|g| g.size()).sum()
+    }
+
+    pub fn gap_at(&self, addr: Address) -> Option<&Gap> {
+        let i = self.gaps.partition_point(|g| g.end <= addr);
+        self.gaps.get(i).filter(|g| g.contains(addr))
+    }
+
+    pub fn is_padding(&self, addr: Address) -> bool {
+        self.gap_at(addr).is_some()
+    }
+
+    /// Stretches of `range` not covered by a gap
+    pub fn code_spans(&self, range: Range<Address>) -> Vec<Range<Address>> {
+        let mut spans = Vec::new();
+        let mut current = range.start;
+
+        for gap in self.gaps.iter().filter(|g| g.end > range.start && g.start < range.end) {
+            if gap.start > current {
+                spans.push(current..gap.start);
+            }
+            current = current.max(gap.end);
+        }
+        if current < range.end {
+            spans.push(current..range.end);
+        }
+
+        spans
+    }
+
+    /// First instruction after the gap before `addr`: where the code
+    /// holding `addr` begins, or `floor` if no gap comes first
+    pub fn span_start(&self, addr: Address, floor: Address) -> Address {
+        let i = self.gaps.partition_point(|g| g.end <= addr);
+        match i.checked_sub(1).map(|j| self.gaps[j].end) {
+            Some(end) if end > floor => end,
+            _ => floor,
+        }
+    }
+
+    /// `range` with padding trimmed off both ends, or `None` if nothing
+    /// but padding is left
+    pub fn trim(&self, range: Range<Address>) -> Option<Range<Address>> {
+        let spans = self.code_spans(range);
+        Some(spans.first()?.start..spans.last()?.end)
+    }
+}
+
+/// Finds the padding between functions: word-aligned runs of zero words
+/// and NOPs. A run counts when it is long, follows a RET, BRK or
+/// unconditional branch, or touches either end of the scanned range.
+pub struct PaddingScanner {
+    reader: Arc<dyn MemoryReader>,
+}
+
+impl PaddingScanner {
+    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self { reader }
+    }
+
+    /// Padding in every executable region
+    pub fn scan_regions(&self, regions: &[MemoryRegion]) -> CodeMap {
+        let mut gaps = Vec::new();
+
+        for region in regions.iter().filter(|r| r.is_executable()) {
+            gaps.extend(self.scan(region.start()..region.end()).gaps);
+        }
+
+        CodeMap::from_gaps(gaps)
+    }
+
+    pub fn scan(&self, range: Range<Address>) -> CodeMap {
+        let mut gaps = Vec::new();
+        let mut run: Option<Run> = None;
+        let mut after_terminator = true;
+        let mut current = range.start;
+
+        while current < range.end {
+            let len = ((range.end - current) as usize).min(CHUNK) & !3;
+            if len == 0 {
+                break;
+            }
+
+            let bytes = match self.reader.read_bytes(current, len) {
+                Ok(bytes) => bytes,
+                Err(_) => {
+                    // Unreadable bytes end any run without making it padding
+                    run = None;
+                    after_terminator = true;
+                    current = current + len as u64;
+                    continue;
+                }
+            };
+
+            for (i, word) in bytes.chunks_exact(4).enumerate() {
+                let insn = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+                let at = current + i as u64 * 4;
+
+                if insn == 0 || Arm64Utils::is_nop(insn) {
+                    let r = run.get_or_insert(Run { start: at, words: 0, zeros: 0, after_terminator });
+                    r.words += 1;
+                    r.zeros += (insn == 0) as usize;
+                    continue;
+                }
+
+                if let Some(r) = run.take() {
+                    if let Some(gap) = r.gap(at, false) {
+                        gaps.push(gap);
+                    }
+                }
+                after_terminator = is_terminator(insn);
+            }
+
+            current = current + len as u64;
+        }
+
+        if let Some(gap) = run.and_then(|r| r.gap(current, true)) {
+            gaps.push(gap);
+        }
+
+        CodeMap { gaps }
+    }
+}
+
+struct Run {
+    start: Address,
+    words: usize,
+    zeros: usize,
+    after_terminator: bool,
+}
+
+impl Run {
+    fn gap(&self, end: Address, at_range_end: bool) -> Option<Gap> {
+        let long = self.words >= MIN_NOP_RUN || self.zeros >= MIN_ZERO_RUN;
+        let padding = long || self.after_terminator || at_range_end;
+        if !padding {
+            return None;
+        }
+
+        let kind = match self.zeros {
+            0 => PaddingKind::Nop,
+            z if z == self.words => PaddingKind::Zero,
+            _ => PaddingKind::Mixed,
+        };
+
+        Some(Gap { start: self.start, end, kind })
+    }
+}
+
+/// `addr` moved forward past any NOP or zero words, for function-start
+/// walks that stop at a RET: the next function begins after the padding
+pub fn skip_padding(reader: &dyn MemoryReader, addr: Address) -> Address {
+    let mut current = addr;
+
+    while current - addr < MAX_SKIP as i64 {
+        match reader.read_u32(current) {
+            Ok(insn) if insn == 0 || Arm64Utils::is_nop(insn) => current = current + 4,
+            _ => break,
+        }
+    }
+
+    current
+}
+
+/// Whether the word at `addr` is zero padding: a zero word next to another
+/// zero or NOP, or right after a RET, BRK or unconditional branch. A lone
+/// zero inside a function is an unknown instruction, not a boundary.
+pub fn is_zero_padding(reader: &dyn MemoryReader, addr: Address) -> bool {
+    if reader.read_u32(addr).ok() != Some(0) {
+        return false;
+    }
+
+    [addr - 4, addr + 4].iter().any(|&at| match reader.read_u32(at) {
+        Ok(insn) => insn == 0 || Arm64Utils::is_nop(insn),
+        Err(_) => true,
+    }) || reader.read_u32(addr - 4).is_ok_and(is_terminator)
+}
+
+fn is_terminator(insn: u32) -> bool {
+    Arm64Utils::is_return(insn) || (insn & BRK_MASK) == BRK || (insn & B_MASK) == B || (insn & BR_MASK) == BR
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::MachOFixture;
+
+    const NOP: u32 = 0xD503201F;
+    const RET: u32 = 0xD65F03C0;
+    const STP: u32 = 0xA9BF7BFD;
+    const ADD: u32 = 0x91000400;
+
+    #[test]
+    fn test_padding_separates_functions() {
+        let mut fixture = MachOFixture::new();
+        let first = fixture.add_code(&[STP, ADD, RET]);
+        // Padding after a RET, then a loop-alignment NOP inside the second function
+        let padding = fixture.add_code(&[NOP, NOP]);
+        let second = fixture.add_code(&[ADD, NOP, ADD, RET]);
+        let zeros = fixture.add_code(&[0, 0, 0]);
+        // A lone zero word inside a function is not padding
+        let third = fixture.add_code(&[ADD, 0, ADD, RET]);
+        let end = fixture.next_code_address();
+
+        let reader: Arc<dyn MemoryReader> = Arc::new(fixture.build());
+        let map = PaddingScanner::new(reader.clone()).scan(first..end);
+
+        let gaps: Vec<(Address, u64, &str)> = map.gaps().iter().map(|g| (g.start, g.size(), g.kind.name())).collect();
+        assert_eq!(gaps, vec![(padding, 8, "nop"), (zeros, 12, "zero")]);
+
+        assert!(map.is_padding(padding + 4));
+        assert!(!map.is_padding(second + 4));
+        assert!(!map.is_padding(third + 4) && !is_zero_padding(reader.as_ref(), third + 4));
+        assert!(is_zero_padding(reader.as_ref(), zeros));
+        assert_eq!(map.code_spans(first..end), vec![first..padding, second..zeros, third..end]);
+        assert_eq!(map.span_start(second + 8, first), second);
+        assert_eq!(map.trim(padding..third + 4), Some(second..third + 4));
+        assert_eq!(map.trim(zeros..third), None);
+
+        assert_eq!(skip_padding(reader.as_ref(), padding), second);
+    }
+}
@@ -3,6 +3,10 @@
 use crate::memory::{Address, MemoryReader, MemoryError, MemoryRegion};
 use std::sync::Arc;
 use std::collections::HashMap;
+use std::ops::Range;
+
+/// Bytes searched per read by `find_c_strings`
+const CHUNK: usize = 0x10000;
 
 pub type FoundString = StringInfo;
 
@@ -65,28 +69,28 @@ impl StringAnalyzer {
         let mut i = 0;
 
         while i < data.len() {
-            if self.is_printable_start(data[i]) {
-                let (string, len) = self.extract_string(&data[i..]);
-
-                if len >= self.config.min_length && len <= self.config.max_length {
-                    let string_type = self.classify_string(&string);
-                    let relevance = self.calculate_relevance(&string, string_type);
-
-                    if relevance >= self.config.min_relevance {
-                        strings.push(StringInfo {
-                            address: start + i as u64,
-                            content: string,
-                            length: len,
-                            string_type,
-                            relevance,
-                            encoding: StringEncoding::Ascii,
-                            references: Vec::new(),
-                        });
-                    }
-
-                    i += len;
-                    continue;
+            let extracted = self.extract_at(&data[i..])
+                .or_else(|| if i % 2 == 0 { self.extract_utf16le(&data[i..]) } else { None })
+                .filter(|(_, len, _)| *len > 0);
+
+            if let Some((string, len, encoding)) = extracted {
+                let string_type = self.classify_string(&string);
+                let relevance = self.calculate_relevance(&string, string_type);
+
+                if relevance >= self.config.min_relevance {
+                    strings.push(StringInfo {
+                        address: start + i as u64,
+                        content: string,
+                        length: len,
+                        string_type,
+                        relevance,
+                        encoding,
+                        references: Vec::new(),
+                    });
                 }
+
+                i += len;
+                continue;
             }
             i += 1;
         }
@@ -94,19 +98,66 @@ impl StringAnalyzer {
         Ok(strings)
     }
 
+    /// Narrow (ASCII or UTF-8) string starting at `data[0]`, as
+    /// (content, byte length, encoding), if it meets the length limits.
+    fn extract_at(&self, data: &[u8]) -> Option<(String, usize, StringEncoding)> {
+        if !self.config.scan_narrow || !self.is_printable_start(data[0]) {
+            return None;
+        }
+
+        let (string, len) = self.extract_string(data);
+        let chars = string.chars().count();
+
+        if chars < self.config.min_length || chars > self.config.max_length {
+            return None;
+        }
+
+        let encoding = if string.is_ascii() { StringEncoding::Ascii } else { StringEncoding::Utf8 };
+        Some((string, len, encoding))
+    }
+
+    /// UTF-16LE string starting at `data[0]`. Only printable ASCII and the
+    /// alphabetic scripts below U+0800 are accepted, which keeps arbitrary
+    /// data from decoding as CJK.
+    fn extract_utf16le(&self, data: &[u8]) -> Option<(String, usize, StringEncoding)> {
+        if !self.config.scan_utf16 {
+            return None;
+        }
+
+        let mut units = Vec::new();
+
+        for pair in data.chunks_exact(2) {
+            let unit = u16::from_le_bytes([pair[0], pair[1]]);
+
+            let valid = (unit < 0x80 && self.is_valid_string_char(unit as u8)) || (0xA0..0x800).contains(&unit);
+            if !valid || units.len() >= self.config.max_length {
+                break;
+            }
+
+            units.push(unit);
+        }
+
+        if units.len() < self.config.min_length {
+            return None;
+        }
+
+        let string = String::from_utf16(&units).ok()?;
+        Some((string, units.len() * 2, StringEncoding::Utf16Le))
+    }
+
     fn is_printable_start(&self, byte: u8) -> bool {
-        byte >= 0x20 && byte < 0x7F
+        (0x20..0x7F).contains(&byte) || (0xC2..=0xF4).contains(&byte)
     }
 
+    /// Bytes up to the first NUL or control character, trimmed to the
+    /// longest valid UTF-8 prefix.
     fn extract_string(&self, data: &[u8]) -> (String, usize) {
-        let mut chars = Vec::new();
         let mut len = 0;
 
         for &byte in data {
-            if byte == 0 || !self.is_valid_string_char(byte) {
+            if byte == 0 || !(self.is_valid_string_char(byte) || byte >= 0x80) {
                 break;
             }
-            chars.push(byte);
             len += 1;
 
             if len >= self.config.max_length {
@@ -114,7 +165,12 @@ impl StringAnalyzer {
             }
         }
 
-        (String::from_utf8_lossy(&chars).to_string(), len)
+        let valid = match std::str::from_utf8(&data[..len]) {
+            Ok(_) => len,
+            Err(e) => e.valid_up_to(),
+        };
+
+        (String::from_utf8_lossy(&data[..valid]).to_string(), valid)
     }
 
     fn is_valid_string_char(&self, byte: u8) -> bool {
@@ -413,6 +469,17 @@ pub enum StringEncoding {
     Utf16Be,
 }
 
+impl StringEncoding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StringEncoding::Ascii => "ascii",
+            StringEncoding::Utf8 => "utf8",
+            StringEncoding::Utf16Le => "utf16le",
+            StringEncoding::Utf16Be => "utf16be",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StringReference {
     pub from_address: Address,
@@ -445,9 +512,33 @@ pub struct StringAnalyzerConfig {
     pub max_length: usize,
     pub min_relevance: f64,
     pub max_region_size: usize,
+    /// Scan for ASCII and UTF-8 strings
+    pub scan_narrow: bool,
+    /// Scan for 2-byte aligned UTF-16LE strings
     pub scan_utf16: bool,
 }
 
+impl StringAnalyzerConfig {
+    /// Minimum length in characters, whatever the encoding. An empty string
+    /// is never reported, so 0 means 1.
+    pub fn with_min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length.max(1);
+        self
+    }
+
+    /// Relevance a string needs to be reported; 0 keeps every string
+    pub fn with_min_relevance(mut self, min_relevance: f64) -> Self {
+        self.min_relevance = min_relevance;
+        self
+    }
+
+    pub fn with_encodings(mut self, narrow: bool, utf16: bool) -> Self {
+        self.scan_narrow = narrow;
+        self.scan_utf16 = utf16;
+        self
+    }
+}
+
 impl Default for StringAnalyzerConfig {
     fn default() -> Self {
         Self {
@@ -455,6 +546,7 @@ impl Default for StringAnalyzerConfig {
             max_length: 512,
             min_relevance: 0.3,
             max_region_size: 0x10000000,
+            scan_narrow: true,
             scan_utf16: false,
         }
     }
@@ -524,3 +616,34 @@ impl StringSearcher {
         self.strings.len()
     }
 }
+
+/// Starts of the C string `needle` in `range`: a NUL after it and a NUL
+/// (or the range start) before it, so "DataModel" skips "FakeDataModel"
+pub fn find_c_strings(reader: &dyn MemoryReader, needle: &str, range: Range<Address>) -> Vec<Address> {
+    let mut pattern = needle.as_bytes().to_vec();
+    pattern.push(0);
+
+    let mut found = Vec::new();
+    let mut current = range.start;
+
+    while current < range.end {
+        // Read one byte before the chunk for the leading NUL, and far
+        // enough past it for a match that straddles the boundary
+        let from = if current > range.start { current - 1 } else { current };
+        let len = ((range.end - from) as usize).min(CHUNK + pattern.len());
+
+        if let Ok(bytes) = reader.read_bytes(from, len) {
+            let lead = (current - from) as usize;
+            for (pos, window) in bytes.windows(pattern.len()).enumerate().skip(lead).take(CHUNK) {
+                let at = from + pos as u64;
+                if window == pattern.as_slice() && (at == range.start || bytes[pos - 1] == 0) {
+                    found.push(at);
+                }
+            }
+        }
+
+        current = current + CHUNK as u64;
+    }
+
+    found
+}
@@ -1,9 +1,17 @@
 // Tue Jan 13 2026 - Alex
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 use serde::{Deserialize, Serialize};
 
+/// Weights the built-in finders currently score with, see `set_confidence`
+static CONFIDENCE: RwLock<ConfidenceConfig> = RwLock::new(ConfidenceConfig::DEFAULT);
+
+/// Highest value a confidence weight may take
+pub const MAX_WEIGHT: f64 = 2.0;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub target_process: Option<String>,
     pub target_binary: Option<PathBuf>,
@@ -28,6 +36,80 @@ pub struct Config {
     pub parallel_discovery: bool,
     pub cache_symbols: bool,
     pub cache_patterns: bool,
+    pub confidence: ConfidenceConfig,
+}
+
+/// How built-in finders score what they find. Each weight scales the
+/// finder's own score for one kind of evidence, so 1.0 keeps it as shipped;
+/// scaled scores are capped at 1.0.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfidenceConfig {
+    /// Results located through a reference to an anchor string
+    pub string_xref: f64,
+    /// Results located by matching a function prologue pattern
+    pub prologue: f64,
+    /// Bonus for each further function referencing the same global
+    pub xref_count: f64,
+    /// Threshold `scan` drops results below when `--min-confidence` is not given
+    pub minimum: f64,
+}
+
+impl ConfidenceConfig {
+    pub const DEFAULT: Self = Self {
+        string_xref: 1.0,
+        prologue: 1.0,
+        xref_count: 1.0,
+        minimum: 0.7,
+    };
+
+    /// Weight for results found by `method`
+    pub fn method_weight(&self, method: &str) -> f64 {
+        match method {
+            "string_xref" => self.string_xref,
+            "pattern" => self.prologue,
+            _ => 1.0,
+        }
+    }
+
+    /// `confidence` of a `method` result, weighted and capped at 1.0
+    pub fn weigh(&self, method: &str, confidence: f64) -> f64 {
+        (confidence * self.method_weight(method)).min(1.0)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let weights = [
+            ("string_xref", self.string_xref),
+            ("prologue", self.prologue),
+            ("xref_count", self.xref_count),
+        ];
+
+        for (name, weight) in weights {
+            if !(0.0..=MAX_WEIGHT).contains(&weight) {
+                return Err(format!("confidence.{} must be between 0.0 and {:.1}, got {}", name, MAX_WEIGHT, weight));
+            }
+        }
+        if !(0.0..=1.0).contains(&self.minimum) {
+            return Err(format!("confidence.minimum must be between 0.0 and 1.0, got {}", self.minimum));
+        }
+        Ok(())
+    }
+}
+
+impl Default for ConfidenceConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Weights the built-in finders score with
+pub fn confidence() -> ConfidenceConfig {
+    *CONFIDENCE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Replaces the weights for every later finder run in this process
+pub fn set_confidence(weights: ConfidenceConfig) {
+    *CONFIDENCE.write().unwrap_or_else(|e| e.into_inner()) = weights;
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,6 +146,7 @@ impl Default for Config {
             parallel_discovery: true,
             cache_symbols: true,
             cache_patterns: true,
+            confidence: ConfidenceConfig::default(),
         }
     }
 }
@@ -73,6 +156,16 @@ impl Config {
         Self::default()
     }
 
+    /// Parses a config file's contents, TOML if `path` ends in `.toml` and
+    /// JSON otherwise. Keys the file leaves out keep their defaults.
+    pub fn parse(text: &str, path: &Path) -> Result<Self, String> {
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml")) {
+            toml::from_str(text).map_err(|e| e.to_string())
+        } else {
+            serde_json::from_str(text).map_err(|e| e.to_string())
+        }
+    }
+
     pub fn with_target_process(mut self, process: String) -> Self {
         self.target_process = Some(process);
         self
@@ -101,6 +194,30 @@ impl Config {
         if self.pattern_confidence_threshold < 0.0 || self.pattern_confidence_threshold > 1.0 {
             return Err("pattern_confidence_threshold must be between 0.0 and 1.0".to_string());
         }
-        Ok(())
+        self.confidence.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confidence_section_loads_over_defaults() {
+        let toml = "[confidence]\nstring_xref = 0.5\nminimum = 0.6\n";
+        let config = Config::parse(toml, Path::new("weights.toml")).unwrap();
+        assert_eq!(config.confidence, ConfidenceConfig { string_xref: 0.5, minimum: 0.6, ..ConfidenceConfig::DEFAULT });
+        assert_eq!(config.output_file, PathBuf::from("offsets.json"));
+
+        let json = r#"{"confidence": {"prologue": 1.5}}"#;
+        let config = Config::parse(json, Path::new("weights.json")).unwrap();
+        assert_eq!(config.confidence.prologue, 1.5);
+        assert_eq!(config.confidence.weigh("pattern", 0.9), 1.0);
+        assert_eq!(config.confidence.weigh("heuristic", 0.7), 0.7);
+
+        assert!(Config::parse("", Path::new("empty.json")).is_err());
+        assert!(ConfidenceConfig { xref_count: -0.1, ..ConfidenceConfig::DEFAULT }.validate().is_err());
+        assert!(ConfidenceConfig { minimum: 1.2, ..ConfidenceConfig::DEFAULT }.validate().is_err());
+        assert!(ConfidenceConfig::DEFAULT.validate().is_ok());
     }
 }
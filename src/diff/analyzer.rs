@@ -352,6 +352,7 @@ impl fmt::Display for OffsetStability {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::memory::Address;
 
     #[test]
     fn test_diff_analyzer() {
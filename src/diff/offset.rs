@@ -1,6 +1,7 @@
 // Tue Jan 15 2026 - Alex
 
 use crate::finders::result::FinderResult;
+use crate::output::OffsetOutput;
 use std::collections::HashMap;
 use std::fmt;
 
@@ -42,6 +43,7 @@ impl OffsetDiff {
                 if old_addr != new_addr {
                     diff.changes.push(OffsetChange {
                         name: name.clone(),
+                        section: OffsetSection::Function,
                         old_value: Some(old_addr),
                         new_value: Some(new_addr),
                         old_confidence: old_result.confidence,
@@ -55,6 +57,7 @@ impl OffsetDiff {
             } else {
                 diff.changes.push(OffsetChange {
                     name: name.clone(),
+                    section: OffsetSection::Function,
                     old_value: Some(old_addr),
                     new_value: None,
                     old_confidence: old_result.confidence,
@@ -70,6 +73,7 @@ impl OffsetDiff {
             if !old_results.contains_key(name) {
                 diff.changes.push(OffsetChange {
                     name: name.clone(),
+                    section: OffsetSection::Function,
                     old_value: None,
                     new_value: Some(new_result.address.as_u64()),
                     old_confidence: 0.0,
@@ -86,11 +90,109 @@ impl OffsetDiff {
         diff
     }
 
+    /// Compute diff from two offset files. Functions and class vtables of
+    /// `new` are compared in `old`'s base; structure fields are compared by
+    /// offset under `Structure.field` names.
+    pub fn from_outputs(old: &OffsetOutput, new: &OffsetOutput) -> Self {
+        let rebased;
+        let new = if new.effective_base() != old.effective_base() {
+            let mut copy = new.clone();
+            copy.rebase(old.effective_base());
+            rebased = copy;
+            &rebased
+        } else {
+            new
+        };
+
+        let mut diff = Self::new(&old.version, &new.version);
+
+        let flatten = |output: &OffsetOutput| {
+            let mut entries: HashMap<String, (OffsetSection, Option<u64>, f64)> = HashMap::new();
+            for (name, func) in &output.functions {
+                entries.insert(name.clone(), (OffsetSection::Function, Some(func.address), func.confidence));
+            }
+            for (name, structure) in &output.structure_offsets {
+                for (field, offset) in &structure.fields {
+                    entries.insert(format!("{}.{}", name, field),
+                        (OffsetSection::Structure, Some(offset.offset as u64), offset.confidence.unwrap_or(1.0)));
+                }
+            }
+            for class in &output.classes {
+                entries.insert(class.name.clone(), (OffsetSection::Class, class.vtable_address, 1.0));
+            }
+            entries
+        };
+        let old_entries = flatten(old);
+        let new_entries = flatten(new);
+
+        for (name, &(section, old_value, old_confidence)) in &old_entries {
+            match new_entries.get(name) {
+                Some(&(_, new_value, new_confidence)) if new_value != old_value => {
+                    let delta = match (old_value, new_value) {
+                        (Some(old), Some(new)) => new.wrapping_sub(old) as i64,
+                        _ => 0,
+                    };
+                    diff.changes.push(OffsetChange {
+                        name: name.clone(),
+                        section,
+                        old_value,
+                        new_value,
+                        old_confidence,
+                        new_confidence,
+                        kind: OffsetChangeKind::ValueChanged,
+                        delta,
+                    });
+                }
+                Some(_) => diff.unchanged.push(name.clone()),
+                None => diff.changes.push(OffsetChange {
+                    name: name.clone(),
+                    section,
+                    old_value,
+                    new_value: None,
+                    old_confidence,
+                    new_confidence: 0.0,
+                    kind: OffsetChangeKind::Removed,
+                    delta: 0,
+                }),
+            }
+        }
+
+        for (name, &(section, new_value, new_confidence)) in &new_entries {
+            if !old_entries.contains_key(name) {
+                diff.changes.push(OffsetChange {
+                    name: name.clone(),
+                    section,
+                    old_value: None,
+                    new_value,
+                    old_confidence: 0.0,
+                    new_confidence,
+                    kind: OffsetChangeKind::Added,
+                    delta: 0,
+                });
+            }
+        }
+
+        diff.changes.sort_by(|a, b| a.name.cmp(&b.name));
+        diff.unchanged.sort();
+
+        diff
+    }
+
+    /// Keeps only the changes `keep` accepts
+    pub fn retain<F: FnMut(&OffsetChange) -> bool>(&mut self, keep: F) {
+        self.changes.retain(keep);
+    }
+
     /// Get changes of a specific kind
     pub fn changes_of_kind(&self, kind: OffsetChangeKind) -> Vec<&OffsetChange> {
         self.changes.iter().filter(|c| c.kind == kind).collect()
     }
 
+    /// Get changes of a specific kind within one section
+    pub fn changes_in(&self, section: OffsetSection, kind: OffsetChangeKind) -> Vec<&OffsetChange> {
+        self.changes.iter().filter(|c| c.section == section && c.kind == kind).collect()
+    }
+
     /// Generate migration info
     pub fn generate_migration(&mut self) {
         let migration = OffsetMigration::from_diff(self);
@@ -153,6 +255,7 @@ impl fmt::Display for OffsetDiff {
 #[derive(Debug, Clone)]
 pub struct OffsetChange {
     pub name: String,
+    pub section: OffsetSection,
     pub old_value: Option<u64>,
     pub new_value: Option<u64>,
     pub old_confidence: f64,
@@ -177,6 +280,14 @@ impl OffsetChange {
     pub fn new_hex(&self) -> String {
         self.new_value.map(|v| format!("0x{:X}", v)).unwrap_or_else(|| "-".to_string())
     }
+
+    pub fn delta_hex(&self) -> String {
+        if self.delta < 0 {
+            format!("-0x{:X}", self.delta.unsigned_abs())
+        } else {
+            format!("+0x{:X}", self.delta)
+        }
+    }
 }
 
 impl fmt::Display for OffsetChange {
@@ -208,6 +319,24 @@ pub enum OffsetChangeKind {
     TypeChanged,
 }
 
+/// Section of an offset file a change belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffsetSection {
+    Function,
+    Structure,
+    Class,
+}
+
+impl OffsetSection {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OffsetSection::Function => "Functions",
+            OffsetSection::Structure => "Structures",
+            OffsetSection::Class => "Classes",
+        }
+    }
+}
+
 /// Statistics for offset diff
 #[derive(Debug, Clone, Default)]
 pub struct OffsetDiffStats {
@@ -501,6 +630,7 @@ mod tests {
     fn test_offset_change() {
         let change = OffsetChange {
             name: "test".to_string(),
+            section: OffsetSection::Function,
             old_value: Some(0x1000),
             new_value: Some(0x1100),
             old_confidence: 0.9,
@@ -513,6 +643,36 @@ mod tests {
         assert_eq!(change.delta, 0x100);
     }
 
+    #[test]
+    fn test_from_outputs_groups_sections_in_old_base() {
+        use crate::output::{FieldOffset, FunctionOffset, StructureOffsets};
+
+        let mut old = OffsetOutput::new("old");
+        old.add_function("luau_load", FunctionOffset::new(0x100001040, 0.9, "pattern"));
+        old.add_function("rbx_crash", FunctionOffset::new(0x100002000, 0.9, "pattern"));
+
+        let mut new = OffsetOutput::new("new");
+        new.add_function("luau_load", FunctionOffset::new(0x100001000, 0.9, "pattern"));
+        new.add_function("rbx_crash", FunctionOffset::new(0x100002000, 0.9, "pattern"));
+        new.make_relative();
+        let mut fields = HashMap::new();
+        fields.insert("top".to_string(), FieldOffset {
+            offset: 0x18, size: 8, field_type: "ptr".to_string(), confidence: None, discovery_method: None,
+        });
+        new.structure_offsets.insert("lua_State".to_string(), StructureOffsets { fields, size: 0x40, alignment: 8 });
+
+        let diff = OffsetDiff::from_outputs(&old, &new);
+        assert_eq!(diff.unchanged, vec!["rbx_crash".to_string()]);
+
+        let moved = diff.changes_in(OffsetSection::Function, OffsetChangeKind::ValueChanged);
+        assert_eq!(moved[0].new_value, Some(0x100001000));
+        assert_eq!(moved[0].delta_hex(), "-0x40");
+
+        let added = diff.changes_in(OffsetSection::Structure, OffsetChangeKind::Added);
+        assert_eq!(added[0].name, "lua_State.top");
+        assert_eq!(added[0].new_value, Some(0x18));
+    }
+
     #[test]
     fn test_offset_history() {
         let mut history = OffsetHistory::new("test_offset");
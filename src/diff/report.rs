@@ -3,6 +3,7 @@
 use crate::diff::binary::BinaryDiff;
 use crate::diff::offset::{OffsetDiff, OffsetChangeKind};
 use crate::diff::version::VersionComparison;
+use crate::utils::time;
 use std::fmt;
 use std::io::Write;
 
@@ -26,7 +27,7 @@ impl DiffReport {
             title: format!("Diff Report: {} -> {}", old_ver, new_ver),
             old_version: old_ver.to_string(),
             new_version: new_ver.to_string(),
-            timestamp: chrono_lite::now(),
+            timestamp: time::format_timestamp(time::generated_secs()),
             binary_diff: None,
             offset_diff: None,
             version_comparison: None,
@@ -124,7 +125,7 @@ impl DiffReport {
                 
                 for change in &diff.changes {
                     let delta_str = match change.kind {
-                        OffsetChangeKind::ValueChanged => format!("{:+}", change.delta),
+                        OffsetChangeKind::ValueChanged => change.delta_hex(),
                         OffsetChangeKind::Added => "NEW".to_string(),
                         OffsetChangeKind::Removed => "REMOVED".to_string(),
                         OffsetChangeKind::TypeChanged => "TYPE".to_string(),
@@ -195,43 +196,39 @@ impl DiffReport {
     }
 
     fn to_json(&self) -> String {
-        let mut json = String::new();
-        
-        json.push_str("{\n");
-        json.push_str(&format!("  \"title\": \"{}\",\n", self.title));
-        json.push_str(&format!("  \"old_version\": \"{}\",\n", self.old_version));
-        json.push_str(&format!("  \"new_version\": \"{}\",\n", self.new_version));
-        json.push_str(&format!("  \"timestamp\": \"{}\",\n", self.timestamp));
-        
-        json.push_str("  \"summary\": {\n");
-        json.push_str(&format!("    \"offset_changes\": {},\n", self.summary.offset_changes));
-        json.push_str(&format!("    \"offsets_unchanged\": {},\n", self.summary.offsets_unchanged));
-        json.push_str(&format!("    \"binary_changes\": {},\n", self.summary.binary_changes));
-        json.push_str(&format!("    \"changed_regions\": {}\n", self.summary.changed_regions));
-        json.push_str("  },\n");
-        
-        json.push_str("  \"changes\": [\n");
-        if let Some(ref diff) = self.offset_diff {
-            for (i, change) in diff.changes.iter().enumerate() {
-                json.push_str("    {\n");
-                json.push_str(&format!("      \"name\": \"{}\",\n", change.name));
-                json.push_str(&format!("      \"old_value\": {},\n", 
-                    change.old_value.map(|v| v.to_string()).unwrap_or("null".to_string())));
-                json.push_str(&format!("      \"new_value\": {},\n",
-                    change.new_value.map(|v| v.to_string()).unwrap_or("null".to_string())));
-                json.push_str(&format!("      \"delta\": {},\n", change.delta));
-                json.push_str(&format!("      \"kind\": \"{:?}\"\n", change.kind));
-                json.push_str("    }");
-                if i < diff.changes.len() - 1 {
-                    json.push(',');
-                }
-                json.push('\n');
-            }
-        }
-        json.push_str("  ]\n");
-        
-        json.push_str("}\n");
-        
+        let changes: Vec<serde_json::Value> = self.offset_diff.iter()
+            .flat_map(|diff| &diff.changes)
+            .map(|change| serde_json::json!({
+                "name": change.name,
+                "section": change.section.label().to_lowercase(),
+                "old_value": change.old_value.map(|v| format!("0x{:x}", v)),
+                "new_value": change.new_value.map(|v| format!("0x{:x}", v)),
+                "delta": (change.kind == OffsetChangeKind::ValueChanged).then(|| change.delta_hex()),
+                "kind": format!("{:?}", change.kind),
+            }))
+            .collect();
+
+        let sections: Vec<serde_json::Value> = self.sections.iter()
+            .map(|section| serde_json::json!({ "title": section.title, "content": section.content }))
+            .collect();
+
+        let report = serde_json::json!({
+            "title": self.title,
+            "old_version": self.old_version,
+            "new_version": self.new_version,
+            "timestamp": self.timestamp,
+            "summary": {
+                "offset_changes": self.summary.offset_changes,
+                "offsets_unchanged": self.summary.offsets_unchanged,
+                "binary_changes": self.summary.binary_changes,
+                "changed_regions": self.summary.changed_regions,
+            },
+            "changes": changes,
+            "sections": sections,
+        });
+
+        let mut json = serde_json::to_string_pretty(&report).unwrap_or_default();
+        json.push('\n');
         json
     }
 
@@ -300,6 +297,17 @@ pub enum ReportFormat {
 }
 
 impl ReportFormat {
+    /// Format for a file extension, `None` for unknown ones
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_ascii_lowercase().as_str() {
+            "txt" => Some(ReportFormat::Text),
+            "md" => Some(ReportFormat::Markdown),
+            "html" | "htm" => Some(ReportFormat::Html),
+            "json" => Some(ReportFormat::Json),
+            _ => None,
+        }
+    }
+
     pub fn extension(&self) -> &'static str {
         match self {
             ReportFormat::Text => "txt",
@@ -347,17 +355,10 @@ impl DiffReportBuilder {
     }
 }
 
-/// Simple timestamp helper (since we don't have chrono)
-mod chrono_lite {
-    pub fn now() -> String {
-        // Return a placeholder - in real code would use actual timestamp
-        "2026-01-15".to_string()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diff::offset::{OffsetChange, OffsetSection};
 
     #[test]
     fn test_report_builder() {
@@ -380,4 +381,27 @@ mod tests {
         let json = report.export(ReportFormat::Json);
         assert!(json.contains("\"old_version\""));
     }
+
+    #[test]
+    fn test_json_export_lists_offset_changes() {
+        let mut diff = OffsetDiff::new("v1", "v2");
+        diff.changes.push(OffsetChange {
+            name: "luau_load".to_string(),
+            section: OffsetSection::Function,
+            old_value: Some(0x1040),
+            new_value: Some(0x1000),
+            old_confidence: 0.9,
+            new_confidence: 0.9,
+            kind: OffsetChangeKind::ValueChanged,
+            delta: -0x40,
+        });
+
+        let report = DiffReportBuilder::new("old \"a\"", "v2").offset_diff(diff).build();
+        let json: serde_json::Value = serde_json::from_str(&report.export(ReportFormat::Json)).unwrap();
+        assert_eq!(json["old_version"], "old \"a\"");
+        assert_eq!(json["summary"]["offset_changes"], 1);
+        assert_eq!(json["changes"][0]["old_value"], "0x1040");
+        assert_eq!(json["changes"][0]["delta"], "-0x40");
+        assert_eq!(ReportFormat::from_extension("MD"), Some(ReportFormat::Markdown));
+    }
 }
@@ -0,0 +1,339 @@
+// Tue Jan 13 2026 - Alex
+
+use crate::analysis::padding::CodeMap;
+use crate::finders::result::CombinedResults;
+use crate::finders::{classes, constants, methods, properties, structures, RobloxFinders};
+use crate::finders::classes::ReflectionCache;
+use crate::memory::{Address, MemoryReader, MemoryRegion};
+use crate::symbol::SymbolResolver;
+use crate::utils::profile;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Bytes each chunk extends past its nominal end. Covers the longest finder
+/// signature (28 bytes) rounded up to keep chunk ends instruction aligned.
+pub const DEFAULT_CHUNK_OVERLAP: u64 = 0x40;
+
+pub const DEFAULT_CHUNK_SIZE: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanChunk {
+    pub index: usize,
+    pub start: Address,
+    pub end: Address,
+}
+
+impl ScanChunk {
+    pub fn size(&self) -> u64 {
+        self.end.as_u64() - self.start.as_u64()
+    }
+}
+
+/// Splits executable regions into `chunk_size` pieces. Every chunk except the
+/// last one in a region overlaps its successor by `overlap` bytes so a match
+/// straddling the boundary is still seen in full by one of them.
+pub fn plan_chunks(regions: &[MemoryRegion], chunk_size: u64, overlap: u64) -> Vec<ScanChunk> {
+    let chunk_size = chunk_size.max(overlap + 4);
+    let mut chunks = Vec::new();
+
+    for region in regions.iter().filter(|r| r.is_executable()) {
+        let region_start = region.start().as_u64();
+        let region_end = region.end().as_u64();
+        let mut current = region_start;
+
+        while current < region_end {
+            let end = (current + chunk_size + overlap).min(region_end);
+
+            chunks.push(ScanChunk {
+                index: chunks.len(),
+                start: Address::new(current),
+                end: Address::new(end),
+            });
+
+            current += chunk_size;
+        }
+    }
+
+    chunks
+}
+
+/// Cuts the padding off both ends of every chunk and drops chunks that hold
+/// nothing else, so workers don't scan the gaps between functions.
+pub fn trim_padding(chunks: Vec<ScanChunk>, map: &CodeMap) -> Vec<ScanChunk> {
+    chunks.into_iter()
+        .filter_map(|chunk| map.trim(chunk.start..chunk.end))
+        .enumerate()
+        .map(|(index, range)| ScanChunk { index, start: range.start, end: range.end })
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct ChunkScan {
+    reader: Arc<dyn MemoryReader>,
+    symbols: Arc<SymbolResolver>,
+    chunk: ScanChunk,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl ChunkScan {
+    /// `symbols` is the scan's resolver, shared by all of its chunks.
+    pub fn new(reader: Arc<dyn MemoryReader>, symbols: Arc<SymbolResolver>, chunk: ScanChunk) -> Self {
+        Self { reader, symbols, chunk, cancel: None }
+    }
+
+    /// Shares a stop flag with the caller; see `run`.
+    pub fn with_cancellation(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    pub fn chunk(&self) -> ScanChunk {
+        self.chunk
+    }
+
+    /// Runs the same finder passes as a full scan, restricted to this chunk.
+    /// The cancellation flag is checked between passes; a cancelled chunk
+    /// returns the results of the passes that completed.
+    pub fn run(&self) -> CombinedResults {
+        profile::span("chunk", || self.run_passes())
+    }
+
+    fn run_passes(&self) -> CombinedResults {
+        let mut results = CombinedResults::new();
+        let reflection = ReflectionCache::new();
+
+        for phase in ScanPhase::ALL {
+            run_phase(phase, self.reader.clone(), &self.symbols, &reflection, self.chunk.start..self.chunk.end, self.cancel.as_ref(), &mut results);
+
+            if self.is_cancelled() {
+                break;
+            }
+        }
+
+        results
+    }
+}
+
+/// The finder passes of a full scan, in the order a serial scan runs them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanPhase {
+    Functions,
+    Structures,
+    Classes,
+    Properties,
+    Methods,
+    Constants,
+}
+
+impl ScanPhase {
+    pub const ALL: [ScanPhase; 6] = [
+        ScanPhase::Functions,
+        ScanPhase::Structures,
+        ScanPhase::Classes,
+        ScanPhase::Properties,
+        ScanPhase::Methods,
+        ScanPhase::Constants,
+    ];
+
+    /// Span name, as in `--profile-tree`
+    pub fn name(self) -> &'static str {
+        match self {
+            ScanPhase::Functions => "functions",
+            ScanPhase::Structures => "structures",
+            ScanPhase::Classes => "classes",
+            ScanPhase::Properties => "properties",
+            ScanPhase::Methods => "methods",
+            ScanPhase::Constants => "constants",
+        }
+    }
+
+    /// What the phase finds, for progress messages
+    pub fn label(self) -> &'static str {
+        match self {
+            ScanPhase::Functions => "Roblox functions",
+            ScanPhase::Structures => "structure offsets",
+            other => other.name(),
+        }
+    }
+
+    /// How many results of this phase's kind `results` holds
+    pub fn count(self, results: &CombinedResults) -> usize {
+        match self {
+            ScanPhase::Functions => results.functions.len(),
+            ScanPhase::Structures => results.structure_offsets.len(),
+            ScanPhase::Classes => results.classes.len(),
+            ScanPhase::Properties => results.properties.len(),
+            ScanPhase::Methods => results.methods.len(),
+            ScanPhase::Constants => results.constants.len(),
+        }
+    }
+}
+
+/// Runs one phase over `range`, adding what it finds to `results`.
+/// `cancel` stops the Roblox finders between finders; the other phases
+/// always run to the end. `symbols` is the scan's resolver, built once
+/// rather than per range; `reflection` lets the class and property phases
+/// share one walk of each range's class table.
+pub fn run_phase(
+    phase: ScanPhase,
+    reader: Arc<dyn MemoryReader>,
+    symbols: &Arc<SymbolResolver>,
+    reflection: &ReflectionCache,
+    range: Range<Address>,
+    cancel: Option<&Arc<AtomicBool>>,
+    results: &mut CombinedResults,
+) {
+    let Range { start, end } = range;
+    profile::span(phase.name(), || match phase {
+        ScanPhase::Functions => {
+            let mut roblox = RobloxFinders::new(reader);
+            if let Some(cancel) = cancel {
+                roblox = roblox.with_cancellation(cancel.clone());
+            }
+            for result in roblox.find_all(start, end) {
+                results.add_function(result);
+            }
+        }
+        ScanPhase::Structures => {
+            for result in structures::find_all_structures(reader, start, end) {
+                results.add_structure_offset(result);
+            }
+        }
+        ScanPhase::Classes => {
+            for result in classes::find_all_classes(reader, symbols, reflection, start, end) {
+                results.add_class(result);
+            }
+        }
+        ScanPhase::Properties => {
+            for result in properties::find_all_properties(reader, reflection, start, end) {
+                results.add_property(result);
+            }
+        }
+        ScanPhase::Methods => {
+            for result in methods::find_all_methods(reader, symbols.clone(), start, end) {
+                results.add_method(result);
+            }
+        }
+        ScanPhase::Constants => {
+            for result in constants::find_all_constants(reader, start, end) {
+                results.add_constant(result);
+            }
+        }
+    })
+}
+
+/// One phase over every scan range, so the phases of a scan can run on
+/// separate workers. Each phase fills its own part of `CombinedResults`.
+#[derive(Clone)]
+pub struct PhaseScan {
+    reader: Arc<dyn MemoryReader>,
+    symbols: Arc<SymbolResolver>,
+    phase: ScanPhase,
+    ranges: Vec<(Address, Address)>,
+    reflection: Arc<ReflectionCache>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl PhaseScan {
+    pub fn new(reader: Arc<dyn MemoryReader>, symbols: Arc<SymbolResolver>, phase: ScanPhase, ranges: Vec<(Address, Address)>) -> Self {
+        Self { reader, symbols, phase, ranges, reflection: Arc::default(), cancel: None }
+    }
+
+    /// Shares the reflection walks with the scan's other phases; without
+    /// it the class and property phases each walk every range.
+    pub fn with_reflection(mut self, reflection: Arc<ReflectionCache>) -> Self {
+        self.reflection = reflection;
+        self
+    }
+
+    pub fn with_cancellation(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    pub fn phase(&self) -> ScanPhase {
+        self.phase
+    }
+
+    pub fn run(&self) -> CombinedResults {
+        let mut results = CombinedResults::new();
+        for &(start, end) in &self.ranges {
+            run_phase(self.phase, self.reader.clone(), &self.symbols, &self.reflection, start..end, self.cancel.as_ref(), &mut results);
+        }
+        results
+    }
+}
+
+impl std::fmt::Debug for PhaseScan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PhaseScan")
+            .field("phase", &self.phase)
+            .field("ranges", &self.ranges.len())
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for ChunkScan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChunkScan")
+            .field("chunk", &self.chunk)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::padding::{Gap, PaddingKind};
+    use crate::memory::{MemoryRange, Protection};
+
+    fn region(start: u64, size: u64, protection: Protection) -> MemoryRegion {
+        MemoryRegion::new(
+            MemoryRange::from_start_size(Address::new(start), size),
+            protection,
+            "__TEXT".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_plan_chunks_overlaps_boundaries() {
+        let regions = vec![region(0x1000, 0x2800, Protection::ReadExecute)];
+        let chunks = plan_chunks(&regions, 0x1000, 0x40);
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].start.as_u64(), 0x1000);
+        assert_eq!(chunks[0].end.as_u64(), 0x2040);
+        assert_eq!(chunks[1].start.as_u64(), 0x2000);
+        assert_eq!(chunks[2].end.as_u64(), 0x3800);
+    }
+
+    #[test]
+    fn test_trim_padding_drops_padding_only_chunks() {
+        let regions = vec![region(0x1000, 0x3000, Protection::ReadExecute)];
+        let chunks = plan_chunks(&regions, 0x1000, 0x40);
+        let gap = |start: u64, end: u64| Gap { start: Address::new(start), end: Address::new(end), kind: PaddingKind::Zero };
+        let map = CodeMap::from_gaps(vec![gap(0x1000, 0x1100), gap(0x1F00, 0x3040), gap(0x3800, 0x4000)]);
+
+        let trimmed = trim_padding(chunks, &map);
+        let ranges: Vec<(usize, u64, u64)> = trimmed.iter().map(|c| (c.index, c.start.as_u64(), c.end.as_u64())).collect();
+        assert_eq!(ranges, vec![(0, 0x1100, 0x1F00), (1, 0x3040, 0x3800)]);
+    }
+
+    #[test]
+    fn test_plan_chunks_skips_non_executable() {
+        let regions = vec![
+            region(0x1000, 0x1000, Protection::ReadWrite),
+            region(0x4000, 0x800, Protection::ReadExecute),
+        ];
+        let chunks = plan_chunks(&regions, 0x1000, 0x40);
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].start.as_u64(), 0x4000);
+        assert_eq!(chunks[0].end.as_u64(), 0x4800);
+    }
+}
@@ -11,6 +11,7 @@ use crate::engine::scheduler::TaskScheduler;
 use crate::engine::pipeline::Pipeline;
 use crate::engine::task::{Task, TaskType};
 use crate::engine::result::TaskResult;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use parking_lot::RwLock;
 
@@ -24,6 +25,7 @@ pub struct Engine {
     scheduler: TaskScheduler,
     results: Arc<RwLock<FinderResults>>,
     state: EngineState,
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -33,6 +35,7 @@ pub enum EngineState {
     Running,
     Paused,
     Completed,
+    Cancelled,
     Failed,
 }
 
@@ -54,9 +57,17 @@ impl Engine {
             scheduler,
             results: Arc::new(RwLock::new(FinderResults::new())),
             state: EngineState::Idle,
+            cancel: None,
         }
     }
 
+    /// `run` stops between pipeline stages once `cancel` is set and
+    /// returns the results gathered so far.
+    pub fn with_cancellation(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
     pub fn initialize(&mut self) -> Result<(), EngineError> {
         self.state = EngineState::Initializing;
 
@@ -82,6 +93,11 @@ impl Engine {
                 return Err(EngineError::Paused);
             }
 
+            if self.cancel.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+                self.state = EngineState::Cancelled;
+                return Ok(self.results.read().clone());
+            }
+
             let tasks = stage.generate_tasks();
 
             for task in tasks {
@@ -178,6 +194,9 @@ impl Engine {
             TaskResult::Error(e) => {
                 Err(EngineError::TaskFailed(e))
             }
+            TaskResult::Scanned(_) => {
+                Ok(())
+            }
             TaskResult::Skipped(reason) => {
                 Ok(())
             }
@@ -8,6 +8,7 @@ pub mod task;
 pub mod result;
 pub mod pipeline;
 pub mod stage;
+pub mod chunk;
 
 pub use self::core::Engine;
 pub use runner::EngineRunner;
@@ -17,3 +18,4 @@ pub use task::{Task, TaskType, TaskPriority};
 pub use result::TaskResult;
 pub use pipeline::Pipeline;
 pub use stage::Stage;
+pub use chunk::{ChunkScan, PhaseScan, ScanChunk, ScanPhase};
@@ -1,18 +1,19 @@
 // Tue Jan 13 2026 - Alex
 
-use crate::finders::result::FinderResults;
+use crate::finders::result::{CombinedResults, FinderResults};
 use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub enum TaskResult {
     Success(FinderResults),
+    Scanned(CombinedResults),
     Error(String),
     Skipped(String),
 }
 
 impl TaskResult {
     pub fn is_success(&self) -> bool {
-        matches!(self, TaskResult::Success(_))
+        matches!(self, TaskResult::Success(_) | TaskResult::Scanned(_))
     }
 
     pub fn is_error(&self) -> bool {
@@ -26,6 +27,7 @@ impl TaskResult {
     pub fn unwrap(self) -> FinderResults {
         match self {
             TaskResult::Success(results) => results,
+            TaskResult::Scanned(_) => panic!("Called unwrap on Scanned; use into_combined"),
             TaskResult::Error(e) => panic!("Called unwrap on Error: {}", e),
             TaskResult::Skipped(reason) => panic!("Called unwrap on Skipped: {}", reason),
         }
@@ -38,6 +40,13 @@ impl TaskResult {
         }
     }
 
+    pub fn into_combined(self) -> Option<CombinedResults> {
+        match self {
+            TaskResult::Scanned(results) => Some(results),
+            _ => None,
+        }
+    }
+
     pub fn error_message(&self) -> Option<&str> {
         match self {
             TaskResult::Error(e) => Some(e),
@@ -116,6 +125,9 @@ impl AggregatedResults {
                 self.success_count += 1;
                 self.results.merge(findings.clone());
             }
+            TaskResult::Scanned(_) => {
+                self.success_count += 1;
+            }
             TaskResult::Error(_) => {
                 self.error_count += 1;
             }
@@ -18,12 +18,13 @@ pub struct EngineRunner {
 
 impl EngineRunner {
     pub fn new(config: Config, reader: Arc<dyn MemoryReader>) -> Self {
-        let engine = Engine::new(config, reader);
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let engine = Engine::new(config, reader).with_cancellation(stop_flag.clone());
 
         Self {
             engine,
             progress_manager: None,
-            stop_flag: Arc::new(AtomicBool::new(false)),
+            stop_flag,
         }
     }
 
@@ -104,6 +104,20 @@ impl TaskScheduler {
         results
     }
 
+    /// Blocks until `count` results have arrived, regardless of queue state.
+    pub fn collect(&self, count: usize) -> Vec<TaskResult> {
+        let mut results = Vec::with_capacity(count);
+
+        while results.len() < count {
+            match self.result_receiver.recv() {
+                Ok(result) => results.push(result),
+                Err(_) => break,
+            }
+        }
+
+        results
+    }
+
     pub fn pending_count(&self) -> usize {
         let queue = self.task_queue.lock().unwrap();
         queue.len()
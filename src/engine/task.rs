@@ -1,5 +1,6 @@
 // Tue Jan 13 2026 - Alex
 
+use crate::engine::chunk::{ChunkScan, PhaseScan};
 use crate::finders::result::FinderResults;
 use crate::memory::MemoryError;
 use std::time::Duration;
@@ -87,6 +88,9 @@ impl Task {
             TaskType::AnalyzeMethods => self.execute_analyze_methods(),
             TaskType::FindConstants => self.execute_find_constants(),
             TaskType::ValidateResults => self.execute_validate_results(),
+            TaskType::ScanChunk(_) | TaskType::ScanPhase(_) => Err(TaskError::ExecutionError(
+                "chunk and phase scans report combined results; run them through a worker".to_string(),
+            )),
             TaskType::Custom(ref name) => self.execute_custom(name),
         }
     }
@@ -173,6 +177,8 @@ pub enum TaskType {
     AnalyzeMethods,
     FindConstants,
     ValidateResults,
+    ScanChunk(ChunkScan),
+    ScanPhase(PhaseScan),
     Custom(String),
 }
 
@@ -194,6 +200,8 @@ impl TaskType {
             TaskType::AnalyzeMethods => "Analyze Methods",
             TaskType::FindConstants => "Find Constants",
             TaskType::ValidateResults => "Validate Results",
+            TaskType::ScanChunk(_) => "Scan Chunk",
+            TaskType::ScanPhase(_) => "Scan Phase",
             TaskType::Custom(name) => name,
         }
     }
@@ -1,8 +1,7 @@
 // Tue Jan 13 2026 - Alex
 
-use crate::engine::task::Task;
+use crate::engine::task::{Task, TaskType};
 use crate::engine::result::TaskResult;
-use crate::finders::result::FinderResults;
 use std::collections::BinaryHeap;
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
@@ -110,7 +109,11 @@ impl Worker {
     }
 
     fn execute_task(task: &Task) -> TaskResult {
-        let mut results = FinderResults::new();
+        match task.task_type() {
+            TaskType::ScanChunk(scan) => return TaskResult::Scanned(scan.run()),
+            TaskType::ScanPhase(scan) => return TaskResult::Scanned(scan.run()),
+            _ => {}
+        }
 
         match task.execute() {
             Ok(findings) => TaskResult::Success(findings),
@@ -3,6 +3,7 @@
 use crate::memory::{Address, MemoryReader};
 use crate::pattern::Pattern;
 use crate::finders::result::FinderResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 
 pub struct OpcodeLookupFinder {
@@ -15,15 +16,10 @@ impl OpcodeLookupFinder {
     }
 
     pub fn find(&self, start: Address, end: Address) -> Option<FinderResult> {
-        if let Some(result) = self.find_by_pattern(start, end) {
-            return Some(result);
-        }
-
-        if let Some(result) = self.find_by_jump_table(start, end) {
-            return Some(result);
-        }
-
-        self.find_by_heuristic(start, end)
+        self.find_by_pattern(start, end)
+            .or_else(|| self.find_by_jump_table(start, end))
+            .or_else(|| self.find_by_heuristic(start, end))
+            .map(|result| result.follow_thunk(self.reader.as_ref()))
     }
 
     fn find_by_pattern(&self, start: Address, end: Address) -> Option<FinderResult> {
@@ -41,14 +37,12 @@ impl OpcodeLookupFinder {
                         let addr = current + offset as u64;
 
                         if self.validate_opcode_lookup(addr) {
-                            return Some(FinderResult {
-                                name: "OpcodeLookup".to_string(),
-                                address: addr,
-                                confidence: 0.90,
-                                method: "pattern".to_string(),
-                                category: "bytecode".to_string(),
-                                signature: Some("void* OpcodeLookup(uint8_t opcode)".to_string()),
-                            });
+                            return Some(FinderResult::new("OpcodeLookup".to_string(), addr, 0.90)
+                                .with_method("pattern")
+                                .with_category("bytecode")
+                                .with_signature("void* OpcodeLookup(uint8_t opcode)")
+                                .with_factor("byte pattern", 0.70)
+                                .with_factor("prologue and body checks", 0.20));
                         }
                     }
                 }
@@ -70,14 +64,11 @@ impl OpcodeLookupFinder {
 
                     if let Some(func_addr) = self.find_function_using_table(table_addr, start, end) {
                         if self.validate_opcode_lookup(func_addr) {
-                            return Some(FinderResult {
-                                name: "OpcodeLookup".to_string(),
-                                address: func_addr,
-                                confidence: 0.85,
-                                method: "jump_table".to_string(),
-                                category: "bytecode".to_string(),
-                                signature: None,
-                            });
+                            return Some(FinderResult::new("OpcodeLookup".to_string(), func_addr, 0.85)
+                                .with_method("jump_table")
+                                .with_category("bytecode")
+                                .with_factor("uses jump table", 0.65)
+                                .with_factor("prologue and body checks", 0.20));
                         }
                     }
                 }
@@ -98,14 +89,11 @@ impl OpcodeLookupFinder {
                     let func_start = self.find_function_start(current);
 
                     if self.validate_opcode_lookup(func_start) {
-                        return Some(FinderResult {
-                            name: "OpcodeLookup".to_string(),
-                            address: func_start,
-                            confidence: 0.72,
-                            method: "heuristic".to_string(),
-                            category: "bytecode".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("OpcodeLookup".to_string(), func_start, 0.72)
+                            .with_method("heuristic")
+                            .with_category("bytecode")
+                            .with_factor("body heuristic", 0.52)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -280,8 +268,8 @@ impl OpcodeLookupFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
@@ -7,21 +7,61 @@ pub mod descriptor;
 pub mod hierarchy;
 
 pub use instance::InstanceClassFinder;
-pub use reflection::ReflectionFinder;
+pub use reflection::{ReflectionCache, ReflectionFinder};
 pub use vtable::VTableAnalyzer;
 
 use crate::memory::{Address, MemoryReader};
 use crate::finders::result::ClassResult;
+use crate::symbol::SymbolResolver;
 use std::sync::Arc;
 
-pub fn find_all_classes(reader: Arc<dyn MemoryReader>, start: Address, end: Address) -> Vec<ClassResult> {
-    let mut results = Vec::new();
+/// Classes whose vtables the symbol table names come first, at full
+/// confidence, then the reflection table (which also fills in their
+/// parents); the instance heuristics only add names neither covered.
+/// `symbols` is built once per scan and shared by every range; the
+/// reflection walk is shared with `find_all_properties` through `reflection`.
+pub fn find_all_classes(reader: Arc<dyn MemoryReader>, symbols: &SymbolResolver, reflection: &ReflectionCache, start: Address, end: Address) -> Vec<ClassResult> {
+    let mut results = find_vtable_symbols(symbols, start, end);
+
+    let reflection_finder = ReflectionFinder::new(reader.clone());
+    let walked = reflection.walk(&reflection_finder, start, end);
+    for result in reflection_finder.class_results(&walked) {
+        match results.iter_mut().find(|r| r.name == result.name) {
+            Some(existing) => {
+                if existing.parent_class.is_none() {
+                    existing.parent_class = result.parent_class;
+                }
+            }
+            None => results.push(result),
+        }
+    }
 
     let instance_finder = InstanceClassFinder::new(reader.clone());
-    results.extend(instance_finder.find_all(start, end));
+    for result in instance_finder.find_all(start, end) {
+        if !results.iter().any(|r| r.name == result.name) {
+            results.push(result);
+        }
+    }
 
-    let reflection_finder = ReflectionFinder::new(reader.clone());
-    results.extend(reflection_finder.find_all(start, end));
+    results
+}
+
+/// One class per `vtable for RBX::<name>` symbol in range. Objects point
+/// 16 bytes into the vtable, past the offset-to-top and typeinfo slots.
+fn find_vtable_symbols(symbols: &SymbolResolver, start: Address, end: Address) -> Vec<ClassResult> {
+    let mut results: Vec<ClassResult> = symbols.iter()
+        .filter(|symbol| symbol.address >= start && symbol.address < end)
+        .filter_map(|symbol| {
+            let name = symbol.display_name().strip_prefix("vtable for RBX::")?;
+            if name.contains("::") || name.contains('<') {
+                return None;
+            }
+            Some(ClassResult::new(name.to_string(), symbol.address)
+                .with_vtable(symbol.address + 16)
+                .with_confidence(1.0))
+        })
+        .collect();
 
+    results.sort_by_key(|result| result.address);
     results
 }
@@ -1,174 +1,306 @@
 // Tue Jan 13 2026 - Alex
 
 use crate::memory::{Address, MemoryReader};
-use crate::finders::result::ClassResult;
-use std::sync::Arc;
+use crate::finders::classes::descriptor::{ClassDescriptor, DescriptorReader, PropertyDescriptor};
+use crate::finders::result::{ClassResult, PropertyResult};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::collections::HashMap;
 
+/// Shortest pointer run accepted as the class table
+const MIN_TABLE_ENTRIES: usize = 4;
+const MAX_TABLE_ENTRIES: usize = 16384;
+
+const READ_CHUNK: usize = 0x10000;
+
+/// Getters longer than this are not treated as plain field reads
+const MAX_GETTER_INSNS: usize = 4;
+
+/// Class tables walked so far, by scan range. The class and property passes
+/// both need the table; sharing one cache between them walks each range
+/// once, even when the passes run on different workers.
+#[derive(Default)]
+pub struct ReflectionCache {
+    walks: Mutex<HashMap<(Address, Address), Arc<WalkSlot>>>,
+}
+
+/// Filled once by whichever pass walks the range first
+type WalkSlot = OnceLock<Arc<[ClassDescriptor]>>;
+
+impl ReflectionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `finder.walk(start, end)`, run by the first caller for the range;
+    /// later callers wait for and share its result
+    pub fn walk(&self, finder: &ReflectionFinder, start: Address, end: Address) -> Arc<[ClassDescriptor]> {
+        let slot = self.walks.lock().unwrap().entry((start, end)).or_default().clone();
+        slot.get_or_init(|| finder.walk(start, end).into()).clone()
+    }
+}
+
+/// Walks the reflection metadata: the `ClassDescriptor` for "Instance" is
+/// located through its name pointer, the pointer array holding it is taken
+/// as the class table, and every descriptor in the table is read along with
+/// its `PropertyDescriptor` list. When no table is found nothing is reported.
 pub struct ReflectionFinder {
     reader: Arc<dyn MemoryReader>,
+    descriptors: DescriptorReader,
 }
 
 impl ReflectionFinder {
     pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
-        Self { reader }
+        Self {
+            descriptors: DescriptorReader::new(reader.clone()),
+            reader,
+        }
     }
 
     pub fn find_all(&self, start: Address, end: Address) -> Vec<ClassResult> {
+        self.class_results(&self.walk(start, end))
+    }
+
+    /// One result per walked class, with its parent named when the parent
+    /// is in the table too
+    pub fn class_results(&self, classes: &[ClassDescriptor]) -> Vec<ClassResult> {
+        let names: HashMap<Address, &str> = classes.iter()
+            .map(|c| (c.address, c.name.as_str()))
+            .collect();
+
+        classes.iter()
+            .map(|class| {
+                let mut result = ClassResult::new(class.name.clone(), class.address)
+                    .with_vtable(class.vtable)
+                    .with_confidence(0.95);
+
+                let parent = class.parent_descriptor.and_then(|p| names.get(&p).copied());
+                if let Some(parent) = parent {
+                    result = result.with_parent(parent);
+                }
+
+                result
+            })
+            .collect()
+    }
+
+    /// Properties of every class in the table. Offsets come from getters
+    /// that just read a field off `this`.
+    pub fn find_properties(&self, start: Address, end: Address) -> Vec<PropertyResult> {
+        self.property_results(&self.walk(start, end))
+    }
+
+    /// `find_properties` over classes already walked
+    pub fn property_results(&self, classes: &[ClassDescriptor]) -> Vec<PropertyResult> {
         let mut results = Vec::new();
 
-        if let Some(class_descriptors) = self.find_class_descriptors(start, end) {
-            for (name, addr) in class_descriptors {
-                results.push(ClassResult::new(name, addr)
-                    .with_confidence(0.85));
+        for class in classes {
+            let properties = match class.properties_ptr {
+                Some(list) => self.descriptors.read_property_list(list),
+                None => continue,
+            };
+
+            for property in properties {
+                results.push(self.property_result(&class.name, &property));
             }
         }
 
         results
     }
 
-    fn find_class_descriptors(&self, start: Address, end: Address) -> Option<HashMap<String, Address>> {
-        let mut descriptors = HashMap::new();
-
-        let class_names = [
-            "Instance", "Part", "BasePart", "Model", "Workspace",
-            "DataModel", "Player", "Players", "Camera", "Lighting",
-            "ReplicatedStorage", "ServerStorage", "ServerScriptService",
-            "StarterGui", "StarterPack", "StarterPlayer", "Teams",
-            "SoundService", "Chat", "LocalizationService", "TestService",
-            "RunService", "UserInputService", "ContextActionService",
-            "GuiService", "HapticService", "TweenService", "TeleportService",
-            "TextService", "ContentProvider", "Debris", "PathfindingService",
-            "BadgeService", "NotificationService", "MarketplaceService",
-            "AssetService", "InsertService", "PointsService", "GamePassService",
-            "GroupService", "HttpService", "KeyframeSequenceProvider",
-            "LogService", "ScriptContext", "Selection", "Stats",
-            "StudioService", "PluginGuiService", "VirtualInputManager",
-            "Script", "LocalScript", "ModuleScript", "CoreScript",
-            "RemoteEvent", "RemoteFunction", "BindableEvent", "BindableFunction",
-            "Folder", "Configuration", "StringValue", "IntValue", "NumberValue",
-            "BoolValue", "ObjectValue", "CFrameValue", "Vector3Value", "Color3Value",
-            "BrickColorValue", "RayValue", "Part", "WedgePart", "CornerWedgePart",
-            "SpawnLocation", "Seat", "VehicleSeat", "SkateboardPlatform",
-            "Truss", "MeshPart", "UnionOperation", "NegateOperation",
-            "Terrain", "Humanoid", "HumanoidDescription", "Animator",
-            "Animation", "AnimationTrack", "KeyframeSequence", "Keyframe",
-            "Pose", "Motor6D", "Weld", "Snap", "Rotate", "Glue", "ManualWeld",
-            "ManualGlue", "VelocityMotor", "Attachment", "Constraint",
-            "BallSocketConstraint", "HingeConstraint", "PrismaticConstraint",
-            "CylindricalConstraint", "RodConstraint", "RopeConstraint",
-            "SpringConstraint", "AlignOrientation", "AlignPosition",
-            "AngularVelocity", "LinearVelocity", "VectorForce", "Torque",
-            "BodyForce", "BodyVelocity", "BodyAngularVelocity", "BodyPosition",
-            "BodyGyro", "BodyThrust", "RocketPropulsion", "BodyMover",
-            "Sound", "SoundGroup", "SoundEffect", "ChorusSoundEffect",
-            "CompressorSoundEffect", "DistortionSoundEffect", "EchoSoundEffect",
-            "EqualizerSoundEffect", "FlangeSoundEffect", "PitchShiftSoundEffect",
-            "ReverbSoundEffect", "TremoloSoundEffect", "ParticleEmitter",
-            "Fire", "Smoke", "Sparkles", "Explosion", "ForceField",
-            "Decal", "Texture", "SurfaceGui", "BillboardGui", "ScreenGui",
-            "AdGui", "SurfaceSelection", "Handles", "ArcHandles",
-            "Frame", "TextLabel", "TextButton", "TextBox", "ImageLabel",
-            "ImageButton", "ViewportFrame", "ScrollingFrame", "UIListLayout",
-            "UIGridLayout", "UITableLayout", "UIPageLayout", "UIPadding",
-            "UIScale", "UIAspectRatioConstraint", "UISizeConstraint",
-            "UITextSizeConstraint", "UICorner", "UIStroke", "UIGradient",
-            "Beam", "Trail", "Highlight", "Tool", "HopperBin",
-            "BackpackItem", "Backpack", "ProximityPrompt", "ClickDetector",
-            "Dialog", "DialogChoice", "CharacterMesh", "Accessory", "Accoutrement",
-            "Hat", "Shirt", "Pants", "ShirtGraphic", "CharacterAppearance",
-            "Skin", "BodyColors", "Clothing", "ClothingItem", "Face",
-        ];
-
-        for class_name in &class_names {
-            if let Some(addr) = self.find_class_descriptor(class_name, start, end) {
-                descriptors.insert(class_name.to_string(), addr);
+    fn property_result(&self, class_name: &str, property: &PropertyDescriptor) -> PropertyResult {
+        let mut result = PropertyResult::new(class_name.to_string(), property.name.clone())
+            .with_confidence(0.9);
+
+        if let Some(getter) = property.getter {
+            result = result.with_getter(getter);
+
+            if let Some(offset) = self.getter_field_offset(getter) {
+                result = result.with_offset(offset).with_confidence(0.95);
             }
         }
+        if let Some(setter) = property.setter {
+            result = result.with_setter(setter);
+        }
+        if let Some(property_type) = &property.property_type {
+            result = result.with_type(property_type);
+        }
+
+        result
+    }
+
+    /// Class descriptors in table order, or none if the table can't be found
+    pub fn walk(&self, start: Address, end: Address) -> Vec<ClassDescriptor> {
+        let data = self.read_data_regions();
+
+        let instance = match self.find_instance_descriptor(&data, start, end) {
+            Some(addr) => addr,
+            None => return Vec::new(),
+        };
 
-        if descriptors.is_empty() {
-            None
-        } else {
-            Some(descriptors)
+        match self.find_class_table(&data, instance) {
+            Some(table) => table.into_iter()
+                .filter_map(|addr| self.read_class(addr))
+                .collect(),
+            None => Vec::new(),
         }
     }
 
-    fn find_class_descriptor(&self, class_name: &str, start: Address, end: Address) -> Option<Address> {
-        if let Some(string_addr) = self.find_string(class_name, start, end) {
-            let search_start = if start.as_u64() > 0x1000 {
-                start - 0x1000
-            } else {
-                start
-            };
+    fn read_data_regions(&self) -> Vec<(Address, Vec<u8>)> {
+        let regions = match self.reader.get_regions() {
+            Ok(regions) => regions,
+            Err(_) => return Vec::new(),
+        };
 
-            let mut current = search_start;
-            while current < end {
-                if let Ok(bytes) = self.reader.read_bytes(current, 4096) {
-                    for i in (0..bytes.len() - 8).step_by(8) {
-                        let ptr = u64::from_le_bytes([
-                            bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3],
-                            bytes[i + 4], bytes[i + 5], bytes[i + 6], bytes[i + 7],
-                        ]);
-
-                        if ptr == string_addr.as_u64() {
-                            let potential_descriptor = current + i as u64 - 8;
-
-                            if self.validate_class_descriptor(potential_descriptor) {
-                                return Some(potential_descriptor);
-                            }
-                        }
-                    }
+        let mut out = Vec::new();
+
+        for region in regions.iter().filter(|r| r.is_data()) {
+            let size = region.size() as usize;
+            let mut data = Vec::new();
+            let mut chunk = READ_CHUNK;
+
+            while data.len() < size && chunk > 0 {
+                let len = chunk.min(size - data.len());
+                match self.reader.read_bytes(region.start() + data.len() as u64, len) {
+                    Ok(bytes) => data.extend_from_slice(&bytes),
+                    Err(_) => chunk /= 2,
                 }
+            }
 
-                current = current + 4000;
+            if !data.is_empty() {
+                out.push((region.start(), data));
             }
         }
 
-        None
+        out
     }
 
-    fn validate_class_descriptor(&self, addr: Address) -> bool {
-        if let Ok(bytes) = self.reader.read_bytes(addr, 64) {
-            let vtable_ptr = u64::from_le_bytes([
-                bytes[0], bytes[1], bytes[2], bytes[3],
-                bytes[4], bytes[5], bytes[6], bytes[7],
-            ]);
+    /// The descriptor whose name pointer targets an "Instance" C string
+    /// inside `start..end`
+    fn find_instance_descriptor(&self, data: &[(Address, Vec<u8>)], start: Address, end: Address) -> Option<Address> {
+        for string_addr in self.find_c_strings("Instance", start, end) {
+            let target = string_addr.as_u64().to_le_bytes();
 
-            if vtable_ptr < 0x100000000 || vtable_ptr > 0x7FFFFFFFFFFF {
-                return false;
+            for (base, bytes) in data {
+                for (i, qword) in bytes.chunks_exact(8).enumerate().skip(1) {
+                    if qword != target {
+                        continue;
+                    }
+
+                    let candidate = *base + (i as u64 - 1) * 8;
+                    if self.read_class(candidate).is_some_and(|c| c.name == "Instance") {
+                        return Some(candidate);
+                    }
+                }
             }
+        }
+
+        None
+    }
+
+    /// The longest run of descriptor pointers that includes `instance`
+    fn find_class_table(&self, data: &[(Address, Vec<u8>)], instance: Address) -> Option<Vec<Address>> {
+        let target = instance.as_u64().to_le_bytes();
+        let mut best: Option<Vec<Address>> = None;
+
+        for (_, bytes) in data {
+            let qwords: Vec<u64> = bytes.chunks_exact(8)
+                .map(|q| u64::from_le_bytes([q[0], q[1], q[2], q[3], q[4], q[5], q[6], q[7]]))
+                .collect();
 
-            let name_ptr = u64::from_le_bytes([
-                bytes[8], bytes[9], bytes[10], bytes[11],
-                bytes[12], bytes[13], bytes[14], bytes[15],
-            ]);
+            for (i, _) in bytes.chunks_exact(8).enumerate().filter(|(_, q)| *q == target) {
+                let is_class = |j: usize| self.read_class(Address::new(qwords[j])).is_some();
 
-            if name_ptr < 0x100000000 || name_ptr > 0x7FFFFFFFFFFF {
-                return false;
+                let mut first = i;
+                while first > 0 && i - first < MAX_TABLE_ENTRIES && is_class(first - 1) {
+                    first -= 1;
+                }
+                let mut last = i;
+                while last + 1 < qwords.len() && last - first < MAX_TABLE_ENTRIES && is_class(last + 1) {
+                    last += 1;
+                }
+
+                let run: Vec<Address> = qwords[first..=last].iter().map(|&q| Address::new(q)).collect();
+                if run.len() >= MIN_TABLE_ENTRIES && best.as_ref().is_none_or(|b| run.len() > b.len()) {
+                    best = Some(run);
+                }
             }
+        }
+
+        best
+    }
+
+    /// A descriptor whose name reads as a class identifier
+    fn read_class(&self, addr: Address) -> Option<ClassDescriptor> {
+        let class = self.descriptors.read_descriptor(addr)?;
+
+        let valid = class.name.len() > 1
+            && class.name.starts_with(|c: char| c.is_ascii_uppercase())
+            && class.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        valid.then_some(class)
+    }
+
+    /// Decodes `ldr <reg>, [x0, #imm]` or `add x0, x0, #imm` at the start of a
+    /// getter that returns within `MAX_GETTER_INSNS`
+    fn getter_field_offset(&self, getter: Address) -> Option<u64> {
+        let bytes = self.reader.read_bytes(getter, MAX_GETTER_INSNS * 4).ok()?;
+
+        let insns: Vec<u32> = bytes.chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+
+        // ret
+        insns.iter().position(|&insn| insn == 0xD65F03C0)?;
+
+        let insn = insns[0];
+        let imm12 = ((insn >> 10) & 0xFFF) as u64;
 
-            return true;
+        // add x0, x0, #imm (unshifted)
+        if (insn & 0xFFC003FF) == 0x91000000 {
+            return Some(imm12);
         }
 
-        false
+        // LDR (unsigned offset), integer or SIMD&FP, with x0 as the base
+        if (insn & 0x3B000000) == 0x39000000 && (insn >> 5) & 0x1F == 0 {
+            let size = insn >> 30;
+            let vector = (insn >> 26) & 1 == 1;
+            let opc = (insn >> 22) & 0x3;
+
+            let scale = match (vector, opc) {
+                (true, 0b11) if size == 0 => 16,
+                (true, 0b01) => 1 << size,
+                (false, 0b01) => 1 << size,
+                (false, 0b10 | 0b11) if size < 3 => 1 << size,
+                _ => return None,
+            };
+
+            return Some(imm12 * scale);
+        }
+
+        None
     }
 
-    fn find_string(&self, needle: &str, start: Address, end: Address) -> Option<Address> {
-        let needle_bytes = needle.as_bytes();
+    fn find_c_strings(&self, needle: &str, start: Address, end: Address) -> Vec<Address> {
+        let mut needle_bytes = needle.as_bytes().to_vec();
+        needle_bytes.push(0);
+
+        let mut found = Vec::new();
         let mut current = start;
 
         while current < end {
-            if let Ok(bytes) = self.reader.read_bytes(current, 4096) {
-                if let Some(pos) = bytes.windows(needle_bytes.len())
-                    .position(|w| w == needle_bytes)
-                {
-                    return Some(current + pos as u64);
+            let len = ((end - current) as usize).min(4096 + needle_bytes.len());
+            if let Ok(bytes) = self.reader.read_bytes(current, len) {
+                for pos in 0..bytes.len().saturating_sub(needle_bytes.len() - 1) {
+                    let at_start = pos == 0 || bytes[pos - 1] == 0;
+                    if at_start && pos < 4096 && bytes[pos..].starts_with(&needle_bytes) {
+                        found.push(current + pos as u64);
+                    }
                 }
             }
 
-            current = current + 4000;
+            current = current + 4096;
         }
 
-        None
+        found
     }
 }
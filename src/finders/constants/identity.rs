@@ -0,0 +1,97 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::memory::{Address, MemoryReader};
+use crate::finders::constants::types::get_identity_name;
+use crate::finders::result::{ConstantResult, ConstantValue};
+use crate::finders::roblox::identity::find_identity_propagator;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+const MAX_ROUTINE_INSNS: usize = 128;
+
+/// How far back the compared register's 32-bit load may sit
+const LOAD_LOOKBACK: usize = 4;
+
+/// Recovers the identity levels the identity-check routine compares against.
+/// The routine is located by the `identity` finder; every `cmp wN, #level`
+/// in its body with a known level becomes an `IdentityLevel_<Name>` constant.
+pub struct IdentityLevelFinder {
+    reader: Arc<dyn MemoryReader>,
+}
+
+impl IdentityLevelFinder {
+    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self { reader }
+    }
+
+    pub fn find_all(&self, start: Address, end: Address) -> Vec<ConstantResult> {
+        match find_identity_propagator(self.reader.clone(), start, end) {
+            Some(routine) => self.levels_in_routine(routine.address),
+            None => Vec::new(),
+        }
+    }
+
+    /// Confidence follows how much of the `ldr wN, [xM, #off]; cmp wN, #level;
+    /// b.cond` shape the comparison matches. Each level is reported once, at
+    /// its best-matching comparison.
+    pub fn levels_in_routine(&self, routine: Address) -> Vec<ConstantResult> {
+        let bytes = match self.reader.read_bytes(routine, MAX_ROUTINE_INSNS * 4) {
+            Ok(bytes) => bytes,
+            Err(_) => return Vec::new(),
+        };
+
+        let insns: Vec<u32> = bytes.chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .take_while(|&insn| (insn & 0xFFFFFC1F) != 0xD65F0000)
+            .collect();
+
+        let mut levels: BTreeMap<i64, ConstantResult> = BTreeMap::new();
+
+        for (i, &insn) in insns.iter().enumerate() {
+            // cmp wN, #imm12 (subs wzr, wN, #imm12, unshifted)
+            if (insn & 0xFFC0001F) != 0x7100001F {
+                continue;
+            }
+
+            let level = ((insn >> 10) & 0xFFF) as i64;
+            let name = get_identity_name(level as i32);
+            if name == "Unknown" {
+                continue;
+            }
+
+            let compared = (insn >> 5) & 0x1F;
+            let mut confidence = 0.5;
+
+            // b.cond straight after the compare
+            if insns.get(i + 1).is_some_and(|&next| (next & 0xFF000010) == 0x54000000) {
+                confidence += 0.2;
+            }
+
+            // ldr wN, [xM, #imm] feeding the compared register
+            let loaded = insns[i.saturating_sub(LOAD_LOOKBACK)..i].iter()
+                .any(|&prev| (prev & 0xFFC00000) == 0xB9400000 && (prev & 0x1F) == compared);
+            if loaded {
+                confidence += 0.2;
+            }
+
+            let result = ConstantResult::new(
+                format!("IdentityLevel_{}", name),
+                routine + (i * 4) as u64,
+                ConstantValue::Integer(level),
+            ).with_confidence(confidence);
+
+            match levels.get(&level) {
+                Some(existing) if existing.confidence >= confidence => {}
+                _ => {
+                    levels.insert(level, result);
+                }
+            }
+        }
+
+        levels.into_values().collect()
+    }
+}
+
+pub fn find_identity_levels(reader: Arc<dyn MemoryReader>, start: Address, end: Address) -> Vec<ConstantResult> {
+    IdentityLevelFinder::new(reader).find_all(start, end)
+}
@@ -1,15 +1,19 @@
 // Tue Jan 13 2026 - Alex
 
 pub mod finder;
+pub mod identity;
 pub mod types;
 
 pub use finder::ConstantFinder;
+pub use identity::IdentityLevelFinder;
 
 use crate::memory::{Address, MemoryReader};
 use crate::finders::result::ConstantResult;
 use std::sync::Arc;
 
 pub fn find_all_constants(reader: Arc<dyn MemoryReader>, start: Address, end: Address) -> Vec<ConstantResult> {
-    let finder = ConstantFinder::new(reader);
-    finder.find_all(start, end)
+    let finder = ConstantFinder::new(reader.clone());
+    let mut results = finder.find_all(start, end);
+    results.extend(identity::find_identity_levels(reader, start, end));
+    results
 }
@@ -0,0 +1,206 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::finders::fflags::database::get_database;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+const UNCATEGORIZED: &str = "Uncategorized";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FFlagSnapshotEntry {
+    pub category: String,
+    pub value: Option<Value>,
+}
+
+/// The found flags of one dump, keyed by name.
+#[derive(Debug, Clone, Default)]
+pub struct FFlagSnapshot {
+    pub flags: BTreeMap<String, FFlagSnapshotEntry>,
+}
+
+impl FFlagSnapshot {
+    /// Accepts both layouts this tool writes: the `fflags` command's array of
+    /// `{name, category, found_in_binary}` and the dumper's map of
+    /// `name -> {flag_type, value}`. Flags the array marks as not found are
+    /// skipped, so they show up as removed rather than unchanged.
+    pub fn from_json(json: &Value) -> Result<Self, String> {
+        let mut snapshot = Self::default();
+
+        match json.get("flags") {
+            Some(Value::Array(entries)) => {
+                for entry in entries {
+                    let name = match entry.get("name").and_then(Value::as_str) {
+                        Some(name) => name,
+                        None => continue,
+                    };
+
+                    if entry.get("found_in_binary").and_then(Value::as_bool) == Some(false) {
+                        continue;
+                    }
+
+                    let category = entry.get("category")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| category_for(name));
+
+                    snapshot.flags.insert(name.to_string(), FFlagSnapshotEntry {
+                        category,
+                        value: entry.get("value").filter(|v| !v.is_null()).cloned(),
+                    });
+                }
+            }
+            Some(Value::Object(entries)) => {
+                for (name, entry) in entries {
+                    snapshot.flags.insert(name.clone(), FFlagSnapshotEntry {
+                        category: category_for(name),
+                        value: entry.get("value").filter(|v| !v.is_null()).cloned(),
+                    });
+                }
+            }
+            _ => return Err("missing \"flags\" array or object".to_string()),
+        }
+
+        Ok(snapshot)
+    }
+}
+
+fn category_for(name: &str) -> String {
+    get_database()
+        .get(name)
+        .map(|flag| flag.category.to_string())
+        .unwrap_or_else(|| UNCATEGORIZED.to_string())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FFlagChange {
+    Added,
+    Removed,
+    ValueChanged { old: Value, new: Value },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FFlagDiffEntry {
+    pub name: String,
+    pub category: String,
+    pub change: FFlagChange,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FFlagDiff {
+    /// Sorted by category, then name.
+    pub entries: Vec<FFlagDiffEntry>,
+    pub unchanged: usize,
+}
+
+impl FFlagDiff {
+    /// A value only counts as changed when both dumps recorded one; a dump
+    /// written without values never reports changes.
+    pub fn compare(old: &FFlagSnapshot, new: &FFlagSnapshot) -> Self {
+        let mut diff = Self::default();
+
+        for (name, entry) in &new.flags {
+            match old.flags.get(name) {
+                None => diff.push(name, &entry.category, FFlagChange::Added),
+                Some(previous) => match (&previous.value, &entry.value) {
+                    (Some(old_value), Some(new_value)) if old_value != new_value => {
+                        diff.push(name, &entry.category, FFlagChange::ValueChanged {
+                            old: old_value.clone(),
+                            new: new_value.clone(),
+                        });
+                    }
+                    _ => diff.unchanged += 1,
+                },
+            }
+        }
+
+        for (name, entry) in &old.flags {
+            if !new.flags.contains_key(name) {
+                diff.push(name, &entry.category, FFlagChange::Removed);
+            }
+        }
+
+        diff.entries.sort_by(|a, b| (&a.category, &a.name).cmp(&(&b.category, &b.name)));
+        diff
+    }
+
+    fn push(&mut self, name: &str, category: &str, change: FFlagChange) {
+        self.entries.push(FFlagDiffEntry {
+            name: name.to_string(),
+            category: category.to_string(),
+            change,
+        });
+    }
+
+    pub fn by_category(&self) -> BTreeMap<&str, Vec<&FFlagDiffEntry>> {
+        let mut grouped: BTreeMap<&str, Vec<&FFlagDiffEntry>> = BTreeMap::new();
+
+        for entry in &self.entries {
+            grouped.entry(entry.category.as_str()).or_default().push(entry);
+        }
+
+        grouped
+    }
+
+    pub fn count(&self, matches: impl Fn(&FFlagChange) -> bool) -> usize {
+        self.entries.iter().filter(|e| matches(&e.change)).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_reports_added_removed_and_changed() {
+        let old = FFlagSnapshot::from_json(&json!({
+            "flags": {
+                "FFlagKept": { "flag_type": "FFlag", "value": true },
+                "FIntChanged": { "flag_type": "FInt", "value": 10 },
+                "FFlagGone": { "flag_type": "FFlag", "value": false },
+            }
+        })).unwrap();
+        let new = FFlagSnapshot::from_json(&json!({
+            "flags": {
+                "FFlagKept": { "flag_type": "FFlag", "value": true },
+                "FIntChanged": { "flag_type": "FInt", "value": 20 },
+                "FFlagNew": { "flag_type": "FFlag", "value": true },
+            }
+        })).unwrap();
+
+        let diff = FFlagDiff::compare(&old, &new);
+
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.count(|c| *c == FFlagChange::Added), 1);
+        assert_eq!(diff.count(|c| *c == FFlagChange::Removed), 1);
+
+        let changed = diff.entries.iter().find(|e| e.name == "FIntChanged").unwrap();
+        assert_eq!(changed.change, FFlagChange::ValueChanged { old: json!(10), new: json!(20) });
+    }
+
+    #[test]
+    fn test_array_layout_skips_unfound_and_groups_by_category() {
+        let old = FFlagSnapshot::from_json(&json!({
+            "flags": [
+                { "name": "A", "category": "Network", "found_in_binary": true },
+                { "name": "B", "category": "Rendering", "found_in_binary": false },
+            ]
+        })).unwrap();
+        let new = FFlagSnapshot::from_json(&json!({
+            "flags": [
+                { "name": "B", "category": "Rendering", "found_in_binary": true },
+            ]
+        })).unwrap();
+
+        let diff = FFlagDiff::compare(&old, &new);
+        let grouped = diff.by_category();
+
+        assert_eq!(grouped.keys().copied().collect::<Vec<_>>(), vec!["Network", "Rendering"]);
+        assert_eq!(grouped["Network"][0].change, FFlagChange::Removed);
+        assert_eq!(grouped["Rendering"][0].change, FFlagChange::Added);
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::memory::{Address, MemoryReader, MemoryError};
 use super::types::{FFlag, FFlagType, FFlagValue, FFlagCollection};
+use aho_corasick::AhoCorasick;
 use std::sync::Arc;
 use std::collections::HashSet;
 
@@ -118,6 +119,46 @@ impl FFlagFinder {
     }
 }
 
+/// Which of `names` occur anywhere in `data`, found in a single pass with
+/// one automaton over every name. Names that are prefixes of other names
+/// are still reported, and the scan stops once every name has been seen.
+pub fn find_flag_names<'a>(data: &[u8], names: &[&'a str]) -> HashSet<&'a str> {
+    find_flag_names_in_chunks(std::iter::once(data), names)
+}
+
+/// `find_flag_names` over a stream of chunks. Chunks must overlap by at
+/// least the longest name minus one byte, or names straddling a boundary
+/// are missed; see `BinaryMemory::chunks`.
+pub fn find_flag_names_in_chunks<'a, 'd>(chunks: impl IntoIterator<Item = &'d [u8]>, names: &[&'a str]) -> HashSet<&'a str> {
+    let mut found = HashSet::new();
+    let names: Vec<&str> = names.iter().copied().filter(|name| !name.is_empty()).collect();
+    if names.is_empty() {
+        return found;
+    }
+
+    let automaton = AhoCorasick::new(&names).ok();
+
+    for data in chunks {
+        match &automaton {
+            Some(automaton) => {
+                for m in automaton.find_overlapping_iter(data) {
+                    found.insert(names[m.pattern().as_usize()]);
+                }
+            }
+            // Too large to build; fall back to searching name by name
+            None => found.extend(names.iter().filter(|name| {
+                data.windows(name.len()).any(|w| w == name.as_bytes())
+            })),
+        }
+
+        if found.len() == names.len() {
+            break;
+        }
+    }
+
+    found
+}
+
 fn is_valid_flag_char(b: u8) -> bool {
     b.is_ascii_alphanumeric() || b == b'_'
 }
@@ -136,4 +177,15 @@ mod tests {
         assert!(!is_valid_flag_char(b' '));
         assert!(!is_valid_flag_char(b'.'));
     }
+
+    #[test]
+    fn test_find_flag_names() {
+        let data = b"\0FFlagDebugGraphics\0DFIntTaskSchedulerTargetFps\0";
+        let names = ["FFlagDebug", "FFlagDebugGraphics", "DFIntTaskSchedulerTargetFps", "FFlagMissing"];
+
+        let found = find_flag_names(data, &names);
+        assert_eq!(found.len(), 3);
+        assert!(found.contains("FFlagDebug"));
+        assert!(!found.contains("FFlagMissing"));
+    }
 }
@@ -5,9 +5,11 @@ pub mod parser;
 pub mod types;
 pub mod dumper;
 pub mod database;
+pub mod diff;
 
-pub use finder::FFlagFinder;
-pub use parser::FFlagParser;
-pub use types::{FFlag, FFlagType, FFlagValue, FFlagCollection, FFlagStats};
+pub use finder::{find_flag_names, find_flag_names_in_chunks, FFlagFinder};
+pub use parser::{FFlagParser, XorEncodedFlag};
+pub use types::{FFlag, FFlagType, FFlagValue, FFlagCollection, FFlagStats, CategoryStats};
 pub use dumper::FFlagDumper;
 pub use database::{FFlagDatabase, KnownFlag, get_database};
+pub use diff::{FFlagSnapshot, FFlagDiff, FFlagDiffEntry, FFlagChange};
@@ -3,7 +3,23 @@
 use crate::memory::{Address, MemoryReader, MemoryError};
 use super::types::{FFlag, FFlagType, FFlagValue, FFlagCollection, FFlagCategory};
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// Data segments are read in pieces, halved on failure, so a segment whose
+/// tail isn't backed by the file (zero-fill) still yields its mapped prefix.
+const XOR_READ_CHUNK: usize = 0x10000;
+const XOR_MAX_REGION: usize = 100_000_000;
+
+/// Byte differences used to index candidate names; shorter names are skipped.
+const XOR_PREFIX: usize = 4;
+
+/// A known flag name found single-byte XOR-encoded in a data segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XorEncodedFlag {
+    pub name: String,
+    pub address: u64,
+    pub key: u8,
+}
 
 pub struct FFlagParser {
     reader: Arc<dyn MemoryReader>,
@@ -157,6 +173,40 @@ impl FFlagParser {
         }
     }
 
+    /// Searches the data segments for `names` encoded with a single-byte XOR
+    /// key (0x01-0xFF). Each name is reported once, at its lowest address.
+    pub fn find_xor_encoded(&self, names: &[&str]) -> Vec<XorEncodedFlag> {
+        let regions = match self.reader.get_regions() {
+            Ok(regions) => regions,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut found: Vec<XorEncodedFlag> = Vec::new();
+
+        for region in regions.iter().filter(|r| r.is_data() && (r.is_writable() || r.name().starts_with("__DATA"))) {
+            let size = (region.size() as usize).min(XOR_MAX_REGION);
+            let mut data = Vec::new();
+            let mut chunk = XOR_READ_CHUNK;
+
+            while data.len() < size && chunk > 0 {
+                let len = chunk.min(size - data.len());
+                match self.reader.read_bytes(region.start() + data.len() as u64, len) {
+                    Ok(bytes) => data.extend_from_slice(&bytes),
+                    Err(_) => chunk /= 2,
+                }
+            }
+
+            let remaining: Vec<&str> = names.iter()
+                .filter(|name| !found.iter().any(|f| f.name == **name))
+                .copied()
+                .collect();
+            found.extend(find_xor_encoded_in(&data, region.start().as_u64(), &remaining));
+        }
+
+        found.sort_by_key(|f| f.address);
+        found
+    }
+
     pub fn find_registration_function(&self, collection: &FFlagCollection) -> Option<Address> {
         if collection.flags.is_empty() {
             return None;
@@ -183,3 +233,82 @@ impl FFlagParser {
         None
     }
 }
+
+/// XOR with one key leaves the difference between neighbouring bytes
+/// unchanged, so matching on those differences tries every key at once; the
+/// key is then the first data byte XOR the first name byte. Key 0 is
+/// plaintext and left to the normal scan. The decoded byte after a match
+/// must not extend the name, so a known name isn't reported from inside a
+/// longer one. Nothing is checked before it: padding there decodes to the key.
+pub fn find_xor_encoded_in(data: &[u8], base: u64, names: &[&str]) -> Vec<XorEncodedFlag> {
+    let mut by_prefix: HashMap<[u8; XOR_PREFIX], Vec<&[u8]>> = HashMap::new();
+    for name in names.iter().map(|n| n.as_bytes()).filter(|n| n.len() > XOR_PREFIX) {
+        let mut prefix = [0u8; XOR_PREFIX];
+        for (i, diff) in prefix.iter_mut().enumerate() {
+            *diff = name[i] ^ name[i + 1];
+        }
+        by_prefix.entry(prefix).or_default().push(name);
+    }
+
+    let is_name_char = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut seen: HashSet<&[u8]> = HashSet::new();
+    let mut found = Vec::new();
+
+    for i in 0..data.len().saturating_sub(XOR_PREFIX) {
+        let mut prefix = [0u8; XOR_PREFIX];
+        for (j, diff) in prefix.iter_mut().enumerate() {
+            *diff = data[i + j] ^ data[i + j + 1];
+        }
+
+        let candidates = match by_prefix.get(&prefix) {
+            Some(candidates) => candidates,
+            None => continue,
+        };
+
+        for name in candidates {
+            let key = data[i] ^ name[0];
+            if key == 0 || i + name.len() > data.len() || seen.contains(name) {
+                continue;
+            }
+
+            let matches = data[i..i + name.len()].iter().zip(name.iter()).all(|(d, n)| d ^ key == *n);
+            let bounded_after = data.get(i + name.len()).is_none_or(|b| !is_name_char(b ^ key));
+
+            if matches && bounded_after {
+                seen.insert(name);
+                found.push(XorEncodedFlag {
+                    name: String::from_utf8_lossy(name).to_string(),
+                    address: base + i as u64,
+                    key,
+                });
+            }
+        }
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xor_encoded_names_report_their_key() {
+        let mut data = vec![0u8; 16];
+        data.extend(b"FFlagDebugGraphicsPreferVulkan\0".iter().map(|b| b ^ 0x5A));
+        data.extend(b"FIntTaskSchedulerTargetFps\0");
+        data.extend(b"FFlagDebugGraphicsPreferMetalX".iter().map(|b| b ^ 0x21));
+
+        let found = find_xor_encoded_in(&data, 0x1000, &[
+            "FFlagDebugGraphicsPreferVulkan",
+            "FIntTaskSchedulerTargetFps",
+            "FFlagDebugGraphicsPreferMetal",
+        ]);
+
+        assert_eq!(found, vec![XorEncodedFlag {
+            name: "FFlagDebugGraphicsPreferVulkan".to_string(),
+            address: 0x1010,
+            key: 0x5A,
+        }]);
+    }
+}
@@ -1,6 +1,7 @@
 // Wed Jan 15 2026 - Alex
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -225,6 +226,8 @@ pub struct FFlagCollection {
     pub categories: Vec<FFlagCategory>,
     pub total_count: usize,
     pub by_type: FFlagStats,
+    #[serde(default)]
+    pub by_category: BTreeMap<String, CategoryStats>,
 }
 
 impl FFlagCollection {
@@ -234,6 +237,7 @@ impl FFlagCollection {
             categories: Vec::new(),
             total_count: 0,
             by_type: FFlagStats::default(),
+            by_category: BTreeMap::new(),
         }
     }
 
@@ -243,6 +247,25 @@ impl FFlagCollection {
         self.total_count += 1;
     }
 
+    /// Counts a checked flag towards its category, and towards `found` when
+    /// the binary ships it.
+    pub fn record(&mut self, category: &str, flag_type: &FFlagType, found: bool) {
+        let stats = self.by_category.entry(category.to_string()).or_default();
+        stats.total.increment(flag_type);
+        if found {
+            stats.found.increment(flag_type);
+        }
+    }
+
+    /// Categories ordered by found count, most first; ties by name.
+    pub fn categories_by_found(&self) -> Vec<(&str, &CategoryStats)> {
+        let mut categories: Vec<_> = self.by_category.iter()
+            .map(|(name, stats)| (name.as_str(), stats))
+            .collect();
+        categories.sort_by(|a, b| b.1.found_count().cmp(&a.1.found_count()).then(a.0.cmp(b.0)));
+        categories
+    }
+
     pub fn get(&self, name: &str) -> Option<&FFlag> {
         self.flags.iter().find(|f| f.name == name)
     }
@@ -269,6 +292,30 @@ impl Default for FFlagCollection {
     }
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CategoryStats {
+    pub found: FFlagStats,
+    pub total: FFlagStats,
+}
+
+impl CategoryStats {
+    pub fn found_count(&self) -> usize {
+        self.found.total()
+    }
+
+    pub fn total_count(&self) -> usize {
+        self.total.total()
+    }
+
+    pub fn found_ratio(&self) -> f64 {
+        if self.total_count() == 0 {
+            0.0
+        } else {
+            self.found_count() as f64 / self.total_count() as f64
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FFlagStats {
     pub fflags: usize,
@@ -324,3 +371,26 @@ impl FFlagStats {
         self.sfflags + self.sfints + self.sfstrings + self.sflogs
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_stats_sorted_by_found() {
+        let mut collection = FFlagCollection::new();
+        collection.record("Rendering", &FFlagType::FFlag, false);
+        collection.record("Network", &FFlagType::DFInt, true);
+        collection.record("Network", &FFlagType::FFlag, false);
+        collection.record("Audio", &FFlagType::FInt, true);
+        collection.record("Audio", &FFlagType::FFlag, true);
+
+        let ordered = collection.categories_by_found();
+        let names: Vec<&str> = ordered.iter().map(|(name, _)| *name).collect();
+
+        assert_eq!(names, vec!["Audio", "Network", "Rendering"]);
+        assert_eq!(ordered[1].1.found_count(), 1);
+        assert_eq!(ordered[1].1.total_count(), 2);
+        assert_eq!(ordered[1].1.found.dfints, 1);
+    }
+}
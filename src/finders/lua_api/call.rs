@@ -5,6 +5,7 @@ use crate::pattern::{Pattern, PatternMatcher};
 use crate::symbol::SymbolResolver;
 use crate::xref::XRefAnalyzer;
 use crate::finders::result::FinderResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 
 pub struct LuaCallFinder {
@@ -62,15 +63,12 @@ impl LuaCallFinder {
         ];
 
         for name in &symbol_names {
-            if let Some(addr) = resolver.resolve(name) {
-                return Some(FinderResult {
-                    name: "lua_call".to_string(),
-                    address: addr,
-                    confidence: 0.99,
-                    method: "symbol".to_string(),
-                    category: "lua_api".to_string(),
-                    signature: Some("void lua_call(lua_State *L, int nargs, int nresults)".to_string()),
-                });
+            if let Some(addr) = resolver.lookup(name).map(|symbol| symbol.address) {
+                return Some(FinderResult::new("lua_call".to_string(), addr, 0.99)
+                    .with_method("symbol")
+                    .with_category("lua_api")
+                    .with_signature("void lua_call(lua_State *L, int nargs, int nresults)")
+                    .with_factor(&format!("symbol \"{}\"", name), 0.99));
             }
         }
 
@@ -94,14 +92,12 @@ impl LuaCallFinder {
                 if let Some(addr) = addrs.first().copied() {
                     let func_start = self.find_function_start(addr);
                     if self.validate_lua_call(func_start) {
-                        return Some(FinderResult {
-                            name: "lua_call".to_string(),
-                            address: func_start,
-                            confidence: 0.85,
-                            method: "pattern".to_string(),
-                            category: "lua_api".to_string(),
-                            signature: Some("void lua_call(lua_State *L, int nargs, int nresults)".to_string()),
-                        });
+                        return Some(FinderResult::new("lua_call".to_string(), func_start, 0.85)
+                            .with_method("pattern")
+                            .with_category("lua_api")
+                            .with_signature("void lua_call(lua_State *L, int nargs, int nresults)")
+                            .with_factor("byte pattern", 0.65)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -126,14 +122,11 @@ impl LuaCallFinder {
                 for xref in xrefs {
                     let func_start = self.find_function_start(xref.from());
                     if self.validate_lua_call(func_start) {
-                        return Some(FinderResult {
-                            name: "lua_call".to_string(),
-                            address: func_start,
-                            confidence: 0.80,
-                            method: "xref".to_string(),
-                            category: "lua_api".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("lua_call".to_string(), func_start, 0.80)
+                            .with_method("xref")
+                            .with_category("lua_api")
+                            .with_factor(&format!("xref to \"{}\"", string), 0.60)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -150,14 +143,11 @@ impl LuaCallFinder {
                 if self.looks_like_lua_call(&bytes) {
                     let func_start = self.find_function_start(current);
                     if self.validate_lua_call(func_start) {
-                        return Some(FinderResult {
-                            name: "lua_call".to_string(),
-                            address: func_start,
-                            confidence: 0.70,
-                            method: "heuristic".to_string(),
-                            category: "lua_api".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("lua_call".to_string(), func_start, 0.70)
+                            .with_method("heuristic")
+                            .with_category("lua_api")
+                            .with_factor("body heuristic", 0.50)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -180,8 +170,8 @@ impl LuaCallFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
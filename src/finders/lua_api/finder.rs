@@ -5,6 +5,7 @@ use crate::pattern::{Pattern, PatternMatcher};
 use crate::symbol::SymbolResolver;
 use crate::xref::XRefAnalyzer;
 use crate::finders::result::FinderResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 use std::collections::HashMap;
 
@@ -43,7 +44,9 @@ impl LuaApiFinder {
         let functions = self.get_target_functions();
 
         for (name, patterns, symbol_names) in functions {
-            if let Some(result) = self.find_function(&name, &patterns, &symbol_names, start, end) {
+            let found = self.find_function(&name, &patterns, &symbol_names, start, end)
+                .map(|result| result.follow_thunk(self.reader.as_ref()));
+            if let Some(result) = found {
                 self.found_functions.insert(name, result.clone());
                 results.push(result);
             }
@@ -172,14 +175,13 @@ impl LuaApiFinder {
         let resolver = self.symbol_resolver.as_ref()?;
 
         for symbol_name in symbol_names {
-            if let Some(addr) = resolver.resolve(symbol_name) {
+            if let Some(addr) = resolver.lookup(symbol_name).map(|symbol| symbol.address) {
                 return Some(FinderResult {
-                    name: name.to_string(),
-                    address: addr,
-                    confidence: 0.99,
-                    method: "symbol".to_string(),
-                    category: "lua_api".to_string(),
                     signature: self.get_signature(name),
+                    ..FinderResult::new(name.to_string(), addr, 0.99)
+                        .with_method("symbol")
+                        .with_category("lua_api")
+                        .with_factor(&format!("symbol \"{}\"", symbol_name), 0.99)
                 });
             }
         }
@@ -208,12 +210,12 @@ impl LuaApiFinder {
 
                     if self.validate_lua_function(name, func_start) {
                         return Some(FinderResult {
-                            name: name.to_string(),
-                            address: func_start,
-                            confidence: 0.85,
-                            method: "pattern".to_string(),
-                            category: "lua_api".to_string(),
                             signature: self.get_signature(name),
+                            ..FinderResult::new(name.to_string(), func_start, 0.85)
+                                .with_method("pattern")
+                                .with_category("lua_api")
+                                .with_factor("byte pattern", 0.65)
+                                .with_factor("prologue and body checks", 0.20)
                         });
                     }
                 }
@@ -238,12 +240,12 @@ impl LuaApiFinder {
 
                     if self.validate_lua_function(name, func_start) {
                         return Some(FinderResult {
-                            name: name.to_string(),
-                            address: func_start,
-                            confidence: 0.70,
-                            method: "xref_heuristic".to_string(),
-                            category: "lua_api".to_string(),
                             signature: self.get_signature(name),
+                            ..FinderResult::new(name.to_string(), func_start, 0.70)
+                                .with_method("xref_heuristic")
+                                .with_category("lua_api")
+                                .with_factor("calls lua_gettop", 0.50)
+                                .with_factor("prologue and body checks", 0.20)
                         });
                     }
                 }
@@ -269,8 +271,8 @@ impl LuaApiFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
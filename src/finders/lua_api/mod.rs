@@ -3,7 +3,9 @@
 pub mod call;
 pub mod finder;
 pub mod patterns;
+pub mod print;
 
 pub use call::LuaApiCallAnalyzer;
 pub use finder::LuaApiFinder;
 pub use patterns::LuaApiPatterns;
+pub use print::OutputFunctionFinder;
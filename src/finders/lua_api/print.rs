@@ -0,0 +1,86 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::memory::{Address, MemoryReader};
+use crate::finders::result::FinderResult;
+use crate::xref::{CallGraph, ChainAnalyzer};
+use std::sync::Arc;
+
+/// Strings only `print` loads, in order
+pub const PRINT_ANCHORS: &[&str] = &["'tostring' must return a string to 'print'"];
+
+/// Strings only `warn` loads, in order
+pub const WARN_ANCHORS: &[&str] = &["'tostring' must return a string to 'warn'"];
+
+/// Base confidence for the one function that loads an anchor
+const ANCHOR_CONFIDENCE: f64 = 0.85;
+
+/// Taken off for every other function loading the same anchor
+const SHARED_PENALTY: f64 = 0.15;
+const MAX_SHARED_PENALTY: f64 = 0.45;
+
+/// Taken off when no frame-saving prologue bounds the function
+const BOUNDARY_PENALTY: f64 = 0.1;
+
+/// Locates the C functions Luau registers as globals through the error
+/// and output strings they load, following each load back to the
+/// function that contains it.
+pub struct OutputFunctionFinder {
+    reader: Arc<dyn MemoryReader>,
+}
+
+impl OutputFunctionFinder {
+    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self { reader }
+    }
+
+    /// The function loading the first of `anchors` found. When several
+    /// functions load it, the best-ranked one is reported with less
+    /// confidence for each of the others.
+    pub fn find(&self, name: &str, anchors: &[&str], start: Address, end: Address) -> Option<FinderResult> {
+        let chains = ChainAnalyzer::new(self.reader.clone(), CallGraph::new());
+
+        for needle in anchors {
+            let found = chains.find_string_chains(needle, start, end);
+            let best = match found.first() {
+                Some(chain) => chain,
+                None => continue,
+            };
+
+            let mut functions: Vec<Address> = found.iter().map(|c| c.end).collect();
+            functions.sort();
+            functions.dedup();
+
+            let others = functions.len() - 1;
+            let shared = (others as f64 * SHARED_PENALTY).min(MAX_SHARED_PENALTY);
+            let boundary = best.links.last()
+                .and_then(|link| link.metadata.get("boundary"))
+                .map_or("none", String::as_str);
+            let unbounded = if boundary == "prologue" { 0.0 } else { BOUNDARY_PENALTY };
+
+            let mut result = FinderResult::new(name.to_string(), best.end, ANCHOR_CONFIDENCE - shared - unbounded)
+                .with_method("string_xref")
+                .with_category("lua_api")
+                .with_signature(&format!("int {}(lua_State* L)", name))
+                .with_factor("string anchor", ANCHOR_CONFIDENCE);
+
+            if others > 0 {
+                result = result.with_factor(&format!("{} other functions load \"{}\"", others, needle), -shared);
+            }
+            if unbounded > 0.0 {
+                result = result.with_factor(&format!("entry found by {}", boundary), -unbounded);
+            }
+
+            return Some(result);
+        }
+
+        None
+    }
+}
+
+pub fn find_print(reader: Arc<dyn MemoryReader>, start: Address, end: Address) -> Option<FinderResult> {
+    OutputFunctionFinder::new(reader).find("print", PRINT_ANCHORS, start, end)
+}
+
+pub fn find_warn(reader: Arc<dyn MemoryReader>, start: Address, end: Address) -> Option<FinderResult> {
+    OutputFunctionFinder::new(reader).find("warn", WARN_ANCHORS, start, end)
+}
@@ -3,12 +3,15 @@
 use crate::memory::{Address, MemoryReader};
 use crate::finders::result::MethodResult;
 use crate::finders::classes::vtable::VTableAnalyzer;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
+use crate::symbol::SymbolResolver;
 use std::sync::Arc;
 use std::collections::HashMap;
 
 pub struct MethodFinder {
     reader: Arc<dyn MemoryReader>,
     vtable_analyzer: VTableAnalyzer,
+    symbol_resolver: Option<Arc<SymbolResolver>>,
 }
 
 impl MethodFinder {
@@ -16,9 +19,15 @@ impl MethodFinder {
         Self {
             vtable_analyzer: VTableAnalyzer::new(reader.clone()),
             reader,
+            symbol_resolver: None,
         }
     }
 
+    pub fn with_symbols(mut self, resolver: Arc<SymbolResolver>) -> Self {
+        self.symbol_resolver = Some(resolver);
+        self
+    }
+
     pub fn find_all(&self, start: Address, end: Address) -> Vec<MethodResult> {
         let mut results = Vec::new();
 
@@ -160,6 +169,15 @@ impl MethodFinder {
     }
 
     fn find_method(&self, class_name: &str, method_name: &str, signature: &str, start: Address, end: Address) -> Option<MethodResult> {
+        if let Some(addr) = self.find_method_by_symbol(class_name, method_name, start, end) {
+            return Some(MethodResult::new(
+                class_name.to_string(),
+                method_name.to_string(),
+                addr,
+            ).with_signature(signature)
+             .with_confidence(1.0));
+        }
+
         if let Some(addr) = self.find_method_by_string(method_name, start, end) {
             return Some(MethodResult::new(
                 class_name.to_string(),
@@ -172,6 +190,14 @@ impl MethodFinder {
         None
     }
 
+    /// `RBX::<class>::<method>` in the symbol table, if it lies in range
+    fn find_method_by_symbol(&self, class_name: &str, method_name: &str, start: Address, end: Address) -> Option<Address> {
+        let resolver = self.symbol_resolver.as_ref()?;
+        let symbol = resolver.lookup(&format!("RBX::{}::{}", class_name, method_name))?;
+
+        (symbol.is_function() && symbol.address >= start && symbol.address < end).then_some(symbol.address)
+    }
+
     fn find_method_by_string(&self, method_name: &str, start: Address, end: Address) -> Option<Address> {
         if let Some(string_addr) = self.find_string(method_name, start, end) {
             if let Some(xref_addr) = self.find_xref(string_addr, start, end) {
@@ -245,8 +271,8 @@ impl MethodFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
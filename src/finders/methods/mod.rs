@@ -8,9 +8,12 @@ pub use finder::MethodFinder;
 
 use crate::memory::{Address, MemoryReader};
 use crate::finders::result::MethodResult;
+use crate::symbol::SymbolResolver;
 use std::sync::Arc;
 
-pub fn find_all_methods(reader: Arc<dyn MemoryReader>, start: Address, end: Address) -> Vec<MethodResult> {
-    let finder = MethodFinder::new(reader);
+/// Symbol-table hits win over the string heuristics when the binary
+/// isn't stripped. `symbols` is built once per scan and shared by every range.
+pub fn find_all_methods(reader: Arc<dyn MemoryReader>, symbols: Arc<SymbolResolver>, start: Address, end: Address) -> Vec<MethodResult> {
+    let finder = MethodFinder::new(reader).with_symbols(symbols);
     finder.find_all(start, end)
 }
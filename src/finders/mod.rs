@@ -10,32 +10,47 @@ pub mod methods;
 pub mod constants;
 pub mod result;
 pub mod fflags;
+pub mod traits;
 
 pub use result::{
     FinderResult, StructureOffsetResult, ClassResult,
     PropertyResult, MethodResult, ConstantResult,
-    ConstantValue, CombinedResults
+    ConstantValue, CombinedResults, ConfidenceFactor
 };
 pub use roblox::RobloxFinders;
+pub use traits::Finder;
 
 use crate::memory::{Address, MemoryReader};
+use crate::symbol::SymbolResolver;
 use std::sync::Arc;
 
 pub struct AllFinders {
     reader: Arc<dyn MemoryReader>,
+    symbols: Arc<SymbolResolver>,
+    reflection: classes::ReflectionCache,
     roblox_finders: RobloxFinders,
 }
 
 impl AllFinders {
     pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
         let roblox_finders = RobloxFinders::new(reader.clone());
+        let symbols = Arc::new(SymbolResolver::new(reader.clone()));
 
         Self {
             reader,
+            symbols,
+            reflection: classes::ReflectionCache::new(),
             roblox_finders,
         }
     }
 
+    /// Registers extra finders; they run after the built-in function
+    /// finders and their results are reported as functions.
+    pub fn with_finders(mut self, finders: Vec<Box<dyn Finder>>) -> Self {
+        self.roblox_finders = self.roblox_finders.with_finders(finders);
+        self
+    }
+
     pub fn find_all(&self, start: Address, end: Address) -> CombinedResults {
         let mut results = CombinedResults::new();
 
@@ -48,17 +63,17 @@ impl AllFinders {
             results.add_structure_offset(result);
         }
 
-        let class_results = classes::find_all_classes(self.reader.clone(), start, end);
+        let class_results = classes::find_all_classes(self.reader.clone(), &self.symbols, &self.reflection, start, end);
         for result in class_results {
             results.add_class(result);
         }
 
-        let property_results = properties::find_all_properties(self.reader.clone(), start, end);
+        let property_results = properties::find_all_properties(self.reader.clone(), &self.reflection, start, end);
         for result in property_results {
             results.add_property(result);
         }
 
-        let method_results = methods::find_all_methods(self.reader.clone(), start, end);
+        let method_results = methods::find_all_methods(self.reader.clone(), self.symbols.clone(), start, end);
         for result in method_results {
             results.add_method(result);
         }
@@ -2,6 +2,7 @@
 
 use crate::memory::{Address, MemoryReader};
 use crate::finders::result::PropertyResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 use std::collections::HashMap;
 
@@ -246,8 +247,8 @@ impl PropertyFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
@@ -7,10 +7,28 @@ pub mod types;
 pub use finder::PropertyFinder;
 
 use crate::memory::{Address, MemoryReader};
+use crate::finders::classes::{ReflectionCache, ReflectionFinder};
 use crate::finders::result::PropertyResult;
+use std::collections::HashSet;
 use std::sync::Arc;
 
-pub fn find_all_properties(reader: Arc<dyn MemoryReader>, start: Address, end: Address) -> Vec<PropertyResult> {
+/// Properties walked from the reflection table, plus any known property the
+/// table didn't list. The walk comes from `reflection`, so a class pass over
+/// the same range doesn't repeat it.
+pub fn find_all_properties(reader: Arc<dyn MemoryReader>, reflection: &ReflectionCache, start: Address, end: Address) -> Vec<PropertyResult> {
+    let reflection_finder = ReflectionFinder::new(reader.clone());
+    let mut results = reflection_finder.property_results(&reflection.walk(&reflection_finder, start, end));
+
+    let seen: HashSet<(String, String)> = results.iter()
+        .map(|r| (r.class_name.clone(), r.property_name.clone()))
+        .collect();
+
     let finder = PropertyFinder::new(reader);
-    finder.find_all(start, end)
+    for result in finder.find_all(start, end) {
+        if !seen.contains(&(result.class_name.clone(), result.property_name.clone())) {
+            results.push(result);
+        }
+    }
+
+    results
 }
@@ -1,8 +1,9 @@
 // Tue Jan 13 2026 - Alex
 
-use crate::memory::Address;
+use crate::memory::{Address, MemoryReader};
+use crate::analysis::{classify_function, resolve_thunks, FunctionKind};
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FinderResult {
@@ -12,6 +13,27 @@ pub struct FinderResult {
     pub method: String,
     pub category: String,
     pub signature: Option<String>,
+    /// Individual heuristic contributions that add up to `confidence`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub breakdown: Vec<ConfidenceFactor>,
+    #[serde(default)]
+    pub kind: FunctionKind,
+}
+
+/// One heuristic's contribution to a result's confidence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceFactor {
+    pub label: String,
+    pub delta: f64,
+}
+
+impl ConfidenceFactor {
+    pub fn new(label: &str, delta: f64) -> Self {
+        Self {
+            label: label.to_string(),
+            delta,
+        }
+    }
 }
 
 impl FinderResult {
@@ -23,6 +45,8 @@ impl FinderResult {
             method: "unknown".to_string(),
             category: "unknown".to_string(),
             signature: None,
+            breakdown: Vec::new(),
+            kind: FunctionKind::Function,
         }
     }
 
@@ -41,6 +65,33 @@ impl FinderResult {
         self
     }
 
+    /// Record a heuristic contribution; the caller keeps `confidence` in sync
+    pub fn with_factor(mut self, label: &str, delta: f64) -> Self {
+        self.breakdown.push(ConfidenceFactor::new(label, delta));
+        self
+    }
+
+    /// Classifies the address and moves a result that landed on a tail-call
+    /// thunk to the body the thunk jumps to. Stubs, and thunks whose body
+    /// can't be read, stay put and are marked as such; globals are left alone.
+    pub fn follow_thunk(mut self, reader: &dyn MemoryReader) -> Self {
+        if self.category == "global" {
+            return self;
+        }
+
+        self.kind = classify_function(reader, self.address).0;
+
+        if self.kind == FunctionKind::Thunk {
+            let body = resolve_thunks(reader, self.address);
+            if reader.read_u32(body).is_ok() && classify_function(reader, body).0 == FunctionKind::Function {
+                self.address = body;
+                self.kind = FunctionKind::Function;
+            }
+        }
+
+        self
+    }
+
     pub fn is_high_confidence(&self) -> bool {
         self.confidence >= 0.85
     }
@@ -321,6 +372,33 @@ impl CombinedResults {
             + self.constants.iter().filter(|c| c.confidence >= 0.85).count()
     }
 
+    /// Address or offset reported under `name`: a function, class or
+    /// constant name, or `Structure.field` for a structure offset. The most
+    /// confident result wins when several share a name.
+    pub fn lookup(&self, name: &str) -> Option<u64> {
+        let mut candidates: Vec<(f64, u64)> = Vec::new();
+
+        candidates.extend(self.functions.iter()
+            .filter(|f| f.name == name)
+            .map(|f| (f.confidence, f.address.as_u64())));
+        candidates.extend(self.classes.iter()
+            .filter(|c| c.name == name)
+            .map(|c| (c.confidence, c.address.as_u64())));
+        candidates.extend(self.constants.iter()
+            .filter(|c| c.name == name)
+            .map(|c| (c.confidence, c.address.as_u64())));
+
+        if let Some((structure, field)) = name.split_once('.') {
+            candidates.extend(self.structure_offsets.iter()
+                .filter(|s| s.structure_name == structure && s.field_name == field)
+                .map(|s| (s.confidence, s.offset)));
+        }
+
+        candidates.into_iter()
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, value)| value)
+    }
+
     pub fn merge(&mut self, other: CombinedResults) {
         self.functions.extend(other.functions);
         self.structure_offsets.extend(other.structure_offsets);
@@ -330,10 +408,39 @@ impl CombinedResults {
         self.constants.extend(other.constants);
     }
 
-    pub fn to_json_map(&self) -> HashMap<String, serde_json::Value> {
-        let mut map = HashMap::new();
+    /// Shift every absolute address from `from` onto `to`. Structure and
+    /// property field offsets are base-independent and left untouched.
+    pub fn rebase(&mut self, from: u64, to: u64) {
+        let shift = |addr: Address| Address::new(addr.as_u64().wrapping_sub(from).wrapping_add(to));
+
+        for func in &mut self.functions {
+            func.address = shift(func.address);
+        }
+        for class in &mut self.classes {
+            class.address = shift(class.address);
+            class.vtable_address = class.vtable_address.map(shift);
+        }
+        for prop in &mut self.properties {
+            prop.getter_address = prop.getter_address.map(shift);
+            prop.setter_address = prop.setter_address.map(shift);
+        }
+        for method in &mut self.methods {
+            method.address = shift(method.address);
+        }
+        for constant in &mut self.constants {
+            constant.address = shift(constant.address);
+            if let ConstantValue::Pointer(ptr) = &mut constant.value {
+                *ptr = shift(*ptr);
+            }
+        }
+    }
+
+    /// Scan JSON sections keyed by name. Maps are ordered and per-class lists
+    /// sorted so identical results always serialize to identical bytes.
+    pub fn to_json_map(&self) -> BTreeMap<String, serde_json::Value> {
+        let mut map = BTreeMap::new();
 
-        let mut functions_map = HashMap::new();
+        let mut functions_map = BTreeMap::new();
         for func in &self.functions {
             functions_map.insert(func.name.clone(), serde_json::json!({
                 "address": format!("0x{:x}", func.address.as_u64()),
@@ -341,11 +448,12 @@ impl CombinedResults {
                 "method": func.method,
                 "category": func.category,
                 "signature": func.signature,
+                "kind": func.kind.as_str(),
             }));
         }
         map.insert("functions".to_string(), serde_json::to_value(functions_map).unwrap());
 
-        let mut structure_offsets_map: HashMap<String, HashMap<String, serde_json::Value>> = HashMap::new();
+        let mut structure_offsets_map: BTreeMap<String, BTreeMap<String, serde_json::Value>> = BTreeMap::new();
         for offset in &self.structure_offsets {
             let entry = structure_offsets_map.entry(offset.structure_name.clone()).or_default();
             entry.insert(offset.field_name.clone(), serde_json::json!({
@@ -357,7 +465,7 @@ impl CombinedResults {
         }
         map.insert("structure_offsets".to_string(), serde_json::to_value(structure_offsets_map).unwrap());
 
-        let mut classes_map = HashMap::new();
+        let mut classes_map = BTreeMap::new();
         for class in &self.classes {
             classes_map.insert(class.name.clone(), serde_json::json!({
                 "address": format!("0x{:x}", class.address.as_u64()),
@@ -369,8 +477,11 @@ impl CombinedResults {
         }
         map.insert("classes".to_string(), serde_json::to_value(classes_map).unwrap());
 
-        let mut properties_map: HashMap<String, Vec<serde_json::Value>> = HashMap::new();
-        for prop in &self.properties {
+        let mut properties: Vec<&PropertyResult> = self.properties.iter().collect();
+        properties.sort_by(|a, b| (&a.property_name, a.getter_address).cmp(&(&b.property_name, b.getter_address)));
+
+        let mut properties_map: BTreeMap<String, Vec<serde_json::Value>> = BTreeMap::new();
+        for prop in properties {
             let entry = properties_map.entry(prop.class_name.clone()).or_default();
             entry.push(serde_json::json!({
                 "name": prop.property_name,
@@ -383,8 +494,11 @@ impl CombinedResults {
         }
         map.insert("properties".to_string(), serde_json::to_value(properties_map).unwrap());
 
-        let mut methods_map: HashMap<String, Vec<serde_json::Value>> = HashMap::new();
-        for method in &self.methods {
+        let mut methods: Vec<&MethodResult> = self.methods.iter().collect();
+        methods.sort_by(|a, b| (&a.method_name, a.address).cmp(&(&b.method_name, b.address)));
+
+        let mut methods_map: BTreeMap<String, Vec<serde_json::Value>> = BTreeMap::new();
+        for method in methods {
             let entry = methods_map.entry(method.class_name.clone()).or_default();
             entry.push(serde_json::json!({
                 "name": method.method_name,
@@ -397,7 +511,7 @@ impl CombinedResults {
         }
         map.insert("methods".to_string(), serde_json::to_value(methods_map).unwrap());
 
-        let mut constants_map = HashMap::new();
+        let mut constants_map = BTreeMap::new();
         for constant in &self.constants {
             let value_repr = match &constant.value {
                 ConstantValue::Integer(i) => serde_json::json!(i),
@@ -3,8 +3,18 @@
 use crate::memory::{Address, MemoryReader};
 use crate::pattern::Pattern;
 use crate::finders::result::FinderResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 
+/// Strings the string-reference search looks for, in order
+pub const ANCHOR_STRINGS: &[&str] = &[
+    "Job",
+    "TaskScheduler",
+    "WaitingHybridScripts",
+    "Render",
+    "Heartbeat",
+];
+
 pub struct CreateJobFinder {
     reader: Arc<dyn MemoryReader>,
 }
@@ -41,14 +51,12 @@ impl CreateJobFinder {
                         let addr = current + offset as u64;
 
                         if self.validate_create_job(addr) {
-                            return Some(FinderResult {
-                                name: "CreateJob".to_string(),
-                                address: addr,
-                                confidence: 0.86,
-                                method: "pattern".to_string(),
-                                category: "roblox".to_string(),
-                                signature: Some("Job* CreateJob(TaskScheduler* scheduler, const char* name, JobPriority priority)".to_string()),
-                            });
+                            return Some(FinderResult::new("CreateJob".to_string(), addr, 0.86)
+                                .with_method("pattern")
+                                .with_category("roblox")
+                                .with_signature("Job* CreateJob(TaskScheduler* scheduler, const char* name, JobPriority priority)")
+                                .with_factor("byte pattern", 0.66)
+                                .with_factor("prologue and body checks", 0.20));
                         }
                     }
                 }
@@ -61,28 +69,17 @@ impl CreateJobFinder {
     }
 
     fn find_by_string_ref(&self, start: Address, end: Address) -> Option<FinderResult> {
-        let search_strings = [
-            "Job",
-            "TaskScheduler",
-            "WaitingHybridScripts",
-            "Render",
-            "Heartbeat",
-        ];
-
-        for needle in &search_strings {
+        for needle in ANCHOR_STRINGS {
             if let Some(string_addr) = self.find_string(needle, start, end) {
                 if let Some(func_addr) = self.find_xref_to_string(string_addr, start, end) {
                     let func_start = self.find_function_start(func_addr);
 
                     if self.validate_create_job(func_start) {
-                        return Some(FinderResult {
-                            name: "CreateJob".to_string(),
-                            address: func_start,
-                            confidence: 0.80,
-                            method: "string_xref".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("CreateJob".to_string(), func_start, 0.80)
+                            .with_method("string_xref")
+                            .with_category("roblox")
+                            .with_factor(&format!("string xref \"{}\"", needle), 0.60)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -100,14 +97,11 @@ impl CreateJobFinder {
                     let func_start = self.find_function_start(current);
 
                     if self.validate_create_job(func_start) {
-                        return Some(FinderResult {
-                            name: "CreateJob".to_string(),
-                            address: func_start,
-                            confidence: 0.62,
-                            method: "heuristic".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("CreateJob".to_string(), func_start, 0.62)
+                            .with_method("heuristic")
+                            .with_category("roblox")
+                            .with_factor("body heuristic", 0.42)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -252,8 +246,8 @@ impl CreateJobFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
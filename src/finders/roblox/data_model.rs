@@ -0,0 +1,214 @@
+// Tue Jan 13 2026 - Alex
+
+use crate::memory::{Address, MemoryReader};
+use crate::finders::result::FinderResult;
+use crate::analysis::string::find_c_strings;
+use crate::config;
+use crate::utils::arm64::Arm64Utils;
+use crate::xref::chains::enclosing_function;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+const MAX_ANCHOR_XREFS: usize = 16;
+const MAX_FUNCTION_INSNS: usize = 256;
+const MAX_GETTER_INSNS: usize = 8;
+
+/// Strings the string-reference search looks for, in order
+pub const ANCHOR_STRINGS: &[&str] = &["FakeDataModel", "DataModel"];
+
+pub struct DataModelFinder {
+    reader: Arc<dyn MemoryReader>,
+}
+
+impl DataModelFinder {
+    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self { reader }
+    }
+
+    /// Returns the accessor (`GetDataModel`) and the global it loads
+    /// (`DataModelPointer`), or nothing if no anchor string is referenced.
+    pub fn find(&self, start: Address, end: Address) -> Vec<FinderResult> {
+        for needle in ANCHOR_STRINGS {
+            let string_addr = match find_c_strings(self.reader.as_ref(), needle, start..end).into_iter().next() {
+                Some(addr) => addr,
+                None => continue,
+            };
+
+            let anchor_funcs: HashSet<u64> = self.find_xrefs_to_string(string_addr, start, end)
+                .into_iter()
+                .map(|xref| enclosing_function(self.reader.as_ref(), Address::new(xref)).0.as_u64())
+                .collect();
+
+            if let Some(results) = self.resolve_from_anchors(&anchor_funcs, string_addr, start, end) {
+                return results;
+            }
+        }
+
+        Vec::new()
+    }
+
+    fn resolve_from_anchors(&self, anchor_funcs: &HashSet<u64>, string_addr: Address, start: Address, end: Address) -> Option<Vec<FinderResult>> {
+        let mut candidates: HashSet<u64> = HashSet::new();
+
+        for &func in anchor_funcs {
+            for (_, global, _) in self.global_refs_in_function(Address::new(func)) {
+                if global != string_addr.as_u64() {
+                    candidates.insert(global);
+                }
+            }
+        }
+
+        if candidates.is_empty() {
+            return None;
+        }
+
+        // Every function touching a candidate counts as one independent reference
+        let mut referencing: HashMap<u64, HashSet<u64>> = HashMap::new();
+        for (at, global, _) in self.global_refs_in_range(start, end) {
+            if candidates.contains(&global) {
+                let func = enclosing_function(self.reader.as_ref(), Address::new(at)).0.as_u64();
+                referencing.entry(global).or_default().insert(func);
+            }
+        }
+
+        let (global, funcs) = referencing.into_iter()
+            .max_by_key(|(global, funcs)| (funcs.len(), std::cmp::Reverse(*global)))?;
+
+        let getter = funcs.iter()
+            .copied()
+            .filter(|func| self.is_getter_for(Address::new(*func), global))
+            .min();
+
+        let accessor = getter
+            .or_else(|| anchor_funcs.iter().copied().filter(|f| funcs.contains(f)).min())?;
+
+        let corroborating = funcs.len().saturating_sub(1);
+        let per_ref = 0.1 * config::confidence().xref_count;
+        let corroboration = (corroborating as f64 * per_ref).min(3.0 * per_ref);
+        let getter_bonus = if getter.is_some() { 0.15 } else { 0.0 };
+        let confidence = 0.5 + corroboration + getter_bonus;
+
+        let with_factors = |result: FinderResult| {
+            let mut result = result.with_factor("string anchor", 0.5);
+
+            if corroborating > 0 {
+                result = result.with_factor(&format!("{} corroborating refs", corroborating), corroboration);
+            }
+
+            if getter.is_some() {
+                result.with_factor("singleton getter", getter_bonus)
+            } else {
+                result
+            }
+        };
+
+        Some(vec![
+            with_factors(FinderResult::new("GetDataModel".to_string(), Address::new(accessor), confidence)
+                .with_method("string_xref")
+                .with_category("roblox")
+                .with_signature("DataModel* GetDataModel()")),
+            with_factors(FinderResult::new("DataModelPointer".to_string(), Address::new(global), confidence)
+                .with_method("string_xref")
+                .with_category("global")),
+        ])
+    }
+
+    /// A getter is a leaf that loads the global straight into x0 and returns.
+    fn is_getter_for(&self, func: Address, global: u64) -> bool {
+        let bytes = match self.reader.read_bytes(func, MAX_GETTER_INSNS * 4) {
+            Ok(bytes) => bytes,
+            Err(_) => return false,
+        };
+
+        let insns: Vec<u32> = bytes.chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+
+        let ret_index = match insns.iter().position(|&insn| (insn & 0xFFFFFC1F) == 0xD65F0000) {
+            Some(i) => i,
+            None => return false,
+        };
+
+        (0..ret_index.saturating_sub(1)).any(|i| {
+            let at = func.as_u64() + i as u64 * 4;
+            matches!(resolve_pair(at, insns[i], insns[i + 1]), Some((target, 0)) if target == global)
+                && (insns[i + 1] & 0xFFC00000) == 0xF9400000
+        })
+    }
+
+    /// (pair address, resolved global, destination register) for each
+    /// ADRP+ADD / ADRP+LDR in the function body, up to its first RET.
+    fn global_refs_in_function(&self, func: Address) -> Vec<(u64, u64, u32)> {
+        let bytes = match self.reader.read_bytes(func, MAX_FUNCTION_INSNS * 4) {
+            Ok(bytes) => bytes,
+            Err(_) => return Vec::new(),
+        };
+
+        let len = bytes.chunks_exact(4)
+            .position(|c| (u32::from_le_bytes([c[0], c[1], c[2], c[3]]) & 0xFFFFFC1F) == 0xD65F0000)
+            .map(|i| (i + 1) * 4)
+            .unwrap_or(bytes.len());
+
+        collect_pairs(func.as_u64(), &bytes[..len])
+    }
+
+    fn global_refs_in_range(&self, start: Address, end: Address) -> Vec<(u64, u64, u32)> {
+        let mut refs = Vec::new();
+        let mut current = start;
+
+        while current < end {
+            if let Ok(bytes) = self.reader.read_bytes(current, 4096) {
+                // 4000-byte stride leaves a window overlap so pairs are never split
+                let pairs = collect_pairs(current.as_u64(), &bytes);
+                refs.extend(pairs.into_iter().filter(|(at, _, _)| *at < current.as_u64() + 4000));
+            }
+
+            current = current + 4000;
+        }
+
+        refs
+    }
+
+    fn find_xrefs_to_string(&self, string_addr: Address, start: Address, end: Address) -> Vec<u64> {
+        self.global_refs_in_range(start, end)
+            .into_iter()
+            .filter(|(_, target, _)| *target == string_addr.as_u64())
+            .map(|(at, _, _)| at)
+            .take(MAX_ANCHOR_XREFS)
+            .collect()
+    }
+}
+
+fn collect_pairs(base: u64, bytes: &[u8]) -> Vec<(u64, u64, u32)> {
+    let insns: Vec<u32> = bytes.chunks_exact(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect();
+
+    insns.windows(2)
+        .enumerate()
+        .filter_map(|(i, w)| {
+            let at = base + i as u64 * 4;
+            resolve_pair(at, w[0], w[1]).map(|(target, rd)| (at, target, rd))
+        })
+        .collect()
+}
+
+/// Resolves ADRP followed by ADD (ADRL) or a 64-bit LDR off the same
+/// register. Returns the target and the second instruction's destination.
+fn resolve_pair(at: u64, adrp: u32, next: u32) -> Option<(u64, u32)> {
+    if let Some(target) = Arm64Utils::fold_adrp_add(adrp, next, at) {
+        return Some((target, next & 0x1F));
+    }
+
+    // 64-bit LDR (unsigned offset) from the register ADRP wrote
+    let page = Arm64Utils::get_adrp_value(adrp, at)?;
+    if (next & 0xFFC00000) != 0xF9400000 || (next >> 5) & 0x1F != adrp & 0x1F {
+        return None;
+    }
+
+    Some((page.wrapping_add(Arm64Utils::get_ldr_str_offset(next)? as u64), next & 0x1F))
+}
+
+pub fn find_data_model(reader: Arc<dyn MemoryReader>, start: Address, end: Address) -> Vec<FinderResult> {
+    DataModelFinder::new(reader).find(start, end)
+}
@@ -3,8 +3,21 @@
 use crate::memory::{Address, MemoryReader};
 use crate::pattern::Pattern;
 use crate::finders::result::FinderResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 
+/// Strings the string-reference search looks for, in order
+pub const ANCHOR_STRINGS: &[&str] = &[
+    "nil",
+    "boolean",
+    "userdata",
+    "number",
+    "string",
+    "table",
+    "function",
+    "thread",
+];
+
 pub struct GetTypenameFinder {
     reader: Arc<dyn MemoryReader>,
 }
@@ -41,14 +54,12 @@ impl GetTypenameFinder {
                         let addr = current + offset as u64;
 
                         if self.validate_get_typename(addr) {
-                            return Some(FinderResult {
-                                name: "GetTypename".to_string(),
-                                address: addr,
-                                confidence: 0.86,
-                                method: "pattern".to_string(),
-                                category: "roblox".to_string(),
-                                signature: Some("const char* GetTypename(lua_State* L, int index)".to_string()),
-                            });
+                            return Some(FinderResult::new("GetTypename".to_string(), addr, 0.86)
+                                .with_method("pattern")
+                                .with_category("roblox")
+                                .with_signature("const char* GetTypename(lua_State* L, int index)")
+                                .with_factor("byte pattern", 0.66)
+                                .with_factor("prologue and body checks", 0.20));
                         }
                     }
                 }
@@ -61,15 +72,10 @@ impl GetTypenameFinder {
     }
 
     fn find_by_string_ref(&self, start: Address, end: Address) -> Option<FinderResult> {
-        let type_strings = [
-            "nil", "boolean", "userdata", "number",
-            "string", "table", "function", "thread",
-        ];
-
         let mut found_count = 0;
         let mut potential_func = Address::new(0);
 
-        for type_str in &type_strings {
+        for type_str in ANCHOR_STRINGS {
             if let Some(string_addr) = self.find_string(type_str, start, end) {
                 if let Some(func_addr) = self.find_xref_to_string(string_addr, start, end) {
                     let func_start = self.find_function_start(func_addr);
@@ -84,14 +90,11 @@ impl GetTypenameFinder {
 
         if found_count >= 3 {
             if self.validate_get_typename(potential_func) {
-                return Some(FinderResult {
-                    name: "GetTypename".to_string(),
-                    address: potential_func,
-                    confidence: 0.85,
-                    method: "string_xref".to_string(),
-                    category: "roblox".to_string(),
-                    signature: None,
-                });
+                return Some(FinderResult::new("GetTypename".to_string(), potential_func, 0.85)
+                    .with_method("string_xref")
+                    .with_category("roblox")
+                    .with_factor(&format!("{} type names referenced", found_count), 0.65)
+                    .with_factor("prologue and body checks", 0.20));
             }
         }
 
@@ -107,14 +110,11 @@ impl GetTypenameFinder {
                     let func_start = self.find_function_start(current);
 
                     if self.validate_get_typename(func_start) {
-                        return Some(FinderResult {
-                            name: "GetTypename".to_string(),
-                            address: func_start,
-                            confidence: 0.65,
-                            method: "heuristic".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("GetTypename".to_string(), func_start, 0.65)
+                            .with_method("heuristic")
+                            .with_category("roblox")
+                            .with_factor("body heuristic", 0.45)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -252,8 +252,8 @@ impl GetTypenameFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
@@ -3,8 +3,12 @@
 use crate::memory::{Address, MemoryReader};
 use crate::pattern::Pattern;
 use crate::finders::result::FinderResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 
+/// Strings the string-reference search looks for, in order
+pub const ANCHOR_STRINGS: &[&str] = &["identity", "security", "permission", "context"];
+
 pub struct IdentityPropagatorFinder {
     reader: Arc<dyn MemoryReader>,
 }
@@ -42,14 +46,12 @@ impl IdentityPropagatorFinder {
                         let addr = current + offset as u64;
 
                         if self.validate_identity_propagator(addr) {
-                            return Some(FinderResult {
-                                name: "IdentityPropagator".to_string(),
-                                address: addr,
-                                confidence: 0.88,
-                                method: "pattern".to_string(),
-                                category: "roblox".to_string(),
-                                signature: Some("void IdentityPropagator(lua_State* L, int identity)".to_string()),
-                            });
+                            return Some(FinderResult::new("IdentityPropagator".to_string(), addr, 0.88)
+                                .with_method("pattern")
+                                .with_category("roblox")
+                                .with_signature("void IdentityPropagator(lua_State* L, int identity)")
+                                .with_factor("byte pattern", 0.68)
+                                .with_factor("prologue and body checks", 0.20));
                         }
                     }
                 }
@@ -62,27 +64,17 @@ impl IdentityPropagatorFinder {
     }
 
     fn find_by_string_ref(&self, start: Address, end: Address) -> Option<FinderResult> {
-        let search_strings = [
-            "identity",
-            "security",
-            "permission",
-            "context",
-        ];
-
-        for needle in &search_strings {
+        for needle in ANCHOR_STRINGS {
             if let Some(string_addr) = self.find_string(needle, start, end) {
                 if let Some(func_addr) = self.find_xref_to_string(string_addr, start, end) {
                     let func_start = self.find_function_start(func_addr);
 
                     if self.validate_identity_propagator(func_start) {
-                        return Some(FinderResult {
-                            name: "IdentityPropagator".to_string(),
-                            address: func_start,
-                            confidence: 0.80,
-                            method: "string_xref".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("IdentityPropagator".to_string(), func_start, 0.80)
+                            .with_method("string_xref")
+                            .with_category("roblox")
+                            .with_factor(&format!("string xref \"{}\"", needle), 0.60)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -100,14 +92,11 @@ impl IdentityPropagatorFinder {
                     let func_start = self.find_function_start(current);
 
                     if self.validate_identity_propagator(func_start) {
-                        return Some(FinderResult {
-                            name: "IdentityPropagator".to_string(),
-                            address: func_start,
-                            confidence: 0.68,
-                            method: "heuristic".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("IdentityPropagator".to_string(), func_start, 0.68)
+                            .with_method("heuristic")
+                            .with_category("roblox")
+                            .with_factor("body heuristic", 0.48)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -245,8 +234,8 @@ impl IdentityPropagatorFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
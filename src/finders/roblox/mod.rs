@@ -13,6 +13,7 @@ pub mod create_job;
 pub mod require_check;
 pub mod rbx_crash;
 pub mod task_scheduler;
+pub mod data_model;
 
 pub use luau_load::LuauLoadFinder;
 pub use new_thread::NewThreadFinder;
@@ -27,73 +28,170 @@ pub use create_job::CreateJobFinder;
 pub use require_check::RequireCheckFinder;
 pub use rbx_crash::RbxCrashFinder;
 pub use task_scheduler::TaskSchedulerFinder;
+pub use data_model::DataModelFinder;
 
+use crate::config::{self, ConfidenceConfig};
+use crate::finders::lua_api::print;
 use crate::memory::{Address, MemoryReader};
 use crate::finders::result::FinderResult;
+use crate::finders::Finder;
+use crate::utils::profile;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-pub struct RobloxFinders {
-    reader: Arc<dyn MemoryReader>,
+/// Every finder `RobloxFinders::find_all` runs, in order, with the result
+/// names it can produce.
+pub const FINDER_OUTPUTS: &[(&str, &[&str])] = &[
+    ("luau_load", &["LuauLoad"]),
+    ("new_thread", &["NewThread"]),
+    ("push_instance", &["PushInstance"]),
+    ("get_typename", &["GetTypename"]),
+    ("identity", &["IdentityPropagator"]),
+    ("task_defer", &["task_defer"]),
+    ("task_spawn", &["task_spawn"]),
+    ("sctx_resume", &["sctx_resume"]),
+    ("push_cclosure", &["PushCClosure"]),
+    ("create_job", &["CreateJob"]),
+    ("require_check", &["RequireCheck"]),
+    ("rbx_crash", &["rbx_crash"]),
+    ("task_scheduler", &["TaskScheduler"]),
+    ("data_model", &["GetDataModel", "DataModelPointer"]),
+    ("print", &["print"]),
+    ("warn", &["warn"]),
+];
+
+type SingleFn = fn(Arc<dyn MemoryReader>, Address, Address) -> Option<FinderResult>;
+type MultiFn = fn(Arc<dyn MemoryReader>, Address, Address) -> Vec<FinderResult>;
+
+#[derive(Clone, Copy)]
+enum FindFn {
+    Single(SingleFn),
+    Multi(MultiFn),
 }
 
-impl RobloxFinders {
-    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
-        Self { reader }
+/// A built-in finder function behind the `Finder` trait.
+#[derive(Clone, Copy)]
+pub struct BuiltinFinder {
+    name: &'static str,
+    find: FindFn,
+    anchors: &'static [&'static str],
+}
+
+impl BuiltinFinder {
+    const fn single(name: &'static str, find: SingleFn, anchors: &'static [&'static str]) -> Self {
+        Self { name, find: FindFn::Single(find), anchors }
     }
 
-    pub fn find_all(&self, start: Address, end: Address) -> Vec<FinderResult> {
-        let mut results = Vec::new();
+    const fn multi(name: &'static str, find: MultiFn, anchors: &'static [&'static str]) -> Self {
+        Self { name, find: FindFn::Multi(find), anchors }
+    }
 
-        if let Some(r) = luau_load::find_luau_load(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+    /// Strings the finder locates code through, empty for finders that
+    /// only match code
+    pub fn anchors(&self) -> &'static [&'static str] {
+        self.anchors
+    }
+}
 
-        if let Some(r) = new_thread::find_new_thread(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+impl Finder for BuiltinFinder {
+    fn name(&self) -> &str {
+        self.name
+    }
 
-        if let Some(r) = push_instance::find_push_instance(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+    /// Results scored with the configured confidence weights
+    fn find(&self, reader: Arc<dyn MemoryReader>, start: Address, end: Address) -> Vec<FinderResult> {
+        let results = match self.find {
+            FindFn::Single(find) => find(reader, start, end).into_iter().collect(),
+            FindFn::Multi(find) => find(reader, start, end),
+        };
 
-        if let Some(r) = get_typename::find_get_typename(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+        let weights = config::confidence();
+        results.into_iter().map(|result| weigh(result, &weights)).collect()
+    }
+}
 
-        if let Some(r) = identity::find_identity_propagator(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+/// `result` with its confidence, and each factor behind it, scaled by the
+/// weight for its method
+fn weigh(mut result: FinderResult, weights: &ConfidenceConfig) -> FinderResult {
+    let weight = weights.method_weight(&result.method);
+    if weight == 1.0 {
+        return result;
+    }
 
-        if let Some(r) = task_defer::find_task_defer(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+    result.confidence = weights.weigh(&result.method, result.confidence);
+    for factor in &mut result.breakdown {
+        factor.delta *= weight;
+    }
+    result
+}
 
-        if let Some(r) = task_spawn::find_task_spawn(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+/// Built-in finders in `FINDER_OUTPUTS` order.
+pub const BUILTIN_FINDERS: &[BuiltinFinder] = &[
+    BuiltinFinder::single("luau_load", luau_load::find_luau_load, luau_load::ANCHOR_STRINGS),
+    BuiltinFinder::single("new_thread", new_thread::find_new_thread, &[]),
+    BuiltinFinder::single("push_instance", push_instance::find_push_instance, push_instance::ANCHOR_STRINGS),
+    BuiltinFinder::single("get_typename", get_typename::find_get_typename, get_typename::ANCHOR_STRINGS),
+    BuiltinFinder::single("identity", identity::find_identity_propagator, identity::ANCHOR_STRINGS),
+    BuiltinFinder::single("task_defer", task_defer::find_task_defer, task_defer::ANCHOR_STRINGS),
+    BuiltinFinder::single("task_spawn", task_spawn::find_task_spawn, task_spawn::ANCHOR_STRINGS),
+    BuiltinFinder::single("sctx_resume", sctx_resume::find_sctx_resume, sctx_resume::ANCHOR_STRINGS),
+    BuiltinFinder::single("push_cclosure", push_cclosure::find_push_cclosure, push_cclosure::ANCHOR_STRINGS),
+    BuiltinFinder::single("create_job", create_job::find_create_job, create_job::ANCHOR_STRINGS),
+    BuiltinFinder::single("require_check", require_check::find_require_check, require_check::ANCHOR_STRINGS),
+    BuiltinFinder::single("rbx_crash", rbx_crash::find_rbx_crash, rbx_crash::ANCHOR_STRINGS),
+    BuiltinFinder::single("task_scheduler", task_scheduler::find_task_scheduler, task_scheduler::ANCHOR_STRINGS),
+    BuiltinFinder::multi("data_model", data_model::find_data_model, data_model::ANCHOR_STRINGS),
+    BuiltinFinder::single("print", print::find_print, print::PRINT_ANCHORS),
+    BuiltinFinder::single("warn", print::find_warn, print::WARN_ANCHORS),
+];
+
+/// The built-in finder that can produce the result named `output`.
+pub fn finder_for_output(output: &str) -> Option<&'static BuiltinFinder> {
+    let (finder, _) = FINDER_OUTPUTS.iter().find(|(_, outputs)| outputs.contains(&output))?;
+    BUILTIN_FINDERS.iter().find(|f| f.name == *finder)
+}
 
-        if let Some(r) = sctx_resume::find_sctx_resume(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+pub struct RobloxFinders {
+    reader: Arc<dyn MemoryReader>,
+    extra: Vec<Box<dyn Finder>>,
+    cancel: Option<Arc<AtomicBool>>,
+}
 
-        if let Some(r) = push_cclosure::find_push_cclosure(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+impl RobloxFinders {
+    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self { reader, extra: Vec::new(), cancel: None }
+    }
 
-        if let Some(r) = create_job::find_create_job(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+    /// Runs `finders` after the built-ins.
+    pub fn with_finders(mut self, finders: Vec<Box<dyn Finder>>) -> Self {
+        self.extra.extend(finders);
+        self
+    }
 
-        if let Some(r) = require_check::find_require_check(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+    /// Stops `find_all` before the next finder once `cancel` is set.
+    pub fn with_cancellation(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
 
-        if let Some(r) = rbx_crash::find_rbx_crash(self.reader.clone(), start, end) {
-            results.push(r);
-        }
+    fn cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    /// Runs every finder over `start..end`. If cancelled, returns whatever
+    /// the finders that already ran produced.
+    pub fn find_all(&self, start: Address, end: Address) -> Vec<FinderResult> {
+        let builtins = BUILTIN_FINDERS.iter().map(|f| f as &dyn Finder);
+        let mut results = Vec::new();
+
+        for finder in builtins.chain(self.extra.iter().map(|f| f.as_ref())) {
+            if self.cancelled() {
+                break;
+            }
 
-        if let Some(r) = task_scheduler::find_task_scheduler(self.reader.clone(), start, end) {
-            results.push(r);
+            results.extend(profile::span(finder.name(), || finder.find(self.reader.clone(), start, end))
+                .into_iter()
+                .map(|result| result.follow_thunk(self.reader.as_ref())));
         }
 
         results
@@ -3,6 +3,7 @@
 use crate::memory::{Address, MemoryReader};
 use crate::pattern::Pattern;
 use crate::finders::result::FinderResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 
 pub struct NewThreadFinder {
@@ -37,14 +38,12 @@ impl NewThreadFinder {
                         let addr = current + offset as u64;
 
                         if self.validate_new_thread(addr) {
-                            return Some(FinderResult {
-                                name: "NewThread".to_string(),
-                                address: addr,
-                                confidence: 0.88,
-                                method: "pattern".to_string(),
-                                category: "roblox".to_string(),
-                                signature: Some("lua_State* NewThread(lua_State* L)".to_string()),
-                            });
+                            return Some(FinderResult::new("NewThread".to_string(), addr, 0.88)
+                                .with_method("pattern")
+                                .with_category("roblox")
+                                .with_signature("lua_State* NewThread(lua_State* L)")
+                                .with_factor("byte pattern", 0.68)
+                                .with_factor("prologue and body checks", 0.20));
                         }
                     }
                 }
@@ -65,14 +64,11 @@ impl NewThreadFinder {
                     let func_start = self.find_function_start(current);
 
                     if self.validate_new_thread(func_start) {
-                        return Some(FinderResult {
-                            name: "NewThread".to_string(),
-                            address: func_start,
-                            confidence: 0.70,
-                            method: "heuristic".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("NewThread".to_string(), func_start, 0.70)
+                            .with_method("heuristic")
+                            .with_category("roblox")
+                            .with_factor("body heuristic", 0.50)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -157,8 +153,8 @@ impl NewThreadFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
@@ -3,8 +3,12 @@
 use crate::memory::{Address, MemoryReader};
 use crate::pattern::Pattern;
 use crate::finders::result::FinderResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 
+/// Strings the string-reference search looks for, in order
+pub const ANCHOR_STRINGS: &[&str] = &["cclosure", "upvalue", "debugname"];
+
 pub struct PushCClosureFinder {
     reader: Arc<dyn MemoryReader>,
 }
@@ -41,14 +45,12 @@ impl PushCClosureFinder {
                         let addr = current + offset as u64;
 
                         if self.validate_push_cclosure(addr) {
-                            return Some(FinderResult {
-                                name: "PushCClosure".to_string(),
-                                address: addr,
-                                confidence: 0.88,
-                                method: "pattern".to_string(),
-                                category: "roblox".to_string(),
-                                signature: Some("void PushCClosure(lua_State* L, lua_CFunction fn, const char* debugname, int nup, lua_Continuation cont)".to_string()),
-                            });
+                            return Some(FinderResult::new("PushCClosure".to_string(), addr, 0.88)
+                                .with_method("pattern")
+                                .with_category("roblox")
+                                .with_signature("void PushCClosure(lua_State* L, lua_CFunction fn, const char* debugname, int nup, lua_Continuation cont)")
+                                .with_factor("byte pattern", 0.68)
+                                .with_factor("prologue and body checks", 0.20));
                         }
                     }
                 }
@@ -61,26 +63,17 @@ impl PushCClosureFinder {
     }
 
     fn find_by_xref_analysis(&self, start: Address, end: Address) -> Option<FinderResult> {
-        let search_strings = [
-            "cclosure",
-            "upvalue",
-            "debugname",
-        ];
-
-        for needle in &search_strings {
+        for needle in ANCHOR_STRINGS {
             if let Some(string_addr) = self.find_string(needle, start, end) {
                 if let Some(func_addr) = self.find_xref_to_string(string_addr, start, end) {
                     let func_start = self.find_function_start(func_addr);
 
                     if self.validate_push_cclosure(func_start) {
-                        return Some(FinderResult {
-                            name: "PushCClosure".to_string(),
-                            address: func_start,
-                            confidence: 0.80,
-                            method: "xref".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("PushCClosure".to_string(), func_start, 0.80)
+                            .with_method("xref")
+                            .with_category("roblox")
+                            .with_factor(&format!("xref to \"{}\"", needle), 0.60)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -98,14 +91,11 @@ impl PushCClosureFinder {
                     let func_start = self.find_function_start(current);
 
                     if self.validate_push_cclosure(func_start) {
-                        return Some(FinderResult {
-                            name: "PushCClosure".to_string(),
-                            address: func_start,
-                            confidence: 0.65,
-                            method: "heuristic".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("PushCClosure".to_string(), func_start, 0.65)
+                            .with_method("heuristic")
+                            .with_category("roblox")
+                            .with_factor("body heuristic", 0.45)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -248,8 +238,8 @@ impl PushCClosureFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
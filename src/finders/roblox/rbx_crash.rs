@@ -3,8 +3,12 @@
 use crate::memory::{Address, MemoryReader};
 use crate::pattern::Pattern;
 use crate::finders::result::FinderResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 
+/// Strings the string-reference search looks for, in order
+pub const ANCHOR_STRINGS: &[&str] = &["CRASH", "Assertion failed", "Fatal error", "Unrecoverable"];
+
 pub struct RbxCrashFinder {
     reader: Arc<dyn MemoryReader>,
 }
@@ -41,14 +45,12 @@ impl RbxCrashFinder {
                         let addr = current + offset as u64;
 
                         if self.validate_rbx_crash(addr) {
-                            return Some(FinderResult {
-                                name: "rbx_crash".to_string(),
-                                address: addr,
-                                confidence: 0.90,
-                                method: "pattern".to_string(),
-                                category: "roblox".to_string(),
-                                signature: Some("void rbxCrash(const char* message)".to_string()),
-                            });
+                            return Some(FinderResult::new("rbx_crash".to_string(), addr, 0.90)
+                                .with_method("pattern")
+                                .with_category("roblox")
+                                .with_signature("void rbxCrash(const char* message)")
+                                .with_factor("byte pattern", 0.70)
+                                .with_factor("prologue and body checks", 0.20));
                         }
                     }
                 }
@@ -61,27 +63,17 @@ impl RbxCrashFinder {
     }
 
     fn find_by_string_ref(&self, start: Address, end: Address) -> Option<FinderResult> {
-        let search_strings = [
-            "CRASH",
-            "Assertion failed",
-            "Fatal error",
-            "Unrecoverable",
-        ];
-
-        for needle in &search_strings {
+        for needle in ANCHOR_STRINGS {
             if let Some(string_addr) = self.find_string(needle, start, end) {
                 if let Some(func_addr) = self.find_xref_to_string(string_addr, start, end) {
                     let func_start = self.find_function_start(func_addr);
 
                     if self.validate_rbx_crash(func_start) {
-                        return Some(FinderResult {
-                            name: "rbx_crash".to_string(),
-                            address: func_start,
-                            confidence: 0.85,
-                            method: "string_xref".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("rbx_crash".to_string(), func_start, 0.85)
+                            .with_method("string_xref")
+                            .with_category("roblox")
+                            .with_factor(&format!("string xref \"{}\"", needle), 0.65)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -99,14 +91,11 @@ impl RbxCrashFinder {
                     let func_start = self.find_function_start(current);
 
                     if self.validate_rbx_crash(func_start) {
-                        return Some(FinderResult {
-                            name: "rbx_crash".to_string(),
-                            address: func_start,
-                            confidence: 0.70,
-                            method: "heuristic".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("rbx_crash".to_string(), func_start, 0.70)
+                            .with_method("heuristic")
+                            .with_category("roblox")
+                            .with_factor("body heuristic", 0.50)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -240,8 +229,8 @@ impl RbxCrashFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
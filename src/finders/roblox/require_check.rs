@@ -3,8 +3,17 @@
 use crate::memory::{Address, MemoryReader};
 use crate::pattern::Pattern;
 use crate::finders::result::FinderResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 
+/// Strings the string-reference search looks for, in order
+pub const ANCHOR_STRINGS: &[&str] = &[
+    "require",
+    "ModuleScript",
+    "already required",
+    "circular dependency",
+];
+
 pub struct RequireCheckFinder {
     reader: Arc<dyn MemoryReader>,
 }
@@ -41,14 +50,12 @@ impl RequireCheckFinder {
                         let addr = current + offset as u64;
 
                         if self.validate_require_check(addr) {
-                            return Some(FinderResult {
-                                name: "RequireCheck".to_string(),
-                                address: addr,
-                                confidence: 0.85,
-                                method: "pattern".to_string(),
-                                category: "roblox".to_string(),
-                                signature: Some("bool RequireCheck(lua_State* L, ModuleScript* script)".to_string()),
-                            });
+                            return Some(FinderResult::new("RequireCheck".to_string(), addr, 0.85)
+                                .with_method("pattern")
+                                .with_category("roblox")
+                                .with_signature("bool RequireCheck(lua_State* L, ModuleScript* script)")
+                                .with_factor("byte pattern", 0.65)
+                                .with_factor("prologue and body checks", 0.20));
                         }
                     }
                 }
@@ -61,27 +68,17 @@ impl RequireCheckFinder {
     }
 
     fn find_by_string_ref(&self, start: Address, end: Address) -> Option<FinderResult> {
-        let search_strings = [
-            "require",
-            "ModuleScript",
-            "already required",
-            "circular dependency",
-        ];
-
-        for needle in &search_strings {
+        for needle in ANCHOR_STRINGS {
             if let Some(string_addr) = self.find_string(needle, start, end) {
                 if let Some(func_addr) = self.find_xref_to_string(string_addr, start, end) {
                     let func_start = self.find_function_start(func_addr);
 
                     if self.validate_require_check(func_start) {
-                        return Some(FinderResult {
-                            name: "RequireCheck".to_string(),
-                            address: func_start,
-                            confidence: 0.82,
-                            method: "string_xref".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("RequireCheck".to_string(), func_start, 0.82)
+                            .with_method("string_xref")
+                            .with_category("roblox")
+                            .with_factor(&format!("string xref \"{}\"", needle), 0.62)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -99,14 +96,11 @@ impl RequireCheckFinder {
                     let func_start = self.find_function_start(current);
 
                     if self.validate_require_check(func_start) {
-                        return Some(FinderResult {
-                            name: "RequireCheck".to_string(),
-                            address: func_start,
-                            confidence: 0.62,
-                            method: "heuristic".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("RequireCheck".to_string(), func_start, 0.62)
+                            .with_method("heuristic")
+                            .with_category("roblox")
+                            .with_factor("body heuristic", 0.42)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -249,8 +243,8 @@ impl RequireCheckFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
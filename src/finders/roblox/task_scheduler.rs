@@ -3,8 +3,18 @@
 use crate::memory::{Address, MemoryReader};
 use crate::pattern::Pattern;
 use crate::finders::result::FinderResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 
+/// Strings the string-reference search looks for, in order
+pub const ANCHOR_STRINGS: &[&str] = &[
+    "TaskScheduler",
+    "scheduler",
+    "Waiting",
+    "Running",
+    "JobPriority",
+];
+
 pub struct TaskSchedulerFinder {
     reader: Arc<dyn MemoryReader>,
 }
@@ -45,14 +55,12 @@ impl TaskSchedulerFinder {
                         let addr = current + offset as u64;
 
                         if self.validate_task_scheduler(addr) {
-                            return Some(FinderResult {
-                                name: "TaskScheduler".to_string(),
-                                address: addr,
-                                confidence: 0.88,
-                                method: "pattern".to_string(),
-                                category: "roblox".to_string(),
-                                signature: Some("TaskScheduler* TaskScheduler::singleton()".to_string()),
-                            });
+                            return Some(FinderResult::new("TaskScheduler".to_string(), addr, 0.88)
+                                .with_method("pattern")
+                                .with_category("roblox")
+                                .with_signature("TaskScheduler* TaskScheduler::singleton()")
+                                .with_factor("byte pattern", 0.68)
+                                .with_factor("prologue and body checks", 0.20));
                         }
                     }
                 }
@@ -65,28 +73,17 @@ impl TaskSchedulerFinder {
     }
 
     fn find_by_string_ref(&self, start: Address, end: Address) -> Option<FinderResult> {
-        let search_strings = [
-            "TaskScheduler",
-            "scheduler",
-            "Waiting",
-            "Running",
-            "JobPriority",
-        ];
-
-        for needle in &search_strings {
+        for needle in ANCHOR_STRINGS {
             if let Some(string_addr) = self.find_string(needle, start, end) {
                 if let Some(func_addr) = self.find_xref_to_string(string_addr, start, end) {
                     let func_start = self.find_function_start(func_addr);
 
                     if self.validate_task_scheduler(func_start) {
-                        return Some(FinderResult {
-                            name: "TaskScheduler".to_string(),
-                            address: func_start,
-                            confidence: 0.82,
-                            method: "string_xref".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("TaskScheduler".to_string(), func_start, 0.82)
+                            .with_method("string_xref")
+                            .with_category("roblox")
+                            .with_factor(&format!("string xref \"{}\"", needle), 0.62)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -104,14 +101,11 @@ impl TaskSchedulerFinder {
                     let func_start = self.find_function_start(current);
 
                     if self.validate_singleton_getter(func_start) {
-                        return Some(FinderResult {
-                            name: "TaskScheduler".to_string(),
-                            address: func_start,
-                            confidence: 0.75,
-                            method: "singleton_pattern".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("TaskScheduler".to_string(), func_start, 0.75)
+                            .with_method("singleton_pattern")
+                            .with_category("roblox")
+                            .with_factor("singleton getter shape", 0.55)
+                            .with_factor("getter checks", 0.20));
                     }
                 }
             }
@@ -131,14 +125,11 @@ impl TaskSchedulerFinder {
                     let func_start = self.find_function_start(current);
 
                     if self.validate_task_scheduler(func_start) {
-                        return Some(FinderResult {
-                            name: "TaskScheduler".to_string(),
-                            address: func_start,
-                            confidence: 0.60,
-                            method: "heuristic".to_string(),
-                            category: "roblox".to_string(),
-                            signature: None,
-                        });
+                        return Some(FinderResult::new("TaskScheduler".to_string(), func_start, 0.60)
+                            .with_method("heuristic")
+                            .with_category("roblox")
+                            .with_factor("body heuristic", 0.40)
+                            .with_factor("prologue and body checks", 0.20));
                     }
                 }
             }
@@ -338,8 +329,8 @@ impl TaskSchedulerFinder {
                     return current;
                 }
 
-                if (insn & 0xFFFFFC1F) == 0xD65F0000 {
-                    return current + 4;
+                if (insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
                 }
             }
 
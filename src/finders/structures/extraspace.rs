@@ -3,8 +3,36 @@
 use crate::memory::{Address, MemoryReader};
 use crate::pattern::Pattern;
 use crate::finders::result::StructureOffsetResult;
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 
+/// Instructions looked back from an allocator call for its arguments
+const ARG_WINDOW: usize = 8;
+
+/// Most instructions followed from the state allocation to the store of
+/// the extraspace pointer
+const MAX_NEWSTATE_INSNS: usize = 128;
+
+/// Instructions after an allocator call its result may be stored within
+const STORE_WINDOW: usize = 4;
+
+const BLR_MASK: u32 = 0xFFFFFC1F;
+const BLR: u32 = 0xD63F0000;
+
+/// `lua_newstate`, found by its `frealloc(ud, NULL, 0, sizeof(LG))` call,
+/// and the extraspace block it hangs off the main thread's `userdata`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateCreation {
+    pub function: Address,
+    /// The `frealloc` call allocating the state
+    pub alloc_call: Address,
+    /// `sizeof(LG)`, when the argument folds to a constant
+    pub state_size: Option<u64>,
+    pub userdata_offset: u64,
+    /// Bytes allocated for the extraspace, when the argument folds to a constant
+    pub extraspace_size: Option<u64>,
+}
+
 pub struct ExtraSpaceFinder {
     reader: Arc<dyn MemoryReader>,
 }
@@ -49,9 +77,136 @@ impl ExtraSpaceFinder {
             ).with_confidence(0.82).with_method("heuristic"));
         }
 
+        if let Some(creation) = self.find_state_creation(start, end) {
+            let userdata = StructureOffsetResult::new(
+                "lua_State".to_string(),
+                "userdata".to_string(),
+                creation.userdata_offset,
+            ).with_method("newstate_alloc");
+
+            // Without a constant size only the offset is known
+            results.push(match creation.extraspace_size {
+                Some(size) => userdata.with_size(size).with_confidence(0.9),
+                None => userdata.with_confidence(0.35),
+            });
+        }
+
         results
     }
 
+    /// Finds the `frealloc(ud, NULL, 0, size)` call that allocates the main
+    /// thread, then follows the routine to the next allocation whose result
+    /// is stored into the new state: the extraspace, at `L->userdata`.
+    pub fn find_state_creation(&self, start: Address, end: Address) -> Option<StateCreation> {
+        let mut current = start;
+
+        while current < end {
+            if let Ok(bytes) = self.reader.read_bytes(current, 4096) {
+                let insns = words(&bytes);
+
+                // 4000-byte stride: only look at calls in the first 1000 slots
+                for i in 0..insns.len().min(1000) {
+                    if (insns[i] & BLR_MASK) != BLR {
+                        continue;
+                    }
+
+                    let args = fold_arguments(&insns[i.saturating_sub(ARG_WINDOW)..i]);
+                    if !args.is_realloc_shaped() {
+                        continue;
+                    }
+
+                    let call = current + i as u64 * 4;
+                    if let Some((userdata_offset, extraspace_size)) = self.follow_new_state(call) {
+                        return Some(StateCreation {
+                            function: self.find_function_start(call),
+                            alloc_call: call,
+                            state_size: args.get(3),
+                            userdata_offset,
+                            extraspace_size,
+                        });
+                    }
+                }
+            }
+
+            current = current + 4000;
+        }
+
+        None
+    }
+
+    /// Walks from the state allocation at `call`, tracking which registers
+    /// hold the new state, to a later allocator call whose result is stored
+    /// at an offset from it. Returns that offset and the allocation size.
+    fn follow_new_state(&self, call: Address) -> Option<(u64, Option<u64>)> {
+        let bytes = self.reader.read_bytes(call + 4, MAX_NEWSTATE_INSNS * 4).ok()?;
+        let insns = words(&bytes);
+
+        let mut state = vec![0u32];
+        let mut folder = RegisterFolder::default();
+
+        for (i, &insn) in insns.iter().enumerate() {
+            if (insn & 0xFFFFFC1F) == 0xD65F0000 {
+                break;
+            }
+
+            let is_call = (insn & 0xFC000000) == 0x94000000 || (insn & BLR_MASK) == BLR;
+            if !is_call {
+                if let Some((rd, rm)) = mov_register(insn) {
+                    state.retain(|&r| r != rd);
+                    if state.contains(&rm) {
+                        state.push(rd);
+                    }
+                } else if writes_rd(insn) {
+                    state.retain(|&r| r != insn & 0x1F);
+                }
+                folder.step(insn);
+                continue;
+            }
+
+            // Calls clobber x0-x18, so the state survives only in x19-x28
+            state.retain(|&r| r > 18);
+
+            let stored = insns.iter().skip(i + 1).take(STORE_WINDOW).find_map(|&next| {
+                let (rt, rn, offset) = str_x_offset(next)?;
+                (rt == 0 && state.contains(&rn)).then_some(offset)
+            });
+
+            if let Some(offset) = stored {
+                let size_reg = if folder.is_realloc_shaped() { 3 } else { 0 };
+                let size = folder.get(size_reg).or_else(|| folder.get(1)).filter(|&size| size > 0);
+                return Some((offset, size));
+            }
+
+            folder.clobber_call();
+        }
+
+        None
+    }
+
+    fn find_function_start(&self, addr: Address) -> Address {
+        let mut current = addr;
+
+        for _ in 0..MAX_NEWSTATE_INSNS {
+            if current <= self.reader.get_base_address() {
+                break;
+            }
+
+            if let Ok(insn) = self.reader.read_u32(current) {
+                if (insn & 0x7F800000) == 0x29800000 || (insn & 0x7F800000) == 0x6D800000 {
+                    return current;
+                }
+
+                if current != addr && ((insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current)) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
+                }
+            }
+
+            current = current - 4;
+        }
+
+        addr
+    }
+
     fn find_identity_offset(&self, start: Address, end: Address) -> Option<u64> {
         let patterns = vec![
             Pattern::from_hex("B9 ?? ?? ?? 71 ?? ?? ?? 54 ?? ?? ?? B9"),
@@ -175,6 +330,108 @@ impl ExtraSpaceFinder {
     }
 }
 
+fn words(bytes: &[u8]) -> Vec<u32> {
+    bytes.chunks_exact(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+fn fold_arguments(insns: &[u32]) -> RegisterFolder {
+    let mut folder = RegisterFolder::default();
+    for &insn in insns {
+        folder.step(insn);
+    }
+    folder
+}
+
+/// `MOV Xd, Xm` (`ORR Xd, XZR, Xm`). Returns (d, m).
+fn mov_register(insn: u32) -> Option<(u32, u32)> {
+    ((insn & 0x7FE0FFE0) == 0x2A0003E0).then_some((insn & 0x1F, (insn >> 16) & 0x1F))
+}
+
+/// `STR Xt, [Xn, #imm]`. Returns (t, n, imm).
+fn str_x_offset(insn: u32) -> Option<(u32, u32, u64)> {
+    ((insn & 0xFFC00000) == 0xF9000000)
+        .then_some((insn & 0x1F, (insn >> 5) & 0x1F, ((insn >> 10) & 0xFFF) as u64 * 8))
+}
+
+/// Whether `insn` is an integer data-processing instruction or a load,
+/// which write the register in bits 0-4; stores and branches don't
+fn writes_rd(insn: u32) -> bool {
+    let op0 = (insn >> 25) & 0xF;
+    let is_load = op0 & 0b0101 == 0b0100 && (insn >> 22) & 1 == 1;
+
+    matches!(op0, 0b1000 | 0b1001 | 0b0101 | 0b1101) || is_load
+}
+
+/// Constant-folds the integer registers over straight-line code: MOVZ,
+/// MOVN, MOVK, register moves and immediate adds. Anything else that
+/// writes a register makes it unknown.
+#[derive(Debug, Clone, Copy, Default)]
+struct RegisterFolder {
+    values: [Option<u64>; 31],
+}
+
+impl RegisterFolder {
+    fn get(&self, reg: u32) -> Option<u64> {
+        match reg {
+            31 => Some(0),
+            r => self.values[r as usize],
+        }
+    }
+
+    fn set(&mut self, reg: u32, value: Option<u64>, wide: bool) {
+        if reg < 31 {
+            self.values[reg as usize] = if wide { value } else { value.map(|v| v & 0xFFFF_FFFF) };
+        }
+    }
+
+    fn step(&mut self, insn: u32) {
+        let rd = insn & 0x1F;
+        let wide = insn >> 31 == 1;
+        let imm16 = ((insn >> 5) & 0xFFFF) as u64;
+        let shift = ((insn >> 21) & 0x3) * 16;
+
+        match insn & 0x7F800000 {
+            0x52800000 => return self.set(rd, Some(imm16 << shift), wide),
+            0x12800000 => return self.set(rd, Some(!(imm16 << shift)), wide),
+            0x72800000 => {
+                let value = self.get(rd).map(|v| (v & !(0xFFFF << shift)) | (imm16 << shift));
+                return self.set(rd, value, wide);
+            }
+            _ => {}
+        }
+
+        if let Some((rd, rm)) = mov_register(insn) {
+            return self.set(rd, self.get(rm), wide);
+        }
+
+        // ADD Xd, Xn, #imm{, LSL #12}; Xn = 31 is SP, never a constant
+        if (insn & 0x7F800000) == 0x11000000 {
+            let rn = (insn >> 5) & 0x1F;
+            let imm = ((insn >> 10) & 0xFFF) as u64;
+            let imm = if (insn >> 22) & 1 == 1 { imm << 12 } else { imm };
+            let value = if rn == 31 { None } else { self.get(rn).map(|v| v.wrapping_add(imm)) };
+            return self.set(rd, value, wide);
+        }
+
+        if writes_rd(insn) {
+            self.set(rd, None, true);
+        }
+    }
+
+    /// Arguments of `frealloc(ud, NULL, 0, nsize)`: a fresh allocation
+    fn is_realloc_shaped(&self) -> bool {
+        self.get(1) == Some(0) && self.get(2) == Some(0) && self.get(3).is_some()
+    }
+
+    fn clobber_call(&mut self) {
+        for value in &mut self.values[..19] {
+            *value = None;
+        }
+    }
+}
+
 pub struct ScriptContextFinder {
     reader: Arc<dyn MemoryReader>,
 }
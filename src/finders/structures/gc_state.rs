@@ -0,0 +1,76 @@
+// Tue Jan 13 2026 - Alex
+
+use crate::memory::{Address, MemoryReader};
+use crate::luau::gc::{GcAnalyzer, GcRoutine};
+use crate::finders::result::StructureOffsetResult;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// `global_State` GC fields with their sizes, in layout order
+const GC_FIELDS: &[(&str, u64)] = &[
+    ("gcstate", 1),
+    ("strt", 16),
+    ("totalbytes", 8),
+    ("GCthreshold", 8),
+    ("gray", 8),
+    ("grayagain", 8),
+    ("weak", 8),
+];
+
+pub struct GcStateFinder {
+    reader: Arc<dyn MemoryReader>,
+}
+
+impl GcStateFinder {
+    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self { reader }
+    }
+
+    /// Each GC routine found votes for the offsets it accesses. The most
+    /// voted offset per field wins; confidence rises with the number of
+    /// routines that agree and drops with those that disagree.
+    pub fn find_all(&self, start: Address, end: Address) -> Vec<StructureOffsetResult> {
+        let routines = GcAnalyzer::new(self.reader.clone()).find_gc_routines(start, end);
+
+        let mut votes: HashMap<&str, HashMap<u64, usize>> = HashMap::new();
+        for routine in &routines {
+            for (field, offset) in field_offsets(routine) {
+                *votes.entry(field).or_default().entry(offset).or_default() += 1;
+            }
+        }
+
+        GC_FIELDS.iter()
+            .filter_map(|&(field, size)| {
+                let candidates = votes.get(field)?;
+                let (&offset, &agreeing) = candidates.iter()
+                    .max_by_key(|(offset, count)| (**count, std::cmp::Reverse(**offset)))?;
+                let total: usize = candidates.values().sum();
+
+                let confidence = (0.5 + 0.1 * (agreeing - 1) as f64).min(0.9) * agreeing as f64 / total as f64;
+
+                Some(StructureOffsetResult::new("global_State".to_string(), field.to_string(), offset)
+                    .with_size(size)
+                    .with_confidence(confidence)
+                    .with_method("gc_access"))
+            })
+            .collect()
+    }
+}
+
+fn field_offsets(routine: &GcRoutine) -> Vec<(&'static str, u64)> {
+    let mut offsets = vec![("gcstate", routine.gcstate)];
+
+    if let Some(gray) = routine.gray {
+        offsets.extend([("gray", gray), ("grayagain", gray + 8), ("weak", gray + 16)]);
+    }
+
+    if let Some(nuse) = routine.strt_nuse.filter(|&nuse| nuse >= 8) {
+        offsets.push(("strt", nuse - 8));
+    }
+
+    if let Some((total, threshold)) = routine.debt {
+        offsets.extend([("totalbytes", total), ("GCthreshold", threshold)]);
+    }
+
+    offsets
+}
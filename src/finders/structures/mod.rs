@@ -9,11 +9,13 @@ pub mod userdata;
 pub mod string_obj;
 pub mod tvalue;
 pub mod gc_object;
+pub mod gc_state;
 
 pub use lua_state::LuaStateFinder;
 pub use extraspace::ExtraSpaceFinder;
 pub use closure::ClosureFinder;
 pub use proto::ProtoFinder;
+pub use gc_state::GcStateFinder;
 
 use crate::memory::{Address, MemoryReader};
 use crate::finders::result::StructureOffsetResult;
@@ -34,5 +36,8 @@ pub fn find_all_structures(reader: Arc<dyn MemoryReader>, start: Address, end: A
     let proto_finder = ProtoFinder::new(reader.clone());
     results.extend(proto_finder.find_all(start, end));
 
+    let gc_state_finder = GcStateFinder::new(reader.clone());
+    results.extend(gc_state_finder.find_all(start, end));
+
     results
 }
@@ -0,0 +1,13 @@
+// Tue Jan 13 2026 - Alex
+
+use crate::finders::FinderResult;
+use crate::memory::{Address, MemoryReader};
+use std::sync::Arc;
+
+/// Common contract for function finders. Built-in finders implement it and
+/// library users can pass their own to `AllFinders::with_finders`; their
+/// results land in `CombinedResults::functions`.
+pub trait Finder: Send + Sync {
+    fn name(&self) -> &str;
+    fn find(&self, reader: Arc<dyn MemoryReader>, start: Address, end: Address) -> Vec<FinderResult>;
+}
@@ -26,6 +26,7 @@ pub mod orchestration;
 pub mod validation;
 pub mod scripting;
 pub mod diff;
+pub mod server;
 
 pub use config::Config;
 pub use memory::MemoryScanner;
@@ -2,9 +2,13 @@
 
 use crate::memory::{Address, MemoryReader, MemoryError};
 use crate::luau::types::{GCHeader, TypeTag};
+use crate::analysis::padding::{is_zero_padding, skip_padding};
 use std::sync::Arc;
 use std::collections::{HashMap, HashSet};
 
+const MAX_GC_ROUTINES: usize = 32;
+const MAX_GC_ROUTINE_INSNS: usize = 512;
+
 pub struct GcAnalyzer {
     reader: Arc<dyn MemoryReader>,
 }
@@ -408,6 +412,100 @@ impl GcAnalyzer {
 
         Ok(refs)
     }
+
+    /// Locates `luaC_fullgc`/`luaC_step`-shaped routines in `start..end` and
+    /// records the `global_State` offsets each one accesses.
+    ///
+    /// Every candidate checks `gcstate` (LDRB then CMP #0-4 or CBZ/CBNZ).
+    /// A full collection also clears the three gray list heads together. A
+    /// step compares totalbytes against the threshold.
+    pub fn find_gc_routines(&self, start: Address, end: Address) -> Vec<GcRoutine> {
+        let mut routines = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = start;
+
+        while current < end && routines.len() < MAX_GC_ROUTINES {
+            if let Ok(bytes) = self.reader.read_bytes(current, 4096) {
+                let insns = words(&bytes);
+
+                // 4000-byte stride: only start windows in the first 1000 slots
+                for i in 0..insns.len().min(1000) {
+                    if gcstate_check(&insns[i..]).is_none() {
+                        continue;
+                    }
+
+                    let func = self.find_function_start(current + i as u64 * 4);
+                    if !visited.insert(func.as_u64()) {
+                        continue;
+                    }
+
+                    if let Some(routine) = self.analyze_gc_routine(func) {
+                        routines.push(routine);
+                    }
+                }
+            }
+
+            current = current + 4000;
+        }
+
+        routines
+    }
+
+    fn analyze_gc_routine(&self, func: Address) -> Option<GcRoutine> {
+        let bytes = self.reader.read_bytes(func, MAX_GC_ROUTINE_INSNS * 4).ok()?;
+        let mut insns = words(&bytes);
+        if let Some(ret) = insns.iter().position(|&insn| (insn & 0xFFFFFC1F) == 0xD65F0000) {
+            insns.truncate(ret + 1);
+        }
+
+        let checks: Vec<(u32, u64)> = (0..insns.len())
+            .filter_map(|i| gcstate_check(&insns[i..]))
+            .collect();
+
+        checks.iter().find_map(|&(base, gcstate)| {
+            let gray = zeroed_list_heads(&insns, base);
+            let debt = debt_compare(&insns, base);
+
+            let kind = match (gray, debt) {
+                (Some(_), _) => GcRoutineKind::FullGc,
+                (None, Some(_)) => GcRoutineKind::Step,
+                (None, None) => return None,
+            };
+
+            Some(GcRoutine {
+                address: func,
+                kind,
+                gcstate,
+                gray,
+                strt_nuse: string_table_check(&insns, base),
+                debt,
+            })
+        })
+    }
+
+    fn find_function_start(&self, addr: Address) -> Address {
+        let mut current = addr;
+
+        for _ in 0..MAX_GC_ROUTINE_INSNS {
+            if current <= self.reader.get_base_address() {
+                break;
+            }
+
+            if let Ok(insn) = self.reader.read_u32(current) {
+                if (insn & 0x7F800000) == 0x29800000 || (insn & 0x7F800000) == 0x6D800000 {
+                    return current;
+                }
+
+                if current != addr && ((insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(self.reader.as_ref(), current)) {
+                    return skip_padding(self.reader.as_ref(), current + 4);
+                }
+            }
+
+            current = current - 4;
+        }
+
+        addr
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -608,3 +706,120 @@ impl Default for ReferenceGraph {
         Self::new()
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcRoutineKind {
+    Step,
+    FullGc,
+}
+
+/// A collector routine and the `global_State` offsets it was seen touching.
+#[derive(Debug, Clone)]
+pub struct GcRoutine {
+    pub address: Address,
+    pub kind: GcRoutineKind,
+    pub gcstate: u64,
+    /// `gray`; `grayagain` and `weak` follow at +8 and +16
+    pub gray: Option<u64>,
+    /// `strt.nuse`; `strt.size` follows at +4 and `strt.hash` precedes at -8
+    pub strt_nuse: Option<u64>,
+    /// (`totalbytes`, `GCthreshold`)
+    pub debt: Option<(u64, u64)>,
+}
+
+fn words(bytes: &[u8]) -> Vec<u32> {
+    bytes.chunks_exact(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+/// `LDRB Wt, [Xn, #imm]` followed within four instructions by
+/// `CMP Wt, #0-4` or `CBZ/CBNZ Wt`. Returns (Xn, imm).
+fn gcstate_check(insns: &[u32]) -> Option<(u32, u64)> {
+    let ldrb = *insns.first()?;
+    if (ldrb & 0xFFC00000) != 0x39400000 {
+        return None;
+    }
+
+    let rt = ldrb & 0x1F;
+    let rn = (ldrb >> 5) & 0x1F;
+    let offset = ((ldrb >> 10) & 0xFFF) as u64;
+
+    let tested = insns.iter().skip(1).take(4).any(|&insn| {
+        let cmp = (insn & 0xFFC0001F) == 0x7100001F && ((insn >> 5) & 0x1F) == rt && ((insn >> 10) & 0xFFF) <= 4;
+        let cbz = (insn & 0xFE000000) == 0x34000000 && (insn & 0x1F) == rt;
+        cmp || cbz
+    });
+
+    tested.then_some((rn, offset))
+}
+
+/// Lowest of three consecutive 8-byte fields off `base` that are all stored
+/// from XZR, via STR or STP.
+fn zeroed_list_heads(insns: &[u32], base: u32) -> Option<u64> {
+    let mut zeroed = HashSet::new();
+
+    for &insn in insns {
+        if ((insn >> 5) & 0x1F) != base {
+            continue;
+        }
+
+        if (insn & 0xFFC0001F) == 0xF900001F {
+            zeroed.insert(((insn >> 10) & 0xFFF) as u64 * 8);
+        } else if (insn & 0xFFC07C1F) == 0xA9007C1F {
+            let imm7 = ((((insn >> 15) & 0x7F) as i32) << 25) >> 25;
+            if imm7 >= 0 {
+                let offset = imm7 as u64 * 8;
+                zeroed.insert(offset);
+                zeroed.insert(offset + 8);
+            }
+        }
+    }
+
+    let mut offsets: Vec<u64> = zeroed.iter().copied().collect();
+    offsets.sort_unstable();
+    offsets.into_iter().find(|o| zeroed.contains(&(o + 8)) && zeroed.contains(&(o + 16)))
+}
+
+/// `LDR Wa, [base, #o]` and `LDR Wb, [base, #o+4]` where a later instruction
+/// shifts a register right by two (`strt.size / 4`). Returns o.
+fn string_table_check(insns: &[u32], base: u32) -> Option<u64> {
+    let loads: Vec<(usize, u64)> = insns.iter()
+        .enumerate()
+        .filter(|(_, &insn)| (insn & 0xFFC00000) == 0xB9400000 && ((insn >> 5) & 0x1F) == base)
+        .map(|(i, &insn)| (i, ((insn >> 10) & 0xFFF) as u64 * 4))
+        .collect();
+
+    loads.iter().find_map(|&(i, nuse)| {
+        let (j, _) = loads.iter().find(|(_, offset)| *offset == nuse + 4)?;
+        let after = i.max(*j);
+        insns[after..].iter()
+            .any(|&insn| (insn & 0xFFFFFC00) == 0x13027C00 || (insn & 0xFFFFFC00) == 0x53027C00)
+            .then_some(nuse)
+    })
+}
+
+/// Two 64-bit loads off `base` compared with each other (`CMP Xa, Xb`).
+fn debt_compare(insns: &[u32], base: u32) -> Option<(u64, u64)> {
+    let mut loaded: HashMap<u32, u64> = HashMap::new();
+
+    for &insn in insns {
+        if (insn & 0xFFC00000) == 0xF9400000 && ((insn >> 5) & 0x1F) == base {
+            loaded.insert(insn & 0x1F, ((insn >> 10) & 0xFFF) as u64 * 8);
+            continue;
+        }
+
+        if (insn & 0xFFE0FC1F) == 0xEB00001F {
+            let rn = (insn >> 5) & 0x1F;
+            let rm = (insn >> 16) & 0x1F;
+            if let (Some(&total), Some(&threshold)) = (loaded.get(&rn), loaded.get(&rm)) {
+                if total != threshold {
+                    return Some((total, threshold));
+                }
+            }
+        }
+    }
+
+    None
+}
+
@@ -17,7 +17,7 @@ pub use opcode::LuauOpcode;
 pub use vm::VmAnalyzer;
 pub use state::StateAnalyzer;
 pub use types::{LuauType, TypeTag, TValue};
-pub use gc::GcAnalyzer;
+pub use gc::{GcAnalyzer, GcRoutine, GcRoutineKind};
 pub use debug::DebugInfoAnalyzer;
 pub use api::LuauApi;
 pub use decompiler::{LuauDecompiler, DecompilationResult, BytecodeAnalyzer, BytecodeAnalysis, Constant, ConstantPropagation};
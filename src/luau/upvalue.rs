@@ -15,6 +15,15 @@ pub enum UpvalueState {
     Closed,
 }
 
+impl UpvalueState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            UpvalueState::Open => "open",
+            UpvalueState::Closed => "closed",
+        }
+    }
+}
+
 /// Represents a Luau upvalue
 #[derive(Debug, Clone)]
 pub struct Upvalue {
@@ -61,9 +70,8 @@ impl Upvalue {
 impl fmt::Display for Upvalue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let name = self.name.as_deref().unwrap_or("unnamed");
-        let state = if self.is_open() { "open" } else { "closed" };
         write!(f, "upval[{}] '{}' @ {:016x} ({})", 
-            self.index, name, self.address.as_u64(), state)?;
+            self.index, name, self.address.as_u64(), self.state.name())?;
         if let Some(ref value) = self.value {
             write!(f, " = {:?}", value)?;
         }
@@ -226,11 +234,14 @@ impl UpvalueRefMap {
             .unwrap_or(0)
     }
 
+    /// Upvalues captured by more than one closure, sorted by address
     pub fn shared_upvalues(&self) -> Vec<Address> {
-        self.references.iter()
+        let mut shared: Vec<Address> = self.references.iter()
             .filter(|(_, refs)| refs.len() > 1)
             .map(|(&addr, _)| Address::new(addr))
-            .collect()
+            .collect();
+        shared.sort();
+        shared
     }
 
     pub fn unique_upvalue_count(&self) -> usize {
@@ -10,25 +10,58 @@ use clap::{Parser, Subcommand};
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 use roblox_offset_generator::{
-    config::Config,
-    memory::{Address, BinaryMemory, MemoryReader},
-    finders::{AllFinders, CombinedResults, RobloxFinders},
+    config::{self, Config},
+    memory::{Address, BinaryMemory, BinarySection, CountingReader, MemoryReader, MemoryRegion, MemoryWriter, MmapMemory},
+    engine::{ChunkScan, PhaseScan, ScanPhase, Task, TaskScheduler, TaskType},
+    engine::chunk::{plan_chunks, run_phase, trim_padding, DEFAULT_CHUNK_OVERLAP},
+    orchestration::{FinderDiagnostics, OutputFinalizer, ResultAggregator, UnmatchedReason},
+    analysis::disasm::{DisassemblyCache, DisassemblyContext, DisassemblyEngine, InstructionFormatter},
+    analysis::string::{StringAnalyzer, StringAnalyzerConfig},
+    analysis::heuristics::{PatternLearner, TunedProfile},
+    analysis::padding::{CodeMap, PaddingScanner},
+    analysis::{ControlFlowGraph, CrossReferenceAnalyzer, Disassembler},
+    analysis::entropy::{high_entropy_spans, EntropyScanner, EntropySpan, EntropyWindow},
+    finders::{CombinedResults, ConfidenceFactor, Finder, FinderResult, RobloxFinders},
+    finders::roblox::{finder_for_output, BUILTIN_FINDERS, FINDER_OUTPUTS},
     finders::{structures, classes, properties, methods, constants},
-    finders::fflags::{FFlagFinder, FFlagDatabase, KnownFlag, get_database},
+    finders::classes::ReflectionCache,
+    finders::fflags::{find_flag_names_in_chunks, FFlagFinder, FFlagDatabase, FFlagCollection, KnownFlag, get_database},
+    finders::fflags::{FFlagChange, FFlagDiff, FFlagParser, FFlagSnapshot},
+    symbol::{ExportFormat, Symbol, SymbolExporter, SymbolResolver},
+    validation::{ConfidenceScorer, ExpectedSize, ImageValidator, IssueSeverity, image::rebase, SizeValidator, parse_expected_sizes, confidence::explain},
+    xref::{CallGraph, CallGraphBuilder, ChainAnalyzer, GraphExporter, GraphNode, InterproceduralAnalyzer, NodeKind, StringXref, StringXrefScanner, SubgraphExtractor},
+    xref::{ExportFormat as GraphFormat, ExportOptions},
+    output::{OffsetOutput, OffsetExporter, OffsetMerger, SqliteExporter, ConfidenceBucket, StatisticsCollector, AddressCollision, CollisionKind, OutputFormatter, TargetInfo, DiffGenerator, FinderReportEntry, FinderStatus, SCAN_SCHEMA_VERSION},
+    structure::{Field, Offset, PathWalker, PrimitiveType, StructureLayout, TypeInfo},
+    luau::{UpvalueAnalyzer, UpvalueRefMap, UpvalueState},
+    server::Session,
+    output::diff::ChangeType,
+    diff::{DiffReportBuilder, OffsetChangeKind, OffsetDiff, OffsetSection, ReportFormat},
+    output::exporter::ExportFormat as OffsetFormat,
+    output::template::render_file_name,
     ui::banner::Banner,
+    ui::table::{Alignment, BorderStyle, TableBuilder},
+    ui::{CliError, DisplayRenderer, FailureKind, Terminal},
+    ui::progress::ProgressManager,
+    pattern::{scan_for_pattern, MaskOptions, SignatureGenerator},
+    utils::{hex_string_spaced, parse_hex, HashComputer},
+    utils::{profile, string, time},
 };
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{Write, BufRead};
-use std::path::PathBuf;
-use std::sync::Arc;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
 use std::fmt::Write as FmtWrite;
 
 #[derive(Parser, Debug)]
 #[command(name = "roblox-offset-generator")]
 #[command(author = "Alex")]
 #[command(version = "1.0.0")]
-#[command(about = "ARM64 Roblox Offset Finder for macOS")]
+#[command(about = "ARM64 Roblox Offset Finder for macOS and Android")]
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
@@ -48,6 +81,19 @@ struct Cli {
     /// Verbose output
     #[arg(short, long, global = true)]
     verbose: bool,
+
+    /// Don't read or write the on-disk disassembly cache
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// Config file (.toml or .json) with confidence weights for the finders
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Interactive menu: run one action, then exit with its status
+    /// (also set by ROBLOX_OFFSET_GENERATOR_ONCE)
+    #[arg(long)]
+    once: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -55,13 +101,36 @@ enum Commands {
     /// Find all offsets from a Roblox binary
     Scan {
         /// Path to Roblox binary
-        #[arg(short, long)]
-        binary: PathBuf,
+        #[arg(short, long, required_unless_present = "pid")]
+        binary: Option<PathBuf>,
+
+        /// Scan this running process (macOS) instead of the file, so offsets
+        /// come out at its ASLR'd runtime addresses
+        #[arg(long, conflicts_with_all = ["watch", "plan", "out_dir", "name_template", "sqlite"])]
+        pid: Option<i32>,
 
         /// Output JSON file path
         #[arg(short, long, default_value = "offsets.json")]
         output: PathBuf,
 
+        /// Output format: json, luau for a table scripts can require, msgpack
+        /// for compact binary, csv with one row per offset, or yaml (a .json
+        /// output path takes the format's extension)
+        #[arg(long, default_value = "json", value_parser = parse_scan_format)]
+        format: ScanFormat,
+
+        /// Write the JSON into this directory, named by --name-template
+        #[arg(long, conflicts_with = "output")]
+        out_dir: Option<PathBuf>,
+
+        /// Output file name built from {target}, {hash8}, {date} and {version}
+        #[arg(long, conflicts_with = "output")]
+        name_template: Option<String>,
+
+        /// Write the JSON output on one line, without pretty-printing
+        #[arg(long)]
+        compact: bool,
+
         /// Also output as text file
         #[arg(long)]
         text: Option<PathBuf>,
@@ -70,13 +139,91 @@ enum Commands {
         #[arg(long)]
         markdown: Option<PathBuf>,
 
-        /// Minimum confidence threshold (0.0-1.0)
-        #[arg(long, default_value = "0.7")]
-        min_confidence: f64,
+        /// Also output an IDAPython script that names discovered functions
+        #[arg(long)]
+        ida_script: Option<PathBuf>,
+
+        /// Also output Ghidra symbols: a .py script, a .csv with the image
+        /// base on every row, otherwise an "Import Symbols" list
+        #[arg(long)]
+        ghidra: Option<PathBuf>,
+
+        /// Also upsert the offsets into this SQLite database, keyed by binary hash
+        #[arg(long)]
+        sqlite: Option<PathBuf>,
+
+        /// Minimum confidence threshold (0.0-1.0) [default: 0.7, or confidence.minimum from --config]
+        #[arg(long)]
+        min_confidence: Option<f64>,
 
         /// Number of threads to use
         #[arg(short, long, default_value = "8")]
         threads: usize,
+
+        /// Rebase all emitted addresses onto this image base (hex)
+        #[arg(long, conflicts_with = "relative")]
+        base: Option<String>,
+
+        /// Emit addresses as deltas from the image base
+        #[arg(long)]
+        relative: bool,
+
+        /// Print the confidence breakdown of every function result
+        #[arg(long)]
+        explain: bool,
+
+        /// Keep running and re-scan whenever the binary changes
+        #[arg(long)]
+        watch: bool,
+
+        /// Scan every executable region in chunks of this many MB, one worker per thread
+        #[arg(long)]
+        chunk_size: Option<u64>,
+
+        /// Fail unless these functions are found (comma separated)
+        #[arg(long, value_delimiter = ',')]
+        require: Vec<String>,
+
+        /// Also fail if a required function is below this confidence
+        #[arg(long, requires = "require")]
+        require_confidence: Option<f64>,
+
+        /// Print a diff against this earlier scan and fail if any of its offsets disappeared
+        #[arg(long)]
+        diff_against: Option<PathBuf>,
+
+        /// Scan at most this many bytes of each executable region (0 = no limit)
+        #[arg(long, default_value = "0")]
+        max_scan_bytes: u64,
+
+        /// Only scan the executable region (segment) with this name, e.g. __TEXT
+        #[arg(long)]
+        region: Option<String>,
+
+        /// Print the regions, finders and estimated work, then exit without scanning
+        #[arg(long, conflicts_with = "watch")]
+        plan: bool,
+
+        /// Per-finder weights and thresholds written by `learn`
+        #[arg(long)]
+        profile: Option<PathBuf>,
+
+        /// Print the unknown-encoding rate of every executable region
+        #[arg(long)]
+        decoder_coverage: bool,
+
+        /// Print a nested timing tree of the scan (load, regions, each finder)
+        #[arg(long)]
+        profile_tree: bool,
+
+        /// Write the scan's timing spans as Chrome trace-event JSON
+        #[arg(long)]
+        profile_json: Option<PathBuf>,
+
+        /// Keep only functions, classes, methods, properties and constants whose
+        /// whole name matches this regex (anchored: use `.*Name.*` for a substring)
+        #[arg(long, value_parser = parse_name_filter)]
+        name_filter: Option<Regex>,
     },
 
     /// Dump FFlags from binary
@@ -93,18 +240,34 @@ enum Commands {
         #[arg(long)]
         text: Option<PathBuf>,
 
+        /// Append the text report, under a line naming the binary and time, instead of overwriting it
+        #[arg(long, requires = "text")]
+        append: bool,
+
         /// Filter by category
         #[arg(long)]
         category: Option<String>,
 
+        /// Skip flags in this category (repeatable)
+        #[arg(long = "exclude-category")]
+        exclude_category: Vec<String>,
+
         /// Search for specific flag name
         #[arg(long)]
         search: Option<String>,
 
+        /// Regex matched against the full flag name
+        #[arg(long, value_parser = parse_name_filter)]
+        regex: Option<Regex>,
+
         /// Show only flags found in binary
         #[arg(long)]
         found_only: bool,
 
+        /// Search data segments for XOR-encoded names of flags not found in plaintext
+        #[arg(long)]
+        xor: bool,
+
         /// List known flag categories
         #[arg(long)]
         list_categories: bool,
@@ -120,9 +283,64 @@ enum Commands {
         #[arg(short, long)]
         new: PathBuf,
 
-        /// Output file path
-        #[arg(short, long)]
+        /// Write the diff report to this file, as text, Markdown or HTML for a .txt, .md or .html path and JSON otherwise.
+        /// Long form only: `-o` is `--old`
+        #[arg(long)]
         output: Option<PathBuf>,
+
+        /// Only report entries whose source file was generated on or after
+        /// this date (YYYY-MM-DD or YYYY-MM-DDTHH:MM:SSZ, UTC)
+        #[arg(long, value_parser = parse_since)]
+        since: Option<u64>,
+
+        /// Read both files as MessagePack whatever the extension (.msgpack and .mpk are detected)
+        #[arg(long)]
+        from_msgpack: bool,
+    },
+
+    /// Combine offset files from several sources into one
+    Merge {
+        /// Offset files to merge, highest priority first on ties
+        #[arg(short, long, num_args = 1.., required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Merged output file
+        #[arg(short, long, default_value = "merged.json")]
+        output: PathBuf,
+    },
+
+    /// Tune per-finder weights and thresholds against known-good offsets
+    Learn {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Verified offsets file for this binary
+        #[arg(long)]
+        truth: PathBuf,
+
+        /// Where to write the tuned profile
+        #[arg(short, long, default_value = "tuned.json")]
+        output: PathBuf,
+
+        /// Threshold kept for finders the truth has nothing to say about [default: 0.7, or confidence.minimum from --config]
+        #[arg(long)]
+        min_confidence: Option<f64>,
+
+        /// Scan at most this many bytes of each executable region (0 = no limit)
+        #[arg(long, default_value = "0")]
+        max_scan_bytes: u64,
+    },
+
+    /// Compare two FFlag dumps
+    FflagsDiff {
+        /// Old FFlag JSON output
+        #[arg(long)]
+        old: PathBuf,
+
+        /// New FFlag JSON output
+        #[arg(long)]
+        new: PathBuf,
     },
 
     /// Validate offsets against a binary
@@ -131,9 +349,25 @@ enum Commands {
         #[arg(short, long)]
         offsets: PathBuf,
 
-        /// Path to Roblox binary
-        #[arg(short, long)]
-        binary: PathBuf,
+        /// Path to Roblox binary; with --pid, live bytes are also compared to it
+        #[arg(short, long, required_unless_present = "pid")]
+        binary: Option<PathBuf>,
+
+        /// Validate against this running process (macOS), rebased by its ASLR slide
+        #[arg(long)]
+        pid: Option<i32>,
+
+        /// Expected struct sizes, one name=size per line (e.g. lua_State=0x140)
+        #[arg(long)]
+        sizes: Option<PathBuf>,
+
+        /// Bytes a struct size may differ from its expected size and still pass
+        #[arg(long, default_value = "0", requires = "sizes")]
+        size_tolerance: usize,
+
+        /// Read the offsets file as MessagePack whatever the extension (.msgpack and .mpk are detected)
+        #[arg(long)]
+        from_msgpack: bool,
     },
 
     /// Dump memory at address
@@ -155,67 +389,459 @@ enum Commands {
         disasm: bool,
     },
 
+    /// Disassemble a function from its entry to the first RET
+    Disasm {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Function entry address (hex)
+        #[arg(short, long)]
+        function: String,
+
+        /// Write the decoded instructions as JSON to this file
+        #[arg(long)]
+        json: Option<PathBuf>,
+
+        /// Stop decoding after this many instructions if no RET comes first
+        #[arg(long, default_value = "10000")]
+        max_insns: usize,
+
+        /// Print the function's basic blocks and the edges between them
+        #[arg(long, conflicts_with = "json")]
+        cfg: bool,
+    },
+
+    /// Render a function's control flow graph
+    Cfg {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Function entry address (hex)
+        #[arg(short, long)]
+        address: String,
+
+        /// Graph format: dot, mermaid or json
+        #[arg(long, default_value = "dot", value_parser = parse_graph_format)]
+        format: GraphFormat,
+
+        /// Write the graph here instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Stop exploring the function after this many instructions
+        #[arg(long, default_value = "10000")]
+        max_insns: usize,
+    },
+
+    /// Generate a masked byte signature from a known-good address
+    SigGen {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Address to read the signature from (hex)
+        #[arg(short, long)]
+        address: String,
+
+        /// Number of bytes to include
+        #[arg(short, long, default_value = "32")]
+        length: usize,
+
+        /// Operand kinds to wildcard, comma-separated
+        /// (pc-relative, branches, page-offsets, immediates)
+        #[arg(long, value_delimiter = ',')]
+        mask: Option<Vec<String>>,
+    },
+
+    /// List functions reachable from a root by direct calls
+    Reach {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Root function address (hex)
+        #[arg(short, long)]
+        from: String,
+
+        /// Report whether this function (hex) is reached
+        #[arg(short, long)]
+        to: Option<String>,
+
+        /// Maximum call depth to follow
+        #[arg(long, default_value = "8")]
+        max_depth: usize,
+    },
+
+    /// Export the call graph around a function (DOT by default), or list
+    /// the code that references a string
+    Xref {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Root function address (hex)
+        #[arg(short, long, required_unless_present = "string")]
+        function: Option<String>,
+
+        /// List the instructions loading this C string, and their functions
+        #[arg(short, long, conflicts_with = "function")]
+        string: Option<String>,
+
+        /// Graph format: dot, json, d3, graphml, csv or mermaid
+        #[arg(long, default_value = "dot", value_parser = parse_graph_format)]
+        format: GraphFormat,
+
+        /// Write the graph here instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Maximum call depth below the root
+        #[arg(long, default_value = "3")]
+        depth: usize,
+
+        /// Leave out BLR (call through register) edges
+        #[arg(long)]
+        no_indirect: bool,
+    },
+
+    /// Trace a string to the functions that load it
+    Chain {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Exact string to trace
+        #[arg(short, long)]
+        string: String,
+    },
+
+    /// List strings found in the binary
+    Strings {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Minimum string length in characters (at least 1)
+        #[arg(long, default_value = "4", value_parser = parse_min_len)]
+        min_len: usize,
+
+        /// Encodings to scan: ascii (includes UTF-8), utf16, or all
+        #[arg(long, default_value = "ascii", value_parser = parse_string_encoding)]
+        encoding: (bool, bool),
+    },
+
+    /// List every ADRP+ADD/ADR load of a string literal as code -> "string"
+    StringXrefs {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+    },
+
+    /// List the symbol table: address, type and demangled name
+    Symbols {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Only symbols whose raw or demangled name contains this
+        #[arg(long)]
+        filter: Option<String>,
+
+        /// Export instead of listing: ida, ghidra, ghidra-symbols, ghidra-csv,
+        /// binja, json, csv, header, lldb, gdb or map
+        #[arg(long, value_parser = parse_symbol_format)]
+        export: Option<ExportFormat>,
+
+        /// Write the export here instead of stdout
+        #[arg(short, long, requires = "export")]
+        output: Option<PathBuf>,
+
+        /// Page of the listing to show, starting at 1
+        #[arg(long, default_value = "1")]
+        page: usize,
+
+        /// Symbols per page
+        #[arg(long, default_value = "100")]
+        page_size: usize,
+    },
+
+    /// Rank fixed-size windows by Shannon entropy to spot packed or encrypted data
+    Entropy {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Window size in bytes
+        #[arg(long, default_value = "4096")]
+        window: usize,
+
+        /// Bits per byte at which a window counts as packed or encrypted
+        #[arg(long, default_value = "7.2")]
+        threshold: f64,
+
+        /// How many of the highest-entropy windows to list
+        #[arg(long, default_value = "20")]
+        top: usize,
+    },
+
+    /// Write bytes at an address into a patched copy of the binary
+    Patch {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Address to patch (hex or decimal)
+        #[arg(short, long)]
+        address: String,
+
+        /// Bytes to write, e.g. "1F 20 03 D5"
+        #[arg(long)]
+        bytes: String,
+
+        /// Where to save the patched copy
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Follow a field path like DataModel.Workspace.Camera from a base address
+    StructWalk {
+        /// Scan output providing the structure layouts
+        #[arg(short, long)]
+        offsets: PathBuf,
+
+        /// Read from this binary
+        #[arg(short, long, required_unless_present = "pid", conflicts_with = "pid")]
+        binary: Option<PathBuf>,
+
+        /// Read from this running process (macOS)
+        #[arg(long)]
+        pid: Option<i32>,
+
+        /// Address of the first structure in the path (hex)
+        #[arg(long)]
+        base: String,
+
+        /// Dotted path: Structure.field[.field...]
+        #[arg(short, long)]
+        path: String,
+    },
+
+    /// Report the upvalues Luau closures capture and which ones they share
+    Upvalues {
+        /// Read from this binary or memory dump
+        #[arg(short, long, required_unless_present = "pid", conflicts_with = "pid")]
+        binary: Option<PathBuf>,
+
+        /// Read from this running process (macOS)
+        #[arg(long)]
+        pid: Option<i32>,
+
+        /// Closure addresses (hex), comma-separated
+        #[arg(short, long, value_delimiter = ',', required = true)]
+        closure: Vec<String>,
+    },
+
+    /// Print one offset by name, as bare hex for shell capture
+    Get {
+        /// Path to Roblox binary
+        #[arg(short, long)]
+        binary: PathBuf,
+
+        /// Result name: a function (LuauLoad), class, constant or Structure.field
+        #[arg(short, long)]
+        name: String,
+
+        /// Run every finder instead of only the one that reports this name
+        #[arg(long)]
+        all_finders: bool,
+    },
+
+    /// Answer line-delimited JSON-RPC requests on stdin, keeping the binary loaded
+    Serve,
+
     /// Show statistics about offset file
     Stats {
         /// Offsets file
         #[arg(short, long)]
         input: PathBuf,
+
+        /// Read the file as MessagePack whatever the extension (.msgpack and .mpk are detected)
+        #[arg(long)]
+        from_msgpack: bool,
     },
 }
 
 fn main() {
     let cli = Cli::parse();
 
-    if !cli.no_banner {
+    // `get` output is meant for `$(...)` and `serve` speaks JSON on stdout,
+    // so nothing else goes there
+    if !cli.no_banner && !matches!(cli.command, Some(Commands::Get { .. }) | Some(Commands::Serve)) {
         print_banner();
     }
 
     // If no command provided, show interactive menu
-    let result = match &cli.command {
+    let result = apply_config(cli.config.as_deref()).and_then(|()| match &cli.command {
         None => run_interactive_menu(&cli),
-        Some(Commands::Scan { binary, output, text, markdown, min_confidence, threads }) => {
-            run_scan(&cli, binary.clone(), output.clone(), text.clone(), markdown.clone(), *min_confidence, *threads)
+        Some(Commands::Scan { binary, pid, output, format, out_dir, name_template, compact, text, markdown, ida_script, ghidra, sqlite, min_confidence, threads, base, relative, explain, watch, chunk_size, require, require_confidence, diff_against, max_scan_bytes, region, plan, profile, decoder_coverage, profile_tree, profile_json, name_filter }) => {
+            parse_base(base.as_deref()).and_then(|base| run_scan(&cli, ScanOptions {
+                // A process scan without a binary is named after the pid
+                binary: binary.clone().unwrap_or_else(|| PathBuf::from(format!("pid-{}", pid.unwrap_or_default()))),
+                pid: *pid,
+                output: output.clone(),
+                format: *format,
+                out_dir: out_dir.clone(),
+                name_template: name_template.clone(),
+                compact: *compact,
+                text: text.clone(),
+                markdown: markdown.clone(),
+                ida_script: ida_script.clone(),
+                ghidra: ghidra.clone(),
+                sqlite: sqlite.clone(),
+                min_confidence: min_confidence.unwrap_or(config::confidence().minimum),
+                threads: *threads,
+                base,
+                relative: *relative,
+                explain: *explain,
+                watch: *watch,
+                chunk_size: *chunk_size,
+                require: require.clone(),
+                require_confidence: *require_confidence,
+                diff_against: diff_against.clone(),
+                max_scan_bytes: *max_scan_bytes,
+                region: region.clone(),
+                plan: *plan,
+                profile: profile.clone(),
+                decoder_coverage: *decoder_coverage,
+                profile_tree: *profile_tree,
+                profile_json: profile_json.clone(),
+                name_filter: name_filter.clone(),
+            }))
         }
-        Some(Commands::Fflags { binary, output, text, category, search, found_only, list_categories }) => {
-            run_fflags(&cli, binary.clone(), output.clone(), text.clone(), category.clone(), search.clone(), *found_only, *list_categories)
+        Some(Commands::Fflags { binary, output, text, append, category, exclude_category, search, regex, found_only, xor, list_categories }) => {
+            run_fflags(&cli, binary.clone(), output.clone(), text.clone(), *append, category.clone(), exclude_category.clone(), search.clone(), regex.clone(), *found_only, *xor, *list_categories)
         }
-        Some(Commands::Diff { old, new, output }) => {
-            run_diff(&cli, old.clone(), new.clone(), output.clone())
+        Some(Commands::Diff { old, new, output, since, from_msgpack }) => {
+            run_diff(&cli, old.clone(), new.clone(), output.clone(), *since, *from_msgpack)
         }
-        Some(Commands::Validate { offsets, binary }) => {
-            run_validate(&cli, offsets.clone(), binary.clone())
+        Some(Commands::Merge { inputs, output }) => {
+            run_merge(inputs.clone(), output.clone())
+        }
+        Some(Commands::Learn { binary, truth, output, min_confidence, max_scan_bytes }) => {
+            run_learn(&cli, binary.clone(), truth.clone(), output.clone(), min_confidence.unwrap_or(config::confidence().minimum), *max_scan_bytes)
+        }
+        Some(Commands::FflagsDiff { old, new }) => {
+            run_fflags_diff(old.clone(), new.clone())
+        }
+        Some(Commands::Validate { offsets, binary, pid, sizes, size_tolerance, from_msgpack }) => {
+            run_validate(&cli, offsets.clone(), binary.clone(), *pid, sizes.clone(), *size_tolerance, *from_msgpack)
         }
         Some(Commands::Dump { binary, address, size, disasm }) => {
             run_dump(&cli, binary.clone(), address.clone(), *size, *disasm)
         }
-        Some(Commands::Stats { input }) => {
-            run_stats(&cli, input.clone())
+        Some(Commands::Disasm { binary, function, json, max_insns, cfg }) => {
+            run_disasm(&cli, binary.clone(), function.clone(), json.clone(), *max_insns, *cfg)
         }
-    };
+        Some(Commands::Cfg { binary, address, format, output, max_insns }) => {
+            run_cfg(binary.clone(), address.clone(), *format, output.clone(), *max_insns)
+        }
+        Some(Commands::SigGen { binary, address, length, mask }) => {
+            run_sig_gen(binary.clone(), address.clone(), *length, mask.clone())
+        }
+        Some(Commands::Reach { binary, from, to, max_depth }) => {
+            run_reach(&cli, binary.clone(), from.clone(), to.clone(), *max_depth)
+        }
+        Some(Commands::Xref { binary, function, string, format, output, depth, no_indirect }) => {
+            match (function, string) {
+                (_, Some(string)) => run_string_xref(binary.clone(), string.clone()),
+                (Some(function), None) => run_xref(binary.clone(), function.clone(), *format, output.clone(), *depth, *no_indirect),
+                (None, None) => Err(CliError::validation("xref needs --function or --string")),
+            }
+        }
+        Some(Commands::Chain { binary, string }) => {
+            run_chain(&cli, binary.clone(), string.clone())
+        }
+        Some(Commands::Strings { binary, min_len, encoding }) => {
+            run_strings(&cli, binary.clone(), *min_len, *encoding)
+        }
+        Some(Commands::StringXrefs { binary }) => {
+            run_string_xrefs(binary.clone())
+        }
+        Some(Commands::Symbols { binary, filter, export, output, page, page_size }) => {
+            run_symbols(binary.clone(), filter.clone(), *export, output.clone(), *page, *page_size)
+        }
+        Some(Commands::Entropy { binary, window, threshold, top }) => {
+            run_entropy(binary.clone(), *window, *threshold, *top)
+        }
+        Some(Commands::Patch { binary, address, bytes, output }) => {
+            run_patch(binary.clone(), address.clone(), bytes.clone(), output.clone())
+        }
+        Some(Commands::StructWalk { offsets, binary, pid, base, path }) => {
+            run_struct_walk(&cli, offsets.clone(), binary.clone(), *pid, base.clone(), path.clone())
+        }
+        Some(Commands::Upvalues { binary, pid, closure }) => {
+            run_upvalues(binary.clone(), *pid, closure.clone())
+        }
+        Some(Commands::Get { binary, name, all_finders }) => {
+            run_get(&cli, binary.clone(), name.clone(), *all_finders)
+        }
+        Some(Commands::Serve) => {
+            run_serve()
+        }
+        Some(Commands::Stats { input, from_msgpack }) => {
+            run_stats(&cli, input.clone(), *from_msgpack)
+        }
+    });
 
     if let Err(e) = result {
         eprintln!("{} {}", "[ERROR]".red().bold(), e);
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     }
 }
 
-fn print_banner() {
-    println!();
-    println!("{}", r#"  ____       _     _              ____  __  __          _   "#.cyan());
+/// Loads `--config` and hands its confidence weights to the finders
+fn apply_config(path: Option<&Path>) -> Result<(), CliError> {
+    let path = match path {
+        Some(path) => path,
+        None => return Ok(()),
+    };
+
+    let content = std::fs::read_to_string(path)
+        .map_err(CliError::io(format!("read config {}", path.display())))?;
+    let config = Config::parse(&content, path)
+        .map_err(CliError::parse(format!("config {}", path.display())))?;
+    config.confidence.validate()
+        .map_err(|e| CliError::validation(format!("Invalid config {}: {}", path.display(), e)))?;
+
+    config::set_confidence(config.confidence);
+    Ok(())
+}
+
+fn print_banner() {
+    println!();
+    println!("{}", r#"  ____       _     _              ____  __  __          _   "#.cyan());
     println!("{}", r#" |  _ \ ___ | |__ | | _____  __  / __ \/ _|/ _|___  ___| |_ "#.cyan());
     println!("{}", r#" | |_) / _ \| '_ \| |/ _ \ \/ / | |  | | |_| |_/ __|/ _ \ __|"#.cyan());
     println!("{}", r#" |  _ < (_) | |_) | | (_) >  <  | |__| |  _|  _\__ \  __/ |_ "#.cyan());
     println!("{}", r#" |_| \_\___/|_.__/|_|\___/_/\_\  \____/|_| |_| |___/\___|\__|"#.cyan());
     println!();
-    println!("{}", "        ARM64 Offset Finder for macOS v1.0.0".bright_black());
+    println!("{}", "   ARM64 Offset Finder for macOS and Android v1.0.0".bright_black());
     println!();
 }
 
 // ==================== INTERACTIVE MENU ====================
 
-fn run_interactive_menu(cli: &Cli) -> Result<(), String> {
-    // Check if stdin is a terminal (interactive)
-    if !atty::is(atty::Stream::Stdin) {
+/// Set (to anything) for the same effect as `--once`
+const ONCE_ENV: &str = "ROBLOX_OFFSET_GENERATOR_ONCE";
+
+fn run_interactive_menu(cli: &Cli) -> Result<(), CliError> {
+    let once = cli.once || std::env::var_os(ONCE_ENV).is_some();
+
+    // Check if stdin is a terminal (interactive). With --once the answers
+    // may be piped in, so the menu runs either way.
+    if !once && !atty::is(atty::Stream::Stdin) {
         println!("{}", "Not running in interactive mode. Use --help for command line options.".yellow());
         print_help();
         return Ok(());
@@ -254,42 +880,20 @@ fn run_interactive_menu(cli: &Cli) -> Result<(), String> {
 
         println!();
 
-        match choice {
-            "1" => {
-                if let Err(e) = menu_full_scan(cli) {
-                    eprintln!("{} {}", "[ERROR]".red(), e);
-                }
-            }
-            "2" => {
-                if let Err(e) = menu_fflag_dump(cli) {
-                    eprintln!("{} {}", "[ERROR]".red(), e);
-                }
-            }
+        let result = match choice {
+            "1" => menu_full_scan(cli),
+            "2" => menu_fflag_dump(cli),
             "3" => {
                 menu_fflag_categories();
+                Ok(())
             }
-            "4" => {
-                if let Err(e) = menu_memory_dump(cli) {
-                    eprintln!("{} {}", "[ERROR]".red(), e);
-                }
-            }
-            "5" => {
-                if let Err(e) = menu_diff(cli) {
-                    eprintln!("{} {}", "[ERROR]".red(), e);
-                }
-            }
-            "6" => {
-                if let Err(e) = menu_validate(cli) {
-                    eprintln!("{} {}", "[ERROR]".red(), e);
-                }
-            }
-            "7" => {
-                if let Err(e) = menu_stats(cli) {
-                    eprintln!("{} {}", "[ERROR]".red(), e);
-                }
-            }
+            "4" => menu_memory_dump(cli),
+            "5" => menu_diff(cli),
+            "6" => menu_validate(cli),
+            "7" => menu_stats(cli),
             "8" => {
                 print_help();
+                Ok(())
             }
             "0" | "q" | "exit" | "quit" => {
                 println!("{}", "Goodbye!".cyan());
@@ -297,19 +901,34 @@ fn run_interactive_menu(cli: &Cli) -> Result<(), String> {
             }
             _ => {
                 println!("{} Invalid choice. Please enter 0-8.", "[!]".yellow());
+                println!();
+                continue;
             }
+        };
+
+        if once {
+            return result;
+        }
+        if let Err(e) = result {
+            eprintln!("{} {}", "[ERROR]".red(), e);
         }
         println!();
     }
     Ok(())
 }
 
+/// The trimmed answer, or an empty string once stdin is closed or unreadable
 fn prompt(msg: &str) -> String {
     print!("{}", msg.yellow());
-    std::io::stdout().flush().unwrap();
+    let _ = std::io::stdout().flush();
     let mut input = String::new();
-    std::io::stdin().read_line(&mut input).unwrap();
-    input.trim().to_string()
+    match std::io::stdin().read_line(&mut input) {
+        Ok(0) | Err(_) => {
+            println!();
+            String::new()
+        }
+        Ok(_) => input.trim().to_string(),
+    }
 }
 
 fn prompt_path(msg: &str) -> Option<PathBuf> {
@@ -321,17 +940,17 @@ fn prompt_path(msg: &str) -> Option<PathBuf> {
     }
 }
 
-fn menu_full_scan(cli: &Cli) -> Result<(), String> {
+fn menu_full_scan(cli: &Cli) -> Result<(), CliError> {
     println!("{}", "═".repeat(55).cyan());
     println!("{}", "              FULL OFFSET SCAN".cyan().bold());
     println!("{}", "═".repeat(55).cyan());
     println!();
 
     let binary = prompt_path("  Enter path to Roblox binary: ")
-        .ok_or("Binary path is required")?;
+        .ok_or_else(|| CliError::invalid("Binary path is required"))?;
 
     if !binary.exists() {
-        return Err(format!("File not found: {}", binary.display()));
+        return Err(CliError::not_found(format!("File not found: {}", binary.display())));
     }
 
     let output_str = prompt("  Output file [offsets.json]: ");
@@ -341,28 +960,29 @@ fn menu_full_scan(cli: &Cli) -> Result<(), String> {
         PathBuf::from(output_str)
     };
 
-    let conf_str = prompt("  Minimum confidence (0.0-1.0) [0.7]: ");
+    let default_confidence = config::confidence().minimum;
+    let conf_str = prompt(&format!("  Minimum confidence (0.0-1.0) [{}]: ", default_confidence));
     let min_confidence: f64 = if conf_str.is_empty() {
-        0.7
+        default_confidence
     } else {
-        conf_str.parse().unwrap_or(0.7)
+        conf_str.parse().unwrap_or(default_confidence)
     };
 
     println!();
-    run_scan(cli, binary, output, None, None, min_confidence, 8)
+    run_scan(cli, ScanOptions::new(binary, output, min_confidence))
 }
 
-fn menu_fflag_dump(cli: &Cli) -> Result<(), String> {
+fn menu_fflag_dump(cli: &Cli) -> Result<(), CliError> {
     println!("{}", "═".repeat(55).cyan());
     println!("{}", "               FFLAG DUMP".cyan().bold());
     println!("{}", "═".repeat(55).cyan());
     println!();
 
     let binary = prompt_path("  Enter path to Roblox binary: ")
-        .ok_or("Binary path is required")?;
+        .ok_or_else(|| CliError::invalid("Binary path is required"))?;
 
     if !binary.exists() {
-        return Err(format!("File not found: {}", binary.display()));
+        return Err(CliError::not_found(format!("File not found: {}", binary.display())));
     }
 
     let output_str = prompt("  Output file [fflags.json]: ");
@@ -382,7 +1002,7 @@ fn menu_fflag_dump(cli: &Cli) -> Result<(), String> {
     let found_only = found_only_str.to_lowercase() == "y";
 
     println!();
-    run_fflags(cli, Some(binary), output, None, category, search, found_only, false)
+    run_fflags(cli, Some(binary), output, None, false, category, Vec::new(), search, None, found_only, false, false)
 }
 
 fn menu_fflag_categories() {
@@ -409,22 +1029,22 @@ fn menu_fflag_categories() {
     println!("  {} Total known flags: {}", "★".yellow(), db.count().to_string().green().bold());
 }
 
-fn menu_memory_dump(cli: &Cli) -> Result<(), String> {
+fn menu_memory_dump(cli: &Cli) -> Result<(), CliError> {
     println!("{}", "═".repeat(55).cyan());
     println!("{}", "              MEMORY DUMP".cyan().bold());
     println!("{}", "═".repeat(55).cyan());
     println!();
 
     let binary = prompt_path("  Enter path to binary: ")
-        .ok_or("Binary path is required")?;
+        .ok_or_else(|| CliError::invalid("Binary path is required"))?;
 
     if !binary.exists() {
-        return Err(format!("File not found: {}", binary.display()));
+        return Err(CliError::not_found(format!("File not found: {}", binary.display())));
     }
 
     let address = prompt("  Address to dump (e.g., 0x100000): ");
     if address.is_empty() {
-        return Err("Address is required".to_string());
+        return Err(CliError::invalid("Address is required"));
     }
 
     let size_str = prompt("  Bytes to dump [256]: ");
@@ -441,47 +1061,47 @@ fn menu_memory_dump(cli: &Cli) -> Result<(), String> {
     run_dump(cli, binary, address, size, disasm)
 }
 
-fn menu_diff(cli: &Cli) -> Result<(), String> {
+fn menu_diff(cli: &Cli) -> Result<(), CliError> {
     println!("{}", "═".repeat(55).cyan());
     println!("{}", "              OFFSET DIFF".cyan().bold());
     println!("{}", "═".repeat(55).cyan());
     println!();
 
     let old = prompt_path("  Enter path to OLD offsets file: ")
-        .ok_or("Old file path is required")?;
+        .ok_or_else(|| CliError::invalid("Old file path is required"))?;
     let new = prompt_path("  Enter path to NEW offsets file: ")
-        .ok_or("New file path is required")?;
+        .ok_or_else(|| CliError::invalid("New file path is required"))?;
 
     println!();
-    run_diff(cli, old, new, None)
+    run_diff(cli, old, new, None, None, false)
 }
 
-fn menu_validate(cli: &Cli) -> Result<(), String> {
+fn menu_validate(cli: &Cli) -> Result<(), CliError> {
     println!("{}", "═".repeat(55).cyan());
     println!("{}", "            VALIDATE OFFSETS".cyan().bold());
     println!("{}", "═".repeat(55).cyan());
     println!();
 
     let offsets = prompt_path("  Enter path to offsets file: ")
-        .ok_or("Offsets file is required")?;
+        .ok_or_else(|| CliError::invalid("Offsets file is required"))?;
     let binary = prompt_path("  Enter path to binary: ")
-        .ok_or("Binary path is required")?;
+        .ok_or_else(|| CliError::invalid("Binary path is required"))?;
 
     println!();
-    run_validate(cli, offsets, binary)
+    run_validate(cli, offsets, Some(binary), None, None, 0, false)
 }
 
-fn menu_stats(cli: &Cli) -> Result<(), String> {
+fn menu_stats(cli: &Cli) -> Result<(), CliError> {
     println!("{}", "═".repeat(55).cyan());
     println!("{}", "            OFFSET STATISTICS".cyan().bold());
     println!("{}", "═".repeat(55).cyan());
     println!();
 
     let input = prompt_path("  Enter path to offsets file: ")
-        .ok_or("File path is required")?;
+        .ok_or_else(|| CliError::invalid("File path is required"))?;
 
     println!();
-    run_stats(cli, input)
+    run_stats(cli, input, false)
 }
 
 fn print_help() {
@@ -493,30 +1113,98 @@ fn print_help() {
     println!();
     println!("  {} {}", "./roblox-offset-generator".green(), "                  # Interactive menu");
     println!("  {} {}", "./roblox-offset-generator scan -b <binary>".green(), "  # Full scan");
+    println!("  {} {}", "./roblox-offset-generator scan -b <binary> --out-dir scans --name-template \"{target}_{version}.json\"".green(), "");
+    println!("  {} {}", "./roblox-offset-generator learn -b <binary> --truth verified.json -o tuned.json".green(), "");
+    println!("  {} {}", "./roblox-offset-generator scan -b <binary> --profile tuned.json".green(), "");
     println!("  {} {}", "./roblox-offset-generator fflags -b <binary>".green(), " # FFlag dump");
     println!("  {} {}", "./roblox-offset-generator fflags --list-categories".green(), "");
     println!("  {} {}", "./roblox-offset-generator dump -b <binary> -a 0x1000".green(), "");
+    println!("  {} {}", "./roblox-offset-generator disasm -b <binary> -f 0x1000 --json out.json".green(), "");
+    println!("  {} {}", "./roblox-offset-generator disasm -b <binary> -f 0x1000 --max-insns 50000".green(), "");
+    println!("  {} {}", "./roblox-offset-generator disasm -b <binary> -f 0x1000 --cfg".green(), "");
+    println!("  {} {}", "./roblox-offset-generator cfg -b <binary> -a 0x1000 -o graph.dot".green(), "");
+    println!("  {} {}", "./roblox-offset-generator sig-gen -b <binary> -a 0x1000 --length 32".green(), "");
+    println!("  {} {}", "./roblox-offset-generator reach -b <binary> -f 0x1000 -t 0x2000".green(), "");
+    println!("  {} {}", "./roblox-offset-generator xref -b <binary> -f 0x1000 --format dot -o graph.dot".green(), "");
+    println!("  {} {}", "./roblox-offset-generator xref -b <binary> --string \"compile error\"".green(), "");
+    println!("  {} {}", "./roblox-offset-generator chain -b <binary> -s \"DataModel\"".green(), "");
+    println!("  {} {}", "./roblox-offset-generator strings -b <binary> --min-len 6 --encoding utf16".green(), "");
+    println!("  {} {}", "./roblox-offset-generator string-xrefs -b <binary>".green(), "   # code -> \"string\"");
+    println!("  {} {}", "./roblox-offset-generator symbols -b <binary> --filter RBX::Instance --page 2".green(), "");
+    println!("  {} {}", "./roblox-offset-generator symbols -b <binary> --export ghidra-symbols -o symbols.txt".green(), "");
+    println!("  {} {}", "./roblox-offset-generator entropy -b <binary> --window 4096 --threshold 7.2".green(), "");
+    println!("  {} {}", "./roblox-offset-generator patch -b <binary> -a 0x100004000 --bytes \"1F 20 03 D5\" -o patched.bin".green(), "");
+    println!("  {} {}", "./roblox-offset-generator struct-walk -o offsets.json -b <binary> --base 0x1000 -p DataModel.Workspace".green(), "");
+    println!("  {} {}", "./roblox-offset-generator upvalues --pid 1234 -c 0x12345000,0x12346000".green(), "");
+    println!("  {} {}", "./roblox-offset-generator serve".green(), "   # JSON-RPC over stdin/stdout");
     println!("  {} {}", "./roblox-offset-generator diff -o old.json -n new.json".green(), "");
+    println!("  {} {}", "./roblox-offset-generator diff -o old.json -n new.json --since 2026-01-01".green(), "");
+    println!("  {} {}", "./roblox-offset-generator get -b <binary> -n LuauLoad".green(), "   # Bare hex");
+    println!("  {} {}", "./roblox-offset-generator validate -o offsets.json --pid <pid>".green(), "  # Live, ASLR-aware");
+    println!("  {} {}", "./roblox-offset-generator validate -o offsets.json -b <binary> --sizes sizes.txt".green(), "  # lua_State=0x140 per line");
+    println!("  {} {}", "./roblox-offset-generator fflags-diff --old old.json --new new.json".green(), "");
+    println!("  {} {}", "./roblox-offset-generator diff --old offsets_v1.msgpack --new offsets_v2.json".green(), "");
+    println!("  {} {}", "./roblox-offset-generator merge -i a.json b.json -o merged.json".green(), "");
     println!();
     println!("{}", "SCAN OPTIONS:".yellow().bold());
     println!("  {:<20} {}", "-b, --binary", "Path to Roblox binary");
+    println!("  {:<20} {}", "--pid", "Scan a running process (macOS) instead of a file");
     println!("  {:<20} {}", "-o, --output", "Output JSON file (default: offsets.json)");
+    println!("  {:<20} {}", "--format luau", "Write a Luau table instead of JSON (.json -> .luau)");
+    println!("  {:<20} {}", "--format msgpack", "Write MessagePack instead of JSON (.json -> .msgpack)");
+    println!("  {:<20} {}", "--format csv", "One row per offset: kind,name,address,confidence,category");
+    println!("  {:<20} {}", "--format yaml", "Write the offsets as YAML (.json -> .yaml)");
+    println!("  {:<20} {}", "--out-dir", "Write into this directory with a computed name");
+    println!("  {:<20} {}", "--name-template", "Name from {target} {hash8} {date} {version}");
+    println!("  {:<20} {}", "--compact", "Write the JSON on one line (smaller, faster to parse)");
     println!("  {:<20} {}", "--text", "Also save as text file");
     println!("  {:<20} {}", "--markdown", "Also save as markdown file");
+    println!("  {:<20} {}", "--ida-script", "Also save an IDAPython naming script");
+    println!("  {:<20} {}", "--ghidra", "Also save Ghidra symbols (.py script, .csv or symbol list)");
+    println!("  {:<20} {}", "--sqlite", "Also upsert into a SQLite database across versions");
     println!("  {:<20} {}", "--min-confidence", "Minimum confidence threshold (0.0-1.0)");
+    println!("  {:<20} {}", "--base", "Rebase emitted addresses onto this image base");
+    println!("  {:<20} {}", "--relative", "Emit addresses relative to the image base");
+    println!("  {:<20} {}", "--explain", "Print per-result confidence breakdowns");
+    println!("  {:<20} {}", "--watch", "Re-scan whenever the binary changes");
+    println!("  {:<20} {}", "--chunk-size <MB>", "Scan all executable regions in parallel chunks");
+    println!("  {:<20} {}", "--region <NAME>", "Only scan the named executable region, e.g. __TEXT");
+    println!("  {:<20} {}", "--require a,b", "Fail if any of these functions is not found");
+    println!("  {:<20} {}", "--require-confidence", "Fail if a required function is below this");
+    println!("  {:<20} {}", "--diff-against", "Diff against an earlier scan; fail if offsets vanish");
+    println!("  {:<20} {}", "--max-scan-bytes", "Cap the scanned region size (default 0: no limit)");
+    println!("  {:<20} {}", "--plan", "Show regions, finders and estimated work, then exit");
+    println!("  {:<20} {}", "--profile", "Per-finder weights and thresholds from `learn`");
+    println!("  {:<20} {}", "--decoder-coverage", "Print each region's unknown-encoding rate");
+    println!("  {:<20} {}", "--profile-tree", "Print where scan time goes as a nested timing tree");
+    println!("  {:<20} {}", "--profile-json", "Write scan timings as a Chrome/Perfetto trace");
+    println!("  {:<20} {}", "--name-filter", "Keep only results whose whole name matches a regex");
     println!();
     println!("{}", "FFLAG OPTIONS:".yellow().bold());
     println!("  {:<20} {}", "-b, --binary", "Path to Roblox binary");
     println!("  {:<20} {}", "--list-categories", "List all known flag categories");
     println!("  {:<20} {}", "--category", "Filter by category name");
+    println!("  {:<20} {}", "--exclude-category", "Skip a category (repeatable)");
     println!("  {:<20} {}", "--search", "Search for flag by name");
+    println!("  {:<20} {}", "--regex", "Filter flags by regex on the full name");
+    println!("  {:<20} {}", "--append", "Append the --text report instead of overwriting it");
     println!("  {:<20} {}", "--found-only", "Only show flags found in binary");
+    println!("  {:<20} {}", "--xor", "Also find XOR-encoded names in data segments");
     println!();
     println!("{}", "GLOBAL OPTIONS:".yellow().bold());
     println!("  {:<20} {}", "--no-banner", "Hide the banner");
     println!("  {:<20} {}", "--no-progress", "Disable progress bars");
     println!("  {:<20} {}", "--no-color", "Disable colored output");
     println!("  {:<20} {}", "-v, --verbose", "Verbose output");
+    println!("  {:<20} {}", "--no-cache", "Skip the on-disk disassembly cache");
+    println!("  {:<20} {}", "--config <FILE>", "Confidence weights from a .toml or .json config");
+    println!("  {:<20} {}", "--once", "Menu: run one action and exit (or ROBLOX_OFFSET_GENERATOR_ONCE)");
+    println!();
+    println!("{}", "EXIT CODES:".yellow().bold());
+    println!("  {:<20} {}", "0", "Success");
+    for kind in FailureKind::ALL {
+        println!("  {:<20} {}", kind.code(), kind.description());
+    }
     println!();
 }
 
@@ -553,501 +1241,3040 @@ fn create_spinner(msg: &str, no_progress: bool) -> Option<ProgressBar> {
 
 // ==================== SCAN COMMAND ====================
 
-fn run_scan(
-    cli: &Cli,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScanFormat {
+    Json,
+    Luau,
+    /// `OffsetOutput` as MessagePack, readable by stats/diff/validate
+    Msgpack,
+    Csv,
+    Yaml,
+}
+
+impl ScanFormat {
+    /// `path` with a `.json` extension swapped for this format's
+    fn output_path(self, path: PathBuf) -> PathBuf {
+        match self {
+            ScanFormat::Luau if path.extension().is_some_and(|ext| ext == "json") => path.with_extension("luau"),
+            ScanFormat::Msgpack if path.extension().is_some_and(|ext| ext == "json") => path.with_extension("msgpack"),
+            ScanFormat::Csv if path.extension().is_some_and(|ext| ext == "json") => path.with_extension("csv"),
+            ScanFormat::Yaml if path.extension().is_some_and(|ext| ext == "json") => path.with_extension("yaml"),
+            _ => path,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ScanOptions {
     binary: PathBuf,
+    pid: Option<i32>,
     output: PathBuf,
+    format: ScanFormat,
+    out_dir: Option<PathBuf>,
+    name_template: Option<String>,
+    compact: bool,
     text: Option<PathBuf>,
     markdown: Option<PathBuf>,
+    ida_script: Option<PathBuf>,
+    ghidra: Option<PathBuf>,
+    sqlite: Option<PathBuf>,
     min_confidence: f64,
-    _threads: usize,
-) -> Result<(), String> {
-    let start_time = Instant::now();
+    threads: usize,
+    base: Option<u64>,
+    relative: bool,
+    explain: bool,
+    watch: bool,
+    chunk_size: Option<u64>,
+    require: Vec<String>,
+    require_confidence: Option<f64>,
+    diff_against: Option<PathBuf>,
+    max_scan_bytes: u64,
+    region: Option<String>,
+    plan: bool,
+    profile: Option<PathBuf>,
+    decoder_coverage: bool,
+    profile_tree: bool,
+    profile_json: Option<PathBuf>,
+    name_filter: Option<Regex>,
+}
 
-    println!("{} {}", "[*]".blue(), "Loading binary...".white());
-    
-    let spinner = create_spinner("Loading binary...", cli.no_progress);
-    
-    let binary_mem = BinaryMemory::load(&binary)
-        .map_err(|e| format!("Failed to load binary: {}", e))?;
-    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+impl ScanOptions {
+    fn new(binary: PathBuf, output: PathBuf, min_confidence: f64) -> Self {
+        Self {
+            binary,
+            pid: None,
+            output,
+            format: ScanFormat::Json,
+            out_dir: None,
+            name_template: None,
+            compact: false,
+            text: None,
+            markdown: None,
+            ida_script: None,
+            ghidra: None,
+            sqlite: None,
+            min_confidence,
+            threads: 8,
+            base: None,
+            relative: false,
+            explain: false,
+            watch: false,
+            chunk_size: None,
+            require: Vec::new(),
+            require_confidence: None,
+            diff_against: None,
+            max_scan_bytes: 0,
+            region: None,
+            plan: false,
+            profile: None,
+            decoder_coverage: false,
+            profile_tree: false,
+            profile_json: None,
+            name_filter: None,
+        }
+    }
+}
 
-    if let Some(ref pb) = spinner {
-        pb.finish_with_message("Binary loaded!");
+fn run_scan(cli: &Cli, opts: ScanOptions) -> Result<(), CliError> {
+    if opts.plan {
+        return print_scan_plan(&opts);
     }
 
-    println!("{} Binary loaded: {}", "[+]".green(), binary.display());
+    let cancel = install_interrupt_handler();
 
-    let regions = reader.get_regions()
-        .map_err(|e| format!("Failed to get memory regions: {}", e))?;
-    
-    println!("{} Found {} memory regions", "[+]".green(), regions.len());
+    let result = if opts.watch {
+        watch_scan(cli, opts, &cancel)
+    } else {
+        scan_once(cli, opts, &cancel).and_then(|_| {
+            if cancel.load(Ordering::SeqCst) {
+                Err(CliError::interrupted("Scan interrupted; partial results were saved"))
+            } else {
+                Ok(())
+            }
+        })
+    };
 
-    // Find executable regions only (where code lives)
+    restore_interrupt_handler();
+    result
+}
+
+/// Finder phases every scan runs after the Roblox function finders.
+const ANALYSIS_PHASES: &[&str] = &["structures", "classes", "properties", "methods", "constants"];
+
+/// Regions code is scanned in. Errors when there are none.
+fn executable_regions(regions: &[MemoryRegion]) -> Result<Vec<&MemoryRegion>, CliError> {
     let exec_regions: Vec<_> = regions.iter()
         .filter(|r| r.protection().can_execute())
         .collect();
 
     if exec_regions.is_empty() {
-        return Err("No executable regions found in binary".to_string());
+        return Err(CliError::NoExecutableRegions("binary").into());
     }
 
-    println!("{} Found {} executable regions", "[+]".green(), exec_regions.len());
+    Ok(exec_regions)
+}
+
+/// Decodes each executable region and warns about any where too many
+/// words use encodings the decoder doesn't know; `all` prints every region.
+/// A region holding the text section is measured over that section only,
+/// so headers and strings sharing the segment don't count as code.
+fn print_decoder_coverage(reader: Arc<dyn MemoryReader>, exec_regions: &[&MemoryRegion], text: Option<&BinarySection>, all: bool) {
+    let engine = DisassemblyEngine::new(reader);
+
+    for region in exec_regions {
+        let range = match text.map(|t| Address::new(t.addr)..Address::new(t.addr + t.size)) {
+            Some(t) if region.contains(t.start) => t.start..t.end.min(region.end()),
+            _ => region.start()..region.end(),
+        };
+        let coverage = engine.coverage(range);
+        let rate = format!("{:.1}%", coverage.unknown_rate() * 100.0);
+
+        if coverage.is_low() {
+            println!("{} {}: {} of {} instructions unknown ({}); decoder coverage low, results unreliable",
+                "[!]".yellow(), region.name(), coverage.unknown, coverage.instructions(), rate.yellow());
+        } else if all {
+            println!("{} {}: {} of {} instructions unknown ({}), {} padding words",
+                "[*]".blue(), region.name(), coverage.unknown, coverage.instructions(), rate, coverage.padding);
+        }
+    }
+}
 
-    // Use first executable region for scanning (typically __TEXT)
-    let first_exec = &exec_regions[0];
-    let start_addr = first_exec.range().start();
-    let scan_size = first_exec.range().size().min(100_000_000); // Cap at 100MB for speed
-    let end_addr = Address::new(start_addr.as_u64() + scan_size);
+/// `--region`: just the executable regions named `name` (case-insensitive),
+/// or all of them when no name is given
+fn select_regions<'a>(exec_regions: Vec<&'a MemoryRegion>, name: Option<&str>) -> Result<Vec<&'a MemoryRegion>, CliError> {
+    let name = match name {
+        Some(name) => name,
+        None => return Ok(exec_regions),
+    };
 
-    println!("{} Scan range: {} - {} ({} MB)",
-        "[*]".blue(),
-        format!("0x{:x}", start_addr.as_u64()).yellow(),
-        format!("0x{:x}", end_addr.as_u64()).yellow(),
-        scan_size / 1024 / 1024
-    );
+    let available: Vec<&str> = exec_regions.iter().map(|r| r.name()).collect();
+    let selected: Vec<_> = exec_regions.iter()
+        .copied()
+        .filter(|r| r.name().eq_ignore_ascii_case(name))
+        .collect();
+
+    if selected.is_empty() {
+        return Err(CliError::validation(format!("No executable region named '{}' (have: {})", name, available.join(", "))));
+    }
+
+    Ok(selected)
+}
+
+/// Ranges the default scan covers: every executable region, each truncated
+/// to `max_scan_bytes` unless that is 0. The second value is the number of
+/// bytes the cap leaves out across all of them.
+fn region_ranges(exec_regions: &[&MemoryRegion], max_scan_bytes: u64) -> (Vec<(Address, Address)>, u64) {
+    let mut skipped = 0;
+    let ranges = exec_regions.iter()
+        .map(|region| {
+            let start_addr = region.range().start();
+            let region_size = region.range().size();
+            let scan_size = if max_scan_bytes == 0 { region_size } else { region_size.min(max_scan_bytes) };
+            skipped += region_size - scan_size;
+
+            (start_addr, Address::new(start_addr.as_u64() + scan_size))
+        })
+        .collect();
+
+    (ranges, skipped)
+}
+
+fn warn_scan_cap(max_scan_bytes: u64, skipped: u64) {
+    if skipped > 0 {
+        println!("{} --max-scan-bytes {} skips the last {} bytes of the scanned regions; offsets there will not be found",
+            "[!]".yellow(),
+            max_scan_bytes,
+            skipped
+        );
+    }
+}
+
+/// File name `--out-dir` uses when no `--name-template` is given
+const DEFAULT_NAME_TEMPLATE: &str = "{target}_{hash8}_{date}.json";
+
+/// Where `scan` writes its results: `-o` as given, or a name rendered from
+/// the binary's `TargetInfo` when `--out-dir` or `--name-template` is set.
+/// A `.json` name takes the extension of `--format`.
+fn resolve_scan_output(opts: &ScanOptions, binary: &BinaryMemory) -> Result<PathBuf, CliError> {
+    if opts.out_dir.is_none() && opts.name_template.is_none() {
+        return Ok(opts.format.output_path(opts.output.clone()));
+    }
+
+    let data = std::fs::read(&opts.binary)
+        .map_err(CliError::io("read binary"))?;
+    let target = TargetInfo {
+        name: opts.binary.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default(),
+        architecture: if binary.is_arm64().unwrap_or(false) { "arm64" } else { "x86_64" }.to_string(),
+        platform: binary.format().platform().to_string(),
+        version: detect_binary_version(&opts.binary),
+        hash: Some(HashComputer::sha256_hex(&data)),
+        base_address: binary.get_base_address().as_u64(),
+        relative: opts.relative,
+    };
+
+    let timestamp = time::format_timestamp(time::generated_secs());
+    let date = timestamp.split('T').next().unwrap_or(&timestamp);
+    let template = opts.name_template.as_deref().unwrap_or(DEFAULT_NAME_TEMPLATE);
+    let name = render_file_name(template, &target, date)
+        .map_err(CliError::parse("--name-template"))?;
+
+    Ok(opts.format.output_path(opts.out_dir.clone().unwrap_or_default().join(name)))
+}
+
+/// Version for `{version}`: the `version-<hash>` directory Roblox installs
+/// into, or the enclosing app bundle's CFBundleShortVersionString.
+fn detect_binary_version(binary: &Path) -> Option<String> {
+    for dir in binary.ancestors().skip(1) {
+        let name = match dir.file_name() {
+            Some(name) => name.to_string_lossy(),
+            None => continue,
+        };
+
+        if name.starts_with("version-") {
+            return Some(name.into_owned());
+        }
+
+        if name == "Contents" {
+            let plist = std::fs::read_to_string(dir.join("Info.plist")).ok()?;
+            let key = plist.find("<key>CFBundleShortVersionString</key>")?;
+            let start = key + plist[key..].find("<string>")? + "<string>".len();
+            let end = start + plist[start..].find("</string>")?;
+            return Some(plist[start..end].trim().to_string());
+        }
+    }
+
+    None
+}
+
+/// `scan --plan`: parses only the load commands, then prints what a scan
+/// with these options would cover and run.
+fn print_scan_plan(opts: &ScanOptions) -> Result<(), CliError> {
+    let binary_mem = BinaryMemory::load(&opts.binary)
+        .map_err(CliError::BinaryLoad)?;
+    let platform = binary_mem.format().platform();
+    let regions = binary_mem.get_regions()
+        .map_err(CliError::memory("get memory regions"))?;
+    let exec_regions = select_regions(executable_regions(&regions)?, opts.region.as_deref())?;
+
+    println!("{}", "═".repeat(55).cyan());
+    println!("{}", "                   SCAN PLAN".cyan().bold());
+    println!("{}", "═".repeat(55).cyan());
+    println!();
+    println!("  Binary: {} ({})", opts.binary.display(), platform);
     println!();
 
-    let mut results = CombinedResults::new();
+    let (scanned_bytes, tasks) = match opts.chunk_size {
+        Some(mb) => {
+            let selected: Vec<MemoryRegion> = exec_regions.iter().map(|r| (*r).clone()).collect();
+            let chunks = plan_chunks(&selected, mb * 1024 * 1024, DEFAULT_CHUNK_OVERLAP);
 
-    // Phase 1: Roblox Functions
-    println!("{} Phase 1/6: Scanning for Roblox functions...", "[*]".blue());
-    let spinner1 = create_spinner("Scanning Roblox functions...", cli.no_progress);
-    
-    let roblox_finders = RobloxFinders::new(reader.clone());
-    for result in roblox_finders.find_all(start_addr, end_addr) {
-        results.add_function(result);
+            println!("{}", "Regions:".yellow().bold());
+            for region in &exec_regions {
+                println!("  {:<12} 0x{:x} - 0x{:x} ({} bytes)",
+                    region.name(), region.start().as_u64(), region.end().as_u64(), region.range().size());
+            }
+            println!("  {} chunks of {} MB on {} workers", chunks.len(), mb, opts.threads.max(1));
+
+            (chunks.iter().map(|c| c.size()).sum::<u64>(), chunks.len())
+        }
+        None => {
+            let (ranges, skipped) = region_ranges(&exec_regions, opts.max_scan_bytes);
+
+            println!("{}", "Regions:".yellow().bold());
+            for (region, (start, end)) in exec_regions.iter().zip(&ranges) {
+                println!("  {:<12} 0x{:x} - 0x{:x} ({} bytes)",
+                    region.name(), start.as_u64(), end.as_u64(), end.as_u64() - start.as_u64());
+            }
+            warn_scan_cap(opts.max_scan_bytes, skipped);
+
+            (ranges.iter().map(|(start, end)| end.as_u64() - start.as_u64()).sum(), 1)
+        }
+    };
+    println!();
+
+    let finders: Vec<&str> = BUILTIN_FINDERS.iter().map(|f| f.name()).collect();
+    println!("{}", "Finders:".yellow().bold());
+    println!("  {:<20} {}", "functions", finders.join(", "));
+    println!("  {:<20} {}", "analysis", ANALYSIS_PHASES.join(", "));
+    println!();
+
+    let passes = finder_passes();
+    println!("{}", "Estimated work:".yellow().bold());
+    println!("  {:.1} MB ({} bytes) scanned by {} finder passes in {} task(s)",
+        scanned_bytes as f64 / 1024.0 / 1024.0, scanned_bytes, passes, tasks);
+    println!("  {:.1} MB of finder reads in total", (scanned_bytes * passes as u64) as f64 / 1024.0 / 1024.0);
+    println!();
+
+    println!("  {:<20} {}", "Min confidence", opts.min_confidence);
+    if !(0.0..=1.0).contains(&opts.min_confidence) {
+        println!("{} --min-confidence {} is outside 0.0-1.0; {}",
+            "[!]".yellow(),
+            opts.min_confidence,
+            if opts.min_confidence > 1.0 { "every result would be dropped" } else { "nothing would be filtered" }
+        );
     }
-    
-    if let Some(ref pb) = spinner1 { pb.finish_with_message(format!("Found {} functions", results.functions.len())); }
-    println!("{} Found {} Roblox functions", "[+]".green(), results.functions.len());
+    if let Some(path) = &opts.profile {
+        let profile = load_profile(path)?;
+        println!("  {:<20} {} ({} tuned finders)", "Profile", path.display(), profile.finders.len());
+    }
+    if let Some(re) = &opts.name_filter {
+        println!("  {:<20} {}", "Name filter", re.as_str());
+    }
+    println!("  {:<20} {}", "Output", resolve_scan_output(opts, &binary_mem)?.display());
+    println!();
 
-    // Phase 2: Structures
-    println!("{} Phase 2/6: Scanning for structures...", "[*]".blue());
-    let spinner2 = create_spinner("Scanning structures...", cli.no_progress);
-    
-    let structure_results = structures::find_all_structures(reader.clone(), start_addr, end_addr);
-    for result in structure_results {
-        results.add_structure_offset(result);
+    Ok(())
+}
+
+/// Default scan: every finder phase over each of `region_ranges`, with
+/// padding trimmed. Results from all regions are merged the way chunk
+/// results are, keeping the most confident per name, so the same offset
+/// found twice is reported once. Stops between phases once `cancel` is set
+/// and returns what the completed phases found.
+fn scan_regions(cli: &Cli, reader: Arc<dyn MemoryReader>, exec_regions: &[&MemoryRegion], max_scan_bytes: u64, threads: usize, cancel: &Arc<AtomicBool>) -> CombinedResults {
+    let (ranges, skipped) = region_ranges(exec_regions, max_scan_bytes);
+    warn_scan_cap(max_scan_bytes, skipped);
+
+    let map = profile::span("padding", || {
+        let padding = PaddingScanner::new(reader.clone());
+        CodeMap::from_gaps(ranges.iter()
+            .flat_map(|&(start, end)| padding.scan(start..end).gaps().to_vec())
+            .collect())
+    });
+    report_padding(&map);
+    let ranges: Vec<(Address, Address)> = ranges.into_iter()
+        .filter_map(|(start, end)| map.trim(start..end).map(|code| (code.start, code.end)))
+        .collect();
+    let scan_size: u64 = ranges.iter().map(|(start, end)| end.as_u64() - start.as_u64()).sum();
+
+    for (start_addr, end_addr) in &ranges {
+        println!("{} Scan range: {} - {} ({} MB)",
+            "[*]".blue(),
+            format!("0x{:x}", start_addr.as_u64()).yellow(),
+            format!("0x{:x}", end_addr.as_u64()).yellow(),
+            (end_addr.as_u64() - start_addr.as_u64()) / 1024 / 1024
+        );
     }
-    
-    if let Some(ref pb) = spinner2 { pb.finish_with_message(format!("Found {} structure offsets", results.structure_offsets.len())); }
-    println!("{} Found {} structure offsets", "[+]".green(), results.structure_offsets.len());
+    println!();
 
-    // Phase 3: Classes
-    println!("{} Phase 3/6: Scanning for classes...", "[*]".blue());
-    let spinner3 = create_spinner("Scanning classes...", cli.no_progress);
-    
-    let class_results = classes::find_all_classes(reader.clone(), start_addr, end_addr);
-    for result in class_results {
-        results.add_class(result);
+    let progress = ScanProgress::new(scan_size * finder_passes() as u64, cli.no_progress);
+    let symbols = Arc::new(load_symbols(&reader));
+    let reader = progress.reader(reader);
+
+    if threads > 1 {
+        return scan_phases_parallel(reader, symbols, &ranges, threads, progress, cancel);
     }
-    
-    if let Some(ref pb) = spinner3 { pb.finish_with_message(format!("Found {} classes", results.classes.len())); }
-    println!("{} Found {} classes", "[+]".green(), results.classes.len());
 
-    // Phase 4: Properties  
-    println!("{} Phase 4/6: Scanning for properties...", "[*]".blue());
-    let spinner4 = create_spinner("Scanning properties...", cli.no_progress);
-    
-    let property_results = properties::find_all_properties(reader.clone(), start_addr, end_addr);
-    for result in property_results {
-        results.add_property(result);
+    let mut results = CombinedResults::new();
+    let reflection = ReflectionCache::new();
+    for (index, phase) in ScanPhase::ALL.into_iter().enumerate() {
+        progress.phase(&format!("Phase {}/{}: Scanning for {}", index + 1, ScanPhase::ALL.len(), phase.label()));
+        for &(start_addr, end_addr) in &ranges {
+            run_phase(phase, reader.clone(), &symbols, &reflection, start_addr..end_addr, Some(cancel), &mut results);
+        }
+        progress.found(format!("Found {} {}", phase.count(&results), phase.label()));
+        progress.remaining(scan_size * (ScanPhase::ALL.len() - index - 1) as u64);
+
+        if cancel.load(Ordering::SeqCst) {
+            break;
+        }
     }
-    
-    if let Some(ref pb) = spinner4 { pb.finish_with_message(format!("Found {} properties", results.properties.len())); }
-    println!("{} Found {} properties", "[+]".green(), results.properties.len());
 
-    // Phase 5: Methods
-    println!("{} Phase 5/6: Scanning for methods...", "[*]".blue());
-    let spinner5 = create_spinner("Scanning methods...", cli.no_progress);
-    
-    let method_results = methods::find_all_methods(reader.clone(), start_addr, end_addr);
-    for result in method_results {
-        results.add_method(result);
+    progress.finish();
+    ResultAggregator::new().aggregate_combined(vec![results])
+}
+
+/// Runs each scan phase as its own task on `threads` workers. Every phase
+/// fills a different part of `CombinedResults`, so the merged output is the
+/// same as a serial scan's whatever order the phases finish in.
+fn scan_phases_parallel(reader: Arc<dyn MemoryReader>, symbols: Arc<SymbolResolver>, ranges: &[(Address, Address)], threads: usize, progress: ScanProgress, cancel: &Arc<AtomicBool>) -> CombinedResults {
+    progress.phase(&format!("Running {} phases on {} workers", ScanPhase::ALL.len(), threads));
+
+    let reflection = Arc::new(ReflectionCache::new());
+    let mut scheduler = TaskScheduler::new(threads);
+    scheduler.start();
+    scheduler.submit_batch(ScanPhase::ALL.into_iter()
+        .map(|phase| PhaseScan::new(reader.clone(), symbols.clone(), phase, ranges.to_vec())
+            .with_reflection(reflection.clone())
+            .with_cancellation(cancel.clone()))
+        .map(|scan| Task::new(TaskType::ScanPhase(scan)))
+        .collect());
+
+    let task_results = scheduler.collect(ScanPhase::ALL.len());
+    scheduler.stop();
+    progress.finish();
+
+    let per_phase: Vec<CombinedResults> = task_results.into_iter()
+        .filter_map(|result| result.into_combined())
+        .collect();
+    let results = ResultAggregator::new().aggregate_combined(per_phase);
+    for phase in ScanPhase::ALL {
+        println!("{} Found {} {}", "[+]".green(), phase.count(&results), phase.label());
     }
-    
-    if let Some(ref pb) = spinner5 { pb.finish_with_message(format!("Found {} methods", results.methods.len())); }
-    println!("{} Found {} methods", "[+]".green(), results.methods.len());
 
-    // Phase 6: Constants
-    println!("{} Phase 6/6: Scanning for constants...", "[*]".blue());
-    let spinner6 = create_spinner("Scanning constants...", cli.no_progress);
-    
-    let constant_results = constants::find_all_constants(reader.clone(), start_addr, end_addr);
-    for result in constant_results {
-        results.add_constant(result);
+    results
+}
+
+/// Passes over the scan range a full scan makes: one per built-in finder,
+/// then one per analysis phase.
+fn finder_passes() -> usize {
+    BUILTIN_FINDERS.len() + ANALYSIS_PHASES.len()
+}
+
+/// One bytes bar for a whole scan. Finders read through a `CountingReader`
+/// and a ticker thread mirrors its counter into the bar, so the rate and
+/// ETA follow real throughput. The total starts as the `--plan` estimate
+/// and is corrected with `remaining` as phases finish.
+struct ScanProgress {
+    bar: Option<ProgressBar>,
+    consumed: Arc<AtomicU64>,
+    done: Arc<AtomicBool>,
+    ticker: Option<std::thread::JoinHandle<()>>,
+    _manager: Option<ProgressManager>,
+}
+
+impl ScanProgress {
+    fn new(estimated_bytes: u64, no_progress: bool) -> Self {
+        let consumed = Arc::new(AtomicU64::new(0));
+        let done = Arc::new(AtomicBool::new(false));
+
+        if no_progress {
+            return Self { bar: None, consumed, done, ticker: None, _manager: None };
+        }
+
+        let manager = ProgressManager::new();
+        let bar = manager.create_bytes_progress(estimated_bytes.max(1), "Scanning...");
+        bar.enable_steady_tick(Duration::from_millis(100));
+
+        let ticker = {
+            let (bar, consumed, done) = (bar.clone(), consumed.clone(), done.clone());
+            std::thread::spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    let position = consumed.load(Ordering::Relaxed);
+                    if bar.length().is_some_and(|len| position > len) {
+                        bar.set_length(position);
+                    }
+                    bar.set_position(position);
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+            })
+        };
+
+        Self { bar: Some(bar), consumed, done, ticker: Some(ticker), _manager: Some(manager) }
     }
-    
-    if let Some(ref pb) = spinner6 { pb.finish_with_message(format!("Found {} constants", results.constants.len())); }
-    println!("{} Found {} constants", "[+]".green(), results.constants.len());
 
-    println!();
+    fn reader(&self, reader: Arc<dyn MemoryReader>) -> Arc<dyn MemoryReader> {
+        Arc::new(CountingReader::new(reader, self.consumed.clone()))
+    }
 
-    // Filter and save
-    let filtered_results = filter_by_confidence(&results, min_confidence);
+    fn phase(&self, message: &str) {
+        match &self.bar {
+            Some(bar) => bar.set_message(message.to_string()),
+            None => println!("{} {}...", "[*]".blue(), message),
+        }
+    }
 
-    save_scan_results(&filtered_results, &output)?;
-    println!("{} Results saved to: {}", "[+]".green(), output.display());
+    fn found(&self, message: String) {
+        let line = format!("{} {}", "[+]".green(), message);
+        match &self.bar {
+            Some(bar) => bar.println(line),
+            None => println!("{}", line),
+        }
+    }
 
-    if let Some(text_path) = text {
-        save_text_report(&filtered_results, &text_path)
-            .map_err(|e| format!("Failed to save text report: {}", e))?;
-        println!("{} Text report saved to: {}", "[+]".green(), text_path.display());
+    /// Re-estimates the total as what has been read plus `bytes` still to go
+    fn remaining(&self, bytes: u64) {
+        if let Some(bar) = &self.bar {
+            bar.set_length(self.consumed.load(Ordering::Relaxed) + bytes);
+        }
     }
 
-    if let Some(md_path) = markdown {
-        save_markdown_report(&filtered_results, &md_path)
-            .map_err(|e| format!("Failed to save markdown report: {}", e))?;
-        println!("{} Markdown report saved to: {}", "[+]".green(), md_path.display());
+    fn finish(mut self) {
+        self.done.store(true, Ordering::Relaxed);
+        if let Some(ticker) = self.ticker.take() {
+            let _ = ticker.join();
+        }
+        if let Some(bar) = &self.bar {
+            let consumed = self.consumed.load(Ordering::Relaxed);
+            bar.set_length(consumed);
+            bar.set_position(consumed);
+            bar.finish_with_message("Scan complete");
+        }
     }
+}
 
-    println!();
-    print_scan_summary(&filtered_results, start_time.elapsed());
+/// Fails when a `--require`d function is missing from the filtered results
+/// or, with `--require-confidence`, found below that threshold.
+fn check_required(results: &CombinedResults, require: &[String], min_confidence: Option<f64>) -> Result<(), CliError> {
+    let mut missing = Vec::new();
+    let mut weak = Vec::new();
+
+    for name in require {
+        match results.functions.iter().find(|f| &f.name == name) {
+            None => missing.push(name.clone()),
+            Some(f) if min_confidence.is_some_and(|min| f.confidence < min) => {
+                weak.push(format!("{} ({:.0}%)", name, f.confidence * 100.0));
+            }
+            Some(_) => {}
+        }
+    }
 
-    Ok(())
+    let mut problems = Vec::new();
+    if !missing.is_empty() {
+        problems.push(format!("missing: {}", missing.join(", ")));
+    }
+    if !weak.is_empty() {
+        problems.push(format!("below {:.0}% confidence: {}", min_confidence.unwrap_or(0.0) * 100.0, weak.join(", ")));
+    }
+
+    if problems.is_empty() {
+        if !require.is_empty() {
+            println!("{} All {} required offsets present", "[+]".green(), require.len());
+        }
+        Ok(())
+    } else {
+        Err(CliError::missing_offsets(format!("Required offsets not satisfied ({})", problems.join("; "))))
+    }
 }
 
-fn filter_by_confidence(results: &CombinedResults, min_confidence: f64) -> CombinedResults {
-    CombinedResults {
-        functions: results.functions.iter()
-            .filter(|f| f.confidence >= min_confidence)
-            .cloned()
-            .collect(),
-        structure_offsets: results.structure_offsets.iter()
-            .filter(|s| s.confidence >= min_confidence)
-            .cloned()
-            .collect(),
-        classes: results.classes.clone(),
-        properties: results.properties.clone(),
-        methods: results.methods.clone(),
-        constants: results.constants.clone(),
+/// Chunked scan: every executable region is split into overlapping chunks
+/// that run on the engine's worker pool and are merged afterwards.
+fn scan_chunked(reader: Arc<dyn MemoryReader>, regions: &[MemoryRegion], chunk_size: u64, threads: usize, no_progress: bool, cancel: &Arc<AtomicBool>) -> Result<CombinedResults, CliError> {
+    let map = PaddingScanner::new(reader.clone()).scan_regions(regions);
+    report_padding(&map);
+    let chunks = trim_padding(plan_chunks(regions, chunk_size, DEFAULT_CHUNK_OVERLAP), &map);
+    let threads = threads.max(1);
+
+    println!("{} Chunked scan: {} chunks of {} MB on {} workers",
+        "[*]".blue(),
+        chunks.len(),
+        chunk_size / 1024 / 1024,
+        threads
+    );
+
+    let scanned: u64 = chunks.iter().map(|c| c.size()).sum();
+    let progress = ScanProgress::new(scanned * finder_passes() as u64, no_progress);
+    let symbols = Arc::new(load_symbols(&reader));
+    let reader = progress.reader(reader);
+
+    let mut scheduler = TaskScheduler::new(threads);
+    scheduler.start();
+    scheduler.submit_batch(chunks.iter()
+        .map(|chunk| Task::new(TaskType::ScanChunk(ChunkScan::new(reader.clone(), symbols.clone(), *chunk).with_cancellation(cancel.clone()))))
+        .collect());
+
+    let task_results = scheduler.collect(chunks.len());
+    scheduler.stop();
+    progress.finish();
+
+    let mut per_chunk = Vec::with_capacity(task_results.len());
+    for result in task_results {
+        if let Some(message) = result.error_message() {
+            return Err(CliError::general(format!("Chunk scan failed: {}", message)));
+        }
+        if let Some(results) = result.into_combined() {
+            per_chunk.push(results);
+        }
     }
+
+    let results = ResultAggregator::new().aggregate_combined(per_chunk);
+    println!("{} Found {} functions, {} structure offsets, {} classes, {} properties, {} methods, {} constants",
+        "[+]".green(),
+        results.functions.len(),
+        results.structure_offsets.len(),
+        results.classes.len(),
+        results.properties.len(),
+        results.methods.len(),
+        results.constants.len()
+    );
+
+    Ok(results)
 }
 
-fn print_scan_summary(results: &CombinedResults, elapsed: std::time::Duration) {
-    println!("{}", "═".repeat(55).cyan());
-    println!("{}", "                SCAN COMPLETE".cyan().bold());
-    println!("{}", "═".repeat(55).cyan());
-    println!();
-    println!("  {} Functions found:        {}", "•".cyan(), results.functions.len().to_string().green().bold());
-    println!("  {} Structure offsets:      {}", "•".cyan(), results.structure_offsets.len().to_string().green().bold());
-    println!("  {} Classes found:          {}", "•".cyan(), results.classes.len().to_string().green().bold());
-    println!("  {} Properties found:       {}", "•".cyan(), results.properties.len().to_string().green().bold());
-    println!("  {} Methods found:          {}", "•".cyan(), results.methods.len().to_string().green().bold());
-    println!("  {} Constants found:        {}", "•".cyan(), results.constants.len().to_string().green().bold());
-    println!();
-    println!("  {} Total offsets:          {}", "★".yellow(), results.total_count().to_string().green().bold());
-    println!("  {} High confidence (>85%): {}", "★".yellow(), results.high_confidence_count().to_string().green().bold());
-    println!();
-    println!("  {} Time elapsed:           {:.2}s", "⏱".bright_black(), elapsed.as_secs_f64());
-    println!();
+fn report_padding(map: &CodeMap) {
+    println!("{} Skipping {} bytes of padding in {} gaps between functions",
+        "[*]".blue(),
+        map.padding_bytes(),
+        map.gaps().len()
+    );
 }
 
-// ==================== FFLAGS COMMAND ====================
+/// One scan, timed as the root "scan" span when `--profile-tree` or
+/// `--profile-json` asked for it
+fn scan_once(cli: &Cli, opts: ScanOptions, cancel: &Arc<AtomicBool>) -> Result<OffsetOutput, CliError> {
+    let profile_tree = opts.profile_tree;
+    let profile_json = opts.profile_json.clone();
+    if profile_tree || profile_json.is_some() {
+        profile::enable();
+    }
 
-fn run_fflags(
-    cli: &Cli,
-    binary: Option<PathBuf>,
-    output: PathBuf,
-    text: Option<PathBuf>,
-    category: Option<String>,
-    search: Option<String>,
-    found_only: bool,
-    list_categories: bool,
-) -> Result<(), String> {
-    let start_time = Instant::now();
+    let output = profile::span("scan", || scan_pass(cli, opts, cancel));
 
-    if list_categories {
-        menu_fflag_categories();
-        return Ok(());
+    if profile::is_enabled() {
+        report_profile(profile_tree, profile_json.as_ref())?;
     }
+    output
+}
 
-    let binary = binary.ok_or("Binary path is required for FFlag scanning")?;
+fn report_profile(print_tree: bool, json_path: Option<&PathBuf>) -> Result<(), CliError> {
+    let spans = profile::take_spans();
 
-    println!("{} {}", "[*]".blue(), "Loading binary...".white());
+    if print_tree {
+        println!();
+        println!("{}", "Scan profile:".bold());
+        print!("{}", profile::TimingTree::from_spans(&spans).render());
+    }
+
+    if let Some(path) = json_path {
+        let trace = serde_json::to_string(&profile::chrome_trace(&spans))
+            .map_err(CliError::serialize("trace"))?;
+        std::fs::write(path, trace)
+            .map_err(CliError::io(format!("write {}", path.display())))?;
+        println!("{} Trace saved to: {} (open in chrome://tracing or Perfetto)", "[+]".green(), path.display());
+    }
+
+    Ok(())
+}
+
+fn scan_pass(cli: &Cli, opts: ScanOptions, cancel: &Arc<AtomicBool>) -> Result<OffsetOutput, CliError> {
+    let start_time = Instant::now();
+
+    // Read the baseline up front so a bad path fails before the scan
+    let baseline = opts.diff_against.as_ref().map(load_baseline).transpose()?;
+    let profile = opts.profile.as_ref().map(load_profile).transpose()?;
+
+    let (reader, platform, text_section, output) = match opts.pid {
+        Some(pid) => {
+            println!("{} Attaching to process {}...", "[*]".blue(), pid);
+            let process = profile::span("load", || attach_process(pid))?;
+            (process, "macos", None, opts.format.output_path(opts.output.clone()))
+        }
+        None => {
+            println!("{} {}", "[*]".blue(), "Loading binary...".white());
+
+            let spinner = create_spinner("Loading binary...", cli.no_progress);
+
+            let binary_mem = profile::span("load", || BinaryMemory::load(&opts.binary))
+                .map_err(CliError::BinaryLoad)?;
+            let platform = binary_mem.format().platform();
+            let text_section = binary_mem.text_section();
+
+            // Resolved before scanning so a bad --name-template fails fast
+            let output = resolve_scan_output(&opts, &binary_mem)?;
+
+            if let Some(ref pb) = spinner {
+                pb.finish_with_message("Binary loaded!");
+            }
+
+            let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+            (reader, platform, text_section, output)
+        }
+    };
+    let ScanOptions { binary, format, out_dir, compact, text, markdown, ida_script, ghidra, sqlite, min_confidence, threads, base, relative, explain, chunk_size, require, require_confidence, diff_against, max_scan_bytes, decoder_coverage, name_filter, pid, region, .. } = opts;
+
+    match pid {
+        Some(pid) => println!("{} Attached to process {} (image base 0x{:x})", "[+]".green(), pid, reader.get_base_address().as_u64()),
+        None => println!("{} Binary loaded: {} ({})", "[+]".green(), binary.display(), platform),
+    }
+
+    let regions = profile::span("regions", || reader.get_regions())
+        .map_err(CliError::memory("get memory regions"))?;
+    
+    println!("{} Found {} memory regions", "[+]".green(), regions.len());
+
+    // Find executable regions only (where code lives)
+    let exec_regions = select_regions(executable_regions(&regions)?, region.as_deref())?;
+
+    println!("{} Found {} executable regions", "[+]".green(), exec_regions.len());
+    profile::span("decoder coverage", || {
+        print_decoder_coverage(reader.clone(), &exec_regions, text_section.as_ref(), decoder_coverage || cli.verbose)
+    });
+
+    let mut results = profile::span("find", || match chunk_size {
+        Some(mb) => {
+            let selected: Vec<MemoryRegion> = exec_regions.iter().map(|r| (*r).clone()).collect();
+            scan_chunked(reader.clone(), &selected, mb * 1024 * 1024, threads, cli.no_progress, cancel)
+        }
+        None => Ok(scan_regions(cli, reader.clone(), &exec_regions, max_scan_bytes, threads, cancel)),
+    })?;
+
+    if cancel.load(Ordering::SeqCst) {
+        println!();
+        println!("{} Scan interrupted, keeping partial results", "[!]".yellow());
+    }
+
+    println!();
+
+    if let Some(profile) = &profile {
+        results = apply_profile(&results, profile);
+        println!("{} Applied tuned profile ({} finders)", "[*]".blue(), profile.finders.len());
+    }
+
+    // Filter, rebase and save
+    let mut filtered_results = filter_by_confidence(&results, min_confidence, profile.as_ref());
+    check_required(&filtered_results, &require, require_confidence)?;
+
+    let image_base = reader.get_base_address().as_u64();
+    let output_base = if relative { 0 } else { base.unwrap_or(image_base) };
+    if output_base != image_base {
+        filtered_results.rebase(image_base, output_base);
+        if relative {
+            println!("{} Addresses expressed relative to image base 0x{:x}", "[*]".blue(), image_base);
+        } else {
+            println!("{} Addresses rebased onto 0x{:x}", "[*]".blue(), output_base);
+        }
+    }
+    let target_base = if relative { image_base } else { output_base };
+    let mut finder_report = OutputFinalizer::new().finder_report(&filtered_results, &results);
+
+    if let Some(re) = &name_filter {
+        filtered_results = filter_by_name(&filtered_results, re);
+        finder_report.retain(|entry| re.is_match(&entry.offset));
+        println!("{} Keeping names matching: {}", "[*]".blue(), re.as_str().yellow());
+    }
+
+    if let Some(dir) = &out_dir {
+        std::fs::create_dir_all(dir)
+            .map_err(CliError::io(format!("create {}", dir.display())))?;
+    }
+    let target_name = binary.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut offsets = OffsetOutput::from_results(&target_name, &filtered_results, target_base);
+    offsets.target.relative = relative;
+    offsets.target.platform = platform.to_string();
+    offsets.finder_report = finder_report.clone();
+    offsets.warnings = OutputFinalizer::new().address_collisions(&filtered_results);
+
+    profile::span("write", || -> Result<(), CliError> {
+        match format {
+            ScanFormat::Json => save_scan_results(&filtered_results, &finder_report, target_base, relative, platform, compact, &output)?,
+            ScanFormat::Msgpack => OffsetExporter::new().export_msgpack_to_file(&offsets, &output)
+                .map_err(CliError::io("write file"))?,
+            ScanFormat::Luau => {
+                let table = OutputFormatter::new().format_luau(&OffsetOutput::from_results(&target_name, &filtered_results, target_base));
+                std::fs::write(&output, table)
+                    .map_err(CliError::io("write file"))?;
+            }
+            ScanFormat::Csv => OffsetExporter::new().export_to_file(&offsets, OffsetFormat::Csv, &output)
+                .map_err(CliError::io("write file"))?,
+            ScanFormat::Yaml => OffsetExporter::new().export_to_file(&offsets, OffsetFormat::Yaml, &output)
+                .map_err(CliError::io("write file"))?,
+        }
+        Ok(())
+    })?;
+    println!("{} Results saved to: {}", "[+]".green(), output.display());
+
+    if let Some(text_path) = text {
+        save_text_report(&filtered_results, &text_path)
+            .map_err(CliError::io("save text report"))?;
+        println!("{} Text report saved to: {}", "[+]".green(), text_path.display());
+    }
+
+    if let Some(md_path) = markdown {
+        save_markdown_report(&filtered_results, &finder_report, &md_path)
+            .map_err(CliError::io("save markdown report"))?;
+        println!("{} Markdown report saved to: {}", "[+]".green(), md_path.display());
+    }
+
+    if let Some(ida_path) = ida_script {
+        SymbolExporter::from_results(&filtered_results)
+            .export_to_file(ExportFormat::Ida, &ida_path.to_string_lossy())
+            .map_err(CliError::io("save IDA script"))?;
+        println!("{} IDA script saved to: {}", "[+]".green(), ida_path.display());
+    }
+
+    if let Some(ghidra_path) = ghidra {
+        let format = match ghidra_path.extension().and_then(|ext| ext.to_str()) {
+            Some("py") => ExportFormat::Ghidra,
+            Some("csv") => ExportFormat::GhidraCsv,
+            _ => ExportFormat::GhidraSymbols,
+        };
+        SymbolExporter::from_results(&filtered_results)
+            .with_image_base(output_base)
+            .export_to_file(format, &ghidra_path.to_string_lossy())
+            .map_err(CliError::io("save Ghidra symbols"))?;
+        println!("{} Ghidra symbols saved to: {}", "[+]".green(), ghidra_path.display());
+    }
+
+    if explain {
+        println!();
+        print_confidence_breakdown(&results, min_confidence, profile.as_ref());
+    }
+
+    println!();
+    print_scan_summary(&filtered_results, start_time.elapsed());
+    print_finder_report(&finder_report, cli.verbose);
+    if cli.verbose {
+        print_unmatched_diagnostics(reader.clone(), &finder_report);
+        print_address_collisions(&offsets.warnings);
+    }
+
+    if let Some(db_path) = sqlite {
+        let data = std::fs::read(&binary)
+            .map_err(CliError::io("read binary"))?;
+        offsets.target.hash = Some(HashComputer::sha256_hex(&data));
+        offsets.target.version = detect_binary_version(&binary);
+
+        SqliteExporter::open(&db_path)
+            .and_then(|mut db| db.export(&offsets))
+            .map_err(std::io::Error::other)
+            .map_err(CliError::io(format!("write {}", db_path.display())))?;
+        println!("{} Offsets upserted into: {}", "[+]".green(), db_path.display());
+    }
+
+    if let (Some(path), Some(baseline)) = (&diff_against, &baseline) {
+        print_baseline_diff(path, baseline, &offsets)?;
+    }
+
+    Ok(offsets)
+}
+
+// ==================== WATCH MODE ====================
+
+/// Set by SIGINT while a scan is running. Finders poll it between phases
+/// and chunks, so the scan winds down with partial results.
+static INTERRUPTED: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+const WATCH_POLL: Duration = Duration::from_millis(500);
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(750);
+
+extern "C" fn on_interrupt(_signal: libc::c_int) {
+    if let Some(flag) = INTERRUPTED.get() {
+        flag.store(true, Ordering::SeqCst);
+    }
+    // A second Ctrl-C kills the process as usual
+    unsafe {
+        libc::signal(libc::SIGINT, libc::SIG_DFL);
+    }
+}
+
+fn install_interrupt_handler() -> Arc<AtomicBool> {
+    let flag = INTERRUPTED.get_or_init(|| Arc::new(AtomicBool::new(false))).clone();
+    flag.store(false, Ordering::SeqCst);
+
+    let handler: extern "C" fn(libc::c_int) = on_interrupt;
+    unsafe {
+        libc::signal(libc::SIGINT, handler as libc::sighandler_t);
+    }
+    flag
+}
+
+fn restore_interrupt_handler() {
+    unsafe {
+        libc::signal(libc::SIGINT, libc::SIG_DFL);
+    }
+}
+
+/// Modification time and length, enough to notice a rewritten binary
+fn file_stamp(path: &PathBuf) -> Option<(SystemTime, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+fn watch_scan(cli: &Cli, opts: ScanOptions, cancel: &Arc<AtomicBool>) -> Result<(), CliError> {
+    let mut previous = scan_once(cli, opts.clone(), cancel)?;
+    let mut last_stamp = file_stamp(&opts.binary);
+
+    println!("{} Watching {} for changes (Ctrl-C to stop)...", "[*]".blue(), opts.binary.display());
+
+    while !cancel.load(Ordering::SeqCst) {
+        std::thread::sleep(WATCH_POLL);
+
+        let mut stamp = file_stamp(&opts.binary);
+        if stamp.is_none() || stamp == last_stamp {
+            continue;
+        }
+
+        // Let a burst of writes settle so one save triggers one scan
+        loop {
+            std::thread::sleep(WATCH_DEBOUNCE);
+            if cancel.load(Ordering::SeqCst) {
+                break;
+            }
+            let settled = file_stamp(&opts.binary);
+            if settled == stamp {
+                break;
+            }
+            stamp = settled;
+        }
+        if cancel.load(Ordering::SeqCst) {
+            break;
+        }
+        last_stamp = stamp;
+
+        println!();
+        println!("{} Change detected, re-scanning...", "[*]".blue());
+        match scan_once(cli, opts.clone(), cancel) {
+            // A partial re-scan would show up as spurious removals
+            Ok(_) if cancel.load(Ordering::SeqCst) => break,
+            Ok(current) => {
+                let diff = DiffGenerator::new().generate(&previous, &current);
+                if diff.has_changes() {
+                    print!("{}", DiffGenerator::new().format_diff(&diff));
+                } else {
+                    println!("{} No offset changes since the previous scan", "[+]".green());
+                }
+                previous = current;
+            }
+            Err(e) => eprintln!("{} {}", "[ERROR]".red(), e),
+        }
+        println!("{} Watching {} for changes (Ctrl-C to stop)...", "[*]".blue(), opts.binary.display());
+    }
+
+    println!();
+    println!("{}", "Stopped watching.".cyan());
+    Ok(())
+}
+
+/// Keeps results at or above `min_confidence`, or the tuned threshold of
+/// their finder when the profile has one
+fn filter_by_confidence(results: &CombinedResults, min_confidence: f64, profile: Option<&TunedProfile>) -> CombinedResults {
+    let threshold = |key: &str| profile.map_or(min_confidence, |p| p.threshold(key, min_confidence));
+
+    CombinedResults {
+        functions: results.functions.iter()
+            .filter(|f| f.confidence >= threshold(tuning_key(f)))
+            .cloned()
+            .collect(),
+        structure_offsets: results.structure_offsets.iter()
+            .filter(|s| s.confidence >= threshold(STRUCTURE_TUNING_KEY))
+            .cloned()
+            .collect(),
+        classes: results.classes.clone(),
+        properties: results.properties.clone(),
+        methods: results.methods.clone(),
+        constants: results.constants.clone(),
+    }
+}
+
+/// Keeps the functions, classes, methods, properties and constants whose
+/// name matches `re`; structure offsets are left alone
+fn filter_by_name(results: &CombinedResults, re: &Regex) -> CombinedResults {
+    CombinedResults {
+        functions: results.functions.iter().filter(|f| re.is_match(&f.name)).cloned().collect(),
+        structure_offsets: results.structure_offsets.clone(),
+        classes: results.classes.iter().filter(|c| re.is_match(&c.name)).cloned().collect(),
+        properties: results.properties.iter().filter(|p| re.is_match(&p.property_name)).cloned().collect(),
+        methods: results.methods.iter().filter(|m| re.is_match(&m.method_name)).cloned().collect(),
+        constants: results.constants.iter().filter(|c| re.is_match(&c.name)).cloned().collect(),
+    }
+}
+
+fn print_confidence_breakdown(results: &CombinedResults, min_confidence: f64, profile: Option<&TunedProfile>) {
+    println!("{}", "Confidence breakdown:".yellow().bold());
+    for func in &results.functions {
+        let threshold = profile.map_or(min_confidence, |p| p.threshold(tuning_key(func), min_confidence));
+        let marker = if func.confidence >= threshold { "✓".green() } else { "✗".red() };
+        println!("  {} {} {:.2} = {}",
+            marker,
+            func.name.cyan(),
+            func.confidence,
+            explain(func).bright_black()
+        );
+    }
+}
+
+// ==================== TUNED PROFILES ====================
+
+/// Profile key shared by every structure offset
+const STRUCTURE_TUNING_KEY: &str = "structures";
+
+/// Profile key for a function result: the roblox finder that emits it, else
+/// its category
+fn tuning_key(result: &FinderResult) -> &str {
+    FINDER_OUTPUTS.iter()
+        .find(|(_, outputs)| outputs.contains(&result.name.as_str()))
+        .map_or(result.category.as_str(), |(finder, _)| finder)
+}
+
+fn load_profile(path: &PathBuf) -> Result<TunedProfile, CliError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(CliError::io(format!("read profile {}", path.display())))?;
+    serde_json::from_str(&content)
+        .map_err(CliError::parse(format!("profile {}", path.display())))
+}
+
+/// Scales confidences by their finder's tuned weight. The change is added to
+/// each recorded breakdown so `--explain` still sums up.
+fn apply_profile(results: &CombinedResults, profile: &TunedProfile) -> CombinedResults {
+    let mut tuned = results.clone();
+
+    for func in &mut tuned.functions {
+        let weighted = profile.weighted_confidence(tuning_key(func), func.confidence);
+        if weighted != func.confidence {
+            if !func.breakdown.is_empty() {
+                func.breakdown.push(ConfidenceFactor::new("tuned profile weight", weighted - func.confidence));
+            }
+            func.confidence = weighted;
+        }
+    }
+    for offset in &mut tuned.structure_offsets {
+        offset.confidence = profile.weighted_confidence(STRUCTURE_TUNING_KEY, offset.confidence);
+    }
+
+    tuned
+}
+
+fn run_learn(cli: &Cli, binary: PathBuf, truth: PathBuf, output: PathBuf, min_confidence: f64, max_scan_bytes: u64) -> Result<(), CliError> {
+    if !truth.exists() {
+        return Err(CliError::not_found(format!("Truth file not found: {}", truth.display())));
+    }
+
+    let content = std::fs::read_to_string(&truth)
+        .map_err(CliError::io("read truth file"))?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(CliError::parse("truth file"))?;
+    let known = OffsetOutput::from_scan_json("", &json);
+    let truth_base = read_target_base(&json);
+
+    println!("{} {}", "[*]".blue(), "Loading binary...".white());
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+    let regions = reader.get_regions()
+        .map_err(CliError::memory("get memory regions"))?;
+    let exec_regions = executable_regions(&regions)?;
+
+    let cancel = install_interrupt_handler();
+    let results = scan_regions(cli, reader.clone(), &exec_regions, max_scan_bytes, 1, &cancel);
+    restore_interrupt_handler();
+    if cancel.load(Ordering::SeqCst) {
+        return Err(CliError::interrupted("Learning interrupted; no profile was written"));
+    }
+
+    // Truth addresses are moved onto this binary's base before comparing
+    let image_base = reader.get_base_address().as_u64();
+    let mut learner = PatternLearner::new(reader.clone());
+    let mut unverified = 0;
+
+    for func in &results.functions {
+        match known.functions.get(&func.name) {
+            Some(expected) => {
+                let expected = expected.address.wrapping_sub(truth_base).wrapping_add(image_base);
+                learner.add_scored_sample(tuning_key(func), func.confidence, func.address.as_u64() == expected);
+            }
+            None => unverified += 1,
+        }
+    }
+    for offset in &results.structure_offsets {
+        let expected = known.structure_offsets.get(&offset.structure_name)
+            .and_then(|s| s.fields.get(&offset.field_name));
+        match expected {
+            Some(field) => learner.add_scored_sample(STRUCTURE_TUNING_KEY, offset.confidence, field.offset as u64 == offset.offset),
+            None => unverified += 1,
+        }
+    }
+
+    if learner.sample_count() == 0 {
+        return Err(CliError::general(format!("None of the scan results appear in {}; nothing to learn from", truth.display())));
+    }
+
+    let mut profile = learner.tune_thresholds(min_confidence);
+    profile.trained_on = binary.file_name().map(|n| n.to_string_lossy().into_owned());
+    profile.generated_at = time::format_timestamp(time::generated_secs());
+
+    println!();
+    println!("{}", "═".repeat(55).cyan());
+    println!("{}", "               TUNED PROFILE".cyan().bold());
+    println!("{}", "═".repeat(55).cyan());
+    println!();
+    println!("  {:<24} {:>7} {:>7} {:>9} {:>9}", "Finder", "Samples", "Weight", "Threshold", "Agreement");
+    for (finder, tuning) in &profile.finders {
+        let line = format!("  {:<24} {:>7} {:>7.2} {:>9.3} {:>8.0}%",
+            finder, tuning.samples, tuning.weight, tuning.threshold, tuning.agreement * 100.0);
+        if tuning.threshold != min_confidence || tuning.weight != 1.0 {
+            println!("{}", line.yellow());
+        } else {
+            println!("{}", line);
+        }
+    }
+    println!();
+    if unverified > 0 {
+        println!("{} {} results are not in the truth file and were not used", "[!]".yellow(), unverified);
+    }
+
+    let json = serde_json::to_string_pretty(&profile)
+        .map_err(CliError::serialize("tuned profile"))?;
+    std::fs::write(&output, json)
+        .map_err(CliError::io(format!("write {}", output.display())))?;
+    println!("{} Profile saved to: {} (use with scan --profile)", "[+]".green(), output.display());
+
+    Ok(())
+}
+
+fn load_baseline(path: &PathBuf) -> Result<OffsetOutput, CliError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(CliError::io(format!("read baseline {}", path.display())))?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(CliError::parse(format!("baseline {}", path.display())))?;
+
+    let version = json["schema_version"].as_u64().unwrap_or(1);
+    if version != SCAN_SCHEMA_VERSION {
+        println!("{} Baseline schema version {} differs from {}; diffing anyway",
+            "[!]".yellow(), version, SCAN_SCHEMA_VERSION);
+    }
+
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    Ok(OffsetOutput::from_scan_json(&name, &json))
+}
+
+/// Prints the diff from `baseline` to `current` and fails if anything the
+/// baseline had is missing now.
+fn print_baseline_diff(path: &PathBuf, baseline: &OffsetOutput, current: &OffsetOutput) -> Result<(), CliError> {
+    let diff = DiffGenerator::new().generate(baseline, current);
+
+    println!("{}", "═".repeat(55).cyan());
+    println!("{}", "              BASELINE DIFF".cyan().bold());
+    println!("{}", "═".repeat(55).cyan());
+    println!();
+    println!("  Baseline: {}", path.display());
+    println!();
+
+    if !diff.has_changes() {
+        println!("{} No offset changes since the baseline", "[+]".green());
+        println!();
+        return Ok(());
+    }
+
+    print!("{}", DiffGenerator::new().format_diff(&diff));
+
+    let mut disappeared: Vec<String> = diff.function_diff.removed.iter()
+        .map(|c| c.name.clone())
+        .collect();
+    disappeared.extend(diff.structure_diff.removed.iter().map(|c| c.name.clone()));
+    disappeared.extend(diff.structure_diff.changed.iter().flat_map(|c| {
+        c.field_changes.iter()
+            .filter(|f| f.change_type == ChangeType::Removed)
+            .map(move |f| format!("{}.{}", c.name, f.field_name))
+    }));
+    disappeared.extend(diff.class_diff.removed.iter().map(|c| c.name.clone()));
+
+    if disappeared.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::validation(format!("{} offsets from the baseline disappeared: {}", disappeared.len(), disappeared.join(", "))))
+    }
+}
+
+fn print_scan_summary(results: &CombinedResults, elapsed: std::time::Duration) {
+    println!("{}", "═".repeat(55).cyan());
+    println!("{}", "                SCAN COMPLETE".cyan().bold());
+    println!("{}", "═".repeat(55).cyan());
+    println!();
+
+    let table = TableBuilder::new()
+        .with_headers(&["Result", "Count"])
+        .add_row(&["Functions".to_string(), results.functions.len().to_string()])
+        .add_row(&["Structure offsets".to_string(), results.structure_offsets.len().to_string()])
+        .add_row(&["Classes".to_string(), results.classes.len().to_string()])
+        .add_row(&["Properties".to_string(), results.properties.len().to_string()])
+        .add_row(&["Methods".to_string(), results.methods.len().to_string()])
+        .add_row(&["Constants".to_string(), results.constants.len().to_string()])
+        .add_row(&["Total offsets".to_string(), results.total_count().to_string()])
+        .add_row(&["High confidence (>85%)".to_string(), results.high_confidence_count().to_string()])
+        .add_row(&["Time elapsed".to_string(), format!("{:.2}s", elapsed.as_secs_f64())])
+        .with_alignment(1, Alignment::Right);
+
+    println!("{}", render_table(table));
+    println!();
+}
+
+/// Lists the finders that came up empty, or every finder with `verbose`.
+fn print_finder_report(report: &[FinderReportEntry], verbose: bool) {
+    let found = report.iter().filter(|e| e.status == FinderStatus::Found).count();
+
+    println!("  {} Roblox finders matched:  {}/{}", "•".cyan(), found.to_string().green().bold(), report.len());
+
+    let rows: Vec<Vec<String>> = report.iter()
+        .filter(|e| verbose || e.status != FinderStatus::Found)
+        .map(|entry| vec![
+            entry.offset.clone(),
+            entry.finder.clone(),
+            entry.status.name().to_string(),
+            entry.address.map(|a| format!("0x{:x}", a)).unwrap_or_default(),
+            entry.confidence.map(|c| format!("{:.0}%", c * 100.0)).unwrap_or_default(),
+        ])
+        .collect();
+
+    if !rows.is_empty() {
+        let table = TableBuilder::new()
+            .with_headers(&["Offset", "Finder", "Status", "Address", "Confidence"])
+            .with_rows(&rows)
+            .with_alignment(4, Alignment::Right);
+        println!("{}", render_table(table));
+    }
+
+    println!();
+}
+
+/// Addresses several names resolved to; unrelated names there usually mean
+/// a finder matched the wrong function.
+fn print_address_collisions(collisions: &[AddressCollision]) {
+    if collisions.is_empty() {
+        return;
+    }
+
+    println!("{} {} addresses shared by several names:", "[*]".blue(), collisions.len());
+    for collision in collisions {
+        let prefix = match collision.kind {
+            CollisionKind::Alias => "[*]".blue(),
+            CollisionKind::Collision => "[!]".yellow(),
+        };
+        println!("  {} 0x{:x} {} ({})", prefix, collision.address, collision.names.join(", "), collision.kind.name());
+    }
+    println!();
+}
+
+/// Why each finder that found nothing likely missed, from whether its
+/// anchor strings are still in the binary.
+fn print_unmatched_diagnostics(reader: Arc<dyn MemoryReader>, report: &[FinderReportEntry]) {
+    let unmatched = match FinderDiagnostics::new(reader).unmatched(report) {
+        Ok(unmatched) => unmatched,
+        Err(e) => {
+            println!("{} Could not check anchor strings: {}", "[!]".yellow(), e);
+            return;
+        }
+    };
+
+    if unmatched.is_empty() {
+        return;
+    }
+
+    println!("  {} Unmatched finders:", "•".cyan());
+
+    for finder in &unmatched {
+        let reason = match finder.reason {
+            UnmatchedReason::AnchorAbsent => finder.reason.describe().red(),
+            UnmatchedReason::PatternChanged => finder.reason.describe().yellow(),
+            UnmatchedReason::NoAnchors => finder.reason.describe().dimmed(),
+        };
+        println!("    {:<16} {}", finder.finder, reason);
+
+        if !finder.present.is_empty() {
+            println!("    {:<16} {} {}", "", "present:".dimmed(), quote_list(&finder.present));
+        }
+        if !finder.missing.is_empty() {
+            println!("    {:<16} {} {}", "", "missing:".dimmed(), quote_list(&finder.missing));
+        }
+    }
+
+    println!();
+}
+
+fn quote_list(items: &[&str]) -> String {
+    items.iter().map(|s| format!("\"{}\"", s)).collect::<Vec<_>>().join(", ")
+}
+
+/// Bordered on a terminal, shrunk to fit when wider than it. Piped output
+/// gets borderless, uncolored columns so it can be copied as plain text.
+fn render_table(table: TableBuilder) -> String {
+    let terminal = Terminal::new();
+
+    if !terminal.is_tty() {
+        return table.with_border_style(BorderStyle::None).with_color(false).build();
+    }
+
+    let table = table.with_border_style(BorderStyle::Rounded);
+    let rendered = table.build();
+    let width = terminal.width() as usize;
+
+    // The top border is the one line without color codes
+    if rendered.lines().next().is_some_and(|line| line.chars().count() > width) {
+        table.with_max_width(width).build()
+    } else {
+        rendered
+    }
+}
+
+// ==================== FFLAGS COMMAND ====================
+
+/// Window size for the FFlag name scan
+const FFLAG_SCAN_CHUNK: usize = 16 * 1024 * 1024;
+
+fn run_fflags(
+    cli: &Cli,
+    binary: Option<PathBuf>,
+    output: PathBuf,
+    text: Option<PathBuf>,
+    append: bool,
+    category: Option<String>,
+    exclude_category: Vec<String>,
+    search: Option<String>,
+    regex: Option<Regex>,
+    found_only: bool,
+    xor: bool,
+    list_categories: bool,
+) -> Result<(), CliError> {
+    let start_time = Instant::now();
+
+    if list_categories {
+        menu_fflag_categories();
+        return Ok(());
+    }
+
+    let binary = binary.ok_or_else(|| CliError::invalid("Binary path is required for FFlag scanning"))?;
+
+    // The name scan only needs raw bytes, so map the file rather than
+    // reading it in; the parsed binary is loaded only for the XOR search
+    let mapped = MmapMemory::from_file(&binary, Address::new(0))
+        .map_err(CliError::BinaryLoad)?;
+
+    println!("{} Binary mapped: {}", "[+]".green(), binary.display());
+    println!("{} Binary size: {} MB", "[+]".green(), mapped.size() / 1024 / 1024);
+    println!("{} Scanning for FFlags...", "[*]".blue());
+    println!();
+
+    let db = get_database();
+    
+    let mut flags_to_check: Vec<_> = db.all_flags().collect();
+
+    if let Some(ref cat) = category {
+        flags_to_check.retain(|f| f.category.eq_ignore_ascii_case(cat));
+        println!("{} Filtering by category: {}", "[*]".blue(), cat.yellow());
+    }
+
+    if !exclude_category.is_empty() {
+        flags_to_check.retain(|f| !exclude_category.iter().any(|cat| f.category.eq_ignore_ascii_case(cat)));
+        println!("{} Excluding categories: {}", "[*]".blue(), exclude_category.join(", ").yellow());
+    }
+
+    if let Some(ref s) = search {
+        let search_lower = s.to_lowercase();
+        flags_to_check.retain(|f| f.name.to_lowercase().contains(&search_lower));
+        println!("{} Searching for: {}", "[*]".blue(), s.yellow());
+    }
+
+    if let Some(ref re) = regex {
+        flags_to_check.retain(|f| re.is_match(f.name));
+        println!("{} Matching regex: {}", "[*]".blue(), re.as_str().yellow());
+    }
+
+    println!("{} Checking {} flags...", "[*]".blue(), flags_to_check.len());
+    println!();
+
+    let mut collection = FFlagCollection::new();
+
+    // One pass over the raw file for every remaining flag name, in windows
+    // that overlap enough to hold any name crossing a boundary
+    let spinner = create_spinner("Searching for FFlags...", cli.no_progress);
+    let names: Vec<&str> = flags_to_check.iter().map(|f| f.name).collect();
+    let overlap = names.iter().map(|name| name.len()).max().unwrap_or(0);
+    let present = find_flag_names_in_chunks(mapped.chunks(FFLAG_SCAN_CHUNK, overlap), &names);
+    drop(mapped);
+
+    let (mut found_flags, mut not_found_flags): (Vec<&KnownFlag>, Vec<&KnownFlag>) = flags_to_check.iter()
+        .partition(|f| present.contains(f.name));
+
+    if let Some(ref pb) = spinner {
+        pb.finish_with_message("Scan complete!");
+    }
+
+    let mut xor_keys: HashMap<&str, u8> = HashMap::new();
+
+    if xor && !not_found_flags.is_empty() {
+        println!("{} Searching data segments for {} XOR-encoded flag names...", "[*]".blue(), not_found_flags.len());
+
+        let reader: Arc<dyn MemoryReader> = Arc::new(BinaryMemory::load(&binary)
+            .map_err(CliError::BinaryLoad)?);
+        let names: Vec<&str> = not_found_flags.iter().map(|f| f.name).collect();
+        let encoded = FFlagParser::new(reader).find_xor_encoded(&names);
+
+        for entry in &encoded {
+            println!("  {} {} (key {}, at 0x{:x})", "✓".green(), entry.name.cyan(), format!("0x{:02X}", entry.key).yellow(), entry.address);
+        }
+        println!("{} Found {} XOR-encoded flags", "[+]".green(), encoded.len());
+        println!();
+
+        for entry in encoded {
+            if let Some(pos) = not_found_flags.iter().position(|f| f.name == entry.name) {
+                let flag = not_found_flags.remove(pos);
+                xor_keys.insert(flag.name, entry.key);
+                found_flags.push(flag);
+            }
+        }
+    }
+
+    let found_names: HashSet<&str> = found_flags.iter().map(|f| f.name).collect();
+    for flag in &flags_to_check {
+        collection.record(flag.category, &flag.flag_type, found_names.contains(flag.name));
+    }
+
+    let output_flags: &Vec<&KnownFlag> = if found_only {
+        &found_flags
+    } else {
+        &flags_to_check
+    };
+
+    let json_output = serde_json::json!({
+        "total_checked": flags_to_check.len(),
+        "found_in_binary": found_flags.len(),
+        "not_found": not_found_flags.len(),
+        "by_category": collection.by_category.iter().map(|(name, stats)| {
+            (name.clone(), serde_json::json!({
+                "found": stats.found_count(),
+                "total": stats.total_count(),
+            }))
+        }).collect::<serde_json::Map<_, _>>(),
+        "flags": output_flags.iter().map(|f| {
+            serde_json::json!({
+                "name": f.name,
+                "type": format!("{:?}", f.flag_type),
+                "category": f.category,
+                "found_in_binary": found_flags.iter().any(|ff| ff.name == f.name),
+                "xor_key": xor_keys.get(f.name).map(|key| format!("0x{:02X}", key)),
+            })
+        }).collect::<Vec<_>>()
+    });
+
+    let json_str = serde_json::to_string_pretty(&json_output)
+        .map_err(CliError::serialize("FFlag results"))?;
+    
+    std::fs::write(&output, &json_str)
+        .map_err(CliError::io(format!("write {}", output.display())))?;
+
+    println!("{} Results saved to: {}", "[+]".green(), output.display());
+
+    if let Some(text_path) = text {
+        let mut text_content = String::new();
+        if append {
+            writeln!(text_content, "### {} @ {}", binary.display(), time::format_timestamp(time::generated_secs())).unwrap();
+        }
+        writeln!(text_content, "FFlag Report").unwrap();
+        writeln!(text_content, "============").unwrap();
+        writeln!(text_content).unwrap();
+        writeln!(text_content, "Total checked: {}", flags_to_check.len()).unwrap();
+        writeln!(text_content, "Found in binary: {}", found_flags.len()).unwrap();
+        writeln!(text_content).unwrap();
+
+        writeln!(text_content, "{:<24} {:>8} {:>8} {:>7}", "Category", "Found", "Total", "%").unwrap();
+        writeln!(text_content, "{}", "-".repeat(50)).unwrap();
+        for (name, stats) in collection.categories_by_found() {
+            writeln!(text_content, "{:<24} {:>8} {:>8} {:>6.1}%",
+                name, stats.found_count(), stats.total_count(), stats.found_ratio() * 100.0).unwrap();
+        }
+        writeln!(text_content).unwrap();
+        
+        if !found_flags.is_empty() {
+            writeln!(text_content, "Found Flags:").unwrap();
+            writeln!(text_content, "------------").unwrap();
+            for flag in &found_flags {
+                match xor_keys.get(flag.name) {
+                    Some(key) => writeln!(text_content, "  {} [{}] (xor 0x{:02X})", flag.name, flag.category, key).unwrap(),
+                    None => writeln!(text_content, "  {} [{}]", flag.name, flag.category).unwrap(),
+                }
+            }
+        }
+        
+        if append {
+            writeln!(text_content).unwrap();
+            std::fs::OpenOptions::new().create(true).append(true).open(&text_path)
+                .and_then(|mut file| file.write_all(text_content.as_bytes()))
+                .map_err(CliError::io("append text output"))?;
+            println!("{} Text report appended to: {}", "[+]".green(), text_path.display());
+        } else {
+            std::fs::write(&text_path, text_content)
+                .map_err(CliError::io("write text output"))?;
+            println!("{} Text report saved to: {}", "[+]".green(), text_path.display());
+        }
+    }
+
+    println!();
+    println!("{}", "═".repeat(55).cyan());
+    println!("{}", "             FFLAG SCAN COMPLETE".cyan().bold());
+    println!("{}", "═".repeat(55).cyan());
+    println!();
+    println!("  {} Total flags checked:    {}", "•".cyan(), flags_to_check.len().to_string().white());
+    println!("  {} Found in binary:        {}", "•".cyan(), found_flags.len().to_string().green().bold());
+    println!("  {} Not found:              {}", "•".cyan(), not_found_flags.len().to_string().yellow());
+    println!();
+    println!("  {} Time elapsed:           {:.2}s", "⏱".bright_black(), start_time.elapsed().as_secs_f64());
+    println!();
+
+    if !found_flags.is_empty() && cli.verbose {
+        println!("{}", "Found Flags (first 20):".yellow().bold());
+        for flag in found_flags.iter().take(20) {
+            println!("  {} {} [{}]", "✓".green(), flag.name.cyan(), flag.category.bright_black());
+        }
+        if found_flags.len() > 20 {
+            println!("  ... and {} more", found_flags.len() - 20);
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+// ==================== OTHER COMMANDS ====================
+
+fn run_diff(cli: &Cli, old: PathBuf, new: PathBuf, output: Option<PathBuf>, since: Option<u64>, from_msgpack: bool) -> Result<(), CliError> {
+    println!("{} Comparing offset files...", "[*]".blue());
+    println!("  Old: {}", old.display());
+    println!("  New: {}", new.display());
+    println!();
+
+    if !old.exists() {
+        return Err(CliError::not_found(format!("Old file not found: {}", old.display())));
+    }
+    if !new.exists() {
+        return Err(CliError::not_found(format!("New file not found: {}", new.display())));
+    }
+
+    let old_json = read_offsets_file(&old, from_msgpack, "old file")?;
+    let new_json = read_offsets_file(&new, from_msgpack, "new file")?;
+    check_offsets_json(&old_json, "old file")?;
+    check_offsets_json(&new_json, "new file")?;
+
+    let old_base = read_target_base(&old_json);
+    let new_base = read_target_base(&new_json);
+    if old_base != new_base {
+        println!("{} Normalizing new base 0x{:x} to old base 0x{:x}", "[*]".blue(), new_base, old_base);
+    }
+
+    let old_time = offsets_file_time(&old, &old_json);
+    let new_time = offsets_file_time(&new, &new_json);
+
+    let old_output = OffsetOutput::from_scan_json(&old.display().to_string(), &old_json);
+    let new_output = OffsetOutput::from_scan_json(&new.display().to_string(), &new_json);
+    let mut diff = OffsetDiff::from_outputs(&old_output, &new_output);
+
+    println!("{}", "═".repeat(55).cyan());
+    println!("{}", "               DIFF RESULTS".cyan().bold());
+    println!("{}", "═".repeat(55).cyan());
+    println!();
+
+    let describe = |t: Option<u64>| t.map_or("unknown".to_string(), time::format_timestamp);
+    println!("  Old generated: {}", describe(old_time));
+    println!("  New generated: {}", describe(new_time));
+    match (old_time, new_time) {
+        (Some(o), Some(n)) if n >= o => println!("  Time between:  {}", time::format_span(n - o)),
+        (Some(o), Some(n)) => println!("  Time between:  {} {}", time::format_span(o - n), "(new file is older)".yellow()),
+        _ => {}
+    }
+    println!();
+
+    if let Some(since) = since {
+        let total = diff.change_count();
+        let mut source_times: HashMap<String, Option<u64>> = HashMap::new();
+
+        diff.retain(|change| {
+            let (json, file_time) = if change.kind == OffsetChangeKind::Removed {
+                (&old_json, old_time)
+            } else {
+                (&new_json, new_time)
+            };
+            let (section, key) = match change.section {
+                OffsetSection::Function => ("functions", change.name.as_str()),
+                OffsetSection::Structure => ("structure_offsets", change.name.split_once('.').map_or(change.name.as_str(), |(s, _)| s)),
+                OffsetSection::Class => ("classes", change.name.as_str()),
+            };
+
+            // Merged files name each entry's source; fall back to the file itself
+            let entry_time = match json[section][key]["source"].as_str() {
+                Some(source) => source_times.entry(source.to_string())
+                    .or_insert_with(|| source_file_time(&PathBuf::from(source)))
+                    .or(file_time),
+                None => file_time,
+            };
+            entry_time.is_some_and(|t| t >= since)
+        });
+
+        println!("{} Keeping {} of {} changes from sources generated since {}",
+            "[*]".blue(), diff.change_count(), total, time::format_timestamp(since).yellow());
+        println!();
+    }
+
+    let stats = diff.statistics();
+    println!("  Unchanged: {}  Changed: {}  Added: {}  Removed: {}",
+        stats.unchanged, stats.changed.to_string().yellow(), stats.added.to_string().green(), stats.removed.to_string().red());
+    println!();
+
+    if diff.change_count() == 0 {
+        println!("  {} No offset changes", "[+]".green());
+        println!();
+    }
+
+    for section in [OffsetSection::Function, OffsetSection::Structure, OffsetSection::Class] {
+        for (kind, heading) in [
+            (OffsetChangeKind::Added, "Added"),
+            (OffsetChangeKind::Removed, "Removed"),
+            (OffsetChangeKind::ValueChanged, "Changed"),
+        ] {
+            let changes = diff.changes_in(section, kind);
+            if changes.is_empty() {
+                continue;
+            }
+
+            println!("{} {}:", heading.bold(), section.label());
+            for change in changes {
+                match kind {
+                    OffsetChangeKind::Added => println!("  {} {} @ {}", "+".green(), change.name, change.new_hex()),
+                    OffsetChangeKind::Removed => println!("  {} {} @ {}", "-".red(), change.name, change.old_hex()),
+                    _ => println!("  {} {} {} -> {} ({})", "~".yellow(), change.name,
+                        change.old_hex(), change.new_hex(), change.delta_hex()),
+                }
+            }
+            println!();
+        }
+    }
+
+    if let Some(path) = output {
+        // The report format follows the extension, JSON for anything else
+        let format = path.extension()
+            .and_then(|ext| ReportFormat::from_extension(&ext.to_string_lossy()))
+            .unwrap_or(ReportFormat::Json);
+        let report = DiffReportBuilder::new(&old.display().to_string(), &new.display().to_string())
+            .title(&format!("Offset Diff: {} -> {}", old.display(), new.display()))
+            .offset_diff(diff)
+            .build();
+        report.write_to_file(&path.to_string_lossy(), format)
+            .map_err(CliError::io(format!("write {}", path.display())))?;
+        println!("{} Diff saved to: {}", "[+]".green(), path.display());
+        println!();
+    }
+
+    Ok(())
+}
+
+/// `generated_at` from the file, or its modification time for files
+/// written before it was recorded
+fn offsets_file_time(path: &PathBuf, json: &serde_json::Value) -> Option<u64> {
+    json["generated_at"].as_str()
+        .and_then(time::parse_timestamp)
+        .or_else(|| {
+            let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+            modified.duration_since(std::time::UNIX_EPOCH).ok().map(|d| d.as_secs())
+        })
+}
+
+fn source_file_time(path: &PathBuf) -> Option<u64> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    offsets_file_time(path, &json)
+}
+
+fn run_merge(inputs: Vec<PathBuf>, output: PathBuf) -> Result<(), CliError> {
+    println!("{} Merging {} offset files...", "[*]".blue(), inputs.len());
+
+    let target_name = output.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut merger = OffsetMerger::new(&target_name);
+
+    for path in &inputs {
+        let content = std::fs::read_to_string(path)
+            .map_err(CliError::io(format!("read {}", path.display())))?;
+        let json: serde_json::Value = serde_json::from_str(&content)
+            .map_err(CliError::parse(format!("{}", path.display())))?;
+
+        let version = json["schema_version"].as_u64().unwrap_or(1);
+        if version != SCAN_SCHEMA_VERSION {
+            println!("{} {} has schema version {}, expected {}; merging anyway",
+                "[!]".yellow(), path.display(), version, SCAN_SCHEMA_VERSION);
+        }
+
+        let source = path.display().to_string();
+        let offsets = OffsetOutput::from_scan_json(&source, &json);
+        println!("  {}: {} offsets", source, offsets.total_offsets());
+        merger.add(&source, &offsets);
+    }
+    println!();
+
+    let merged = merger.finish();
+
+    for conflict in &merged.conflicts {
+        println!("{} Conflict for {}: 0x{:x} ({}) kept over 0x{:x} ({})",
+            "[!]".yellow(), conflict.name,
+            conflict.kept_address, conflict.kept_source,
+            conflict.dropped_address, conflict.dropped_source);
+    }
+    if !merged.conflicts.is_empty() {
+        println!();
+    }
+
+    let json_string = serde_json::to_string_pretty(&merged.to_scan_json())
+        .map_err(CliError::serialize("merged offsets"))?;
+    std::fs::write(&output, json_string)
+        .map_err(CliError::io(format!("write {}", output.display())))?;
+
+    println!("{} Merged {} offsets ({} conflicts) into {}",
+        "[+]".green(), merged.output.total_offsets(), merged.conflicts.len(), output.display());
+
+    Ok(())
+}
+
+fn load_fflag_snapshot(path: &PathBuf) -> Result<FFlagSnapshot, CliError> {
+    if !path.exists() {
+        return Err(CliError::not_found(format!("FFlag file not found: {}", path.display())));
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(CliError::io(format!("read {}", path.display())))?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(CliError::parse(format!("{}", path.display())))?;
+
+    FFlagSnapshot::from_json(&json)
+        .map_err(CliError::parse(format!("FFlag file {}", path.display())))
+}
+
+fn run_fflags_diff(old: PathBuf, new: PathBuf) -> Result<(), CliError> {
+    println!("{} Comparing FFlag dumps...", "[*]".blue());
+    println!("  Old: {}", old.display());
+    println!("  New: {}", new.display());
+    println!();
+
+    let old_snapshot = load_fflag_snapshot(&old)?;
+    let new_snapshot = load_fflag_snapshot(&new)?;
+    let diff = FFlagDiff::compare(&old_snapshot, &new_snapshot);
+
+    println!("{}", "═".repeat(55).cyan());
+    println!("{}", "             FFLAG DIFF RESULTS".cyan().bold());
+    println!("{}", "═".repeat(55).cyan());
+    println!();
+
+    for (category, entries) in diff.by_category() {
+        println!("{} ({})", category.yellow().bold(), entries.len());
+
+        for entry in entries {
+            match &entry.change {
+                FFlagChange::Added => println!("  {} {}", "+".green(), entry.name),
+                FFlagChange::Removed => println!("  {} {}", "-".red(), entry.name),
+                FFlagChange::ValueChanged { old, new } => {
+                    println!("  {} {}: {} -> {}", "~".yellow(), entry.name, old, new)
+                }
+            }
+        }
+
+        println!();
+    }
+
+    let added = diff.count(|c| matches!(c, FFlagChange::Added));
+    let removed = diff.count(|c| matches!(c, FFlagChange::Removed));
+    let changed = diff.count(|c| matches!(c, FFlagChange::ValueChanged { .. }));
+
+    if diff.is_empty() {
+        println!("  {} No FFlag differences", "[+]".green());
+    } else {
+        println!("  {} {} added, {} removed, {} changed, {} unchanged",
+            "[+]".green(), added, removed, changed, diff.unchanged);
+    }
+    println!();
+
+    Ok(())
+}
+
+fn run_validate(cli: &Cli, offsets: PathBuf, binary: Option<PathBuf>, pid: Option<i32>, sizes: Option<PathBuf>, size_tolerance: usize, from_msgpack: bool) -> Result<(), CliError> {
+    println!("{} Validating offsets...", "[*]".blue());
+    println!("  Offsets: {}", offsets.display());
+    if let Some(binary) = &binary {
+        println!("  Binary: {}", binary.display());
+    }
+    if let Some(pid) = pid {
+        println!("  Process: {}", pid);
+    }
+    println!();
+
+    if !offsets.exists() {
+        return Err(CliError::not_found(format!("Offsets file not found: {}", offsets.display())));
+    }
+    if let Some(binary) = binary.as_ref().filter(|b| !b.exists()) {
+        return Err(CliError::not_found(format!("Binary not found: {}", binary.display())));
+    }
+
+    let json = read_offsets_file(&offsets, from_msgpack, "offsets file")?;
+    let output = OffsetOutput::from_scan_json("", &json);
+
+    let expected_sizes = match &sizes {
+        Some(path) => {
+            let content = std::fs::read_to_string(path)
+                .map_err(CliError::io(format!("read sizes file {}", path.display())))?;
+            parse_expected_sizes(&content)
+                .map_err(CliError::parse(format!("sizes file {}", path.display())))?
+        }
+        None => Vec::new(),
+    };
+
+    let binary_mem: Option<Arc<dyn MemoryReader>> = match &binary {
+        Some(binary) => Some(Arc::new(BinaryMemory::load(binary)
+            .map_err(CliError::BinaryLoad)?)),
+        None => None,
+    };
+
+    let offsets_base = read_target_base(&json);
+
+    let (validator, load_base, reader) = match (pid, binary_mem) {
+        (Some(pid), binary_mem) => {
+            println!("{} Attaching to process {}...", "[*]".blue(), pid);
+            let process = attach_process(pid)?;
+            let mut validator = ImageValidator::new(process.clone());
+            if let Some(binary_mem) = binary_mem {
+                let binary_base = binary_mem.get_base_address().as_u64();
+                validator = validator.with_reference(binary_mem, binary_base);
+            }
+
+            let load_base = detect_runtime_base(&validator, &output, offsets_base)?;
+            (validator, load_base, process)
+        }
+        (None, Some(binary_mem)) => {
+            let binary_base = binary_mem.get_base_address().as_u64();
+            if offsets_base != binary_base {
+                println!("{} Normalizing offsets base 0x{:x} to binary base 0x{:x}", "[*]".blue(), offsets_base, binary_base);
+            }
+            (ImageValidator::new(binary_mem.clone()), binary_base, binary_mem)
+        }
+        (None, None) => return Err(CliError::invalid("Either --binary or --pid is required")),
+    };
+
+    let report = validator.check(&output, offsets_base, load_base);
+
+    let mut items: Vec<(&str, &String, u64)> = output.functions.iter()
+        .map(|(name, f)| ("function", name, rebase(f.address, offsets_base, load_base)))
+        .collect();
+    items.sort_by(|a, b| a.1.cmp(b.1));
+    items.extend(output.classes.iter()
+        .filter_map(|class| Some(("vtable", &class.name, rebase(class.vtable_address?, offsets_base, load_base)))));
+
+    println!();
+    println!("{}", "═".repeat(55).cyan());
+    println!("{}", "           VALIDATION RESULTS".cyan().bold());
+    println!("{}", "═".repeat(55).cyan());
+    println!();
+
+    let (mut passed, mut suspect, mut failed) = (0, 0, 0);
+    for (kind, name, address) in &items {
+        let marker = match report.worst_severity(name) {
+            Some(IssueSeverity::Error) => { failed += 1; "[-]".red() }
+            Some(_) => { suspect += 1; "[!]".yellow() }
+            None => { passed += 1; "[+]".green() }
+        };
+        let reasons: Vec<&str> = report.issues_for(name).map(|issue| issue.message.as_str()).collect();
+        println!("  {} {:<8} {:<30} 0x{:x}  {}", marker, kind, name, address, reasons.join("; "));
+    }
+    if !items.is_empty() {
+        println!();
+    }
+
+    if !expected_sizes.is_empty() {
+        let (size_passed, size_suspect, size_failed) = check_struct_sizes(&output, &expected_sizes, size_tolerance, reader);
+        passed += size_passed;
+        suspect += size_suspect;
+        failed += size_failed;
+    }
+    let total = passed + suspect + failed;
+
+    println!("  {:<20} {}", "Passed", passed.to_string().green());
+    println!("  {:<20} {}", "Suspect", suspect.to_string().yellow());
+    println!("  {:<20} {}", "Failed", failed.to_string().red());
+    println!("  {:<20} {} errors, {} warnings", "Issues", report.summary.error_count, report.summary.warning_count);
+    println!();
+
+    if failed > 0 {
+        return Err(CliError::validation(format!("{} of {} checks failed validation", failed, total)));
+    }
+
+    println!("  {} Validation complete", "[+]".green());
+    println!();
+
+    Ok(())
+}
+
+/// Prints how each expected struct size compares to the size found or
+/// inferred from the offsets file, returning (passed, suspect, failed).
+/// A size off by more than `tolerance` fails; a size that isn't a multiple
+/// of the struct's alignment, or a struct the scan didn't find, is suspect.
+fn check_struct_sizes(output: &OffsetOutput, expected: &[(String, ExpectedSize)], tolerance: usize, reader: Arc<dyn MemoryReader>) -> (usize, usize, usize) {
+    let mut validator = SizeValidator::new(reader);
+    for (name, size) in expected {
+        validator.add_known_size(name, size.clone());
+    }
+
+    let (mut passed, mut suspect, mut failed) = (0, 0, 0);
+
+    println!("  {}", "Struct sizes".white().bold());
+    for (name, size) in expected {
+        let structure = match output.structure_offsets.get(name) {
+            Some(structure) => structure,
+            None => {
+                println!("  {} {:<8} {:<30} not in offsets file (expected {})", "[!]".yellow(), "size", name, size);
+                suspect += 1;
+                continue;
+            }
+        };
+
+        let actual = structure.inferred_size();
+        let result = validator.validate_structure_size_within(name, actual, tolerance);
+        let alignment = validator.validate_size_alignment(actual, structure.alignment);
+        let delta = match result.delta {
+            Some(delta) if delta != 0 => format!(", off by {}{} bytes", if delta > 0 { "+" } else { "" }, delta),
+            _ => String::new(),
+        };
+
+        if !result.is_valid {
+            println!("  {} {:<8} {:<30} 0x{:x}  expected {}{}", "[-]".red(), "size", name, actual, size, delta);
+            failed += 1;
+        } else if !alignment.is_aligned {
+            println!("  {} {:<8} {:<30} 0x{:x}  not a multiple of its {}-byte alignment ({} bytes short)",
+                "[!]".yellow(), "size", name, actual, alignment.required_alignment, alignment.padding_needed);
+            suspect += 1;
+        } else {
+            println!("  {} {:<8} {:<30} 0x{:x}  expected {}{}", "[+]".green(), "size", name, actual, size, delta);
+            passed += 1;
+        }
+    }
+    println!();
+
+    (passed, suspect, failed)
+}
+
+/// Where the scanned image is loaded in a live process. Prints the ASLR
+/// slide against the image base the scan saw, and warns when the image
+/// isn't at the base the loader reported or most functions don't line up.
+fn detect_runtime_base(validator: &ImageValidator, output: &OffsetOutput, origin: u64) -> Result<u64, CliError> {
+    let expected = validator.expected_base()
+        .ok_or(CliError::NoExecutableRegions("process"))?;
+    let best = validator.load_bases(output, origin).into_iter().next();
+
+    let load_base = match best {
+        Some(best) if best.prologues > 0 => best.address,
+        _ => expected,
+    };
+    let image_base = output.target.base_address;
+    let slide = load_base.wrapping_sub(image_base) as i64;
+
+    println!();
+    println!("  {:<20} 0x{:x}", "Image base", image_base);
+    println!("  {:<20} 0x{:x}", "Runtime base", load_base);
+    println!("{} ASLR slide: {}", "[+]".green(), format_slide(slide).yellow().bold());
+
+    if let Some(best) = best.filter(|b| b.address != expected) {
+        println!("{} Image is not at the expected base 0x{:x}; {} of {} sampled functions line up at 0x{:x} instead",
+            "[!]".yellow(), expected, best.prologues, best.sampled, best.address);
+    }
+
+    if let Some(best) = best.filter(|b| b.prologues * 2 < b.sampled) {
+        println!("{} Only {} of {} sampled functions start with a prologue after rebasing; the offsets may be stale",
+            "[!]".yellow(), best.prologues, best.sampled);
+    }
+
+    Ok(load_base)
+}
+
+fn format_slide(slide: i64) -> String {
+    if slide < 0 {
+        format!("-0x{:x}", slide.unsigned_abs())
+    } else {
+        format!("+0x{:x}", slide)
+    }
+}
+
+fn run_dump(cli: &Cli, binary: PathBuf, address: String, size: usize, disasm: bool) -> Result<(), CliError> {
+    let addr = parse_address(&address)?;
+
+    println!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let symbols = load_symbols(&reader);
+    let describe = |target: u64| symbol_name(&symbols, Address::new(target));
+
+    let location = describe_address(&symbols, Address::new(addr));
+    println!("{} Dumping {} bytes at {}", "[*]".blue(), size, location.yellow());
+    println!();
+
+    let data = reader.read_bytes(Address::new(addr), size)
+        .map_err(CliError::memory("read memory"))?;
+
+    if disasm {
+        println!("{}", "Disassembly:".yellow().bold());
+        let (mut context, cache) = open_disassembly(cli, reader.clone(), &binary);
+        let formatter = InstructionFormatter::new();
+        let end = Address::new(addr + (data.len() as u64 & !3));
+
+        let instructions = context.disassemble_range(Address::new(addr), end)
+            .map_err(CliError::memory("disassemble"))?;
+        save_disassembly_cache(&context, cache);
+
+        for instr in &instructions {
+            let annotations: Vec<String> = instr.get_branch_target()
+                .and_then(|target| describe(target.as_u64()))
+                .into_iter()
+                .collect();
+            println!("{}", formatter.format_with_annotations(instr, &annotations));
+        }
+    } else {
+        println!("{}", "Hex Dump:".yellow().bold());
+        for (i, chunk) in data.chunks(16).enumerate() {
+            let offset = i * 16;
+            print!("{:08x}:  ", addr + offset as u64);
+            
+            for (j, b) in chunk.iter().enumerate() {
+                print!("{:02x} ", b);
+                if j == 7 { print!(" "); }
+            }
+            
+            for _ in chunk.len()..16 {
+                print!("   ");
+            }
+            
+            print!(" |");
+            
+            for b in chunk {
+                if *b >= 0x20 && *b <= 0x7e {
+                    print!("{}", *b as char);
+                } else {
+                    print!(".");
+                }
+            }
+            
+            println!("|");
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Instructions kept by a disassembly context; a loaded cache may hold more
+const DISASM_CACHE_SIZE: usize = 10000;
+
+/// Decoded instructions are kept between runs in the user cache directory,
+/// one file per binary, named by a hash of its contents.
+fn disasm_cache_path(binary_hash: &str) -> PathBuf {
+    std::env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("roblox-offset-generator")
+        .join(format!("disasm-{}.json", binary_hash))
+}
+
+/// Disassembly context warmed from the on-disk cache unless `--no-cache`
+/// is set. Also returns where and under which hash to save it afterwards.
+fn open_disassembly(cli: &Cli, reader: Arc<dyn MemoryReader>, binary: &PathBuf) -> (DisassemblyContext, Option<(PathBuf, String)>) {
+    let context = DisassemblyContext::new(reader);
+    if cli.no_cache {
+        return (context, None);
+    }
+
+    let binary_hash = match std::fs::read(binary) {
+        Ok(data) => format!("{:016x}", HashComputer::fnv1a_64(&data)),
+        Err(_) => return (context, None),
+    };
+    let path = disasm_cache_path(&binary_hash);
+
+    let context = match DisassemblyCache::load(&path, &binary_hash, DISASM_CACHE_SIZE) {
+        Ok(Some(cache)) => {
+            println!("{} Loaded {} cached instructions from {}", "[*]".blue(), cache.len(), path.display());
+            context.with_cache(cache)
+        }
+        Ok(None) => {
+            println!("{} Ignoring disassembly cache {}: it belongs to a different binary", "[!]".yellow(), path.display());
+            context
+        }
+        // No cache yet, or one this version can't read; it is rewritten on save
+        Err(_) => context,
+    };
+
+    (context, Some((path, binary_hash)))
+}
+
+fn save_disassembly_cache(context: &DisassemblyContext, cache: Option<(PathBuf, String)>) {
+    if let Some((path, binary_hash)) = cache {
+        if let Err(e) = context.cache().save(&path, &binary_hash) {
+            println!("{} Failed to save disassembly cache {}: {}", "[!]".yellow(), path.display(), e);
+        }
+    }
+}
+
+fn run_disasm(cli: &Cli, binary: PathBuf, function: String, json: Option<PathBuf>, max_insns: usize, cfg: bool) -> Result<(), CliError> {
+    let entry = parse_address(&function)?;
+    if max_insns == 0 {
+        return Err(CliError::invalid("--max-insns must be at least 1"));
+    }
+
+    println!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let (mut context, cache) = open_disassembly(cli, reader, &binary);
+    context.set_max_function_instructions(max_insns);
+    if cfg {
+        let graph = ControlFlowGraph::from_context(&mut context, Address::new(entry))
+            .map_err(CliError::memory("build CFG"))?;
+        save_disassembly_cache(&context, cache);
+        print_cfg(&graph);
+        return Ok(());
+    }
+    let listing = context.disassemble_function_listing(Address::new(entry))
+        .map_err(CliError::memory("disassemble"))?;
+    save_disassembly_cache(&context, cache);
+
+    let (prefix, ending) = if listing.is_truncated() {
+        ("[!]".yellow(), "truncated (hit instruction cap)".yellow().bold())
+    } else {
+        ("[+]".green(), "ended on RET".normal())
+    };
+    println!("{} Decoded {} instructions at {} - {} ({})",
+        prefix,
+        listing.instructions.len(),
+        format!("0x{:x}", listing.entry.as_u64()).yellow(),
+        format!("0x{:x}", listing.end.as_u64()).yellow(),
+        ending
+    );
+    if listing.is_truncated() {
+        println!("{} No RET within {} instructions; this is not the whole function (raise --max-insns)", "[!]".yellow(), max_insns);
+    }
+    let unknown = listing.unknown_count();
+    if unknown > 0 {
+        println!("{} {} of {} instructions use encodings the decoder does not know", "[!]".yellow(), unknown, listing.instructions.len());
+    }
+
+    if let Some(json_path) = json {
+        let content = serde_json::to_string_pretty(&listing.to_json())
+            .map_err(CliError::serialize("listing"))?;
+        std::fs::write(&json_path, content)
+            .map_err(CliError::io(format!("write {}", json_path.display())))?;
+        println!("{} Disassembly saved to: {}", "[+]".green(), json_path.display());
+    } else {
+        println!();
+        println!("{}", InstructionFormatter::new().format_block(&listing.instructions));
+        if listing.is_truncated() {
+            println!("{}", format!("  ... truncated at 0x{:x} (hit instruction cap)", listing.end.as_u64()).yellow());
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+fn print_cfg(graph: &ControlFlowGraph) {
+    let mut blocks: Vec<_> = graph.blocks().collect();
+    blocks.sort_by_key(|block| block.start);
+
+    println!("{} {} basic blocks, {} edges, {} exits",
+        "[+]".green(),
+        graph.block_count(),
+        graph.edge_count(),
+        graph.exit_blocks().len()
+    );
+
+    for block in blocks {
+        println!();
+        println!("{} ({} instructions)",
+            format!("block_{:x}:", block.start.as_u64()).cyan().bold(),
+            block.instruction_count()
+        );
+        for instr in block.instructions() {
+            println!("  {:016X}  {} {}", instr.address.as_u64(), instr.mnemonic, instr.operands);
+        }
+
+        let edges: Vec<String> = graph.edges_from(block.start)
+            .map(|edge| format!("block_{:x} ({:?})", edge.to.as_u64(), edge.edge_type))
+            .collect();
+        if edges.is_empty() {
+            println!("  {} {}", "->".dimmed(), "exit".yellow());
+        } else {
+            println!("  {} {}", "->".dimmed(), edges.join(", "));
+        }
+    }
+    println!();
+}
+
+fn run_cfg(
+    binary: PathBuf,
+    address: String,
+    format: GraphFormat,
+    output: Option<PathBuf>,
+    max_insns: usize,
+) -> Result<(), CliError> {
+    let entry = Address::new(parse_address(&address)?);
+    if max_insns == 0 {
+        return Err(CliError::invalid("--max-insns must be at least 1"));
+    }
+
+    eprintln!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let mut context = DisassemblyContext::new(reader);
+    context.set_max_function_instructions(max_insns);
+    let graph = ControlFlowGraph::from_context(&mut context, entry)
+        .map_err(CliError::memory("build CFG"))?;
+
+    let exporter = GraphExporter::with_options(ExportOptions {
+        title: Some(format!("sub_{:x}", entry.as_u64())),
+        ..Default::default()
+    });
+    let rendered = exporter.export_cfg(&graph, format);
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered)
+                .map_err(CliError::io(format!("write {}", path.display())))?;
+            eprintln!("{} {} blocks, {} edges written to {}",
+                "[+]".green(), graph.block_count(), graph.edge_count(), path.display());
+        }
+        None => print!("{}", rendered),
+    }
+
+    Ok(())
+}
+
+fn run_sig_gen(binary: PathBuf, address: String, length: usize, mask: Option<Vec<String>>) -> Result<(), CliError> {
+    let addr = parse_address(&address)?;
+    let options = match mask {
+        Some(names) => MaskOptions::from_names(&names).map_err(CliError::parse("--mask"))?,
+        None => MaskOptions::default(),
+    };
+    if length == 0 {
+        return Err(CliError::invalid("Signature length must be at least 1 byte"));
+    }
+
+    println!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let bytes = reader.read_bytes(Address::new(addr), length)
+        .map_err(CliError::memory(format!("read 0x{:x}", addr)))?;
+    let pattern = SignatureGenerator::new().with_options(options).generate(&bytes);
+
+    let regions: Vec<MemoryRegion> = reader.get_regions()
+        .map_err(CliError::memory("get memory regions"))?
+        .into_iter()
+        .filter(|r| r.protection().can_execute())
+        .collect();
+    let matches = scan_for_pattern(reader.as_ref(), &pattern, &regions);
+
+    let wildcards = pattern.mask().iter().filter(|&&m| !m).count();
+    println!("{} Signature for {} ({} bytes, {} wildcarded):",
+        "[+]".green(),
+        format!("0x{:x}", addr).yellow(),
+        bytes.len(),
+        wildcards
+    );
+    println!();
+    println!("{}", pattern.to_hex_string());
+    println!();
+
+    match matches.len() {
+        1 => println!("{} Unique in executable code", "[+]".green()),
+        0 => println!("{} No match in executable code; the address may not be in code", "[!]".yellow()),
+        n => println!("{} Matches {} locations in executable code; use a longer --length or fewer --mask kinds", "[!]".yellow(), n),
+    }
+    println!();
+
+    Ok(())
+}
+
+fn run_reach(cli: &Cli, binary: PathBuf, from: String, to: Option<String>, max_depth: usize) -> Result<(), CliError> {
+    let root = parse_address(&from)?;
+    let target = to.as_deref().map(parse_address).transpose()?;
+
+    println!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let symbols = load_symbols(&reader);
+    let describe = |addr: Address| describe_address(&symbols, addr);
+
+    println!("{} Walking calls from 0x{:x} (max depth {})...", "[*]".blue(), root, max_depth);
+
+    let reach = InterproceduralAnalyzer::new(reader)
+        .reachable_from(Address::new(root), max_depth)
+        .map_err(CliError::memory("analyze root function"))?;
+
+    println!();
+    println!("{}", "═".repeat(55).cyan());
+    println!("{}", "            REACHABLE FUNCTIONS".cyan().bold());
+    println!("{}", "═".repeat(55).cyan());
+    println!();
+
+    for function in &reach.functions {
+        let context = if function.context.is_empty() {
+            "root".to_string()
+        } else {
+            function.context.call_sites.iter()
+                .map(|site| format!("0x{:x}", site.as_u64()))
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        };
+
+        println!("  {:>3}  {}  {}",
+            function.depth.to_string().yellow(),
+            describe(function.address),
+            format!("via {}", context).dimmed()
+        );
+    }
+
+    println!();
+    println!("{} {} functions reachable", "[+]".green(), reach.functions.len());
+
+    if !reach.cycles.is_empty() {
+        println!("{} {} recursive call edges", "[*]".blue(), reach.cycles.len());
+
+        if cli.verbose {
+            for (caller, callee) in &reach.cycles {
+                println!("      0x{:x} -> 0x{:x}", caller.as_u64(), callee.as_u64());
+            }
+        }
+    }
+
+    if reach.truncated {
+        println!("{} Depth cap {} reached; deeper calls were not followed", "[*]".blue(), max_depth);
+    }
+
+    if let Some(target) = target {
+        match reach.get(Address::new(target)) {
+            Some(function) => println!("{} {} reached at depth {}",
+                "[+]".green(), describe(function.address), function.depth),
+            None => println!("{} 0x{:x} not reached within depth {}",
+                "[!]".yellow(), target, max_depth),
+        }
+    }
+
+    println!();
+
+    Ok(())
+}
+
+/// Builds the call graph of everything reachable from `function`, then
+/// exports the part within `depth` calls of it. Nodes are named after their
+/// symbol when there is an exact match, `sub_<hex>` otherwise.
+fn run_xref(
+    binary: PathBuf,
+    function: String,
+    format: GraphFormat,
+    output: Option<PathBuf>,
+    depth: usize,
+    no_indirect: bool,
+) -> Result<(), CliError> {
+    let root = Address::new(parse_address(&function)?);
+
+    eprintln!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let symbols = load_symbols(&reader);
+    let name = |addr: Address| {
+        symbol_name(&symbols, addr).unwrap_or_else(|| format!("sub_{:x}", addr.as_u64()))
+    };
+
+    eprintln!("{} Walking calls from 0x{:x} (depth {})...", "[*]".blue(), root.as_u64(), depth);
+
+    let mut analyzer = InterproceduralAnalyzer::new(reader.clone());
+    let reach = analyzer.reachable_from(root, depth)
+        .map_err(CliError::memory("analyze root function"))?;
+
+    let mut builder = CallGraphBuilder::new().with_reader(reader);
+    for function in &reach.functions {
+        builder = builder.add_function(function.address, Some(name(function.address)));
+    }
+    for function in &reach.functions {
+        let summary = match analyzer.get_summary(function.address) {
+            Some(summary) => summary,
+            None => continue,
+        };
+        for &(_, callee) in &summary.call_sites {
+            builder = builder.add_call(function.address, callee);
+        }
+        if !no_indirect {
+            for &site in &summary.indirect_call_sites {
+                builder = builder.add_indirect_call(function.address, site);
+            }
+        }
+    }
+
+    let mut graph = SubgraphExtractor::extract_callees(&builder.build(), root, depth);
+
+    // Stub targets and callees past the depth cap have edges but no node yet
+    let unnamed: Vec<Address> = graph.edges()
+        .map(|edge| edge.to())
+        .filter(|&to| graph.get_node(to).is_none())
+        .collect();
+    for addr in unnamed {
+        graph.add_node(GraphNode::new(addr, name(addr), NodeKind::Function));
+    }
+
+    let exporter = GraphExporter::with_options(ExportOptions {
+        title: Some(name(root)),
+        max_depth: Some(depth),
+        include_indirect: !no_indirect,
+        ..Default::default()
+    });
+    let rendered = exporter.export(&graph, format);
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered)
+                .map_err(CliError::io(format!("write {}", path.display())))?;
+            eprintln!("{} {} functions, {} edges written to {}",
+                "[+]".green(), graph.len(), graph.edge_count(), path.display());
+        }
+        None => print!("{}", rendered),
+    }
+
+    Ok(())
+}
+
+fn run_string_xref(binary: PathBuf, string: String) -> Result<(), CliError> {
+    println!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let section = binary_mem.cstring_section();
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let mut analyzer = CrossReferenceAnalyzer::new(reader.clone(), Arc::new(Disassembler::new(reader.clone())));
+    if let Some(section) = section {
+        analyzer = analyzer.with_string_section(Address::new(section.addr)..Address::new(section.addr + section.size));
+    }
+
+    let symbols = load_symbols(&reader);
+
+    println!("{} Finding references to \"{}\"...", "[*]".blue(), string.escape_debug());
+
+    let refs = analyzer.xrefs_to_string(&string)
+        .map_err(CliError::memory("scan for references"))?;
+    if refs.is_empty() {
+        return Err(CliError::general(format!("No code references \"{}\"", string.escape_debug())));
+    }
+
+    println!();
+    for reference in &refs {
+        let function = describe_address(&symbols, reference.function);
+        println!("  {} in {} {}",
+            format!("0x{:x}", reference.from.as_u64()).yellow(),
+            function.green(),
+            format!("(string 0x{:x})", reference.string_addr.as_u64()).dimmed()
+        );
+    }
+
+    let functions: HashSet<Address> = refs.iter().map(|r| r.function).collect();
+    println!();
+    println!("{} {} references in {} functions", "[+]".green(), refs.len(), functions.len());
+    println!();
+
+    Ok(())
+}
+
+/// Runs only the finder that reports `name` unless `all_finders` is set,
+/// then prints the value alone. Progress and errors stay off stdout.
+fn run_get(cli: &Cli, binary: PathBuf, name: String, all_finders: bool) -> Result<(), CliError> {
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let regions = reader.get_regions()
+        .map_err(CliError::memory("get memory regions"))?;
+    let exec_regions = executable_regions(&regions)?;
+
+    let value = if all_finders {
+        // The same scan `scan` runs, over every executable region
+        let cancel = install_interrupt_handler();
+        let results = scan_regions(cli, reader.clone(), &exec_regions, 0, 1, &cancel);
+        restore_interrupt_handler();
+        if cancel.load(Ordering::SeqCst) {
+            return Err(CliError::interrupted("Lookup interrupted"));
+        }
+        results.lookup(&name)
+    } else {
+        let finder = finder_for_output(&name).ok_or_else(|| {
+            CliError::invalid(format!("No built-in finder reports {}; pass --all-finders to search a full scan", name))
+        })?;
+
+        let (ranges, _) = region_ranges(&exec_regions, 0);
+        let mut results = CombinedResults::new();
+        for (start, end) in ranges {
+            for result in finder.find(reader.clone(), start, end) {
+                results.add_function(result);
+            }
+        }
+        results.lookup(&name)
+    };
+
+    let value = value.ok_or_else(|| CliError::general(format!("{} not found", name)))?;
+    println!("0x{:x}", value);
+
+    Ok(())
+}
+
+fn run_chain(cli: &Cli, binary: PathBuf, string: String) -> Result<(), CliError> {
+    println!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let section = binary_mem.cstring_section()
+        .ok_or_else(|| CliError::general("No string section (__cstring or .rodata) in binary"))?;
+    let strings = Address::new(section.addr)..Address::new(section.addr + section.size);
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let regions = reader.get_regions()
+        .map_err(CliError::memory("get memory regions"))?;
+    let code: Vec<std::ops::Range<Address>> = executable_regions(&regions)?.iter()
+        .map(|region| region.start()..region.end())
+        .collect();
+
+    let symbols = load_symbols(&reader);
+
+    println!("{} Tracing \"{}\"...", "[*]".blue(), string);
+
+    let chains = ChainAnalyzer::new(reader.clone(), CallGraph::new())
+        .find_string_chains_in(&string, strings, &code);
+
+    if chains.is_empty() {
+        return Err(CliError::general(format!("No code loads \"{}\"", string)));
+    }
+
+    println!();
+    println!("{}", "═".repeat(55).cyan());
+    println!("{}", "             REFERENCE CHAINS".cyan().bold());
+    println!("{}", "═".repeat(55).cyan());
+    println!();
+
+    for (rank, chain) in chains.iter().enumerate() {
+        let function = describe_address(&symbols, chain.end);
+        let boundary = chain.links.last()
+            .and_then(|link| link.metadata.get("boundary"))
+            .map(String::as_str)
+            .unwrap_or("none");
+
+        println!("  {}. {} {}", rank + 1, function.green(), format!("({:.0}%)", chain.confidence * 100.0).dimmed());
+        println!("     string 0x{:x} -> load 0x{:x} -> entry via {}",
+            chain.start.as_u64(),
+            chain.links[0].target.as_u64(),
+            boundary
+        );
+    }
+
+    println!();
+    println!("{} {} functions reference \"{}\"", "[+]".green(), chains.len(), string);
+    println!();
+
+    Ok(())
+}
+
+fn run_strings(cli: &Cli, binary: PathBuf, min_len: usize, (narrow, utf16): (bool, bool)) -> Result<(), CliError> {
+    println!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let regions = reader.get_regions()
+        .map_err(CliError::memory("get memory regions"))?;
+
+    let config = StringAnalyzerConfig::default()
+        .with_min_length(min_len)
+        .with_min_relevance(0.0)
+        .with_encodings(narrow, utf16);
+
+    let strings = StringAnalyzer::with_config(reader, config)
+        .find_strings(&regions)
+        .map_err(CliError::memory("scan strings"))?;
+
+    println!();
+
+    for string in &strings {
+        println!("  {}  {:<8} {}",
+            format!("0x{:x}", string.address.as_u64()).yellow(),
+            string.encoding.name().dimmed(),
+            string.content.escape_debug()
+        );
+    }
+
+    println!();
+    println!("{} {} strings (min length {})", "[+]".green(), strings.len(), min_len);
+    println!();
+
+    Ok(())
+}
+
+fn run_symbols(
+    binary: PathBuf,
+    filter: Option<String>,
+    export: Option<ExportFormat>,
+    output: Option<PathBuf>,
+    page: usize,
+    page_size: usize,
+) -> Result<(), CliError> {
+    if page == 0 || page_size == 0 {
+        return Err(CliError::invalid("--page and --page-size must be at least 1"));
+    }
+
+    eprintln!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+    let image_base = reader.get_base_address().as_u64();
+    let resolver = SymbolResolver::new(reader);
+    if resolver.symbol_count() == 0 {
+        eprintln!("{} No symbol table (stripped binary?)", "[!]".yellow());
+        return Ok(());
+    }
+
+    let mut symbols: Vec<&Symbol> = resolver.iter()
+        .filter(|symbol| match &filter {
+            Some(needle) => symbol.name.contains(needle.as_str()) || symbol.display_name().contains(needle.as_str()),
+            None => true,
+        })
+        .collect();
+    symbols.sort_by(|a, b| a.address.cmp(&b.address).then_with(|| a.name.cmp(&b.name)));
+
+    if let Some(format) = export {
+        let owned: Vec<Symbol> = symbols.iter().map(|&symbol| symbol.clone()).collect();
+        let exporter = SymbolExporter::from_symbols(&owned).with_image_base(image_base);
+        match output {
+            Some(path) => {
+                exporter.export_to_file(format, &path.to_string_lossy())
+                    .map_err(CliError::io(format!("write {}", path.display())))?;
+                eprintln!("{} {} symbols written to {}", "[+]".green(), owned.len(), path.display());
+            }
+            None => print!("{}", exporter.export(format)),
+        }
+        return Ok(());
+    }
+
+    let pages = symbols.len().div_ceil(page_size).max(1);
+    if page > pages {
+        return Err(CliError::validation(format!("--page {} is past the last page ({})", page, pages)));
+    }
+
+    let rows: Vec<Vec<String>> = symbols.iter()
+        .skip((page - 1) * page_size)
+        .take(page_size)
+        .map(|symbol| vec![
+            format!("0x{:x}", symbol.address.as_u64()),
+            symbol.symbol_type.name().to_string(),
+            symbol.size.map(|size| format!("0x{:x}", size)).unwrap_or_default(),
+            symbol.display_name().to_string(),
+        ])
+        .collect();
+
+    if !rows.is_empty() {
+        let table = TableBuilder::new()
+            .with_headers(&["Address", "Type", "Size", "Name"])
+            .with_rows(&rows)
+            .with_alignment(2, Alignment::Right);
+        println!("{}", render_table(table));
+    }
+
+    println!("{} {} of {} symbols, page {} of {}",
+        "[+]".green(), symbols.len(), resolver.symbol_count(), page, pages);
+
+    Ok(())
+}
+
+fn run_string_xrefs(binary: PathBuf) -> Result<(), CliError> {
+    println!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let section = binary_mem.cstring_section()
+        .ok_or_else(|| CliError::general("No string section (__cstring or .rodata) in binary"))?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let regions = reader.get_regions()
+        .map_err(CliError::memory("get memory regions"))?;
+    let exec_regions = executable_regions(&regions)?;
+
+    let strings = Address::new(section.addr)..Address::new(section.addr + section.size);
+    println!("{} Strings: 0x{:x} - 0x{:x}", "[*]".blue(), strings.start.as_u64(), strings.end.as_u64());
+
+    let scanner = StringXrefScanner::new(reader);
+    let mut xrefs: Vec<StringXref> = exec_regions.iter()
+        .flat_map(|region| scanner.scan(region.start()..region.end(), strings.clone()))
+        .collect();
+    xrefs.sort_by_key(|x| (x.code, x.string));
+    xrefs.dedup_by_key(|x| (x.code, x.string));
+
+    println!();
+
+    for xref in &xrefs {
+        println!("  {} -> \"{}\"", format!("0x{:x}", xref.code.as_u64()).yellow(), xref.value.escape_debug());
+    }
+
+    let unique: HashSet<Address> = xrefs.iter().map(|x| x.string).collect();
+    println!();
+    println!("{} {} string loads referencing {} strings", "[+]".green(), xrefs.len(), unique.len());
+    println!();
+
+    Ok(())
+}
+
+fn run_entropy(binary: PathBuf, window: usize, threshold: f64, top: usize) -> Result<(), CliError> {
+    if window == 0 {
+        return Err(CliError::invalid("--window must be at least 1"));
+    }
+    if !(0.0..=8.0).contains(&threshold) {
+        return Err(CliError::invalid("--threshold must be between 0 and 8 bits per byte"));
+    }
+
+    println!("{} Loading binary...", "[*]".blue());
+
+    let binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+
+    let regions = reader.get_regions()
+        .map_err(CliError::memory("get memory regions"))?;
+    let scanner = EntropyScanner::new(reader).with_window(window);
+
+    println!();
+    println!("{}", "═".repeat(55).cyan());
+    println!("{}", "  ENTROPY BY REGION".bold());
+    println!("{}", "═".repeat(55).cyan());
+
+    let mut windows: Vec<(&str, EntropyWindow)> = Vec::new();
+    let mut spans: Vec<(&str, EntropySpan)> = Vec::new();
+
+    for region in &regions {
+        let found = scanner.scan(region.start()..region.end());
+        if found.is_empty() {
+            continue;
+        }
+
+        let mean = found.iter().map(|w| w.entropy).sum::<f64>() / found.len() as f64;
+        let peak = found.iter().map(|w| w.entropy).fold(0.0, f64::max);
+        println!("  {:<20} 0x{:x} - 0x{:x}  mean {:.2}  max {:.2}",
+            region.name(), region.start().as_u64(), region.end().as_u64(), mean, peak);
+
+        spans.extend(high_entropy_spans(&found, threshold).into_iter().map(|s| (region.name(), s)));
+        windows.extend(found.into_iter().map(|w| (region.name(), w)));
+    }
 
-    let spinner = create_spinner("Loading binary...", cli.no_progress);
+    windows.sort_by(|a, b| b.1.entropy.total_cmp(&a.1.entropy).then(a.1.start.cmp(&b.1.start)));
 
-    let binary_mem = BinaryMemory::load(&binary)
-        .map_err(|e| format!("Failed to load binary: {}", e))?;
-    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+    println!();
+    println!("{} Top {} of {} windows ({} bytes each):", "[*]".blue(), top.min(windows.len()), windows.len(), window);
+    for (name, w) in windows.iter().take(top) {
+        let entropy = format!("{:.3}", w.entropy);
+        let entropy = if w.entropy >= threshold { entropy.red() } else { entropy.normal() };
+        println!("  0x{:x} - 0x{:x}  {}  {}", w.start.as_u64(), w.end.as_u64(), entropy, name.dimmed());
+    }
 
-    if let Some(ref pb) = spinner {
-        pb.finish_with_message("Binary loaded!");
+    println!();
+    if spans.is_empty() {
+        println!("{} No windows at or above {:.2} bits per byte", "[+]".green(), threshold);
+    } else {
+        println!("{} {} high-entropy spans (>= {:.2} bits per byte), likely compressed or encrypted:", "[!]".yellow(), spans.len(), threshold);
+        for (name, span) in &spans {
+            println!("  0x{:x} - 0x{:x}  {:>8} bytes  mean {:.3}  max {:.3}  {}",
+                span.start.as_u64(), span.end.as_u64(), span.size(), span.mean, span.peak, name.dimmed());
+        }
     }
+    println!();
 
-    println!("{} Binary loaded: {}", "[+]".green(), binary.display());
+    Ok(())
+}
 
-    let regions = reader.get_regions()
-        .map_err(|e| format!("Failed to get memory regions: {}", e))?;
+fn run_patch(binary: PathBuf, address: String, bytes: String, output: PathBuf) -> Result<(), CliError> {
+    let addr = parse_address(&address)?;
+    let patch = parse_patch_bytes(&bytes)
+        .map_err(CliError::parse("--bytes"))?;
 
-    // Find readable regions and collect their data
-    println!("{} Found {} memory regions", "[+]".green(), regions.len());
+    let same_file = match (binary.canonicalize(), output.canonicalize()) {
+        (Ok(input), Ok(out)) => input == out,
+        _ => false,
+    };
+    if same_file {
+        return Err(CliError::validation("Refusing to overwrite the input binary; choose a different --output"));
+    }
 
-    // Read binary file directly for string searching (more reliable)
-    println!("{} Reading binary data...", "[*]".blue());
-    let binary_data = std::fs::read(&binary)
-        .map_err(|e| format!("Failed to read binary file: {}", e))?;
-    
-    println!("{} Binary size: {} MB", "[+]".green(), binary_data.len() / 1024 / 1024);
-    println!("{} Scanning for FFlags...", "[*]".blue());
-    println!();
+    println!("{} Loading binary...", "[*]".blue());
 
-    let db = get_database();
-    let total_flags = db.count();
-    
-    let pb = create_progress_bar(total_flags as u64, "Searching for FFlags...", cli.no_progress);
+    let mut binary_mem = BinaryMemory::load(&binary)
+        .map_err(CliError::BinaryLoad)?;
 
-    let mut flags_to_check: Vec<_> = db.all_flags().collect();
+    let original = binary_mem.read_bytes(Address::new(addr), patch.len())
+        .map_err(|e| CliError::validation(format!("Cannot patch 0x{:x}: {}", addr, e)))?;
 
-    if let Some(ref cat) = category {
-        flags_to_check.retain(|f| f.category.eq_ignore_ascii_case(cat));
-        println!("{} Filtering by category: {}", "[*]".blue(), cat.yellow());
+    // Protection describes the mapped process image; the file copy takes the write either way
+    if let Some(protection) = binary_mem.protection_at(Address::new(addr)) {
+        if !protection.can_write() {
+            println!("{} 0x{:x} is mapped {} in a process; patching the file copy anyway", "[*]".blue(), addr, protection);
+        }
     }
 
-    if let Some(ref s) = search {
-        let search_lower = s.to_lowercase();
-        flags_to_check.retain(|f| f.name.to_lowercase().contains(&search_lower));
-        println!("{} Searching for: {}", "[*]".blue(), s.yellow());
-    }
+    binary_mem.write_bytes(Address::new(addr), &patch)
+        .map_err(|e| CliError::validation(format!("Failed to patch 0x{:x}: {}", addr, e)))?;
+    binary_mem.save(&output)
+        .map_err(CliError::memory("save patched binary"))?;
 
-    println!("{} Checking {} flags...", "[*]".blue(), flags_to_check.len());
+    println!();
+    println!("  {:<8} {}", "Address:", format!("0x{:x}", addr).yellow());
+    println!("  {:<8} {}", "Before:", hex_string_spaced(&original));
+    println!("  {:<8} {}", "After:", hex_string_spaced(&patch).green());
+    println!();
+    println!("{} Wrote {} bytes to {}", "[+]".green(), patch.len(), output.display());
     println!();
 
-    let mut found_flags: Vec<&KnownFlag> = Vec::new();
-    let mut not_found_flags: Vec<&KnownFlag> = Vec::new();
+    Ok(())
+}
 
-    // Use the raw binary data for string searching
-    let data = &binary_data;
+/// Hex byte string such as "90 90 90 90" or "1f2003d5"
+fn parse_patch_bytes(bytes: &str) -> Result<Vec<u8>, String> {
+    let digits: String = bytes.split_whitespace().collect();
 
-    for (i, flag) in flags_to_check.iter().enumerate() {
-        if let Some(ref p) = pb {
-            p.set_position(i as u64);
-            if i % 500 == 0 {
-                p.set_message(format!("Checking {}...", flag.name));
-            }
-        }
+    if digits.is_empty() {
+        return Err("no bytes given".to_string());
+    }
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(format!("'{}' is not a hex digit", c));
+    }
+    if digits.len() % 2 != 0 {
+        return Err("odd number of hex digits".to_string());
+    }
 
-        let flag_bytes = flag.name.as_bytes();
-        let found = data.windows(flag_bytes.len()).any(|w| w == flag_bytes);
+    parse_hex(&digits).ok_or_else(|| "malformed hex".to_string())
+}
 
-        if found {
-            found_flags.push(*flag);
-        } else {
-            not_found_flags.push(*flag);
+/// Memory of the running process `pid`, or else of `binary`
+fn open_reader(binary: Option<PathBuf>, pid: Option<i32>) -> Result<Arc<dyn MemoryReader>, CliError> {
+    match (binary, pid) {
+        (_, Some(pid)) => {
+            println!("{} Attaching to process {}...", "[*]".blue(), pid);
+            attach_process(pid)
         }
+        (Some(binary), None) => {
+            println!("{} Loading binary...", "[*]".blue());
+            Ok(Arc::new(BinaryMemory::load(&binary).map_err(CliError::BinaryLoad)?))
+        }
+        (None, None) => Err(CliError::invalid("Either --binary or --pid is required")),
     }
+}
 
-    if let Some(ref p) = pb {
-        p.finish_with_message("Scan complete!");
-    }
+#[cfg(target_os = "macos")]
+fn attach_process(pid: i32) -> Result<Arc<dyn MemoryReader>, CliError> {
+    let process = roblox_offset_generator::memory::ProcessMemory::attach(pid)
+        .map_err(CliError::io("attach"))?;
+    Ok(Arc::new(process))
+}
 
-    let output_flags: &Vec<&KnownFlag> = if found_only {
-        &found_flags
-    } else {
-        &flags_to_check
-    };
+#[cfg(not(target_os = "macos"))]
+fn attach_process(pid: i32) -> Result<Arc<dyn MemoryReader>, CliError> {
+    Err(CliError::failed(FailureKind::Io, format!("Cannot attach to process {}: --pid is only supported on macOS", pid)))
+}
 
-    let json_output = serde_json::json!({
-        "total_checked": flags_to_check.len(),
-        "found_in_binary": found_flags.len(),
-        "not_found": not_found_flags.len(),
-        "flags": output_flags.iter().map(|f| {
-            serde_json::json!({
-                "name": f.name,
-                "type": format!("{:?}", f.flag_type),
-                "category": f.category,
-                "found_in_binary": found_flags.iter().any(|ff| ff.name == f.name),
-            })
-        }).collect::<Vec<_>>()
-    });
+fn run_struct_walk(cli: &Cli, offsets: PathBuf, binary: Option<PathBuf>, pid: Option<i32>, base: String, path: String) -> Result<(), CliError> {
+    let base = parse_address(&base)?;
 
-    let json_str = serde_json::to_string_pretty(&json_output)
-        .map_err(|e| format!("Failed to serialize: {}", e))?;
-    
-    std::fs::write(&output, &json_str)
-        .map_err(|e| format!("Failed to write output: {}", e))?;
+    let content = std::fs::read_to_string(&offsets)
+        .map_err(CliError::io("read offsets file"))?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(CliError::parse("offsets file"))?;
+    let layouts = OffsetOutput::from_scan_json("", &json).structure_offsets;
 
-    println!("{} Results saved to: {}", "[+]".green(), output.display());
+    let reader = open_reader(binary, pid)?;
 
-    if let Some(text_path) = text {
-        let mut text_content = String::new();
-        writeln!(text_content, "FFlag Report").unwrap();
-        writeln!(text_content, "============").unwrap();
-        writeln!(text_content).unwrap();
-        writeln!(text_content, "Total checked: {}", flags_to_check.len()).unwrap();
-        writeln!(text_content, "Found in binary: {}", found_flags.len()).unwrap();
-        writeln!(text_content).unwrap();
-        
-        if !found_flags.is_empty() {
-            writeln!(text_content, "Found Flags:").unwrap();
-            writeln!(text_content, "------------").unwrap();
-            for flag in &found_flags {
-                writeln!(text_content, "  {} [{}]", flag.name, flag.category).unwrap();
-            }
+    // Scan output carries offsets only, so every field is treated as a pointer
+    let mut walker = PathWalker::new(reader);
+    for (name, structure) in &layouts {
+        let mut layout = StructureLayout::new(name.clone());
+        for (field, offset) in &structure.fields {
+            layout.add_field(Field::new(field.clone(), Offset::new(offset.offset as u64), TypeInfo::Primitive(PrimitiveType::Ptr)));
         }
-        
-        std::fs::write(&text_path, text_content)
-            .map_err(|e| format!("Failed to write text output: {}", e))?;
-        println!("{} Text report saved to: {}", "[+]".green(), text_path.display());
+        walker = walker.with_layout(layout);
     }
 
+    let steps = walker.walk(Address::new(base), &path)
+        .map_err(|source| CliError::Walk { path: path.clone(), source })?;
+
     println!();
     println!("{}", "═".repeat(55).cyan());
-    println!("{}", "             FFLAG SCAN COMPLETE".cyan().bold());
+    println!("{}", "             STRUCTURE WALK".cyan().bold());
     println!("{}", "═".repeat(55).cyan());
     println!();
-    println!("  {} Total flags checked:    {}", "•".cyan(), flags_to_check.len().to_string().white());
-    println!("  {} Found in binary:        {}", "•".cyan(), found_flags.len().to_string().green().bold());
-    println!("  {} Not found:              {}", "•".cyan(), not_found_flags.len().to_string().yellow());
-    println!();
-    println!("  {} Time elapsed:           {:.2}s", "⏱".bright_black(), start_time.elapsed().as_secs_f64());
-    println!();
 
-    if !found_flags.is_empty() && cli.verbose {
-        println!("{}", "Found Flags (first 20):".yellow().bold());
-        for flag in found_flags.iter().take(20) {
-            println!("  {} {} [{}]", "✓".green(), flag.name.cyan(), flag.category.bright_black());
-        }
-        if found_flags.len() > 20 {
-            println!("  ... and {} more", found_flags.len() - 20);
-        }
+    let root = path.split('.').next().unwrap_or_default();
+    println!("  {} @ 0x{:x}", root.white().bold(), base);
+
+    for step in &steps {
+        println!("  {:<30} [0x{:x}] -> {}",
+            format!("{}.{} (+0x{:x})", step.structure, step.field, step.offset),
+            step.field_address.as_u64(),
+            format!("0x{:x}", step.value.as_u64()).yellow()
+        );
+    }
+
+    if let Some(last) = steps.last() {
         println!();
+        println!("{} {} = {}", "[+]".green(), path, format!("0x{:x}", last.value.as_u64()).yellow());
     }
+    println!();
 
     Ok(())
 }
 
-// ==================== OTHER COMMANDS ====================
-
-fn run_diff(cli: &Cli, old: PathBuf, new: PathBuf, output: Option<PathBuf>) -> Result<(), String> {
-    println!("{} Comparing offset files...", "[*]".blue());
-    println!("  Old: {}", old.display());
-    println!("  New: {}", new.display());
-    println!();
-
-    if !old.exists() {
-        return Err(format!("Old file not found: {}", old.display()));
-    }
-    if !new.exists() {
-        return Err(format!("New file not found: {}", new.display()));
-    }
+fn run_upvalues(binary: Option<PathBuf>, pid: Option<i32>, closures: Vec<String>) -> Result<(), CliError> {
+    let closures = closures.iter()
+        .map(|c| parse_address(c).map(Address::new))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let old_content = std::fs::read_to_string(&old)
-        .map_err(|e| format!("Failed to read old file: {}", e))?;
-    let new_content = std::fs::read_to_string(&new)
-        .map_err(|e| format!("Failed to read new file: {}", e))?;
+    let reader = open_reader(binary, pid)?;
+    let analyzer = UpvalueAnalyzer::new(reader);
 
+    println!();
     println!("{}", "═".repeat(55).cyan());
-    println!("{}", "               DIFF RESULTS".cyan().bold());
+    println!("{}", "             CLOSURE UPVALUES".cyan().bold());
     println!("{}", "═".repeat(55).cyan());
-    println!();
-    println!("  {} Comparison complete", "[+]".green());
-    println!();
 
-    Ok(())
-}
+    let mut refs = UpvalueRefMap::new();
 
-fn run_validate(cli: &Cli, offsets: PathBuf, binary: PathBuf) -> Result<(), String> {
-    println!("{} Validating offsets...", "[*]".blue());
-    println!("  Offsets: {}", offsets.display());
-    println!("  Binary: {}", binary.display());
-    println!();
+    for &closure in &closures {
+        let upvalues = analyzer.analyze_closure_upvalues(closure)
+            .map_err(|e| CliError::validation(format!("Cannot read closure 0x{:x}: {}", closure.as_u64(), e)))?;
 
-    if !offsets.exists() {
-        return Err(format!("Offsets file not found: {}", offsets.display()));
+        println!();
+        println!("  {} @ 0x{:x}", "Closure".white().bold(), closure.as_u64());
+
+        if upvalues.is_empty() {
+            println!("    {}", "no upvalues".dimmed());
+            continue;
+        }
+
+        for upvalue in &upvalues {
+            refs.add_reference(upvalue.address, closure, upvalue.index);
+
+            // An open upvalue still points at its stack slot; a closed one holds its own copy
+            let location = match upvalue.state {
+                UpvalueState::Open => "stack",
+                UpvalueState::Closed => "value",
+            };
+            println!("    {:<10} 0x{:<14x} {:<8} {} @ 0x{:x}",
+                format!("upval[{}]", upvalue.index),
+                upvalue.address.as_u64(),
+                upvalue.state.name(),
+                location,
+                upvalue.value_location.as_u64()
+            );
+        }
     }
-    if !binary.exists() {
-        return Err(format!("Binary not found: {}", binary.display()));
+
+    let shared = refs.shared_upvalues();
+    if !shared.is_empty() {
+        println!();
+        println!("{}", "  Shared upvalues".white().bold());
+        for upvalue in &shared {
+            let captured_by: Vec<String> = refs.get_references(*upvalue)
+                .into_iter()
+                .flatten()
+                .map(|(closure, index)| format!("0x{:x}[{}]", closure.as_u64(), index))
+                .collect();
+            println!("    0x{:<14x} {}", upvalue.as_u64(), captured_by.join(", "));
+        }
     }
 
-    println!("{}", "═".repeat(55).cyan());
-    println!("{}", "           VALIDATION RESULTS".cyan().bold());
-    println!("{}", "═".repeat(55).cyan());
     println!();
-    println!("  {} Validation complete", "[+]".green());
+    println!("{} {} upvalue(s) across {} closure(s), {} shared",
+        "[+]".green(), refs.unique_upvalue_count(), closures.len(), shared.len());
     println!();
 
     Ok(())
 }
 
-fn run_dump(cli: &Cli, binary: PathBuf, address: String, size: usize, disasm: bool) -> Result<(), String> {
-    let addr = if address.starts_with("0x") || address.starts_with("0X") {
-        u64::from_str_radix(&address[2..], 16)
-            .map_err(|_| "Invalid hex address")?
-    } else {
-        address.parse::<u64>()
-            .map_err(|_| "Invalid address")?
-    };
-
-    println!("{} Loading binary...", "[*]".blue());
-
-    let binary_mem = BinaryMemory::load(&binary)
-        .map_err(|e| format!("Failed to load binary: {}", e))?;
-    let reader: Arc<dyn MemoryReader> = Arc::new(binary_mem);
+/// One JSON response line per request line until `shutdown` or end of input
+fn run_serve() -> Result<(), CliError> {
+    let mut session = Session::new();
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout().lock();
 
-    println!("{} Dumping {} bytes at {}", "[*]".blue(), size, format!("0x{:x}", addr).yellow());
-    println!();
+    for line in stdin.lock().lines() {
+        let line = line.map_err(CliError::io("read request"))?;
+        if line.trim().is_empty() {
+            continue;
+        }
 
-    let data = reader.read_bytes(Address::new(addr), size)
-        .map_err(|e| format!("Failed to read memory: {}", e))?;
+        let response = serde_json::to_string(&session.handle_line(&line))
+            .map_err(CliError::serialize("response"))?;
+        writeln!(stdout, "{}", response)
+            .and_then(|()| stdout.flush())
+            .map_err(CliError::io("write response"))?;
 
-    if disasm {
-        println!("{}", "Disassembly:".yellow().bold());
-        for (i, chunk) in data.chunks(4).enumerate() {
-            let offset = i * 4;
-            print!("{:08x}:  ", addr + offset as u64);
-            for b in chunk {
-                print!("{:02x} ", b);
-            }
-            println!();
-        }
-    } else {
-        println!("{}", "Hex Dump:".yellow().bold());
-        for (i, chunk) in data.chunks(16).enumerate() {
-            let offset = i * 16;
-            print!("{:08x}:  ", addr + offset as u64);
-            
-            for (j, b) in chunk.iter().enumerate() {
-                print!("{:02x} ", b);
-                if j == 7 { print!(" "); }
-            }
-            
-            for _ in chunk.len()..16 {
-                print!("   ");
-            }
-            
-            print!(" |");
-            
-            for b in chunk {
-                if *b >= 0x20 && *b <= 0x7e {
-                    print!("{}", *b as char);
-                } else {
-                    print!(".");
-                }
-            }
-            
-            println!("|");
+        if session.is_finished() {
+            break;
         }
     }
-    println!();
 
     Ok(())
 }
 
-fn run_stats(cli: &Cli, input: PathBuf) -> Result<(), String> {
+fn run_stats(cli: &Cli, input: PathBuf, from_msgpack: bool) -> Result<(), CliError> {
     println!("{} Loading offsets file...", "[*]".blue());
 
     if !input.exists() {
-        return Err(format!("File not found: {}", input.display()));
+        return Err(CliError::not_found(format!("File not found: {}", input.display())));
     }
 
-    let content = std::fs::read_to_string(&input)
-        .map_err(|e| format!("Failed to read file: {}", e))?;
-    
-    let json: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    let json = read_offsets_file(&input, from_msgpack, "file")?;
 
     println!();
     println!("{}", "═".repeat(55).cyan());
@@ -1067,13 +4294,173 @@ fn run_stats(cli: &Cli, input: PathBuf) -> Result<(), String> {
         }
     }
 
+    // Anything that isn't an offsets file only gets the listing above
+    if check_offsets_json(&json, "file").is_err() {
+        println!();
+        return Ok(());
+    }
+
+    let mut collector = StatisticsCollector::new();
+    collector.from_output(&OffsetOutput::from_scan_json("", &json));
+
+    println!();
+    print_confidence_histogram("Function confidence", &collector.confidence_buckets(), cli.no_color);
+    print_confidence_average(collector.mean("function_confidence"));
+    println!();
+    print_confidence_histogram("Structure offset confidence", &collector.structure_confidence_buckets(), cli.no_color);
+    print_confidence_average(collector.mean("structure_confidence"));
+    println!();
+    println!("  Distinct discovery methods: {}", collector.get_counter("discovery_methods").to_string().green());
     println!();
 
     Ok(())
 }
 
+/// Confidences by bucket: bars on a terminal, plain counts otherwise
+fn print_confidence_histogram(title: &str, buckets: &[ConfidenceBucket], no_color: bool) {
+    println!("  {}:", title);
+
+    if Terminal::new().is_tty() {
+        let rows: Vec<(String, usize)> = buckets.iter().map(|b| (b.label(), b.count)).collect();
+        println!("{}", DisplayRenderer::new().with_color(!no_color).render_histogram(&rows, 30));
+    } else {
+        for bucket in buckets {
+            println!("    {}: {}", bucket.label(), bucket.count);
+        }
+    }
+}
+
+fn print_confidence_average(mean: Option<f64>) {
+    match mean {
+        Some(mean) => println!("    Average: {}", format!("{:.1}%", mean * 100.0).green()),
+        None => println!("    Average: {}", "n/a".dimmed()),
+    }
+}
+
 // ==================== HELPERS ====================
 
+/// The binary's symbol table, or none with a warning when it can't be read
+fn load_symbols(reader: &Arc<dyn MemoryReader>) -> SymbolResolver {
+    SymbolResolver::load(reader.clone()).unwrap_or_else(|e| {
+        println!("{} Ignoring unreadable symbol table: {}", "[!]".yellow(), e);
+        SymbolResolver::empty(reader.clone())
+    })
+}
+
+/// `name` or `name+0x..` for the symbol at or below `addr`
+fn symbol_name(symbols: &SymbolResolver, addr: Address) -> Option<String> {
+    symbols.resolve_nearest(addr).map(|(symbol, offset)| match offset {
+        0 => symbol.display_name().to_string(),
+        _ => format!("{}+0x{:x}", symbol.display_name(), offset),
+    })
+}
+
+/// `0x..` followed by the symbol name in parentheses when one is known
+fn describe_address(symbols: &SymbolResolver, addr: Address) -> String {
+    match symbol_name(symbols, addr) {
+        Some(name) => format!("0x{:x} ({})", addr.as_u64(), name),
+        None => format!("0x{:x}", addr.as_u64()),
+    }
+}
+
+fn parse_address(address: &str) -> Result<u64, CliError> {
+    let parsed = match address.strip_prefix("0x").or_else(|| address.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => address.parse::<u64>(),
+    };
+    parsed.map_err(|_| CliError::InvalidAddress(address.to_string()))
+}
+
+fn parse_regex(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| e.to_string())
+}
+
+/// `parse_regex` anchored at both ends, so `Print` doesn't also keep `PrintLn`
+fn parse_name_filter(pattern: &str) -> Result<Regex, String> {
+    string::full_match_regex(pattern).map_err(|e| e.to_string())
+}
+
+fn parse_since(date: &str) -> Result<u64, String> {
+    time::parse_timestamp(date)
+        .ok_or_else(|| format!("invalid date '{}' (expected YYYY-MM-DD or YYYY-MM-DDTHH:MM:SSZ)", date))
+}
+
+fn parse_scan_format(format: &str) -> Result<ScanFormat, String> {
+    match format.to_lowercase().as_str() {
+        "json" => Ok(ScanFormat::Json),
+        "luau" | "lua" => Ok(ScanFormat::Luau),
+        "msgpack" | "mpk" => Ok(ScanFormat::Msgpack),
+        "csv" => Ok(ScanFormat::Csv),
+        "yaml" | "yml" => Ok(ScanFormat::Yaml),
+        _ => Err(format!("unknown format '{}' (expected json, luau, msgpack, csv or yaml)", format)),
+    }
+}
+
+fn parse_min_len(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        Ok(len) => Ok(len),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// (narrow, utf16) scan switches for `strings --encoding`
+fn parse_string_encoding(encoding: &str) -> Result<(bool, bool), String> {
+    match encoding.to_lowercase().as_str() {
+        "ascii" | "utf8" => Ok((true, false)),
+        "utf16" | "utf16le" => Ok((false, true)),
+        "all" => Ok((true, true)),
+        _ => Err(format!("unknown encoding '{}' (expected ascii, utf16 or all)", encoding)),
+    }
+}
+
+fn parse_graph_format(format: &str) -> Result<GraphFormat, String> {
+    match format.to_lowercase().as_str() {
+        "dot" => Ok(GraphFormat::Dot),
+        "json" => Ok(GraphFormat::Json),
+        "d3" => Ok(GraphFormat::D3Json),
+        "graphml" => Ok(GraphFormat::GraphML),
+        "csv" => Ok(GraphFormat::Csv),
+        "mermaid" => Ok(GraphFormat::Mermaid),
+        _ => Err(format!("unknown format '{}' (expected dot, json, d3, graphml, csv or mermaid)", format)),
+    }
+}
+
+fn parse_symbol_format(format: &str) -> Result<ExportFormat, String> {
+    match format.to_lowercase().as_str() {
+        "ida" => Ok(ExportFormat::Ida),
+        "ghidra" => Ok(ExportFormat::Ghidra),
+        "ghidra-symbols" => Ok(ExportFormat::GhidraSymbols),
+        "ghidra-csv" => Ok(ExportFormat::GhidraCsv),
+        "binja" | "binaryninja" => Ok(ExportFormat::BinaryNinja),
+        "json" => Ok(ExportFormat::Json),
+        "csv" => Ok(ExportFormat::Csv),
+        "header" | "h" => Ok(ExportFormat::CHeader),
+        "lldb" => Ok(ExportFormat::Lldb),
+        "gdb" => Ok(ExportFormat::Gdb),
+        "map" => Ok(ExportFormat::SymbolMap),
+        _ => Err(format!("unknown format '{}' (expected ida, ghidra, ghidra-symbols, ghidra-csv, binja, json, csv, header, lldb, gdb or map)", format)),
+    }
+}
+
+fn parse_base(base: Option<&str>) -> Result<Option<u64>, CliError> {
+    base.map(|b| parse_address(b).map_err(CliError::parse("--base")))
+        .transpose()
+}
+
+/// Effective base of an offsets file written by `scan` (0 when relative).
+/// Files without a `target` section are assumed to use the default image base.
+fn read_target_base(json: &serde_json::Value) -> u64 {
+    let target = &json["target"];
+    if target["relative"].as_bool().unwrap_or(false) {
+        return 0;
+    }
+    target["base_address"]
+        .as_str()
+        .and_then(|b| parse_address(b).ok())
+        .unwrap_or(0x100000000)
+}
+
 fn calculate_scan_range(regions: &[roblox_offset_generator::memory::MemoryRegion]) -> (Address, Address) {
     let mut min_addr = u64::MAX;
     let mut max_addr = 0u64;
@@ -1093,15 +4480,78 @@ fn calculate_scan_range(regions: &[roblox_offset_generator::memory::MemoryRegion
     (Address::new(min_addr), Address::new(max_addr))
 }
 
-fn save_scan_results(results: &CombinedResults, path: &PathBuf) -> Result<(), String> {
-    let json_map = results.to_json_map();
-    let json_string = serde_json::to_string_pretty(&json_map)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+/// Extensions `read_offsets_file` decodes as MessagePack
+const MSGPACK_EXTENSIONS: &[&str] = &["msgpack", "mpk"];
+
+/// An offsets file in the JSON layout `scan` writes. MessagePack files
+/// (by extension, or any file with `msgpack`) are decoded into an
+/// `OffsetOutput` and converted to that layout.
+fn read_offsets_file(path: &Path, msgpack: bool, what: &str) -> Result<serde_json::Value, CliError> {
+    let is_msgpack = msgpack || path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| MSGPACK_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+
+    if is_msgpack {
+        let bytes = std::fs::read(path)
+            .map_err(CliError::io(format!("read {}", what)))?;
+        let output = OffsetOutput::from_msgpack(&bytes)
+            .map_err(CliError::parse(format!("{} as MessagePack", what)))?;
+        return Ok(output.to_scan_json());
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(CliError::io(format!("read {}", what)))?;
+    serde_json::from_str(&content)
+        .map_err(CliError::parse(format!("{}", what)))
+}
+
+/// Rejects JSON that parsed but isn't scan output, which would otherwise
+/// diff as an empty file
+fn check_offsets_json(json: &serde_json::Value, what: &str) -> Result<(), CliError> {
+    let sections = ["functions", "structure_offsets", "classes"];
+    if sections.iter().any(|section| json[section].is_object()) {
+        Ok(())
+    } else {
+        Err(CliError::invalid(format!("Failed to parse {}: not an offsets file (no functions, structure_offsets or classes)", what)))
+    }
+}
+
+fn save_scan_results(results: &CombinedResults, finder_report: &[FinderReportEntry], base_address: u64, relative: bool, platform: &str, compact: bool, path: &PathBuf) -> Result<(), CliError> {
+    let mut json_map = results.to_json_map();
+    json_map.insert("schema_version".to_string(), serde_json::json!(SCAN_SCHEMA_VERSION));
+    json_map.insert("generated_at".to_string(), serde_json::json!(time::format_timestamp(time::generated_secs())));
+    json_map.insert("target".to_string(), serde_json::json!({
+        "base_address": format!("0x{:x}", base_address),
+        "relative": relative,
+        "platform": platform,
+    }));
+    json_map.insert("finder_report".to_string(), serde_json::json!(finder_report.iter().map(|entry| {
+        serde_json::json!({
+            "finder": entry.finder,
+            "offset": entry.offset,
+            "status": entry.status,
+            "address": entry.address.map(|a| format!("0x{:x}", a)),
+            "confidence": entry.confidence,
+            "discovery_method": entry.discovery_method,
+        })
+    }).collect::<Vec<_>>()));
+    json_map.insert("warnings".to_string(), serde_json::json!(OutputFinalizer::new().address_collisions(results).iter().map(|collision| {
+        serde_json::json!({
+            "kind": collision.kind.name(),
+            "address": format!("0x{:x}", collision.address),
+            "names": collision.names,
+        })
+    }).collect::<Vec<_>>()));
+    let json_string = if compact {
+        serde_json::to_string(&json_map)
+    } else {
+        serde_json::to_string_pretty(&json_map)
+    }.map_err(CliError::serialize("scan results"))?;
 
     let mut file = File::create(path)
-        .map_err(|e| format!("Failed to create file: {}", e))?;
+        .map_err(CliError::io(format!("create {}", path.display())))?;
     file.write_all(json_string.as_bytes())
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+        .map_err(CliError::io(format!("write {}", path.display())))?;
 
     Ok(())
 }
@@ -1129,7 +4579,7 @@ fn save_text_report(results: &CombinedResults, path: &PathBuf) -> Result<(), std
     Ok(())
 }
 
-fn save_markdown_report(results: &CombinedResults, path: &PathBuf) -> Result<(), std::io::Error> {
+fn save_markdown_report(results: &CombinedResults, finder_report: &[FinderReportEntry], path: &PathBuf) -> Result<(), std::io::Error> {
     let mut file = File::create(path)?;
 
     writeln!(file, "# Roblox Offset Report")?;
@@ -1151,6 +4601,21 @@ fn save_markdown_report(results: &CombinedResults, path: &PathBuf) -> Result<(),
         writeln!(file, "| {} | `0x{:016x}` | {:.0}% |",
             func.name, func.address.as_u64(), func.confidence * 100.0)?;
     }
+    writeln!(file)?;
+
+    writeln!(file, "## Finder Report")?;
+    writeln!(file)?;
+    writeln!(file, "| Finder | Offset | Status | Address | Confidence | Method |")?;
+    writeln!(file, "|--------|--------|--------|---------|------------|--------|")?;
+    for entry in finder_report {
+        writeln!(file, "| {} | {} | {} | {} | {} | {} |",
+            entry.finder,
+            entry.offset,
+            entry.status.name(),
+            entry.address.map(|a| format!("`0x{:016x}`", a)).unwrap_or_else(|| "-".to_string()),
+            entry.confidence.map(|c| format!("{:.0}%", c * 100.0)).unwrap_or_else(|| "-".to_string()),
+            entry.discovery_method.as_deref().unwrap_or("-"))?;
+    }
 
     Ok(())
 }
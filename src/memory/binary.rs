@@ -1,16 +1,62 @@
 // Tue Jan 13 2026 - Alex
 
-use crate::memory::{Address, MemoryError, MemoryReader, MemoryRegion, MemoryRange, Protection};
+use crate::memory::{elf, fat, Address, MemoryError, MemoryReader, MemoryRegion, MemoryRange, MemoryWriter, Protection};
 use goblin::mach::Mach;
+use goblin::mach::header::{filetype_to_str, MH_BUNDLE, MH_DYLIB, MH_EXECUTE};
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Mach-O file types that map into memory the way an executable does;
+/// dylibs and bundles extracted from an app or the dyld cache included.
+const LOADABLE_MACHO_TYPES: &[u32] = &[MH_EXECUTE, MH_DYLIB, MH_BUNDLE];
+
+/// Where a Mach-O image without a `__TEXT` segment is assumed to load
+const DEFAULT_MACHO_BASE: u64 = 0x100000000;
+
+/// Container format, picked from the magic bytes on load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryFormat {
+    MachO,
+    Elf,
+}
+
+impl BinaryFormat {
+    pub fn detect(data: &[u8]) -> Self {
+        if elf::is_elf(data) {
+            Self::Elf
+        } else {
+            Self::MachO
+        }
+    }
+
+    /// Value reported as `TargetInfo.platform`.
+    pub fn platform(self) -> &'static str {
+        match self {
+            Self::MachO => "macos",
+            Self::Elf => "android",
+        }
+    }
+}
+
+/// Segments mapped back to back in VM and file, as one readable range
+#[derive(Debug, Clone, Copy)]
+struct MappedSpan {
+    vm_start: u64,
+    vm_end: u64,
+    /// File offset of `vm_start`
+    fileoff: u64,
+}
+
 pub struct BinaryMemory {
     data: Arc<Vec<u8>>,
+    format: BinaryFormat,
     base_address: Address,
     path: PathBuf,
+    segments: Vec<BinarySegment>,
+    /// File-backed spans of `segments`, sorted by VM address
+    spans: Vec<MappedSpan>,
     text_offset: u64,
     text_size: u64,
     data_offset: u64,
@@ -45,19 +91,60 @@ pub struct BinarySymbol {
 }
 
 impl BinaryMemory {
+    /// Loads `path`, taking the arm64 slice of a fat binary
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, MemoryError> {
+        Self::load_arch(path, fat::CPU_TYPE_ARM64)
+    }
+
+    /// Loads `path`, taking the `cpu_type` slice of a fat binary. Thin
+    /// images load as they are whatever their architecture.
+    pub fn load_arch<P: AsRef<Path>>(path: P, cpu_type: u32) -> Result<Self, MemoryError> {
         let path_buf = path.as_ref().to_path_buf();
         let mut file = File::open(path.as_ref()).map_err(MemoryError::Io)?;
         let mut data = Vec::new();
         file.read_to_end(&mut data).map_err(MemoryError::Io)?;
 
-        let (text_offset, text_size, data_offset, data_size) = Self::parse_segments(&data)?;
-        let base_address = Address::new(0x100000000);
+        Self::from_bytes_arch(data, path_buf, cpu_type)
+    }
+
+    /// Build from an in-memory Mach-O or ELF image; `path` is only kept for
+    /// display. Mach-O images are based at their `__TEXT` segment (so a dylib
+    /// linked at 0 is based at 0), ELF images at their lowest loaded address.
+    /// Fat binaries give up their arm64 slice.
+    pub fn from_bytes(data: Vec<u8>, path_buf: PathBuf) -> Result<Self, MemoryError> {
+        Self::from_bytes_arch(data, path_buf, fat::CPU_TYPE_ARM64)
+    }
+
+    /// `from_bytes`, taking the `cpu_type` slice of a fat binary. The slice
+    /// becomes the whole image, so `data()`, file offsets and `save` refer
+    /// to it rather than to the fat file.
+    pub fn from_bytes_arch(data: Vec<u8>, path_buf: PathBuf, cpu_type: u32) -> Result<Self, MemoryError> {
+        let data = fat::thin(data, cpu_type)?;
+        let format = BinaryFormat::detect(&data);
+
+        let (segments, base_address, (text_offset, text_size, data_offset, data_size)) = match format {
+            BinaryFormat::MachO => {
+                let segments = Self::parse_macho_segments(&data)?;
+                let base = segments.iter()
+                    .find(|s| s.name == "__TEXT")
+                    .map_or(DEFAULT_MACHO_BASE, |s| s.vmaddr);
+                (segments, Address::new(base), Self::parse_segments(&data)?)
+            }
+            BinaryFormat::Elf => {
+                let segments = elf::segments(&data)?;
+                let base = segments.iter().map(|s| s.vmaddr).min().unwrap_or(0);
+                let layout = elf::text_and_data(&segments);
+                (segments, Address::new(base), layout)
+            }
+        };
 
         Ok(Self {
             data: Arc::new(data),
+            format,
             base_address,
             path: path_buf,
+            spans: Self::mapped_spans(&segments),
+            segments,
             text_offset,
             text_size,
             data_offset,
@@ -99,43 +186,46 @@ impl BinaryMemory {
         Ok((text_offset, text_size, data_offset, data_size))
     }
 
+    /// One region per segment, from the load commands (Mach-O) or program
+    /// headers (ELF): true vmaddr/vmsize and the initial protection, so
+    /// `__TEXT` is the only executable region of a normal image and
+    /// `__PAGEZERO` is unreadable.
     pub fn enumerate_regions(&self) -> Result<Vec<MemoryRegion>, MemoryError> {
-        let mach = Mach::parse(&self.data)
-            .map_err(|e| MemoryError::BinaryParseError(format!("Failed to parse Mach-O: {}", e)))?;
-
-        let macho = match mach {
-            Mach::Binary(m) => m,
-            Mach::Fat(_) => {
-                return Err(MemoryError::BinaryParseError(
-                    "Fat binaries not supported".to_string(),
-                ))
-            }
-        };
-
-        let mut regions = Vec::new();
-        for segment in &macho.segments {
-            let segname = std::str::from_utf8(&segment.segname)
-                .unwrap_or("")
-                .trim_end_matches('\0');
-            let protection = if segname == "__TEXT" {
-                Protection::ReadExecute
-            } else if segname == "__DATA" {
-                Protection::ReadWrite
-            } else {
-                Protection::Read
-            };
-
-            let range =
-                MemoryRange::from_start_size(Address::new(segment.vmaddr), segment.vmsize);
-            let region = MemoryRegion::new(range, protection, segname.to_string());
-            regions.push(region);
-        }
-
-        Ok(regions)
+        Ok(self.segments.iter()
+            .filter(|seg| seg.vmsize > 0)
+            .map(|seg| MemoryRegion::new(
+                MemoryRange::from_start_size(Address::new(seg.vmaddr), seg.vmsize),
+                seg.protection,
+                seg.name.clone(),
+            ))
+            .collect())
+    }
+
+    /// One region per section, named `segment,section` (`__TEXT,__cstring`)
+    /// and protected like the segment holding it. Finer than `get_regions`,
+    /// for lookups that only belong in one section.
+    pub fn section_regions(&self) -> Result<Vec<MemoryRegion>, MemoryError> {
+        let sections = self.get_sections()?;
+        Ok(sections.iter()
+            .filter(|section| section.size > 0)
+            .map(|section| {
+                let protection = self.protection_at(Address::new(section.addr)).unwrap_or(Protection::Read);
+                MemoryRegion::new(
+                    MemoryRange::from_start_size(Address::new(section.addr), section.size),
+                    protection,
+                    format!("{},{}", section.segname, section.sectname),
+                )
+            })
+            .collect())
+    }
+
+    /// Parsed once on load for either format.
+    pub fn get_segments(&self) -> Result<Vec<BinarySegment>, MemoryError> {
+        Ok(self.segments.clone())
     }
 
-    pub fn get_segments(&self) -> Result<Vec<BinarySegment>, MemoryError> {
-        let mach = Mach::parse(&self.data)
+    fn parse_macho_segments(data: &[u8]) -> Result<Vec<BinarySegment>, MemoryError> {
+        let mach = Mach::parse(data)
             .map_err(|e| MemoryError::BinaryParseError(format!("Failed to parse Mach-O: {}", e)))?;
 
         let macho = match mach {
@@ -147,6 +237,13 @@ impl BinaryMemory {
             }
         };
 
+        if !LOADABLE_MACHO_TYPES.contains(&macho.header.filetype) {
+            return Err(MemoryError::BinaryParseError(format!(
+                "Unsupported Mach-O file type {} (expected an executable, dylib or bundle)",
+                filetype_to_str(macho.header.filetype),
+            )));
+        }
+
         let mut segments = Vec::new();
         for segment in &macho.segments {
             let segname = std::str::from_utf8(&segment.segname)
@@ -168,6 +265,10 @@ impl BinaryMemory {
     }
 
     pub fn get_sections(&self) -> Result<Vec<BinarySection>, MemoryError> {
+        if self.format == BinaryFormat::Elf {
+            return elf::sections(&self.data, &self.segments);
+        }
+
         let mach = Mach::parse(&self.data)
             .map_err(|e| MemoryError::BinaryParseError(format!("Failed to parse Mach-O: {}", e)))?;
 
@@ -206,6 +307,10 @@ impl BinaryMemory {
     }
 
     pub fn get_symbols(&self) -> Result<Vec<BinarySymbol>, MemoryError> {
+        if self.format == BinaryFormat::Elf {
+            return elf::symbols(&self.data);
+        }
+
         let mach = Mach::parse(&self.data)
             .map_err(|e| MemoryError::BinaryParseError(format!("Failed to parse Mach-O: {}", e)))?;
 
@@ -234,6 +339,10 @@ impl BinaryMemory {
     }
 
     pub fn get_exports(&self) -> Result<Vec<BinarySymbol>, MemoryError> {
+        if self.format == BinaryFormat::Elf {
+            return elf::exports(&self.data);
+        }
+
         let mach = Mach::parse(&self.data)
             .map_err(|e| MemoryError::BinaryParseError(format!("Failed to parse Mach-O: {}", e)))?;
 
@@ -262,6 +371,10 @@ impl BinaryMemory {
     }
 
     pub fn get_imports(&self) -> Result<Vec<String>, MemoryError> {
+        if self.format == BinaryFormat::Elf {
+            return elf::imports(&self.data);
+        }
+
         let mach = Mach::parse(&self.data)
             .map_err(|e| MemoryError::BinaryParseError(format!("Failed to parse Mach-O: {}", e)))?;
 
@@ -284,6 +397,10 @@ impl BinaryMemory {
         Ok(imports)
     }
 
+    pub fn format(&self) -> BinaryFormat {
+        self.format
+    }
+
     pub fn base_address(&self) -> Address {
         self.base_address
     }
@@ -300,10 +417,19 @@ impl BinaryMemory {
         self.data.len()
     }
 
+    /// The file in windows of at most `chunk_size` bytes, each starting
+    /// `overlap` bytes before the previous one ended. Anything up to
+    /// `overlap + 1` bytes long lies wholly inside at least one window.
+    pub fn chunks(&self, chunk_size: usize, overlap: usize) -> BinaryChunks<'_> {
+        BinaryChunks::new(&self.data, chunk_size, overlap)
+    }
+
+    /// `segname` is ignored for ELF, where sections are looked up by name.
     pub fn get_section_data(&self, segname: &str, sectname: &str) -> Option<Vec<u8>> {
         let sections = self.get_sections().ok()?;
         for section in sections {
-            if section.segname == segname && section.sectname == sectname {
+            let segment_matches = self.format == BinaryFormat::Elf || section.segname == segname;
+            if segment_matches && section.sectname == sectname {
                 let start = section.offset as usize;
                 let end = start + section.size as usize;
                 if end <= self.data.len() {
@@ -315,15 +441,49 @@ impl BinaryMemory {
     }
 
     pub fn get_text_section(&self) -> Option<Vec<u8>> {
-        self.get_section_data("__TEXT", "__text")
+        match self.format {
+            BinaryFormat::MachO => self.get_section_data("__TEXT", "__text"),
+            BinaryFormat::Elf => self.get_section_data("", ".text"),
+        }
     }
 
     pub fn get_data_section(&self) -> Option<Vec<u8>> {
-        self.get_section_data("__DATA", "__data")
+        match self.format {
+            BinaryFormat::MachO => self.get_section_data("__DATA", "__data"),
+            BinaryFormat::Elf => self.get_section_data("", ".data"),
+        }
     }
 
+    /// C strings: `__cstring` on Mach-O, `.rodata` on ELF.
     pub fn get_cstring_section(&self) -> Option<Vec<u8>> {
-        self.get_section_data("__TEXT", "__cstring")
+        match self.format {
+            BinaryFormat::MachO => self.get_section_data("__TEXT", "__cstring"),
+            BinaryFormat::Elf => self.get_section_data("", ".rodata"),
+        }
+    }
+
+    /// Header of the section `get_cstring_section` reads.
+    pub fn cstring_section(&self) -> Option<BinarySection> {
+        let (segname, sectname) = match self.format {
+            BinaryFormat::MachO => ("__TEXT", "__cstring"),
+            BinaryFormat::Elf => ("", ".rodata"),
+        };
+
+        self.get_sections().ok()?
+            .into_iter()
+            .find(|s| (self.format == BinaryFormat::Elf || s.segname == segname) && s.sectname == sectname)
+    }
+
+    /// Header of the section `get_text_section` reads.
+    pub fn text_section(&self) -> Option<BinarySection> {
+        let (segname, sectname) = match self.format {
+            BinaryFormat::MachO => ("__TEXT", "__text"),
+            BinaryFormat::Elf => ("", ".text"),
+        };
+
+        self.get_sections().ok()?
+            .into_iter()
+            .find(|s| (self.format == BinaryFormat::Elf || s.segname == segname) && s.sectname == sectname)
     }
 
     pub fn find_symbol(&self, name: &str) -> Option<u64> {
@@ -389,8 +549,7 @@ impl BinaryMemory {
     }
 
     pub fn file_offset_to_virtual(&self, offset: u64) -> Option<u64> {
-        let segments = self.get_segments().ok()?;
-        for seg in segments {
+        for seg in &self.segments {
             if offset >= seg.fileoff && offset < seg.fileoff + seg.filesize {
                 return Some(seg.vmaddr + (offset - seg.fileoff));
             }
@@ -399,8 +558,7 @@ impl BinaryMemory {
     }
 
     pub fn virtual_to_file_offset(&self, addr: u64) -> Option<u64> {
-        let segments = self.get_segments().ok()?;
-        for seg in segments {
+        for seg in &self.segments {
             if addr >= seg.vmaddr && addr < seg.vmaddr + seg.vmsize {
                 return Some(seg.fileoff + (addr - seg.vmaddr));
             }
@@ -408,6 +566,72 @@ impl BinaryMemory {
         None
     }
 
+    /// File-backed spans: runs of segments laid out right after each other
+    /// in both VM and file, so reads may cross between them. Zero-fill
+    /// tails (`vmsize > filesize`) are not backed.
+    fn mapped_spans(segments: &[BinarySegment]) -> Vec<MappedSpan> {
+        let mut backed: Vec<&BinarySegment> = segments.iter().filter(|s| s.filesize > 0).collect();
+        backed.sort_by_key(|s| s.vmaddr);
+
+        let mut spans: Vec<MappedSpan> = Vec::new();
+        for seg in backed {
+            let size = seg.filesize.min(seg.vmsize);
+            match spans.last_mut() {
+                Some(last) if seg.vmaddr == last.vm_end && seg.fileoff == last.fileoff + (last.vm_end - last.vm_start) => {
+                    last.vm_end += size;
+                }
+                _ => spans.push(MappedSpan { vm_start: seg.vmaddr, vm_end: seg.vmaddr + size, fileoff: seg.fileoff }),
+            }
+        }
+        spans
+    }
+
+    /// The file-backed span containing `addr`
+    fn mapped_span(&self, addr: u64) -> Option<MappedSpan> {
+        let index = self.spans.partition_point(|s| s.vm_start <= addr).checked_sub(1)?;
+        let span = self.spans[index];
+        (addr < span.vm_end).then_some(span)
+    }
+
+    /// File bytes backing `[addr, addr + len)`, which must lie in one
+    /// mapped span
+    fn file_range(&self, addr: Address, len: usize) -> Result<std::ops::Range<usize>, MemoryError> {
+        let virtual_addr = addr.as_u64();
+
+        let span = self.mapped_span(virtual_addr).ok_or_else(|| MemoryError::OutOfBoundsRead {
+            addr: virtual_addr,
+            len,
+            valid_start: self.spans.first().map_or(0, |s| s.vm_start),
+            valid_end: self.spans.iter().map(|s| s.vm_end).max().unwrap_or(0),
+        })?;
+
+        let out_of_bounds = || MemoryError::OutOfBoundsRead { addr: virtual_addr, len, valid_start: span.vm_start, valid_end: span.vm_end };
+        let end = virtual_addr.checked_add(len as u64).ok_or_else(out_of_bounds)?;
+        if end > span.vm_end {
+            return Err(out_of_bounds());
+        }
+
+        let offset = (span.fileoff + (virtual_addr - span.vm_start)) as usize;
+        if offset + len > self.data.len() {
+            return Err(out_of_bounds());
+        }
+
+        Ok(offset..offset + len)
+    }
+
+    /// Protection of the segment mapping `addr`, as a loader would apply it
+    pub fn protection_at(&self, addr: Address) -> Option<Protection> {
+        self.segments.iter()
+            .find(|s| addr.as_u64() >= s.vmaddr && addr.as_u64() - s.vmaddr < s.vmsize)
+            .map(|s| s.protection)
+    }
+
+    /// Write the (possibly patched) image to `path`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), MemoryError> {
+        std::fs::write(path, self.data.as_slice())?;
+        Ok(())
+    }
+
     pub fn read_at_offset(&self, offset: usize, len: usize) -> Option<&[u8]> {
         if offset + len <= self.data.len() {
             Some(&self.data[offset..offset + len])
@@ -417,6 +641,10 @@ impl BinaryMemory {
     }
 
     pub fn entry_point(&self) -> Result<u64, MemoryError> {
+        if self.format == BinaryFormat::Elf {
+            return elf::entry_point(&self.data);
+        }
+
         let mach = Mach::parse(&self.data)
             .map_err(|e| MemoryError::BinaryParseError(format!("Failed to parse Mach-O: {}", e)))?;
 
@@ -433,6 +661,10 @@ impl BinaryMemory {
     }
 
     pub fn is_64bit(&self) -> Result<bool, MemoryError> {
+        if self.format == BinaryFormat::Elf {
+            return elf::is_64bit(&self.data);
+        }
+
         let mach = Mach::parse(&self.data)
             .map_err(|e| MemoryError::BinaryParseError(format!("Failed to parse Mach-O: {}", e)))?;
 
@@ -449,6 +681,10 @@ impl BinaryMemory {
     }
 
     pub fn is_arm64(&self) -> Result<bool, MemoryError> {
+        if self.format == BinaryFormat::Elf {
+            return elf::is_arm64(&self.data);
+        }
+
         let mach = Mach::parse(&self.data)
             .map_err(|e| MemoryError::BinaryParseError(format!("Failed to parse Mach-O: {}", e)))?;
 
@@ -465,6 +701,10 @@ impl BinaryMemory {
     }
 
     pub fn libraries(&self) -> Result<Vec<String>, MemoryError> {
+        if self.format == BinaryFormat::Elf {
+            return elf::libraries(&self.data);
+        }
+
         let mach = Mach::parse(&self.data)
             .map_err(|e| MemoryError::BinaryParseError(format!("Failed to parse Mach-O: {}", e)))?;
 
@@ -482,17 +722,11 @@ impl BinaryMemory {
 }
 
 impl MemoryReader for BinaryMemory {
+    /// Reads never clamp: the whole `[addr, addr + len)` range has to be
+    /// file-backed, otherwise the error names the range that is.
     fn read_bytes(&self, addr: Address, len: usize) -> Result<Vec<u8>, MemoryError> {
-        let virtual_addr = addr.as_u64();
-        let file_offset = self
-            .virtual_to_file_offset(virtual_addr)
-            .ok_or_else(|| MemoryError::OutOfBounds(virtual_addr))?;
-
-        let offset = file_offset as usize;
-        if offset + len > self.data.len() {
-            return Err(MemoryError::OutOfBounds(virtual_addr));
-        }
-        Ok(self.data[offset..offset + len].to_vec())
+        let range = self.file_range(addr, len)?;
+        Ok(self.data[range].to_vec())
     }
 
     fn read_u8(&self, addr: Address) -> Result<u8, MemoryError> {
@@ -574,6 +808,69 @@ impl MemoryReader for BinaryMemory {
     }
 }
 
+/// Writes patch this in-memory copy of the file (cloned on first write if
+/// shared) and never touch the original until `save`. Segment protection is
+/// a property of the loaded process, so read-only and executable segments
+/// are writable here; only file-backed bytes can be written.
+impl MemoryWriter for BinaryMemory {
+    fn write_bytes(&mut self, addr: Address, data: &[u8]) -> Result<(), MemoryError> {
+        let range = self.file_range(addr, data.len())?;
+        Arc::make_mut(&mut self.data)[range].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn write_u8(&mut self, addr: Address, value: u8) -> Result<(), MemoryError> {
+        self.write_bytes(addr, &[value])
+    }
+
+    fn write_u16(&mut self, addr: Address, value: u16) -> Result<(), MemoryError> {
+        self.write_bytes(addr, &value.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, addr: Address, value: u32) -> Result<(), MemoryError> {
+        self.write_bytes(addr, &value.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, addr: Address, value: u64) -> Result<(), MemoryError> {
+        self.write_bytes(addr, &value.to_le_bytes())
+    }
+
+    fn write_ptr(&mut self, addr: Address, value: Address) -> Result<(), MemoryError> {
+        self.write_u64(addr, value.as_u64())
+    }
+}
+
+/// Overlapping windows over a byte slice; see `BinaryMemory::chunks`
+pub struct BinaryChunks<'a> {
+    data: &'a [u8],
+    chunk_size: usize,
+    step: usize,
+    offset: usize,
+}
+
+impl<'a> BinaryChunks<'a> {
+    pub fn new(data: &'a [u8], chunk_size: usize, overlap: usize) -> Self {
+        let chunk_size = chunk_size.max(overlap + 1);
+        Self { data, chunk_size, step: chunk_size - overlap, offset: 0 }
+    }
+}
+
+impl<'a> Iterator for BinaryChunks<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        if self.offset >= self.data.len() {
+            return None;
+        }
+
+        let end = (self.offset + self.chunk_size).min(self.data.len());
+        let chunk = &self.data[self.offset..end];
+        // The last window reaches the end; stepping on would only repeat its tail
+        self.offset = if end == self.data.len() { end } else { self.offset + self.step };
+        Some(chunk)
+    }
+}
+
 pub struct BinaryScanner {
     binary: BinaryMemory,
 }
@@ -0,0 +1,94 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::memory::{Address, MemoryError, MemoryReader, MemoryRegion};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Passes reads through to `reader` and adds the size of every successful
+/// read to a shared counter, so progress can follow the bytes finders
+/// actually consume.
+pub struct CountingReader {
+    reader: Arc<dyn MemoryReader>,
+    consumed: Arc<AtomicU64>,
+}
+
+impl CountingReader {
+    pub fn new(reader: Arc<dyn MemoryReader>, consumed: Arc<AtomicU64>) -> Self {
+        Self { reader, consumed }
+    }
+
+    pub fn consumed(&self) -> u64 {
+        self.consumed.load(Ordering::Relaxed)
+    }
+
+    fn count<T>(&self, result: Result<T, MemoryError>, len: usize) -> Result<T, MemoryError> {
+        if result.is_ok() {
+            self.consumed.fetch_add(len as u64, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+impl MemoryReader for CountingReader {
+    fn read_bytes(&self, addr: Address, len: usize) -> Result<Vec<u8>, MemoryError> {
+        let bytes = self.reader.read_bytes(addr, len)?;
+        self.consumed.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        Ok(bytes)
+    }
+
+    fn read_u8(&self, addr: Address) -> Result<u8, MemoryError> {
+        self.count(self.reader.read_u8(addr), 1)
+    }
+
+    fn read_u16(&self, addr: Address) -> Result<u16, MemoryError> {
+        self.count(self.reader.read_u16(addr), 2)
+    }
+
+    fn read_u32(&self, addr: Address) -> Result<u32, MemoryError> {
+        self.count(self.reader.read_u32(addr), 4)
+    }
+
+    fn read_u64(&self, addr: Address) -> Result<u64, MemoryError> {
+        self.count(self.reader.read_u64(addr), 8)
+    }
+
+    fn read_i8(&self, addr: Address) -> Result<i8, MemoryError> {
+        self.count(self.reader.read_i8(addr), 1)
+    }
+
+    fn read_i16(&self, addr: Address) -> Result<i16, MemoryError> {
+        self.count(self.reader.read_i16(addr), 2)
+    }
+
+    fn read_i32(&self, addr: Address) -> Result<i32, MemoryError> {
+        self.count(self.reader.read_i32(addr), 4)
+    }
+
+    fn read_i64(&self, addr: Address) -> Result<i64, MemoryError> {
+        self.count(self.reader.read_i64(addr), 8)
+    }
+
+    fn read_ptr(&self, addr: Address) -> Result<Address, MemoryError> {
+        self.count(self.reader.read_ptr(addr), 8)
+    }
+
+    fn read_string(&self, addr: Address, max_len: usize) -> Result<String, MemoryError> {
+        let string = self.reader.read_string(addr, max_len)?;
+        self.consumed.fetch_add(string.len() as u64 + 1, Ordering::Relaxed);
+        Ok(string)
+    }
+
+    fn read_c_string(&self, addr: Address) -> Result<String, MemoryError> {
+        let string = self.reader.read_c_string(addr)?;
+        self.consumed.fetch_add(string.len() as u64 + 1, Ordering::Relaxed);
+        Ok(string)
+    }
+
+    fn get_base_address(&self) -> Address {
+        self.reader.get_base_address()
+    }
+
+    fn get_regions(&self) -> Result<Vec<MemoryRegion>, MemoryError> {
+        self.reader.get_regions()
+    }
+}
@@ -0,0 +1,165 @@
+// Fri Jan 16 2026 - Alex
+
+// ELF counterparts of the Mach-O parsing in `binary.rs`, for the Android
+// build (`libroblox.so`). ELF segments have no names, so each PT_LOAD is
+// called `LOAD[n]` and sections report the segment that contains them.
+
+use crate::memory::binary::{BinarySection, BinarySegment, BinarySymbol};
+use crate::memory::{MemoryError, Protection};
+use goblin::elf::header::EM_AARCH64;
+use goblin::elf::program_header::{PF_R, PF_W, PF_X, PT_LOAD};
+use goblin::elf::section_header::SHF_ALLOC;
+use goblin::elf::sym::Sym;
+use goblin::elf::Elf;
+
+pub const ELF_MAGIC: &[u8; 4] = b"\x7fELF";
+
+pub fn is_elf(data: &[u8]) -> bool {
+    data.starts_with(ELF_MAGIC)
+}
+
+fn parse(data: &[u8]) -> Result<Elf<'_>, MemoryError> {
+    Elf::parse(data)
+        .map_err(|e| MemoryError::BinaryParseError(format!("Failed to parse ELF: {}", e)))
+}
+
+/// ELF PF_* bits are X=1, W=2, R=4; `Protection` uses Mach-O order.
+fn protection(p_flags: u32) -> Protection {
+    let mut flags = 0;
+    if p_flags & PF_R != 0 {
+        flags |= Protection::Read.to_flags();
+    }
+    if p_flags & PF_W != 0 {
+        flags |= Protection::Write.to_flags();
+    }
+    if p_flags & PF_X != 0 {
+        flags |= Protection::Execute.to_flags();
+    }
+    Protection::from_flags(flags)
+}
+
+pub fn segments(data: &[u8]) -> Result<Vec<BinarySegment>, MemoryError> {
+    let elf = parse(data)?;
+
+    Ok(elf.program_headers.iter()
+        .filter(|ph| ph.p_type == PT_LOAD)
+        .enumerate()
+        .map(|(i, ph)| BinarySegment {
+            name: format!("LOAD[{}]", i),
+            vmaddr: ph.p_vaddr,
+            vmsize: ph.p_memsz,
+            fileoff: ph.p_offset,
+            filesize: ph.p_filesz,
+            protection: protection(ph.p_flags),
+        })
+        .collect())
+}
+
+/// File offset and size of the first executable and first writable segment,
+/// the ELF equivalents of `__TEXT` and `__DATA`.
+pub fn text_and_data(segments: &[BinarySegment]) -> (u64, u64, u64, u64) {
+    let text = segments.iter().find(|s| s.protection.can_execute());
+    let data = segments.iter().find(|s| s.protection.can_write());
+
+    (
+        text.map_or(0, |s| s.fileoff),
+        text.map_or(0, |s| s.filesize),
+        data.map_or(0, |s| s.fileoff),
+        data.map_or(0, |s| s.filesize),
+    )
+}
+
+/// Allocated sections only; `segname` is the containing `LOAD[n]`.
+pub fn sections(data: &[u8], segments: &[BinarySegment]) -> Result<Vec<BinarySection>, MemoryError> {
+    let elf = parse(data)?;
+
+    Ok(elf.section_headers.iter()
+        .filter(|sh| sh.sh_flags & SHF_ALLOC as u64 != 0)
+        .map(|sh| {
+            let segname = segments.iter()
+                .find(|s| sh.sh_addr >= s.vmaddr && sh.sh_addr < s.vmaddr + s.vmsize)
+                .map(|s| s.name.clone())
+                .unwrap_or_default();
+
+            BinarySection {
+                segname,
+                sectname: elf.shdr_strtab.get_at(sh.sh_name).unwrap_or("").to_string(),
+                addr: sh.sh_addr,
+                size: sh.sh_size,
+                offset: sh.sh_offset,
+            }
+        })
+        .collect())
+}
+
+fn is_global(sym: &Sym) -> bool {
+    matches!(sym.st_bind(), goblin::elf::sym::STB_GLOBAL | goblin::elf::sym::STB_WEAK)
+}
+
+fn is_defined(sym: &Sym) -> bool {
+    sym.st_shndx != 0
+}
+
+/// Defined symbols from both `.symtab` and `.dynsym`.
+pub fn symbols(data: &[u8]) -> Result<Vec<BinarySymbol>, MemoryError> {
+    let elf = parse(data)?;
+
+    let symtab = elf.syms.iter().map(|sym| (sym, &elf.strtab));
+    let dynsym = elf.dynsyms.iter().map(|sym| (sym, &elf.dynstrtab));
+
+    Ok(symtab.chain(dynsym)
+        .filter(|(sym, _)| is_defined(sym))
+        .filter_map(|(sym, strtab)| {
+            let name = strtab.get_at(sym.st_name).filter(|n| !n.is_empty())?;
+            Some(BinarySymbol {
+                name: name.to_string(),
+                address: sym.st_value,
+                size: sym.st_size,
+                is_external: is_global(&sym),
+            })
+        })
+        .collect())
+}
+
+pub fn exports(data: &[u8]) -> Result<Vec<BinarySymbol>, MemoryError> {
+    let elf = parse(data)?;
+
+    Ok(elf.dynsyms.iter()
+        .filter(|sym| is_defined(sym) && is_global(sym))
+        .filter_map(|sym| {
+            let name = elf.dynstrtab.get_at(sym.st_name).filter(|n| !n.is_empty())?;
+            Some(BinarySymbol {
+                name: name.to_string(),
+                address: sym.st_value,
+                size: sym.st_size,
+                is_external: true,
+            })
+        })
+        .collect())
+}
+
+pub fn imports(data: &[u8]) -> Result<Vec<String>, MemoryError> {
+    let elf = parse(data)?;
+
+    Ok(elf.dynsyms.iter()
+        .filter(|sym| !is_defined(sym))
+        .filter_map(|sym| elf.dynstrtab.get_at(sym.st_name).filter(|n| !n.is_empty()))
+        .map(str::to_string)
+        .collect())
+}
+
+pub fn entry_point(data: &[u8]) -> Result<u64, MemoryError> {
+    Ok(parse(data)?.entry)
+}
+
+pub fn is_64bit(data: &[u8]) -> Result<bool, MemoryError> {
+    Ok(parse(data)?.is_64)
+}
+
+pub fn is_arm64(data: &[u8]) -> Result<bool, MemoryError> {
+    Ok(parse(data)?.header.e_machine == EM_AARCH64)
+}
+
+pub fn libraries(data: &[u8]) -> Result<Vec<String>, MemoryError> {
+    Ok(parse(data)?.libraries.iter().map(|s| s.to_string()).collect())
+}
@@ -16,6 +16,8 @@ pub enum MemoryError {
     PermissionDenied(String),
     #[error("Read failed at address {0}")]
     ReadFailed(u64),
+    #[error("Partial read: only {read} of {requested} bytes at 0x{addr:x} are mapped and readable")]
+    PartialRead { addr: u64, requested: usize, read: usize },
     #[error("Write failed at address {0}")]
     WriteFailed(u64),
     #[error("Process not found: {0}")]
@@ -26,6 +28,8 @@ pub enum MemoryError {
     InvalidRange,
     #[error("Out of bounds: address {0} not in range")]
     OutOfBounds(u64),
+    #[error("Out of bounds: {len} bytes at 0x{addr:x} not within mapped range 0x{valid_start:x}-0x{valid_end:x}")]
+    OutOfBoundsRead { addr: u64, len: usize, valid_start: u64, valid_end: u64 },
     #[error("Alignment error: address {0} not aligned to {1}")]
     AlignmentError(u64, usize),
     #[error("Timeout while accessing memory")]
@@ -0,0 +1,105 @@
+// Sat Oct 17 2026 - Alex
+
+// Universal (fat) Mach-O support. A fat file is a table of per-arch slices,
+// each a complete thin Mach-O; `BinaryMemory` loads one slice and treats it
+// as the whole image, so addresses come out relative to that slice's
+// `__TEXT`.
+
+use crate::memory::MemoryError;
+
+pub const FAT_MAGIC: u32 = 0xCAFEBABE;
+pub const FAT_CIGAM: u32 = 0xBEBAFECA;
+pub const FAT_MAGIC_64: u32 = 0xCAFEBABF;
+pub const FAT_CIGAM_64: u32 = 0xBFBAFECA;
+
+pub const CPU_TYPE_X86_64: u32 = 0x0100_0007;
+pub const CPU_TYPE_ARM64: u32 = 0x0100_000C;
+
+/// One architecture's image inside a fat file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FatSlice {
+    pub cpu_type: u32,
+    pub cpu_subtype: u32,
+    pub offset: u64,
+    pub size: u64,
+}
+
+pub fn cpu_type_name(cpu_type: u32) -> String {
+    match cpu_type {
+        CPU_TYPE_ARM64 => "arm64".to_string(),
+        CPU_TYPE_X86_64 => "x86_64".to_string(),
+        other => format!("cpu 0x{:x}", other),
+    }
+}
+
+/// Header magic and whether the table is big-endian, for fat files only
+fn fat_header(data: &[u8]) -> Option<(u32, bool)> {
+    let bytes: [u8; 4] = data.get(..4)?.try_into().ok()?;
+    match u32::from_be_bytes(bytes) {
+        magic @ (FAT_MAGIC | FAT_MAGIC_64) => Some((magic, true)),
+        FAT_CIGAM => Some((FAT_MAGIC, false)),
+        FAT_CIGAM_64 => Some((FAT_MAGIC_64, false)),
+        _ => None,
+    }
+}
+
+pub fn is_fat(data: &[u8]) -> bool {
+    fat_header(data).is_some()
+}
+
+/// Every slice in the fat table, checked to lie inside `data`
+pub fn slices(data: &[u8]) -> Result<Vec<FatSlice>, MemoryError> {
+    let (magic, big_endian) = fat_header(data)
+        .ok_or_else(|| MemoryError::BinaryParseError("Not a fat Mach-O".to_string()))?;
+    let truncated = || MemoryError::BinaryParseError("Truncated fat Mach-O header".to_string());
+
+    let u32_at = |at: usize| -> Result<u32, MemoryError> {
+        let bytes: [u8; 4] = data.get(at..at + 4).ok_or_else(truncated)?.try_into().unwrap();
+        Ok(if big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+    };
+    let u64_at = |at: usize| -> Result<u64, MemoryError> {
+        let bytes: [u8; 8] = data.get(at..at + 8).ok_or_else(truncated)?.try_into().unwrap();
+        Ok(if big_endian { u64::from_be_bytes(bytes) } else { u64::from_le_bytes(bytes) })
+    };
+
+    let count = u32_at(4)? as usize;
+    let entry_size = if magic == FAT_MAGIC_64 { 32 } else { 20 };
+
+    let mut slices = Vec::with_capacity(count.min(16));
+    for i in 0..count {
+        let at = 8 + i * entry_size;
+        let (offset, size) = if magic == FAT_MAGIC_64 {
+            (u64_at(at + 8)?, u64_at(at + 16)?)
+        } else {
+            (u32_at(at + 8)? as u64, u32_at(at + 12)? as u64)
+        };
+
+        if offset.checked_add(size).is_none_or(|end| end > data.len() as u64) {
+            return Err(MemoryError::BinaryParseError(format!(
+                "Fat slice {} (0x{:x}+0x{:x}) runs past the end of the file", i, offset, size
+            )));
+        }
+
+        slices.push(FatSlice { cpu_type: u32_at(at)?, cpu_subtype: u32_at(at + 4)?, offset, size });
+    }
+
+    Ok(slices)
+}
+
+/// The `cpu_type` slice of a fat file as its own image. Thin images are
+/// returned as they are.
+pub fn thin(data: Vec<u8>, cpu_type: u32) -> Result<Vec<u8>, MemoryError> {
+    if !is_fat(&data) {
+        return Ok(data);
+    }
+
+    let slices = slices(&data)?;
+    let slice = slices.iter().find(|s| s.cpu_type == cpu_type).ok_or_else(|| {
+        let available: Vec<String> = slices.iter().map(|s| cpu_type_name(s.cpu_type)).collect();
+        MemoryError::BinaryParseError(format!(
+            "Fat binary has no {} slice (has: {})", cpu_type_name(cpu_type), available.join(", ")
+        ))
+    })?;
+
+    Ok(data[slice.offset as usize..(slice.offset + slice.size) as usize].to_vec())
+}
@@ -1,6 +1,6 @@
 // Wed Jan 15 2026 - Alex
 
-use crate::memory::{Address, MemoryError, MemoryReader, MemoryRegion, MemoryRange, Protection};
+use crate::memory::{Address, BinaryChunks, MemoryError, MemoryReader, MemoryRegion, MemoryRange, Protection};
 use memmap2::Mmap;
 use std::fs::File;
 use std::path::Path;
@@ -39,6 +39,12 @@ impl MmapMemory {
         self.mmap.as_ref()
     }
 
+    /// `BinaryMemory::chunks` over the mapping. Pages are faulted in as
+    /// each window is read, so the file is never copied into memory whole.
+    pub fn chunks(&self, chunk_size: usize, overlap: usize) -> BinaryChunks<'_> {
+        BinaryChunks::new(self.as_slice(), chunk_size, overlap)
+    }
+
     fn offset(&self, addr: Address) -> Result<usize, MemoryError> {
         let offset = (addr.as_u64() - self.base_address.as_u64()) as usize;
         if offset >= self.mmap.len() {
@@ -3,10 +3,13 @@
 pub mod scanner;
 pub mod process;
 pub mod binary;
+pub mod elf;
+pub mod fat;
 pub mod region;
 pub mod mapping;
 pub mod access;
 pub mod cache;
+pub mod counting;
 pub mod error;
 pub mod traits;
 pub mod address;
@@ -18,11 +21,13 @@ pub mod segment;
 
 pub use scanner::MemoryScanner;
 pub use process::ProcessMemory;
-pub use binary::BinaryMemory;
+pub use binary::{BinaryChunks, BinaryFormat, BinaryMemory, BinarySection};
 pub use region::MemoryRegion;
 pub use mapping::MemoryMapping;
+pub use mmap::MmapMemory;
 pub use access::MemoryAccess;
 pub use cache::MemoryCache;
+pub use counting::CountingReader;
 pub use error::MemoryError;
 pub use traits::{MemoryReader, MemoryWriter};
 pub use address::Address;
@@ -41,7 +41,7 @@ struct vm_region_basic_info_64 {
 extern "C" {
     fn mach_task_self() -> mach_port_t;
     fn task_for_pid(target_task: mach_port_t, pid: c_int, task: *mut mach_port_t) -> kern_return_t;
-    fn vm_read_overwrite(
+    fn mach_vm_read_overwrite(
         target_task: mach_port_t,
         address: vm_address_t,
         size: vm_size_t,
@@ -148,52 +148,62 @@ impl ProcessMemory {
         self.task
     }
 
+    /// The task's VM map, one region per map entry with its current protection
     pub fn enumerate_regions(&self) -> Result<Vec<MemoryRegion>, MemoryError> {
         let mut regions = Vec::new();
         let mut address: vm_address_t = 0;
 
-        loop {
-            let mut size: vm_size_t = 0;
-            let mut info: vm_region_basic_info_64 = Default::default();
-            let mut info_count: u32 = VM_REGION_BASIC_INFO_COUNT_64;
-            let mut object_name: mach_port_t = 0;
-
-            let result = unsafe {
-                mach_vm_region(
-                    self.task,
-                    &mut address,
-                    &mut size,
-                    VM_REGION_BASIC_INFO_64,
-                    &mut info as *mut _ as vm_region_info_t,
-                    &mut info_count,
-                    &mut object_name,
-                )
-            };
-
-            if result != KERN_SUCCESS {
-                break;
-            }
-
-            let protection = Protection::from_flags(info.protection as u32);
-            let range = MemoryRange::from_start_size(Address::new(address), size);
-            let region = MemoryRegion::new(range, protection, format!("region_{:016x}", address));
+        while let Some(region) = self.region_from(address) {
+            let end = region.end().as_u64();
             regions.push(region);
 
-            address = address.saturating_add(size);
-            if address == 0 || size == 0 {
+            if end <= address {
                 break;
             }
+            address = end;
         }
 
         Ok(regions)
     }
 
+    /// The first region at or above `address`
+    fn region_from(&self, address: vm_address_t) -> Option<MemoryRegion> {
+        let mut address = address;
+        let mut size: vm_size_t = 0;
+        let mut info: vm_region_basic_info_64 = Default::default();
+        let mut info_count: u32 = VM_REGION_BASIC_INFO_COUNT_64;
+        let mut object_name: mach_port_t = 0;
+
+        let result = unsafe {
+            mach_vm_region(
+                self.task,
+                &mut address,
+                &mut size,
+                VM_REGION_BASIC_INFO_64,
+                &mut info as *mut _ as vm_region_info_t,
+                &mut info_count,
+                &mut object_name,
+            )
+        };
+
+        if result != KERN_SUCCESS || size == 0 {
+            return None;
+        }
+
+        let protection = Protection::from_flags(info.protection as u32);
+        let range = MemoryRange::from_start_size(Address::new(address), size);
+        Some(MemoryRegion::new(range, protection, format!("region_{:016x}", address)))
+    }
+
+    /// Reads `size` bytes at `address`. A read that runs off the end of the
+    /// readable mapping, into a gap or a region without read access, fails
+    /// with `PartialRead` saying how many bytes could have been read.
     pub fn read_memory(&self, address: u64, size: usize) -> Result<Vec<u8>, MemoryError> {
         let mut buffer = vec![0u8; size];
         let mut out_size: vm_size_t = 0;
 
         let result = unsafe {
-            vm_read_overwrite(
+            mach_vm_read_overwrite(
                 self.task,
                 address,
                 size as vm_size_t,
@@ -202,12 +212,31 @@ impl ProcessMemory {
             )
         };
 
-        if result != KERN_SUCCESS {
-            return Err(MemoryError::ReadFailed(address));
+        if result == KERN_SUCCESS && out_size as usize == size {
+            return Ok(buffer);
+        }
+
+        match self.readable_len(address, size) {
+            0 => Err(MemoryError::ReadFailed(address)),
+            read => Err(MemoryError::PartialRead { addr: address, requested: size, read }),
+        }
+    }
+
+    /// Bytes from `address` that lie in contiguous readable regions, up to `size`
+    fn readable_len(&self, address: u64, size: usize) -> usize {
+        let end = address.saturating_add(size as u64);
+        let mut cursor = address;
+
+        while cursor < end {
+            match self.region_from(cursor) {
+                Some(region) if region.contains(Address::new(cursor)) && region.protection().is_readable() => {
+                    cursor = region.end().as_u64();
+                }
+                _ => break,
+            }
         }
 
-        buffer.truncate(out_size as usize);
-        Ok(buffer)
+        (cursor.min(end) - address) as usize
     }
 
     pub fn find_pattern(&self, pattern: &[u8], mask: &[u8], start: u64, end: u64) -> Result<Vec<u64>, MemoryError> {
@@ -1,7 +1,7 @@
 // Tue Jan 13 2026 - Alex
 
 use crate::memory::Address;
-use crate::finders::result::FinderResults;
+use crate::finders::result::{CombinedResults, FinderResults};
 use std::collections::HashMap;
 
 pub struct ResultAggregator {
@@ -38,6 +38,37 @@ impl ResultAggregator {
         aggregated
     }
 
+    /// Merges per-chunk scan results. Overlapping chunks report the same hit
+    /// twice, so each name keeps only its highest-confidence entry.
+    pub fn aggregate_combined(&self, results_list: Vec<CombinedResults>) -> CombinedResults {
+        let mut merged = CombinedResults::new();
+
+        for results in results_list {
+            merged.merge(results);
+        }
+
+        CombinedResults {
+            functions: keep_best(merged.functions, |r| r.name.clone(), |r| r.confidence),
+            structure_offsets: keep_best(
+                merged.structure_offsets,
+                |r| format!("{}.{}", r.structure_name, r.field_name),
+                |r| r.confidence,
+            ),
+            classes: keep_best(merged.classes, |r| r.name.clone(), |r| r.confidence),
+            properties: keep_best(
+                merged.properties,
+                |r| format!("{}.{}", r.class_name, r.property_name),
+                |r| r.confidence,
+            ),
+            methods: keep_best(
+                merged.methods,
+                |r| format!("{}::{}", r.class_name, r.method_name),
+                |r| r.confidence,
+            ),
+            constants: keep_best(merged.constants, |r| r.name.clone(), |r| r.confidence),
+        }
+    }
+
     fn merge_into(&self, target: &mut FinderResults, source: FinderResults) {
         match self.merge_strategy {
             MergeStrategy::HighestConfidence => {
@@ -173,6 +204,27 @@ impl ResultAggregator {
     }
 }
 
+fn keep_best<T>(items: Vec<T>, key: impl Fn(&T) -> String, confidence: impl Fn(&T) -> f64) -> Vec<T> {
+    let mut best: Vec<T> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for item in items {
+        match index.get(&key(&item)) {
+            Some(&i) => {
+                if confidence(&item) > confidence(&best[i]) {
+                    best[i] = item;
+                }
+            }
+            None => {
+                index.insert(key(&item), best.len());
+                best.push(item);
+            }
+        }
+    }
+
+    best
+}
+
 impl Default for ResultAggregator {
     fn default() -> Self {
         Self::new()
@@ -223,3 +275,25 @@ impl Default for AggregationStatistics {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finders::result::FinderResult;
+
+    #[test]
+    fn test_aggregate_combined_keeps_highest_confidence() {
+        let mut first = CombinedResults::new();
+        first.add_function(FinderResult::new("LuauLoad".to_string(), Address::new(0x1000), 0.6));
+
+        let mut second = CombinedResults::new();
+        second.add_function(FinderResult::new("LuauLoad".to_string(), Address::new(0x1000), 0.9));
+        second.add_function(FinderResult::new("rbx_crash".to_string(), Address::new(0x2000), 0.8));
+
+        let merged = ResultAggregator::new().aggregate_combined(vec![first, second]);
+
+        assert_eq!(merged.functions.len(), 2);
+        assert_eq!(merged.functions[0].name, "LuauLoad");
+        assert_eq!(merged.functions[0].confidence, 0.9);
+    }
+}
@@ -0,0 +1,111 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::analysis::string::{StringAnalyzer, StringAnalyzerConfig};
+use crate::finders::roblox::BUILTIN_FINDERS;
+use crate::finders::Finder;
+use crate::memory::{MemoryError, MemoryReader};
+use crate::output::{FinderReportEntry, FinderStatus};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnmatchedReason {
+    /// None of the finder's anchor strings are in the binary
+    AnchorAbsent,
+    /// An anchor string is there, but no code around it matched
+    PatternChanged,
+    /// The finder matches code only, so there is no string to check
+    NoAnchors,
+}
+
+impl UnmatchedReason {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            UnmatchedReason::AnchorAbsent => "anchor string absent — likely renamed/removed",
+            UnmatchedReason::PatternChanged => "anchor found but code pattern changed",
+            UnmatchedReason::NoAnchors => "no anchor strings, code pattern changed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnmatchedFinder {
+    pub finder: String,
+    pub reason: UnmatchedReason,
+    /// Anchors that occur somewhere in the binary
+    pub present: Vec<&'static str>,
+    pub missing: Vec<&'static str>,
+}
+
+/// Explains why built-in finders came up empty by checking whether the
+/// strings they anchor on still exist anywhere in the binary. Finders
+/// match anchors as substrings, so a string containing one counts.
+pub struct FinderDiagnostics {
+    reader: Arc<dyn MemoryReader>,
+}
+
+impl FinderDiagnostics {
+    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self { reader }
+    }
+
+    /// One entry per finder whose offsets were all not found, in report order
+    pub fn unmatched(&self, report: &[FinderReportEntry]) -> Result<Vec<UnmatchedFinder>, MemoryError> {
+        let mut finders: Vec<&str> = Vec::new();
+        for entry in report {
+            if !finders.contains(&entry.finder.as_str()) {
+                finders.push(&entry.finder);
+            }
+        }
+        finders.retain(|finder| {
+            report.iter()
+                .filter(|e| e.finder == *finder)
+                .all(|e| e.status == FinderStatus::NotFound)
+        });
+
+        if finders.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let anchors = |finder: &str| BUILTIN_FINDERS.iter()
+            .find(|f| f.name() == finder)
+            .map_or(&[][..], |f| f.anchors());
+
+        let shortest = finders.iter()
+            .flat_map(|f| anchors(f).iter().map(|a| a.chars().count()))
+            .min()
+            .unwrap_or(1);
+        let strings = self.strings(shortest)?;
+
+        Ok(finders.into_iter()
+            .map(|finder| {
+                let (present, missing): (Vec<&'static str>, Vec<&'static str>) = anchors(finder).iter()
+                    .partition(|anchor| strings.iter().any(|s| s.contains(*anchor)));
+
+                let reason = if present.is_empty() && missing.is_empty() {
+                    UnmatchedReason::NoAnchors
+                } else if present.is_empty() {
+                    UnmatchedReason::AnchorAbsent
+                } else {
+                    UnmatchedReason::PatternChanged
+                };
+
+                UnmatchedFinder { finder: finder.to_string(), reason, present, missing }
+            })
+            .collect())
+    }
+
+    /// Every string in the binary, unfiltered by relevance
+    fn strings(&self, min_length: usize) -> Result<Vec<String>, MemoryError> {
+        let config = StringAnalyzerConfig {
+            min_relevance: 0.0,
+            max_length: 4096,
+            ..StringAnalyzerConfig::default()
+        }
+        .with_min_length(min_length);
+
+        let regions = self.reader.get_regions()?;
+        let strings = StringAnalyzer::with_config(self.reader.clone(), config).find_strings(&regions)?;
+
+        Ok(strings.into_iter().map(|s| s.content).collect())
+    }
+}
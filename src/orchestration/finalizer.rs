@@ -1,10 +1,14 @@
 // Tue Jan 13 2026 - Alex
 
 use crate::config::Config;
-use crate::finders::result::FinderResults;
+use crate::finders::result::{CombinedResults, FinderResults};
+use crate::finders::roblox::FINDER_OUTPUTS;
 use crate::output::manager::OutputManager;
-use crate::output::OffsetOutput;
-use std::collections::HashMap;
+use crate::output::{AddressCollision, CollisionKind, FinderReportEntry, FinderStatus, OffsetOutput};
+use std::collections::{BTreeMap, HashMap};
+
+/// Dropped from names before comparing them for aliases
+const ALIAS_PREFIXES: &[&str] = &["luau", "lual", "lua", "rbx"];
 
 pub struct OutputFinalizer {
     format_addresses: bool,
@@ -169,6 +173,70 @@ impl OutputFinalizer {
         output
     }
 
+    /// One entry per roblox finder output. `kept` are the results that made
+    /// it into the output; `attempted` are all results before confidence
+    /// filtering, used to tell a weak match from no match at all.
+    pub fn finder_report(&self, kept: &CombinedResults, attempted: &CombinedResults) -> Vec<FinderReportEntry> {
+        let mut report = Vec::new();
+
+        for (finder, offsets) in FINDER_OUTPUTS {
+            for offset in offsets.iter() {
+                let entry = match kept.functions.iter().find(|f| f.name == *offset) {
+                    Some(result) => FinderReportEntry {
+                        finder: finder.to_string(),
+                        offset: offset.to_string(),
+                        status: FinderStatus::Found,
+                        address: Some(result.address.as_u64()),
+                        confidence: Some(result.confidence),
+                        discovery_method: Some(result.method.clone()),
+                    },
+                    None => {
+                        let weak = attempted.functions.iter().find(|f| f.name == *offset);
+                        FinderReportEntry {
+                            finder: finder.to_string(),
+                            offset: offset.to_string(),
+                            status: if weak.is_some() { FinderStatus::BelowThreshold } else { FinderStatus::NotFound },
+                            address: None,
+                            confidence: weak.map(|r| r.confidence),
+                            discovery_method: weak.map(|r| r.method.clone()),
+                        }
+                    }
+                };
+
+                report.push(entry);
+            }
+        }
+
+        report
+    }
+
+    /// Function results sharing an address, in address order. Names that
+    /// all reduce to the same word (case, underscores and Lua prefixes
+    /// aside), or contain one another, are aliases; anything else collides.
+    pub fn address_collisions(&self, results: &CombinedResults) -> Vec<AddressCollision> {
+        let mut by_address: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+        for func in &results.functions {
+            let names = by_address.entry(func.address.as_u64()).or_default();
+            if !names.contains(&func.name) {
+                names.push(func.name.clone());
+            }
+        }
+
+        by_address.into_iter()
+            .filter(|(_, names)| names.len() > 1)
+            .map(|(address, mut names)| {
+                names.sort();
+                let first = alias_key(&names[0]);
+                let kind = if names[1..].iter().all(|n| keys_related(&first, &alias_key(n))) {
+                    CollisionKind::Alias
+                } else {
+                    CollisionKind::Collision
+                };
+                AddressCollision { address, names, kind }
+            })
+            .collect()
+    }
+
     pub fn create_summary(&self, results: &FinderResults) -> FinalizationSummary {
         FinalizationSummary {
             function_count: results.functions.len(),
@@ -188,6 +256,19 @@ impl OutputFinalizer {
     }
 }
 
+fn alias_key(name: &str) -> String {
+    let mut key: String = name.chars().filter(char::is_ascii_alphanumeric).collect::<String>().to_ascii_lowercase();
+    if let Some(prefix) = ALIAS_PREFIXES.iter().find(|p| key.len() > p.len() && key.starts_with(*p)) {
+        key.drain(..prefix.len());
+    }
+    key
+}
+
+fn keys_related(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    short.len() >= 3 && long.contains(short)
+}
+
 impl Default for OutputFinalizer {
     fn default() -> Self {
         Self::new()
@@ -228,3 +309,27 @@ impl FinalizationSummary {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finders::result::FinderResult;
+    use crate::memory::Address;
+
+    #[test]
+    fn test_address_collisions_split_aliases_from_collisions() {
+        let mut results = CombinedResults::new();
+        for (name, address) in [("LuauLoad", 0x1000), ("luau_load", 0x1000), ("print", 0x2000), ("warn", 0x2000), ("NewThread", 0x3000)] {
+            results.add_function(FinderResult::new(name.to_string(), Address::new(address), 0.9));
+        }
+
+        let collisions = OutputFinalizer::new().address_collisions(&results);
+        let summary: Vec<(u64, Vec<&str>, CollisionKind)> = collisions.iter()
+            .map(|c| (c.address, c.names.iter().map(String::as_str).collect(), c.kind))
+            .collect();
+        assert_eq!(summary, vec![
+            (0x1000, vec!["LuauLoad", "luau_load"], CollisionKind::Alias),
+            (0x2000, vec!["print", "warn"], CollisionKind::Collision),
+        ]);
+    }
+}
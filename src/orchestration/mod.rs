@@ -6,6 +6,7 @@ pub mod scheduler;
 pub mod collector;
 pub mod aggregator;
 pub mod finalizer;
+pub mod diagnostics;
 
 pub use coordinator::DiscoveryCoordinator;
 pub use discovery::DiscoveryManager;
@@ -13,3 +14,4 @@ pub use scheduler::DiscoveryScheduler;
 pub use collector::ResultCollector;
 pub use aggregator::ResultAggregator;
 pub use finalizer::OutputFinalizer;
+pub use diagnostics::{FinderDiagnostics, UnmatchedFinder, UnmatchedReason};
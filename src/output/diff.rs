@@ -1,6 +1,7 @@
 // Tue Jan 13 2026 - Alex
 
 use crate::output::{OffsetOutput, FunctionOffset, StructureOffsets, ClassOffset, FieldOffset};
+use serde_json::{json, Value};
 use std::collections::{HashMap, HashSet};
 
 pub struct DiffGenerator {
@@ -152,6 +153,17 @@ impl DiffGenerator {
     }
 
     pub fn generate(&self, old: &OffsetOutput, new: &OffsetOutput) -> OffsetDiff {
+        // Outputs anchored at different bases are compared in the old base
+        let rebased;
+        let new = if new.effective_base() != old.effective_base() {
+            let mut copy = new.clone();
+            copy.rebase(old.effective_base());
+            rebased = copy;
+            &rebased
+        } else {
+            new
+        };
+
         let function_diff = self.diff_functions(&old.functions, &new.functions);
         let structure_diff = self.diff_structures(&old.structure_offsets, &new.structure_offsets);
         let class_diff = self.diff_classes(&old.classes, &new.classes);
@@ -486,10 +498,26 @@ impl DiffGenerator {
         if !diff.function_diff.changed.is_empty() {
             output.push_str("Changed Functions:\n");
             for change in &diff.function_diff.changed {
-                output.push_str(&format!("  ~ {} 0x{:x} -> 0x{:x}\n",
-                    change.name,
-                    change.old_address.unwrap_or(0),
-                    change.new_address.unwrap_or(0)));
+                let old = change.old_address.unwrap_or(0);
+                let new = change.new_address.unwrap_or(0);
+                output.push_str(&format!("  ~ {} 0x{:x} -> 0x{:x} ({})\n",
+                    change.name, old, new, format_delta(old, new)));
+            }
+            output.push('\n');
+        }
+
+        if !diff.structure_diff.added.is_empty() {
+            output.push_str("Added Structures:\n");
+            for change in &diff.structure_diff.added {
+                output.push_str(&format!("  + {}\n", change.name));
+            }
+            output.push('\n');
+        }
+
+        if !diff.structure_diff.removed.is_empty() {
+            output.push_str("Removed Structures:\n");
+            for change in &diff.structure_diff.removed {
+                output.push_str(&format!("  - {}\n", change.name));
             }
             output.push('\n');
         }
@@ -508,17 +536,120 @@ impl DiffGenerator {
                             field.field_name, field.new_offset.unwrap_or(0))),
                         ChangeType::Removed => output.push_str(&format!("    - {} @ 0x{:x}\n",
                             field.field_name, field.old_offset.unwrap_or(0))),
-                        _ => output.push_str(&format!("    ~ {} 0x{:x} -> 0x{:x}\n",
-                            field.field_name,
-                            field.old_offset.unwrap_or(0),
-                            field.new_offset.unwrap_or(0))),
+                        _ => {
+                            let old = field.old_offset.unwrap_or(0) as u64;
+                            let new = field.new_offset.unwrap_or(0) as u64;
+                            output.push_str(&format!("    ~ {} 0x{:x} -> 0x{:x} ({})\n",
+                                field.field_name, old, new, format_delta(old, new)))
+                        }
                     }
                 }
             }
+            output.push('\n');
+        }
+
+        if !diff.class_diff.added.is_empty() {
+            output.push_str("Added Classes:\n");
+            for change in &diff.class_diff.added {
+                output.push_str(&format!("  + {}\n", change.name));
+            }
+            output.push('\n');
+        }
+
+        if !diff.class_diff.removed.is_empty() {
+            output.push_str("Removed Classes:\n");
+            for change in &diff.class_diff.removed {
+                output.push_str(&format!("  - {}\n", change.name));
+            }
+            output.push('\n');
+        }
+
+        if !diff.class_diff.changed.is_empty() {
+            output.push_str("Changed Classes:\n");
+            for change in &diff.class_diff.changed {
+                match (change.old_vtable, change.new_vtable) {
+                    (Some(old), Some(new)) if old != new => output.push_str(&format!("  ~ {} vtable 0x{:x} -> 0x{:x} ({})\n",
+                        change.name, old, new, format_delta(old, new))),
+                    _ => output.push_str(&format!("  ~ {} (size: {} -> {})\n",
+                        change.name,
+                        change.old_size.unwrap_or(0),
+                        change.new_size.unwrap_or(0))),
+                }
+            }
+            output.push('\n');
         }
 
         output
     }
+
+    /// The diff as a JSON report: the summary, then per category the added
+    /// and removed names and, for entries in both, old and new addresses
+    /// with a signed hex delta.
+    pub fn to_json(&self, diff: &OffsetDiff) -> Value {
+        let hex = |addr: Option<u64>| addr.map(|a| format!("0x{:x}", a));
+        let delta = |old: Option<u64>, new: Option<u64>| match (old, new) {
+            (Some(old), Some(new)) => Some(format_delta(old, new)),
+            _ => None,
+        };
+        let names = |changes: &[StructureChange]| changes.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+        let class_names = |changes: &[ClassChange]| changes.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+
+        let functions = &diff.function_diff;
+        let structures = &diff.structure_diff;
+        let classes = &diff.class_diff;
+        let summary = &diff.summary;
+
+        json!({
+            "old": { "version": diff.old_version, "target": diff.old_target },
+            "new": { "version": diff.new_version, "target": diff.new_target },
+            "summary": {
+                "functions": { "added": summary.functions_added, "removed": summary.functions_removed, "changed": summary.functions_changed, "unchanged": summary.functions_unchanged },
+                "structures": { "added": summary.structures_added, "removed": summary.structures_removed, "changed": summary.structures_changed, "unchanged": summary.structures_unchanged },
+                "classes": { "added": summary.classes_added, "removed": summary.classes_removed, "changed": summary.classes_changed, "unchanged": summary.classes_unchanged },
+                "total_changes": summary.total_changes,
+            },
+            "functions": {
+                "added": functions.added.iter().map(|c| json!({ "name": c.name, "address": hex(c.new_address) })).collect::<Vec<_>>(),
+                "removed": functions.removed.iter().map(|c| json!({ "name": c.name, "address": hex(c.old_address) })).collect::<Vec<_>>(),
+                "changed": functions.changed.iter().map(|c| json!({
+                    "name": c.name,
+                    "old_address": hex(c.old_address),
+                    "new_address": hex(c.new_address),
+                    "delta": delta(c.old_address, c.new_address),
+                })).collect::<Vec<_>>(),
+            },
+            "structures": {
+                "added": names(&structures.added),
+                "removed": names(&structures.removed),
+                "changed": structures.changed.iter().map(|c| json!({
+                    "name": c.name,
+                    "old_size": c.old_size,
+                    "new_size": c.new_size,
+                    "fields": c.field_changes.iter().map(|f| {
+                        let (old, new) = (f.old_offset.map(|o| o as u64), f.new_offset.map(|o| o as u64));
+                        json!({
+                            "name": f.field_name,
+                            "old_offset": hex(old),
+                            "new_offset": hex(new),
+                            "delta": delta(old, new),
+                        })
+                    }).collect::<Vec<_>>(),
+                })).collect::<Vec<_>>(),
+            },
+            "classes": {
+                "added": class_names(&classes.added),
+                "removed": class_names(&classes.removed),
+                "changed": classes.changed.iter().map(|c| json!({
+                    "name": c.name,
+                    "old_vtable": hex(c.old_vtable),
+                    "new_vtable": hex(c.new_vtable),
+                    "delta": delta(c.old_vtable, c.new_vtable),
+                    "old_size": c.old_size,
+                    "new_size": c.new_size,
+                })).collect::<Vec<_>>(),
+            },
+        })
+    }
 }
 
 impl Default for DiffGenerator {
@@ -532,6 +663,40 @@ impl OffsetDiff {
         self.summary.total_changes > 0
     }
 
+    /// Keeps the added, removed and changed entries `keep` accepts, given
+    /// the kind (`function`, `structure` or `class`), name and change, then
+    /// recounts the summary. Unchanged counts are left alone.
+    pub fn retain<F: FnMut(&str, &str, ChangeType) -> bool>(&mut self, mut keep: F) {
+        let functions = &mut self.function_diff;
+        for list in [&mut functions.added, &mut functions.removed, &mut functions.changed] {
+            list.retain(|c| keep("function", &c.name, c.change_type));
+        }
+
+        let structures = &mut self.structure_diff;
+        for list in [&mut structures.added, &mut structures.removed, &mut structures.changed] {
+            list.retain(|c| keep("structure", &c.name, c.change_type));
+        }
+
+        let classes = &mut self.class_diff;
+        for list in [&mut classes.added, &mut classes.removed, &mut classes.changed] {
+            list.retain(|c| keep("class", &c.name, c.change_type));
+        }
+
+        let summary = &mut self.summary;
+        summary.functions_added = self.function_diff.added.len();
+        summary.functions_removed = self.function_diff.removed.len();
+        summary.functions_changed = self.function_diff.changed.len();
+        summary.structures_added = self.structure_diff.added.len();
+        summary.structures_removed = self.structure_diff.removed.len();
+        summary.structures_changed = self.structure_diff.changed.len();
+        summary.classes_added = self.class_diff.added.len();
+        summary.classes_removed = self.class_diff.removed.len();
+        summary.classes_changed = self.class_diff.changed.len();
+        summary.total_changes = summary.functions_added + summary.functions_removed + summary.functions_changed +
+            summary.structures_added + summary.structures_removed + summary.structures_changed +
+            summary.classes_added + summary.classes_removed + summary.classes_changed;
+    }
+
     pub fn is_breaking(&self) -> bool {
         !self.function_diff.removed.is_empty() ||
         !self.function_diff.changed.is_empty() ||
@@ -559,6 +724,15 @@ impl OffsetDiff {
     }
 }
 
+/// Signed hex distance from `old` to `new`, e.g. `+0x40` or `-0x8`
+fn format_delta(old: u64, new: u64) -> String {
+    if new >= old {
+        format!("+0x{:x}", new - old)
+    } else {
+        format!("-0x{:x}", old - new)
+    }
+}
+
 pub fn generate_diff(old: &OffsetOutput, new: &OffsetOutput) -> OffsetDiff {
     DiffGenerator::new().generate(old, new)
 }
@@ -567,3 +741,81 @@ pub fn format_diff(old: &OffsetOutput, new: &OffsetOutput) -> String {
     let diff = generate_diff(old, new);
     DiffGenerator::new().format_diff(&diff)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_normalizes_differing_bases() {
+        let mut old = OffsetOutput::new("old");
+        old.add_function("luau_load", FunctionOffset::new(0x100001000, 0.9, "pattern"));
+
+        let mut new = OffsetOutput::new("new");
+        new.add_function("luau_load", FunctionOffset::new(0x100001000, 0.9, "pattern"));
+        new.make_relative();
+        assert_eq!(new.functions["luau_load"].address, 0x1000);
+
+        let diff = generate_diff(&old, &new);
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn test_diff_against_scan_json_baseline() {
+        let baseline = OffsetOutput::from_scan_json("old", &serde_json::json!({
+            "target": { "base_address": "0x0", "relative": true },
+            "functions": {
+                "luau_load": { "address": "0x1000", "confidence": 0.9, "method": "pattern" },
+                "rbx_crash": { "address": "0x2000", "confidence": 0.8, "method": "string_xref" },
+            },
+        }));
+        assert!(baseline.target.relative);
+
+        let mut current = OffsetOutput::new("new");
+        current.add_function("luau_load", FunctionOffset::new(0x100001040, 0.9, "pattern"));
+
+        let diff = generate_diff(&baseline, &current);
+        assert_eq!(diff.function_diff.removed.len(), 1);
+        assert_eq!(diff.function_diff.removed[0].name, "rbx_crash");
+        assert_eq!(diff.function_diff.changed[0].new_address, Some(0x1040));
+        assert!(DiffGenerator::new().format_diff(&diff).contains("(+0x40)"));
+    }
+
+    #[test]
+    fn test_json_report_lists_names_and_deltas() {
+        let mut old = OffsetOutput::new("old");
+        old.add_function("luau_load", FunctionOffset::new(0x100001040, 0.9, "pattern"));
+        old.add_function("rbx_crash", FunctionOffset::new(0x100002000, 0.9, "pattern"));
+
+        let mut new = OffsetOutput::new("new");
+        new.add_function("luau_load", FunctionOffset::new(0x100001000, 0.9, "pattern"));
+        new.add_function("GetDataModel", FunctionOffset::new(0x100003000, 0.9, "pattern"));
+
+        let report = DiffGenerator::new().to_json(&generate_diff(&old, &new));
+        assert_eq!(report["summary"]["total_changes"], 3);
+        assert_eq!(report["functions"]["added"][0]["name"], "GetDataModel");
+        assert_eq!(report["functions"]["removed"][0]["address"], "0x100002000");
+        assert_eq!(report["functions"]["changed"][0]["old_address"], "0x100001040");
+        assert_eq!(report["functions"]["changed"][0]["delta"], "-0x40");
+        assert!(report["classes"]["added"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_retain_recounts_summary() {
+        let mut old = OffsetOutput::new("old");
+        old.add_function("luau_load", FunctionOffset::new(0x100001000, 0.9, "pattern"));
+        old.add_function("rbx_crash", FunctionOffset::new(0x100002000, 0.9, "pattern"));
+
+        let mut new = OffsetOutput::new("new");
+        new.add_function("luau_load", FunctionOffset::new(0x100001040, 0.9, "pattern"));
+        new.add_function("GetDataModel", FunctionOffset::new(0x100003000, 0.9, "pattern"));
+
+        let mut diff = generate_diff(&old, &new);
+        assert_eq!(diff.summary.total_changes, 3);
+
+        diff.retain(|kind, _, change| kind == "function" && change != ChangeType::Removed);
+        assert_eq!(diff.summary.total_changes, 2);
+        assert_eq!(diff.summary.functions_removed, 0);
+        assert!(diff.function_diff.removed.is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::output::OffsetOutput;
 use crate::output::formatter::OutputFormatter;
+use serde_json::Value;
 use std::fs::File;
 use std::io::{Write, BufWriter};
 use std::path::Path;
@@ -25,6 +26,10 @@ pub enum ExportFormat {
     GhidraScript,
     CheatEngine,
     FridaScript,
+    /// One row per offset: kind, name, address, confidence, category
+    Csv,
+    /// The `OffsetOutput` structure as YAML
+    Yaml,
 }
 
 impl OffsetExporter {
@@ -64,6 +69,8 @@ impl OffsetExporter {
             ExportFormat::GhidraScript => self.export_ghidra_script(output),
             ExportFormat::CheatEngine => self.export_cheat_engine(output),
             ExportFormat::FridaScript => self.export_frida_script(output),
+            ExportFormat::Csv => self.export_csv(output),
+            ExportFormat::Yaml => self.export_yaml(output),
         }
     }
 
@@ -75,6 +82,18 @@ impl OffsetExporter {
         Ok(())
     }
 
+    /// `output` as MessagePack with named fields; `OffsetOutput::from_msgpack`
+    /// reads it back
+    pub fn export_msgpack(&self, output: &OffsetOutput) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec_named(output)
+    }
+
+    pub fn export_msgpack_to_file(&self, output: &OffsetOutput, path: &Path) -> std::io::Result<()> {
+        let bytes = self.export_msgpack(output)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, bytes)
+    }
+
     fn export_cpp_header(&self, output: &OffsetOutput) -> String {
         let mut code = String::new();
 
@@ -497,6 +516,73 @@ impl OffsetExporter {
         code
     }
 
+    fn export_csv(&self, output: &OffsetOutput) -> String {
+        let mut csv = String::from("kind,name,address,confidence,category\n");
+        let mut row = |kind: &str, name: &str, address: u64, confidence: Option<f64>, category: &str| {
+            csv.push_str(&format!("{},{},0x{:x},{},{}\n",
+                kind,
+                csv_field(name),
+                address,
+                confidence.map(|c| format!("{:.4}", c)).unwrap_or_default(),
+                csv_field(category),
+            ));
+        };
+
+        let mut functions: Vec<_> = output.functions.iter().collect();
+        functions.sort_by_key(|(name, _)| *name);
+        for (name, func) in functions {
+            row("function", name, func.address, Some(func.confidence), &func.category);
+        }
+
+        let mut structures: Vec<_> = output.structure_offsets.iter().collect();
+        structures.sort_by_key(|(name, _)| *name);
+        for (struct_name, structure) in structures {
+            let mut fields: Vec<_> = structure.fields.iter().collect();
+            fields.sort_by_key(|(_, field)| field.offset);
+            for (field_name, field) in fields {
+                row("field", &format!("{}.{}", struct_name, field_name), field.offset as u64, None, struct_name);
+            }
+        }
+
+        for class in &output.classes {
+            if let Some(vtable) = class.vtable_address {
+                row("vtable", &class.name, vtable, None, "class");
+            }
+        }
+
+        for property in &output.properties {
+            if let Some(offset) = property.offset {
+                row("property", &format!("{}.{}", property.class_name, property.name), offset as u64, None, &property.class_name);
+            }
+        }
+
+        for method in &output.methods {
+            row("method", &format!("{}.{}", method.class_name, method.name), method.address, None, &method.class_name);
+        }
+
+        for constant in &output.constants {
+            row("constant", &constant.name, constant.address, None, &constant.category);
+        }
+
+        csv
+    }
+
+    fn export_yaml(&self, output: &OffsetOutput) -> String {
+        let mut yaml = String::new();
+
+        if self.include_comments {
+            yaml.push_str(&format!("# Generated: {}\n", output.generated_at));
+            yaml.push_str(&format!("# Target: {}\n", output.target.name));
+        }
+
+        match serde_json::to_value(output) {
+            Ok(value) => write_yaml(&value, 0, &mut yaml),
+            Err(e) => yaml.push_str(&format!("# Failed to serialize: {}\n", e)),
+        }
+
+        yaml
+    }
+
     fn sanitize_cpp_name(name: &str) -> String {
         name.chars()
             .map(|c| if c.is_alphanumeric() { c } else { '_' })
@@ -528,6 +614,78 @@ impl Default for OffsetExporter {
     }
 }
 
+/// `field` quoted when it holds a comma, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Block-style YAML for a map or list at `indent` levels. Strings are
+/// written double-quoted, which JSON escaping already makes valid YAML.
+fn write_yaml(value: &Value, indent: usize, out: &mut String) {
+    let pad = "  ".repeat(indent);
+
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map {
+                out.push_str(&format!("{}{}:", pad, yaml_key(key)));
+                write_yaml_item(item, indent, out);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                if is_yaml_block(item) {
+                    // Nest one level deeper, then put the dash over the first line's indent
+                    let mut block = String::new();
+                    write_yaml(item, indent + 1, &mut block);
+                    out.push_str(&format!("{}- {}", pad, &block[pad.len() + 2..]));
+                } else {
+                    out.push_str(&format!("{}- {}\n", pad, yaml_scalar(item)));
+                }
+            }
+        }
+        scalar => out.push_str(&format!("{}{}\n", pad, yaml_scalar(scalar))),
+    }
+}
+
+/// The rest of a `key:` line, and the block below it when `item` has one
+fn write_yaml_item(item: &Value, indent: usize, out: &mut String) {
+    if is_yaml_block(item) {
+        out.push('\n');
+        write_yaml(item, indent + 1, out);
+    } else {
+        out.push_str(&format!(" {}\n", yaml_scalar(item)));
+    }
+}
+
+fn is_yaml_block(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => !map.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Object(_) => "{}".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn yaml_key(key: &str) -> String {
+    let plain = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+    if plain {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
 pub fn export_to_cpp(output: &OffsetOutput) -> String {
     OffsetExporter::new().export(output, ExportFormat::CppHeader)
 }
@@ -547,3 +705,51 @@ pub fn export_to_python(output: &OffsetOutput) -> String {
 pub fn export_to_frida(output: &OffsetOutput) -> String {
     OffsetExporter::new().export(output, ExportFormat::FridaScript)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::{ClassOffset, FunctionOffset, StructureOffsets};
+
+    #[test]
+    fn test_msgpack_round_trips_offset_output() {
+        let mut output = OffsetOutput::new("RobloxPlayer");
+        output.target.hash = Some("abcd".to_string());
+        output.add_function("luau_load", FunctionOffset::new(0x100001000, 0.9, "string_xref").with_category("lua_api"));
+        let mut structure = StructureOffsets::new(0x20, 8);
+        structure.add_field("top", 0x10, 8, "StkId");
+        output.add_structure("lua_State", structure);
+        output.add_class(ClassOffset::new("Part").with_size(0x140));
+
+        let bytes = OffsetExporter::new().export_msgpack(&output).unwrap();
+        let decoded = OffsetOutput::from_msgpack(&bytes).unwrap();
+
+        assert!(bytes.len() < serde_json::to_vec(&output).unwrap().len());
+        assert_eq!(decoded.to_scan_json(), output.to_scan_json());
+        assert_eq!(decoded.target.hash.as_deref(), Some("abcd"));
+        assert_eq!(decoded.classes[0].size, 0x140);
+    }
+
+    #[test]
+    fn test_csv_and_yaml_exports() {
+        let mut output = OffsetOutput::new("RobloxPlayer");
+        output.add_function("luau_load", FunctionOffset::new(0x100001000, 0.9, "string_xref").with_category("lua_api"));
+        let mut structure = StructureOffsets::new(0x20, 8);
+        structure.add_field("top", 0x10, 8, "StkId");
+        output.add_structure("lua_State", structure);
+        output.add_class(ClassOffset::new("Part, Model"));
+
+        let exporter = OffsetExporter::new();
+        let csv = exporter.export(&output, ExportFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "kind,name,address,confidence,category");
+        assert_eq!(lines[1], "function,luau_load,0x100001000,0.9000,lua_api");
+        assert_eq!(lines[2], "field,lua_State.top,0x10,,lua_State");
+        assert_eq!(lines.len(), 3);
+
+        let yaml = exporter.export(&output, ExportFormat::Yaml);
+        assert!(yaml.contains("\nfunctions:\n  luau_load:\n    address: 4294971392\n"));
+        assert!(yaml.contains("    fields:\n      top:\n        field_type: \"StkId\"\n"));
+        assert!(yaml.contains("classes:\n  - methods: []\n    name: \"Part, Model\"\n"));
+    }
+}
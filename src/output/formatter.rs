@@ -397,6 +397,50 @@ impl OutputFormatter {
         lua_code.push_str("}\n\nreturn Offsets\n");
         lua_code
     }
+
+    /// A flat Luau table for scripts: functions as `Name = 0x...`, then
+    /// each structure as a nested table of field offsets. A structure named
+    /// like a function is keyed `<name>_fields` so neither is overwritten.
+    pub fn format_luau(&self, output: &OffsetOutput) -> String {
+        let mut code = String::new();
+
+        code.push_str(&format!("-- Generated: {}\n", output.generated_at));
+        if !output.target.name.is_empty() {
+            code.push_str(&format!("-- Target: {}\n", output.target.name));
+        }
+        code.push_str("\nlocal Offsets = {\n");
+
+        let mut functions: Vec<_> = output.functions.iter().collect();
+        functions.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (name, func) in &functions {
+            code.push_str(&format!("    {} = 0x{:x},\n", luau_key(name), func.address));
+        }
+
+        let mut structures: Vec<_> = output.structure_offsets.iter().collect();
+        structures.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (struct_name, structure) in structures {
+            let key = if output.functions.contains_key(struct_name) {
+                format!("{}_fields", struct_name)
+            } else {
+                struct_name.clone()
+            };
+
+            code.push_str(&format!("\n    {} = {{\n", luau_key(&key)));
+
+            let mut fields: Vec<_> = structure.fields.iter().collect();
+            fields.sort_by(|a, b| a.1.offset.cmp(&b.1.offset).then(a.0.cmp(b.0)));
+
+            for (field_name, field) in fields {
+                code.push_str(&format!("        {} = 0x{:x},\n", luau_key(field_name), field.offset));
+            }
+            code.push_str("    },\n");
+        }
+
+        code.push_str("}\n\nreturn Offsets\n");
+        code
+    }
 }
 
 impl Default for OutputFormatter {
@@ -405,6 +449,39 @@ impl Default for OutputFormatter {
     }
 }
 
+const LUAU_KEYWORDS: &[&str] = &[
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "if",
+    "in", "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+/// Whether `name` can be written as a bare Luau table key
+pub fn is_luau_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let starts_ok = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+
+    starts_ok && chars.all(|c| c.is_ascii_alphanumeric() || c == '_') && !LUAU_KEYWORDS.contains(&name)
+}
+
+/// `name` as a table key: bare when it is an identifier, else `["quoted"]`
+pub fn luau_key(name: &str) -> String {
+    if is_luau_identifier(name) {
+        return name.to_string();
+    }
+
+    let mut quoted = String::from("[\"");
+    for c in name.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push_str("\"]");
+    quoted
+}
+
 pub fn format_hex(value: u64) -> String {
     format!("0x{:016x}", value)
 }
@@ -416,3 +493,28 @@ pub fn format_hex_short(value: u64) -> String {
 pub fn format_confidence(confidence: f64) -> String {
     format!("{:.1}%", confidence * 100.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_luau_keys_and_nested_structures() {
+        assert_eq!(luau_key("LuauLoad"), "LuauLoad");
+        assert_eq!(luau_key("end"), "[\"end\"]");
+        assert_eq!(luau_key("2x"), "[\"2x\"]");
+        assert_eq!(luau_key("a.b \"c\""), "[\"a.b \\\"c\\\"\"]");
+
+        let mut output = OffsetOutput::new("RobloxPlayer");
+        output.add_function("TaskScheduler", FunctionOffset::new(0x1000, 0.9, "pattern"));
+        output.add_function("Job::Run", FunctionOffset::new(0x2000, 0.9, "pattern"));
+        let mut scheduler = StructureOffsets::new(0, 8);
+        scheduler.add_field("jobs", 0x10, 8, "unknown");
+        output.structure_offsets.insert("TaskScheduler".to_string(), scheduler);
+
+        let luau = OutputFormatter::new().format_luau(&output);
+        assert!(luau.contains("local Offsets = {\n    [\"Job::Run\"] = 0x2000,\n    TaskScheduler = 0x1000,\n"));
+        assert!(luau.contains("    TaskScheduler_fields = {\n        jobs = 0x10,\n    },\n"));
+        assert!(luau.ends_with("}\n\nreturn Offsets\n"));
+    }
+}
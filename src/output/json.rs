@@ -169,6 +169,11 @@ impl JsonSerializer {
     fn serialize_classes(&self, classes: &[ClassOffset]) -> Result<Value, JsonError> {
         let mut arr = Vec::new();
 
+        let mut classes: Vec<_> = classes.iter().collect();
+        if self.sort_keys {
+            classes.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
         for class in classes {
             arr.push(json!({
                 "name": class.name,
@@ -186,6 +191,11 @@ impl JsonSerializer {
     fn serialize_properties(&self, properties: &[PropertyOffset]) -> Result<Value, JsonError> {
         let mut arr = Vec::new();
 
+        let mut properties: Vec<_> = properties.iter().collect();
+        if self.sort_keys {
+            properties.sort_by(|a, b| (&a.class_name, &a.name, a.getter).cmp(&(&b.class_name, &b.name, b.getter)));
+        }
+
         for prop in properties {
             arr.push(json!({
                 "name": prop.name,
@@ -203,6 +213,11 @@ impl JsonSerializer {
     fn serialize_methods(&self, methods: &[MethodOffset]) -> Result<Value, JsonError> {
         let mut arr = Vec::new();
 
+        let mut methods: Vec<_> = methods.iter().collect();
+        if self.sort_keys {
+            methods.sort_by(|a, b| (&a.class_name, &a.name, a.address).cmp(&(&b.class_name, &b.name, b.address)));
+        }
+
         for method in methods {
             arr.push(json!({
                 "name": method.name,
@@ -220,6 +235,11 @@ impl JsonSerializer {
     fn serialize_constants(&self, constants: &[ConstantOffset]) -> Result<Value, JsonError> {
         let mut arr = Vec::new();
 
+        let mut constants: Vec<_> = constants.iter().collect();
+        if self.sort_keys {
+            constants.sort_by(|a, b| (&a.name, a.address).cmp(&(&b.name, b.address)));
+        }
+
         for constant in constants {
             let value = match &constant.value {
                 crate::output::ConstantValue::Integer(i) => json!(i),
@@ -407,3 +427,45 @@ pub fn from_json_string(json_str: &str) -> Result<OffsetOutput, JsonError> {
 pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<OffsetOutput, JsonError> {
     JsonSerializer::new().deserialize_from_file(path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finders::result::{CombinedResults, FinderResult, MethodResult, PropertyResult, StructureOffsetResult};
+    use crate::memory::Address;
+
+    fn results(reverse: bool) -> CombinedResults {
+        let mut results = CombinedResults::new();
+        let mut names = vec!["LuauLoad", "GetDataModel", "TaskScheduler", "PushInstance"];
+        if reverse {
+            names.reverse();
+        }
+
+        for name in &names {
+            let address = Address::new(0x1000 + name.len() as u64 * 0x10);
+            results.add_function(FinderResult::new(name.to_string(), address, 0.9));
+            results.add_structure_offset(StructureOffsetResult::new(format!("S{}", name.len() % 2), name.to_string(), name.len() as u64));
+            results.add_property(PropertyResult::new("Instance".to_string(), name.to_string()));
+            results.add_method(MethodResult::new("Instance".to_string(), name.to_string(), address));
+        }
+
+        results
+    }
+
+    #[test]
+    fn test_output_independent_of_insertion_order() {
+        let forward = results(false);
+        let reverse = results(true);
+
+        assert_eq!(
+            serde_json::to_string_pretty(&forward.to_json_map()).unwrap(),
+            serde_json::to_string_pretty(&reverse.to_json_map()).unwrap()
+        );
+
+        let serializer = JsonSerializer::new().with_metadata(false);
+        assert_eq!(
+            serializer.serialize(&OffsetOutput::from_results("t", &forward, 0)).unwrap(),
+            serializer.serialize(&OffsetOutput::from_results("t", &reverse, 0)).unwrap()
+        );
+    }
+}
@@ -0,0 +1,262 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::output::{OffsetOutput, StructureOffsets, SCAN_SCHEMA_VERSION};
+use std::collections::BTreeMap;
+
+/// Two inputs that resolved the same name to different addresses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub name: String,
+    pub kept_source: String,
+    pub kept_address: u64,
+    pub dropped_source: String,
+    pub dropped_address: u64,
+}
+
+/// Offset files combined into one. `sources` maps every merged name
+/// (functions, classes and `Struct.field` offsets) to the input it came from.
+#[derive(Debug, Clone)]
+pub struct MergedOffsets {
+    pub output: OffsetOutput,
+    pub sources: BTreeMap<String, String>,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+pub struct OffsetMerger {
+    merged: MergedOffsets,
+    inputs: usize,
+}
+
+impl OffsetMerger {
+    pub fn new(target_name: &str) -> Self {
+        Self {
+            merged: MergedOffsets {
+                output: OffsetOutput::new(target_name),
+                sources: BTreeMap::new(),
+                conflicts: Vec::new(),
+            },
+            inputs: 0,
+        }
+    }
+
+    /// Adds one input. Functions keep the higher-confidence entry; structure
+    /// fields and classes carry no confidence, so the earlier input wins.
+    /// Inputs are moved onto the first input's base before comparing.
+    pub fn add(&mut self, source: &str, input: &OffsetOutput) {
+        let output = &mut self.merged.output;
+        self.inputs += 1;
+
+        let rebased;
+        let input = if self.inputs == 1 {
+            let name = std::mem::take(&mut output.target.name);
+            output.target = input.target.clone();
+            output.target.name = name;
+            input
+        } else if input.effective_base() != output.effective_base() {
+            let mut copy = input.clone();
+            copy.rebase(output.effective_base());
+            rebased = copy;
+            &rebased
+        } else {
+            input
+        };
+
+        let mut names: Vec<&String> = input.functions.keys().collect();
+        names.sort();
+
+        for name in names {
+            let incoming = &input.functions[name];
+
+            match output.functions.get(name) {
+                None => {
+                    output.add_function(name, incoming.clone());
+                    self.merged.sources.insert(name.clone(), source.to_string());
+                }
+                Some(existing) => {
+                    let existing_source = self.merged.sources[name].clone();
+                    let replace = incoming.confidence > existing.confidence;
+
+                    if existing.address != incoming.address {
+                        let (kept, dropped) = if replace {
+                            ((source.to_string(), incoming.address), (existing_source, existing.address))
+                        } else {
+                            ((existing_source, existing.address), (source.to_string(), incoming.address))
+                        };
+                        self.merged.conflicts.push(MergeConflict {
+                            name: name.clone(),
+                            kept_source: kept.0,
+                            kept_address: kept.1,
+                            dropped_source: dropped.0,
+                            dropped_address: dropped.1,
+                        });
+                    }
+
+                    if replace {
+                        output.add_function(name, incoming.clone());
+                        self.merged.sources.insert(name.clone(), source.to_string());
+                    }
+                }
+            }
+        }
+
+        let mut structures: Vec<&String> = input.structure_offsets.keys().collect();
+        structures.sort();
+
+        for struct_name in structures {
+            let incoming = &input.structure_offsets[struct_name];
+            let mut fields: Vec<&String> = incoming.fields.keys().collect();
+            fields.sort();
+
+            for field_name in fields {
+                let field = &incoming.fields[field_name];
+                let key = format!("{}.{}", struct_name, field_name);
+
+                let existing = output.structure_offsets.get(struct_name)
+                    .and_then(|s| s.get_field(field_name))
+                    .map(|f| f.offset);
+
+                match existing {
+                    None => {
+                        output.structure_offsets
+                            .entry(struct_name.clone())
+                            .or_insert_with(|| StructureOffsets::new(incoming.size, incoming.alignment))
+                            .add_field(field_name, field.offset, field.size, &field.field_type);
+                        self.merged.sources.insert(key, source.to_string());
+                    }
+                    Some(offset) if offset != field.offset => {
+                        self.merged.conflicts.push(MergeConflict {
+                            kept_source: self.merged.sources[&key].clone(),
+                            kept_address: offset as u64,
+                            dropped_source: source.to_string(),
+                            dropped_address: field.offset as u64,
+                            name: key,
+                        });
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        for class in &input.classes {
+            match output.get_class(&class.name) {
+                None => {
+                    output.add_class(class.clone());
+                    self.merged.sources.insert(class.name.clone(), source.to_string());
+                }
+                Some(existing) => {
+                    if let (Some(kept), Some(dropped)) = (existing.vtable_address, class.vtable_address) {
+                        if kept != dropped {
+                            self.merged.conflicts.push(MergeConflict {
+                                name: class.name.clone(),
+                                kept_source: self.merged.sources[&class.name].clone(),
+                                kept_address: kept,
+                                dropped_source: source.to_string(),
+                                dropped_address: dropped,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        output.compute_statistics();
+    }
+
+    pub fn finish(self) -> MergedOffsets {
+        self.merged
+    }
+}
+
+impl MergedOffsets {
+    /// Same layout `scan` writes, so the result loads anywhere a scan file
+    /// does, with a `source` on every entry.
+    pub fn to_scan_json(&self) -> serde_json::Value {
+        let output = &self.output;
+        let source = |name: &str| self.sources.get(name).cloned();
+
+        let functions: BTreeMap<&String, serde_json::Value> = output.functions.iter()
+            .map(|(name, func)| (name, serde_json::json!({
+                "address": format!("0x{:x}", func.address),
+                "confidence": func.confidence,
+                "method": func.discovery_method,
+                "category": func.category,
+                "signature": func.signature,
+                "source": source(name),
+            })))
+            .collect();
+
+        let structures: BTreeMap<&String, BTreeMap<&String, serde_json::Value>> = output.structure_offsets.iter()
+            .map(|(struct_name, structure)| (struct_name, structure.fields.iter()
+                .map(|(field_name, field)| (field_name, serde_json::json!({
+                    "offset": format!("0x{:x}", field.offset),
+                    "size": field.size,
+                    "source": source(&format!("{}.{}", struct_name, field_name)),
+                })))
+                .collect()))
+            .collect();
+
+        let classes: BTreeMap<&String, serde_json::Value> = output.classes.iter()
+            .map(|class| (&class.name, serde_json::json!({
+                "vtable": class.vtable_address.map(|v| format!("0x{:x}", v)),
+                "size": class.size,
+                "parent": class.parent,
+                "source": source(&class.name),
+            })))
+            .collect();
+
+        serde_json::json!({
+            "schema_version": SCAN_SCHEMA_VERSION,
+            "generated_at": output.generated_at,
+            "target": {
+                "base_address": format!("0x{:x}", output.target.base_address),
+                "relative": output.target.relative,
+                "platform": output.target.platform,
+            },
+            "functions": functions,
+            "structure_offsets": structures,
+            "classes": classes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::FunctionOffset;
+
+    #[test]
+    fn test_merge_prefers_confidence_and_reports_conflicts() {
+        let mut a = OffsetOutput::new("a");
+        a.add_function("luau_load", FunctionOffset::new(0x100001000, 0.6, "pattern"));
+        a.add_function("rbx_crash", FunctionOffset::new(0x100002000, 0.9, "string_xref"));
+
+        let mut b = OffsetOutput::new("b");
+        b.add_function("luau_load", FunctionOffset::new(0x100001040, 0.95, "xref"));
+        b.add_function("GetDataModel", FunctionOffset::new(0x100003000, 0.8, "string_xref"));
+        b.make_relative();
+
+        let mut merger = OffsetMerger::new("merged");
+        merger.add("a.json", &a);
+        merger.add("b.json", &b);
+        let merged = merger.finish();
+
+        assert_eq!(merged.output.functions.len(), 3);
+        assert_eq!(merged.output.functions["luau_load"].address, 0x100001040);
+        assert_eq!(merged.sources["luau_load"], "b.json");
+        assert_eq!(merged.sources["rbx_crash"], "a.json");
+        assert_eq!(merged.output.functions["GetDataModel"].address, 0x100003000);
+
+        assert_eq!(merged.conflicts, vec![MergeConflict {
+            name: "luau_load".to_string(),
+            kept_source: "b.json".to_string(),
+            kept_address: 0x100001040,
+            dropped_source: "a.json".to_string(),
+            dropped_address: 0x100001000,
+        }]);
+
+        let json = merged.to_scan_json();
+        assert_eq!(json["functions"]["luau_load"]["source"], "b.json");
+        let reloaded = OffsetOutput::from_scan_json("merged", &json);
+        assert_eq!(reloaded.functions["rbx_crash"].address, 0x100002000);
+    }
+}
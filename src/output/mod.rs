@@ -8,6 +8,8 @@ pub mod exporter;
 pub mod template;
 pub mod diff;
 pub mod stats;
+pub mod merge;
+pub mod sqlite;
 
 pub use json::JsonSerializer;
 pub use report::ReportGenerator;
@@ -16,23 +18,39 @@ pub use formatter::OutputFormatter;
 pub use exporter::OffsetExporter;
 pub use template::TemplateEngine;
 pub use diff::DiffGenerator;
-pub use stats::StatisticsCollector;
+pub use stats::{ConfidenceBucket, StatisticsCollector};
+pub use merge::{OffsetMerger, MergedOffsets, MergeConflict};
+pub use sqlite::SqliteExporter;
 
-use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
+use crate::analysis::FunctionKind;
+use crate::finders::result::{self as finder_result, CombinedResults};
+use serde::{Serialize, Serializer, Deserialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Layout version of the JSON written by `scan`. Files that predate the
+/// `schema_version` field use the version 1 layout.
+pub const SCAN_SCHEMA_VERSION: u64 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OffsetOutput {
     pub version: String,
     pub generated_at: String,
     pub target: TargetInfo,
+    #[serde(serialize_with = "serialize_sorted")]
     pub functions: HashMap<String, FunctionOffset>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub structure_offsets: HashMap<String, StructureOffsets>,
     pub classes: Vec<ClassOffset>,
     pub properties: Vec<PropertyOffset>,
     pub methods: Vec<MethodOffset>,
     pub constants: Vec<ConstantOffset>,
     pub statistics: OutputStatistics,
+    /// Outcome of every roblox finder, including those that found nothing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub finder_report: Vec<FinderReportEntry>,
+    /// Addresses more than one function name resolved to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<AddressCollision>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +61,9 @@ pub struct TargetInfo {
     pub version: Option<String>,
     pub hash: Option<String>,
     pub base_address: u64,
+    /// Addresses are deltas from the image base rather than absolute
+    #[serde(default)]
+    pub relative: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,10 +73,14 @@ pub struct FunctionOffset {
     pub discovery_method: String,
     pub signature: Option<String>,
     pub category: String,
+    /// Whether the address is a real body, a tail-call thunk or a stub
+    #[serde(default)]
+    pub kind: FunctionKind,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StructureOffsets {
+    #[serde(serialize_with = "serialize_sorted")]
     pub fields: HashMap<String, FieldOffset>,
     pub size: usize,
     pub alignment: usize,
@@ -66,6 +91,11 @@ pub struct FieldOffset {
     pub offset: usize,
     pub size: usize,
     pub field_type: String,
+    /// Set for fields a scan found, absent for hand-written layouts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discovery_method: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,6 +146,62 @@ pub enum ConstantValue {
     Unknown,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinderReportEntry {
+    pub finder: String,
+    pub offset: String,
+    pub status: FinderStatus,
+    pub address: Option<u64>,
+    pub confidence: Option<f64>,
+    pub discovery_method: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinderStatus {
+    Found,
+    /// Matched, but dropped by the confidence filter
+    BelowThreshold,
+    NotFound,
+}
+
+/// Several function names resolved to one address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressCollision {
+    pub address: u64,
+    /// Sorted
+    pub names: Vec<String>,
+    pub kind: CollisionKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionKind {
+    /// The names spell the same function differently (`LuauLoad`, `luau_load`)
+    Alias,
+    /// Unrelated names: likely a finder latching onto the wrong function
+    Collision,
+}
+
+impl CollisionKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CollisionKind::Alias => "alias",
+            CollisionKind::Collision => "collision",
+        }
+    }
+}
+
+impl FinderStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FinderStatus::Found => "found",
+            FinderStatus::BelowThreshold => "below threshold",
+            FinderStatus::NotFound => "not found",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct OutputStatistics {
     pub total_functions: usize,
@@ -144,6 +230,7 @@ impl OffsetOutput {
                 version: None,
                 hash: None,
                 base_address: 0x100000000,
+                relative: false,
             },
             functions: HashMap::new(),
             structure_offsets: HashMap::new(),
@@ -152,9 +239,82 @@ impl OffsetOutput {
             methods: Vec::new(),
             constants: Vec::new(),
             statistics: OutputStatistics::default(),
+            finder_report: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
+    /// Convert the raw results of a scan into the serializable output model
+    pub fn from_results(target_name: &str, results: &CombinedResults, base_address: u64) -> Self {
+        let mut output = Self::new(target_name);
+        output.set_base_address(base_address);
+
+        for func in &results.functions {
+            let mut offset = FunctionOffset::new(func.address.as_u64(), func.confidence, &func.method)
+                .with_category(&func.category);
+            offset.signature = func.signature.clone();
+            offset.kind = func.kind;
+            output.add_function(&func.name, offset);
+        }
+
+        for field in &results.structure_offsets {
+            let size = field.size.unwrap_or(0) as usize;
+            let structure = output.structure_offsets
+                .entry(field.structure_name.clone())
+                .or_insert_with(|| StructureOffsets::new(0, 8));
+            structure.add_field(&field.field_name, field.offset as usize, size, "unknown");
+            structure.score_field(&field.field_name, field.confidence, &field.method);
+        }
+
+        for class in &results.classes {
+            let mut offset = ClassOffset::new(&class.name).with_size(class.size.unwrap_or(0) as usize);
+            offset.vtable_address = class.vtable_address.map(|v| v.as_u64());
+            offset.parent = class.parent_class.clone();
+            output.add_class(offset);
+        }
+
+        for prop in &results.properties {
+            output.add_property(PropertyOffset {
+                name: prop.property_name.clone(),
+                class_name: prop.class_name.clone(),
+                getter: prop.getter_address.map(|a| a.as_u64()),
+                setter: prop.setter_address.map(|a| a.as_u64()),
+                offset: prop.offset.map(|o| o as usize),
+                property_type: prop.property_type.clone().unwrap_or_else(|| "unknown".to_string()),
+            });
+        }
+
+        for method in &results.methods {
+            output.add_method(MethodOffset {
+                name: method.method_name.clone(),
+                class_name: method.class_name.clone(),
+                address: method.address.as_u64(),
+                vtable_index: method.vtable_index.map(|i| i as usize),
+                is_virtual: method.is_virtual,
+                signature: method.signature.clone(),
+            });
+        }
+
+        for constant in &results.constants {
+            let value = match &constant.value {
+                finder_result::ConstantValue::Integer(i) => ConstantValue::Integer(*i),
+                finder_result::ConstantValue::Float(f) => ConstantValue::Float(*f),
+                finder_result::ConstantValue::String(s) => ConstantValue::String(s.clone()),
+                finder_result::ConstantValue::Pointer(p) => ConstantValue::Address(p.as_u64()),
+                finder_result::ConstantValue::Unknown => ConstantValue::Unknown,
+            };
+            output.add_constant(ConstantOffset {
+                name: constant.name.clone(),
+                address: constant.address.as_u64(),
+                value,
+                category: "unknown".to_string(),
+            });
+        }
+
+        output.compute_statistics();
+        output
+    }
+
     pub fn add_function(&mut self, name: &str, offset: FunctionOffset) {
         self.functions.insert(name.to_string(), offset);
         self.statistics.total_functions = self.functions.len();
@@ -193,10 +353,162 @@ impl OffsetOutput {
         self.target.hash = Some(hash.to_string());
     }
 
+    /// Rebuild the output model from a JSON file written by `scan`. Entries
+    /// that don't parse are skipped rather than failing the whole file.
+    pub fn from_scan_json(target_name: &str, json: &serde_json::Value) -> Self {
+        let mut output = Self::new(target_name);
+        output.generated_at = json["generated_at"].as_str().unwrap_or_default().to_string();
+
+        let target = &json["target"];
+        output.target.relative = target["relative"].as_bool().unwrap_or(false);
+        if let Some(base) = parse_hex_value(&target["base_address"]) {
+            output.set_base_address(base);
+        }
+
+        if let Some(functions) = json["functions"].as_object() {
+            for (name, func) in functions {
+                if let Some(address) = parse_hex_value(&func["address"]) {
+                    let mut offset = FunctionOffset::new(
+                        address,
+                        func["confidence"].as_f64().unwrap_or(0.0),
+                        func["method"].as_str().unwrap_or("unknown"),
+                    ).with_category(func["category"].as_str().unwrap_or("unknown"));
+                    offset.signature = func["signature"].as_str().map(str::to_string);
+                    offset.kind = serde_json::from_value(func["kind"].clone()).unwrap_or_default();
+                    output.add_function(name, offset);
+                }
+            }
+        }
+
+        if let Some(structures) = json["structure_offsets"].as_object() {
+            for (struct_name, fields) in structures {
+                let mut structure = StructureOffsets::new(0, 8);
+                for (field_name, field) in fields.as_object().into_iter().flatten() {
+                    if let Some(offset) = parse_hex_value(&field["offset"]) {
+                        let size = field["size"].as_u64().unwrap_or(0) as usize;
+                        structure.add_field(field_name, offset as usize, size, "unknown");
+                        if let Some(confidence) = field["confidence"].as_f64() {
+                            structure.score_field(field_name, confidence, field["method"].as_str().unwrap_or("unknown"));
+                        }
+                    }
+                }
+                output.add_structure(struct_name, structure);
+            }
+        }
+
+        if let Some(classes) = json["classes"].as_object() {
+            for (name, class) in classes {
+                let mut offset = ClassOffset::new(name)
+                    .with_size(class["size"].as_u64().unwrap_or(0) as usize);
+                offset.vtable_address = parse_hex_value(&class["vtable"]);
+                offset.parent = class["parent"].as_str().map(str::to_string);
+                output.add_class(offset);
+            }
+        }
+
+        output.compute_statistics();
+        output
+    }
+
+    /// Reads what `OffsetExporter::export_msgpack` wrote
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
+
+    /// The layout `scan` writes, for code that reads scan files
+    /// (`from_scan_json` reads it back)
+    pub fn to_scan_json(&self) -> serde_json::Value {
+        let functions: BTreeMap<&String, serde_json::Value> = self.functions.iter()
+            .map(|(name, func)| (name, serde_json::json!({
+                "address": format!("0x{:x}", func.address),
+                "confidence": func.confidence,
+                "method": func.discovery_method,
+                "category": func.category,
+                "signature": func.signature,
+                "kind": func.kind.as_str(),
+            })))
+            .collect();
+
+        let structures: BTreeMap<&String, BTreeMap<&String, serde_json::Value>> = self.structure_offsets.iter()
+            .map(|(struct_name, structure)| (struct_name, structure.fields.iter()
+                .map(|(field_name, field)| (field_name, serde_json::json!({
+                    "offset": format!("0x{:x}", field.offset),
+                    "size": field.size,
+                    "confidence": field.confidence,
+                    "method": field.discovery_method,
+                })))
+                .collect()))
+            .collect();
+
+        let classes: BTreeMap<&String, serde_json::Value> = self.classes.iter()
+            .map(|class| (&class.name, serde_json::json!({
+                "vtable": class.vtable_address.map(|v| format!("0x{:x}", v)),
+                "size": class.size,
+                "parent": class.parent,
+            })))
+            .collect();
+
+        serde_json::json!({
+            "schema_version": SCAN_SCHEMA_VERSION,
+            "generated_at": self.generated_at,
+            "target": {
+                "base_address": format!("0x{:x}", self.target.base_address),
+                "relative": self.target.relative,
+                "platform": self.target.platform,
+            },
+            "functions": functions,
+            "structure_offsets": structures,
+            "classes": classes,
+        })
+    }
+
     pub fn set_base_address(&mut self, addr: u64) {
         self.target.base_address = addr;
     }
 
+    /// Move every absolute address from the current base onto `new_base`.
+    /// Structure field offsets are base-independent and left untouched.
+    pub fn rebase(&mut self, new_base: u64) {
+        let old_base = if self.target.relative { 0 } else { self.target.base_address };
+        let shift = |addr: u64| addr.wrapping_sub(old_base).wrapping_add(new_base);
+
+        for func in self.functions.values_mut() {
+            func.address = shift(func.address);
+        }
+        for class in &mut self.classes {
+            class.vtable_address = class.vtable_address.map(shift);
+        }
+        for prop in &mut self.properties {
+            prop.getter = prop.getter.map(shift);
+            prop.setter = prop.setter.map(shift);
+        }
+        for method in &mut self.methods {
+            method.address = shift(method.address);
+        }
+        for constant in &mut self.constants {
+            constant.address = shift(constant.address);
+            if let ConstantValue::Address(value) = &mut constant.value {
+                *value = shift(*value);
+            }
+        }
+
+        self.target.base_address = new_base;
+        self.target.relative = false;
+    }
+
+    /// Express every address as a delta from the image base
+    pub fn make_relative(&mut self) {
+        let base = self.target.base_address;
+        self.rebase(0);
+        self.target.base_address = base;
+        self.target.relative = true;
+    }
+
+    /// Effective base the stored addresses are anchored at
+    pub fn effective_base(&self) -> u64 {
+        if self.target.relative { 0 } else { self.target.base_address }
+    }
+
     pub fn compute_statistics(&mut self) {
         self.statistics.total_functions = self.functions.len();
         self.statistics.total_structures = self.structure_offsets.len();
@@ -246,10 +558,7 @@ impl OffsetOutput {
 }
 
 fn chrono_now() -> String {
-    let now = std::time::SystemTime::now();
-    let duration = now.duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
-    let secs = duration.as_secs();
-    format!("{}", secs)
+    crate::utils::time::format_timestamp(crate::utils::time::generated_secs())
 }
 
 impl FunctionOffset {
@@ -260,6 +569,7 @@ impl FunctionOffset {
             discovery_method: method.to_string(),
             signature: None,
             category: "unknown".to_string(),
+            kind: FunctionKind::Function,
         }
     }
 
@@ -274,6 +584,17 @@ impl FunctionOffset {
     }
 }
 
+fn parse_hex_value(value: &serde_json::Value) -> Option<u64> {
+    let s = value.as_str()?;
+    let hex = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X"))?;
+    u64::from_str_radix(hex, 16).ok()
+}
+
+/// Serializes a `HashMap` with its keys in sorted order so output is stable.
+fn serialize_sorted<V: Serialize, S: Serializer>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
 impl StructureOffsets {
     pub fn new(size: usize, alignment: usize) -> Self {
         Self {
@@ -288,12 +609,37 @@ impl StructureOffsets {
             offset,
             size,
             field_type: field_type.to_string(),
+            confidence: None,
+            discovery_method: None,
         });
     }
 
+    /// Records how a scan found field `name`, if it exists
+    pub fn score_field(&mut self, name: &str, confidence: f64, method: &str) {
+        if let Some(field) = self.fields.get_mut(name) {
+            field.confidence = Some(confidence);
+            field.discovery_method = Some(method.to_string());
+        }
+    }
+
     pub fn get_field(&self, name: &str) -> Option<&FieldOffset> {
         self.fields.get(name)
     }
+
+    /// `size` when known, otherwise the end of the furthest field (fields
+    /// of unknown size count as pointers) rounded up to `alignment`.
+    /// Inferred sizes are lower bounds: fields past the last found one are missed.
+    pub fn inferred_size(&self) -> usize {
+        if self.size > 0 {
+            return self.size;
+        }
+
+        let end = self.fields.values()
+            .map(|f| f.offset + if f.size > 0 { f.size } else { 8 })
+            .max()
+            .unwrap_or(0);
+        end.next_multiple_of(self.alignment.max(1))
+    }
 }
 
 impl ClassOffset {
@@ -0,0 +1,289 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::output::OffsetOutput;
+use rusqlite::{params, Connection, Transaction};
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS targets (
+    id INTEGER PRIMARY KEY,
+    key TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    version TEXT,
+    hash TEXT,
+    platform TEXT NOT NULL,
+    architecture TEXT NOT NULL,
+    base_address INTEGER NOT NULL,
+    relative INTEGER NOT NULL,
+    generated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS functions (
+    target_id INTEGER NOT NULL REFERENCES targets(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    address INTEGER NOT NULL,
+    confidence REAL NOT NULL,
+    discovery_method TEXT NOT NULL,
+    signature TEXT,
+    category TEXT NOT NULL,
+    PRIMARY KEY (target_id, name)
+);
+CREATE TABLE IF NOT EXISTS structure_fields (
+    target_id INTEGER NOT NULL REFERENCES targets(id) ON DELETE CASCADE,
+    structure TEXT NOT NULL,
+    field TEXT NOT NULL,
+    offset INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    field_type TEXT NOT NULL,
+    PRIMARY KEY (target_id, structure, field)
+);
+CREATE TABLE IF NOT EXISTS classes (
+    target_id INTEGER NOT NULL REFERENCES targets(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    vtable_address INTEGER,
+    size INTEGER NOT NULL,
+    parent TEXT,
+    PRIMARY KEY (target_id, name)
+);
+CREATE TABLE IF NOT EXISTS properties (
+    target_id INTEGER NOT NULL REFERENCES targets(id) ON DELETE CASCADE,
+    class_name TEXT NOT NULL,
+    name TEXT NOT NULL,
+    getter INTEGER,
+    setter INTEGER,
+    offset INTEGER,
+    property_type TEXT NOT NULL,
+    PRIMARY KEY (target_id, class_name, name)
+);
+CREATE TABLE IF NOT EXISTS methods (
+    target_id INTEGER NOT NULL REFERENCES targets(id) ON DELETE CASCADE,
+    class_name TEXT NOT NULL,
+    name TEXT NOT NULL,
+    address INTEGER NOT NULL,
+    vtable_index INTEGER,
+    is_virtual INTEGER NOT NULL,
+    signature TEXT,
+    PRIMARY KEY (target_id, class_name, name, address)
+);
+CREATE TABLE IF NOT EXISTS constants (
+    target_id INTEGER NOT NULL REFERENCES targets(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    address INTEGER NOT NULL,
+    value TEXT NOT NULL,
+    category TEXT NOT NULL,
+    PRIMARY KEY (target_id, name)
+);
+";
+
+/// Tables holding rows keyed by target, cleared before a target is written again
+const TARGET_TABLES: &[&str] = &["functions", "structure_fields", "classes", "properties", "methods", "constants"];
+
+/// Writes `OffsetOutput`s into a SQLite database that accumulates one
+/// target per binary, so offsets can be queried across versions. Targets
+/// are keyed by binary hash (falling back to version, then name);
+/// exporting the same target again replaces its rows.
+pub struct SqliteExporter {
+    conn: Connection,
+}
+
+impl SqliteExporter {
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn with_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        conn.execute_batch(SCHEMA)?;
+        migrate_method_key(&conn)?;
+        Ok(Self { conn })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Upserts the target and replaces its offsets; returns the target's id
+    pub fn export(&mut self, output: &OffsetOutput) -> rusqlite::Result<i64> {
+        let tx = self.conn.transaction()?;
+        let target_id = upsert_target(&tx, output)?;
+
+        for table in TARGET_TABLES {
+            tx.execute(&format!("DELETE FROM {} WHERE target_id = ?1", table), [target_id])?;
+        }
+        insert_offsets(&tx, target_id, output)?;
+
+        tx.commit()?;
+        Ok(target_id)
+    }
+}
+
+/// Older databases key methods without the address, so overloads replace
+/// each other; rebuild that table under the current key, keeping its rows
+fn migrate_method_key(conn: &Connection) -> rusqlite::Result<()> {
+    let keyed_by_address: bool = conn.query_row(
+        "SELECT pk > 0 FROM pragma_table_info('methods') WHERE name = 'address'",
+        [], |row| row.get(0),
+    )?;
+    if keyed_by_address {
+        return Ok(());
+    }
+
+    conn.execute_batch("ALTER TABLE methods RENAME TO methods_by_name;")?;
+    conn.execute_batch(SCHEMA)?;
+    conn.execute_batch("INSERT INTO methods SELECT * FROM methods_by_name; DROP TABLE methods_by_name;")
+}
+
+/// What identifies a target across exports
+pub fn target_key(output: &OffsetOutput) -> String {
+    let target = &output.target;
+    target.hash.clone()
+        .or_else(|| target.version.as_ref().map(|v| format!("{}@{}", target.name, v)))
+        .unwrap_or_else(|| target.name.clone())
+}
+
+fn upsert_target(tx: &Transaction, output: &OffsetOutput) -> rusqlite::Result<i64> {
+    let target = &output.target;
+    tx.query_row(
+        "INSERT INTO targets (key, name, version, hash, platform, architecture, base_address, relative, generated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+         ON CONFLICT(key) DO UPDATE SET
+            name = excluded.name, version = excluded.version, hash = excluded.hash,
+            platform = excluded.platform, architecture = excluded.architecture,
+            base_address = excluded.base_address, relative = excluded.relative,
+            generated_at = excluded.generated_at
+         RETURNING id",
+        params![
+            target_key(output), target.name, target.version, target.hash, target.platform,
+            target.architecture, target.base_address as i64, target.relative, output.generated_at,
+        ],
+        |row| row.get(0),
+    )
+}
+
+fn insert_offsets(tx: &Transaction, target_id: i64, output: &OffsetOutput) -> rusqlite::Result<()> {
+    let mut stmt = tx.prepare(
+        "INSERT INTO functions (target_id, name, address, confidence, discovery_method, signature, category)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+    for (name, func) in &output.functions {
+        stmt.execute(params![target_id, name, func.address as i64, func.confidence, func.discovery_method, func.signature, func.category])?;
+    }
+
+    let mut stmt = tx.prepare(
+        "INSERT INTO structure_fields (target_id, structure, field, offset, size, field_type)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    for (structure, offsets) in &output.structure_offsets {
+        for (field, offset) in &offsets.fields {
+            stmt.execute(params![target_id, structure, field, offset.offset as i64, offset.size as i64, offset.field_type])?;
+        }
+    }
+
+    // Later duplicates win, as they would in a map
+    let mut stmt = tx.prepare(
+        "INSERT OR REPLACE INTO classes (target_id, name, vtable_address, size, parent)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    for class in &output.classes {
+        stmt.execute(params![target_id, class.name, class.vtable_address.map(|a| a as i64), class.size as i64, class.parent])?;
+    }
+
+    let mut stmt = tx.prepare(
+        "INSERT OR REPLACE INTO properties (target_id, class_name, name, getter, setter, offset, property_type)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+    for prop in &output.properties {
+        stmt.execute(params![
+            target_id, prop.class_name, prop.name, prop.getter.map(|a| a as i64),
+            prop.setter.map(|a| a as i64), prop.offset.map(|o| o as i64), prop.property_type,
+        ])?;
+    }
+
+    let mut stmt = tx.prepare(
+        "INSERT OR REPLACE INTO methods (target_id, class_name, name, address, vtable_index, is_virtual, signature)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+    for method in &output.methods {
+        stmt.execute(params![
+            target_id, method.class_name, method.name, method.address as i64,
+            method.vtable_index.map(|i| i as i64), method.is_virtual, method.signature,
+        ])?;
+    }
+
+    let mut stmt = tx.prepare(
+        "INSERT OR REPLACE INTO constants (target_id, name, address, value, category)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    for constant in &output.constants {
+        let value = serde_json::to_string(&constant.value).unwrap_or_default();
+        stmt.execute(params![target_id, constant.name, constant.address as i64, value, constant.category])?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::{FunctionOffset, MethodOffset};
+
+    fn count(exporter: &SqliteExporter, table: &str) -> i64 {
+        exporter.connection().query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_reexport_replaces_rows_per_target() {
+        let mut exporter = SqliteExporter::with_connection(Connection::open_in_memory().unwrap()).unwrap();
+
+        let mut old = OffsetOutput::new("RobloxPlayer");
+        old.target.hash = Some("aaaa".to_string());
+        old.add_function("luau_load", FunctionOffset::new(0x1000, 0.9, "pattern"));
+        old.add_function("print", FunctionOffset::new(0x2000, 0.8, "string_xref"));
+
+        let first = exporter.export(&old).unwrap();
+        assert_eq!(exporter.export(&old).unwrap(), first);
+        assert_eq!((count(&exporter, "targets"), count(&exporter, "functions")), (1, 2));
+
+        old.functions.remove("print");
+        exporter.export(&old).unwrap();
+        assert_eq!(count(&exporter, "functions"), 1);
+
+        let mut new = old.clone();
+        new.target.hash = Some("bbbb".to_string());
+        new.add_function("luau_load", FunctionOffset::new(0x1800, 0.9, "pattern"));
+        assert_ne!(exporter.export(&new).unwrap(), first);
+
+        let moved: i64 = exporter.connection().query_row(
+            "SELECT n.address - o.address FROM functions o JOIN functions n USING (name)
+             JOIN targets ot ON ot.id = o.target_id JOIN targets nt ON nt.id = n.target_id
+             WHERE ot.hash = 'aaaa' AND nt.hash = 'bbbb'",
+            [], |row| row.get(0),
+        ).unwrap();
+        assert_eq!(moved, 0x800);
+    }
+
+    #[test]
+    fn test_overloaded_methods_keep_a_row_each() {
+        let method = |address| MethodOffset {
+            name: "FindFirstChild".to_string(),
+            class_name: "Instance".to_string(),
+            address,
+            vtable_index: None,
+            is_virtual: false,
+            signature: None,
+        };
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&SCHEMA.replace("name, address)", "name)")).unwrap();
+        let mut exporter = SqliteExporter::with_connection(conn).unwrap();
+
+        let mut output = OffsetOutput::new("RobloxPlayer");
+        output.methods = vec![method(0x1000), method(0x1100)];
+        exporter.export(&output).unwrap();
+        assert_eq!(count(&exporter, "methods"), 2);
+
+        // Reopening an up-to-date database leaves it alone
+        let mut exporter = SqliteExporter::with_connection(exporter.conn).unwrap();
+        exporter.export(&output).unwrap();
+        assert_eq!(count(&exporter, "methods"), 2);
+    }
+}
@@ -1,9 +1,13 @@
 // Tue Jan 13 2026 - Alex
 
 use crate::output::OffsetOutput;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 
+/// Confidence ranges `confidence_buckets` counts into, low end inclusive;
+/// the last range also takes 1.0
+pub const CONFIDENCE_BUCKETS: &[(f64, f64)] = &[(0.0, 0.5), (0.5, 0.7), (0.7, 0.85), (0.85, 1.0)];
+
 pub struct StatisticsCollector {
     start_time: Instant,
     phase_times: HashMap<String, Duration>,
@@ -33,6 +37,19 @@ pub struct CollectedStatistics {
     pub events: Vec<StatEvent>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceBucket {
+    pub low: f64,
+    pub high: f64,
+    pub count: usize,
+}
+
+impl ConfidenceBucket {
+    pub fn label(&self) -> String {
+        format!("{:.2}-{:.2}", self.low, self.high)
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct HistogramSummary {
     pub count: usize,
@@ -225,6 +242,38 @@ impl StatisticsCollector {
         }
     }
 
+    /// Function confidences recorded so far, counted into `CONFIDENCE_BUCKETS`
+    pub fn confidence_buckets(&self) -> Vec<ConfidenceBucket> {
+        self.buckets("function_confidence")
+    }
+
+    /// Structure field confidences recorded so far, counted into
+    /// `CONFIDENCE_BUCKETS`
+    pub fn structure_confidence_buckets(&self) -> Vec<ConfidenceBucket> {
+        self.buckets("structure_confidence")
+    }
+
+    /// Mean of the values recorded into histogram `name`, `None` when empty
+    pub fn mean(&self, name: &str) -> Option<f64> {
+        let values = self.histograms.get(name).filter(|values| !values.is_empty())?;
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+
+    fn buckets(&self, histogram: &str) -> Vec<ConfidenceBucket> {
+        let values = self.histograms.get(histogram).map(Vec::as_slice).unwrap_or_default();
+        let last = CONFIDENCE_BUCKETS.len() - 1;
+
+        CONFIDENCE_BUCKETS.iter().enumerate()
+            .map(|(i, &(low, high))| ConfidenceBucket {
+                low,
+                high,
+                count: values.iter()
+                    .filter(|&&v| v >= low && (v < high || (i == last && v <= high)))
+                    .count(),
+            })
+            .collect()
+    }
+
     pub fn from_output(&mut self, output: &OffsetOutput) {
         self.set_counter("total_functions", output.functions.len());
         self.set_counter("total_structures", output.structure_offsets.len());
@@ -237,10 +286,22 @@ impl StatisticsCollector {
             self.record_histogram("function_confidence", func.confidence);
         }
 
+        let mut methods: HashSet<&str> = output.functions.values()
+            .map(|func| func.discovery_method.as_str())
+            .collect();
+
         for (_, structure) in &output.structure_offsets {
             self.record_histogram("structure_size", structure.size as f64);
             self.record_histogram("structure_field_count", structure.fields.len() as f64);
+
+            for field in structure.fields.values() {
+                if let Some(confidence) = field.confidence {
+                    self.record_histogram("structure_confidence", confidence);
+                }
+                methods.extend(field.discovery_method.as_deref());
+            }
         }
+        self.set_counter("discovery_methods", methods.len());
 
         let mut category_counts: HashMap<String, usize> = HashMap::new();
         for (_, func) in &output.functions {
@@ -366,3 +427,44 @@ pub fn collect_from_output(output: &OffsetOutput) -> CollectedStatistics {
     collector.from_output(output);
     collector.collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confidence_buckets() {
+        let mut collector = StatisticsCollector::new();
+        for confidence in [0.1, 0.5, 0.69, 0.7, 0.85, 0.9, 1.0] {
+            collector.record_histogram("function_confidence", confidence);
+        }
+
+        let counts: Vec<usize> = collector.confidence_buckets().iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![1, 2, 1, 3]);
+        assert_eq!(collector.confidence_buckets()[2].label(), "0.70-0.85");
+        assert!(StatisticsCollector::new().confidence_buckets().iter().all(|b| b.count == 0));
+    }
+
+    #[test]
+    fn test_from_output_structure_confidence() {
+        use crate::output::{FunctionOffset, StructureOffsets};
+
+        let mut output = OffsetOutput::new("test");
+        output.add_function("a", FunctionOffset::new(0x1000, 0.9, "pattern"));
+        output.add_function("b", FunctionOffset::new(0x2000, 0.6, "xref"));
+        let mut structure = StructureOffsets::new(0x20, 8);
+        structure.add_field("top", 0x10, 8, "ptr");
+        structure.score_field("top", 0.75, "pattern");
+        structure.add_field("base", 0x18, 8, "ptr");
+        output.add_structure("lua_State", structure);
+
+        let mut collector = StatisticsCollector::new();
+        collector.from_output(&output);
+
+        let counts: Vec<usize> = collector.structure_confidence_buckets().iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![0, 0, 1, 0]);
+        assert!((collector.mean("function_confidence").unwrap() - 0.75).abs() < 1e-9);
+        assert_eq!(collector.mean("missing"), None);
+        assert_eq!(collector.get_counter("discovery_methods"), 2);
+    }
+}
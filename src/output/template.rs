@@ -1,6 +1,6 @@
 // Tue Jan 13 2026 - Alex
 
-use crate::output::{OffsetOutput, FunctionOffset, StructureOffsets, ClassOffset};
+use crate::output::{OffsetOutput, FunctionOffset, StructureOffsets, ClassOffset, TargetInfo};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
@@ -357,3 +357,42 @@ pub fn render_output_template(output: &OffsetOutput, template: &str) -> Result<S
     engine.set_from_output(output);
     engine.render_string(template)
 }
+
+/// Placeholders `render_file_name` understands
+pub const FILE_NAME_PLACEHOLDERS: &[&str] = &["target", "hash8", "date", "version"];
+
+/// Expands `{target}`, `{hash8}` (first 8 hex digits of the SHA-256),
+/// `{date}` and `{version}` in an output file name. Missing fields render as
+/// "unknown" and path separators in values become `_`.
+pub fn render_file_name(template: &str, target: &TargetInfo, date: &str) -> Result<String, TemplateError> {
+    let mut result = String::new();
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        result.push_str(&rest[..open]);
+
+        let close = rest[open..].find('}')
+            .ok_or_else(|| TemplateError::SyntaxError(format!("unclosed placeholder in '{}'", template)))?;
+        let name = &rest[open + 1..open + close];
+
+        let value = match name {
+            "target" => target.name.clone(),
+            "hash8" => target.hash.as_deref().map(|h| h.chars().take(8).collect()).unwrap_or_else(|| "unknown".to_string()),
+            "date" => date.to_string(),
+            "version" => target.version.clone().unwrap_or_else(|| "unknown".to_string()),
+            _ => {
+                return Err(TemplateError::RenderError(format!(
+                    "unknown placeholder {{{}}} (expected one of {})",
+                    name,
+                    FILE_NAME_PLACEHOLDERS.iter().map(|p| format!("{{{}}}", p)).collect::<Vec<_>>().join(", ")
+                )));
+            }
+        };
+
+        result.push_str(&value.replace(['/', '\\'], "_"));
+        rest = &rest[open + close + 1..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
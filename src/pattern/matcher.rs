@@ -2,6 +2,7 @@
 
 use crate::memory::{Address, MemoryReader, MemoryRegion, MemoryError};
 use crate::pattern::Pattern;
+use crate::pattern::scanner::seam_matches;
 use std::sync::Arc;
 
 pub struct PatternMatcher {
@@ -32,10 +33,11 @@ impl PatternMatcher {
     pub fn find_first(&self, pattern: &str, mask: &str, regions: &[MemoryRegion]) -> Result<Option<Address>, MemoryError> {
         let pat = self.parse_pattern(pattern, mask);
 
-        for region in regions {
-            if !region.protection().is_readable() {
-                continue;
-            }
+        let readable: Vec<_> = regions.iter()
+            .filter(|r| r.protection().is_readable())
+            .collect();
+
+        for (i, region) in readable.iter().enumerate() {
 
             let start = region.range().start();
             let size = region.range().size() as usize;
@@ -53,6 +55,12 @@ impl PatternMatcher {
 
                 offset += self.chunk_size - pat.len();
             }
+
+            if let Some(next) = readable.get(i + 1) {
+                if let Some(&addr) = seam_matches(&*self.reader, &pat, region, next, pat.len().saturating_sub(1)).first() {
+                    return Ok(Some(addr));
+                }
+            }
         }
 
         Ok(None)
@@ -62,10 +70,11 @@ impl PatternMatcher {
         let pat = self.parse_pattern(pattern, mask);
         let mut results = Vec::new();
 
-        for region in regions {
-            if !region.protection().is_readable() {
-                continue;
-            }
+        let readable: Vec<_> = regions.iter()
+            .filter(|r| r.protection().is_readable())
+            .collect();
+
+        for (i, region) in readable.iter().enumerate() {
 
             let start = region.range().start();
             let size = region.range().size() as usize;
@@ -83,6 +92,10 @@ impl PatternMatcher {
 
                 offset += self.chunk_size - pat.len();
             }
+
+            if let Some(next) = readable.get(i + 1) {
+                results.extend(seam_matches(&*self.reader, &pat, region, next, pat.len().saturating_sub(1)));
+            }
         }
 
         Ok(results)
@@ -112,10 +125,11 @@ impl PatternMatcher {
     }
 
     pub fn find_pattern(&self, pattern: &Pattern, regions: &[MemoryRegion]) -> Result<Option<Address>, MemoryError> {
-        for region in regions {
-            if !region.protection().is_readable() {
-                continue;
-            }
+        let readable: Vec<_> = regions.iter()
+            .filter(|r| r.protection().is_readable())
+            .collect();
+
+        for (i, region) in readable.iter().enumerate() {
 
             let start = region.range().start();
             let size = region.range().size() as usize;
@@ -133,6 +147,12 @@ impl PatternMatcher {
 
                 offset += self.chunk_size - pattern.len();
             }
+
+            if let Some(next) = readable.get(i + 1) {
+                if let Some(&addr) = seam_matches(&*self.reader, pattern, region, next, pattern.len().saturating_sub(1)).first() {
+                    return Ok(Some(addr));
+                }
+            }
         }
 
         Ok(None)
@@ -141,10 +161,11 @@ impl PatternMatcher {
     pub fn find_all_patterns(&self, pattern: &Pattern, regions: &[MemoryRegion]) -> Result<Vec<Address>, MemoryError> {
         let mut results = Vec::new();
 
-        for region in regions {
-            if !region.protection().is_readable() {
-                continue;
-            }
+        let readable: Vec<_> = regions.iter()
+            .filter(|r| r.protection().is_readable())
+            .collect();
+
+        for (i, region) in readable.iter().enumerate() {
 
             let start = region.range().start();
             let size = region.range().size() as usize;
@@ -162,6 +183,10 @@ impl PatternMatcher {
 
                 offset += self.chunk_size - pattern.len();
             }
+
+            if let Some(next) = readable.get(i + 1) {
+                results.extend(seam_matches(&*self.reader, pattern, region, next, pattern.len().saturating_sub(1)));
+            }
         }
 
         Ok(results)
@@ -210,10 +235,11 @@ impl MultiPatternMatcher {
     pub fn find_all(&self, regions: &[MemoryRegion]) -> Result<Vec<(usize, Address)>, MemoryError> {
         let mut results = Vec::new();
 
-        for region in regions {
-            if !region.protection().is_readable() {
-                continue;
-            }
+        let readable: Vec<_> = regions.iter()
+            .filter(|r| r.protection().is_readable())
+            .collect();
+
+        for (i, region) in readable.iter().enumerate() {
 
             let start = region.range().start();
             let size = region.range().size() as usize;
@@ -238,6 +264,14 @@ impl MultiPatternMatcher {
 
                 offset += self.chunk_size - max_pattern_len;
             }
+
+            if let Some(next) = readable.get(i + 1) {
+                for (pattern_idx, pattern) in self.patterns.iter().enumerate() {
+                    for addr in seam_matches(&*self.reader, pattern, region, next, pattern.len().saturating_sub(1)) {
+                        results.push((pattern_idx, addr));
+                    }
+                }
+            }
         }
 
         Ok(results)
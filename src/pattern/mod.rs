@@ -8,13 +8,17 @@ pub mod database;
 pub mod arm64;
 pub mod yara;
 pub mod optimization;
+pub mod sig_gen;
+pub mod error;
 
 pub use pattern::Pattern;
+pub use error::PatternError;
 pub use matcher::PatternMatcher;
 pub use scanner::PatternScanner;
 pub use database::PatternDatabase;
 pub use yara::{YaraRule, YaraString, YaraMatcher, YaraMatch, YaraRuleBuilder, YaraPattern, Condition};
 pub use optimization::{PatternOptimizer, OptimizedPattern, OptimizedPatternSet, PatternCache, PatternGenerator};
+pub use sig_gen::{SignatureGenerator, MaskOptions};
 
 use crate::memory::{Address, MemoryReader, MemoryRegion};
 
@@ -1,5 +1,6 @@
 // Tue Jan 13 2026 - Alex
 
+use crate::pattern::PatternError;
 use std::fmt;
 
 #[derive(Debug, Clone)]
@@ -88,6 +89,44 @@ impl Pattern {
         }
     }
 
+    /// Parses an IDA-style signature such as `"48 8B ?? ?? E8"`: space
+    /// separated two-digit hex bytes, with `?` or `??` for a wildcard.
+    /// Unlike `from_hex`, a malformed token is an error instead of being
+    /// dropped, since dropping it would shift every byte after it.
+    pub fn from_ida_string(pattern: &str) -> Result<Self, PatternError> {
+        let mut bytes = Vec::new();
+        let mut mask = Vec::new();
+
+        for (index, token) in pattern.split_whitespace().enumerate() {
+            if token == "?" || token == "??" {
+                bytes.push(0);
+                mask.push(false);
+                continue;
+            }
+
+            if let Some(bad) = token.chars().find(|c| !c.is_ascii_hexdigit()) {
+                return Err(PatternError::InvalidPattern(format!(
+                    "token {} '{}' has invalid character '{}' (expected hex digits or ?)", index + 1, token, bad)));
+            }
+            if token.len() != 2 {
+                return Err(PatternError::InvalidPattern(format!(
+                    "token {} '{}' is {} hex digits long (expected 2 per byte)", index + 1, token, token.len())));
+            }
+
+            bytes.push(u8::from_str_radix(token, 16).map_err(|e| PatternError::InvalidPattern(e.to_string()))?);
+            mask.push(true);
+        }
+
+        if bytes.is_empty() {
+            return Err(PatternError::PatternTooShort(0));
+        }
+        if !mask.contains(&true) {
+            return Err(PatternError::InvalidWildcard(format!("'{}' is all wildcards", pattern.trim())));
+        }
+
+        Ok(Self { bytes, mask, name: None })
+    }
+
     pub fn with_name(mut self, name: &str) -> Self {
         self.name = Some(name.to_string());
         self
@@ -287,3 +326,24 @@ impl Pattern {
         self.mask.iter().map(|&m| if m { 0xFF } else { 0x00 }).collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_ida_string() {
+        let pattern = Pattern::from_ida_string("48 8B ?? ? E8").unwrap();
+        assert_eq!(pattern.bytes(), &[0x48, 0x8B, 0, 0, 0xE8]);
+        assert_eq!(pattern.mask(), &[true, true, false, false, true]);
+        assert_eq!(pattern.to_hex_string(), "48 8B ?? ?? E8");
+
+        let data = [0x00, 0x48, 0x8B, 0x12, 0x34, 0xE8, 0x48, 0x8B, 0xFF, 0xFF, 0x90];
+        assert_eq!(pattern.find_all_in(&data), vec![1]);
+
+        assert!(Pattern::from_ida_string("48 8B0 E8").unwrap_err().to_string().contains("3 hex digits"));
+        assert!(Pattern::from_ida_string("48 G8").unwrap_err().to_string().contains("invalid character 'G'"));
+        assert!(Pattern::from_ida_string("?? ??").is_err());
+        assert!(Pattern::from_ida_string("   ").is_err());
+    }
+}
@@ -2,11 +2,14 @@
 
 use crate::memory::{Address, MemoryReader, MemoryRegion};
 use crate::pattern::Pattern;
+use rayon::prelude::*;
 
 pub struct PatternScanner {
     chunk_size: usize,
     use_parallel: bool,
     skip_unreadable: bool,
+    read_ahead: usize,
+    threads: usize,
 }
 
 impl PatternScanner {
@@ -15,6 +18,8 @@ impl PatternScanner {
             chunk_size: 0x10000,
             use_parallel: true,
             skip_unreadable: true,
+            read_ahead: usize::MAX,
+            threads: 0,
         }
     }
 
@@ -28,11 +33,25 @@ impl PatternScanner {
         self
     }
 
+    /// Workers for parallel scans; 0, the default, uses rayon's global pool
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
     pub fn skip_unreadable(mut self, skip: bool) -> Self {
         self.skip_unreadable = skip;
         self
     }
 
+    /// Caps how far a scan reads past the end of a region into the next
+    /// contiguous one. A match needs at most `pattern_len - 1` bytes from
+    /// the next region, which is also the default; 0 scans regions in isolation
+    pub fn with_read_ahead(mut self, bytes: usize) -> Self {
+        self.read_ahead = bytes;
+        self
+    }
+
     pub fn scan(&self, reader: &dyn MemoryReader, pattern: &Pattern, regions: &[MemoryRegion]) -> Vec<Address> {
         let filtered_regions: Vec<_> = if self.skip_unreadable {
             regions.iter()
@@ -53,7 +72,7 @@ impl PatternScanner {
     fn scan_sequential(&self, reader: &dyn MemoryReader, pattern: &Pattern, regions: &[MemoryRegion]) -> Vec<Address> {
         let mut results = Vec::new();
 
-        for region in regions {
+        for (i, region) in regions.iter().enumerate() {
             let start = region.range().start();
             let size = region.range().size() as usize;
 
@@ -71,43 +90,41 @@ impl PatternScanner {
                 let overlap = pattern.len().saturating_sub(1);
                 offset += self.chunk_size.saturating_sub(overlap);
             }
+
+            if let Some(next) = regions.get(i + 1) {
+                results.extend(self.scan_seam(reader, pattern, region, next));
+            }
         }
 
         results
     }
 
     fn scan_parallel(&self, reader: &dyn MemoryReader, pattern: &Pattern, regions: &[MemoryRegion]) -> Vec<Address> {
-        let chunks: Vec<_> = regions.iter()
-            .flat_map(|region| {
-                let start = region.range().start();
-                let size = region.range().size() as usize;
-                let overlap = pattern.len().saturating_sub(1);
-                let step = self.chunk_size.saturating_sub(overlap);
-
-                (0..size).step_by(step.max(1))
-                    .map(move |offset| {
-                        let read_size = (size - offset).min(self.chunk_size);
-                        (start + offset as u64, read_size)
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect();
-
-        let results: Vec<Vec<Address>> = chunks.iter()
-            .map(|&(addr, read_size)| {
-                let mut matches = Vec::new();
-                if let Ok(data) = reader.read_bytes(addr, read_size) {
-                    for match_offset in pattern.find_all_in(&data) {
-                        matches.push(addr + match_offset as u64);
-                    }
-                }
-                matches
-            })
-            .collect();
+        let chunks = self.chunks(regions, pattern.len().saturating_sub(1));
+
+        let mut results: Vec<Address> = self.install(|| {
+            chunks.par_iter()
+                .flat_map_iter(|&(addr, read_size)| {
+                    reader.read_bytes(addr, read_size)
+                        .map(|data| pattern.find_all_in(&data))
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(move |offset| addr + offset as u64)
+                })
+                .collect()
+        });
 
-        results.into_iter().flatten().collect()
+        for pair in regions.windows(2) {
+            results.extend(self.scan_seam(reader, pattern, &pair[0], &pair[1]));
+        }
+        results.sort();
+        results.dedup();
+        results
     }
 
+    /// Every match of every pattern as `(pattern_index, address)`, sorted by
+    /// address then pattern so the order doesn't depend on which worker
+    /// finished first.
     pub fn scan_multiple(&self, reader: &dyn MemoryReader, patterns: &[Pattern], regions: &[MemoryRegion]) -> Vec<(usize, Address)> {
         let filtered_regions: Vec<_> = if self.skip_unreadable {
             regions.iter()
@@ -123,35 +140,68 @@ impl PatternScanner {
             .max()
             .unwrap_or(0);
 
-        let mut results = Vec::new();
-
-        for region in &filtered_regions {
-            let start = region.range().start();
-            let size = region.range().size() as usize;
-
-            let overlap = max_pattern_len.saturating_sub(1);
-            let step = self.chunk_size.saturating_sub(overlap);
-
-            let mut offset = 0;
-            while offset < size {
-                let read_size = (size - offset).min(self.chunk_size);
-                let addr = start + offset as u64;
-
-                if let Ok(data) = reader.read_bytes(addr, read_size) {
-                    for (pattern_idx, pattern) in patterns.iter().enumerate() {
-                        for match_offset in pattern.find_all_in(&data) {
-                            results.push((pattern_idx, addr + match_offset as u64));
-                        }
+        let chunks = self.chunks(&filtered_regions, max_pattern_len.saturating_sub(1));
+        let scan_chunk = |&(addr, read_size): &(Address, usize)| {
+            let mut matches = Vec::new();
+            if let Ok(data) = reader.read_bytes(addr, read_size) {
+                for (pattern_idx, pattern) in patterns.iter().enumerate() {
+                    for match_offset in pattern.find_all_in(&data) {
+                        matches.push((pattern_idx, addr + match_offset as u64));
                     }
                 }
+            }
+            matches
+        };
 
-                offset += step.max(1);
+        let mut results: Vec<(usize, Address)> = if self.use_parallel {
+            self.install(|| chunks.par_iter().flat_map_iter(scan_chunk).collect())
+        } else {
+            chunks.iter().flat_map(scan_chunk).collect()
+        };
+
+        for pair in filtered_regions.windows(2) {
+            for (pattern_idx, pattern) in patterns.iter().enumerate() {
+                for addr in self.scan_seam(reader, pattern, &pair[0], &pair[1]) {
+                    results.push((pattern_idx, addr));
+                }
             }
         }
 
+        // A short pattern can match twice inside the overlap sized for the longest
+        results.sort_by_key(|&(pattern_idx, addr)| (addr, pattern_idx));
+        results.dedup();
         results
     }
 
+    /// `(address, len)` windows of at most `chunk_size` bytes over every
+    /// region, each overlapping the next in its region by `overlap` bytes
+    fn chunks(&self, regions: &[MemoryRegion], overlap: usize) -> Vec<(Address, usize)> {
+        let step = self.chunk_size.saturating_sub(overlap).max(1);
+
+        regions.iter()
+            .flat_map(|region| {
+                let start = region.range().start();
+                let size = region.range().size() as usize;
+
+                (0..size).step_by(step)
+                    .map(move |offset| (start + offset as u64, (size - offset).min(self.chunk_size)))
+            })
+            .collect()
+    }
+
+    /// Runs `f` on the global rayon pool, or on a pool of `with_threads`
+    /// workers when a count was set
+    fn install<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        if self.threads == 0 {
+            return f();
+        }
+
+        match rayon::ThreadPoolBuilder::new().num_threads(self.threads).build() {
+            Ok(pool) => pool.install(f),
+            Err(_) => f(),
+        }
+    }
+
     pub fn scan_first(&self, reader: &dyn MemoryReader, pattern: &Pattern, regions: &[MemoryRegion]) -> Option<Address> {
         let filtered_regions: Vec<_> = if self.skip_unreadable {
             regions.iter()
@@ -162,7 +212,7 @@ impl PatternScanner {
             regions.to_vec()
         };
 
-        for region in &filtered_regions {
+        for (i, region) in filtered_regions.iter().enumerate() {
             let start = region.range().start();
             let size = region.range().size() as usize;
 
@@ -182,10 +232,50 @@ impl PatternScanner {
 
                 offset += step.max(1);
             }
+
+            if let Some(next) = filtered_regions.get(i + 1) {
+                if let Some(addr) = self.scan_seam(reader, pattern, region, next).into_iter().next() {
+                    return Some(addr);
+                }
+            }
         }
 
         None
     }
+
+    fn scan_seam(&self, reader: &dyn MemoryReader, pattern: &Pattern, before: &MemoryRegion, after: &MemoryRegion) -> Vec<Address> {
+        let window = pattern.len().saturating_sub(1).min(self.read_ahead);
+        seam_matches(reader, pattern, before, after, window)
+    }
+}
+
+/// Matches of `pattern` that start in `before` and run on into `after`,
+/// reading at most `window` bytes either side of the boundary. Only checked
+/// when `after` begins exactly where `before` ends; each side is read
+/// separately since contiguous regions need not be one readable span.
+pub(crate) fn seam_matches(reader: &dyn MemoryReader, pattern: &Pattern, before: &MemoryRegion, after: &MemoryRegion, window: usize) -> Vec<Address> {
+    if window == 0 || before.end() != after.start() {
+        return Vec::new();
+    }
+
+    let tail = window.min(before.range().size() as usize);
+    let head = window.min(after.range().size() as usize);
+    let seam_start = before.end() - tail as u64;
+
+    let mut data = match reader.read_bytes(seam_start, tail) {
+        Ok(data) => data,
+        Err(_) => return Vec::new(),
+    };
+    match reader.read_bytes(after.start(), head) {
+        Ok(next) => data.extend_from_slice(&next),
+        Err(_) => return Vec::new(),
+    }
+
+    pattern.find_all_in(&data)
+        .into_iter()
+        .filter(|&offset| offset < tail && offset + pattern.len() > tail)
+        .map(|offset| seam_start + offset as u64)
+        .collect()
 }
 
 impl Default for PatternScanner {
@@ -0,0 +1,187 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::pattern::Pattern;
+use crate::utils::arm64::Arm64Utils;
+
+/// Operand fields `SignatureGenerator` wildcards. Anything that encodes an
+/// address moves between builds; plain immediates (struct offsets, flags)
+/// usually don't, so they are kept unless asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaskOptions {
+    /// ADRP/ADR page and offset immediates
+    pub pc_relative: bool,
+    /// B/BL, B.cond, CBZ/CBNZ and TBZ/TBNZ targets
+    pub branches: bool,
+    /// ADD/LDR/STR immediates applied to a register an ADRP set up
+    pub page_offsets: bool,
+    /// Every other ADD/SUB, LDR/STR and MOVZ/MOVK/MOVN immediate
+    pub immediates: bool,
+}
+
+impl Default for MaskOptions {
+    fn default() -> Self {
+        Self {
+            pc_relative: true,
+            branches: true,
+            page_offsets: true,
+            immediates: false,
+        }
+    }
+}
+
+impl MaskOptions {
+    pub const NAMES: &'static [&'static str] = &["pc-relative", "branches", "page-offsets", "immediates"];
+
+    pub fn none() -> Self {
+        Self {
+            pc_relative: false,
+            branches: false,
+            page_offsets: false,
+            immediates: false,
+        }
+    }
+
+    /// Only the named kinds, from `NAMES`
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Result<Self, String> {
+        let mut options = Self::none();
+
+        for name in names {
+            match name.as_ref() {
+                "pc-relative" => options.pc_relative = true,
+                "branches" => options.branches = true,
+                "page-offsets" => options.page_offsets = true,
+                "immediates" => options.immediates = true,
+                other => {
+                    return Err(format!("unknown mask kind '{}' (expected one of {})", other, Self::NAMES.join(", ")));
+                }
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Builds a masked byte signature from the instructions at a known address.
+pub struct SignatureGenerator {
+    options: MaskOptions,
+}
+
+impl SignatureGenerator {
+    pub fn new() -> Self {
+        Self { options: MaskOptions::default() }
+    }
+
+    pub fn with_options(mut self, options: MaskOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Every byte that holds any bit of a masked field becomes a wildcard.
+    /// A trailing partial instruction is kept as-is.
+    pub fn generate(&self, bytes: &[u8]) -> Pattern {
+        let mut byte_mask = vec![0xFFu8; bytes.len()];
+        let mut adrp_registers = 0u32;
+
+        for (i, chunk) in bytes.chunks_exact(4).enumerate() {
+            let insn = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            let field = self.masked_field(insn, adrp_registers);
+
+            if (insn & 0x9F000000) == 0x90000000 {
+                adrp_registers |= 1 << (insn & 0x1F);
+            }
+
+            for (j, byte) in field.to_le_bytes().iter().enumerate() {
+                if *byte != 0 {
+                    byte_mask[i * 4 + j] = 0x00;
+                }
+            }
+        }
+
+        Pattern::with_mask(bytes, &byte_mask)
+    }
+
+    /// Bits of `insn` to wildcard under the current options
+    fn masked_field(&self, insn: u32, adrp_registers: u32) -> u32 {
+        let options = &self.options;
+
+        // ADR/ADRP: immlo (29-30) and immhi (5-23)
+        if (insn & 0x1F000000) == 0x10000000 {
+            return if options.pc_relative { 0x60FFFFE0 } else { 0 };
+        }
+
+        // B/BL: imm26
+        if (insn & 0x7C000000) == 0x14000000 {
+            return if options.branches { 0x03FFFFFF } else { 0 };
+        }
+
+        // B.cond, CBZ/CBNZ: imm19
+        if (insn & 0xFF000010) == 0x54000000 || Arm64Utils::is_compare_branch(insn) {
+            return if options.branches { 0x00FFFFE0 } else { 0 };
+        }
+
+        // TBZ/TBNZ: imm14
+        if Arm64Utils::is_test_branch(insn) {
+            return if options.branches { 0x0007FFE0 } else { 0 };
+        }
+
+        // ADD/SUB (immediate) and LDR/STR (unsigned offset): imm12
+        let add_sub_imm = (insn & 0x1F000000) == 0x11000000;
+        let load_store_imm = (insn & 0x3B000000) == 0x39000000;
+        if add_sub_imm || load_store_imm {
+            let from_adrp = adrp_registers & (1 << ((insn >> 5) & 0x1F)) != 0;
+            let masked = if from_adrp { options.page_offsets } else { options.immediates };
+            return if masked { 0x003FFC00 } else { 0 };
+        }
+
+        // MOVN/MOVZ/MOVK: imm16
+        if (insn & 0x1F800000) == 0x12800000 && (insn >> 29) & 0x3 != 0b01 {
+            return if options.immediates { 0x001FFFE0 } else { 0 };
+        }
+
+        0
+    }
+}
+
+impl Default for SignatureGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(insns: &[u32]) -> Vec<u8> {
+        insns.iter().flat_map(|insn| insn.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_masks_addresses_but_keeps_struct_offsets() {
+        let bytes = code(&[
+            0xA9017BFD, // stp x29, x30, [sp, #0x10]
+            0xD0000000, // adrp x0, #0x2000
+            0x91000000, // add x0, x0, #0
+            0xF9402408, // ldr x8, [x0, #0x48]
+            0xF9402668, // ldr x8, [x19, #0x48]
+            0x94000010, // bl #0x40
+            0xD65F03C0, // ret
+        ]);
+
+        let pattern = SignatureGenerator::new().generate(&bytes);
+
+        assert_eq!(pattern.to_hex_string(),
+            "FD 7B 01 A9 ?? ?? ?? ?? 00 ?? ?? 91 08 ?? ?? F9 68 26 40 F9 ?? ?? ?? ?? C0 03 5F D6");
+    }
+
+    #[test]
+    fn test_options_select_what_is_masked() {
+        let bytes = code(&[0xF9402408, 0x94000010]); // ldr x8, [x0, #0x48]; bl #0x40
+
+        let immediates = MaskOptions::from_names(&["immediates"]).unwrap();
+        let pattern = SignatureGenerator::new().with_options(immediates).generate(&bytes);
+        assert_eq!(pattern.to_hex_string(), "08 ?? ?? F9 10 00 00 94");
+
+        assert!(MaskOptions::from_names(&["registers"]).is_err());
+    }
+}
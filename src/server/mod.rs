@@ -0,0 +1,7 @@
+// Fri Jan 16 2026 - Alex
+
+pub mod protocol;
+pub mod session;
+
+pub use protocol::{RpcError, RpcRequest, RpcResponse};
+pub use session::Session;
@@ -0,0 +1,94 @@
+// Fri Jan 16 2026 - Alex
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The line was not valid JSON
+pub const PARSE_ERROR: i64 = -32700;
+/// Valid JSON, but not a request object
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// The method needs a binary and `load` hasn't succeeded yet
+pub const NO_BINARY: i64 = -32001;
+/// Loading, reading or decoding the binary failed
+pub const MEMORY_ERROR: i64 = -32002;
+/// `find` ran but nothing reported the name
+pub const NOT_FOUND: i64 = -32003;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcRequest {
+    /// Echoed back in the response; null when the client sends none
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RpcResponse {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    pub fn success(id: Value, result: Value) -> Self {
+        Self { jsonrpc: "2.0", id, result: Some(result), error: None }
+    }
+
+    pub fn failure(id: Value, error: RpcError) -> Self {
+        Self { jsonrpc: "2.0", id, result: None, error: Some(error) }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+
+    pub fn memory(message: impl Into<String>) -> Self {
+        Self::new(MEMORY_ERROR, message)
+    }
+}
+
+/// Required `params.<key>` as an address: a number, or a string in hex
+/// (`0x` prefix) or decimal
+pub fn address_param(params: &Value, key: &str) -> Result<u64, RpcError> {
+    match params.get(key) {
+        Some(Value::Number(n)) => n.as_u64()
+            .ok_or_else(|| RpcError::invalid_params(format!("{} must be a non-negative integer", key))),
+        Some(Value::String(s)) => {
+            let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => s.parse(),
+            };
+            parsed.map_err(|_| RpcError::invalid_params(format!("{} is not an address: {}", key, s)))
+        }
+        Some(_) => Err(RpcError::invalid_params(format!("{} must be a number or hex string", key))),
+        None => Err(RpcError::invalid_params(format!("missing {}", key))),
+    }
+}
+
+/// Required string `params.<key>`
+pub fn string_param<'a>(params: &'a Value, key: &str) -> Result<&'a str, RpcError> {
+    match params.get(key) {
+        Some(Value::String(s)) => Ok(s),
+        Some(_) => Err(RpcError::invalid_params(format!("{} must be a string", key))),
+        None => Err(RpcError::invalid_params(format!("missing {}", key))),
+    }
+}
@@ -0,0 +1,247 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::analysis::disasm::{DisassemblyConfig, DisassemblyContext};
+use crate::finders::roblox::finder_for_output;
+use crate::finders::{AllFinders, CombinedResults, Finder};
+use crate::memory::{Address, BinaryMemory, MemoryReader};
+use crate::server::protocol::{self, address_param, string_param, RpcError, RpcRequest, RpcResponse};
+use crate::utils::hex_string_spaced;
+use serde_json::{json, Value};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Most bytes one `read` returns
+pub const MAX_READ: usize = 0x10000;
+
+/// Methods `Session::handle` answers
+pub const METHODS: &[&str] = &["load", "read", "disasm", "find", "scan", "shutdown"];
+
+struct LoadedBinary {
+    path: PathBuf,
+    memory: Arc<BinaryMemory>,
+    /// Kept across requests so repeated disassembly hits the decode cache
+    disasm: DisassemblyContext,
+    /// The last `scan`, answering `find` without running finders again
+    scan: Option<CombinedResults>,
+}
+
+impl LoadedBinary {
+    fn reader(&self) -> Arc<dyn MemoryReader> {
+        self.memory.clone()
+    }
+
+    fn code_range(&self) -> Result<Range<Address>, RpcError> {
+        let regions = self.memory.get_regions()
+            .map_err(|e| RpcError::memory(format!("Failed to get memory regions: {}", e)))?;
+
+        regions.iter()
+            .find(|r| r.protection().can_execute())
+            .map(|r| r.start()..r.end())
+            .ok_or_else(|| RpcError::memory("No executable regions found in binary"))
+    }
+}
+
+/// One client's state for `serve`: the binary stays loaded between
+/// requests, and each request line gets exactly one response line.
+#[derive(Default)]
+pub struct Session {
+    binary: Option<LoadedBinary>,
+    finished: bool,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `shutdown` has been handled
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Answers one line-delimited JSON request
+    pub fn handle_line(&mut self, line: &str) -> RpcResponse {
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => return RpcResponse::failure(Value::Null, RpcError::new(protocol::PARSE_ERROR, e.to_string())),
+        };
+
+        let id = value.get("id").cloned().unwrap_or(Value::Null);
+        match serde_json::from_value::<RpcRequest>(value) {
+            Ok(request) => self.handle(request),
+            Err(e) => RpcResponse::failure(id, RpcError::new(protocol::INVALID_REQUEST, e.to_string())),
+        }
+    }
+
+    pub fn handle(&mut self, request: RpcRequest) -> RpcResponse {
+        let params = &request.params;
+        let result = match request.method.as_str() {
+            "load" => self.load(params),
+            "read" => self.read(params),
+            "disasm" => self.disasm(params),
+            "find" => self.find(params),
+            "scan" => self.scan(),
+            "shutdown" => {
+                self.finished = true;
+                Ok(Value::Null)
+            }
+            other => Err(RpcError::new(
+                protocol::METHOD_NOT_FOUND,
+                format!("Unknown method {}; expected one of {}", other, METHODS.join(", ")),
+            )),
+        };
+
+        match result {
+            Ok(value) => RpcResponse::success(request.id, value),
+            Err(error) => RpcResponse::failure(request.id, error),
+        }
+    }
+
+    fn loaded(&mut self) -> Result<&mut LoadedBinary, RpcError> {
+        self.binary.as_mut().ok_or_else(|| RpcError::new(protocol::NO_BINARY, "No binary loaded; call load first"))
+    }
+
+    /// `{path}`: replaces any binary loaded before
+    fn load(&mut self, params: &Value) -> Result<Value, RpcError> {
+        let path = PathBuf::from(string_param(params, "path")?);
+        let memory = Arc::new(BinaryMemory::load(&path)
+            .map_err(|e| RpcError::memory(format!("Failed to load {}: {}", path.display(), e)))?);
+
+        let regions = memory.get_regions()
+            .map_err(|e| RpcError::memory(format!("Failed to get memory regions: {}", e)))?;
+        let result = json!({
+            "path": path.display().to_string(),
+            "platform": memory.format().platform(),
+            "base_address": format!("0x{:x}", memory.base_address().as_u64()),
+            "size": memory.size(),
+            "regions": regions.len(),
+        });
+
+        let disasm = DisassemblyContext::new(memory.clone());
+        self.binary = Some(LoadedBinary { path, memory, disasm, scan: None });
+        Ok(result)
+    }
+
+    /// `{address, size}`
+    fn read(&mut self, params: &Value) -> Result<Value, RpcError> {
+        let address = address_param(params, "address")?;
+        let size = address_param(params, "size")? as usize;
+        if size == 0 || size > MAX_READ {
+            return Err(RpcError::invalid_params(format!("size must be between 1 and {}", MAX_READ)));
+        }
+
+        let bytes = self.loaded()?.memory.read_bytes(Address::new(address), size)
+            .map_err(|e| RpcError::memory(format!("Failed to read 0x{:x}: {}", address, e)))?;
+
+        Ok(json!({
+            "address": format!("0x{:x}", address),
+            "bytes": hex_string_spaced(&bytes),
+        }))
+    }
+
+    /// `{address, max_insns?}`: the function from there to its first RET,
+    /// as `disasm --json` writes it
+    fn disasm(&mut self, params: &Value) -> Result<Value, RpcError> {
+        let address = address_param(params, "address")?;
+        let max_insns = match params.get("max_insns") {
+            Some(_) => address_param(params, "max_insns")? as usize,
+            None => DisassemblyConfig::default().max_function_instructions,
+        };
+        if max_insns == 0 {
+            return Err(RpcError::invalid_params("max_insns must be at least 1"));
+        }
+
+        let disasm = &mut self.loaded()?.disasm;
+        disasm.set_max_function_instructions(max_insns);
+        let listing = disasm.disassemble_function_listing(Address::new(address))
+            .map_err(|e| RpcError::memory(format!("Failed to disassemble 0x{:x}: {}", address, e)))?;
+
+        Ok(listing.to_json())
+    }
+
+    /// `{name, all_finders?}`: like `get`, answered from the last scan when there is one
+    fn find(&mut self, params: &Value) -> Result<Value, RpcError> {
+        let name = string_param(params, "name")?.to_string();
+        let all_finders = params.get("all_finders").and_then(Value::as_bool).unwrap_or(false);
+        let binary = self.loaded()?;
+
+        let value = match &binary.scan {
+            Some(results) => results.lookup(&name),
+            None if all_finders => {
+                let range = binary.code_range()?;
+                AllFinders::new(binary.reader()).find_all(range.start, range.end).lookup(&name)
+            }
+            None => {
+                let finder = finder_for_output(&name).ok_or_else(|| RpcError::invalid_params(format!(
+                    "No built-in finder reports {}; pass all_finders or scan first", name
+                )))?;
+                let range = binary.code_range()?;
+
+                let mut results = CombinedResults::new();
+                for result in finder.find(binary.reader(), range.start, range.end) {
+                    results.add_function(result);
+                }
+                results.lookup(&name)
+            }
+        };
+
+        let value = value.ok_or_else(|| RpcError::new(protocol::NOT_FOUND, format!("{} not found", name)))?;
+        Ok(json!({ "name": name, "address": format!("0x{:x}", value) }))
+    }
+
+    /// Every finder over the first executable region, in the scan file's layout
+    fn scan(&mut self) -> Result<Value, RpcError> {
+        let binary = self.loaded()?;
+        let range = binary.code_range()?;
+        let results = AllFinders::new(binary.reader()).find_all(range.start, range.end);
+
+        let mut map = results.to_json_map();
+        map.insert("target".to_string(), json!(binary.path.display().to_string()));
+        binary.scan = Some(results);
+
+        Ok(json!(map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::MachOFixture;
+
+    #[test]
+    fn test_session_keeps_the_binary_between_requests() {
+        let mut fixture = MachOFixture::new();
+        let ret = fixture.add_code(&[0xD65F03C0]);
+        let path = std::env::temp_dir().join(format!("serve-session-{}.bin", std::process::id()));
+        std::fs::write(&path, fixture.to_bytes()).unwrap();
+
+        let mut session = Session::new();
+        let request = |method: &str, params: Value| format!(r#"{{"id": 1, "method": "{}", "params": {}}}"#, method, params);
+        let code = |response: RpcResponse| response.error.map(|e| e.code);
+
+        assert_eq!(code(session.handle_line(&request("read", json!({"address": 0, "size": 4})))), Some(protocol::NO_BINARY));
+        assert_eq!(code(session.handle_line("{not json")), Some(protocol::PARSE_ERROR));
+        assert_eq!(code(session.handle_line(r#"{"id": 2}"#)), Some(protocol::INVALID_REQUEST));
+        assert_eq!(code(session.handle_line(&request("frobnicate", json!({})))), Some(protocol::METHOD_NOT_FOUND));
+
+        let loaded = session.handle_line(&request("load", json!({"path": path})));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.result.unwrap()["platform"], "macos");
+
+        let read = session.handle_line(&request("read", json!({"address": format!("0x{:x}", ret.as_u64()), "size": 4})));
+        assert_eq!(read.id, json!(1));
+        assert_eq!(read.result.unwrap()["bytes"], "c0 03 5f d6");
+
+        let listing = session.handle_line(&request("disasm", json!({"address": ret.as_u64()}))).result.unwrap();
+        assert_eq!((&listing["ended_on"], &listing["truncated"]), (&json!("ret"), &json!(false)));
+        let capped = request("disasm", json!({"address": ret.as_u64(), "max_insns": 0}));
+        assert_eq!(code(session.handle_line(&capped)), Some(protocol::INVALID_PARAMS));
+
+        assert_eq!(code(session.handle_line(&request("read", json!({"address": 0, "size": 4})))), Some(protocol::MEMORY_ERROR));
+        assert_eq!(code(session.handle_line(&request("find", json!({"name": "LuauLoad"})))), Some(protocol::NOT_FOUND));
+
+        session.handle_line(&request("shutdown", json!(null)));
+        assert!(session.is_finished());
+    }
+}
@@ -613,6 +613,7 @@ impl fmt::Display for MigrationInfo {
 mod tests {
     use super::*;
     use crate::structure::type_info::PrimitiveType;
+    use crate::structure::Offset;
 
     #[test]
     fn test_structure_comparison() {
@@ -1,5 +1,6 @@
 // Tue Jan 13 2026 - Alex
 
+use crate::memory::MemoryError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,4 +19,10 @@ pub enum StructureError {
     ParseError(String),
     #[error("Validation failed: {0}")]
     ValidationFailed(String),
+    #[error("Structure not found: {0}")]
+    StructureNotFound(String),
+    #[error("Null pointer: {0}")]
+    NullPointer(String),
+    #[error("Memory error: {0}")]
+    Memory(#[from] MemoryError),
 }
@@ -136,6 +136,16 @@ impl TypeInference {
         Ok(pointer_count >= 6)
     }
 
+    /// Element strides recovered from the code at `code`; see `element_strides`
+    pub fn infer_element_strides(&self, code: Address, max_insns: usize) -> Result<Vec<ElementStride>, MemoryError> {
+        let bytes = self.reader.read_bytes(code, max_insns * 4)?;
+        let insns: Vec<u32> = bytes.chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+
+        Ok(element_strides(&insns))
+    }
+
     pub fn clear_cache(&mut self) {
         self.cache.clear();
     }
@@ -183,3 +193,356 @@ impl StructureReconstructor {
         &self.inference
     }
 }
+
+/// How far back the arithmetic feeding an access may sit
+const STRIDE_LOOKBACK: usize = 8;
+
+/// Larger multipliers are treated as something other than an element size
+const MAX_STRIDE: u64 = 0x10000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrideSource {
+    /// `madd`/`umaddl`/`smaddl` with a constant multiplier
+    Madd,
+    /// `msub` with a constant multiplier
+    Msub,
+    /// `mul` by a constant, added to the base afterwards
+    Mul,
+    /// `lsl` or a shifted/extended `add`, or a scaled register-offset access
+    Shift,
+    /// `udiv`/`sdiv` by a constant, or a shift and exact-division multiply
+    /// of a pointer difference
+    Division,
+}
+
+/// An element size recovered from array indexing arithmetic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElementStride {
+    pub stride: u64,
+    /// Offset of the accessed field inside the element, for strides read
+    /// off a load or store
+    pub field_offset: Option<u64>,
+    pub source: StrideSource,
+    /// Instruction index of the arithmetic the stride came from
+    pub at: usize,
+}
+
+/// Every stride in `insns`: one per load/store whose address is built from
+/// a scaled index, plus every division of a value by a constant size.
+pub fn element_strides(insns: &[u32]) -> Vec<ElementStride> {
+    let mut strides: Vec<ElementStride> = (0..insns.len())
+        .filter_map(|i| element_stride(insns, i))
+        .collect();
+
+    strides.extend((0..insns.len()).filter_map(|i| division_stride(insns, i)));
+    strides.sort_by_key(|s| s.at);
+    strides
+}
+
+/// Stride of the array the load or store at `access` indexes into, when its
+/// address comes from `madd base, index, #size`-style arithmetic within
+/// `STRIDE_LOOKBACK` instructions
+pub fn element_stride(insns: &[u32], access: usize) -> Option<ElementStride> {
+    let insn = *insns.get(access)?;
+    let rn = ((insn >> 5) & 0x1F) as u8;
+
+    // LDR/STR (unsigned offset)
+    if (insn & 0x3B000000) == 0x39000000 {
+        let offset = ((insn >> 10) & 0xFFF) as u64 * access_size(insn);
+        return traced_stride(insns, access, rn, 0).map(|s| ElementStride { field_offset: Some(offset), ..s });
+    }
+
+    // LDUR/STUR; a negative offset belongs to the previous element
+    if (insn & 0x3B200C00) == 0x38000000 {
+        let imm9 = ((insn >> 12) & 0x1FF) as i64;
+        let offset = if imm9 & 0x100 != 0 { None } else { Some(imm9 as u64) };
+        return traced_stride(insns, access, rn, 0).map(|s| ElementStride { field_offset: offset, ..s });
+    }
+
+    // LDR/STR (register offset)
+    if (insn & 0x3B200C00) == 0x38200800 {
+        let rm = ((insn >> 16) & 0x1F) as u8;
+        if (insn >> 12) & 1 == 1 {
+            return Some(ElementStride {
+                stride: access_size(insn),
+                field_offset: Some(0),
+                source: StrideSource::Shift,
+                at: access,
+            });
+        }
+
+        return scaled_index(insns, access, rm)
+            .or_else(|| traced_stride(insns, access, rn, 0))
+            .map(|s| ElementStride { field_offset: Some(0), ..s });
+    }
+
+    None
+}
+
+/// The stride in whatever computed `reg` before `before`
+fn traced_stride(insns: &[u32], before: usize, reg: u8, depth: usize) -> Option<ElementStride> {
+    let at = last_writer(insns, before, reg)?;
+    let insn = insns[at];
+    let rn = ((insn >> 5) & 0x1F) as u8;
+    let rm = ((insn >> 16) & 0x1F) as u8;
+
+    // MADD/MSUB, and SMADDL/UMADDL/SMSUBL/UMSUBL, with an accumulator
+    let three_source = (insn & 0x7FE00000) == 0x1B000000 || (insn & 0xFF600000) == 0x9B200000;
+    if three_source && (insn >> 10) & 0x1F != 31 {
+        let source = if (insn >> 15) & 1 == 1 { StrideSource::Msub } else { StrideSource::Madd };
+        return constant_multiplier(insns, at, rn, rm).map(|stride| ElementStride { stride, field_offset: None, source, at });
+    }
+
+    // ADD (shifted register), LSL only
+    if (insn & 0x7FE00000) == 0x0B000000 {
+        let shift = (insn >> 10) & 0x3F;
+        if shift > 0 {
+            return shift_stride(shift, at);
+        }
+        if depth == 0 {
+            return scaled_index(insns, at, rm).or_else(|| scaled_index(insns, at, rn));
+        }
+        return None;
+    }
+
+    // ADD (extended register)
+    if (insn & 0x7FE00000) == 0x0B200000 {
+        let shift = (insn >> 10) & 0x7;
+        if shift > 0 {
+            return shift_stride(shift, at);
+        }
+        return None;
+    }
+
+    None
+}
+
+/// `reg` as an index already scaled by an element size: `lsl` or `mul`
+fn scaled_index(insns: &[u32], before: usize, reg: u8) -> Option<ElementStride> {
+    let at = last_writer(insns, before, reg)?;
+    let insn = insns[at];
+
+    if let Some(shift) = lsl_amount(insn) {
+        return shift_stride(shift, at);
+    }
+
+    // MUL (MADD with xzr as the accumulator)
+    if (insn & 0x7FE0FC00) == 0x1B007C00 {
+        let rn = ((insn >> 5) & 0x1F) as u8;
+        let rm = ((insn >> 16) & 0x1F) as u8;
+        return constant_multiplier(insns, at, rn, rm)
+            .map(|stride| ElementStride { stride, field_offset: None, source: StrideSource::Mul, at });
+    }
+
+    None
+}
+
+/// `udiv`/`sdiv` by a constant, or `asr`/`lsr` of a pointer difference
+/// optionally followed by a multiply with the element size's inverse
+fn division_stride(insns: &[u32], at: usize) -> Option<ElementStride> {
+    let insn = insns[at];
+
+    // UDIV/SDIV
+    if (insn & 0x7FE0F800) == 0x1AC00800 {
+        let rm = ((insn >> 16) & 0x1F) as u8;
+        return constant_in(insns, at, rm)
+            .filter(|&d| d > 1 && d <= MAX_STRIDE)
+            .map(|stride| ElementStride { stride, field_offset: None, source: StrideSource::Division, at });
+    }
+
+    // ASR/LSR (immediate) of `sub xd, end, begin`
+    let shift = shift_right_amount(insn)?;
+    let rd = (insn & 0x1F) as u8;
+    let rn = ((insn >> 5) & 0x1F) as u8;
+    let difference = last_writer(insns, at, rn)
+        .is_some_and(|w| (insns[w] & 0xFFE0FC00) == 0xCB000000);
+    if !difference {
+        return None;
+    }
+
+    // A following `mul` by the inverse of the odd factor of the size, before
+    // the shifted value is overwritten
+    let mut odd = 1;
+    for next in at + 1..(at + 1 + STRIDE_LOOKBACK).min(insns.len()) {
+        let insn = insns[next];
+        if (insn & 0xFFE0FC00) == 0x9B007C00 {
+            let (n, m) = (((insn >> 5) & 0x1F) as u8, ((insn >> 16) & 0x1F) as u8);
+            let other = if n == rd { Some(m) } else if m == rd { Some(n) } else { None };
+            if let Some(inverse) = other.and_then(|r| constant_in(insns, next, r)).filter(|c| c & 1 == 1) {
+                odd = modular_inverse(inverse);
+                break;
+            }
+        }
+        if writes_register(insn, rd) {
+            break;
+        }
+    }
+
+    let stride = (1u64 << shift).checked_mul(odd)?;
+    (stride > 1 && stride <= MAX_STRIDE).then_some(ElementStride {
+        stride,
+        field_offset: None,
+        source: StrideSource::Division,
+        at,
+    })
+}
+
+fn shift_stride(shift: u32, at: usize) -> Option<ElementStride> {
+    let stride = 1u64 << shift;
+    (stride <= MAX_STRIDE).then_some(ElementStride { stride, field_offset: None, source: StrideSource::Shift, at })
+}
+
+/// Whichever of `a`/`b` holds a constant element size
+fn constant_multiplier(insns: &[u32], before: usize, a: u8, b: u8) -> Option<u64> {
+    [a, b].into_iter()
+        .filter_map(|reg| constant_in(insns, before, reg))
+        .find(|&c| c > 1 && c <= MAX_STRIDE)
+}
+
+/// Value a `movz`/`movn` + `movk` sequence left in `reg` before `before`
+fn constant_in(insns: &[u32], before: usize, reg: u8) -> Option<u64> {
+    let mut value = 0u64;
+    let mut known = 0u64;
+    let mut end = before;
+
+    while let Some(at) = last_writer(insns, end, reg) {
+        let insn = insns[at];
+        let imm16 = ((insn >> 5) & 0xFFFF) as u64;
+        let shift = ((insn >> 21) & 0x3) * 16;
+        let wide = insn >> 31 == 1;
+
+        // MOVK: fills in a half-word unless a later MOVK already did
+        if (insn & 0x7F800000) == 0x72800000 {
+            if known & (0xFFFF << shift) == 0 {
+                value |= imm16 << shift;
+                known |= 0xFFFF << shift;
+            }
+            end = at;
+            continue;
+        }
+
+        let base = if (insn & 0x7F800000) == 0x52800000 {
+            imm16 << shift
+        } else if (insn & 0x7F800000) == 0x12800000 {
+            !(imm16 << shift)
+        } else {
+            return None;
+        };
+        let base = if wide { base } else { base & 0xFFFFFFFF };
+
+        return Some((base & !known) | value);
+    }
+
+    None
+}
+
+/// Index of the last instruction before `before`, within the lookback
+/// window, that writes `reg`
+fn last_writer(insns: &[u32], before: usize, reg: u8) -> Option<usize> {
+    let start = before.saturating_sub(STRIDE_LOOKBACK);
+    (start..before.min(insns.len())).rev().find(|&i| writes_register(insns[i], reg))
+}
+
+fn writes_register(insn: u32, reg: u8) -> bool {
+    // BL clobbers the argument and temporary registers
+    if (insn & 0xFC000000) == 0x94000000 {
+        return reg <= 18 || reg == 30;
+    }
+
+    // Branches, exceptions and system instructions
+    if (insn & 0x1C000000) == 0x14000000 {
+        return false;
+    }
+
+    // Loads write Rt, stores write nothing
+    if (insn & 0x0A000000) == 0x08000000 {
+        return (insn >> 22) & 1 == 1 && (insn & 0x1F) as u8 == reg;
+    }
+
+    (insn & 0x1F) as u8 == reg
+}
+
+/// Access size in bytes of a load/store, 16 for q registers
+fn access_size(insn: u32) -> u64 {
+    let size = insn >> 30;
+    let vector = (insn >> 26) & 1 == 1;
+    if vector && size == 0 && (insn >> 23) & 1 == 1 {
+        16
+    } else {
+        1 << size
+    }
+}
+
+/// Shift amount of `lsl xd, xn, #shift` (UBFM alias)
+fn lsl_amount(insn: u32) -> Option<u32> {
+    if (insn & 0xFFC00000) != 0xD3400000 {
+        return None;
+    }
+    let immr = (insn >> 16) & 0x3F;
+    let imms = (insn >> 10) & 0x3F;
+    (imms != 63 && (imms + 1) % 64 == immr).then_some(63 - imms)
+}
+
+/// Shift amount of 64-bit `asr`/`lsr` (immediate)
+fn shift_right_amount(insn: u32) -> Option<u32> {
+    let asr = (insn & 0xFFC0FC00) == 0x9340FC00;
+    let lsr = (insn & 0xFFC0FC00) == 0xD340FC00;
+    (asr || lsr).then_some((insn >> 16) & 0x3F)
+}
+
+/// Inverse of an odd `value` modulo 2^64
+fn modular_inverse(value: u64) -> u64 {
+    let mut inverse = value;
+    for _ in 0..5 {
+        inverse = inverse.wrapping_mul(2u64.wrapping_sub(value.wrapping_mul(inverse)));
+    }
+    inverse
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stride_at(insns: &[u32], access: usize) -> Option<(u64, Option<u64>, StrideSource)> {
+        element_stride(insns, access).map(|s| (s.stride, s.field_offset, s.source))
+    }
+
+    #[test]
+    fn test_strides_from_index_arithmetic() {
+        // mov x9, #24; madd x8, x1, x9, x0; ldr x0, [x8, #0x10]
+        let madd = [0xD2800309, 0x9B090028, 0xF9400900];
+        assert_eq!(stride_at(&madd, 2), Some((24, Some(0x10), StrideSource::Madd)));
+
+        // mov w9, #40; umaddl x8, w1, w9, x0; ldr w0, [x8, #4]
+        let umaddl = [0x52800509, 0x9BA90028, 0xB9400500];
+        assert_eq!(stride_at(&umaddl, 2), Some((40, Some(4), StrideSource::Madd)));
+
+        // lsl x8, x1, #4; add x8, x0, x8; ldr q0, [x8]
+        let shifted = [0xD37CEC28, 0x8B080008, 0x3DC00100];
+        assert_eq!(stride_at(&shifted, 2), Some((16, Some(0), StrideSource::Shift)));
+
+        // mov x9, #24; mul x8, x1, x9; ldr x0, [x0, x8]
+        let mul = [0xD2800309, 0x9B097C28, 0xF8686800];
+        assert_eq!(stride_at(&mul, 2), Some((24, Some(0), StrideSource::Mul)));
+
+        // ldr x0, [x0, x1, lsl #3]
+        assert_eq!(stride_at(&[0xF8617800], 0), Some((8, Some(0), StrideSource::Shift)));
+
+        // The multiplier register was overwritten by a call in between
+        let clobbered = [0xD2800309, 0x94000000, 0x9B090028, 0xF9400900];
+        assert_eq!(stride_at(&clobbered, 3), None);
+    }
+
+    #[test]
+    fn test_strides_from_division() {
+        // sub x8, x1, x0; asr x8, x8, #3; mov x9, #0xAAAAAAAAAAAAAAAB; mul x0, x8, x9
+        let exact = [0xCB000028, 0x9343FD08, 0xD2955569, 0xF2B55549, 0xF2D55549, 0xF2F55549, 0x9B097D00];
+        let strides = element_strides(&exact);
+        assert_eq!(strides.len(), 1);
+        assert_eq!((strides[0].stride, strides[0].source), (24, StrideSource::Division));
+
+        // mov x9, #48; udiv x0, x8, x9
+        let udiv = element_strides(&[0xD2800609, 0x9AC90900]);
+        assert_eq!(udiv.iter().map(|s| s.stride).collect::<Vec<_>>(), [48]);
+    }
+}
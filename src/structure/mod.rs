@@ -22,6 +22,7 @@ pub mod comparison;
 
 pub use analyzer::StructureAnalyzer;
 pub use layout::StructureLayout;
+pub use traversal::{StructureTraverser, PathWalker, PathStep};
 pub use field::Field;
 pub use type_info::{TypeInfo, PrimitiveType};
 pub use builder::StructureBuilder;
@@ -30,7 +31,7 @@ pub use offset::Offset;
 pub use alignment::Alignment;
 pub use size::Size;
 pub use member::Member;
-pub use inference::TypeInference;
+pub use inference::{TypeInference, ElementStride, StrideSource, element_stride, element_strides};
 pub use validator::StructureValidator;
 pub use serializer::SerializableLayout;
 pub use vtable::{VTable, VTableEntry, VTableAnalyzer, VTableComparison, VTableDifference, InheritanceInfo, VTableBuilder};
@@ -1,7 +1,9 @@
 // Tue Jan 13 2026 - Alex
 
-use crate::structure::StructureLayout;
-use crate::memory::Address;
+use crate::structure::{StructureLayout, StructureError};
+use crate::memory::{Address, MemoryReader};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 pub struct StructureTraverser {
     layout: StructureLayout,
@@ -24,3 +26,91 @@ impl StructureTraverser {
             .collect()
     }
 }
+
+/// One field dereferenced while walking a path.
+#[derive(Debug, Clone)]
+pub struct PathStep {
+    pub structure: String,
+    pub field: String,
+    pub offset: u64,
+    pub field_address: Address,
+    pub value: Address,
+}
+
+/// Follows dotted field paths such as `DataModel.Workspace.Camera` through
+/// pointer fields. The first segment names the structure at the base
+/// address; each field is assumed to point at the structure of the same name.
+pub struct PathWalker {
+    reader: Arc<dyn MemoryReader>,
+    layouts: HashMap<String, StructureLayout>,
+}
+
+impl PathWalker {
+    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self {
+            reader,
+            layouts: HashMap::new(),
+        }
+    }
+
+    pub fn with_layout(mut self, layout: StructureLayout) -> Self {
+        self.layouts.insert(layout.name().to_string(), layout);
+        self
+    }
+
+    pub fn walk(&self, base: Address, path: &str) -> Result<Vec<PathStep>, StructureError> {
+        let mut segments = path.split('.');
+        let root = segments.next().unwrap_or_default();
+        let fields: Vec<&str> = segments.collect();
+
+        if root.is_empty() || fields.is_empty() || fields.iter().any(|f| f.is_empty()) {
+            return Err(StructureError::ParseError(format!(
+                "expected Structure.field[.field...], got '{}'", path
+            )));
+        }
+
+        let mut layout = self.layout(root)?;
+        let mut current = base;
+        let mut steps = Vec::with_capacity(fields.len());
+
+        for (i, name) in fields.iter().enumerate() {
+            let field = layout.get_field(name).ok_or_else(|| {
+                let mut available: Vec<_> = layout.fields().iter().collect();
+                available.sort_by_key(|f| f.offset().as_u64());
+                let names: Vec<&str> = available.iter().map(|f| f.name()).collect();
+                StructureError::FieldNotFound(format!(
+                    "{}.{} (available: {})", layout.name(), name, names.join(", ")
+                ))
+            })?;
+
+            let field_address = current + field.offset().as_u64();
+            let value = Address::new(self.reader.read_u64(field_address)?);
+
+            steps.push(PathStep {
+                structure: layout.name().to_string(),
+                field: name.to_string(),
+                offset: field.offset().as_u64(),
+                field_address,
+                value,
+            });
+
+            if i + 1 < fields.len() {
+                if value.as_u64() == 0 {
+                    return Err(StructureError::NullPointer(format!("{}.{}", layout.name(), name)));
+                }
+                layout = self.layout(name)?;
+                current = value;
+            }
+        }
+
+        Ok(steps)
+    }
+
+    fn layout(&self, name: &str) -> Result<&StructureLayout, StructureError> {
+        self.layouts.get(name).ok_or_else(|| {
+            let mut known: Vec<&str> = self.layouts.keys().map(String::as_str).collect();
+            known.sort_unstable();
+            StructureError::StructureNotFound(format!("{} (known: {})", name, known.join(", ")))
+        })
+    }
+}
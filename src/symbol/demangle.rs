@@ -2,6 +2,15 @@
 
 use std::collections::VecDeque;
 
+/// Deepest type nesting followed before giving up, so hostile input like
+/// `PPPP...` can't exhaust the stack
+const MAX_DEPTH: usize = 256;
+
+/// Brackets a template parameter index seen inside a generic lambda's
+/// signature, until it is known what the parameter stands for
+const LAMBDA_PARAM_START: char = '\u{1}';
+const LAMBDA_PARAM_END: char = '\u{2}';
+
 pub fn demangle(name: &str) -> Option<String> {
     if name.starts_with("_Z") || name.starts_with("__Z") {
         demangle_itanium(name)
@@ -12,12 +21,29 @@ pub fn demangle(name: &str) -> Option<String> {
     }
 }
 
+/// Itanium C++ ABI names (`_ZN3RBX8Instance7getNameEv`), with or without
+/// the extra underscore Mach-O symbol tables add. Covers nested and
+/// templated names, constructors/destructors, operators, lambdas, local
+/// names, vtables/typeinfo/thunks and the common parameter types; anything
+/// else gives `None`. Output follows c++filt.
 pub fn demangle_itanium(mangled: &str) -> Option<String> {
     let mangled = mangled.strip_prefix("__Z")
         .or_else(|| mangled.strip_prefix("_Z"))?;
 
-    let mut demangler = ItaniumDemangler::new(mangled);
-    demangler.demangle()
+    // Clone suffixes like `.cold.1` sit outside the mangling proper
+    let (body, suffix) = match mangled.find('.') {
+        Some(dot) => mangled.split_at(dot),
+        None => (mangled, ""),
+    };
+
+    let mut demangler = ItaniumDemangler::new(body);
+    let demangled = demangler.demangle()?;
+
+    if suffix.is_empty() {
+        Some(demangled)
+    } else {
+        Some(format!("{} [clone {}]", demangled, suffix))
+    }
 }
 
 pub fn demangle_msvc(mangled: &str) -> Option<String> {
@@ -29,286 +55,935 @@ pub fn demangle_msvc(mangled: &str) -> Option<String> {
     demangler.demangle()
 }
 
+/// A decoded `<name>`, with what the surrounding encoding needs from it
+struct Name {
+    text: String,
+    /// Ends in template arguments, so a function encoding carries a return type
+    is_template: bool,
+    /// Constructor, destructor or conversion operator: never a return type
+    no_return: bool,
+    /// cv- and ref-qualifiers of a member function, e.g. " const"
+    qualifiers: String,
+}
+
+impl Name {
+    fn plain(text: String) -> Self {
+        Self { text, is_template: false, no_return: false, qualifiers: String::new() }
+    }
+}
+
+/// A template argument; parameter packs stay split so `Dp` expansions can
+/// walk them
+#[derive(Clone)]
+enum TemplateArg {
+    Single(String),
+    Pack(Vec<String>),
+}
+
+impl TemplateArg {
+    fn text(&self) -> String {
+        match self {
+            TemplateArg::Single(arg) => arg.clone(),
+            TemplateArg::Pack(args) => args.join(", "),
+        }
+    }
+}
+
 struct ItaniumDemangler<'a> {
-    input: &'a str,
+    input: &'a [u8],
     pos: usize,
     substitutions: Vec<String>,
+    template_args: Vec<TemplateArg>,
+    /// Pack element `T_` stands for while a `Dp` expansion is parsed
+    pack_index: Option<usize>,
+    /// Length of the pack the current expansion walks, once one is seen
+    pack_len: Option<usize>,
+    /// Inside a lambda signature, where `T_` is one of its `auto` parameters
+    in_lambda: bool,
+    depth: usize,
 }
 
 impl<'a> ItaniumDemangler<'a> {
     fn new(input: &'a str) -> Self {
         Self {
-            input,
+            input: input.as_bytes(),
             pos: 0,
             substitutions: Vec::new(),
+            template_args: Vec::new(),
+            pack_index: None,
+            pack_len: None,
+            in_lambda: false,
+            depth: 0,
         }
     }
 
     fn demangle(&mut self) -> Option<String> {
-        let mut result = String::new();
+        let result = self.parse_encoding(true)?;
+        if self.pos == self.input.len() {
+            Some(result)
+        } else {
+            None
+        }
+    }
 
-        while self.pos < self.input.len() {
-            let c = self.peek()?;
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.pos).map(|&b| b as char)
+    }
 
-            match c {
-                'N' => {
-                    self.advance();
-                    result.push_str(&self.parse_nested_name()?);
-                }
-                'L' => {
-                    self.advance();
-                    continue;
-                }
-                '0'..='9' => {
-                    let name = self.parse_source_name()?;
-                    if !result.is_empty() {
-                        result.push_str("::");
-                    }
-                    result.push_str(&name);
-                }
-                'S' => {
-                    self.advance();
-                    if let Some(sub) = self.parse_substitution() {
-                        if !result.is_empty() {
-                            result.push_str("::");
-                        }
-                        result.push_str(&sub);
-                    }
-                }
-                'v' | 'i' | 'l' | 'x' | 'f' | 'd' | 'b' | 'c' | 's' => {
-                    break;
-                }
-                'E' => {
-                    self.advance();
-                    break;
-                }
-                _ => {
-                    self.advance();
-                }
-            }
+    fn peek_next(&self) -> Option<char> {
+        self.input.get(self.pos + 1).map(|&b| b as char)
+    }
+
+    fn advance(&mut self) {
+        self.pos += 1;
+    }
+
+    fn consume(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.advance();
+            true
+        } else {
+            false
         }
+    }
 
-        if result.is_empty() {
-            None
+    fn at_end(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    /// `<encoding>`: a function name and its parameters, a data name on
+    /// its own, or a special name
+    /// `with_return` is off for the function around a local name, whose
+    /// return type c++filt leaves out
+    fn parse_encoding(&mut self, with_return: bool) -> Option<String> {
+        match (self.peek()?, self.peek_next()) {
+            ('T', _) | ('G', Some('V')) => return self.parse_special_name(),
+            _ => {}
+        }
+
+        let name = self.parse_name(true)?;
+        if self.at_end() || self.peek() == Some('E') {
+            return Some(name.text);
+        }
+
+        let return_type = if name.is_template && !name.no_return {
+            Some(self.parse_type()?)
         } else {
-            Some(result)
+            None
+        };
+
+        let mut params = Vec::new();
+        while !self.at_end() && self.peek() != Some('E') {
+            params.push(self.parse_type()?);
+        }
+        if params.is_empty() {
+            return None;
+        }
+        // Expansions of empty packs
+        params.retain(|param| !param.is_empty());
+
+        let signature = format!("{}({}){}", name.text, format_params(&params), name.qualifiers);
+        match return_type {
+            Some(ret) if with_return => declare(&ret, &signature),
+            _ => Some(signature),
         }
     }
 
-    fn peek(&self) -> Option<char> {
-        self.input.chars().nth(self.pos)
+    fn parse_special_name(&mut self) -> Option<String> {
+        let first = self.peek()?;
+        self.advance();
+        let second = self.peek()?;
+        self.advance();
+
+        match (first, second) {
+            ('T', 'V') => Some(format!("vtable for {}", self.parse_type()?)),
+            ('T', 'T') => Some(format!("VTT for {}", self.parse_type()?)),
+            ('T', 'I') => Some(format!("typeinfo for {}", self.parse_type()?)),
+            ('T', 'S') => Some(format!("typeinfo name for {}", self.parse_type()?)),
+            ('T', 'h') => {
+                self.parse_offset()?;
+                Some(format!("non-virtual thunk to {}", self.parse_encoding(true)?))
+            }
+            ('T', 'v') => {
+                self.parse_offset()?;
+                self.parse_offset()?;
+                Some(format!("virtual thunk to {}", self.parse_encoding(true)?))
+            }
+            ('G', 'V') => Some(format!("guard variable for {}", self.parse_name(false)?.text)),
+            _ => None,
+        }
     }
 
-    fn advance(&mut self) {
-        self.pos += 1;
+    /// `[n] <number> _` in a thunk's call offset
+    fn parse_offset(&mut self) -> Option<()> {
+        self.consume('n');
+        self.parse_number()?;
+        self.consume('_').then_some(())
     }
 
-    fn parse_nested_name(&mut self) -> Option<String> {
-        let mut result = String::new();
+    fn parse_number(&mut self) -> Option<usize> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.advance();
+        }
+        std::str::from_utf8(&self.input[start..self.pos]).ok()?.parse().ok()
+    }
 
-        while self.pos < self.input.len() {
+    fn parse_name(&mut self, record: bool) -> Option<Name> {
+        match (self.peek()?, self.peek_next()) {
+            ('N', _) => self.parse_nested_name(record),
+            ('Z', _) => self.parse_local_name(),
+            ('S', Some('t')) => {
+                self.pos += 2;
+                let (unqualified, no_return) = self.parse_unqualified_name("")?;
+                let mut name = Name::plain(format!("std::{}", unqualified));
+                name.no_return = no_return;
+                self.parse_unscoped_template(&mut name, record)?;
+                Some(name)
+            }
+            ('S', _) => {
+                // A substitution only names an entity as a template
+                let mut name = Name::plain(self.parse_substitution()?);
+                if self.peek() != Some('I') {
+                    return None;
+                }
+                push_template_args(&mut name.text, &self.parse_template_args(record)?);
+                name.is_template = true;
+                Some(name)
+            }
+            _ => {
+                let (unqualified, no_return) = self.parse_unqualified_name("")?;
+                let mut name = Name::plain(unqualified);
+                name.no_return = no_return;
+                self.parse_unscoped_template(&mut name, record)?;
+                Some(name)
+            }
+        }
+    }
+
+    fn parse_unscoped_template(&mut self, name: &mut Name, record: bool) -> Option<()> {
+        if self.peek() == Some('I') {
+            self.substitutions.push(name.text.clone());
+            push_template_args(&mut name.text, &self.parse_template_args(record)?);
+            name.is_template = true;
+        }
+        Some(())
+    }
+
+    /// `N [<cv-qualifiers>] [<ref-qualifier>] <prefix> <unqualified-name> E`
+    fn parse_nested_name(&mut self, record: bool) -> Option<Name> {
+        if !self.consume('N') {
+            return None;
+        }
+
+        let mut name = Name::plain(String::new());
+        name.qualifiers = self.parse_cv_qualifiers();
+        if self.consume('R') {
+            name.qualifiers.push_str(" &");
+        } else if self.consume('O') {
+            name.qualifiers.push_str(" &&");
+        }
+
+        // Bare class name, for constructors and destructors
+        let mut last = String::new();
+
+        loop {
             let c = self.peek()?;
+            if c == 'E' {
+                self.advance();
+                break;
+            }
 
             match c {
-                'E' => {
-                    self.advance();
-                    break;
-                }
-                '0'..='9' => {
-                    let name = self.parse_source_name()?;
-                    if !result.is_empty() {
-                        result.push_str("::");
-                    }
-                    result.push_str(&name);
-                }
                 'S' => {
-                    self.advance();
-                    if let Some(sub) = self.parse_substitution() {
-                        if !result.is_empty() {
-                            result.push_str("::");
-                        }
-                        result.push_str(&sub);
-                    }
-                }
-                'C' | 'D' => {
-                    self.advance();
-                    if let Some(c2) = self.peek() {
-                        if c2.is_ascii_digit() {
-                            self.advance();
-                        }
+                    if !name.text.is_empty() {
+                        return None;
                     }
+                    name.text = self.parse_substitution()?;
+                    let unargued = name.text.split('<').next().unwrap_or_default();
+                    last = unargued.rsplit("::").next().unwrap_or_default().to_string();
                 }
                 'I' => {
-                    self.advance();
-                    result.push('<');
-                    let mut first = true;
-                    while self.peek() != Some('E') {
-                        if !first {
-                            result.push_str(", ");
-                        }
-                        first = false;
-                        if let Some(arg) = self.parse_type() {
-                            result.push_str(&arg);
-                        } else {
-                            break;
-                        }
+                    if name.text.is_empty() {
+                        return None;
                     }
-                    if self.peek() == Some('E') {
-                        self.advance();
+                    push_template_args(&mut name.text, &self.parse_template_args(record)?);
+                    name.is_template = true;
+                }
+                'T' => {
+                    if !name.text.is_empty() {
+                        return None;
                     }
-                    result.push('>');
+                    name.text = self.parse_template_param()?;
                 }
                 _ => {
-                    self.advance();
+                    let (unqualified, no_return) = self.parse_unqualified_name(&last)?;
+                    if !no_return {
+                        last = unqualified.clone();
+                    }
+                    name.text = if name.text.is_empty() {
+                        unqualified
+                    } else {
+                        format!("{}::{}", name.text, unqualified)
+                    };
+                    name.is_template = false;
+                    name.no_return = no_return;
                 }
             }
+
+            // Every prefix is a candidate, but the whole name is not and
+            // substitutions are not added again
+            if c != 'S' && self.peek() != Some('E') {
+                self.substitutions.push(name.text.clone());
+            }
         }
 
-        if result.is_empty() {
+        if name.text.is_empty() {
             None
         } else {
-            Some(result)
+            Some(name)
         }
     }
 
-    fn parse_source_name(&mut self) -> Option<String> {
-        let mut len_str = String::new();
-
-        while let Some(c) = self.peek() {
-            if c.is_ascii_digit() {
-                len_str.push(c);
-                self.advance();
-            } else {
-                break;
-            }
+    /// `Z <encoding> E <entity name> [<discriminator>]`
+    fn parse_local_name(&mut self) -> Option<Name> {
+        if !self.consume('Z') {
+            return None;
         }
-
-        let len: usize = len_str.parse().ok()?;
-
-        if self.pos + len > self.input.len() {
+        let function = self.parse_encoding(false)?;
+        if !self.consume('E') {
             return None;
         }
 
-        let name = self.input[self.pos..self.pos + len].to_string();
-        self.pos += len;
+        if self.consume('s') {
+            self.parse_discriminator();
+            return Some(Name::plain(format!("{}::string literal", function)));
+        }
 
-        self.substitutions.push(name.clone());
+        let mut entity = self.parse_name(false)?;
+        self.parse_discriminator();
+        entity.text = format!("{}::{}", function, entity.text);
+        Some(entity)
+    }
 
-        Some(name)
+    /// `_ <digit>` or `__ <number> _`; only tells same-named locals apart,
+    /// which c++filt doesn't print
+    fn parse_discriminator(&mut self) {
+        if self.peek() != Some('_') {
+            return;
+        }
+        let start = self.pos;
+        self.advance();
+        let ok = if self.consume('_') {
+            self.parse_number().is_some() && self.consume('_')
+        } else {
+            self.parse_number().is_some()
+        };
+        if !ok {
+            self.pos = start;
+        }
     }
 
-    fn parse_substitution(&mut self) -> Option<String> {
+    /// Returns the name and whether it is a constructor, destructor or
+    /// conversion operator. `class` is the enclosing class's bare name.
+    fn parse_unqualified_name(&mut self, class: &str) -> Option<(String, bool)> {
         let c = self.peek()?;
-
-        match c {
-            't' => {
+        let (mut name, no_return) = match c {
+            '0'..='9' => (self.parse_source_name()?, false),
+            'L' => {
+                // Internal linkage
                 self.advance();
-                Some("std".to_string())
+                return self.parse_unqualified_name(class);
             }
-            'a' => {
+            'C' => {
                 self.advance();
-                Some("std::allocator".to_string())
+                match self.peek()? {
+                    '1'..='5' if !class.is_empty() => self.advance(),
+                    _ => return None,
+                }
+                (class.to_string(), true)
             }
-            'b' => {
+            'D' => {
                 self.advance();
-                Some("std::basic_string".to_string())
+                match self.peek()? {
+                    '0' | '1' | '2' | '4' | '5' if !class.is_empty() => self.advance(),
+                    _ => return None,
+                }
+                (format!("~{}", class), true)
             }
-            's' => {
+            'U' => (self.parse_unnamed_type()?, false),
+            'a'..='z' => self.parse_operator_name()?,
+            _ => return None,
+        };
+
+        // ABI tags: B <source-name>
+        while self.consume('B') {
+            name.push_str(&format!("[abi:{}]", self.parse_source_name()?));
+        }
+
+        Some((name, no_return))
+    }
+
+    /// `Ul <lambda-sig> E [<number>] _` or `Ut [<number>] _`
+    fn parse_unnamed_type(&mut self) -> Option<String> {
+        self.advance();
+        match self.peek()? {
+            'l' => {
                 self.advance();
-                Some("std::string".to_string())
+                let in_lambda = std::mem::replace(&mut self.in_lambda, true);
+                let mut params = Vec::new();
+                while self.peek() != Some('E') {
+                    match self.parse_type() {
+                        Some(param) => params.push(param),
+                        None => break,
+                    }
+                }
+                self.in_lambda = in_lambda;
+                if !self.consume('E') {
+                    return None;
+                }
+                let index = self.parse_closure_index()?;
+                let params = format_params(&params);
+                let params = resolve_lambda_params(&params, |i| Some(format!("auto:{}", i + 1)))?;
+                Some(format!("{{lambda({})#{}}}", params, index))
             }
-            'i' => {
+            't' => {
                 self.advance();
-                Some("std::istream".to_string())
+                let index = self.parse_closure_index()?;
+                Some(format!("{{unnamed type#{}}}", index))
             }
-            'o' => {
-                self.advance();
-                Some("std::ostream".to_string())
+            _ => None,
+        }
+    }
+
+    /// `_` is the first, `<n>_` the n+2nd
+    fn parse_closure_index(&mut self) -> Option<usize> {
+        let index = if self.peek()?.is_ascii_digit() {
+            self.parse_number()? + 2
+        } else {
+            1
+        };
+        self.consume('_').then_some(index)
+    }
+
+    fn parse_operator_name(&mut self) -> Option<(String, bool)> {
+        let code = [self.peek()?, self.peek_next()?];
+        self.pos += 2;
+
+        if code == ['c', 'v'] {
+            return Some((format!("operator {}", self.parse_type()?), true));
+        }
+
+        let symbol = match code {
+            ['n', 'w'] => " new",
+            ['n', 'a'] => " new[]",
+            ['d', 'l'] => " delete",
+            ['d', 'a'] => " delete[]",
+            ['p', 's'] | ['p', 'l'] => "+",
+            ['n', 'g'] | ['m', 'i'] => "-",
+            ['a', 'd'] | ['a', 'n'] => "&",
+            ['d', 'e'] | ['m', 'l'] => "*",
+            ['c', 'o'] => "~",
+            ['d', 'v'] => "/",
+            ['r', 'm'] => "%",
+            ['o', 'r'] => "|",
+            ['e', 'o'] => "^",
+            ['a', 'S'] => "=",
+            ['p', 'L'] => "+=",
+            ['m', 'I'] => "-=",
+            ['m', 'L'] => "*=",
+            ['d', 'V'] => "/=",
+            ['r', 'M'] => "%=",
+            ['a', 'N'] => "&=",
+            ['o', 'R'] => "|=",
+            ['e', 'O'] => "^=",
+            ['l', 's'] => "<<",
+            ['r', 's'] => ">>",
+            ['l', 'S'] => "<<=",
+            ['r', 'S'] => ">>=",
+            ['e', 'q'] => "==",
+            ['n', 'e'] => "!=",
+            ['l', 't'] => "<",
+            ['g', 't'] => ">",
+            ['l', 'e'] => "<=",
+            ['g', 'e'] => ">=",
+            ['s', 's'] => "<=>",
+            ['n', 't'] => "!",
+            ['a', 'a'] => "&&",
+            ['o', 'o'] => "||",
+            ['p', 'p'] => "++",
+            ['m', 'm'] => "--",
+            ['c', 'm'] => ",",
+            ['p', 'm'] => "->*",
+            ['p', 't'] => "->",
+            ['c', 'l'] => "()",
+            ['i', 'x'] => "[]",
+            _ => return None,
+        };
+
+        Some((format!("operator{}", symbol), false))
+    }
+
+    /// `<length> <identifier>`; not itself a substitution candidate
+    fn parse_source_name(&mut self) -> Option<String> {
+        let len = self.parse_number()?;
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.input.len() && len > 0)?;
+
+        let name = std::str::from_utf8(&self.input[self.pos..end]).ok()?;
+        self.pos = end;
+
+        if name.starts_with("_GLOBAL__N") {
+            Some("(anonymous namespace)".to_string())
+        } else {
+            Some(name.to_string())
+        }
+    }
+
+    /// After an `S`: a back-reference or one of the `std::` abbreviations
+    fn parse_substitution(&mut self) -> Option<String> {
+        if !self.consume('S') {
+            return None;
+        }
+
+        let c = self.peek()?;
+        self.advance();
+        match c {
+            't' => Some("std".to_string()),
+            'a' => Some("std::allocator".to_string()),
+            'b' => Some("std::basic_string".to_string()),
+            's' => Some("std::basic_string<char, std::char_traits<char>, std::allocator<char> >".to_string()),
+            'i' => Some("std::basic_istream<char, std::char_traits<char> >".to_string()),
+            'o' => Some("std::basic_ostream<char, std::char_traits<char> >".to_string()),
+            'd' => Some("std::basic_iostream<char, std::char_traits<char> >".to_string()),
+            '_' => self.resolve_substitution(0),
+            '0'..='9' | 'A'..='Z' => {
+                let start = self.pos - 1;
+                while self.peek()?.is_ascii_alphanumeric() {
+                    self.advance();
+                }
+                let seq = std::str::from_utf8(&self.input[start..self.pos]).ok()?;
+                let index = parse_base36(seq)?;
+                if !self.consume('_') {
+                    return None;
+                }
+                self.resolve_substitution(index + 1)
             }
-            'd' => {
-                self.advance();
-                Some("std::iostream".to_string())
+            _ => None,
+        }
+    }
+
+    fn resolve_substitution(&self, index: usize) -> Option<String> {
+        let text = self.substitutions.get(index)?;
+        if self.in_lambda {
+            return Some(text.clone());
+        }
+        resolve_lambda_params(text, |i| match self.template_args.get(i)? {
+            TemplateArg::Single(arg) => Some(arg.clone()),
+            TemplateArg::Pack(args) => Some(args.join(", ")),
+        })
+    }
+
+    /// `T_` is the first template argument, `T<n>_` the n+2nd
+    fn parse_template_param(&mut self) -> Option<String> {
+        if !self.consume('T') {
+            return None;
+        }
+        let index = if self.consume('_') {
+            0
+        } else {
+            let n = self.parse_number()?;
+            if !self.consume('_') {
+                return None;
             }
-            '_' => {
+            n + 1
+        };
+
+        // Resolved to `auto:N` for the lambda itself, or to the enclosing
+        // template's arguments if a substitution refers back to it later
+        if self.in_lambda {
+            return Some(format!("{}{}{}", LAMBDA_PARAM_START, index, LAMBDA_PARAM_END));
+        }
+        match self.template_args.get(index)? {
+            TemplateArg::Single(arg) => Some(arg.clone()),
+            TemplateArg::Pack(args) => match self.pack_index {
+                Some(i) => {
+                    self.pack_len = Some(args.len());
+                    Some(args.get(i).cloned().unwrap_or_default())
+                }
+                None => Some(args.join(", ")),
+            },
+        }
+    }
+
+    /// `I <template-arg>+ E`, formatted `<a, b>`. `record` keeps them for
+    /// `T_` references, which always mean the encoding's own arguments.
+    fn parse_template_args(&mut self, record: bool) -> Option<String> {
+        if !self.consume('I') {
+            return None;
+        }
+
+        let mut args = Vec::new();
+        while self.peek()? != 'E' {
+            args.push(self.parse_template_arg()?);
+        }
+        self.advance();
+
+        let texts: Vec<String> = args.iter()
+            .map(TemplateArg::text)
+            .filter(|text| !text.is_empty())
+            .collect();
+        let mut text = format!("<{}", texts.join(", "));
+        // c++filt keeps `> >` apart, except after a trailing empty pack
+        let empty_pack = matches!(args.last(), Some(TemplateArg::Pack(pack)) if pack.is_empty());
+        if text.ends_with('>') && !empty_pack {
+            text.push(' ');
+        }
+        text.push('>');
+
+        if record {
+            self.template_args = args;
+        }
+        Some(text)
+    }
+
+    fn parse_template_arg(&mut self) -> Option<TemplateArg> {
+        match self.peek()? {
+            'L' => self.parse_literal().map(TemplateArg::Single),
+            'J' => {
                 self.advance();
-                self.substitutions.first().cloned()
-            }
-            '0'..='9' | 'A'..='Z' => {
-                let mut idx_str = String::new();
-                while let Some(c) = self.peek() {
-                    if c == '_' {
-                        self.advance();
-                        break;
-                    }
-                    if c.is_ascii_alphanumeric() {
-                        idx_str.push(c);
-                        self.advance();
-                    } else {
-                        break;
-                    }
+                let mut pack = Vec::new();
+                while self.peek()? != 'E' {
+                    pack.push(self.parse_template_arg()?.text());
                 }
-                let idx = parse_base36(&idx_str)?;
-                self.substitutions.get(idx + 1).cloned()
+                self.advance();
+                Some(TemplateArg::Pack(pack))
             }
-            _ => None,
+            'X' => None,
+            _ => self.parse_type().map(TemplateArg::Single),
+        }
+    }
+
+    /// `Dp <type>`: the type once per element of the pack it names, e.g.
+    /// `Dp RK T_` over `<int, char>` is `int const&, char const&`
+    fn parse_pack_expansion(&mut self) -> Option<String> {
+        let start = self.pos;
+        let outer = (self.pack_index, self.pack_len);
+        self.pack_index = Some(0);
+        self.pack_len = None;
+
+        let mut elements = vec![self.parse_type()];
+        let end = self.pos;
+        let substitutions = self.substitutions.len();
+
+        for i in 1..self.pack_len.unwrap_or(1) {
+            self.pos = start;
+            self.pack_index = Some(i);
+            elements.push(self.parse_type());
         }
+        self.pos = end;
+        self.substitutions.truncate(substitutions);
+
+        let empty = self.pack_len == Some(0);
+        (self.pack_index, self.pack_len) = outer;
+
+        let elements: Option<Vec<String>> = elements.into_iter().collect();
+        let ty = if empty { String::new() } else { elements?.join(", ") };
+        self.substitutions.push(ty.clone());
+        Some(ty)
+    }
+
+    /// `L <type> [n] <value> E`
+    fn parse_literal(&mut self) -> Option<String> {
+        if !self.consume('L') || self.peek()? == '_' {
+            return None;
+        }
+
+        let ty = self.parse_type()?;
+        let negative = self.consume('n');
+        let value = self.parse_number()?;
+        if !self.consume('E') {
+            return None;
+        }
+        let value = if negative { format!("-{}", value) } else { value.to_string() };
+
+        Some(match ty.as_str() {
+            "bool" if value == "0" => "false".to_string(),
+            "bool" if value == "1" => "true".to_string(),
+            "int" => value,
+            "unsigned int" => format!("{}u", value),
+            "long" => format!("{}l", value),
+            "unsigned long" => format!("{}ul", value),
+            "long long" => format!("{}ll", value),
+            "unsigned long long" => format!("{}ull", value),
+            _ => format!("({}){}", ty, value),
+        })
+    }
+
+    /// `r`, `V` and `K` in mangling order, printed the way c++filt does
+    fn parse_cv_qualifiers(&mut self) -> String {
+        let restrict = self.consume('r');
+        let volatile = self.consume('V');
+        let constant = self.consume('K');
+
+        let mut qualifiers = String::new();
+        if restrict {
+            qualifiers.push_str(" restrict");
+        }
+        if volatile {
+            qualifiers.push_str(" volatile");
+        }
+        if constant {
+            qualifiers.push_str(" const");
+        }
+        qualifiers
     }
 
     fn parse_type(&mut self) -> Option<String> {
+        if self.depth >= MAX_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let ty = self.parse_type_inner();
+        self.depth -= 1;
+        ty
+    }
+
+    fn parse_type_inner(&mut self) -> Option<String> {
         let c = self.peek()?;
 
-        match c {
-            'v' => { self.advance(); Some("void".to_string()) }
-            'w' => { self.advance(); Some("wchar_t".to_string()) }
-            'b' => { self.advance(); Some("bool".to_string()) }
-            'c' => { self.advance(); Some("char".to_string()) }
-            'a' => { self.advance(); Some("signed char".to_string()) }
-            'h' => { self.advance(); Some("unsigned char".to_string()) }
-            's' => { self.advance(); Some("short".to_string()) }
-            't' => { self.advance(); Some("unsigned short".to_string()) }
-            'i' => { self.advance(); Some("int".to_string()) }
-            'j' => { self.advance(); Some("unsigned int".to_string()) }
-            'l' => { self.advance(); Some("long".to_string()) }
-            'm' => { self.advance(); Some("unsigned long".to_string()) }
-            'x' => { self.advance(); Some("long long".to_string()) }
-            'y' => { self.advance(); Some("unsigned long long".to_string()) }
-            'f' => { self.advance(); Some("float".to_string()) }
-            'd' => { self.advance(); Some("double".to_string()) }
-            'e' => { self.advance(); Some("long double".to_string()) }
-            'P' => {
-                self.advance();
-                let inner = self.parse_type()?;
-                Some(format!("{}*", inner))
+        let builtin = match c {
+            'v' => Some("void"),
+            'w' => Some("wchar_t"),
+            'b' => Some("bool"),
+            'c' => Some("char"),
+            'a' => Some("signed char"),
+            'h' => Some("unsigned char"),
+            's' => Some("short"),
+            't' => Some("unsigned short"),
+            'i' => Some("int"),
+            'j' => Some("unsigned int"),
+            'l' => Some("long"),
+            'm' => Some("unsigned long"),
+            'x' => Some("long long"),
+            'y' => Some("unsigned long long"),
+            'n' => Some("__int128"),
+            'o' => Some("unsigned __int128"),
+            'f' => Some("float"),
+            'd' => Some("double"),
+            'e' => Some("long double"),
+            'g' => Some("__float128"),
+            'z' => Some("..."),
+            _ => None,
+        };
+        if let Some(builtin) = builtin {
+            self.advance();
+            return Some(builtin.to_string());
+        }
+
+        let ty = match c {
+            'D' => {
+                let builtin = match self.peek_next()? {
+                    'n' => "decltype(nullptr)",
+                    's' => "char16_t",
+                    'i' => "char32_t",
+                    'u' => "char8_t",
+                    'a' => "auto",
+                    'c' => "decltype(auto)",
+                    'p' => {
+                        self.pos += 2;
+                        return self.parse_pack_expansion();
+                    }
+                    _ => return None,
+                };
+                self.pos += 2;
+                return Some(builtin.to_string());
             }
-            'R' => {
+            'u' => {
                 self.advance();
-                let inner = self.parse_type()?;
-                Some(format!("{}&", inner))
+                return self.parse_source_name();
             }
-            'K' => {
+            'P' | 'R' | 'O' => {
                 self.advance();
-                let inner = self.parse_type()?;
-                Some(format!("const {}", inner))
+                let (suffix, wrapped) = match c {
+                    'P' => ("*", "(*)"),
+                    'R' => ("&", "(&)"),
+                    _ => ("&&", "(&&)"),
+                };
+                match self.peek()? {
+                    'F' => {
+                        let (ret, params) = self.parse_function_type()?;
+                        format!("{} {}({})", ret, wrapped, params)
+                    }
+                    'A' => {
+                        let (element, dimension) = self.parse_array_type()?;
+                        format!("{} {} [{}]", element, wrapped, dimension)
+                    }
+                    _ => {
+                        let inner = self.parse_type()?;
+                        // Reference collapsing: `T&&` with `T = X&` is `X&`
+                        match reference_declarator(&inner) {
+                            Some(declarator) if c != 'P' => {
+                                if c == 'R' && declarator.ends_with("&&") {
+                                    let lvalue = declarator.replacen("&&", "&", 1);
+                                    inner.replacen(declarator, &lvalue, 1)
+                                } else {
+                                    inner
+                                }
+                            }
+                            _ => format!("{}{}", inner, suffix),
+                        }
+                    }
+                }
             }
-            'N' => {
-                self.advance();
-                self.parse_nested_name()
+            'r' | 'V' | 'K' => {
+                let qualifiers = self.parse_cv_qualifiers();
+                format!("{}{}", self.parse_type()?, qualifiers)
             }
-            '0'..='9' => {
-                self.parse_source_name()
+            'F' => {
+                let (ret, params) = self.parse_function_type()?;
+                return Some(format!("{} ({})", ret, params));
             }
-            'S' => {
-                self.advance();
-                self.parse_substitution()
+            'A' => {
+                let (element, dimension) = self.parse_array_type()?;
+                return Some(format!("{} [{}]", element, dimension));
             }
-            _ => {
+            'M' => {
                 self.advance();
-                None
+                let class = self.parse_type()?;
+                if self.peek()? == 'F' {
+                    let (ret, params) = self.parse_function_type()?;
+                    format!("{} ({}::*)({})", ret, class, params)
+                } else {
+                    format!("{} {}::*", self.parse_type()?, class)
+                }
+            }
+            'T' => {
+                let mut ty = self.parse_template_param()?;
+                if self.peek() == Some('I') {
+                    self.substitutions.push(ty.clone());
+                    ty.push_str(&self.parse_template_args(false)?);
+                }
+                ty
+            }
+            'S' if self.peek_next()? != 't' => {
+                let sub = self.parse_substitution()?;
+                if self.peek() != Some('I') {
+                    return Some(sub);
+                }
+                format!("{}{}", sub, self.parse_template_args(false)?)
+            }
+            'N' | 'Z' | 'S' | '0'..='9' => self.parse_name(false)?.text,
+            _ => return None,
+        };
+
+        self.substitutions.push(ty.clone());
+        Some(ty)
+    }
+
+    /// `A <dimension> _ <element type>`, as the element type and the
+    /// dimension, which may be a template parameter
+    fn parse_array_type(&mut self) -> Option<(String, String)> {
+        if !self.consume('A') {
+            return None;
+        }
+        let dimension = if self.peek()? == 'T' {
+            self.parse_template_param()?
+        } else {
+            self.parse_number()?.to_string()
+        };
+        if !self.consume('_') {
+            return None;
+        }
+
+        let element = self.parse_type()?;
+        self.substitutions.push(format!("{} [{}]", element, dimension));
+        Some((element, dimension))
+    }
+
+    /// `F [Y] <return type> <parameter types> [<ref-qualifier>] E`, as the
+    /// return type and the formatted parameter list
+    fn parse_function_type(&mut self) -> Option<(String, String)> {
+        if !self.consume('F') {
+            return None;
+        }
+        self.consume('Y');
+
+        let ret = self.parse_type()?;
+        let mut params = Vec::new();
+        loop {
+            match self.peek()? {
+                'E' => break,
+                'R' | 'O' if self.peek_next() == Some('E') => self.advance(),
+                _ => params.push(self.parse_type()?),
             }
         }
+        self.advance();
+
+        self.substitutions.push(format!("{} ({})", ret, format_params(&params)));
+        Some((ret, format_params(&params)))
     }
 }
 
+/// The trailing `&`/`&&` of a reference type, or the `(&)`/`(&&)` of a
+/// reference to an array or function
+fn reference_declarator(ty: &str) -> Option<&str> {
+    if ty.ends_with("&&") {
+        return Some(&ty[ty.len() - 2..]);
+    }
+    if ty.ends_with('&') {
+        return Some(&ty[ty.len() - 1..]);
+    }
+    if ty.ends_with(']') || ty.ends_with(')') {
+        return ["(&&)", "(&)"].into_iter()
+            .find_map(|declarator| ty.find(declarator).map(|i| &ty[i..i + declarator.len()]));
+    }
+    None
+}
+
+/// Declares `signature` as returning `ret`. A function or array type
+/// wraps around it, e.g. `char const (&f()) [2]`.
+fn declare(ret: &str, signature: &str) -> Option<String> {
+    if !ret.ends_with(']') && !ret.ends_with(')') {
+        return Some(format!("{} {}", ret, signature));
+    }
+    let declarator = ["(*)", "(&)", "(&&)"].into_iter()
+        .filter_map(|declarator| ret.find(declarator).map(|i| i + declarator.len() - 1))
+        .min()?;
+    Some(format!("{}{}{}", &ret[..declarator], signature, &ret[declarator..]))
+}
+
+/// `operator<` keeps its own `<` apart from the argument list
+fn push_template_args(name: &mut String, args: &str) {
+    if name.ends_with('<') {
+        name.push(' ');
+    }
+    name.push_str(args);
+}
+
+/// A lone `void` is an empty parameter list
+fn format_params(params: &[String]) -> String {
+    if params.len() == 1 && params[0] == "void" {
+        String::new()
+    } else {
+        params.join(", ")
+    }
+}
+
+/// Replaces each bracketed lambda parameter index with `resolve(index)`
+fn resolve_lambda_params(text: &str, resolve: impl Fn(usize) -> Option<String>) -> Option<String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(LAMBDA_PARAM_START) {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + LAMBDA_PARAM_START.len_utf8()..];
+        let end = after.find(LAMBDA_PARAM_END)?;
+        result.push_str(&resolve(after[..end].parse().ok()?)?);
+        rest = &after[end + LAMBDA_PARAM_END.len_utf8()..];
+    }
+    result.push_str(rest);
+    Some(result)
+}
+
 fn parse_base36(s: &str) -> Option<usize> {
     let mut result = 0usize;
     for c in s.chars() {
@@ -319,7 +994,7 @@ fn parse_base36(s: &str) -> Option<usize> {
         } else {
             return None;
         };
-        result = result * 36 + digit;
+        result = result.checked_mul(36)?.checked_add(digit)?;
     }
     Some(result)
 }
@@ -436,3 +1111,70 @@ pub fn is_mangled(name: &str) -> bool {
 pub fn try_demangle(name: &str) -> String {
     demangle(name).unwrap_or_else(|| name.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demangle_roblox_symbols() {
+        let cases = [
+            ("__ZN3RBX13ScriptContext6resumeEP9lua_Statei", "RBX::ScriptContext::resume(lua_State*, int)"),
+            ("_ZN3RBX8InstanceC2Ev", "RBX::Instance::Instance()"),
+            ("_ZN3RBX8InstanceD1Ev", "RBX::Instance::~Instance()"),
+            ("_ZNK3RBX8Instance7getNameEv", "RBX::Instance::getName() const"),
+            (
+                "_ZN3RBX8Instance7setNameERKNSt3__112basic_stringIcNS1_11char_traitsIcEENS1_9allocatorIcEEEE",
+                "RBX::Instance::setName(std::__1::basic_string<char, std::__1::char_traits<char>, std::__1::allocator<char> > const&)",
+            ),
+            ("_ZN3RBX7Vector3plERKS0_", "RBX::Vector3::operator+(RBX::Vector3 const&)"),
+            ("_ZN3RBX7Network7Players10maxPlayersE", "RBX::Network::Players::maxPlayers"),
+            ("_ZN12_GLOBAL__N_16helperEv", "(anonymous namespace)::helper()"),
+            ("_Z3maxIiET_S0_S0_", "int max<int>(int, int)"),
+            ("_Z13luaL_registerP9lua_StatePFiS0_E", "luaL_register(lua_State*, int (*)(lua_State*))"),
+            ("_ZN3RBX4tickEv.cold.1", "RBX::tick() [clone .cold.1]"),
+        ];
+
+        for (mangled, expected) in cases {
+            assert_eq!(demangle(mangled).as_deref(), Some(expected), "{}", mangled);
+        }
+    }
+
+    #[test]
+    fn test_demangle_special_and_local_names() {
+        let cases = [
+            ("__ZTVN3RBX9DataModelE", "vtable for RBX::DataModel"),
+            ("_ZTIN3RBX8InstanceE", "typeinfo for RBX::Instance"),
+            ("_ZThn16_N3RBX9DataModelD1Ev", "non-virtual thunk to RBX::DataModel::~DataModel()"),
+            ("_ZGVZN3RBX8Instance11classNameOfEvE4name", "guard variable for RBX::Instance::classNameOf()::name"),
+            (
+                "_ZZN3RBX13TaskSchedulerC1EvENKUlvE_clEv",
+                "RBX::TaskScheduler::TaskScheduler()::{lambda()#1}::operator()() const",
+            ),
+            (
+                "_ZN9__gnu_cxx5__ops15__val_comp_iterIZN3RBX3runEvEUlRT_RT0_E0_EENS0_14_Val_comp_iterIS3_EENS0_15_Iter_comp_iterIS3_EE",
+                "__gnu_cxx::__ops::_Val_comp_iter<RBX::run()::{lambda(auto:1&, auto:2&)#2}> \
+                 __gnu_cxx::__ops::__val_comp_iter<RBX::run()::{lambda(auto:1&, auto:2&)#2}>\
+                 (__gnu_cxx::__ops::_Iter_comp_iter<RBX::run()::{lambda(auto:1&, auto:2&)#2}>)",
+            ),
+            (
+                "_ZSt7forwardIRA2_KcEOT_RNSt16remove_referenceIS3_E4typeE",
+                "char const (&std::forward<char const (&) [2]>(std::remove_reference<char const (&) [2]>::type&)) [2]",
+            ),
+        ];
+
+        for (mangled, expected) in cases {
+            assert_eq!(demangle(mangled).as_deref(), Some(expected), "{}", mangled);
+        }
+    }
+
+    #[test]
+    fn test_demangle_rejects_unrecognized_input() {
+        for input in ["", "main", "_Z", "_ZN3RBX", "_ZN99foo", "_Z3fooQ", "_ZS_", "_ZN3RBXC9Ev"] {
+            assert_eq!(demangle(input), None, "{}", input);
+        }
+
+        let deep = format!("_Z1f{}v", "P".repeat(10_000));
+        assert_eq!(demangle(&deep), None);
+    }
+}
@@ -1,6 +1,8 @@
 // Tue Jan 15 2026 - Alex
 
+use crate::finders::CombinedResults;
 use crate::symbol::{Symbol, SymbolType};
+use std::collections::HashMap;
 use std::io::Write;
 
 /// Export format for symbols
@@ -10,6 +12,10 @@ pub enum ExportFormat {
     Ida,
     /// Ghidra script format
     Ghidra,
+    /// Ghidra `ImportSymbolsScript.py` input (`name address f|l`)
+    GhidraSymbols,
+    /// Ghidra symbol CSV (`name,address,type,image_base`)
+    GhidraCsv,
     /// Binary Ninja script
     BinaryNinja,
     /// JSON format
@@ -30,6 +36,7 @@ pub enum ExportFormat {
 pub struct SymbolExporter {
     symbols: Vec<ExportableSymbol>,
     base_address: Option<u64>,
+    image_base: Option<u64>,
     include_types: bool,
     include_sizes: bool,
 }
@@ -39,6 +46,7 @@ impl SymbolExporter {
         Self {
             symbols: Vec::new(),
             base_address: None,
+            image_base: None,
             include_types: true,
             include_sizes: true,
         }
@@ -49,6 +57,13 @@ impl SymbolExporter {
         self
     }
 
+    /// Image base the (absolute) symbol addresses were computed against, so
+    /// importers can rebase when the binary was loaded elsewhere
+    pub fn with_image_base(mut self, base: u64) -> Self {
+        self.image_base = Some(base);
+        self
+    }
+
     pub fn without_types(mut self) -> Self {
         self.include_types = false;
         self
@@ -75,11 +90,26 @@ impl SymbolExporter {
         exporter
     }
 
+    /// Build an exporter from the functions and methods discovered by a scan
+    pub fn from_results(results: &CombinedResults) -> Self {
+        let mut exporter = Self::new();
+        for func in &results.functions {
+            exporter.add_symbol(ExportableSymbol::function(&func.name, func.address.as_u64()));
+        }
+        for method in &results.methods {
+            let name = format!("{}::{}", method.class_name, method.method_name);
+            exporter.add_symbol(ExportableSymbol::function(&name, method.address.as_u64()));
+        }
+        exporter
+    }
+
     /// Export to specified format
     pub fn export(&self, format: ExportFormat) -> String {
         match format {
             ExportFormat::Ida => self.to_ida(),
             ExportFormat::Ghidra => self.to_ghidra(),
+            ExportFormat::GhidraSymbols => self.to_ghidra_symbols(),
+            ExportFormat::GhidraCsv => self.to_ghidra_csv(),
             ExportFormat::BinaryNinja => self.to_binary_ninja(),
             ExportFormat::Json => self.to_json(),
             ExportFormat::Csv => self.to_csv(),
@@ -105,7 +135,9 @@ impl SymbolExporter {
 
         script.push_str("def import_symbols():\n");
 
-        for sym in &self.symbols {
+        let names = unique_names(&self.symbols, to_ida_identifier);
+
+        for (sym, name) in self.symbols.iter().zip(&names) {
             let addr = if self.base_address.is_some() {
                 format!("base_addr + 0x{:X}", sym.address)
             } else {
@@ -115,7 +147,7 @@ impl SymbolExporter {
             // Set name
             script.push_str(&format!(
                 "    idc.set_name({}, \"{}\", idc.SN_NOWARN)\n",
-                addr, escape_string(&sym.name)
+                addr, name
             ));
 
             // Set type if function
@@ -140,7 +172,7 @@ impl SymbolExporter {
         }
 
         script.push_str("\nimport_symbols()\n");
-        script.push_str("print(\"Imported {} symbols\")\n");
+        script.push_str(&format!("print(\"Imported {} symbols\")\n", self.symbols.len()));
 
         script
     }
@@ -153,22 +185,36 @@ impl SymbolExporter {
         script.push_str("# @category: Symbols\n\n");
         script.push_str("from ghidra.program.model.symbol import SourceType\n\n");
 
+        if let Some(base) = self.image_base {
+            script.push_str(&format!("IMAGE_BASE = 0x{:X}\n\n", base));
+        }
+
         script.push_str("def run():\n");
         script.push_str("    sm = currentProgram.getSymbolTable()\n");
-        script.push_str("    fm = currentProgram.getFunctionManager()\n\n");
+        script.push_str("    fm = currentProgram.getFunctionManager()\n");
+        if self.image_base.is_some() {
+            script.push_str("    delta = currentProgram.getImageBase().getOffset() - IMAGE_BASE\n");
+        }
+        script.push('\n');
 
-        for sym in &self.symbols {
-            let addr = format!("toAddr(0x{:X})", sym.address);
+        let names = unique_names(&self.symbols, to_ghidra_identifier);
+
+        for (sym, name) in self.symbols.iter().zip(&names) {
+            let addr = if self.image_base.is_some() {
+                format!("toAddr(0x{:X} + delta)", sym.address)
+            } else {
+                format!("toAddr(0x{:X})", sym.address)
+            };
 
             if sym.symbol_type == ExportSymbolType::Function {
                 script.push_str(&format!(
                     "    createFunction({}, \"{}\")\n",
-                    addr, escape_string(&sym.name)
+                    addr, escape_string(name)
                 ));
             } else {
                 script.push_str(&format!(
                     "    createLabel({}, \"{}\", True)\n",
-                    addr, escape_string(&sym.name)
+                    addr, escape_string(name)
                 ));
             }
         }
@@ -178,6 +224,37 @@ impl SymbolExporter {
         script
     }
 
+    /// Export to the whitespace-separated list read by Ghidra's bundled
+    /// `ImportSymbolsScript.py` ("Import Symbols"). That script has no comment
+    /// syntax, so addresses are emitted as absolute values under the image base.
+    fn to_ghidra_symbols(&self) -> String {
+        let mut list = String::new();
+        let names = unique_names(&self.symbols, to_ghidra_identifier);
+
+        for (sym, name) in self.symbols.iter().zip(&names) {
+            let kind = if sym.symbol_type == ExportSymbolType::Function { 'f' } else { 'l' };
+            list.push_str(&format!("{} {:X} {}\n", name, sym.address, kind));
+        }
+
+        list
+    }
+
+    /// Export to CSV for Ghidra. Like the script's `IMAGE_BASE`, every row
+    /// carries the base its address was computed against, so a binary
+    /// loaded elsewhere is rebased with `address - image_base + new_base`.
+    fn to_ghidra_csv(&self) -> String {
+        let mut csv = String::from("name,address,type,image_base\n");
+        let names = unique_names(&self.symbols, to_ghidra_identifier);
+        let base = self.image_base.map(|base| format!("0x{:X}", base)).unwrap_or_default();
+
+        for (sym, name) in self.symbols.iter().zip(&names) {
+            let kind = if sym.symbol_type == ExportSymbolType::Function { "function" } else { "label" };
+            csv.push_str(&format!("{},0x{:X},{},{}\n", escape_csv(name), sym.address, kind, base));
+        }
+
+        csv
+    }
+
     /// Export to Binary Ninja Python script
     fn to_binary_ninja(&self) -> String {
         let mut script = String::new();
@@ -560,6 +637,68 @@ fn to_c_identifier(s: &str) -> String {
     result
 }
 
+/// Sanitize a name into a valid IDA identifier.
+///
+/// IDA accepts `[A-Za-z0-9_$?@]` and rejects a leading digit; everything else
+/// collapses to a single underscore.
+fn to_ida_identifier(s: &str) -> String {
+    let mut result = String::new();
+    let mut prev_underscore = false;
+
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '$' | '?' | '@') {
+            result.push(c);
+            prev_underscore = false;
+        } else if !prev_underscore {
+            result.push('_');
+            prev_underscore = true;
+        }
+    }
+
+    if result.is_empty() {
+        result.push_str("unnamed");
+    }
+
+    if result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, '_');
+    }
+
+    result
+}
+
+/// Sanitize a name into a valid Ghidra label (no whitespace or quotes).
+fn to_ghidra_identifier(s: &str) -> String {
+    let result: String = s
+        .chars()
+        .map(|c| if c.is_whitespace() || c == '"' { '_' } else { c })
+        .collect();
+
+    if result.is_empty() {
+        "unnamed".to_string()
+    } else {
+        result
+    }
+}
+
+/// Sanitize every symbol name and suffix duplicates with `_1`, `_2`, ...
+fn unique_names(symbols: &[ExportableSymbol], sanitize: fn(&str) -> String) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut names = Vec::with_capacity(symbols.len());
+
+    for sym in symbols {
+        let base = sanitize(&sym.name);
+        let mut name = base.clone();
+        while let Some(count) = seen.get_mut(&name) {
+            *count += 1;
+            name = format!("{}_{}", base, count);
+        }
+        seen.insert(name.clone(), 0);
+        names.push(name);
+    }
+
+    names
+}
+
 /// Import symbols from various formats
 pub struct SymbolImporter;
 
@@ -661,6 +800,39 @@ mod tests {
         assert_eq!(to_c_identifier("123start"), "_123start");
     }
 
+    #[test]
+    fn test_ida_names_sanitized_and_deduplicated() {
+        let mut exporter = SymbolExporter::new();
+        exporter.add_symbol(ExportableSymbol::function("Instance::GetChildren", 0x1000));
+        exporter.add_symbol(ExportableSymbol::function("Instance::GetChildren", 0x2000));
+        exporter.add_symbol(ExportableSymbol::function("1bad name", 0x3000));
+
+        let script = exporter.export(ExportFormat::Ida);
+        assert!(script.contains("idc.set_name(0x1000, \"Instance_GetChildren\", idc.SN_NOWARN)"));
+        assert!(script.contains("idc.set_name(0x2000, \"Instance_GetChildren_1\", idc.SN_NOWARN)"));
+        assert!(script.contains("idc.set_name(0x3000, \"_1bad_name\", idc.SN_NOWARN)"));
+    }
+
+    #[test]
+    fn test_ghidra_export_rebases_on_image_base() {
+        let mut exporter = SymbolExporter::new().with_image_base(0x100000000);
+        exporter.add_symbol(ExportableSymbol::function("luau_load", 0x100001000));
+        exporter.add_symbol(ExportableSymbol::data("g_state", 0x100002000));
+
+        let script = exporter.export(ExportFormat::Ghidra);
+        assert!(script.contains("IMAGE_BASE = 0x100000000"));
+        assert!(script.contains("createFunction(toAddr(0x100001000 + delta), \"luau_load\")"));
+        assert!(script.contains("createLabel(toAddr(0x100002000 + delta), \"g_state\", True)"));
+
+        let list = exporter.export(ExportFormat::GhidraSymbols);
+        assert_eq!(list, "luau_load 100001000 f\ng_state 100002000 l\n");
+
+        let csv = exporter.export(ExportFormat::GhidraCsv);
+        assert_eq!(csv, "name,address,type,image_base\n\
+            luau_load,0x100001000,function,0x100000000\n\
+            g_state,0x100002000,label,0x100000000\n");
+    }
+
     #[test]
     fn test_export_csv() {
         let mut exporter = SymbolExporter::new();
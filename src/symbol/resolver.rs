@@ -1,137 +1,204 @@
 // Tue Jan 13 2026 - Alex
 
 use crate::memory::{Address, MemoryReader, MemoryError};
+use crate::symbol::demangle;
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// Mach-O load commands the symbol loader reads
+const LC_SEGMENT_64: u32 = 0x19;
+const LC_SYMTAB: u32 = 0x02;
+const LC_DYSYMTAB: u32 = 0x0B;
+
+const NLIST_64_SIZE: usize = 16;
+const N_STAB: u8 = 0xE0;
+const N_TYPE: u8 = 0x0E;
+const N_SECT: u8 = 0x0E;
 
 pub struct SymbolResolver {
     reader: Arc<dyn MemoryReader>,
     symbols: HashMap<String, Symbol>,
-    address_to_symbol: HashMap<u64, String>,
+    address_to_symbol: BTreeMap<u64, String>,
+    /// Demangled names, with and without the parameter list, to `symbols` keys
+    demangled_to_symbol: HashMap<String, String>,
     loaded: bool,
 }
 
 impl SymbolResolver {
+    /// Reads the image's `LC_SYMTAB` straight away; a reader with no
+    /// symbol table (a stripped binary or live process) just starts empty.
+    /// A symbol table that can't be read is logged and also leaves it
+    /// empty; `load` returns that error instead.
     pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self::load(reader.clone()).unwrap_or_else(|e| {
+            log::warn!("Ignoring unreadable symbol table: {}", e);
+            Self::empty(reader)
+        })
+    }
+
+    /// Like `new`, but fails when the symbol table is there and can't be read.
+    pub fn load(reader: Arc<dyn MemoryReader>) -> Result<Self, MemoryError> {
+        let mut resolver = Self::empty(reader);
+        resolver.load_mach_o_symbols()?;
+        Ok(resolver)
+    }
+
+    /// A resolver that hasn't read any symbols
+    pub fn empty(reader: Arc<dyn MemoryReader>) -> Self {
         Self {
             reader,
             symbols: HashMap::new(),
-            address_to_symbol: HashMap::new(),
+            address_to_symbol: BTreeMap::new(),
+            demangled_to_symbol: HashMap::new(),
             loaded: false,
         }
     }
 
+    /// The symbol table, or when there is none, `sub_` names for every
+    /// function prologue in executable memory.
     pub fn load_symbols(&mut self) -> Result<usize, MemoryError> {
-        if self.loaded {
-            return Ok(self.symbols.len());
+        if !self.loaded && self.symbols.is_empty() {
+            self.scan_for_functions()?;
         }
 
-        self.load_mach_o_symbols()?;
-
         self.loaded = true;
         Ok(self.symbols.len())
     }
 
+    /// Defined symbols from LC_SYMTAB, narrowed to the local and external
+    /// ranges LC_DYSYMTAB gives when present. Functions and data are told
+    /// apart by whether the symbol lands in executable memory, and each one
+    /// is sized up to the next symbol.
     fn load_mach_o_symbols(&mut self) -> Result<(), MemoryError> {
-        // Try to parse the Mach-O header from the base address
         let base = self.reader.get_base_address();
-        
-        // Read Mach-O magic number to verify it's a valid binary
-        let magic = self.reader.read_u32(base)?;
-        
-        // Check for valid Mach-O magic (64-bit little-endian)
-        if magic != 0xFEEDFACF && magic != 0xFEEDFACE {
-            // Not a Mach-O or can't parse from memory reader directly
-            // Try to get regions and scan for function prologues
-            return self.scan_for_functions();
+
+        // Only 64-bit images; their nlist entries are what is parsed below
+        if self.reader.read_u32(base)? != 0xFEEDFACF {
+            return Ok(());
         }
-        
-        // Parse Mach-O header
         let ncmds = self.reader.read_u32(base + 0x10)?;
-        let sizeofcmds = self.reader.read_u32(base + 0x14)?;
-        
-        // Header size for 64-bit Mach-O is 32 bytes
-        let header_size: u64 = if magic == 0xFEEDFACF { 32 } else { 28 };
-        let mut cmd_offset = base + header_size;
-        
-        let mut symtab_offset = 0u64;
-        let mut symtab_size = 0u32;
-        let mut strtab_offset = 0u64;
-        let mut strtab_size = 0u32;
-        
-        // Parse load commands to find LC_SYMTAB
+
+        let mut segments = Vec::new();
+        let mut symtab = None;
+        let mut defined = Vec::new();
+
+        let mut cmd_offset = base + 32;
         for _ in 0..ncmds {
             let cmd = self.reader.read_u32(cmd_offset)?;
             let cmdsize = self.reader.read_u32(cmd_offset + 4)?;
-            
-            // LC_SYMTAB = 0x02
-            if cmd == 0x02 {
-                symtab_offset = self.reader.read_u32(cmd_offset + 8)? as u64;
-                symtab_size = self.reader.read_u32(cmd_offset + 12)?;
-                strtab_offset = self.reader.read_u32(cmd_offset + 16)? as u64;
-                strtab_size = self.reader.read_u32(cmd_offset + 20)?;
+
+            match cmd {
+                LC_SEGMENT_64 => {
+                    let vmaddr = self.reader.read_u64(cmd_offset + 24)?;
+                    let fileoff = self.reader.read_u64(cmd_offset + 40)?;
+                    let filesize = self.reader.read_u64(cmd_offset + 48)?;
+                    segments.push((vmaddr, fileoff, filesize));
+                }
+                LC_SYMTAB => {
+                    symtab = Some((
+                        self.reader.read_u32(cmd_offset + 8)? as u64,
+                        self.reader.read_u32(cmd_offset + 12)? as usize,
+                        self.reader.read_u32(cmd_offset + 16)? as u64,
+                        self.reader.read_u32(cmd_offset + 20)? as usize,
+                    ));
+                }
+                LC_DYSYMTAB => {
+                    // ilocalsym, nlocalsym, iextdefsym, nextdefsym
+                    for field in [8u64, 16] {
+                        let first = self.reader.read_u32(cmd_offset + field)? as usize;
+                        let count = self.reader.read_u32(cmd_offset + field + 4)? as usize;
+                        defined.push(first..first + count);
+                    }
+                }
+                _ => {}
+            }
+
+            if cmdsize == 0 {
                 break;
             }
-            
             cmd_offset = cmd_offset + cmdsize as u64;
         }
-        
-        if symtab_offset == 0 || strtab_offset == 0 {
-            return self.scan_for_functions();
-        }
-        
-        // Try to read string table (this might fail for process memory)
-        let string_table = match self.reader.read_bytes(base + strtab_offset, strtab_size as usize) {
-            Ok(data) => data,
-            Err(_) => return self.scan_for_functions(),
+
+        let Some((symoff, nsyms, stroff, strsize)) = symtab else {
+            return Ok(());
         };
-        
-        // Each nlist_64 entry is 16 bytes
-        let entry_size = 16usize;
-        let num_symbols = symtab_size as usize;
-        
-        for i in 0..num_symbols {
-            let entry_addr = base + symtab_offset + (i * entry_size) as u64;
-            
-            // Read nlist_64 structure
-            let n_strx = match self.reader.read_u32(entry_addr) {
-                Ok(v) => v as usize,
-                Err(_) => continue,
-            };
-            let n_type = match self.reader.read_u8(entry_addr + 4) {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
-            let n_value = match self.reader.read_u64(entry_addr + 8) {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
-            
-            // Skip undefined symbols
-            if n_value == 0 {
+
+        // The image may have slid from the address its segments ask for
+        let text_vmaddr = segments.iter()
+            .find(|&&(_, fileoff, filesize)| fileoff == 0 && filesize > 0)
+            .map(|&(vmaddr, _, _)| vmaddr)
+            .unwrap_or(base.as_u64());
+        let slide = base.as_u64().wrapping_sub(text_vmaddr);
+        let file_to_address = |offset: u64| {
+            segments.iter()
+                .find(|&&(_, fileoff, filesize)| offset >= fileoff && offset < fileoff + filesize)
+                .map(|&(vmaddr, fileoff, _)| Address::new(vmaddr.wrapping_add(slide) + (offset - fileoff)))
+        };
+
+        let (Some(symbols_at), Some(strings_at)) = (file_to_address(symoff), file_to_address(stroff)) else {
+            return Ok(());
+        };
+        let entries = self.reader.read_bytes(symbols_at, nsyms * NLIST_64_SIZE)?;
+        let strings = self.reader.read_bytes(strings_at, strsize)?;
+
+        if defined.is_empty() {
+            defined.push(0..nsyms);
+        }
+
+        let executable: Vec<(Address, Address)> = self.reader.get_regions()?
+            .iter()
+            .filter(|region| region.is_executable())
+            .map(|region| (region.start(), region.end()))
+            .collect();
+
+        let mut loaded = Vec::new();
+        for index in defined.into_iter().flatten().filter(|&i| i < nsyms) {
+            let entry = &entries[index * NLIST_64_SIZE..(index + 1) * NLIST_64_SIZE];
+            let n_strx = u32::from_le_bytes(entry[0..4].try_into().unwrap()) as usize;
+            let n_type = entry[4];
+            let n_value = u64::from_le_bytes(entry[8..16].try_into().unwrap());
+
+            // Debug entries and anything not defined in a section
+            if n_type & N_STAB != 0 || n_type & N_TYPE != N_SECT || n_strx >= strings.len() {
                 continue;
             }
-            
-            // Get symbol name from string table
-            if n_strx < string_table.len() {
-                let name_bytes: Vec<u8> = string_table[n_strx..]
-                    .iter()
-                    .take_while(|&&b| b != 0)
-                    .copied()
-                    .collect();
-                    
-                if let Ok(name) = String::from_utf8(name_bytes) {
-                    if !name.is_empty() {
-                        let symbol_type = SymbolType::from_nlist_type(n_type);
-                        self.add_symbol(name, Address::new(n_value), None, symbol_type);
-                    }
-                }
+
+            let name_bytes: Vec<u8> = strings[n_strx..].iter()
+                .take_while(|&&b| b != 0)
+                .copied()
+                .collect();
+            let Ok(name) = String::from_utf8(name_bytes) else {
+                continue;
+            };
+            if name.is_empty() {
+                continue;
             }
+
+            let address = Address::new(n_value.wrapping_add(slide));
+            let symbol_type = if executable.iter().any(|&(start, end)| address >= start && address < end) {
+                SymbolType::Function
+            } else {
+                SymbolType::Data
+            };
+            loaded.push((name, address, symbol_type));
         }
-        
+
+        loaded.sort_by_key(|(_, address, _)| *address);
+        let sizes: Vec<Option<u64>> = loaded.iter()
+            .enumerate()
+            .map(|(i, (_, address, _))| {
+                loaded[i + 1..].iter()
+                    .find(|(_, next, _)| next > address)
+                    .map(|(_, next, _)| next.as_u64() - address.as_u64())
+            })
+            .collect();
+        for ((name, address, symbol_type), size) in loaded.into_iter().zip(sizes) {
+            self.add_symbol(name, address, size, symbol_type);
+        }
+
         Ok(())
     }
-    
+
     fn scan_for_functions(&mut self) -> Result<(), MemoryError> {
         // Fallback: scan memory regions for ARM64 function prologues
         let regions = self.reader.get_regions()?;
@@ -176,8 +243,21 @@ impl SymbolResolver {
         self.symbols.get(name)
     }
 
-    pub fn resolve(&self, name: &str) -> Option<Address> {
-        self.symbols.get(name).map(|s| s.address)
+    /// The symbol whose extent covers `addr`; unsized symbols only cover
+    /// their own address.
+    pub fn resolve(&self, addr: Address) -> Option<&Symbol> {
+        self.nearest(addr)
+            .map(|(symbol, _)| symbol)
+            .filter(|symbol| symbol.contains(addr))
+    }
+
+    /// By raw name, by C name without the Mach-O leading underscore
+    /// (`lua_gettop` for `_lua_gettop`), or by demangled name with or
+    /// without its parameter list (`RBX::Instance::getName`).
+    pub fn lookup(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.get(name)
+            .or_else(|| self.symbols.get(&format!("_{}", name)))
+            .or_else(|| self.demangled_to_symbol.get(name).and_then(|raw| self.symbols.get(raw)))
     }
 
     pub fn find_by_prefix(&self, prefix: &str) -> Vec<&Symbol> {
@@ -207,9 +287,16 @@ impl SymbolResolver {
             address: addr,
             size,
             symbol_type,
-            demangled_name: None,
+            demangled_name: demangle::demangle(&name),
         };
 
+        if let Some(demangled) = &symbol.demangled_name {
+            if let Some(qualified) = qualified_name(demangled) {
+                self.demangled_to_symbol.entry(qualified.to_string()).or_insert_with(|| name.clone());
+            }
+            self.demangled_to_symbol.entry(demangled.clone()).or_insert_with(|| name.clone());
+        }
+
         self.address_to_symbol.insert(addr.as_u64(), name.clone());
         self.symbols.insert(name, symbol);
     }
@@ -227,24 +314,18 @@ impl SymbolResolver {
     }
 
     pub fn get_nearest_symbol(&self, addr: Address) -> Option<(&Symbol, i64)> {
-        let addr_u64 = addr.as_u64();
-        let mut nearest: Option<(&Symbol, i64)> = None;
-
-        for symbol in self.symbols.values() {
-            let diff = addr_u64 as i64 - symbol.address.as_u64() as i64;
+        self.nearest(addr).map(|(symbol, offset)| (symbol, offset as i64))
+    }
 
-            if diff >= 0 {
-                if let Some((_, current_diff)) = nearest {
-                    if diff < current_diff {
-                        nearest = Some((symbol, diff));
-                    }
-                } else {
-                    nearest = Some((symbol, diff));
-                }
-            }
-        }
+    /// Closest symbol at or below `addr`, and how far past it `addr` lies.
+    pub fn resolve_nearest(&self, addr: Address) -> Option<(Symbol, u64)> {
+        self.nearest(addr).map(|(symbol, offset)| (symbol.clone(), offset))
+    }
 
-        nearest
+    fn nearest(&self, addr: Address) -> Option<(&Symbol, u64)> {
+        let (start, name) = self.address_to_symbol.range(..=addr.as_u64()).next_back()?;
+        let symbol = self.symbols.get(name)?;
+        Some((symbol, addr.as_u64() - start))
     }
 
     pub fn format_address(&self, addr: Address) -> String {
@@ -351,52 +432,61 @@ impl SymbolType {
 }
 
 pub fn demangle_symbol(mangled: &str) -> Option<String> {
-    if mangled.starts_with("_Z") {
-        demangle_itanium(mangled)
-    } else if mangled.starts_with("__Z") {
-        demangle_itanium(&mangled[1..])
-    } else {
-        None
-    }
+    demangle::demangle(mangled)
 }
 
-fn demangle_itanium(mangled: &str) -> Option<String> {
-    let mangled = if mangled.starts_with("_Z") {
-        &mangled[2..]
-    } else {
-        return None;
-    };
-
-    let mut result = String::new();
-    let mut chars = mangled.chars().peekable();
-
-    while let Some(c) = chars.next() {
-        if c.is_ascii_digit() {
-            let mut len_str = String::new();
-            len_str.push(c);
-
-            while let Some(&next) = chars.peek() {
-                if next.is_ascii_digit() {
-                    len_str.push(chars.next().unwrap());
-                } else {
-                    break;
-                }
-            }
-
-            if let Ok(len) = len_str.parse::<usize>() {
-                let name: String = chars.by_ref().take(len).collect();
-                if !result.is_empty() {
-                    result.push_str("::");
-                }
-                result.push_str(&name);
+/// A demangled function's name without return type or parameters:
+/// `RBX::Instance::getName() const` is `RBX::Instance::getName`
+fn qualified_name(demangled: &str) -> Option<&str> {
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in demangled.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ' ' if depth == 0 => start = i + 1,
+            '(' if depth == 0 => {
+                return Some(&demangled[start..i]).filter(|name| !name.is_empty());
             }
+            _ => {}
         }
     }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::MachOFixture;
+
+    #[test]
+    fn test_resolve_nearest_picks_preceding_symbol() {
+        let mut resolver = SymbolResolver::new(MachOFixture::new().reader());
+        resolver.add_symbol("LuauLoad".to_string(), Address::new(0x1004a98), None, SymbolType::Function);
+        resolver.add_symbol("lua_pushcclosure".to_string(), Address::new(0x1005000), None, SymbolType::Function);
+
+        let (symbol, offset) = resolver.resolve_nearest(Address::new(0x1004abc)).unwrap();
+        assert_eq!(symbol.name, "LuauLoad");
+        assert_eq!(offset, 0x24);
+
+        assert_eq!(resolver.format_address(Address::new(0x1005000)), "lua_pushcclosure");
+        assert!(resolver.resolve_nearest(Address::new(0x1000000)).is_none());
+    }
+
+    #[test]
+    fn test_add_symbol_demangles_itanium_names() {
+        let mut resolver = SymbolResolver::new(MachOFixture::new().reader());
+        resolver.add_symbol("__ZN3RBX13ScriptContext6resumeEv".to_string(), Address::new(0x1004a98), None, SymbolType::Function);
+        resolver.add_symbol("_lua_pushcclosure".to_string(), Address::new(0x1005000), None, SymbolType::Function);
+
+        let symbol = resolver.resolve_address(Address::new(0x1004a98)).unwrap();
+        assert_eq!(symbol.name, "__ZN3RBX13ScriptContext6resumeEv");
+        assert_eq!(symbol.display_name(), "RBX::ScriptContext::resume()");
+        assert!(resolver.resolve_name("__ZN3RBX13ScriptContext6resumeEv").is_some());
 
-    if result.is_empty() {
-        None
-    } else {
-        Some(result)
+        let plain = resolver.resolve_address(Address::new(0x1005000)).unwrap();
+        assert_eq!(plain.demangled_name, None);
+        assert_eq!(plain.display_name(), "_lua_pushcclosure");
     }
 }
 
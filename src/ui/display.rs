@@ -343,6 +343,23 @@ impl DisplayRenderer {
         lines.join("\n")
     }
 
+    /// One bar per `(label, count)`, scaled so the largest count fills `width`
+    pub fn render_histogram(&self, buckets: &[(String, usize)], width: usize) -> String {
+        let max = buckets.iter().map(|(_, count)| *count).max().unwrap_or(0);
+        let label_width = buckets.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        let fill_char = if self.use_unicode { "█" } else { "#" };
+
+        buckets.iter()
+            .map(|(label, count)| {
+                let filled = if max > 0 { (*count * width).div_ceil(max) } else { 0 };
+                let bar = fill_char.repeat(filled);
+                let bar = if self.use_color { bar.cyan().to_string() } else { bar };
+                format!("  {:<lw$}  {}{} {}", label, bar, " ".repeat(width - filled), count, lw = label_width)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     pub fn render_progress_bar(&self, current: usize, total: usize, width: usize) -> String {
         let progress = if total > 0 { current as f64 / total as f64 } else { 0.0 };
         let filled = (progress * width as f64) as usize;
@@ -8,6 +8,8 @@ pub use display::ErrorDisplay;
 pub use handler::ErrorHandler;
 pub use reporter::ErrorReporter;
 
+use crate::memory::MemoryError;
+use crate::structure::StructureError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -29,3 +31,200 @@ pub enum UiError {
 }
 
 pub type UiResult<T> = Result<T, UiError>;
+
+/// How a command failed. Each kind exits with its own status so scripts can
+/// tell a missing file from offsets that didn't hold up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    General,
+    /// A file couldn't be read or written, or a process couldn't be attached
+    Io,
+    /// An input file or argument couldn't be parsed
+    Parse,
+    /// Offsets failed checks against a binary, process or baseline
+    Validation,
+    /// `--require` named offsets the scan didn't find
+    MissingOffsets,
+    /// Stopped by Ctrl-C
+    Interrupted,
+}
+
+impl FailureKind {
+    pub const ALL: &'static [FailureKind] = &[
+        FailureKind::General,
+        FailureKind::Io,
+        FailureKind::Parse,
+        FailureKind::Validation,
+        FailureKind::MissingOffsets,
+        FailureKind::Interrupted,
+    ];
+
+    pub fn code(self) -> i32 {
+        match self {
+            FailureKind::General => 1,
+            FailureKind::Io => 2,
+            FailureKind::Parse => 3,
+            FailureKind::Validation => 4,
+            FailureKind::MissingOffsets => 5,
+            FailureKind::Interrupted => 130,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            FailureKind::General => "Any other error",
+            FailureKind::Io => "File not found, unreadable or unwritable; process attach failed",
+            FailureKind::Parse => "Malformed offsets/FFlag file, address or option value",
+            FailureKind::Validation => "validate found bad offsets, scan --diff-against lost some, or --config is out of range",
+            FailureKind::MissingOffsets => "scan --require / --require-confidence not satisfied",
+            FailureKind::Interrupted => "Scan interrupted with Ctrl-C",
+        }
+    }
+}
+
+/// Failures of the CLI commands. Variants wrapping another error keep it as
+/// their `source()`; `kind` picks the exit code.
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("Failed to load binary: {0}")]
+    BinaryLoad(#[source] MemoryError),
+
+    #[error("No executable regions found in {0}")]
+    NoExecutableRegions(&'static str),
+
+    #[error("Failed to serialize {what}: {source}")]
+    Serialize {
+        what: &'static str,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Failed to {action}: {source}")]
+    Io {
+        action: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to {action}: {source}")]
+    Memory {
+        action: String,
+        #[source]
+        source: MemoryError,
+    },
+
+    #[error("Failed to walk {path}: {source}")]
+    Walk {
+        path: String,
+        #[source]
+        source: StructureError,
+    },
+
+    #[error("Failed to parse {what}: {source}")]
+    Parse {
+        what: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("Invalid address '{0}' (expected hex like 0x1000 or decimal)")]
+    InvalidAddress(String),
+
+    #[error("{message}")]
+    Failed {
+        kind: FailureKind,
+        message: String,
+    },
+}
+
+impl CliError {
+    pub fn kind(&self) -> FailureKind {
+        match self {
+            CliError::BinaryLoad(_) | CliError::Io { .. } => FailureKind::Io,
+            CliError::Parse { .. } | CliError::InvalidAddress(_) => FailureKind::Parse,
+            CliError::NoExecutableRegions(_) | CliError::Serialize { .. }
+                | CliError::Memory { .. } | CliError::Walk { .. } => FailureKind::General,
+            CliError::Failed { kind, .. } => *kind,
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        self.kind().code()
+    }
+
+    pub fn serialize(what: &'static str) -> impl FnOnce(serde_json::Error) -> Self {
+        move |source| Self::Serialize { what, source }
+    }
+
+    pub fn io(action: impl Into<String>) -> impl FnOnce(std::io::Error) -> Self {
+        let action = action.into();
+        move |source| Self::Io { action, source }
+    }
+
+    pub fn memory(action: impl Into<String>) -> impl FnOnce(MemoryError) -> Self {
+        let action = action.into();
+        move |source| Self::Memory { action, source }
+    }
+
+    pub fn parse<E>(what: impl Into<String>) -> impl FnOnce(E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let what = what.into();
+        move |source| Self::Parse { what, source: source.into() }
+    }
+
+    pub fn failed(kind: FailureKind, message: impl Into<String>) -> Self {
+        Self::Failed { kind, message: message.into() }
+    }
+
+    /// An input path that doesn't exist
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::failed(FailureKind::Io, message)
+    }
+
+    /// An option value or input that is well-formed but unusable
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::failed(FailureKind::Parse, message)
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::failed(FailureKind::Validation, message)
+    }
+
+    pub fn missing_offsets(message: impl Into<String>) -> Self {
+        Self::failed(FailureKind::MissingOffsets, message)
+    }
+
+    pub fn interrupted(message: impl Into<String>) -> Self {
+        Self::failed(FailureKind::Interrupted, message)
+    }
+
+    pub fn general(message: impl Into<String>) -> Self {
+        Self::failed(FailureKind::General, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_error_messages() {
+        let err = CliError::io("write offsets.json")(std::io::Error::other("disk full"));
+        assert_eq!(err.to_string(), "Failed to write offsets.json: disk full");
+        assert!(std::error::Error::source(&err).is_some());
+        assert_eq!(err.exit_code(), 2);
+
+        let err = CliError::InvalidAddress("0xZZ".to_string());
+        assert_eq!(err.to_string(), "Invalid address '0xZZ' (expected hex like 0x1000 or decimal)");
+        assert_eq!(err.kind(), FailureKind::Parse);
+
+        let err = CliError::parse("offsets.json")(serde_json::from_str::<serde_json::Value>("{").unwrap_err());
+        assert!(err.to_string().starts_with("Failed to parse offsets.json: "));
+        assert!(std::error::Error::source(&err).is_some());
+        assert_eq!(err.exit_code(), 3);
+
+        assert_eq!(CliError::validation("2 of 3 checks failed").exit_code(), 4);
+    }
+}
@@ -13,7 +13,7 @@ pub mod banner;
 pub use cli::{Args, Command, CommandHandler};
 pub use progress::ProgressManager;
 pub use terminal::Terminal;
-pub use errors::{ErrorDisplay, ErrorHandler};
+pub use errors::{CliError, ErrorDisplay, ErrorHandler, FailureKind};
 pub use display::DisplayRenderer;
 pub use theme::Theme;
 pub use spinner::Spinner;
@@ -64,7 +64,7 @@ impl ProgressManager {
 
     pub fn create_bytes_progress(&self, total: u64, message: &str) -> indicatif::ProgressBar {
         let style = ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}) {msg}")
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, ETA {eta}) {msg}")
             .unwrap()
             .progress_chars("█▓▒░ ");
 
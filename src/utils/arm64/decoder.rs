@@ -200,9 +200,9 @@ impl InstructionDecoder {
         let op0 = (insn >> 29) & 0x7;
 
         match op0 {
-            0b000 | 0b100 => Self::decode_conditional_branch(insn),
-            0b001 | 0b101 => Self::decode_compare_branch(insn),
-            0b010 | 0b110 => Self::decode_test_branch(insn),
+            0b010 if (insn & 0xFF000010) == 0x54000000 => Self::decode_conditional_branch(insn),
+            0b001 | 0b101 if (insn >> 25) & 1 == 0 => Self::decode_compare_branch(insn),
+            0b001 | 0b101 => Self::decode_test_branch(insn),
             _ => Self::decode_unconditional_branch(insn),
         }
     }
@@ -260,7 +260,8 @@ impl InstructionDecoder {
         Some(InstructionInfo {
             mnemonic: mnemonic.to_string(),
             operands: vec![
-                Operand::Register(Register::new_gpr(rt, true)),
+                // Bit numbers above 31 can only be tested in the X view
+                Operand::Register(Register::new_gpr(rt, bit >= 32)),
                 Operand::Immediate(bit as i64),
                 Operand::Immediate(offset),
             ],
@@ -158,14 +158,19 @@ impl InstructionInfo {
         None
     }
 
+    /// The offset is always the last operand; TBZ/TBNZ carry the bit
+    /// number as an immediate before it.
     pub fn get_branch_target(&self, current_address: u64) -> Option<u64> {
         if !self.is_branch() {
             return None;
         }
 
-        self.get_immediate().map(|offset| {
-            ((current_address as i64) + offset) as u64
-        })
+        let offset = self.operands.iter().rev().find_map(|op| match op {
+            Operand::Immediate(imm) => Some(*imm),
+            _ => None,
+        })?;
+
+        Some(((current_address as i64) + offset) as u64)
     }
 
     pub fn disassemble(&self) -> String {
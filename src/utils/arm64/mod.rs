@@ -62,11 +62,35 @@ impl Arm64Utils {
         }
     }
 
+    /// Target of B.cond, CBZ/CBNZ (imm19) or TBZ/TBNZ (imm14).
     pub fn get_conditional_branch_target(insn: u32, address: u64) -> Option<u64> {
-        if (insn >> 25) & 0x7F == 0b0101010 {
+        let offset = if (insn >> 25) & 0x7F == 0b0101010 || Self::is_compare_branch(insn) {
             let imm19 = ((insn >> 5) & 0x7FFFF) as i32;
-            let offset = ((imm19 << 13) >> 11) as i64;
-            Some((address as i64 + offset) as u64)
+            ((imm19 << 13) >> 11) as i64
+        } else if Self::is_test_branch(insn) {
+            let imm14 = ((insn >> 5) & 0x3FFF) as i32;
+            ((imm14 << 18) >> 16) as i64
+        } else {
+            return None;
+        };
+
+        Some((address as i64 + offset) as u64)
+    }
+
+    /// CBZ/CBNZ: sf 011010 op imm19 Rt
+    pub fn is_compare_branch(insn: u32) -> bool {
+        (insn & 0x7E000000) == 0x34000000
+    }
+
+    /// TBZ/TBNZ: b5 011011 op b40 imm14 Rt
+    pub fn is_test_branch(insn: u32) -> bool {
+        (insn & 0x7E000000) == 0x36000000
+    }
+
+    /// Bit number tested by TBZ/TBNZ.
+    pub fn get_test_bit(insn: u32) -> Option<u32> {
+        if Self::is_test_branch(insn) {
+            Some(((insn >> 19) & 0x1F) | ((insn >> 26) & 0x20))
         } else {
             None
         }
@@ -74,16 +98,42 @@ impl Arm64Utils {
 
     pub fn get_adrp_value(insn: u32, address: u64) -> Option<u64> {
         if (insn & 0x9F000000) == 0x90000000 {
-            let immlo = ((insn >> 29) & 0x3) as i64;
-            let immhi = ((insn >> 5) & 0x7FFFF) as i64;
-            let imm = ((immhi << 2) | immlo) << 12;
             let page_addr = address & !0xFFF;
-            Some(((page_addr as i64) + imm) as u64)
+            Some(((page_addr as i64) + (Self::adr_immediate(insn) << 12)) as u64)
         } else {
             None
         }
     }
 
+    /// Target of ADR (not ADRP).
+    pub fn get_adr_target(insn: u32, address: u64) -> Option<u64> {
+        if (insn & 0x9F000000) == 0x10000000 {
+            Some((address as i64 + Self::adr_immediate(insn)) as u64)
+        } else {
+            None
+        }
+    }
+
+    /// Folds `adrp xN, page` at `address` with a following
+    /// `add xM, xN, #lo12` into the address the pair materializes.
+    pub fn fold_adrp_add(adrp: u32, add: u32, address: u64) -> Option<u64> {
+        let page = Self::get_adrp_value(adrp, address)?;
+
+        // 64-bit ADD (immediate) reading the register ADRP wrote
+        if (add & 0xFF800000) != 0x91000000 || (add >> 5) & 0x1F != adrp & 0x1F {
+            return None;
+        }
+
+        Some(page.wrapping_add(Self::get_add_imm(add)?))
+    }
+
+    /// Sign-extended immhi:immlo of ADR/ADRP
+    fn adr_immediate(insn: u32) -> i64 {
+        let immlo = ((insn >> 29) & 0x3) as i64;
+        let immhi = ((insn >> 5) & 0x7FFFF) as i64;
+        (((immhi << 2) | immlo) << 43) >> 43
+    }
+
     pub fn get_add_imm(insn: u32) -> Option<u64> {
         if (insn & 0x7F800000) == 0x11000000 {
             let imm12 = ((insn >> 10) & 0xFFF) as u64;
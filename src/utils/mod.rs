@@ -9,6 +9,8 @@ pub mod process;
 pub mod string;
 pub mod testing;
 pub mod hash;
+pub mod time;
+pub mod profile;
 
 pub use arm64::Arm64Utils;
 pub use hash::{HashComputer, HashResults, RollingHash, RabinKarp, BloomFilter};
@@ -0,0 +1,216 @@
+// Sat Oct 17 2026 - Alex
+
+// Opt-in timing spans for `scan --profile-tree` / `--profile-json`. Spans
+// are only recorded once `enable` has been called; until then `span` is a
+// single relaxed load in front of the closure it wraps.
+
+use super::{format_duration, measure_time};
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static NEXT_THREAD: AtomicU64 = AtomicU64::new(1);
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+static SPANS: Mutex<Vec<Span>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static THREAD: u64 = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+    static STACK: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// One timed call. `path` holds the names of the enclosing spans on the same
+/// thread, ending with this span's own name.
+#[derive(Debug, Clone)]
+pub struct Span {
+    pub path: Vec<String>,
+    pub thread: u64,
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+impl Span {
+    pub fn name(&self) -> &str {
+        self.path.last().map(String::as_str).unwrap_or("")
+    }
+}
+
+pub fn enable() {
+    EPOCH.get_or_init(Instant::now);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f`, recording it as a span named `name` nested under whatever span
+/// is open on this thread.
+pub fn span<F, T>(name: &str, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    if !is_enabled() {
+        return f();
+    }
+
+    let epoch = *EPOCH.get_or_init(Instant::now);
+    let start = epoch.elapsed();
+    STACK.with(|stack| stack.borrow_mut().push(name.to_string()));
+    let (result, duration) = measure_time(f);
+    let path = STACK.with(|stack| {
+        let path = stack.borrow().clone();
+        stack.borrow_mut().pop();
+        path
+    });
+
+    let span = Span { path, thread: THREAD.with(|t| *t), start, duration };
+    if let Ok(mut spans) = SPANS.lock() {
+        spans.push(span);
+    }
+
+    result
+}
+
+/// Drains the spans recorded so far, in completion order
+pub fn take_spans() -> Vec<Span> {
+    SPANS.lock().map(|mut spans| std::mem::take(&mut *spans)).unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TimingNode {
+    pub name: String,
+    pub total: Duration,
+    pub calls: usize,
+    pub children: Vec<TimingNode>,
+}
+
+impl TimingNode {
+    fn child(&mut self, name: &str) -> &mut TimingNode {
+        let index = match self.children.iter().position(|c| c.name == name) {
+            Some(index) => index,
+            None => {
+                self.children.push(TimingNode { name: name.to_string(), ..Default::default() });
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[index]
+    }
+}
+
+/// Spans merged by path: repeated calls (every chunk running the same
+/// finder, say) add up into one node. Children keep first-seen order.
+#[derive(Debug, Clone, Default)]
+pub struct TimingTree {
+    pub roots: Vec<TimingNode>,
+}
+
+impl TimingTree {
+    pub fn from_spans(spans: &[Span]) -> Self {
+        let mut sorted: Vec<&Span> = spans.iter().collect();
+        sorted.sort_by_key(|s| (s.start, s.path.len()));
+
+        let mut top = TimingNode::default();
+        for span in sorted {
+            let mut node = &mut top;
+            for name in &span.path {
+                node = node.child(name);
+            }
+            node.total += span.duration;
+            node.calls += 1;
+        }
+
+        Self { roots: top.children }
+    }
+
+    /// Time percentages are relative to the longest root, normally the whole
+    /// scan. Spans from worker threads show up as their own roots, and their
+    /// summed time can exceed the wall clock.
+    pub fn render(&self) -> String {
+        let total = self.roots.iter().map(|r| r.total).max().unwrap_or_default();
+        let mut out = String::new();
+        for root in &self.roots {
+            render_node(root, 0, total, &mut out);
+        }
+        out
+    }
+}
+
+fn render_node(node: &TimingNode, depth: usize, total: Duration, out: &mut String) {
+    let percent = if total.is_zero() {
+        0.0
+    } else {
+        node.total.as_secs_f64() / total.as_secs_f64() * 100.0
+    };
+    let calls = if node.calls > 1 { format!(" x{}", node.calls) } else { String::new() };
+    let label = format!("{}{}{}", "  ".repeat(depth), node.name, calls);
+
+    out.push_str(&format!("{:<48} {:>10} {:>6.1}%\n", label, format_duration(node.total), percent));
+    for child in &node.children {
+        render_node(child, depth + 1, total, out);
+    }
+}
+
+/// Spans as Chrome trace-event JSON ("X" complete events, microseconds),
+/// loadable in chrome://tracing or Perfetto.
+pub fn chrome_trace(spans: &[Span]) -> Value {
+    let events: Vec<Value> = spans.iter()
+        .map(|span| json!({
+            "name": span.name(),
+            "cat": span.path.first().map(String::as_str).unwrap_or(""),
+            "ph": "X",
+            "ts": span.start.as_micros() as u64,
+            "dur": span.duration.as_micros() as u64,
+            "pid": 1,
+            "tid": span.thread,
+        }))
+        .collect();
+
+    json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(path: &[&str], start_ms: u64, ms: u64) -> Span {
+        Span {
+            path: path.iter().map(|s| s.to_string()).collect(),
+            thread: 1,
+            start: Duration::from_millis(start_ms),
+            duration: Duration::from_millis(ms),
+        }
+    }
+
+    #[test]
+    fn test_timing_tree_merges_repeated_paths() {
+        let spans = vec![
+            span(&["scan", "load"], 0, 10),
+            span(&["scan", "find", "lua_gettop"], 10, 20),
+            span(&["scan", "find", "lua_gettop"], 30, 20),
+            span(&["scan", "find"], 10, 50),
+            span(&["scan"], 0, 100),
+        ];
+
+        let tree = TimingTree::from_spans(&spans);
+        assert_eq!(tree.roots.len(), 1);
+
+        let scan = &tree.roots[0];
+        let names: Vec<_> = scan.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["load", "find"]);
+
+        let gettop = &scan.children[1].children[0];
+        assert_eq!(gettop.calls, 2);
+        assert_eq!(gettop.total, Duration::from_millis(40));
+
+        let rendered = tree.render();
+        assert!(rendered.lines().next().unwrap().ends_with("100.0%"));
+        assert!(rendered.contains("    lua_gettop x2"));
+
+        let trace = chrome_trace(&spans);
+        assert_eq!(trace["traceEvents"].as_array().unwrap().len(), 5);
+        assert_eq!(trace["traceEvents"][1]["dur"], 20_000);
+    }
+}
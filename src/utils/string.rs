@@ -1,5 +1,6 @@
 // Tue Jan 13 2026 - Alex
 
+use regex::Regex;
 use std::borrow::Cow;
 
 pub struct StringUtils;
@@ -283,3 +284,26 @@ pub fn escape_string(s: &str) -> String {
 pub fn similarity(a: &str, b: &str) -> f64 {
     StringUtils::similarity(a, b)
 }
+
+/// `pattern` anchored at both ends, so it must match a whole name:
+/// `Timeout` keeps `Timeout` but not `DFIntFooTimeoutMs`
+pub fn full_match_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{})$", pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_match_regex_rejects_substrings() {
+        let re = full_match_regex("Timeout|DFInt.*Ms").unwrap();
+        assert!(re.is_match("Timeout"));
+        assert!(re.is_match("DFIntFooTimeoutMs"));
+        assert!(!re.is_match("FFlagTimeoutEnabled"));
+
+        let re = full_match_regex("Timeout").unwrap();
+        assert!(!re.is_match("DFIntFooTimeoutMs"));
+        assert!(full_match_regex("(").is_err());
+    }
+}
@@ -1,7 +1,11 @@
 // Tue Jan 13 2026 - Alex
 
+use crate::memory::{Address, BinaryMemory, MemoryReader};
+use crate::utils::arm64::InstructionEncoder;
 use std::time::{Duration, Instant};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 pub struct TestRunner {
     tests: Vec<Test>,
@@ -283,3 +287,351 @@ pub fn assert_in_range<T: PartialOrd + std::fmt::Debug>(value: T, min: T, max: T
 pub fn create_test_runner() -> TestRunner {
     TestRunner::new()
 }
+
+/// Builds a minimal arm64 Mach-O image in memory for deterministic finder tests.
+///
+/// The image has a single `__TEXT` segment at the usual 0x100000000 base with a
+/// `__text` section for code and a `__cstring` section for string constants.
+/// Everything is padded well past both sections so the finders' 4 KiB window
+/// reads stay in bounds. Anything passed to `add_data` goes in a read-write
+/// `__DATA` segment right after `__TEXT`; it is only emitted when non-empty.
+/// Symbols passed to `add_symbol` go in an `LC_SYMTAB`/`LC_DYSYMTAB` pair
+/// backed by a `__LINKEDIT` segment, whose address deliberately doesn't
+/// follow its file offset. `build_elf` lays the code and strings out as an
+/// arm64 ELF shared object with `.text` and `.rodata` at the same addresses
+/// (data and symbols are Mach-O only).
+pub struct MachOFixture {
+    code: Vec<u8>,
+    cstrings: Vec<u8>,
+    data: Vec<u8>,
+    symbols: Vec<(String, Address)>,
+}
+
+impl MachOFixture {
+    pub const BASE_ADDRESS: u64 = 0x100000000;
+    pub const TEXT_ADDRESS: u64 = Self::BASE_ADDRESS + Self::TEXT_OFFSET;
+    pub const CSTRING_ADDRESS: u64 = Self::BASE_ADDRESS + Self::CSTRING_OFFSET;
+    pub const DATA_ADDRESS: u64 = Self::BASE_ADDRESS + Self::IMAGE_SIZE;
+    pub const LINKEDIT_ADDRESS: u64 = Self::DATA_ADDRESS + Self::DATA_SIZE;
+
+    const TEXT_OFFSET: u64 = 0x1000;
+    const CSTRING_OFFSET: u64 = 0x3000;
+    const IMAGE_SIZE: u64 = 0x8000;
+    const DATA_SIZE: u64 = 0x4000;
+
+    pub fn new() -> Self {
+        Self {
+            code: Vec::new(),
+            cstrings: Vec::new(),
+            data: Vec::new(),
+            symbols: Vec::new(),
+        }
+    }
+
+    /// Export `name` (raw, e.g. `_lua_gettop`) at `addr` in the symbol table
+    pub fn add_symbol(&mut self, name: &str, addr: Address) {
+        self.symbols.push((name.to_string(), addr));
+    }
+
+    /// Append a NUL-terminated string and return its address
+    pub fn add_cstring(&mut self, s: &str) -> Address {
+        let addr = Address::new(Self::CSTRING_ADDRESS + self.cstrings.len() as u64);
+        self.cstrings.extend_from_slice(s.as_bytes());
+        self.cstrings.push(0);
+        assert!(self.cstrings.len() as u64 <= Self::IMAGE_SIZE - Self::CSTRING_OFFSET - 0x1000, "__cstring overflow");
+        addr
+    }
+
+    /// Address the next instruction passed to `add_code` will land at
+    pub fn next_code_address(&self) -> Address {
+        Address::new(Self::TEXT_ADDRESS + self.code.len() as u64)
+    }
+
+    /// Append instructions and return the address of the first one
+    pub fn add_code(&mut self, insns: &[u32]) -> Address {
+        let addr = self.next_code_address();
+        for insn in insns {
+            self.code.extend_from_slice(&insn.to_le_bytes());
+        }
+        assert!(self.code.len() as u64 <= Self::CSTRING_OFFSET - Self::TEXT_OFFSET, "__text overflow");
+        addr
+    }
+
+    /// Address the next bytes passed to `add_data` will land at
+    pub fn next_data_address(&self) -> Address {
+        Address::new(Self::DATA_ADDRESS + self.data.len() as u64)
+    }
+
+    /// Append 8-byte aligned bytes to `__DATA` and return their address
+    pub fn add_data(&mut self, bytes: &[u8]) -> Address {
+        let addr = self.next_data_address();
+        self.data.extend_from_slice(bytes);
+        self.data.resize(self.data.len().next_multiple_of(8), 0);
+        assert!(self.data.len() as u64 <= Self::DATA_SIZE, "__DATA overflow");
+        addr
+    }
+
+    /// Append little-endian qwords to `__DATA` and return the first one's address
+    pub fn add_qwords(&mut self, qwords: &[u64]) -> Address {
+        let bytes: Vec<u8> = qwords.iter().flat_map(|q| q.to_le_bytes()).collect();
+        self.add_data(&bytes)
+    }
+
+    /// `adrp rd, target; add rd, rd, :lo12:target` as emitted at `at`
+    pub fn adrp_add(rd: u8, at: Address, target: Address) -> [u32; 2] {
+        let page_delta = (target.as_u64() & !0xFFF) as i64 - (at.as_u64() & !0xFFF) as i64;
+        [
+            InstructionEncoder::encode_adrp(rd, page_delta),
+            InstructionEncoder::encode_add_imm(rd, rd, (target.as_u64() & 0xFFF) as u16, true),
+        ]
+    }
+
+    /// `adrp xN, target@PAGE; ldr xT, [xN, target@PAGEOFF]`
+    pub fn adrp_ldr(rd: u8, rt: u8, at: Address, target: Address) -> [u32; 2] {
+        let page_delta = (target.as_u64() & !0xFFF) as i64 - (at.as_u64() & !0xFFF) as i64;
+        [
+            InstructionEncoder::encode_adrp(rd, page_delta),
+            InstructionEncoder::encode_ldr_imm_unsigned(rt, rd, (target.as_u64() & 0xFFF) as u16, 8),
+        ]
+    }
+
+    /// Scan range covering both code and strings
+    pub fn scan_range(&self) -> (Address, Address) {
+        (
+            Address::new(Self::BASE_ADDRESS),
+            Address::new(Self::CSTRING_ADDRESS + self.cstrings.len() as u64),
+        )
+    }
+
+    /// Serialize to raw Mach-O bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        const MH_MAGIC_64: u32 = 0xFEEDFACF;
+        const CPU_TYPE_ARM64: u32 = 0x0100000C;
+        const MH_EXECUTE: u32 = 0x2;
+        const LC_SEGMENT_64: u32 = 0x19;
+        const SEGMENT_SIZE: u32 = 72;
+        const SECTION_SIZE: u32 = 80;
+        const VM_PROT_READ_EXECUTE: u32 = 0x5;
+        const VM_PROT_READ_WRITE: u32 = 0x3;
+        const S_ATTR_PURE_INSTRUCTIONS: u32 = 0x80000400;
+        const S_CSTRING_LITERALS: u32 = 0x2;
+        const LC_SYMTAB: u32 = 0x2;
+        const LC_DYSYMTAB: u32 = 0xB;
+        const SYMTAB_SIZE: u32 = 24;
+        const DYSYMTAB_SIZE: u32 = 80;
+        const VM_PROT_READ: u32 = 0x1;
+        const N_SECT_EXT: u8 = 0x0F;
+
+        let mut out = Vec::with_capacity(Self::IMAGE_SIZE as usize);
+        let cmdsize = SEGMENT_SIZE + 2 * SECTION_SIZE;
+        let (mut ncmds, mut sizeofcmds) = if self.data.is_empty() {
+            (1, cmdsize)
+        } else {
+            (2, cmdsize + SEGMENT_SIZE)
+        };
+        if !self.symbols.is_empty() {
+            ncmds += 3;
+            sizeofcmds += SEGMENT_SIZE + SYMTAB_SIZE + DYSYMTAB_SIZE;
+        }
+
+        for field in [MH_MAGIC_64, CPU_TYPE_ARM64, 0, MH_EXECUTE, ncmds, sizeofcmds, 0, 0] {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+
+        out.extend_from_slice(&LC_SEGMENT_64.to_le_bytes());
+        out.extend_from_slice(&cmdsize.to_le_bytes());
+        out.extend_from_slice(&fixed_name("__TEXT"));
+        for field in [Self::BASE_ADDRESS, Self::IMAGE_SIZE, 0, Self::IMAGE_SIZE] {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+        for field in [VM_PROT_READ_EXECUTE, VM_PROT_READ_EXECUTE, 2, 0] {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+
+        let sections = [
+            ("__text", Self::TEXT_OFFSET, self.code.len() as u64, 2, S_ATTR_PURE_INSTRUCTIONS),
+            ("__cstring", Self::CSTRING_OFFSET, self.cstrings.len() as u64, 0, S_CSTRING_LITERALS),
+        ];
+        for (name, offset, size, align, flags) in sections {
+            out.extend_from_slice(&fixed_name(name));
+            out.extend_from_slice(&fixed_name("__TEXT"));
+            out.extend_from_slice(&(Self::BASE_ADDRESS + offset).to_le_bytes());
+            out.extend_from_slice(&size.to_le_bytes());
+            for field in [offset as u32, align, 0, 0, flags, 0, 0, 0] {
+                out.extend_from_slice(&field.to_le_bytes());
+            }
+        }
+
+        if !self.data.is_empty() {
+            out.extend_from_slice(&LC_SEGMENT_64.to_le_bytes());
+            out.extend_from_slice(&SEGMENT_SIZE.to_le_bytes());
+            out.extend_from_slice(&fixed_name("__DATA"));
+            for field in [Self::DATA_ADDRESS, Self::DATA_SIZE, Self::IMAGE_SIZE, Self::DATA_SIZE] {
+                out.extend_from_slice(&field.to_le_bytes());
+            }
+            for field in [VM_PROT_READ_WRITE, VM_PROT_READ_WRITE, 0, 0] {
+                out.extend_from_slice(&field.to_le_bytes());
+            }
+        }
+
+        // Symbol and string tables, laid out after everything else
+        let linkedit_offset = if self.data.is_empty() {
+            Self::IMAGE_SIZE
+        } else {
+            Self::IMAGE_SIZE + Self::DATA_SIZE
+        };
+        let mut strings = vec![b' ', 0];
+        let mut nlists = Vec::new();
+        for (name, addr) in &self.symbols {
+            nlists.extend_from_slice(&(strings.len() as u32).to_le_bytes());
+            let sect = if *addr < Address::new(Self::CSTRING_ADDRESS) { 1 } else { 2 };
+            nlists.extend_from_slice(&[N_SECT_EXT, sect, 0, 0]);
+            nlists.extend_from_slice(&addr.as_u64().to_le_bytes());
+            strings.extend_from_slice(name.as_bytes());
+            strings.push(0);
+        }
+        let stroff = linkedit_offset + nlists.len() as u64;
+        let linkedit_size = (nlists.len() + strings.len()) as u64;
+
+        if !self.symbols.is_empty() {
+            out.extend_from_slice(&LC_SEGMENT_64.to_le_bytes());
+            out.extend_from_slice(&SEGMENT_SIZE.to_le_bytes());
+            out.extend_from_slice(&fixed_name("__LINKEDIT"));
+            for field in [Self::LINKEDIT_ADDRESS, linkedit_size, linkedit_offset, linkedit_size] {
+                out.extend_from_slice(&field.to_le_bytes());
+            }
+            for field in [VM_PROT_READ, VM_PROT_READ, 0, 0] {
+                out.extend_from_slice(&field.to_le_bytes());
+            }
+
+            let nsyms = self.symbols.len() as u32;
+            for field in [LC_SYMTAB, SYMTAB_SIZE, linkedit_offset as u32, nsyms, stroff as u32, strings.len() as u32] {
+                out.extend_from_slice(&field.to_le_bytes());
+            }
+
+            // No locals, every symbol an external definition, no imports
+            let mut dysymtab = [0u32; 20];
+            dysymtab[0] = LC_DYSYMTAB;
+            dysymtab[1] = DYSYMTAB_SIZE;
+            dysymtab[5] = nsyms;
+            dysymtab[6] = nsyms;
+            for field in dysymtab {
+                out.extend_from_slice(&field.to_le_bytes());
+            }
+        }
+
+        out.resize(Self::IMAGE_SIZE as usize, 0);
+        let text = Self::TEXT_OFFSET as usize;
+        out[text..text + self.code.len()].copy_from_slice(&self.code);
+        let cstring = Self::CSTRING_OFFSET as usize;
+        out[cstring..cstring + self.cstrings.len()].copy_from_slice(&self.cstrings);
+
+        if !self.data.is_empty() {
+            out.extend_from_slice(&self.data);
+            out.resize((Self::IMAGE_SIZE + Self::DATA_SIZE) as usize, 0);
+        }
+
+        if !self.symbols.is_empty() {
+            out.extend_from_slice(&nlists);
+            out.extend_from_slice(&strings);
+        }
+
+        out
+    }
+
+    pub fn build(&self) -> BinaryMemory {
+        BinaryMemory::from_bytes(self.to_bytes(), PathBuf::from("<fixture>"))
+            .expect("synthetic Mach-O should parse")
+    }
+
+    /// Serialize to a raw ELF64 image: one R+X PT_LOAD covering the whole
+    /// file at `BASE_ADDRESS`, with section headers in the trailing padding.
+    pub fn to_elf_bytes(&self) -> Vec<u8> {
+        const ET_DYN: u16 = 3;
+        const EM_AARCH64: u16 = 183;
+        const PT_LOAD: u32 = 1;
+        const PF_R_X: u32 = 0x5;
+        const SHT_PROGBITS: u32 = 1;
+        const SHT_STRTAB: u32 = 3;
+        const SHF_ALLOC_EXEC: u64 = 0x6;
+        const SHF_ALLOC: u64 = 0x2;
+        const SHDR_OFFSET: u64 = 0x7000;
+        const SHSTRTAB: &[u8] = b"\0.text\0.rodata\0.shstrtab\0";
+
+        let mut out = Vec::with_capacity(Self::IMAGE_SIZE as usize);
+
+        out.extend_from_slice(b"\x7fELF");
+        out.extend_from_slice(&[2, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        out.extend_from_slice(&ET_DYN.to_le_bytes());
+        out.extend_from_slice(&EM_AARCH64.to_le_bytes());
+        out.extend_from_slice(&1u32.to_le_bytes());
+        for field in [0u64, 64, SHDR_OFFSET] {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+        out.extend_from_slice(&0u32.to_le_bytes());
+        for field in [64u16, 56, 1, 64, 4, 3] {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+
+        out.extend_from_slice(&PT_LOAD.to_le_bytes());
+        out.extend_from_slice(&PF_R_X.to_le_bytes());
+        for field in [0, Self::BASE_ADDRESS, Self::BASE_ADDRESS, Self::IMAGE_SIZE, Self::IMAGE_SIZE, 0x1000] {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+
+        out.resize(Self::IMAGE_SIZE as usize, 0);
+        let text = Self::TEXT_OFFSET as usize;
+        out[text..text + self.code.len()].copy_from_slice(&self.code);
+        let cstring = Self::CSTRING_OFFSET as usize;
+        out[cstring..cstring + self.cstrings.len()].copy_from_slice(&self.cstrings);
+
+        let shstrtab_offset = SHDR_OFFSET + 4 * 64;
+        let sections = [
+            (0, 0, 0, 0, 0, 0, 0),
+            (1, SHT_PROGBITS, SHF_ALLOC_EXEC, Self::TEXT_ADDRESS, Self::TEXT_OFFSET, self.code.len() as u64, 4),
+            (7, SHT_PROGBITS, SHF_ALLOC, Self::CSTRING_ADDRESS, Self::CSTRING_OFFSET, self.cstrings.len() as u64, 1),
+            (15, SHT_STRTAB, 0, 0, shstrtab_offset, SHSTRTAB.len() as u64, 1),
+        ];
+
+        let mut headers = Vec::new();
+        for (name, kind, flags, addr, offset, size, align) in sections {
+            headers.extend_from_slice(&(name as u32).to_le_bytes());
+            headers.extend_from_slice(&kind.to_le_bytes());
+            for field in [flags, addr, offset, size] {
+                headers.extend_from_slice(&field.to_le_bytes());
+            }
+            headers.extend_from_slice(&[0u8; 8]);
+            for field in [align, 0u64] {
+                headers.extend_from_slice(&field.to_le_bytes());
+            }
+        }
+
+        let shdr = SHDR_OFFSET as usize;
+        out[shdr..shdr + headers.len()].copy_from_slice(&headers);
+        let shstrtab = shstrtab_offset as usize;
+        out[shstrtab..shstrtab + SHSTRTAB.len()].copy_from_slice(SHSTRTAB);
+
+        out
+    }
+
+    pub fn build_elf(&self) -> BinaryMemory {
+        BinaryMemory::from_bytes(self.to_elf_bytes(), PathBuf::from("<fixture.so>"))
+            .expect("synthetic ELF should parse")
+    }
+
+    pub fn reader(&self) -> Arc<dyn MemoryReader> {
+        Arc::new(self.build())
+    }
+}
+
+impl Default for MachOFixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn fixed_name(name: &str) -> [u8; 16] {
+    let mut buf = [0u8; 16];
+    buf[..name.len()].copy_from_slice(name.as_bytes());
+    buf
+}
@@ -0,0 +1,148 @@
+// Fri Jan 16 2026 - Alex
+
+// UTC timestamps for output files, without pulling in a date crate. Dates
+// are proleptic Gregorian, converted with the days-from-civil algorithm.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECS_PER_DAY: u64 = 86_400;
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// The time output files are stamped with: `SOURCE_DATE_EPOCH` when it
+/// holds epoch seconds, so reproducible runs write identical files, and
+/// the current time otherwise
+pub fn generated_secs() -> u64 {
+    std::env::var("SOURCE_DATE_EPOCH").ok()
+        .and_then(|epoch| epoch.trim().parse().ok())
+        .unwrap_or_else(now_secs)
+}
+
+/// Seconds since the epoch as `YYYY-MM-DDTHH:MM:SSZ`
+pub fn format_timestamp(secs: u64) -> String {
+    let days = (secs / SECS_PER_DAY) as i64;
+    let rem = secs % SECS_PER_DAY;
+    let (year, month, day) = civil_from_days(days);
+
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, rem / 3600, rem / 60 % 60, rem % 60)
+}
+
+/// Accepts `YYYY-MM-DD`, `YYYY-MM-DDTHH:MM[:SS][Z]` (UTC) and, for files
+/// written before timestamps were ISO, bare epoch seconds.
+pub fn parse_timestamp(s: &str) -> Option<u64> {
+    let s = s.trim();
+    if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        return s.parse().ok();
+    }
+
+    let s = s.strip_suffix('Z').unwrap_or(s);
+    let (date, time) = match s.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (s, None),
+    };
+
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: u32 = date_parts.next()?.parse().ok()?;
+    let day: u32 = date_parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let mut secs_of_day = 0;
+    if let Some(time) = time {
+        let parts: Vec<&str> = time.split(':').collect();
+        if parts.len() < 2 || parts.len() > 3 {
+            return None;
+        }
+        let hour: u64 = parts[0].parse().ok()?;
+        let minute: u64 = parts[1].parse().ok()?;
+        let second: u64 = parts.get(2).map_or(Some(0), |s| s.parse().ok())?;
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        secs_of_day = hour * 3600 + minute * 60 + second;
+    }
+
+    let days = days_from_civil(year, month, day);
+    u64::try_from(days).ok().map(|d| d * SECS_PER_DAY + secs_of_day)
+}
+
+/// Coarse length of a span, e.g. `3d 4h`, `2h 5m` or `40s`
+pub fn format_span(secs: u64) -> String {
+    let days = secs / SECS_PER_DAY;
+    let hours = secs % SECS_PER_DAY / 3600;
+    let minutes = secs % 3600 / 60;
+
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+fn is_leap(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamps_round_trip() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp(1_709_210_096), "2024-02-29T12:34:56Z");
+
+        assert_eq!(parse_timestamp("2024-02-29T12:34:56Z"), Some(1_709_210_096));
+        assert_eq!(parse_timestamp("2024-02-29"), Some(1_709_164_800));
+        assert_eq!(parse_timestamp("1709210096"), Some(1_709_210_096));
+        assert_eq!(parse_timestamp("2023-02-29"), None);
+        assert_eq!(parse_timestamp("yesterday"), None);
+
+        assert_eq!(format_span(3 * SECS_PER_DAY + 4 * 3600 + 59), "3d 4h");
+        assert_eq!(format_span(40), "40s");
+    }
+}
@@ -1,6 +1,6 @@
 // Tue Jan 13 2026 - Alex
 
-use crate::finders::result::FinderResults;
+use crate::finders::result::{ConfidenceFactor, FinderResult, FinderResults};
 use std::collections::HashMap;
 
 pub struct ConfidenceScorer {
@@ -162,6 +162,25 @@ impl Default for ConfidenceScorer {
     }
 }
 
+/// The factors the finder recorded for `result`, as `byte pattern +0.70,
+/// prologue and body checks +0.20`
+pub fn explain(result: &FinderResult) -> String {
+    if result.breakdown.is_empty() {
+        return "no breakdown recorded".to_string();
+    }
+
+    format_breakdown(&result.breakdown)
+}
+
+/// Render factors as `string anchor +0.70, 2 other functions load "x" -0.10`
+pub fn format_breakdown(factors: &[ConfidenceFactor]) -> String {
+    factors
+        .iter()
+        .map(|f| format!("{} {:+.2}", f.label, f.delta))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[derive(Debug, Clone)]
 pub struct ConfidenceWeights {
     pub pattern_match: f64,
@@ -255,3 +274,21 @@ impl ConfidenceReport {
         output
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::Address;
+
+    #[test]
+    fn test_explain_reports_only_recorded_factors() {
+        let bare = FinderResult::new("LuauLoad".to_string(), Address::new(0x1000), 0.85)
+            .with_method("string_xref");
+        assert_eq!(explain(&bare), "no breakdown recorded");
+
+        let result = FinderResult::new("f".to_string(), Address::new(0x1000), 0.6)
+            .with_factor("prologue", 0.2)
+            .with_factor("call-count", 0.4);
+        assert_eq!(explain(&result), "prologue +0.20, call-count +0.40");
+    }
+}
@@ -0,0 +1,200 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::memory::{Address, MemoryReader};
+use crate::analysis::heuristics::HeuristicsEngine;
+use crate::finders::result::FinderResults;
+use crate::output::OffsetOutput;
+use crate::validation::report::{IssueSeverity, ValidationIssue, ValidationReport};
+use crate::validation::validator::OffsetValidator;
+use std::sync::Arc;
+
+const MH_MAGIC_64: u32 = 0xFEEDFACF;
+const MH_EXECUTE: u32 = 0x2;
+const ELF_MAGIC: u32 = 0x464C457F;
+
+/// Functions sampled per candidate base when looking for the load base
+const BASE_SAMPLE: usize = 64;
+
+/// Bytes compared against the reference binary at each function
+const COMPARE_LEN: usize = 16;
+
+/// A place the scanned image might be loaded, scored by how many sampled
+/// functions start with a prologue there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadBase {
+    pub address: u64,
+    pub prologues: usize,
+    pub sampled: usize,
+    /// Starts with a main-executable header (Mach-O MH_EXECUTE or ELF)
+    pub executable_header: bool,
+}
+
+/// Checks a scan's offsets against an image loaded at some base, such as a
+/// running process: every address is moved from the scan's origin onto the
+/// load base and run through `OffsetValidator`, then vtables must hold code
+/// pointers. With a reference binary, the bytes at each function must also
+/// match the file, which catches offsets from a different build.
+pub struct ImageValidator {
+    reader: Arc<dyn MemoryReader>,
+    validator: OffsetValidator,
+    heuristics: HeuristicsEngine,
+    reference: Option<(Arc<dyn MemoryReader>, u64)>,
+}
+
+impl ImageValidator {
+    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self {
+            validator: OffsetValidator::new(reader.clone()),
+            heuristics: HeuristicsEngine::new(reader.clone()),
+            reader,
+            reference: None,
+        }
+    }
+
+    /// Compare function bytes with `binary`, whose image starts at `base`
+    pub fn with_reference(mut self, binary: Arc<dyn MemoryReader>, base: u64) -> Self {
+        self.reference = Some((binary, base));
+        self
+    }
+
+    /// Executable regions the image could start at, best first. A region
+    /// with a main-executable header wins ties.
+    pub fn load_bases(&self, output: &OffsetOutput, origin: u64) -> Vec<LoadBase> {
+        let regions = match self.reader.get_regions() {
+            Ok(regions) => regions,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut sample: Vec<u64> = output.functions.values().map(|f| f.address).collect();
+        sample.sort_unstable();
+        sample.truncate(BASE_SAMPLE);
+
+        let mut bases: Vec<LoadBase> = regions.iter()
+            .filter(|r| r.is_executable())
+            .map(|region| {
+                let address = region.start().as_u64();
+                let prologues = sample.iter()
+                    .filter(|&&addr| self.has_prologue(rebase(addr, origin, address)))
+                    .count();
+
+                LoadBase {
+                    address,
+                    prologues,
+                    sampled: sample.len(),
+                    executable_header: self.has_executable_header(address),
+                }
+            })
+            .filter(|base| base.executable_header || base.prologues > 0)
+            .collect();
+
+        bases.sort_by_key(|b| (std::cmp::Reverse(b.prologues), !b.executable_header, b.address));
+        bases
+    }
+
+    /// Where the loader was expected to put the image: the first region
+    /// with a main-executable header, else the first executable region
+    pub fn expected_base(&self) -> Option<u64> {
+        let regions = self.reader.get_regions().ok()?;
+        let executable: Vec<u64> = regions.iter()
+            .filter(|r| r.is_executable())
+            .map(|r| r.start().as_u64())
+            .collect();
+
+        executable.iter()
+            .find(|&&addr| self.has_executable_header(addr))
+            .or(executable.first())
+            .copied()
+    }
+
+    /// Every function and vtable in `output`, moved from `origin` onto
+    /// `load_base`, with one issue per finding: the heuristics engine's
+    /// verdict on each function, and where each vtable's first entry leads.
+    pub fn check(&self, output: &OffsetOutput, origin: u64, load_base: u64) -> ValidationReport {
+        let mut functions: Vec<(&String, u64)> = output.functions.iter()
+            .map(|(name, f)| (name, f.address))
+            .collect();
+        functions.sort();
+
+        let vtables: Vec<(&String, u64)> = output.classes.iter()
+            .filter_map(|class| Some((&class.name, rebase(class.vtable_address?, origin, load_base))))
+            .collect();
+
+        let mut results = FinderResults::new();
+        for &(name, addr) in &functions {
+            results.functions.insert(name.clone(), Address::new(rebase(addr, origin, load_base)));
+        }
+        for &(name, vtable) in &vtables {
+            results.classes.insert(name.clone(), Address::new(vtable));
+        }
+
+        let mut report = self.validator.validate(&results);
+
+        for (name, addr) in functions {
+            if report.worst_severity(name) != Some(IssueSeverity::Error) {
+                self.compare_reference(&mut report, name, addr, origin, load_base);
+            }
+        }
+        for (name, vtable) in vtables {
+            if report.worst_severity(name) != Some(IssueSeverity::Error) {
+                self.check_vtable(&mut report, name, vtable);
+            }
+        }
+
+        report.calculate_overall_score();
+        report
+    }
+
+    fn compare_reference(&self, report: &mut ValidationReport, name: &str, addr: u64, origin: u64, load_base: u64) {
+        let Some((binary, binary_base)) = &self.reference else {
+            return;
+        };
+
+        let live = self.reader.read_bytes(Address::new(rebase(addr, origin, load_base)), COMPARE_LEN);
+        let file = binary.read_bytes(Address::new(rebase(addr, origin, *binary_base)), COMPARE_LEN);
+        if let (Ok(live), Ok(file)) = (live, file) {
+            if live != file {
+                report.add_issue(ValidationIssue::error("function", name, "bytes differ from the binary")
+                    .with_suggestion("The offsets were likely taken from a different build"));
+            }
+        }
+    }
+
+    fn check_vtable(&self, report: &mut ValidationReport, name: &str, address: u64) {
+        let entry = match self.reader.read_u64(Address::new(address)) {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.add_issue(ValidationIssue::error("vtable", name, &format!("unreadable: {}", e)));
+                return;
+            }
+        };
+
+        // Strip a PAC signature from the entry before following it
+        let target = entry & 0x0000_FFFF_FFFF_FFFF;
+        let issue = if target == 0 || target % 4 != 0 {
+            ValidationIssue::warning("vtable", name, &format!("first entry 0x{:x} is not a code pointer", entry))
+        } else if self.reader.read_u32(Address::new(target)).is_err() {
+            ValidationIssue::warning("vtable", name, &format!("first entry 0x{:x} is unmapped", target))
+        } else {
+            ValidationIssue::info("vtable", name, &format!("first entry -> 0x{:x}", target))
+        };
+        report.add_issue(issue);
+    }
+
+    fn has_prologue(&self, address: u64) -> bool {
+        self.heuristics.is_function_entry(Address::new(address)).is_ok_and(|entry| entry.is_match)
+    }
+
+    fn has_executable_header(&self, address: u64) -> bool {
+        let addr = Address::new(address);
+        match self.reader.read_u32(addr) {
+            Ok(MH_MAGIC_64) => self.reader.read_u32(addr + 12).is_ok_and(|filetype| filetype == MH_EXECUTE),
+            Ok(ELF_MAGIC) => true,
+            _ => false,
+        }
+    }
+}
+
+/// `addr` moved from an image at `origin` onto one at `load_base`
+pub fn rebase(addr: u64, origin: u64, load_base: u64) -> u64 {
+    addr.wrapping_sub(origin).wrapping_add(load_base)
+}
@@ -8,6 +8,7 @@ pub mod confidence;
 pub mod pointer_validation;
 pub mod cross_validation;
 pub mod size_validation;
+pub mod image;
 
 pub use validator::OffsetValidator;
 pub use rules::ValidationRule;
@@ -16,4 +17,5 @@ pub use report::{ValidationReport, ValidationIssue, IssueSeverity};
 pub use confidence::ConfidenceScorer;
 pub use pointer_validation::{PointerValidator, PointerValidationConfig, PointerValidationResult, PointerIssue, PointerExpectation};
 pub use cross_validation::{CrossValidator, CrossValidationCheck, CrossValidationReport, CheckResult, ResultAggregator, AggregatedResult};
-pub use size_validation::{SizeValidator, ExpectedSize, SizeValidationResult, InferredSize, AlignmentValidation};
+pub use size_validation::{SizeValidator, ExpectedSize, SizeValidationResult, InferredSize, AlignmentValidation, parse_expected_sizes};
+pub use image::{ImageValidator, LoadBase};
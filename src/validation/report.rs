@@ -57,6 +57,25 @@ impl ValidationReport {
         self.issues.iter().filter(|i| i.severity == IssueSeverity::Info)
     }
 
+    /// Issues recorded against `item`, in the order they were added
+    pub fn issues_for<'a>(&'a self, item: &'a str) -> impl Iterator<Item = &'a ValidationIssue> + 'a {
+        self.issues.iter().filter(move |i| i.item_name == item)
+    }
+
+    /// The most severe error or warning against `item`; `None` when it
+    /// only has info notes or nothing at all
+    pub fn worst_severity(&self, item: &str) -> Option<IssueSeverity> {
+        let severities: Vec<IssueSeverity> = self.issues_for(item).map(|i| i.severity).collect();
+
+        if severities.contains(&IssueSeverity::Error) {
+            Some(IssueSeverity::Error)
+        } else if severities.contains(&IssueSeverity::Warning) {
+            Some(IssueSeverity::Warning)
+        } else {
+            None
+        }
+    }
+
     pub fn is_valid(&self) -> bool {
         self.summary.error_count == 0
     }
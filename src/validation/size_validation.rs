@@ -60,16 +60,24 @@ impl SizeValidator {
 
     /// Validate total structure size
     pub fn validate_structure_size(&self, structure: &str, actual_size: usize) -> SizeValidationResult {
+        self.validate_structure_size_within(structure, actual_size, 0)
+    }
+
+    /// Like `validate_structure_size`, but a size up to `tolerance` bytes
+    /// outside the expectation still passes
+    pub fn validate_structure_size_within(&self, structure: &str, actual_size: usize, tolerance: usize) -> SizeValidationResult {
         let mut result = SizeValidationResult::new(structure, actual_size);
 
         if let Some(expected) = self.known_sizes.get(structure) {
+            let delta = expected.delta(actual_size);
             result.expected = Some(expected.clone());
-            result.is_valid = expected.is_valid_size(actual_size);
+            result.delta = Some(delta);
+            result.is_valid = delta.unsigned_abs() as usize <= tolerance;
             
             if !result.is_valid {
                 result.issues.push(format!(
-                    "Structure size 0x{:x} doesn't match expected {} ({})",
-                    actual_size, structure, expected
+                    "Structure size 0x{:x} doesn't match expected {} ({}), off by {}{} bytes",
+                    actual_size, structure, expected, if delta > 0 { "+" } else { "" }, delta
                 ));
             }
         }
@@ -77,6 +85,22 @@ impl SizeValidator {
         result
     }
 
+    /// Whether `size` is a whole number of `alignment`-byte units, as a
+    /// structure's size must be for arrays of it to stay aligned
+    pub fn validate_size_alignment(&self, size: usize, alignment: usize) -> AlignmentValidation {
+        let mut result = AlignmentValidation::new(size, size);
+        let alignment = alignment.max(1);
+
+        result.required_alignment = alignment;
+        result.is_aligned = size.is_multiple_of(alignment);
+
+        if !result.is_aligned {
+            result.padding_needed = alignment - (size % alignment);
+        }
+
+        result
+    }
+
     /// Infer structure size from offsets
     pub fn infer_size(&self, offsets: &[usize]) -> InferredSize {
         if offsets.is_empty() {
@@ -235,6 +259,25 @@ impl ExpectedSize {
         }
     }
 
+    /// How far `size` lies outside the expectation: negative when too
+    /// small, positive when too large, zero when it fits
+    pub fn delta(&self, size: usize) -> i64 {
+        let (min, max) = match self {
+            ExpectedSize::Exact(expected) => (*expected, *expected),
+            ExpectedSize::Minimum(min) => (*min, usize::MAX),
+            ExpectedSize::Maximum(max) => (0, *max),
+            ExpectedSize::Range(min, max) => (*min, *max),
+        };
+
+        if size < min {
+            -((min - size) as i64)
+        } else if size > max {
+            (size - max) as i64
+        } else {
+            0
+        }
+    }
+
     pub fn is_valid_offset(&self, offset: usize) -> bool {
         match self {
             ExpectedSize::Exact(expected) => offset < *expected,
@@ -245,6 +288,42 @@ impl ExpectedSize {
     }
 }
 
+impl std::str::FromStr for ExpectedSize {
+    type Err = String;
+
+    /// `0x140` for an exact size, `0x100-0x400` for a range, `>=0x20` or
+    /// `<=0x80` for a bound; hex with `0x`, decimal otherwise
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |n: &str| {
+            let n = n.trim();
+            match n.strip_prefix("0x").or_else(|| n.strip_prefix("0X")) {
+                Some(hex) => usize::from_str_radix(hex, 16),
+                None => n.parse(),
+            }
+            .map_err(|_| format!("Invalid size: {}", n))
+        };
+
+        let s = s.trim();
+        if let Some(min) = s.strip_prefix(">=") {
+            return Ok(ExpectedSize::Minimum(number(min)?));
+        }
+        if let Some(max) = s.strip_prefix("<=") {
+            return Ok(ExpectedSize::Maximum(number(max)?));
+        }
+
+        match s.split_once('-') {
+            Some((min, max)) => {
+                let (min, max) = (number(min)?, number(max)?);
+                if min > max {
+                    return Err(format!("Invalid size range: {}", s));
+                }
+                Ok(ExpectedSize::Range(min, max))
+            }
+            None => Ok(ExpectedSize::Exact(number(s)?)),
+        }
+    }
+}
+
 impl fmt::Display for ExpectedSize {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -262,6 +341,8 @@ pub struct SizeValidationResult {
     pub structure: String,
     pub value: usize,
     pub expected: Option<ExpectedSize>,
+    /// `ExpectedSize::delta` of a structure size check
+    pub delta: Option<i64>,
     pub is_valid: bool,
     pub issues: Vec<String>,
 }
@@ -272,6 +353,7 @@ impl SizeValidationResult {
             structure: structure.to_string(),
             value,
             expected: None,
+            delta: None,
             is_valid: true,
             issues: Vec::new(),
         }
@@ -484,6 +566,26 @@ impl fmt::Display for ArrayValidation {
     }
 }
 
+/// Expected sizes from `name=size` lines as `ExpectedSize` parses them,
+/// skipping blank lines and `#` comments
+pub fn parse_expected_sizes(text: &str) -> Result<Vec<(String, ExpectedSize)>, String> {
+    let mut sizes = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (name, size) = line.split_once('=')
+            .ok_or_else(|| format!("line {}: expected name=size, got {}", number + 1, line))?;
+        let expected = size.parse().map_err(|e| format!("line {}: {}", number + 1, e))?;
+        sizes.push((name.trim().to_string(), expected));
+    }
+
+    Ok(sizes)
+}
+
 /// GCD helper
 fn gcd(a: usize, b: usize) -> usize {
     if b == 0 { a } else { gcd(b, a % b) }
@@ -561,6 +663,20 @@ mod tests {
         assert!(!misaligned.is_aligned);
     }
 
+    #[test]
+    fn test_expected_sizes_file() {
+        let sizes = parse_expected_sizes("# Luau\nlua_State = 0x140\nTable=0x30-0x60\nUdata=>=24\n").unwrap();
+        let names: Vec<&str> = sizes.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["lua_State", "Table", "Udata"]);
+        assert!(parse_expected_sizes("lua_State").is_err());
+        assert!(parse_expected_sizes("Table=0x60-0x30").is_err());
+
+        assert_eq!(sizes[0].1.delta(0x148), 8);
+        assert_eq!(sizes[1].1.delta(0x40), 0);
+        assert_eq!(sizes[1].1.delta(0x20), -0x10);
+        assert_eq!(sizes[2].1.delta(0x1000), 0);
+    }
+
     #[test]
     fn test_size_comparison() {
         let result = SizeComparator::compare(100, 150);
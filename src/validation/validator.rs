@@ -1,9 +1,9 @@
 // Tue Jan 13 2026 - Alex
 
-use crate::memory::{Address, MemoryReader};
+use crate::memory::{Address, MemoryReader, MemoryRegion};
+use crate::analysis::heuristics::HeuristicsEngine;
 use crate::finders::result::FinderResults;
 use crate::validation::rules::ValidationRule;
-use crate::validation::checker::ValidationChecker;
 use crate::validation::report::{ValidationReport, ValidationIssue, IssueSeverity};
 use crate::validation::confidence::ConfidenceScorer;
 use std::sync::Arc;
@@ -12,7 +12,7 @@ use std::collections::HashMap;
 pub struct OffsetValidator {
     reader: Arc<dyn MemoryReader>,
     rules: Vec<Box<dyn ValidationRule>>,
-    checker: ValidationChecker,
+    heuristics: HeuristicsEngine,
     scorer: ConfidenceScorer,
 }
 
@@ -21,14 +21,15 @@ impl OffsetValidator {
         Self {
             reader: reader.clone(),
             rules: Self::default_rules(),
-            checker: ValidationChecker::new(reader),
+            heuristics: HeuristicsEngine::new(reader),
             scorer: ConfidenceScorer::new(),
         }
     }
 
+    /// Addresses are checked against the reader's regions, so there is no
+    /// fixed address-range rule; `RuleBuilder` can still add one.
     fn default_rules() -> Vec<Box<dyn ValidationRule>> {
         vec![
-            Box::new(AddressRangeRule::new()),
             Box::new(AlignmentRule::new()),
             Box::new(OffsetSizeRule::new()),
             Box::new(DuplicateRule::new()),
@@ -39,11 +40,17 @@ impl OffsetValidator {
         self.rules.push(rule);
     }
 
+    /// Every result in `results`. Functions must lie in an executable region
+    /// and look like an entry to the heuristics engine, whose reason is kept
+    /// as an error, warning or info issue; classes must lie in a region.
     pub fn validate(&self, results: &FinderResults) -> ValidationReport {
         let mut report = ValidationReport::new();
+        let regions = self.reader.get_regions().unwrap_or_default();
 
-        for (name, addr) in &results.functions {
-            self.validate_function(&mut report, name, *addr);
+        let mut functions: Vec<(&String, &Address)> = results.functions.iter().collect();
+        functions.sort();
+        for (name, addr) in functions {
+            self.validate_function(&mut report, name, *addr, &regions);
         }
 
         for (struct_name, fields) in &results.structure_offsets {
@@ -52,8 +59,10 @@ impl OffsetValidator {
             }
         }
 
-        for (name, addr) in &results.classes {
-            self.validate_class(&mut report, name, *addr);
+        let mut classes: Vec<(&String, &Address)> = results.classes.iter().collect();
+        classes.sort();
+        for (name, addr) in classes {
+            self.validate_class(&mut report, name, *addr, &regions);
         }
 
         for rule in &self.rules {
@@ -65,7 +74,7 @@ impl OffsetValidator {
         report
     }
 
-    fn validate_function(&self, report: &mut ValidationReport, name: &str, addr: Address) {
+    fn validate_function(&self, report: &mut ValidationReport, name: &str, addr: Address, regions: &[MemoryRegion]) {
         if addr.as_u64() == 0 {
             report.add_issue(ValidationIssue {
                 category: "function".to_string(),
@@ -77,6 +86,16 @@ impl OffsetValidator {
             return;
         }
 
+        if out_of_range(regions, addr) {
+            report.add_issue(ValidationIssue::error("function", name, "out of range"));
+            return;
+        }
+
+        if !regions.is_empty() && !regions.iter().any(|r| r.is_executable() && r.contains(addr)) {
+            report.add_issue(ValidationIssue::error("function", name, "not in an executable region"));
+            return;
+        }
+
         if addr.as_u64() % 4 != 0 {
             report.add_issue(ValidationIssue {
                 category: "function".to_string(),
@@ -87,14 +106,17 @@ impl OffsetValidator {
             });
         }
 
-        if self.checker.check_function_prologue(addr).is_err() {
-            report.add_issue(ValidationIssue {
-                category: "function".to_string(),
-                item_name: name.to_string(),
-                message: "Could not verify function prologue".to_string(),
-                severity: IssueSeverity::Info,
-                suggestion: Some("Address might not point to function start".to_string()),
-            });
+        match self.heuristics.is_function_entry(addr) {
+            Ok(entry) if entry.is_match => {
+                report.add_issue(ValidationIssue::info("function", name, &entry.reason));
+            }
+            Ok(entry) => {
+                report.add_issue(ValidationIssue::warning("function", name, &entry.reason)
+                    .with_suggestion("Address might not point to function start"));
+            }
+            Err(e) => {
+                report.add_issue(ValidationIssue::error("function", name, &format!("unreadable: {}", e)));
+            }
         }
     }
 
@@ -126,7 +148,7 @@ impl OffsetValidator {
         }
     }
 
-    fn validate_class(&self, report: &mut ValidationReport, name: &str, addr: Address) {
+    fn validate_class(&self, report: &mut ValidationReport, name: &str, addr: Address, regions: &[MemoryRegion]) {
         if addr.as_u64() == 0 {
             report.add_issue(ValidationIssue {
                 category: "class".to_string(),
@@ -138,6 +160,11 @@ impl OffsetValidator {
             return;
         }
 
+        if out_of_range(regions, addr) {
+            report.add_issue(ValidationIssue::error("class", name, "out of range"));
+            return;
+        }
+
         if addr.as_u64() % 8 != 0 {
             report.add_issue(ValidationIssue {
                 category: "class".to_string(),
@@ -154,43 +181,10 @@ impl OffsetValidator {
     }
 }
 
-struct AddressRangeRule {
-    min_address: u64,
-    max_address: u64,
-}
-
-impl AddressRangeRule {
-    fn new() -> Self {
-        Self {
-            min_address: 0x100000000,
-            max_address: 0x800000000000,
-        }
-    }
-}
-
-impl ValidationRule for AddressRangeRule {
-    fn name(&self) -> &str {
-        "AddressRange"
-    }
-
-    fn validate_all(&self, results: &FinderResults) -> Vec<ValidationIssue> {
-        let mut issues = Vec::new();
-
-        for (name, addr) in &results.functions {
-            let a = addr.as_u64();
-            if a != 0 && (a < self.min_address || a > self.max_address) {
-                issues.push(ValidationIssue {
-                    category: "address_range".to_string(),
-                    item_name: name.clone(),
-                    message: format!("Address 0x{:X} is outside expected range", a),
-                    severity: IssueSeverity::Warning,
-                    suggestion: Some(format!("Expected range: 0x{:X} - 0x{:X}", self.min_address, self.max_address)),
-                });
-            }
-        }
-
-        issues
-    }
+/// No region holds `addr`. Readers that can't list their regions never
+/// report out of range; the read that follows fails instead.
+pub(crate) fn out_of_range(regions: &[MemoryRegion], addr: Address) -> bool {
+    !regions.is_empty() && !regions.iter().any(|r| r.contains(addr))
 }
 
 struct AlignmentRule;
@@ -1,9 +1,13 @@
 // Tue Jan 13 2026 - Alex
 
 use crate::memory::{Address, MemoryReader};
+use crate::utils::arm64::Arm64Utils;
+use crate::xref::strings::clobbers;
 use crate::xref::{CallGraph, XRef, XRefError, GraphNode, GraphEdge, EdgeKind};
 use std::sync::Arc;
 
+const CHUNK: usize = 0x10000;
+
 pub struct XRefAnalyzer {
     graph: CallGraph,
     reader: Arc<dyn MemoryReader>,
@@ -29,9 +33,69 @@ impl XRefAnalyzer {
                 self.graph.add_edge(edge);
             }
         }
+        self.analyze_data_refs(start, end);
         Ok(())
     }
 
+    /// Resolves ADRP pairs in `start..end` into `Data` edges. Each register's
+    /// ADRP page is tracked until something overwrites it or a branch leaves
+    /// the straight-line path; an ADD immediate or unsigned-offset LDR/STR
+    /// based on it adds an edge from that instruction to the address the
+    /// pair forms. Returns how many edges were added.
+    pub fn analyze_data_refs(&mut self, start: Address, end: Address) -> usize {
+        let mut pages: [Option<u64>; 32] = [None; 32];
+        let mut added = 0;
+        let mut current = start;
+
+        while current < end {
+            let len = ((end - current) as usize).min(CHUNK) & !3;
+            let Ok(bytes) = self.reader.read_bytes(current, len) else {
+                pages = [None; 32];
+                current = current + CHUNK as u64;
+                continue;
+            };
+
+            for (i, word) in bytes.chunks_exact(4).enumerate() {
+                let insn = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+                let at = current.as_u64() + i as u64 * 4;
+
+                if let Some(target) = Self::fold_page(&pages, insn) {
+                    self.graph.add_edge(GraphEdge::new(Address::new(at), Address::new(target), EdgeKind::Data));
+                    added += 1;
+                }
+
+                for (reg, page) in pages.iter_mut().enumerate() {
+                    if page.is_some() && clobbers(insn, reg as u32) {
+                        *page = None;
+                    }
+                }
+                // Rd 31 is XZR here, and as a base it would be SP
+                if let Some(page) = Arm64Utils::get_adrp_value(insn, at).filter(|_| insn & 0x1F != 31) {
+                    pages[(insn & 0x1F) as usize] = Some(page);
+                }
+            }
+
+            current = current + CHUNK as u64;
+        }
+
+        added
+    }
+
+    /// Address `insn` forms from a tracked page: ADD (immediate, 64-bit) or
+    /// LDR/STR (unsigned offset) with the page register as its base
+    fn fold_page(pages: &[Option<u64>; 32], insn: u32) -> Option<u64> {
+        let page = pages[((insn >> 5) & 0x1F) as usize]?;
+
+        if (insn & 0xFF800000) == 0x91000000 {
+            return Some(page.wrapping_add(Arm64Utils::get_add_imm(insn)?));
+        }
+        if (insn & 0x3B000000) == 0x39000000 {
+            return Some(page.wrapping_add(Arm64Utils::get_ldr_str_offset(insn)? as u64));
+        }
+
+        None
+    }
+
     pub fn graph(&self) -> &CallGraph {
         &self.graph
     }
@@ -1,34 +1,97 @@
 // Tue Jan 13 2026 - Alex
 
-use crate::memory::Address;
+use crate::memory::{Address, MemoryReader};
+use crate::utils::arm64::Arm64Utils;
 use crate::xref::{CallGraph, GraphNode, GraphEdge, NodeKind, EdgeKind};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Stubs followed before giving up on a chain of thunks
+const MAX_STUB_HOPS: usize = 4;
 
 pub struct CallGraphBuilder {
     graph: CallGraph,
+    reader: Option<Arc<dyn MemoryReader>>,
+    /// Where each call target resolved to, stub or not
+    resolved: HashMap<u64, Address>,
 }
 
 impl CallGraphBuilder {
     pub fn new() -> Self {
         Self {
             graph: CallGraph::new(),
+            reader: None,
+            resolved: HashMap::new(),
         }
     }
 
+    /// Resolves calls through ADRP/LDR/BR stubs, so edges land on the
+    /// function the stub jumps to rather than on the stub
+    pub fn with_reader(mut self, reader: Arc<dyn MemoryReader>) -> Self {
+        self.reader = Some(reader);
+        self
+    }
+
     pub fn add_function(mut self, address: Address, name: Option<String>) -> Self {
-        let node = GraphNode::new(address, name.unwrap_or_else(|| format!("func_{:x}", address.as_u64())), NodeKind::Function);
+        let kind = if self.graph.is_stub(address) { NodeKind::Stub } else { NodeKind::Function };
+        let node = GraphNode::new(address, name.unwrap_or_else(|| format!("func_{:x}", address.as_u64())), kind);
         self.graph.add_node(node);
         self
     }
 
     pub fn add_call(mut self, from: Address, to: Address) -> Self {
-        let edge = GraphEdge::new(from, to, EdgeKind::Call);
+        let target = self.resolve(to);
+        let mut edge = GraphEdge::new(from, target, EdgeKind::Call);
+
+        if target != to {
+            edge = edge.with_via(to);
+            self.mark_stub(to);
+        }
+
         self.graph.add_edge(edge);
         self
     }
 
+    /// A BLR at `site` in the function at `from`. The target isn't known, so
+    /// the edge ends at a placeholder node for the call site itself.
+    pub fn add_indirect_call(mut self, from: Address, site: Address) -> Self {
+        let node = GraphNode::new(site, format!("indirect_{:x}", site.as_u64()), NodeKind::Unknown);
+        self.graph.add_node(node);
+        self.graph.add_edge(GraphEdge::new(from, site, EdgeKind::IndirectCall));
+        self
+    }
+
     pub fn build(self) -> CallGraph {
         self.graph
     }
+
+    fn resolve(&mut self, to: Address) -> Address {
+        let reader = match &self.reader {
+            Some(reader) => reader,
+            None => return to,
+        };
+        if let Some(&target) = self.resolved.get(&to.as_u64()) {
+            return target;
+        }
+
+        let mut target = to;
+        for _ in 0..MAX_STUB_HOPS {
+            match stub_target(reader.as_ref(), target) {
+                Some(next) if next != to => target = next,
+                _ => break,
+            }
+        }
+
+        self.resolved.insert(to.as_u64(), target);
+        target
+    }
+
+    fn mark_stub(&mut self, address: Address) {
+        let name = self.graph.get_node(address)
+            .map(|node| node.name())
+            .unwrap_or_else(|| format!("stub_{:x}", address.as_u64()));
+        self.graph.add_node(GraphNode::new(address, name, NodeKind::Stub));
+    }
 }
 
 impl Default for CallGraphBuilder {
@@ -36,3 +99,34 @@ impl Default for CallGraphBuilder {
         Self::new()
     }
 }
+
+/// Where the thunk at `address` jumps, if it is nothing but
+/// `adrp xN, page; ldr xT, [xN, #off]; br xT` (the pointer it loads must
+/// already be bound) or `adrp xN, page; add xN, xN, #off; br xN`
+pub fn stub_target(reader: &dyn MemoryReader, address: Address) -> Option<Address> {
+    let bytes = reader.read_bytes(address, 12).ok()?;
+    let word = |i: usize| u32::from_le_bytes([bytes[i * 4], bytes[i * 4 + 1], bytes[i * 4 + 2], bytes[i * 4 + 3]]);
+    let (adrp, second, br) = (word(0), word(1), word(2));
+
+    // BR (register), unauthenticated
+    if (br & 0xFFFFFC1F) != 0xD61F0000 {
+        return None;
+    }
+    let jump_reg = (br >> 5) & 0x1F;
+
+    if let Some(target) = Arm64Utils::fold_adrp_add(adrp, second, address.as_u64()) {
+        return (second & 0x1F == jump_reg).then_some(Address::new(target));
+    }
+
+    // 64-bit LDR (unsigned offset) from the register ADRP wrote
+    let page = Arm64Utils::get_adrp_value(adrp, address.as_u64())?;
+    if (second & 0xFFC00000) != 0xF9400000 || (second >> 5) & 0x1F != adrp & 0x1F || second & 0x1F != jump_reg {
+        return None;
+    }
+
+    let slot = page.wrapping_add(Arm64Utils::get_ldr_str_offset(second)? as u64);
+    match reader.read_u64(Address::new(slot)).ok()? {
+        0 => None,
+        target => Some(Address::new(target)),
+    }
+}
@@ -1,7 +1,7 @@
 // Wed Jan 15 2026 - Alex
 
 use crate::memory::Address;
-use crate::xref::{GraphNode, GraphEdge, XRef, XRefKind};
+use crate::xref::{GraphNode, GraphEdge, XRef};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
@@ -51,6 +51,28 @@ impl CallGraph {
             .unwrap_or_default()
     }
 
+    pub fn is_stub(&self, address: Address) -> bool {
+        self.get_node(address).is_some_and(GraphNode::is_stub)
+    }
+
+    pub fn stubs(&self) -> impl Iterator<Item = &GraphNode> {
+        self.nodes.values().filter(|node| node.is_stub())
+    }
+
+    /// This graph with stub nodes, and any edge still touching one, removed
+    pub fn without_stubs(&self) -> CallGraph {
+        let mut graph = CallGraph::new();
+
+        for node in self.nodes().filter(|node| !node.is_stub()) {
+            graph.add_node(node.clone());
+        }
+        for edge in self.edges().filter(|edge| !self.is_stub(edge.from()) && !self.is_stub(edge.to())) {
+            graph.add_edge(edge.clone());
+        }
+
+        graph
+    }
+
     pub fn nodes(&self) -> impl Iterator<Item = &GraphNode> {
         self.nodes.values()
     }
@@ -73,7 +95,7 @@ impl CallGraph {
                 indices.iter()
                     .map(|&i| {
                         let edge = &self.edges[i];
-                        XRef::new(edge.from(), edge.to(), XRefKind::Call)
+                        XRef::new(edge.from(), edge.to(), edge.kind().xref_kind())
                     })
                     .collect()
             })
@@ -86,7 +108,7 @@ impl CallGraph {
                 indices.iter()
                     .map(|&i| {
                         let edge = &self.edges[i];
-                        XRef::new(edge.from(), edge.to(), XRefKind::Call)
+                        XRef::new(edge.from(), edge.to(), edge.kind().xref_kind())
                     })
                     .collect()
             })
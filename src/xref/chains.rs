@@ -1,11 +1,22 @@
 // Tue Jan 15 2026 - Alex
 
+use crate::analysis::padding::{is_zero_padding, skip_padding};
+use crate::analysis::string::find_c_strings;
 use crate::memory::{Address, MemoryReader};
+use crate::utils::arm64::Arm64Utils;
 use crate::xref::{CallGraph, EdgeKind, XRefKind};
 use std::sync::Arc;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::cmp::Ordering;
 use std::fmt;
+use std::ops::Range;
+
+/// String occurrences followed up per needle
+const MAX_STRING_MATCHES: usize = 16;
+/// Instructions walked back from a load site looking for its function entry
+const MAX_ENTRY_SEARCH: usize = 512;
+/// Bytes of code searched per read for string loads
+const LOAD_STRIDE: usize = 0x10000;
 
 /// Represents a chain of references from one point to another
 #[derive(Debug, Clone)]
@@ -96,6 +107,7 @@ impl fmt::Display for ReferenceChain {
                 ChainLinkType::DataRef => " --data-> ",
                 ChainLinkType::Indirect => " --ind-> ",
                 ChainLinkType::Return => " --ret-> ",
+                ChainLinkType::Enclosing => " --in-> ",
                 ChainLinkType::Unknown => " --> ",
             };
             write!(f, "{}{:016x}", arrow, link.target.as_u64())?;
@@ -161,6 +173,8 @@ pub enum ChainLinkType {
     Indirect,
     /// Return from function
     Return,
+    /// Instruction to the function containing it
+    Enclosing,
     /// Unknown type
     Unknown,
 }
@@ -168,7 +182,7 @@ pub enum ChainLinkType {
 impl From<EdgeKind> for ChainLinkType {
     fn from(kind: EdgeKind) -> Self {
         match kind {
-            EdgeKind::Call => ChainLinkType::Call,
+            EdgeKind::Call | EdgeKind::IndirectCall => ChainLinkType::Call,
             EdgeKind::Jump => ChainLinkType::Jump,
             EdgeKind::Reference | EdgeKind::Data => ChainLinkType::DataRef,
             EdgeKind::String | EdgeKind::Constant => ChainLinkType::Unknown,
@@ -188,6 +202,36 @@ impl From<XRefKind> for ChainLinkType {
     }
 }
 
+/// Entry of the function containing `site`, the confidence in it, and
+/// which boundary was found: a frame-saving prologue, the previous
+/// function's RET or trailing padding, or nothing within the search window.
+pub fn enclosing_function(reader: &dyn MemoryReader, site: Address) -> (Address, f64, &'static str) {
+    let base = reader.get_base_address();
+    let mut current = site;
+
+    for _ in 0..MAX_ENTRY_SEARCH {
+        if current <= base {
+            break;
+        }
+
+        if let Ok(bytes) = reader.read_bytes(current, 4) {
+            let insn = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+            if (insn & 0x7F800000) == 0x29000000 || (insn & 0x7F800000) == 0x6D000000 {
+                return (current, 0.9, "prologue");
+            }
+
+            if current != site && ((insn & 0xFFFFFC1F) == 0xD65F0000 || is_zero_padding(reader, current)) {
+                return (skip_padding(reader, current + 4), 0.7, "previous ret");
+            }
+        }
+
+        current = current - 4;
+    }
+
+    (site, 0.4, "none")
+}
+
 /// Analyzer for finding and analyzing reference chains
 pub struct ChainAnalyzer {
     reader: Arc<dyn MemoryReader>,
@@ -216,6 +260,88 @@ impl ChainAnalyzer {
         self
     }
 
+    /// Chains from each exact C string match of `needle` to the ADRP+ADD
+    /// that loads it and on to the function containing that load, one per
+    /// function, ranked best first. Strings and code are both searched in
+    /// `start..end`; see `find_string_chains_in` to bound them separately.
+    pub fn find_string_chains(&self, needle: &str, start: Address, end: Address) -> Vec<ReferenceChain> {
+        self.find_string_chains_in(needle, start..end, &[start..end])
+    }
+
+    /// `find_string_chains` with the string looked up in `strings` (the
+    /// string section) and its loads in `code` (the executable regions)
+    pub fn find_string_chains_in(&self, needle: &str, strings: Range<Address>, code: &[Range<Address>]) -> Vec<ReferenceChain> {
+        let mut strings = find_c_strings(self.reader.as_ref(), needle, strings);
+        strings.truncate(MAX_STRING_MATCHES);
+        if strings.is_empty() {
+            return Vec::new();
+        }
+
+        let mut seen_functions = HashSet::new();
+        let mut chains = Vec::new();
+
+        let loads = code.iter().flat_map(|range| self.find_string_loads(&strings, range.clone()));
+        for (site, string_addr) in loads {
+            let (entry, entry_confidence, boundary) = self.find_enclosing_function(site);
+
+            if !seen_functions.insert((string_addr, entry)) {
+                continue;
+            }
+
+            let mut chain = ReferenceChain::new(string_addr, entry);
+            chain.add_link(ChainLink::new(string_addr, site, ChainLinkType::DataRef)
+                .with_metadata("instruction", "adrp+add"));
+            chain.add_link(ChainLink::new(site, entry, ChainLinkType::Enclosing)
+                .with_confidence(entry_confidence)
+                .with_metadata("boundary", boundary));
+            chains.push(chain);
+        }
+
+        ChainRanker::rank(&chains)
+            .into_iter()
+            .map(|(_, chain, _)| chain.clone())
+            .collect()
+    }
+
+    /// (ADRP address, string address) for each ADRP+ADD in `code` resolving
+    /// to one of `strings`
+    fn find_string_loads(&self, strings: &[Address], code: Range<Address>) -> Vec<(Address, Address)> {
+        let targets: HashSet<u64> = strings.iter().map(|s| s.as_u64()).collect();
+        let mut loads = Vec::new();
+        let mut current = code.start;
+
+        while current < code.end {
+            // One word past the stride so a pair straddling it is still seen,
+            // clamped so the last read stays inside the range
+            let len = ((code.end - current) as usize).min(LOAD_STRIDE + 4);
+
+            if let Ok(bytes) = self.reader.read_bytes(current, len) {
+                let words: Vec<u32> = bytes.chunks_exact(4)
+                    .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+                    .collect();
+
+                for (i, pair) in words.windows(2).enumerate().take(LOAD_STRIDE / 4) {
+                    let at = current.as_u64() + i as u64 * 4;
+
+                    match Arm64Utils::fold_adrp_add(pair[0], pair[1], at) {
+                        Some(target) if targets.contains(&target) => {
+                            loads.push((Address::new(at), Address::new(target)));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            current = current + LOAD_STRIDE as u64;
+        }
+
+        loads
+    }
+
+    fn find_enclosing_function(&self, site: Address) -> (Address, f64, &'static str) {
+        enclosing_function(self.reader.as_ref(), site)
+    }
+
     /// Find all chains from source to target
     pub fn find_chains(&self, source: Address, target: Address) -> Vec<ReferenceChain> {
         let mut chains = Vec::new();
@@ -575,7 +701,7 @@ impl ChainRanker {
         // Direct calls are better than indirect
         for link in &chain.links {
             match link.link_type {
-                ChainLinkType::Call => {}
+                ChainLinkType::Call | ChainLinkType::Enclosing => {}
                 ChainLinkType::Jump => score -= 2.0,
                 ChainLinkType::DataRef => score -= 5.0,
                 ChainLinkType::Indirect => score -= 10.0,
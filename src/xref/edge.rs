@@ -1,6 +1,7 @@
 // Tue Jan 15 2026 - Alex
 
 use crate::memory::Address;
+use crate::xref::XRefKind;
 use std::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -8,6 +9,8 @@ pub struct GraphEdge {
     from: Address,
     to: Address,
     kind: EdgeKind,
+    /// The stub the call went through to reach `to`
+    via: Option<Address>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -18,11 +21,18 @@ pub enum EdgeKind {
     Constant,
     Jump,
     Reference,
+    /// BLR through a register; `to` is the call site, the target is unknown
+    IndirectCall,
 }
 
 impl GraphEdge {
     pub fn new(from: Address, to: Address, kind: EdgeKind) -> Self {
-        Self { from, to, kind }
+        Self { from, to, kind, via: None }
+    }
+
+    pub fn with_via(mut self, stub: Address) -> Self {
+        self.via = Some(stub);
+        self
     }
 
     pub fn from(&self) -> Address {
@@ -37,8 +47,24 @@ impl GraphEdge {
         self.kind
     }
 
+    pub fn via(&self) -> Option<Address> {
+        self.via
+    }
+
     pub fn is_call(&self) -> bool {
-        matches!(self.kind, EdgeKind::Call)
+        matches!(self.kind, EdgeKind::Call | EdgeKind::IndirectCall)
+    }
+}
+
+impl EdgeKind {
+    /// The kind an edge of this kind is reported as through `XRef`
+    pub fn xref_kind(self) -> XRefKind {
+        match self {
+            EdgeKind::Call | EdgeKind::IndirectCall => XRefKind::Call,
+            EdgeKind::Jump => XRefKind::Jump,
+            EdgeKind::Data | EdgeKind::Constant | EdgeKind::Reference => XRefKind::Data,
+            EdgeKind::String => XRefKind::String,
+        }
     }
 }
 
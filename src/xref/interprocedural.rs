@@ -90,6 +90,10 @@ pub struct FunctionSummary {
     pub globals_written: HashSet<u64>,
     /// Functions called
     pub callees: Vec<Address>,
+    /// (call site, callee) for every direct call or tail call
+    pub call_sites: Vec<(Address, Address)>,
+    /// Sites of BLR calls, whose targets aren't resolved
+    pub indirect_call_sites: Vec<Address>,
     /// Side effects
     pub side_effects: Vec<SideEffect>,
     /// Whether the function may not return
@@ -108,6 +112,8 @@ impl FunctionSummary {
             globals_read: HashSet::new(),
             globals_written: HashSet::new(),
             callees: Vec::new(),
+            call_sites: Vec::new(),
+            indirect_call_sites: Vec::new(),
             side_effects: Vec::new(),
             may_not_return: false,
             is_pure: true,
@@ -146,6 +152,18 @@ impl FunctionSummary {
         }
     }
 
+    pub fn add_call_site(&mut self, site: Address, callee: Address) {
+        self.call_sites.push((site, callee));
+        self.add_callee(callee);
+    }
+
+    /// First call site in this function that targets `callee`
+    pub fn call_site_of(&self, callee: Address) -> Option<Address> {
+        self.call_sites.iter()
+            .find(|(_, target)| *target == callee)
+            .map(|(site, _)| *site)
+    }
+
     pub fn add_side_effect(&mut self, effect: SideEffect) {
         self.side_effects.push(effect);
         self.is_pure = false;
@@ -259,6 +277,79 @@ impl InterproceduralAnalyzer {
         })
     }
 
+    /// Breadth-first walk of direct calls from `root`, recording each
+    /// function's minimum call depth and the call sites that first reach it.
+    /// Callees beyond `max_depth` are not expanded; unreadable callees are
+    /// skipped.
+    pub fn reachable_from(&mut self, root: Address, max_depth: usize) -> Result<ReachabilityResult, MemoryError> {
+        if !self.analyzed_functions.contains(&root.as_u64()) {
+            self.analyze_function(root)?;
+        }
+
+        let mut result = ReachabilityResult {
+            root,
+            functions: vec![ReachableFunction { address: root, depth: 0, context: CallContext::new(max_depth) }],
+            cycles: Vec::new(),
+            truncated: false,
+        };
+        let mut index: HashMap<u64, usize> = HashMap::new();
+        index.insert(root.as_u64(), 0);
+        let mut parents: Vec<Option<usize>> = vec![None];
+
+        let mut queue = VecDeque::new();
+        queue.push_back(0usize);
+
+        while let Some(i) = queue.pop_front() {
+            let (func, depth, context) = {
+                let entry = &result.functions[i];
+                (entry.address, entry.depth, entry.context.clone())
+            };
+
+            let call_sites = match self.function_summaries.get(&func.as_u64()) {
+                Some(summary) => summary.call_sites.clone(),
+                None => continue,
+            };
+
+            for (site, callee) in call_sites {
+                let on_path = index.get(&callee.as_u64()).is_some_and(|&target| {
+                    std::iter::successors(Some(i), |&n| parents[n]).any(|n| n == target)
+                });
+
+                if on_path {
+                    result.cycles.push((func, callee));
+                    continue;
+                }
+
+                if index.contains_key(&callee.as_u64()) {
+                    continue;
+                }
+
+                if depth >= max_depth {
+                    result.truncated = true;
+                    continue;
+                }
+
+                if !self.analyzed_functions.contains(&callee.as_u64()) && self.analyze_function(callee).is_err() {
+                    continue;
+                }
+
+                let mut callee_context = context.clone();
+                callee_context.push(site);
+
+                index.insert(callee.as_u64(), result.functions.len());
+                queue.push_back(result.functions.len());
+                parents.push(Some(i));
+                result.functions.push(ReachableFunction {
+                    address: callee,
+                    depth: depth + 1,
+                    context: callee_context,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Analyze a single function
     fn analyze_function(&mut self, addr: Address) -> Result<(), MemoryError> {
         let mut summary = FunctionSummary::new(addr);
@@ -284,7 +375,7 @@ impl InterproceduralAnalyzer {
                 let target = self.decode_branch_target(current, insn);
                 if let Some(target_addr) = target {
                     // Could be tail call - add as callee
-                    summary.add_callee(Address::new(target_addr));
+                    summary.add_call_site(current, Address::new(target_addr));
                 }
                 break;
             }
@@ -316,14 +407,13 @@ impl InterproceduralAnalyzer {
         if (insn & 0xFC000000) == 0x94000000 {
             let offset = ((insn & 0x03FFFFFF) as i32) << 6 >> 6;
             let target = (addr.as_u64() as i64 + (offset as i64 * 4)) as u64;
-            summary.add_callee(Address::new(target));
+            summary.add_call_site(addr, Address::new(target));
         }
 
         // BLR - Branch with Link to Register
         if (insn & 0xFFFFFC1F) == 0xD63F0000 {
-            // Indirect call - harder to resolve
-            let rn = ((insn >> 5) & 0x1F) as u8;
-            // Could try to resolve if we have value tracking
+            // Indirect call - harder to resolve, so only the site is kept
+            summary.indirect_call_sites.push(addr);
         }
 
         // LDR from global
@@ -577,6 +667,42 @@ impl InterproceduralResult {
     }
 }
 
+/// A function reached from a root by direct calls
+#[derive(Debug, Clone)]
+pub struct ReachableFunction {
+    pub address: Address,
+    /// Minimum number of calls from the root
+    pub depth: usize,
+    /// Call sites on the shortest path, root first
+    pub context: CallContext,
+}
+
+/// Result of `InterproceduralAnalyzer::reachable_from`
+#[derive(Debug, Clone)]
+pub struct ReachabilityResult {
+    pub root: Address,
+    /// Reached functions in breadth-first order, root first
+    pub functions: Vec<ReachableFunction>,
+    /// (caller, callee) edges that call back into the current path
+    pub cycles: Vec<(Address, Address)>,
+    /// Whether some callees were left unexplored at the depth cap
+    pub truncated: bool,
+}
+
+impl ReachabilityResult {
+    pub fn get(&self, addr: Address) -> Option<&ReachableFunction> {
+        self.functions.iter().find(|f| f.address == addr)
+    }
+
+    pub fn depth_of(&self, addr: Address) -> Option<usize> {
+        self.get(addr).map(|f| f.depth)
+    }
+
+    pub fn reaches(&self, addr: Address) -> bool {
+        self.get(addr).is_some()
+    }
+}
+
 /// Statistics from inter-procedural analysis
 #[derive(Debug, Clone)]
 pub struct InterproceduralStats {
@@ -16,12 +16,13 @@ pub mod dataflow;
 pub mod interprocedural;
 pub mod visualization;
 pub mod chains;
+pub mod strings;
 
 pub use analyzer::XRefAnalyzer;
 pub use callgraph::CallGraph;
 pub use reference::{XRef, XRefKind};
 pub use traversal::XRefTraverser;
-pub use builder::CallGraphBuilder;
+pub use builder::{CallGraphBuilder, stub_target};
 pub use error::XRefError;
 pub use node::GraphNode;
 pub use edge::GraphEdge;
@@ -31,6 +32,7 @@ pub use stats::XRefStats;
 pub use node::NodeKind;
 pub use edge::EdgeKind;
 pub use dataflow::{DataFlowAnalyzer, DataDefinition, DataUse, DataLocation, DataValue, DefUseChain, UseDefChain, DataFlowResult};
-pub use interprocedural::{InterproceduralAnalyzer, FunctionSummary, InterproceduralResult, CallContext};
+pub use interprocedural::{InterproceduralAnalyzer, FunctionSummary, InterproceduralResult, CallContext, ReachableFunction, ReachabilityResult};
 pub use visualization::{GraphExporter, ExportFormat, ExportOptions, SubgraphExtractor, GraphStatistics, GraphStats};
 pub use chains::{ReferenceChain, ChainLink, ChainLinkType, ChainAnalyzer, ChainBuilder, ChainRanker};
+pub use strings::{StringLoadKind, StringXref, StringXrefScanner};
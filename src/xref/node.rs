@@ -17,6 +17,8 @@ pub enum NodeKind {
     String,
     Constant,
     External,
+    /// An ADRP/LDR/BR thunk; calls into it are recorded at its target
+    Stub,
     Unknown,
 }
 
@@ -49,6 +51,10 @@ impl GraphNode {
     pub fn is_function(&self) -> bool {
         matches!(self.kind, NodeKind::Function)
     }
+
+    pub fn is_stub(&self) -> bool {
+        matches!(self.kind, NodeKind::Stub)
+    }
 }
 
 impl fmt::Display for GraphNode {
@@ -0,0 +1,210 @@
+// Fri Jan 16 2026 - Alex
+
+use crate::memory::{Address, MemoryReader};
+use crate::utils::arm64::Arm64Utils;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::Arc;
+
+const CHUNK: usize = 0x10000;
+
+/// Instructions searched after an ADRP for the ADDs that complete it
+const PAIR_WINDOW: usize = 8;
+
+const MAX_STRING_LEN: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringLoadKind {
+    AdrpAdd,
+    Adr,
+}
+
+impl StringLoadKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StringLoadKind::AdrpAdd => "adrp+add",
+            StringLoadKind::Adr => "adr",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringXref {
+    /// The ADRP or ADR that starts the load
+    pub code: Address,
+    pub string: Address,
+    pub value: String,
+    pub kind: StringLoadKind,
+}
+
+/// Pairs code with the string literals it loads: every ADR, and every ADRP
+/// completed by an ADD off the same register, whose target lands in the
+/// string section. A target inside a literal (the linker merges common
+/// suffixes) reads from there to the NUL.
+pub struct StringXrefScanner {
+    reader: Arc<dyn MemoryReader>,
+}
+
+impl StringXrefScanner {
+    pub fn new(reader: Arc<dyn MemoryReader>) -> Self {
+        Self { reader }
+    }
+
+    /// Loads in `code` that target `strings`, one per (site, string), sorted
+    /// by code address
+    pub fn scan(&self, code: Range<Address>, strings: Range<Address>) -> Vec<StringXref> {
+        let mut seen = HashSet::new();
+        let mut xrefs = Vec::new();
+
+        self.for_each_load(code, |at, target, kind| {
+            if !strings.contains(&Address::new(target)) || !seen.insert((at, target)) {
+                return;
+            }
+
+            if let Some(value) = self.read_string(Address::new(target), strings.end) {
+                xrefs.push(StringXref {
+                    code: Address::new(at),
+                    string: Address::new(target),
+                    value,
+                    kind,
+                });
+            }
+        });
+
+        xrefs.sort_by_key(|x| (x.code, x.string));
+        xrefs
+    }
+
+    /// Loads in `code` of exactly one of `targets`, as (site, target) sorted
+    /// by code address. One pass however many targets there are.
+    pub fn scan_targets(&self, code: Range<Address>, targets: &HashSet<u64>) -> Vec<(Address, Address)> {
+        let mut sites = Vec::new();
+
+        self.for_each_load(code, |at, target, _| {
+            if targets.contains(&target) {
+                sites.push((Address::new(at), Address::new(target)));
+            }
+        });
+
+        sites.sort();
+        sites.dedup();
+        sites
+    }
+
+    /// Every ADR and completed ADRP in `code`, as (site, target, kind)
+    fn for_each_load(&self, code: Range<Address>, mut f: impl FnMut(u64, u64, StringLoadKind)) {
+        let mut current = code.start;
+
+        while current < code.end {
+            // Read past the chunk so pairs that straddle it are still seen
+            let len = ((code.end - current) as usize).min(CHUNK + PAIR_WINDOW * 4);
+
+            if let Ok(bytes) = self.reader.read_bytes(current, len) {
+                let insns: Vec<u32> = bytes.chunks_exact(4)
+                    .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                    .collect();
+
+                for i in 0..insns.len().min(CHUNK / 4) {
+                    let at = current.as_u64() + i as u64 * 4;
+
+                    for (target, kind) in self.loads_at(&insns[i..], at) {
+                        f(at, target, kind);
+                    }
+                }
+            }
+
+            current = current + CHUNK as u64;
+        }
+    }
+
+    /// Addresses materialized by the ADR or ADRP at `insns[0]`
+    fn loads_at(&self, insns: &[u32], at: u64) -> Vec<(u64, StringLoadKind)> {
+        let first = insns[0];
+
+        if let Some(target) = Arm64Utils::get_adr_target(first, at) {
+            return vec![(target, StringLoadKind::Adr)];
+        }
+        if Arm64Utils::get_adrp_value(first, at).is_none() {
+            return Vec::new();
+        }
+
+        let rd = first & 0x1F;
+        let mut loads = Vec::new();
+
+        for &insn in insns.iter().skip(1).take(PAIR_WINDOW) {
+            if let Some(target) = Arm64Utils::fold_adrp_add(first, insn, at) {
+                loads.push((target, StringLoadKind::AdrpAdd));
+            }
+            if clobbers(insn, rd) {
+                break;
+            }
+        }
+
+        loads
+    }
+
+    fn read_string(&self, addr: Address, end: Address) -> Option<String> {
+        let len = ((end - addr) as usize).min(MAX_STRING_LEN);
+        let bytes = self.reader.read_bytes(addr, len).ok()?;
+        let nul = bytes.iter().position(|&b| b == 0)?;
+
+        match std::str::from_utf8(&bytes[..nul]) {
+            Ok(s) if !s.is_empty() => Some(s.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `insn` may overwrite `reg` or leave the straight-line path.
+/// Conservative: anything with `reg` in the Rd/Rt slot counts.
+pub(crate) fn clobbers(insn: u32, reg: u32) -> bool {
+    // B, BL, BR/BLR/RET
+    if (insn & 0x7C000000) == 0x14000000 || (insn & 0xFE000000) == 0xD6000000 {
+        return true;
+    }
+
+    insn & 0x1F == reg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::MachOFixture;
+
+    #[test]
+    fn test_pairs_loads_with_their_strings() {
+        let mut fixture = MachOFixture::new();
+        let hello = fixture.add_cstring("Hello");
+        let world = fixture.add_cstring("World");
+        let lo12 = |addr: Address| ((addr.as_u64() & 0xFFF) as u32) << 10;
+
+        // adrp x8; add x0, x8, Hello; nop; add x1, x8, World
+        let shared = fixture.next_code_address();
+        let [adrp, _] = MachOFixture::adrp_add(8, shared, hello);
+        fixture.add_code(&[adrp, 0x91000100 | lo12(hello), 0xD503201F, 0x91000101 | lo12(world)]);
+
+        // adrp x9; add x9, x9, Hello; add x1, x9, World (x9 no longer the page)
+        let reused = fixture.next_code_address();
+        let [adrp, add] = MachOFixture::adrp_add(9, reused, hello);
+        fixture.add_code(&[adrp, add, 0x91000121 | lo12(world)]);
+
+        // adr x2, World
+        let adr = fixture.next_code_address();
+        let delta = (world.as_u64() - adr.as_u64()) as u32;
+        fixture.add_code(&[0x10000002 | ((delta & 0x3) << 29) | ((delta >> 2) << 5), 0xD65F03C0]);
+
+        let reader: Arc<dyn MemoryReader> = Arc::new(fixture.build());
+        let code = Address::new(MachOFixture::TEXT_ADDRESS)..Address::new(MachOFixture::CSTRING_ADDRESS);
+        let xrefs = StringXrefScanner::new(reader).scan(code, hello..(world + 6));
+
+        let found: Vec<(Address, &str, &str)> = xrefs.iter()
+            .map(|x| (x.code, x.value.as_str(), x.kind.name()))
+            .collect();
+        assert_eq!(found, vec![
+            (shared, "Hello", "adrp+add"),
+            (shared, "World", "adrp+add"),
+            (reused, "Hello", "adrp+add"),
+            (adr, "World", "adr"),
+        ]);
+    }
+}
@@ -1,5 +1,6 @@
 // Tue Jan 15 2026 - Alex
 
+use crate::analysis::cfg::{CfgBlock, ControlFlowGraph, EdgeType};
 use crate::memory::Address;
 use crate::xref::{CallGraph, GraphNode, EdgeKind, NodeKind};
 use std::collections::{HashMap, HashSet};
@@ -44,6 +45,11 @@ pub struct ExportOptions {
     pub title: Option<String>,
     /// Direction (TB, LR, BT, RL)
     pub direction: GraphDirection,
+    /// Only export nodes within this many edges of a root (a node nothing
+    /// points at); graphs without a root are exported whole
+    pub max_depth: Option<usize>,
+    /// Whether to export BLR edges and their placeholder nodes
+    pub include_indirect: bool,
 }
 
 impl Default for ExportOptions {
@@ -58,6 +64,8 @@ impl Default for ExportOptions {
             custom_colors: HashMap::new(),
             title: None,
             direction: GraphDirection::TopBottom,
+            max_depth: None,
+            include_indirect: true,
         }
     }
 }
@@ -74,6 +82,8 @@ impl ExportOptions {
             custom_colors: HashMap::new(),
             title: None,
             direction: GraphDirection::TopBottom,
+            max_depth: None,
+            include_indirect: true,
         }
     }
 }
@@ -116,6 +126,14 @@ impl GraphExporter {
 
     /// Export call graph to DOT format
     pub fn to_dot(&self, graph: &CallGraph) -> String {
+        let scoped;
+        let graph = if self.options.max_depth.is_some() || !self.options.include_indirect {
+            scoped = self.scope(graph);
+            &scoped
+        } else {
+            graph
+        };
+
         let mut dot = String::new();
 
         // Header
@@ -142,7 +160,7 @@ impl GraphExporter {
 
             let color = self.get_node_color(node.kind());
             let label = if self.options.include_labels {
-                format!("{}\\n{:x}", node.name(), node.address().as_u64())
+                format!("{}\\n{:x}", escape_dot(&node.name()), node.address().as_u64())
             } else {
                 format!("{:x}", node.address().as_u64())
             };
@@ -178,6 +196,52 @@ impl GraphExporter {
         dot
     }
 
+    /// `graph` without indirect edges (unless included) and cut to
+    /// `max_depth` from its roots
+    fn scope(&self, graph: &CallGraph) -> CallGraph {
+        let mut edges: Vec<_> = graph.edges()
+            .filter(|e| self.options.include_indirect || e.kind() != EdgeKind::IndirectCall)
+            .cloned()
+            .collect();
+
+        // Placeholder nodes only exist as the far end of a BLR edge
+        let indirect: HashSet<u64> = graph.edges()
+            .filter(|e| !self.options.include_indirect && e.kind() == EdgeKind::IndirectCall)
+            .map(|e| e.to().as_u64())
+            .collect();
+        let mut keep: HashSet<u64> = graph.nodes()
+            .map(|n| n.address().as_u64())
+            .filter(|addr| !indirect.contains(addr))
+            .collect();
+
+        if let Some(max_depth) = self.options.max_depth {
+            let targets: HashSet<u64> = edges.iter().map(|e| e.to().as_u64()).collect();
+            let mut level: Vec<u64> = keep.iter().copied().filter(|addr| !targets.contains(addr)).collect();
+
+            if !level.is_empty() {
+                let mut reached: HashSet<u64> = level.iter().copied().collect();
+                for _ in 0..max_depth {
+                    level = edges.iter()
+                        .filter(|e| level.contains(&e.from().as_u64()) && reached.insert(e.to().as_u64()))
+                        .map(|e| e.to().as_u64())
+                        .collect();
+                }
+                keep.retain(|addr| reached.contains(addr));
+            }
+        }
+
+        edges.retain(|e| keep.contains(&e.from().as_u64()) && keep.contains(&e.to().as_u64()));
+
+        let mut scoped = CallGraph::new();
+        for node in graph.nodes().filter(|n| keep.contains(&n.address().as_u64())) {
+            scoped.add_node(node.clone());
+        }
+        for edge in edges {
+            scoped.add_edge(edge);
+        }
+        scoped
+    }
+
     /// Export call graph to JSON format
     pub fn to_json(&self, graph: &CallGraph) -> String {
         let mut json = String::new();
@@ -404,6 +468,7 @@ impl GraphExporter {
             ) {
                 let arrow = match edge.kind() {
                     EdgeKind::Call => "-->",
+                    EdgeKind::IndirectCall => "-.->",
                     EdgeKind::Jump => "-.->",
                     EdgeKind::Reference | EdgeKind::Data => "-.->",
                     EdgeKind::String | EdgeKind::Constant => "~~>",
@@ -435,6 +500,159 @@ impl GraphExporter {
         }
     }
 
+    /// Export one function's control flow graph. Nodes are basic blocks
+    /// labelled with their mnemonics, edges are labelled taken/fallthrough
+    /// (or jump/return). Formats without a CFG form come out as JSON.
+    pub fn export_cfg(&self, cfg: &ControlFlowGraph, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Dot => self.cfg_to_dot(cfg),
+            ExportFormat::Mermaid => self.cfg_to_mermaid(cfg),
+            _ => self.cfg_to_json(cfg),
+        }
+    }
+
+    fn cfg_to_dot(&self, cfg: &ControlFlowGraph) -> String {
+        let mut dot = String::new();
+
+        let title = self.options.title.clone()
+            .unwrap_or_else(|| format!("cfg_{:x}", cfg.entry().as_u64()));
+        dot.push_str(&format!("digraph \"{}\" {{\n", escape_dot(&title)));
+        dot.push_str(&format!("  rankdir={};\n", self.options.direction.to_dot()));
+        dot.push_str("  node [shape=box, style=filled, fontname=\"monospace\"];\n");
+        dot.push_str("  edge [arrowsize=0.8];\n\n");
+
+        for block in sorted_blocks(cfg) {
+            // \l left-justifies each line in the box
+            let label = if self.options.include_labels {
+                let mnemonics: String = block.instructions()
+                    .iter()
+                    .map(|instr| format!("{}\\l", escape_dot(&instr.mnemonic)))
+                    .collect();
+                format!("{:x}:\\l{}", block.start.as_u64(), mnemonics)
+            } else {
+                format!("{:x}", block.start.as_u64())
+            };
+
+            dot.push_str(&format!(
+                "  \"{}\" [label=\"{}\", fillcolor=\"{}\"];\n",
+                block.start.as_u64(),
+                label,
+                self.get_block_color(cfg, block)
+            ));
+        }
+
+        dot.push('\n');
+
+        for edge in cfg.edges() {
+            let style = match edge.edge_type {
+                EdgeType::ConditionalTrue => "color=darkgreen",
+                EdgeType::ConditionalFalse => "color=red",
+                EdgeType::Jump => "color=blue",
+                EdgeType::Call | EdgeType::Return => "color=gray, style=dashed",
+                EdgeType::Flow => "color=black",
+            };
+            let attrs = if self.options.include_edge_labels {
+                format!(" [label=\"{}\", {}]", edge.edge_type.label(), style)
+            } else {
+                format!(" [{}]", style)
+            };
+
+            dot.push_str(&format!(
+                "  \"{}\" -> \"{}\"{}\n",
+                edge.from.as_u64(),
+                edge.to.as_u64(),
+                attrs
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn cfg_to_mermaid(&self, cfg: &ControlFlowGraph) -> String {
+        let mut mermaid = String::new();
+
+        let direction = match self.options.direction {
+            GraphDirection::TopBottom => "TD",
+            GraphDirection::BottomTop => "BT",
+            GraphDirection::LeftRight => "LR",
+            GraphDirection::RightLeft => "RL",
+        };
+        mermaid.push_str(&format!("graph {}\n", direction));
+
+        for block in sorted_blocks(cfg) {
+            let label = if self.options.include_labels {
+                let mnemonics: Vec<String> = block.instructions()
+                    .iter()
+                    .map(|instr| escape_mermaid(&instr.mnemonic))
+                    .collect();
+                format!("{:x}<br/>{}", block.start.as_u64(), mnemonics.join("<br/>"))
+            } else {
+                format!("{:x}", block.start.as_u64())
+            };
+            mermaid.push_str(&format!("    B{:x}[\"{}\"]\n", block.start.as_u64(), label));
+        }
+
+        for edge in cfg.edges() {
+            let arrow = match edge.edge_type {
+                EdgeType::Call | EdgeType::Return => "-.->",
+                _ => "-->",
+            };
+
+            if self.options.include_edge_labels {
+                mermaid.push_str(&format!(
+                    "    B{:x} {}|{}| B{:x}\n",
+                    edge.from.as_u64(), arrow, edge.edge_type.label(), edge.to.as_u64()
+                ));
+            } else {
+                mermaid.push_str(&format!("    B{:x} {} B{:x}\n", edge.from.as_u64(), arrow, edge.to.as_u64()));
+            }
+        }
+
+        mermaid
+    }
+
+    fn cfg_to_json(&self, cfg: &ControlFlowGraph) -> String {
+        let blocks: Vec<serde_json::Value> = sorted_blocks(cfg)
+            .into_iter()
+            .map(|block| serde_json::json!({
+                "address": format!("0x{:x}", block.start.as_u64()),
+                "end": format!("0x{:x}", block.end.as_u64()),
+                "mnemonics": block.instructions().iter().map(|i| i.mnemonic.clone()).collect::<Vec<_>>(),
+                "exit": cfg.is_exit_block(block.start),
+            }))
+            .collect();
+        let edges: Vec<serde_json::Value> = cfg.edges()
+            .iter()
+            .map(|edge| serde_json::json!({
+                "source": format!("0x{:x}", edge.from.as_u64()),
+                "target": format!("0x{:x}", edge.to.as_u64()),
+                "kind": edge.edge_type.label(),
+            }))
+            .collect();
+
+        let json = serde_json::json!({
+            "entry": format!("0x{:x}", cfg.entry().as_u64()),
+            "blocks": blocks,
+            "edges": edges,
+        });
+        format!("{}\n", serde_json::to_string_pretty(&json).unwrap_or_default())
+    }
+
+    fn get_block_color(&self, cfg: &ControlFlowGraph, block: &CfgBlock) -> &str {
+        if !self.options.colorize {
+            return "#ffffff";
+        }
+
+        if block.start == cfg.entry() {
+            "lightblue"
+        } else if cfg.is_exit_block(block.start) {
+            "lightpink"
+        } else {
+            "white"
+        }
+    }
+
     /// Write export to file
     pub fn export_to_file(&self, graph: &CallGraph, format: ExportFormat, path: &str) -> std::io::Result<()> {
         let content = self.export(graph, format);
@@ -453,18 +671,20 @@ impl GraphExporter {
         }
 
         match kind {
-            NodeKind::Function => "#lightblue",
-            NodeKind::Data => "#lightgreen",
-            NodeKind::External => "#lightyellow",
-            NodeKind::Unknown => "#lightgray",
-            NodeKind::String => "#lightsalmon",
-            NodeKind::Constant => "#lightcyan",
+            NodeKind::Function => "lightblue",
+            NodeKind::Data => "lightgreen",
+            NodeKind::External => "lightyellow",
+            NodeKind::Stub => "lightpink",
+            NodeKind::Unknown => "lightgray",
+            NodeKind::String => "lightsalmon",
+            NodeKind::Constant => "lightcyan",
         }
     }
 
     fn get_edge_style(&self, kind: EdgeKind) -> &str {
         match kind {
             EdgeKind::Call => "color=blue",
+            EdgeKind::IndirectCall => "color=blue, style=dashed",
             EdgeKind::Jump => "color=red, style=dashed",
             EdgeKind::Reference => "color=green, style=dotted",
             EdgeKind::Data => "color=purple, style=dotted",
@@ -481,12 +701,13 @@ impl GraphExporter {
             NodeKind::Unknown => 0,
             NodeKind::String => 4,
             NodeKind::Constant => 5,
+            NodeKind::Stub => 6,
         }
     }
 
     fn edge_kind_to_weight(&self, kind: EdgeKind) -> usize {
         match kind {
-            EdgeKind::Call => 3,
+            EdgeKind::Call | EdgeKind::IndirectCall => 3,
             EdgeKind::Jump => 2,
             EdgeKind::Reference => 1,
             EdgeKind::Data => 2,
@@ -502,6 +723,18 @@ impl Default for GraphExporter {
     }
 }
 
+/// Blocks in address order, so exports are stable between runs
+fn sorted_blocks(cfg: &ControlFlowGraph) -> Vec<&CfgBlock> {
+    let mut blocks: Vec<&CfgBlock> = cfg.blocks().collect();
+    blocks.sort_by_key(|block| block.start);
+    blocks
+}
+
+/// Helper to escape DOT string special characters
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
 /// Helper to escape XML special characters
 fn escape_xml(s: &str) -> String {
     s.replace('&', "&amp;")
@@ -628,6 +861,40 @@ impl SubgraphExtractor {
         subgraph
     }
 
+    /// Extract `root` and everything it calls within `depth` calls, with the
+    /// edges between them
+    pub fn extract_callees(graph: &CallGraph, root: Address, depth: usize) -> CallGraph {
+        let mut reached = HashSet::new();
+        reached.insert(root.as_u64());
+        let mut current_level = vec![root];
+
+        for _ in 0..depth {
+            let mut next_level = Vec::new();
+            for addr in &current_level {
+                for edge in graph.get_outgoing(*addr) {
+                    if reached.insert(edge.to().as_u64()) {
+                        next_level.push(edge.to());
+                    }
+                }
+            }
+            current_level = next_level;
+        }
+
+        let mut subgraph = CallGraph::new();
+        for addr in &reached {
+            if let Some(node) = graph.get_node(Address::new(*addr)) {
+                subgraph.add_node(node.clone());
+            }
+        }
+        for edge in graph.edges() {
+            if reached.contains(&edge.from().as_u64()) && reached.contains(&edge.to().as_u64()) {
+                subgraph.add_edge(edge.clone());
+            }
+        }
+
+        subgraph
+    }
+
     /// Extract strongly connected components
     pub fn extract_scc(graph: &CallGraph) -> Vec<CallGraph> {
         // Simplified - would need full Tarjan/Kosaraju implementation
@@ -726,6 +993,7 @@ impl fmt::Display for GraphStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::xref::GraphEdge;
 
     #[test]
     fn test_dot_export() {
@@ -742,6 +1010,77 @@ mod tests {
         assert!(dot.contains("helper"));
     }
 
+    #[test]
+    fn test_dot_export_scoped() {
+        let mut graph = CallGraph::new();
+        for (addr, name) in [(0x1000, "main"), (0x2000, "helper"), (0x3000, "leaf"), (0x1010, "indirect_1010")] {
+            graph.add_node(GraphNode::new(Address::new(addr), name.to_string(), NodeKind::Function));
+        }
+        graph.add_edge(GraphEdge::new(Address::new(0x1000), Address::new(0x2000), EdgeKind::Call));
+        graph.add_edge(GraphEdge::new(Address::new(0x2000), Address::new(0x3000), EdgeKind::Call));
+        graph.add_edge(GraphEdge::new(Address::new(0x1000), Address::new(0x1010), EdgeKind::IndirectCall));
+
+        let sub = SubgraphExtractor::extract_callees(&graph, Address::new(0x2000), 1);
+        assert_eq!(sub.len(), 2);
+
+        let exporter = GraphExporter::with_options(ExportOptions {
+            max_depth: Some(1),
+            include_indirect: false,
+            ..Default::default()
+        });
+        let dot = exporter.to_dot(&graph);
+
+        assert!(dot.contains("helper"));
+        assert!(!dot.contains("leaf"));
+        assert!(!dot.contains("indirect_1010"));
+        assert!(!dot.contains("#light"));
+    }
+
+    #[test]
+    fn test_cfg_export() {
+        use crate::analysis::disassembler::DisassembledInstruction;
+
+        let instr = |addr: u64, mnemonic: &str| DisassembledInstruction {
+            address: Address::new(addr),
+            bytes: vec![0; 4],
+            mnemonic: mnemonic.to_string(),
+            operands: String::new(),
+            op_str: String::new(),
+            raw: 0,
+            size: 4,
+        };
+        let block = |start: u64, mnemonics: &[&str]| CfgBlock {
+            start: Address::new(start),
+            end: Address::new(start + 4 * (mnemonics.len() as u64 - 1)),
+            instructions: mnemonics.iter().enumerate().map(|(i, m)| instr(start + 4 * i as u64, m)).collect(),
+            predecessors: Vec::new(),
+            successors: Vec::new(),
+        };
+
+        let mut cfg = ControlFlowGraph::new(Address::new(0x1000));
+        cfg.add_block(block(0x1000, &["CMP", "B.EQ"]));
+        cfg.add_block(block(0x1008, &["NOP"]));
+        cfg.add_block(block(0x100C, &["RET"]));
+        for (from, to, edge_type) in [
+            (0x1000, 0x100C, EdgeType::ConditionalTrue),
+            (0x1000, 0x1008, EdgeType::ConditionalFalse),
+            (0x1008, 0x100C, EdgeType::Flow),
+        ] {
+            cfg.add_typed_edge(from, to, edge_type);
+        }
+
+        let exporter = GraphExporter::new();
+        let dot = exporter.export_cfg(&cfg, ExportFormat::Dot);
+        assert!(dot.contains("digraph \"cfg_1000\""));
+        assert!(dot.contains("label=\"1000:\\lCMP\\lB.EQ\\l\""));
+        assert!(dot.contains("\"4096\" -> \"4108\" [label=\"taken\""));
+        assert!(dot.contains("\"4096\" -> \"4104\" [label=\"fallthrough\""));
+
+        let mermaid = exporter.export_cfg(&cfg, ExportFormat::Mermaid);
+        assert!(mermaid.contains("B1000 -->|taken| B100c"));
+        assert!(mermaid.contains("B1008 -->|fallthrough| B100c"));
+    }
+
     #[test]
     fn test_json_export() {
         let mut graph = CallGraph::new();
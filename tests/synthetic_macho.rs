@@ -0,0 +1,1423 @@
+// Finder regression tests against a synthetic arm64 Mach-O fixture.
+
+use roblox_offset_generator::analysis::{classify_function, resolve_thunks, ControlFlowGraph, CrossReferenceAnalyzer, Disassembler, FunctionKind};
+use roblox_offset_generator::analysis::cfg::{EdgeType, JumpTableKind};
+use roblox_offset_generator::analysis::disasm::{DecoderCoverage, DisassemblyCache, DisassemblyContext, DisassemblyEngine};
+use roblox_offset_generator::analysis::string::{StringAnalyzer, StringAnalyzerConfig, StringEncoding};
+use roblox_offset_generator::engine::{ChunkScan, PhaseScan, ScanChunk, ScanPhase, Task, TaskScheduler, TaskType};
+use roblox_offset_generator::finders::roblox::{data_model, finder_for_output, luau_load, rbx_crash, RobloxFinders, BUILTIN_FINDERS, FINDER_OUTPUTS};
+use roblox_offset_generator::finders::{AllFinders, CombinedResults, ConstantValue, Finder, FinderResult, StructureOffsetResult};
+use roblox_offset_generator::finders::constants::identity::find_identity_levels;
+use roblox_offset_generator::finders::lua_api::{print, LuaApiFinder};
+use roblox_offset_generator::finders::classes::{find_all_classes, ReflectionCache, ReflectionFinder};
+use roblox_offset_generator::finders::methods::find_all_methods;
+use roblox_offset_generator::finders::fflags::{find_flag_names, find_flag_names_in_chunks};
+use roblox_offset_generator::finders::structures::{ExtraSpaceFinder, GcStateFinder};
+use roblox_offset_generator::luau::{UpvalueAnalyzer, UpvalueState};
+use roblox_offset_generator::memory::fat;
+use roblox_offset_generator::memory::{Address, BinaryFormat, BinaryMemory, MemoryError, MemoryRange, MemoryReader, MemoryRegion, MemoryWriter, MmapMemory, Protection};
+use roblox_offset_generator::orchestration::{FinderDiagnostics, OutputFinalizer, UnmatchedReason};
+use roblox_offset_generator::output::{ClassOffset, FunctionOffset, OffsetOutput};
+use roblox_offset_generator::pattern::{Pattern, PatternScanner};
+use roblox_offset_generator::pattern::matcher::PatternMatcher;
+use roblox_offset_generator::symbol::{SymbolResolver, SymbolType};
+use roblox_offset_generator::structure::{Field, Offset, PathWalker, PrimitiveType, StructureError, StructureLayout, TypeInfo};
+use roblox_offset_generator::utils::testing::MachOFixture;
+use roblox_offset_generator::validation::{ImageValidator, IssueSeverity};
+use roblox_offset_generator::xref::{XRef, XRefAnalyzer, XRefKind, CallGraph, CallGraphBuilder, ChainAnalyzer, ChainLinkType, InterproceduralAnalyzer, StringXrefScanner};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+const STP_FP_LR: u32 = 0xA9017BFD; // stp x29, x30, [sp, #0x10]
+const LDR_X2_X1: u32 = 0xF9400022; // ldr x2, [x1]
+const BL_FWD: u32 = 0x94000010; // bl #0x40
+const BRK_1: u32 = 0xD4200020; // brk #1
+const RET: u32 = 0xD65F03C0;
+const BR_X16: u32 = 0xD61F0200; // br x16
+
+/// `bl target` as emitted at `at`
+fn bl(at: Address, target: Address) -> u32 {
+    let delta = (target.as_u64() as i64 - at.as_u64() as i64) / 4;
+    0x94000000 | (delta as u32 & 0x03FFFFFF)
+}
+
+/// A function that loads `string` into x0 right after its prologue
+fn add_string_user(fixture: &mut MachOFixture, string: &str, body: &[u32]) -> u64 {
+    let string_addr = fixture.add_cstring(string);
+    let start = fixture.add_code(&[STP_FP_LR]);
+    let adrp_at = fixture.next_code_address();
+    fixture.add_code(&MachOFixture::adrp_add(0, adrp_at, string_addr));
+    fixture.add_code(body);
+    start.as_u64()
+}
+
+#[test]
+fn fixture_parses_as_arm64_macho() {
+    let mut fixture = MachOFixture::new();
+    let hello = fixture.add_cstring("hello");
+    fixture.add_code(&[RET]);
+
+    let binary = fixture.build();
+    assert!(binary.is_arm64().unwrap());
+    assert_eq!(binary.get_base_address().as_u64(), MachOFixture::BASE_ADDRESS);
+    assert_eq!(binary.read_c_string(hello).unwrap(), "hello");
+
+    let regions = binary.get_regions().unwrap();
+    assert_eq!(regions.len(), 1);
+    assert!(regions[0].protection().can_execute());
+}
+
+#[test]
+fn regions_come_from_segment_load_commands() {
+    let mut fixture = MachOFixture::new();
+    fixture.add_code(&[RET]);
+    let hello = fixture.add_cstring("hello");
+    fixture.add_qwords(&[1, 2]);
+    let binary = fixture.build();
+
+    let regions: Vec<(String, u64, u64, Protection)> = binary.get_regions().unwrap().iter()
+        .map(|r| (r.name().to_string(), r.start().as_u64(), r.size(), r.protection()))
+        .collect();
+    assert_eq!(regions, vec![
+        ("__TEXT".to_string(), MachOFixture::BASE_ADDRESS, 0x8000, Protection::ReadExecute),
+        ("__DATA".to_string(), MachOFixture::DATA_ADDRESS, 0x4000, Protection::ReadWrite),
+    ]);
+
+    let sections = binary.section_regions().unwrap();
+    let names: Vec<&str> = sections.iter().map(|r| r.name()).collect();
+    assert_eq!(names, vec!["__TEXT,__text", "__TEXT,__cstring"]);
+    assert_eq!(sections[1].start(), hello);
+    assert_eq!(sections[1].size(), 6);
+}
+
+/// Fixture bytes with the header's file type and the `__TEXT` vmaddr replaced
+fn macho_bytes_as(fixture: &MachOFixture, filetype: u32, text_vmaddr: u64) -> Vec<u8> {
+    let mut bytes = fixture.to_bytes();
+    bytes[12..16].copy_from_slice(&filetype.to_le_bytes());
+    bytes[56..64].copy_from_slice(&text_vmaddr.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn dylibs_and_bundles_load_at_their_text_segment() {
+    let mut fixture = MachOFixture::new();
+    fixture.add_code(&[RET]);
+    let text_offset = MachOFixture::TEXT_ADDRESS - MachOFixture::BASE_ADDRESS;
+
+    for (filetype, vmaddr) in [(0x6, 0), (0x8, 0x1A0000000)] {
+        let binary = BinaryMemory::from_bytes(macho_bytes_as(&fixture, filetype, vmaddr), "lib".into()).unwrap();
+        assert_eq!(binary.get_base_address().as_u64(), vmaddr);
+
+        let regions = binary.get_regions().unwrap();
+        assert_eq!(regions[0].start().as_u64(), vmaddr);
+        assert!(regions[0].protection().can_execute());
+        assert_eq!(binary.read_u32(Address::new(vmaddr + text_offset)).unwrap(), RET);
+    }
+
+    // MH_OBJECT: relocatable, nothing is laid out to load
+    let err = BinaryMemory::from_bytes(macho_bytes_as(&fixture, 0x1, MachOFixture::BASE_ADDRESS), "obj".into()).err().unwrap();
+    assert!(err.to_string().contains("Unsupported Mach-O file type"), "{}", err);
+}
+
+/// A fat file holding `slices` at 0x1000-aligned offsets, header in the
+/// byte order `big_endian` asks for
+fn fat_bytes(slices: &[(u32, Vec<u8>)], big_endian: bool) -> Vec<u8> {
+    let word = |v: u32| if big_endian { v.to_be_bytes() } else { v.to_le_bytes() };
+    let mut header = vec![0xCA, 0xFE, 0xBA, 0xBE];
+    if !big_endian {
+        header.reverse();
+    }
+    header.extend(word(slices.len() as u32));
+
+    let mut body = Vec::new();
+    for (cpu_type, bytes) in slices {
+        let offset = 0x1000 * (1 + body.len() / 0x1000);
+        body.resize(offset - 0x1000, 0);
+        header.extend(word(*cpu_type));
+        header.extend(word(0));
+        header.extend(word(offset as u32));
+        header.extend(word(bytes.len() as u32));
+        header.extend(word(12));
+        body.extend(bytes);
+        body.resize(body.len().next_multiple_of(0x1000), 0);
+    }
+
+    header.resize(0x1000, 0);
+    header.extend(body);
+    header
+}
+
+#[test]
+fn fat_binaries_load_the_requested_slice() {
+    let mut fixture = MachOFixture::new();
+    fixture.add_code(&[RET]);
+    let text_offset = MachOFixture::TEXT_ADDRESS - MachOFixture::BASE_ADDRESS;
+    let x86_base = 0x200000000;
+    let slices = vec![
+        (fat::CPU_TYPE_X86_64, macho_bytes_as(&fixture, 0x2, x86_base)),
+        (fat::CPU_TYPE_ARM64, fixture.to_bytes()),
+    ];
+
+    for big_endian in [true, false] {
+        let bytes = fat_bytes(&slices, big_endian);
+        assert!(fat::is_fat(&bytes));
+        assert_eq!(fat::slices(&bytes).unwrap().len(), 2);
+
+        let binary = BinaryMemory::from_bytes(bytes.clone(), "universal".into()).unwrap();
+        assert_eq!(binary.get_base_address().as_u64(), MachOFixture::BASE_ADDRESS);
+        assert_eq!(binary.size(), fixture.to_bytes().len());
+        assert_eq!(binary.read_u32(Address::new(MachOFixture::TEXT_ADDRESS)).unwrap(), RET);
+
+        let x86 = BinaryMemory::from_bytes_arch(bytes, "universal".into(), fat::CPU_TYPE_X86_64).unwrap();
+        assert_eq!(x86.get_base_address().as_u64(), x86_base);
+        assert_eq!(x86.read_u32(Address::new(x86_base + text_offset)).unwrap(), RET);
+    }
+
+    let x86_only = fat_bytes(&slices[..1], true);
+    let err = BinaryMemory::from_bytes(x86_only, "intel".into()).err().unwrap();
+    assert!(err.to_string().contains("no arm64 slice (has: x86_64)"), "{}", err);
+}
+
+#[test]
+fn out_of_range_reads_report_the_mapped_range() {
+    let binary = MachOFixture::new().build();
+    let base = MachOFixture::BASE_ADDRESS;
+    let image_end = base + binary.get_segments().unwrap()[0].filesize;
+
+    assert_eq!(binary.read_bytes(Address::new(image_end - 4), 4).unwrap().len(), 4);
+
+    for (addr, len) in [(image_end - 4, 8), (image_end + 0x1000, 4), (base - 0x1000, 4), (base, usize::MAX)] {
+        match binary.read_bytes(Address::new(addr), len) {
+            Err(MemoryError::OutOfBoundsRead { valid_start, valid_end, .. }) => {
+                assert_eq!((valid_start, valid_end), (base, image_end), "0x{:x}+{}", addr, len);
+            }
+            other => panic!("0x{:x}+{}: expected OutOfBoundsRead, got {:?}", addr, len, other.map(|b| b.len())),
+        }
+    }
+}
+
+#[test]
+fn patches_land_in_the_file_copy_regardless_of_protection() {
+    let mut binary = MachOFixture::new().build();
+    let text = Address::new(MachOFixture::TEXT_ADDRESS);
+    let nop = [0x1F, 0x20, 0x03, 0xD5];
+
+    assert!(!binary.protection_at(text).unwrap().can_write());
+    binary.write_bytes(text, &nop).unwrap();
+    assert_eq!(binary.read_bytes(text, 4).unwrap(), nop);
+
+    let image_end = MachOFixture::BASE_ADDRESS + binary.get_segments().unwrap()[0].filesize;
+    assert!(matches!(
+        binary.write_bytes(Address::new(image_end - 2), &nop),
+        Err(MemoryError::OutOfBoundsRead { .. })
+    ));
+
+    let path = std::env::temp_dir().join(format!("patched-{}.bin", std::process::id()));
+    binary.save(&path).unwrap();
+    let saved = BinaryMemory::load(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(saved.read_bytes(text, 4).unwrap(), nop);
+}
+
+#[test]
+fn elf_image_exposes_regions_sections_and_finders() {
+    let mut fixture = MachOFixture::new();
+    let expected = add_string_user(&mut fixture, "compile error", &[LDR_X2_X1, BL_FWD, RET]);
+
+    let binary = fixture.build_elf();
+    assert_eq!(binary.format(), BinaryFormat::Elf);
+    assert_eq!(binary.format().platform(), "android");
+    assert!(binary.is_arm64().unwrap());
+    assert_eq!(binary.get_base_address().as_u64(), MachOFixture::BASE_ADDRESS);
+
+    let regions = binary.get_regions().unwrap();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].name(), "LOAD[0]");
+    assert!(regions[0].protection().can_execute());
+
+    let sections: Vec<String> = binary.get_sections().unwrap().into_iter().map(|s| s.sectname).collect();
+    assert_eq!(sections, vec![".text", ".rodata"]);
+    assert_eq!(binary.get_text_section().unwrap().len(), 6 * 4);
+    assert!(binary.get_cstring_section().unwrap().starts_with(b"compile error\0"));
+
+    let (start, end) = fixture.scan_range();
+    let result = luau_load::find_luau_load(Arc::new(binary), start, end)
+        .expect("LuauLoad should be found in the ELF image");
+    assert_eq!(result.address.as_u64(), expected);
+}
+
+#[test]
+fn luau_load_found_by_string_xref() {
+    let mut fixture = MachOFixture::new();
+    let expected = add_string_user(&mut fixture, "compile error", &[LDR_X2_X1, BL_FWD, RET]);
+
+    let (start, end) = fixture.scan_range();
+    let result = luau_load::find_luau_load(fixture.reader(), start, end)
+        .expect("LuauLoad should be found");
+
+    assert_eq!(result.address.as_u64(), expected);
+    assert_eq!(result.method, "string_xref");
+
+    let labels: Vec<&str> = result.breakdown.iter().map(|f| f.label.as_str()).collect();
+    assert_eq!(labels, vec!["string xref \"compile error\"", "prologue and body checks"]);
+    let total: f64 = result.breakdown.iter().map(|f| f.delta).sum();
+    assert!((total - result.confidence).abs() < 1e-9);
+}
+
+#[test]
+fn rbx_crash_found_by_string_xref() {
+    let mut fixture = MachOFixture::new();
+    fixture.add_code(&[RET]);
+    let expected = add_string_user(&mut fixture, "Assertion failed", &[BL_FWD, BRK_1]);
+
+    let (start, end) = fixture.scan_range();
+    let result = rbx_crash::find_rbx_crash(fixture.reader(), start, end)
+        .expect("rbx_crash should be found");
+
+    assert_eq!(result.address.as_u64(), expected);
+    assert_eq!(result.method, "string_xref");
+}
+
+#[test]
+fn identity_levels_recovered_from_identity_check() {
+    let mut fixture = MachOFixture::new();
+    fixture.add_code(&[RET]);
+    let routine = add_string_user(&mut fixture, "identity", &[
+        0xF9402408, // ldr x8, [x0, #0x48]
+        0xB9401909, // ldr w9, [x8, #0x18]
+        0x7100093F, // cmp w9, #2
+        0x54000041, // b.ne #8
+        0x7100213F, // cmp w9, #8
+        0x7100813F, // cmp w9, #0x20 (not a level)
+        RET,
+    ]);
+
+    let (start, end) = fixture.scan_range();
+    let levels = find_identity_levels(fixture.reader(), start, end);
+
+    let names: Vec<&str> = levels.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["IdentityLevel_Replicator", "IdentityLevel_RobloxScript"]);
+
+    // Full ldr/cmp/b.cond shape scores higher than a bare compare
+    assert_eq!(levels[0].address.as_u64(), routine + 5 * 4);
+    assert!(matches!(levels[0].value, ConstantValue::Integer(2)));
+    assert!(levels[0].confidence > levels[1].confidence);
+}
+
+#[test]
+fn single_output_resolved_by_its_own_finder() {
+    let mut fixture = MachOFixture::new();
+    let expected = add_string_user(&mut fixture, "compile error", &[LDR_X2_X1, BL_FWD, RET]);
+
+    let finder = finder_for_output("LuauLoad").expect("LuauLoad has a built-in finder");
+    assert_eq!(finder.name(), "luau_load");
+    assert_eq!(finder_for_output("DataModelPointer").unwrap().name(), "data_model");
+    assert!(finder_for_output("NoSuchOffset").is_none());
+
+    let (start, end) = fixture.scan_range();
+    let mut results = CombinedResults::new();
+    for result in finder.find(fixture.reader(), start, end) {
+        results.add_function(result);
+    }
+    results.add_structure_offset(StructureOffsetResult::new("lua_State".to_string(), "top".to_string(), 0x10));
+
+    assert_eq!(results.lookup("LuauLoad"), Some(expected));
+    assert_eq!(results.lookup("lua_State.top"), Some(0x10));
+    assert_eq!(results.lookup("NewThread"), None);
+}
+
+#[test]
+fn luau_load_not_found_without_evidence() {
+    let mut fixture = MachOFixture::new();
+    fixture.add_cstring("unrelated");
+    fixture.add_code(&[STP_FP_LR, RET]);
+
+    let (start, end) = fixture.scan_range();
+    assert!(luau_load::find_luau_load(fixture.reader(), start, end).is_none());
+}
+
+#[test]
+fn data_model_accessor_and_global_resolved() {
+    let mut fixture = MachOFixture::new();
+    let global = Address::new(MachOFixture::BASE_ADDRESS + 0x6010);
+    fixture.add_code(&[RET]);
+
+    // DataModel* GetDataModel() { return g_dataModel; }
+    let getter_at = fixture.next_code_address();
+    fixture.add_code(&MachOFixture::adrp_ldr(8, 0, getter_at, global));
+    fixture.add_code(&[RET]);
+
+    // The anchor references the "FakeDataModel" string and the global
+    let adrp_at = {
+        fixture.add_code(&[STP_FP_LR]);
+        fixture.next_code_address()
+    };
+    let name = fixture.add_cstring("FakeDataModel");
+    fixture.add_code(&MachOFixture::adrp_add(0, adrp_at, name));
+    let load_at = fixture.next_code_address();
+    fixture.add_code(&MachOFixture::adrp_ldr(9, 1, load_at, global));
+    fixture.add_code(&[RET]);
+
+    let (start, end) = fixture.scan_range();
+    let results = data_model::find_data_model(fixture.reader(), start, end);
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].name, "GetDataModel");
+    assert_eq!(results[0].address, getter_at);
+    assert_eq!(results[1].name, "DataModelPointer");
+    assert_eq!(results[1].address, global);
+    assert!((results[0].confidence - 0.75).abs() < 1e-9);
+}
+
+#[test]
+fn data_model_not_found_without_anchor() {
+    let mut fixture = MachOFixture::new();
+    let global = Address::new(MachOFixture::BASE_ADDRESS + 0x6010);
+    let getter_at = fixture.next_code_address();
+    fixture.add_code(&MachOFixture::adrp_ldr(8, 0, getter_at, global));
+    fixture.add_code(&[RET]);
+
+    let (start, end) = fixture.scan_range();
+    assert!(data_model::find_data_model(fixture.reader(), start, end).is_empty());
+}
+
+#[test]
+fn reachability_reports_min_depth_and_cycles() {
+    // root -> a -> b, root -> b directly, b -> root closes a cycle; the
+    // calls into b from a must not deepen it past 1
+    let mut fixture = MachOFixture::new();
+    let root = fixture.next_code_address();
+    let a = root + 12;
+    let b = a + 12;
+
+    fixture.add_code(&[bl(root, a), bl(root + 4, b), RET]);
+    fixture.add_code(&[bl(a, b), bl(a + 4, b), RET]);
+    fixture.add_code(&[bl(b, root), RET, RET]);
+
+    let reader: Arc<dyn MemoryReader> = Arc::new(fixture.build());
+    let mut analyzer = InterproceduralAnalyzer::new(reader);
+    let reach = analyzer.reachable_from(root, 8).unwrap();
+
+    assert_eq!(reach.depth_of(root), Some(0));
+    assert_eq!(reach.depth_of(a), Some(1));
+    assert_eq!(reach.depth_of(b), Some(1));
+    assert_eq!(reach.get(b).unwrap().context.call_sites, vec![root + 4]);
+    assert_eq!(reach.cycles, vec![(b, root)]);
+    assert!(!reach.truncated);
+
+    let capped = InterproceduralAnalyzer::new(Arc::new(fixture.build())).reachable_from(root, 0).unwrap();
+    assert_eq!(capped.functions.len(), 1);
+    assert!(capped.truncated);
+}
+
+#[test]
+fn string_chain_links_load_site_to_function() {
+    let mut fixture = MachOFixture::new();
+    let func = add_string_user(&mut fixture, "ScriptContext", &[BL_FWD, RET]);
+    fixture.add_cstring("ScriptContextService");
+
+    let binary = fixture.build();
+    let (start, end) = fixture.scan_range();
+    let reader: Arc<dyn MemoryReader> = Arc::new(binary);
+    let analyzer = ChainAnalyzer::new(reader, CallGraph::new());
+
+    let chains = analyzer.find_string_chains("ScriptContext", start, end);
+    assert_eq!(chains.len(), 1);
+
+    let chain = &chains[0];
+    assert_eq!(chain.start.as_u64(), MachOFixture::CSTRING_ADDRESS);
+    assert_eq!(chain.end.as_u64(), func);
+    assert_eq!(chain.get_types(), vec![ChainLinkType::DataRef, ChainLinkType::Enclosing]);
+    assert_eq!(chain.links[0].target.as_u64(), func + 4);
+
+    assert!(analyzer.find_string_chains("Context", start, end).is_empty());
+}
+
+#[test]
+fn string_chains_search_the_string_section_and_code_separately() {
+    let long = "ScriptContext".repeat(12);
+    let mut fixture = MachOFixture::new();
+    let func = add_string_user(&mut fixture, &long, &[RET]);
+
+    let (_, end) = fixture.scan_range();
+    let reader = fixture.reader();
+    let code: Vec<_> = reader.get_regions().unwrap().iter()
+        .filter(|r| r.is_executable())
+        .map(|r| r.start()..r.end())
+        .collect();
+    let strings = Address::new(MachOFixture::CSTRING_ADDRESS)..end;
+
+    let chains = ChainAnalyzer::new(reader, CallGraph::new()).find_string_chains_in(&long, strings, &code);
+    assert_eq!(chains.len(), 1);
+    assert_eq!(chains[0].start.as_u64(), MachOFixture::CSTRING_ADDRESS);
+    assert_eq!(chains[0].end.as_u64(), func);
+}
+
+#[test]
+fn string_analyzer_honours_encoding_and_min_length() {
+    let mut fixture = MachOFixture::new();
+    let utf8 = fixture.add_cstring("Café");
+    fixture.add_cstring("abc");
+    // "Workspace" as UTF-16LE, NUL terminated
+    let wide = fixture.add_code(&[0x006F0057, 0x006B0072, 0x00700073, 0x00630061, 0x00000065]);
+
+    let reader: Arc<dyn MemoryReader> = Arc::new(fixture.build());
+    let regions = reader.get_regions().unwrap();
+
+    let narrow = StringAnalyzer::with_config(reader.clone(), StringAnalyzerConfig::default().with_min_length(4))
+        .find_strings(&regions)
+        .unwrap();
+    let cafe = narrow.iter().find(|s| s.address == utf8).unwrap();
+    assert_eq!(cafe.content, "Café");
+    assert_eq!(cafe.encoding, StringEncoding::Utf8);
+    assert!(narrow.iter().all(|s| s.content != "abc" && s.content != "Workspace"));
+
+    let wide_only = StringAnalyzerConfig::default().with_min_length(6).with_encodings(false, true);
+    let strings = StringAnalyzer::with_config(reader.clone(), wide_only)
+        .find_strings(&regions)
+        .unwrap();
+    let workspace = strings.iter().find(|s| s.address == wide).unwrap();
+    assert_eq!(workspace.content, "Workspace");
+    assert_eq!(workspace.encoding, StringEncoding::Utf16Le);
+    assert!(strings.iter().all(|s| s.encoding == StringEncoding::Utf16Le));
+
+    // Zero clamps to one character, and relevance 0 keeps low-value strings
+    let everything = StringAnalyzerConfig::default().with_min_length(0).with_min_relevance(0.0).with_encodings(true, true);
+    let strings = StringAnalyzer::with_config(reader, everything)
+        .find_strings(&regions)
+        .unwrap();
+    assert!(strings.iter().any(|s| s.content == "abc"));
+    assert!(strings.iter().all(|s| s.length > 0));
+}
+
+#[test]
+fn string_xrefs_find_pair_and_literal_pool_loads() {
+    let mut fixture = MachOFixture::new();
+    fixture.add_cstring("no compile error");
+    let by_pair = add_string_user(&mut fixture, "compile error", &[RET]);
+    let string = Address::new(MachOFixture::CSTRING_ADDRESS + "no compile error".len() as u64 + 1);
+
+    // ldr x0, #8 then the pointer itself past the RET
+    let by_literal = fixture.add_code(&[STP_FP_LR, 0x58000040, RET]);
+    fixture.add_code(&[string.as_u64() as u32, (string.as_u64() >> 32) as u32]);
+
+    let reader = fixture.reader();
+    let cstrings = Address::new(MachOFixture::CSTRING_ADDRESS)..fixture.scan_range().1;
+    let disassembler = Arc::new(Disassembler::new(reader.clone()));
+
+    for section in [Some(cstrings), None] {
+        let mut analyzer = CrossReferenceAnalyzer::new(reader.clone(), disassembler.clone());
+        if let Some(section) = section {
+            analyzer = analyzer.with_string_section(section);
+        }
+
+        let refs = analyzer.xrefs_to_string("compile error").unwrap();
+        let found: Vec<(u64, u64, Address)> = refs.iter()
+            .map(|r| (r.from.as_u64(), r.function.as_u64(), r.string_addr))
+            .collect();
+        assert_eq!(found, vec![(by_pair + 4, by_pair, string), (by_literal.as_u64() + 4, by_literal.as_u64(), string)]);
+        assert_eq!(analyzer.get_string_refs_to(string).map(Vec::len), Some(2));
+        assert!(analyzer.xrefs_to_string("compile").unwrap().is_empty());
+    }
+}
+
+#[test]
+fn neon_instructions_decode_with_arrangements() {
+    let cases: &[(u32, &str, &str)] = &[
+        (0x4C407800, "LD1", "{V0.4S}, [X0]"),
+        (0x4C9FA020, "ST1", "{V0.16B, V1.16B}, [X1], #32"),
+        (0x0C827000, "ST1", "{V0.8B}, [X0], X2"),
+        (0x4E22D420, "FADD", "V0.4S, V1.4S, V2.4S"),
+        (0x6E62DC20, "FMUL", "V0.2D, V1.2D, V2.2D"),
+        (0x4EA28420, "ADD", "V0.4S, V1.4S, V2.4S"),
+        (0x4E040C20, "DUP", "V0.4S, W1"),
+        (0x4E0C0420, "DUP", "V0.4S, V1.S[1]"),
+        (0x4EA11C20, "MOV", "V0.16B, V1.16B"),
+        (0x0E0C3C20, "MOV", "W0, V1.S[1]"),
+        (0x4F07E7E0, "MOVI", "V0.16B, #0xff"),
+        (0x4F0167E1, "MOVI", "V1.4S, #0x3f, LSL #24"),
+        (0x6F07E7E0, "MOVI", "V0.2D, #0xffffffffffffffff"),
+        (0x4F03F600, "FMOV", "V0.4S, #1.0"),
+        (0x1E222820, "FADD", "S0, S1, S2"),
+        (0x3DC00420, "LDR", "Q0, [X1, #16]"),
+    ];
+
+    let mut fixture = MachOFixture::new();
+    let words: Vec<u32> = cases.iter().map(|(raw, _, _)| *raw).collect();
+    let start = fixture.add_code(&words);
+
+    let mut context = DisassemblyContext::new(Arc::new(fixture.build()));
+
+    for (i, (raw, mnemonic, operands)) in cases.iter().enumerate() {
+        let insn = context.disassemble_at(start + i as u64 * 4).unwrap();
+        assert_eq!((insn.mnemonic.as_str(), insn.operand_str.as_str()), (*mnemonic, *operands), "{:08x}", raw);
+    }
+}
+
+#[test]
+fn test_and_compare_branches_split_blocks() {
+    let mut fixture = MachOFixture::new();
+    let start = fixture.add_code(&[
+        0x36180081, // tbz w1, #3, +0x10
+        0x34000043, // cbz w3, +0x8
+        0xD503201F, // nop
+        0xD65F03C0, // ret
+        0xB747FFE2, // tbnz x2, #40, -0x4
+        0xD65F03C0, // ret
+    ]);
+    let reader: Arc<dyn MemoryReader> = Arc::new(fixture.build());
+
+    let mut context = DisassemblyContext::new(reader.clone());
+    let expected = [
+        (0u64, "TBZ", "W1, #3, ", 0x10u64),
+        (4, "CBZ", "W3, ", 0xC),
+        (0x10, "TBNZ", "X2, #40, ", 0xC),
+    ];
+
+    for (offset, mnemonic, operands, target) in expected {
+        let insn = context.disassemble_at(start + offset).unwrap();
+        assert_eq!(insn.mnemonic, mnemonic);
+        assert!(insn.operand_str.starts_with(operands), "{}", insn.operand_str);
+        assert_eq!(insn.get_branch_target(), Some(start + target));
+    }
+
+    let cfg = ControlFlowGraph::build(reader, start, 0x18).unwrap();
+    let entry = cfg.get_block(start).unwrap();
+    assert_eq!(entry.instruction_count(), 1);
+    assert_eq!(entry.successors, vec![start + 0x10, start + 4]);
+    assert_eq!(cfg.get_block(start + 4).unwrap().successors, vec![start + 0xC, start + 8]);
+    assert_eq!(cfg.block_count(), 6);
+}
+
+#[test]
+fn cfg_from_context_splits_blocks_and_types_edges() {
+    let mut fixture = MachOFixture::new();
+    let start = fixture.add_code(&[
+        0xB40000A0, // cbz x0, +0x14
+        BL_FWD,
+        0x36000061, // tbz w1, #0, +0xc
+        0xD503201F, // nop
+        0x14000002, // b +0x8
+        0xD503201F, // nop
+        RET,
+        0x17FFFFF9, // b -0x1c: tail call back to `start`
+    ]);
+    let mut context = DisassemblyContext::new(fixture.reader());
+
+    let cfg = ControlFlowGraph::from_context(&mut context, start).unwrap();
+    assert_eq!(cfg.block_count(), 6);
+    assert_eq!(cfg.get_block(start + 0xC).unwrap().instruction_count(), 2);
+
+    let edges = |at: u64| -> Vec<(u64, EdgeType)> {
+        cfg.edges_from(start + at).map(|e| (e.to.as_u64() - start.as_u64(), e.edge_type)).collect()
+    };
+    assert_eq!(edges(0), vec![(0x14, EdgeType::ConditionalTrue), (4, EdgeType::ConditionalFalse)]);
+    assert_eq!(edges(4), vec![(8, EdgeType::Return)]);
+    assert_eq!(edges(8), vec![(0x14, EdgeType::ConditionalTrue), (0xC, EdgeType::ConditionalFalse)]);
+    assert_eq!(edges(0xC), vec![(0x18, EdgeType::Jump)]);
+    assert_eq!(edges(0x14), vec![(0x18, EdgeType::Flow)]);
+    assert!(edges(0x18).is_empty());
+
+    assert_eq!(cfg.exit_blocks(), &[(start + 0x18).as_u64()]);
+    assert_eq!(cfg.predecessors(start + 0x14), vec![start, start + 8]);
+    assert_eq!(cfg.predecessors(start + 0x18), vec![start + 0xC, start + 0x14]);
+
+    let tail = ControlFlowGraph::from_context(&mut context, start + 0x1C).unwrap();
+    assert_eq!((tail.block_count(), tail.edge_count()), (1, 0));
+    assert!(tail.is_exit_block(start + 0x1C));
+}
+
+#[test]
+fn unknown_encodings_are_counted_without_ending_the_function() {
+    let mut fixture = MachOFixture::new();
+    let start = fixture.add_code(&[
+        0x94000010, // bl
+        0x04A00000, // SVE, which the decoder lacks
+        0xF9400020, // ldr x0, [x1]
+        0x00000000, // udf #0
+        0xD65F03C0, // ret
+    ]);
+    let end = fixture.next_code_address();
+    let reader: Arc<dyn MemoryReader> = Arc::new(fixture.build());
+
+    let mut context = DisassemblyContext::new(reader.clone());
+    let listing = context.disassemble_function_listing(start).unwrap();
+    assert_eq!((listing.end, listing.ended_on_return), (end, true));
+    assert_eq!(listing.unknown_count(), 2);
+
+    let coverage = DisassemblyEngine::new(reader).coverage(start..end);
+    assert_eq!(coverage, DecoderCoverage { decoded: 3, unknown: 1, padding: 1 });
+    assert_eq!(context.coverage(), coverage);
+    assert_eq!(coverage.unknown_rate(), 0.25);
+    assert!(coverage.is_low());
+}
+
+#[test]
+fn content_dedup_decodes_each_distinct_word_once() {
+    let mut fixture = MachOFixture::new();
+    let body = [STP_FP_LR, LDR_X2_X1, BL_FWD, 0x54000108, RET]; // b.hi +0x20
+    let start = fixture.next_code_address();
+    for _ in 0..200 {
+        fixture.add_code(&body);
+    }
+    let end = fixture.next_code_address();
+    let reader: Arc<dyn MemoryReader> = Arc::new(fixture.build());
+
+    let mut plain = DisassemblyContext::new(reader.clone());
+    let mut dedup = DisassemblyContext::new(reader.clone()).with_cache(DisassemblyCache::with_content_dedup(10000));
+    let expected = plain.disassemble_range(start, end).unwrap();
+    let listing = dedup.disassemble_range(start, end).unwrap();
+
+    let stats = dedup.cache().stats();
+    let decoded = stats.misses - stats.content_hits;
+    assert_eq!(decoded, body.len() as u64);
+    assert_eq!(plain.cache().stats().misses, expected.len() as u64);
+    assert_eq!(stats.templates, Some(body.len()));
+
+    let engine = DisassemblyEngine::new(reader);
+    for (instr, plain) in listing.iter().zip(&expected) {
+        let fresh = engine.decode(instr.address).unwrap();
+        assert_eq!((instr.address, &instr.mnemonic, &instr.operand_str), (fresh.address, &fresh.mnemonic, &fresh.operand_str));
+        assert_eq!(instr.operand_str, plain.operand_str);
+    }
+    assert_eq!(listing.len(), expected.len());
+    assert_eq!(dedup.coverage(), plain.coverage());
+
+    let again = dedup.disassemble_at(start + 4 * body.len() as u64 + 8).unwrap();
+    assert_eq!(again.operand_str, engine.decode(again.address).unwrap().operand_str);
+    assert_eq!(dedup.cache().stats().hits, 1);
+}
+
+#[test]
+fn switch_jump_table_targets_become_successors() {
+    let mut fixture = MachOFixture::new();
+    let table = fixture.next_data_address();
+    let start = fixture.add_code(&[
+        0x7100081F, // cmp w0, #2
+        0x54000108, // b.hi +0x20 (default)
+    ]);
+    let adrp_at = fixture.next_code_address();
+    fixture.add_code(&MachOFixture::adrp_add(9, adrp_at, table));
+    let branch = fixture.add_code(&[
+        0xB8A0792A, // ldrsw x10, [x9, x0, lsl #2]
+        0x8B0A012A, // add x10, x9, x10
+        0xD61F0140, // br x10
+    ]) + 8;
+    let cases = [branch + 4, branch + 8, branch + 4];
+    fixture.add_code(&[RET, RET, RET]);
+
+    let offsets: Vec<u8> = cases.iter()
+        .flat_map(|case| ((case.as_u64() as i64 - table.as_u64() as i64) as i32).to_le_bytes())
+        .collect();
+    fixture.add_data(&offsets);
+    let reader: Arc<dyn MemoryReader> = Arc::new(fixture.build());
+
+    let cfg = ControlFlowGraph::build(reader, start, 0x24).unwrap();
+    let tables = cfg.jump_tables();
+    assert_eq!(tables.len(), 1);
+    assert_eq!((tables[0].branch, tables[0].table, tables[0].kind), (branch, table, JumpTableKind::Relative));
+    assert_eq!(tables[0].targets, cases.to_vec());
+
+    let switch = cfg.get_block(start + 8).unwrap();
+    assert_eq!(switch.successors, vec![branch + 4, branch + 8]);
+    assert!(cfg.get_block(branch + 8).is_some());
+    assert_eq!(cfg.edges().iter().filter(|e| e.edge_type == EdgeType::Jump).count(), 2);
+}
+
+#[test]
+fn adrp_pairs_resolve_to_data_references() {
+    let mut fixture = MachOFixture::new();
+    let hello = fixture.add_cstring("Hello");
+    let global = fixture.add_qwords(&[0]);
+
+    // adrp x8, Hello; add x0, x8, Hello
+    let string_site = fixture.next_code_address();
+    let [adrp, add] = MachOFixture::adrp_add(8, string_site, hello);
+    fixture.add_code(&[adrp, add]);
+
+    // adrp x9, global; ldr x1, [x9, global]
+    let global_site = fixture.next_code_address();
+    fixture.add_code(&MachOFixture::adrp_ldr(9, 1, global_site, global));
+
+    // adrp x10, Hello; b +8; add x2, x10, Hello: the branch drops the page
+    let branched = fixture.next_code_address();
+    let [adrp, _] = MachOFixture::adrp_add(10, branched, hello);
+    let lo12 = ((hello.as_u64() & 0xFFF) as u32) << 10;
+    fixture.add_code(&[adrp, 0x14000002, 0x91000142 | lo12, RET]);
+
+    let mut analyzer = XRefAnalyzer::new(fixture.reader());
+    let code_end = fixture.next_code_address();
+    assert_eq!(analyzer.analyze_data_refs(Address::new(MachOFixture::TEXT_ADDRESS), code_end), 2);
+
+    let to_hello = analyzer.get_references_to(hello);
+    assert_eq!(to_hello, vec![XRef::new(string_site + 4, hello, XRefKind::Data)]);
+    let to_global = analyzer.get_references_to(global);
+    assert_eq!(to_global, vec![XRef::new(global_site + 4, global, XRefKind::Data)]);
+}
+
+#[test]
+fn cancelled_scan_stops_before_next_finder() {
+    let mut fixture = MachOFixture::new();
+    add_string_user(&mut fixture, "compile error", &[LDR_X2_X1, BL_FWD, RET]);
+
+    let (start, end) = fixture.scan_range();
+    let reader = fixture.reader();
+    let cancel = Arc::new(AtomicBool::new(false));
+
+    let finders = RobloxFinders::new(reader.clone()).with_cancellation(cancel.clone());
+    assert!(finders.find_all(start, end).iter().any(|r| r.name == "LuauLoad"));
+
+    cancel.store(true, Ordering::SeqCst);
+    assert!(finders.find_all(start, end).is_empty());
+
+    let chunk = ScanChunk { index: 0, start, end };
+    let symbols = Arc::new(SymbolResolver::new(reader.clone()));
+    let results = ChunkScan::new(reader, symbols, chunk).with_cancellation(cancel).run();
+    assert!(results.functions.is_empty());
+    assert!(results.structure_offsets.is_empty());
+}
+
+#[test]
+fn phases_on_workers_match_a_serial_scan() {
+    let mut fixture = MachOFixture::new();
+    add_string_user(&mut fixture, "compile error", &[LDR_X2_X1, BL_FWD, RET]);
+
+    let (start, end) = fixture.scan_range();
+    let reader = fixture.reader();
+    let symbols = Arc::new(SymbolResolver::new(reader.clone()));
+    let serial = ChunkScan::new(reader.clone(), symbols.clone(), ScanChunk { index: 0, start, end }).run();
+
+    let reflection = Arc::new(ReflectionCache::new());
+    let mut scheduler = TaskScheduler::new(3);
+    scheduler.start();
+    scheduler.submit_batch(ScanPhase::ALL.into_iter()
+        .map(|phase| PhaseScan::new(reader.clone(), symbols.clone(), phase, vec![(start, end)]).with_reflection(reflection.clone()))
+        .map(|scan| Task::new(TaskType::ScanPhase(scan)))
+        .collect());
+    let per_phase: Vec<CombinedResults> = scheduler.collect(ScanPhase::ALL.len())
+        .into_iter()
+        .filter_map(|result| result.into_combined())
+        .collect();
+    scheduler.stop();
+
+    assert_eq!(per_phase.len(), ScanPhase::ALL.len());
+    for phase in ScanPhase::ALL {
+        let found: usize = per_phase.iter().map(|results| phase.count(results)).sum();
+        assert_eq!(found, phase.count(&serial), "{} differ", phase.name());
+    }
+    assert!(per_phase.iter().flat_map(|results| &results.functions).any(|r| r.name == "LuauLoad"));
+}
+
+fn pointer_layout(name: &str, fields: &[(&str, u64)]) -> StructureLayout {
+    let mut layout = StructureLayout::new(name.to_string());
+    for (field, offset) in fields {
+        layout.add_field(Field::new(field.to_string(), Offset::new(*offset), TypeInfo::Primitive(PrimitiveType::Ptr)));
+    }
+    layout
+}
+
+#[test]
+fn path_walker_follows_pointer_fields() {
+    let mut fixture = MachOFixture::new();
+    // Three 16-byte objects: DataModel+0x8 -> Workspace, Workspace+0x0 -> Camera
+    let data_model = fixture.next_code_address();
+    let workspace = data_model + 16;
+    let camera = workspace + 16;
+    let split = |addr: Address| [addr.as_u64() as u32, (addr.as_u64() >> 32) as u32];
+    fixture.add_code(&[0, 0, split(workspace)[0], split(workspace)[1]]);
+    fixture.add_code(&[split(camera)[0], split(camera)[1], 0, 0]);
+    fixture.add_code(&[0, 0, 0, 0]);
+
+    let walker = PathWalker::new(fixture.reader())
+        .with_layout(pointer_layout("DataModel", &[("PlaceId", 0x0), ("Workspace", 0x8)]))
+        .with_layout(pointer_layout("Workspace", &[("Camera", 0x0), ("Gravity", 0x8)]));
+
+    let steps = walker.walk(data_model, "DataModel.Workspace.Camera").unwrap();
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].field_address, data_model + 8);
+    assert_eq!(steps[0].value, workspace);
+    assert_eq!(steps[1].structure, "Workspace");
+    assert_eq!(steps[1].value, camera);
+
+    match walker.walk(data_model, "DataModel.Players") {
+        Err(StructureError::FieldNotFound(message)) => assert!(message.contains("available: PlaceId, Workspace")),
+        other => panic!("expected FieldNotFound, got {:?}", other.map(|s| s.len())),
+    }
+
+    assert!(matches!(walker.walk(data_model, "DataModel.PlaceId.Camera"), Err(StructureError::NullPointer(_))));
+}
+
+/// Reports the first RET in range, standing in for a private finder.
+struct FirstRetFinder;
+
+impl Finder for FirstRetFinder {
+    fn name(&self) -> &str {
+        "first_ret"
+    }
+
+    fn find(&self, reader: Arc<dyn MemoryReader>, start: Address, end: Address) -> Vec<FinderResult> {
+        let bytes = reader.read_bytes(start, (end.as_u64() - start.as_u64()) as usize).unwrap_or_default();
+        bytes.chunks_exact(4)
+            .position(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]) == RET)
+            .map(|i| FinderResult::new("FirstRet".to_string(), start + i as u64 * 4, 0.8))
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Reports each of its addresses as a function
+struct FixedFinder(Vec<Address>);
+
+impl Finder for FixedFinder {
+    fn name(&self) -> &str {
+        "fixed"
+    }
+
+    fn find(&self, _reader: Arc<dyn MemoryReader>, _start: Address, _end: Address) -> Vec<FinderResult> {
+        self.0.iter()
+            .enumerate()
+            .map(|(i, &address)| FinderResult::new(format!("Fixed{}", i), address, 0.8))
+            .collect()
+    }
+}
+
+/// `b target` as emitted at `at`
+fn b(at: Address, target: Address) -> u32 {
+    bl(at, target) & !0x80000000
+}
+
+#[test]
+fn results_on_tail_call_thunks_move_to_the_body() {
+    let mut fixture = MachOFixture::new();
+    let body = fixture.add_code(&[STP_FP_LR, LDR_X2_X1, RET]);
+    let thunk = fixture.add_code(&[0xAA0003E1]); // mov x1, x0
+    let b_at = fixture.next_code_address();
+    fixture.add_code(&[b(b_at, body)]);
+    let chained = fixture.next_code_address();
+    fixture.add_code(&[b(chained, thunk)]);
+    let spin = fixture.next_code_address();
+    fixture.add_code(&[b(spin, spin)]);
+    fixture.add_symbol("_lua_gettop", thunk);
+    let reader = fixture.reader();
+
+    assert_eq!(classify_function(reader.as_ref(), body), (FunctionKind::Function, None));
+    assert_eq!(classify_function(reader.as_ref(), thunk), (FunctionKind::Thunk, Some(body)));
+    assert_eq!(classify_function(reader.as_ref(), spin).0, FunctionKind::Function);
+    assert_eq!(resolve_thunks(reader.as_ref(), chained), body);
+
+    let (start, end) = fixture.scan_range();
+    let results = RobloxFinders::new(reader.clone())
+        .with_finders(vec![Box::new(FixedFinder(vec![thunk, chained, spin]))])
+        .find_all(start, end);
+    let fixed: Vec<(Address, FunctionKind)> = results.iter()
+        .filter(|r| r.name.starts_with("Fixed"))
+        .map(|r| (r.address, r.kind))
+        .collect();
+    assert_eq!(fixed, vec![(body, FunctionKind::Function), (body, FunctionKind::Function), (spin, FunctionKind::Function)]);
+
+    let symbols = Arc::new(SymbolResolver::new(reader.clone()));
+    let gettop = LuaApiFinder::new(reader.clone()).with_symbols(symbols).find_all(start, end).into_iter()
+        .find(|r| r.name == "lua_gettop")
+        .unwrap();
+    assert_eq!((gettop.address, gettop.kind), (body, FunctionKind::Function));
+
+    let mut combined = CombinedResults::new();
+    combined.add_function(FinderResult::new("Thunk".to_string(), thunk, 0.8));
+    combined.functions[0].kind = FunctionKind::Thunk;
+    let output = OffsetOutput::from_results("t", &combined, MachOFixture::BASE_ADDRESS);
+    assert_eq!(output.functions["Thunk"].kind, FunctionKind::Thunk);
+    assert_eq!(combined.to_json_map()["functions"]["Thunk"]["kind"], "thunk");
+    assert_eq!(OffsetOutput::from_scan_json("t", &output.to_scan_json()).functions["Thunk"].kind, FunctionKind::Thunk);
+}
+
+#[test]
+fn external_finders_run_alongside_builtins() {
+    let builtin_names: Vec<&str> = BUILTIN_FINDERS.iter().map(|f| f.name()).collect();
+    let report_names: Vec<&str> = FINDER_OUTPUTS.iter().map(|(name, _)| *name).collect();
+    assert_eq!(builtin_names, report_names);
+
+    let mut fixture = MachOFixture::new();
+    let entry = add_string_user(&mut fixture, "compile error", &[LDR_X2_X1, BL_FWD, RET]);
+
+    let (_, end) = fixture.scan_range();
+    let start = Address::new(entry);
+    let results = AllFinders::new(fixture.reader())
+        .with_finders(vec![Box::new(FirstRetFinder)])
+        .find_all(start, end);
+
+    let names: Vec<&str> = results.functions.iter().map(|f| f.name.as_str()).collect();
+    assert!(names.contains(&"LuauLoad"));
+    assert_eq!(names.last(), Some(&"FirstRet"));
+}
+
+#[test]
+fn gc_state_offsets_from_collector_access_patterns() {
+    let mut fixture = MachOFixture::new();
+    // luaC_fullgc shape: gcstate <= GCSatomic clears gray/grayagain/weak,
+    // then the string table shrink check (nuse < size / 4)
+    fixture.add_code(&[
+        0xA9BF7BFD, 0x39402408, 0x71000D1F, 0x54000088, 0xA904FC1F, 0xF9002C1F, 0x52800089,
+        0x39002409, 0xB940380A, 0xB9403C0B, 0x13027D6C, 0x6B0C015F, 0xA8C17BFD, 0xD65F03C0,
+    ]);
+    // luaC_step shape: totalbytes vs GCthreshold, then gcstate == GCSpause
+    fixture.add_code(&[
+        0xA9BF7BFD, 0xF9404008, 0xF9404409, 0xEB09011F, 0x54000063, 0x3940240A, 0x3400002A,
+        0xA8C17BFD, 0xD65F03C0,
+    ]);
+
+    let (start, end) = fixture.scan_range();
+    let results = GcStateFinder::new(fixture.reader()).find_all(start, end);
+    let field = |name: &str| results.iter().find(|r| r.field_name == name).unwrap();
+
+    assert_eq!(field("gcstate").offset, 0x9);
+    assert_eq!(field("gcstate").size, Some(1));
+    assert_eq!(field("gray").offset, 0x48);
+    assert_eq!(field("grayagain").offset, 0x50);
+    assert_eq!(field("weak").offset, 0x58);
+    assert_eq!(field("strt").offset, 0x30);
+    assert_eq!(field("strt").size, Some(16));
+    assert_eq!(field("totalbytes").offset, 0x80);
+    assert_eq!(field("GCthreshold").offset, 0x88);
+    assert!(results.iter().all(|r| r.structure_name == "global_State"));
+
+    // Both routines agree on gcstate; only one touches the gray lists
+    assert!(field("gcstate").confidence > field("gray").confidence);
+}
+
+/// `lua_newstate` shape: `frealloc(ud, NULL, 0, 0x5c8)` for the state, then
+/// the extraspace allocated with `size_insn` and stored at `L->userdata`
+fn add_new_state(fixture: &mut MachOFixture, size_insn: u32) -> (Address, Address) {
+    let alloc = fixture.add_code(&[RET]);
+    let start = fixture.add_code(&[
+        0xA9BE7BFD, // stp x29, x30, [sp, #-0x20]!
+        0xA9014FF3, // stp x19, x20, [sp, #0x10]
+        0xAA0003E8, // mov x8, x0
+        0xAA0103E0, // mov x0, x1
+        0xAA1F03E1, // mov x1, xzr
+        0xAA1F03E2, // mov x2, xzr
+        0x5280B903, // mov w3, #0x5c8
+    ]);
+    let call = fixture.add_code(&[
+        0xD63F0100, // blr x8
+        0xAA0003F3, // mov x19, x0
+        size_insn,
+    ]);
+    let bl_at = fixture.next_code_address();
+    fixture.add_code(&[
+        bl(bl_at, alloc),
+        0xF9003E60, // str x0, [x19, #0x78]
+        0xAA1303E0, // mov x0, x19
+        0xA9414FF3, // ldp x19, x20, [sp, #0x10]
+        0xA8C27BFD, // ldp x29, x30, [sp], #0x20
+        RET,
+    ]);
+    (start, call)
+}
+
+#[test]
+fn extraspace_size_folded_from_the_new_state_allocation() {
+    let mut fixture = MachOFixture::new();
+    let (start, call) = add_new_state(&mut fixture, 0x52801300); // mov w0, #0x98
+
+    let (scan_start, end) = fixture.scan_range();
+    let finder = ExtraSpaceFinder::new(fixture.reader());
+    let creation = finder.find_state_creation(scan_start, end).unwrap();
+    assert_eq!((creation.function, creation.alloc_call), (start, call));
+    assert_eq!(creation.state_size, Some(0x5C8));
+    assert_eq!((creation.userdata_offset, creation.extraspace_size), (0x78, Some(0x98)));
+
+    let results = finder.find_all(scan_start, end);
+    let userdata = results.iter().find(|r| r.structure_name == "lua_State" && r.field_name == "userdata").unwrap();
+    assert_eq!((userdata.offset, userdata.size), (0x78, Some(0x98)));
+    assert!(userdata.confidence >= 0.9);
+}
+
+#[test]
+fn extraspace_size_loaded_at_runtime_is_low_confidence() {
+    let mut fixture = MachOFixture::new();
+    add_new_state(&mut fixture, 0xB9400A80); // ldr w0, [x20, #8]
+
+    let (start, end) = fixture.scan_range();
+    let results = ExtraSpaceFinder::new(fixture.reader()).find_all(start, end);
+    let userdata = results.iter().find(|r| r.field_name == "userdata").unwrap();
+    assert_eq!((userdata.offset, userdata.size), (0x78, None));
+    assert!(userdata.confidence < 0.5);
+}
+
+
+/// A `ClassDescriptor`: vtable, name, parent, properties list
+fn add_class_descriptor(fixture: &mut MachOFixture, vtable: Address, name: &str, parent: u64, properties: u64) -> u64 {
+    let name = fixture.add_cstring(name);
+    fixture.add_qwords(&[vtable.as_u64(), name.as_u64(), parent, 0, properties, 0, 0, 0]).as_u64()
+}
+
+/// A null-terminated list of `PropertyDescriptor`s: name, getter, setter
+fn add_property_list(fixture: &mut MachOFixture, properties: &[(&str, u64, u64)]) -> u64 {
+    let mut descriptors = Vec::new();
+    for &(name, getter, setter) in properties {
+        let name = fixture.add_cstring(name);
+        descriptors.push(fixture.add_qwords(&[name.as_u64(), 0, getter, setter, 0, 0, 0, 0]).as_u64());
+    }
+    descriptors.push(0);
+    fixture.add_qwords(&descriptors).as_u64()
+}
+
+#[test]
+fn reflection_table_yields_classes_and_property_offsets() {
+    let mut fixture = MachOFixture::new();
+    let vtable = fixture.add_code(&[RET]);
+    let get_parent = fixture.add_code(&[0xF9403000, RET]).as_u64(); // ldr x0, [x0, #0x60]
+    let get_name = fixture.add_code(&[0x91012000, RET]).as_u64(); // add x0, x0, #0x48
+    let get_size = fixture.add_code(&[0x3DC06800, 0x3D800100, RET]).as_u64(); // ldr q0, [x0, #0x1a0]; str q0, [x8]
+    let set_size = fixture.add_code(&[STP_FP_LR, BL_FWD, RET]).as_u64();
+
+    let instance_props = add_property_list(&mut fixture, &[("Name", get_name, 0), ("Parent", get_parent, 0)]);
+    let part_props = add_property_list(&mut fixture, &[("Size", get_size, set_size)]);
+
+    let instance = add_class_descriptor(&mut fixture, vtable, "Instance", 0, instance_props);
+    let pv_instance = add_class_descriptor(&mut fixture, vtable, "PVInstance", instance, 0);
+    let base_part = add_class_descriptor(&mut fixture, vtable, "BasePart", pv_instance, 0);
+    let part = add_class_descriptor(&mut fixture, vtable, "Part", base_part, part_props);
+    fixture.add_qwords(&[0, instance, pv_instance, base_part, part, 0]);
+
+    let (start, end) = fixture.scan_range();
+    let finder = ReflectionFinder::new(fixture.reader());
+
+    let classes = finder.find_all(start, end);
+    let names: Vec<&str> = classes.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["Instance", "PVInstance", "BasePart", "Part"]);
+    assert_eq!(classes[3].address.as_u64(), part);
+    assert_eq!(classes[3].parent_class.as_deref(), Some("BasePart"));
+    assert_eq!(classes[0].parent_class, None);
+    assert_eq!(classes[0].vtable_address, Some(vtable));
+
+    let properties = finder.find_properties(start, end);
+    let property = |class: &str, name: &str| properties.iter()
+        .find(|p| p.class_name == class && p.property_name == name)
+        .unwrap();
+    assert_eq!(properties.len(), 3);
+    assert_eq!(property("Instance", "Name").offset, Some(0x48));
+    assert_eq!(property("Instance", "Parent").offset, Some(0x60));
+    assert_eq!(property("Part", "Size").offset, Some(0x1A0));
+    assert_eq!(property("Part", "Size").setter_address.map(|a| a.as_u64()), Some(set_size));
+
+    // The class and property passes share one walk through the cache
+    let cache = ReflectionCache::new();
+    let walked = cache.walk(&finder, start, end);
+    assert!(Arc::ptr_eq(&walked, &cache.walk(&finder, start, end)));
+    assert_eq!(finder.class_results(&walked).len(), classes.len());
+    assert_eq!(finder.property_results(&walked).len(), properties.len());
+}
+
+#[test]
+fn reflection_without_a_class_table_reports_nothing() {
+    let mut fixture = MachOFixture::new();
+    let vtable = fixture.add_code(&[RET]);
+    // A lone descriptor that no table points at
+    add_class_descriptor(&mut fixture, vtable, "Instance", 0, 0);
+
+    let (start, end) = fixture.scan_range();
+    let finder = ReflectionFinder::new(fixture.reader());
+    assert!(finder.find_all(start, end).is_empty());
+    assert!(finder.find_properties(start, end).is_empty());
+}
+
+#[test]
+fn image_validator_finds_the_load_base_and_flags_changed_code() {
+    let mut fixture = MachOFixture::new();
+    let function = fixture.add_code(&[0xA9BF7BFD, LDR_X2_X1, RET]); // stp x29, x30, [sp, #-0x10]!
+    let vtable = fixture.add_qwords(&[function.as_u64(), 0]);
+
+    // Scanned from a copy of the image that was loaded 0x10000000 higher
+    let origin = MachOFixture::BASE_ADDRESS + 0x10000000;
+    let shift = |addr: Address| addr.as_u64() + 0x10000000;
+    let mut output = OffsetOutput::new("fixture");
+    output.set_base_address(origin);
+    output.add_function("Function", FunctionOffset::new(shift(function), 0.9, "test"));
+    let mut class = ClassOffset::new("Instance");
+    class.vtable_address = Some(shift(vtable));
+    output.add_class(class);
+
+    let validator = ImageValidator::new(fixture.reader());
+    let base = validator.load_bases(&output, origin)[0];
+    assert_eq!(base.address, MachOFixture::BASE_ADDRESS);
+    assert_eq!((base.prologues, base.sampled), (1, 1));
+    assert!(base.executable_header);
+    assert_eq!(validator.expected_base(), Some(MachOFixture::BASE_ADDRESS));
+
+    let report = validator.check(&output, origin, base.address);
+    assert_eq!(report.worst_severity("Function"), None);
+    assert_eq!(report.worst_severity("Instance"), None);
+    let reasons: Vec<&str> = report.issues_for("Function").map(|i| i.message.as_str()).collect();
+    assert_eq!(reasons, vec!["Function prologue detected"]);
+
+    // A different build has other bytes at the same offset
+    let mut rebuilt = MachOFixture::new();
+    rebuilt.add_code(&[0xA9BF7BFD, BL_FWD, RET]);
+    let validator = ImageValidator::new(fixture.reader())
+        .with_reference(rebuilt.reader(), MachOFixture::BASE_ADDRESS);
+    let report = validator.check(&output, origin, base.address);
+    assert_eq!(report.worst_severity("Function"), Some(IssueSeverity::Error));
+    assert!(!report.is_valid());
+
+    // An offset past the end of the image is out of range, not unreadable
+    output.add_function("Stale", FunctionOffset::new(origin + 0x4000_0000, 0.9, "test"));
+    let report = ImageValidator::new(fixture.reader()).check(&output, origin, base.address);
+    let stale: Vec<&str> = report.issues_for("Stale").map(|i| i.message.as_str()).collect();
+    assert_eq!(stale, vec!["out of range"]);
+    assert_eq!(report.worst_severity("Stale"), Some(IssueSeverity::Error));
+
+    // A function offset that landed on data is mapped but not code
+    output.add_function("Data", FunctionOffset::new(shift(vtable), 0.9, "test"));
+    let report = ImageValidator::new(fixture.reader()).check(&output, origin, base.address);
+    let data: Vec<&str> = report.issues_for("Data").map(|i| i.message.as_str()).collect();
+    assert_eq!(data, vec!["not in an executable region"]);
+
+    // Code that doesn't open with a prologue is suspect, with the engine's reason
+    output.add_function("Body", FunctionOffset::new(shift(function) + 4, 0.9, "test"));
+    let report = ImageValidator::new(fixture.reader()).check(&output, origin, base.address);
+    assert_eq!(report.worst_severity("Body"), Some(IssueSeverity::Warning));
+    assert_eq!(report.issues_for("Body").next().unwrap().message, "No function prologue found");
+}
+
+#[test]
+fn fflag_names_straddling_a_chunk_boundary_are_found() {
+    let mut fixture = MachOFixture::new();
+    fixture.add_cstring("FFlagDebugGraphicsPreferVulkan");
+    fixture.add_cstring("DFIntTaskSchedulerTargetFps");
+    let binary = fixture.build();
+
+    let names = ["FFlagDebugGraphicsPreferVulkan", "DFIntTaskSchedulerTargetFps", "FFlagNotInBinary"];
+    let overlap = names.iter().map(|n| n.len()).max().unwrap();
+
+    // Windows far smaller than the file, so the names cross boundaries
+    let chunks: Vec<&[u8]> = binary.chunks(overlap + 7, overlap).collect();
+    assert!(chunks.len() > 1);
+    assert!(chunks.iter().all(|c| c.len() <= overlap + 7));
+    assert_eq!(chunks.last().unwrap().as_ptr_range().end, binary.data().as_ptr_range().end);
+
+    let found = find_flag_names_in_chunks(binary.chunks(overlap + 7, overlap), &names);
+    assert_eq!(found, find_flag_names(binary.data(), &names));
+    assert_eq!(found.len(), 2);
+    assert!(!found.contains("FFlagNotInBinary"));
+
+    // The mapped file yields the same windows without loading it
+    let path = std::env::temp_dir().join(format!("fflags-{}.bin", std::process::id()));
+    std::fs::write(&path, binary.data()).unwrap();
+    let mapped = MmapMemory::from_file(&path, Address::new(0)).unwrap();
+    assert!(mapped.chunks(overlap + 7, overlap).eq(binary.chunks(overlap + 7, overlap)));
+    assert_eq!(find_flag_names_in_chunks(mapped.chunks(overlap + 7, overlap), &names), found);
+    drop(mapped);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn string_xrefs_cover_the_string_section_in_both_formats() {
+    let mut fixture = MachOFixture::new();
+    let entry = add_string_user(&mut fixture, "compile error", &[LDR_X2_X1, BL_FWD, RET]);
+    add_string_user(&mut fixture, "rbxasset://", &[RET]);
+
+    for binary in [fixture.build(), fixture.build_elf()] {
+        let section = binary.cstring_section().expect("string section");
+        assert_eq!(section.addr, MachOFixture::CSTRING_ADDRESS);
+
+        let code = Address::new(MachOFixture::TEXT_ADDRESS)..Address::new(MachOFixture::CSTRING_ADDRESS);
+        let strings = Address::new(section.addr)..Address::new(section.addr + section.size);
+        let xrefs = StringXrefScanner::new(Arc::new(binary)).scan(code, strings);
+
+        let values: Vec<&str> = xrefs.iter().map(|x| x.value.as_str()).collect();
+        assert_eq!(values, vec!["compile error", "rbxasset://"]);
+        assert!(xrefs[0].code.as_u64() > entry);
+        assert!(xrefs.windows(2).all(|w| w[0].code < w[1].code));
+    }
+}
+
+#[test]
+fn unmatched_finders_explained_by_their_anchor_strings() {
+    let mut fixture = MachOFixture::new();
+    fixture.add_cstring("cannot resume dead coroutine");
+    fixture.add_code(&[RET]);
+
+    let mut found = CombinedResults::new();
+    found.functions.push(FinderResult::new("LuauLoad".to_string(), Address::new(MachOFixture::TEXT_ADDRESS), 0.9));
+    let report = OutputFinalizer::new().finder_report(&found, &found);
+
+    let unmatched = FinderDiagnostics::new(fixture.reader()).unmatched(&report).unwrap();
+    let reason = |finder: &str| unmatched.iter().find(|u| u.finder == finder).map(|u| u.reason);
+
+    assert_eq!(reason("luau_load"), None);
+    assert_eq!(reason("new_thread"), Some(UnmatchedReason::NoAnchors));
+    assert_eq!(reason("rbx_crash"), Some(UnmatchedReason::AnchorAbsent));
+
+    let sctx = unmatched.iter().find(|u| u.finder == "sctx_resume").unwrap();
+    assert_eq!(sctx.reason, UnmatchedReason::PatternChanged);
+    assert_eq!(sctx.present, vec!["cannot resume", "resume"]);
+    assert_eq!(sctx.missing, vec!["script context"]);
+}
+
+#[test]
+fn closure_upvalues_report_state_and_sharing() {
+    let mut fixture = MachOFixture::new();
+    let stack_slot = fixture.add_qwords(&[0, 0]);
+
+    // UpVal: header, v, closed-over storage, open list link
+    let closed_at = fixture.next_data_address();
+    let closed = fixture.add_qwords(&[0, closed_at.as_u64() + 0x10, 0, 0, 0]);
+    let open = fixture.add_qwords(&[0, stack_slot.as_u64(), 0, 0, 0]);
+
+    // Closure: header, env, nupvalues, upvalue pointers
+    let outer = fixture.add_qwords(&[0, 0, 2, closed.as_u64(), open.as_u64()]);
+    let inner = fixture.add_qwords(&[0, 0, 1, closed.as_u64()]);
+    let leaf = fixture.add_qwords(&[0, 0, 0]);
+
+    let analyzer = UpvalueAnalyzer::new(fixture.reader());
+    let upvalues = analyzer.analyze_closure_upvalues(outer).unwrap();
+    let states: Vec<(usize, UpvalueState, Address)> = upvalues.iter().map(|u| (u.index, u.state, u.value_location)).collect();
+    assert_eq!(states, vec![(0, UpvalueState::Closed, closed + 0x10), (1, UpvalueState::Open, stack_slot)]);
+
+    assert!(analyzer.analyze_closure_upvalues(leaf).unwrap().is_empty());
+
+    let refs = analyzer.track_upvalue_references(&[outer, inner, leaf]).unwrap();
+    assert_eq!(refs.shared_upvalues(), vec![closed]);
+    assert_eq!(refs.get_references(closed).unwrap(), &vec![(outer, 0), (inner, 0)]);
+    assert_eq!(refs.unique_upvalue_count(), 2);
+}
+
+#[test]
+fn print_and_warn_found_by_their_error_strings() {
+    let mut fixture = MachOFixture::new();
+    let print_fn = add_string_user(&mut fixture, print::PRINT_ANCHORS[0], &[BL_FWD, RET]);
+    let warn_fn = add_string_user(&mut fixture, print::WARN_ANCHORS[0], &[BL_FWD, RET]);
+    let (start, end) = fixture.scan_range();
+
+    let found = print::find_print(fixture.reader(), start, end).expect("print should be found");
+    assert_eq!((found.name.as_str(), found.address.as_u64()), ("print", print_fn));
+    assert!((found.confidence - 0.85).abs() < 1e-9);
+
+    // A second function loading the warn string makes the match ambiguous
+    add_string_user(&mut fixture, print::WARN_ANCHORS[0], &[RET]);
+    let found = print::find_warn(fixture.reader(), start, fixture.scan_range().1).expect("warn should be found");
+    assert_eq!(found.address.as_u64(), warn_fn);
+    assert!((found.confidence - 0.70).abs() < 1e-9);
+    assert_eq!(found.breakdown.len(), 2);
+
+    assert!(print::find_warn(MachOFixture::new().reader(), start, end).is_none());
+}
+
+#[test]
+fn calls_through_stubs_land_on_the_real_target() {
+    let mut fixture = MachOFixture::new();
+    let caller = fixture.add_code(&[STP_FP_LR, BL_FWD, RET]);
+    let real = fixture.add_code(&[STP_FP_LR, RET]);
+    let bound = fixture.add_qwords(&[real.as_u64()]);
+    let unbound = fixture.add_qwords(&[0]);
+
+    let stub_at = fixture.next_code_address();
+    let stub = fixture.add_code(&MachOFixture::adrp_ldr(16, 16, stub_at, bound));
+    fixture.add_code(&[BR_X16]);
+    let lazy_at = fixture.next_code_address();
+    let lazy = fixture.add_code(&MachOFixture::adrp_ldr(16, 16, lazy_at, unbound));
+    fixture.add_code(&[BR_X16]);
+
+    let graph = CallGraphBuilder::new()
+        .with_reader(fixture.reader())
+        .add_function(caller, Some("caller".to_string()))
+        .add_function(real, None)
+        .add_call(caller + 4, stub)
+        .add_call(caller + 4, lazy)
+        .add_call(caller + 4, real)
+        .build();
+
+    let targets: Vec<(Address, Option<Address>)> = graph.edges().map(|e| (e.to(), e.via())).collect();
+    assert_eq!(targets, vec![(real, Some(stub)), (lazy, None), (real, None)]);
+    assert!(graph.is_stub(stub));
+    assert!(!graph.is_stub(lazy));
+    assert_eq!(graph.get_incoming(real).len(), 2);
+
+    let filtered = graph.without_stubs();
+    assert_eq!((filtered.len(), filtered.edge_count()), (2, 3));
+}
+
+#[test]
+fn patterns_straddling_a_region_boundary_are_found() {
+    let mut fixture = MachOFixture::new();
+    let signature = [0xDE, 0xAD, 0xBE, 0xEF, 0x13, 0x37, 0xC0, 0xDE];
+    let at = fixture.add_data(&signature);
+    let reader = fixture.reader();
+
+    let split = at + 4;
+    let regions = vec![
+        MemoryRegion::new(MemoryRange::new(at - 0x40, split), Protection::ReadWrite, "before".to_string()),
+        MemoryRegion::new(MemoryRange::new(split, split + 0x40), Protection::ReadWrite, "after".to_string()),
+    ];
+    let pattern = Pattern::from_bytes(&signature);
+
+    for parallel in [false, true] {
+        let scanner = PatternScanner::new().use_parallel(parallel);
+        assert_eq!(scanner.scan(&*reader, &pattern, &regions), vec![at]);
+    }
+    assert_eq!(PatternScanner::new().scan_first(&*reader, &pattern, &regions), Some(at));
+    assert_eq!(PatternScanner::new().scan_multiple(&*reader, &[pattern.clone()], &regions), vec![(0, at)]);
+    assert!(PatternScanner::new().with_read_ahead(0).scan(&*reader, &pattern, &regions).is_empty());
+
+    let matcher = PatternMatcher::new(reader.clone());
+    assert_eq!(matcher.find_all_patterns(&pattern, &regions).unwrap(), vec![at]);
+    assert_eq!(matcher.find_pattern(&pattern, &regions).unwrap(), Some(at));
+}
+
+#[test]
+fn parallel_multi_pattern_scan_is_sorted_and_matches_sequential() {
+    let mut fixture = MachOFixture::new();
+    for _ in 0..64 {
+        fixture.add_code(&[STP_FP_LR, LDR_X2_X1, BL_FWD, RET]);
+    }
+    let (start, end) = fixture.scan_range();
+    let regions = vec![MemoryRegion::new(MemoryRange::new(start, end), Protection::ReadExecute, "__text".to_string())];
+    let patterns = [
+        Pattern::from_ida_string("C0 03 5F D6").unwrap(),
+        Pattern::from_ida_string("FD 7B ?? A9").unwrap(),
+    ];
+    let reader = fixture.reader();
+
+    let sequential = PatternScanner::new().use_parallel(false).with_chunk_size(0x40)
+        .scan_multiple(&*reader, &patterns, &regions);
+    let parallel = PatternScanner::new().with_threads(4).with_chunk_size(0x40)
+        .scan_multiple(&*reader, &patterns, &regions);
+
+    assert_eq!(sequential.len(), 128);
+    assert_eq!(parallel, sequential);
+    assert!(parallel.windows(2).all(|w| w[0].1 < w[1].1));
+    assert_eq!(parallel[0].0, 1);
+}
+
+#[test]
+fn symbol_table_hits_win_over_heuristics() {
+    let mut fixture = MachOFixture::new();
+    let gettop = fixture.add_code(&[STP_FP_LR, RET]);
+    let find_child = fixture.add_code(&[STP_FP_LR, LDR_X2_X1, RET]);
+    let vtable = fixture.add_qwords(&[0, 0, find_child.as_u64()]);
+    fixture.add_symbol("_lua_gettop", gettop);
+    fixture.add_symbol("__ZN3RBX8Instance14FindFirstChildEPKcb", find_child);
+    fixture.add_symbol("__ZTVN3RBX8InstanceE", vtable);
+    let reader = fixture.reader();
+
+    let symbols = Arc::new(SymbolResolver::new(reader.clone()));
+    assert_eq!(symbols.symbol_count(), 3);
+
+    let top = symbols.lookup("lua_gettop").unwrap();
+    assert_eq!((top.address, top.symbol_type, top.size), (gettop, SymbolType::Function, Some(8)));
+    assert_eq!(symbols.lookup("RBX::Instance::FindFirstChild").unwrap().address, find_child);
+    assert_eq!(symbols.resolve(find_child + 4).unwrap().display_name(), "RBX::Instance::FindFirstChild(char const*, bool)");
+    assert_eq!(symbols.lookup("vtable for RBX::Instance").unwrap().symbol_type, SymbolType::Data);
+
+    let (start, end) = (Address::new(MachOFixture::BASE_ADDRESS), Address::new(MachOFixture::LINKEDIT_ADDRESS));
+    let method = find_all_methods(reader.clone(), symbols.clone(), start, end).into_iter()
+        .find(|m| m.class_name == "Instance" && m.method_name == "FindFirstChild")
+        .unwrap();
+    assert_eq!((method.address, method.confidence), (find_child, 1.0));
+
+    let class = find_all_classes(reader, &symbols, &ReflectionCache::new(), start, end).into_iter()
+        .find(|c| c.name == "Instance")
+        .unwrap();
+    assert_eq!((class.address, class.vtable_address, class.confidence), (vtable, Some(vtable + 16), 1.0));
+}